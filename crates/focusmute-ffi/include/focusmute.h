@@ -0,0 +1,77 @@
+/*
+ * focusmute.h — C API for FocusMute (focusmute-ffi).
+ *
+ * Link against focusmute.dll / libfocusmute.so (or the static library).
+ *
+ * Functions returning int give 0 on success and -1 on failure; functions
+ * returning a pointer give NULL on failure. fm_last_error() then describes
+ * the failure for the calling thread.
+ */
+
+#ifndef FOCUSMUTE_H
+#define FOCUSMUTE_H
+
+#include <stdbool.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* An open device with its resolved mute indicator. */
+typedef struct FmDevice FmDevice;
+
+/* A running mute watch (see fm_watch_mute). */
+typedef struct FmWatch FmWatch;
+
+typedef struct FmStatus {
+    /* 1 muted, 0 live, -1 unknown (no audio backend). */
+    int mic_muted;
+    /* Whether the mute indicator is currently shown on the device. */
+    bool led_muted;
+} FmStatus;
+
+/* Called with the new mute state and the user_data given to fm_watch_mute. */
+typedef void (*FmMuteCallback)(bool muted, void *user_data);
+
+/* Library version, e.g. "0.4.0". Static; never freed. */
+const char *fm_version(void);
+
+/* Message for the last failure on this thread, or NULL. Valid until the
+ * next failing call on the same thread. */
+const char *fm_last_error(void);
+
+/* Open the device with the given serial (NULL or "" auto-selects), using
+ * the user's FocusMute config for mute color, inputs, and I/O timeouts. */
+FmDevice *fm_device_open(const char *serial);
+
+/* Clear the mute indicator if shown and close the device. NULL is ignored. */
+void fm_device_close(FmDevice *dev);
+
+/* Model name, e.g. "Scarlett 2i2 4th Gen". Valid until fm_device_close. */
+const char *fm_device_name(const FmDevice *dev);
+
+/* Show (muted = true) or clear the mute indicator on the device LEDs.
+ * The OS microphone state is left alone. */
+int fm_device_set_mute_led(const FmDevice *dev, bool muted);
+
+/* Whether the default microphone is muted: 1 muted, 0 live, -1 on failure.
+ * The audio backend is opened on the first call and kept for the process. */
+int fm_mic_muted(void);
+
+/* Fill *out with the microphone and indicator state. dev may be NULL. The
+ * audio backend is opened on the first call and kept with dev. */
+int fm_status(const FmDevice *dev, FmStatus *out);
+
+/* Call callback on a background thread whenever the default microphone's
+ * mute state changes, starting with the current state. */
+FmWatch *fm_watch_mute(FmMuteCallback callback, void *user_data);
+
+/* Stop a watch and wait for its thread; no callback runs after this
+ * returns. Don't call it from inside the callback. NULL is ignored. */
+void fm_unwatch(FmWatch *watch);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* FOCUSMUTE_H */
@@ -0,0 +1,591 @@
+//! C ABI over focusmute-lib — open the device, drive the mute indicator,
+//! query the microphone, and get called back on mute changes.
+//!
+//! Lets OBS plugins, AutoHotkey scripts, and other native tools integrate
+//! without shelling out to `focusmute-cli`. The header is
+//! `include/focusmute.h`; the library builds as `focusmute.dll` /
+//! `libfocusmute.so` (and a static library).
+//!
+//! Conventions: functions returning `int` give `0` on success and `-1` on
+//! failure, functions returning a pointer give NULL on failure. Either way
+//! [`fm_last_error`] then describes the failure for the calling thread. NULL
+//! handles are rejected, never dereferenced.
+
+use std::cell::RefCell;
+use std::ffi::{CStr, CString, c_char, c_int, c_void};
+use std::fmt::Display;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, mpsc};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use focusmute_lib::FocusmuteError;
+use focusmute_lib::audio::{self, MuteMonitor};
+use focusmute_lib::config::Config;
+use focusmute_lib::context::DeviceContext;
+use focusmute_lib::device::{ScarlettDevice, open_device_with_options};
+use focusmute_lib::device_worker::DeviceWorker;
+use focusmute_lib::led;
+use focusmute_lib::monitor::MuteIndicator;
+
+/// How long the watch thread waits for a mute event before re-checking.
+const WATCH_POLL: Duration = Duration::from_millis(250);
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_error(error: impl Display) {
+    let message = CString::new(error.to_string().replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|e| *e.borrow_mut() = Some(message));
+}
+
+/// Record `error` for [`fm_last_error`] and return `-1`.
+fn fail(error: impl Display) -> c_int {
+    set_error(error);
+    -1
+}
+
+/// Record `error` for [`fm_last_error`] and return NULL.
+fn fail_null<T>(error: impl Display) -> *mut T {
+    set_error(error);
+    ptr::null_mut()
+}
+
+/// Run an exported function's body, turning a panic into `on_panic` (and an
+/// [`fm_last_error`] message) instead of unwinding into the caller.
+fn guard<T>(on_panic: T, body: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|_| {
+        set_error("internal error (panic) in focusmute");
+        on_panic
+    })
+}
+
+/// The platform microphone backend (WASAPI / PulseAudio or PipeWire). On
+/// Windows the caller must hold COM for the thread (see [`Mic::spawn`]).
+fn platform_monitor() -> audio::Result<Box<dyn MuteMonitor>> {
+    #[cfg(windows)]
+    {
+        Ok(Box::new(audio::WasapiMonitor::new()?))
+    }
+    #[cfg(target_os = "linux")]
+    {
+        let monitor = audio::LinuxMonitor::open(audio::AudioBackend::Auto, &[])?;
+        monitor.stabilize();
+        Ok(Box::new(monitor))
+    }
+    #[cfg(not(any(windows, target_os = "linux")))]
+    {
+        Err(audio::AudioError::InitFailed(
+            "Audio mute monitoring is not supported on this platform.".into(),
+        ))
+    }
+}
+
+/// A microphone monitor living on its own thread, opened once and queried
+/// over a channel — so COM is initialized once on a thread we own, whatever
+/// apartment the host's calling threads are in.
+struct Mic {
+    requests: Option<mpsc::Sender<mpsc::SyncSender<bool>>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+/// Microphone for calls made without a device handle.
+static SHARED_MIC: Mutex<Option<Mic>> = Mutex::new(None);
+
+impl Mic {
+    /// Start the thread and open the monitor on it with `open`.
+    fn spawn<F>(open: F) -> audio::Result<Self>
+    where
+        F: FnOnce() -> audio::Result<Box<dyn MuteMonitor>> + Send + 'static,
+    {
+        let (requests, requests_rx) = mpsc::channel::<mpsc::SyncSender<bool>>();
+        let (ready, ready_rx) = mpsc::channel();
+        let thread = std::thread::Builder::new()
+            .name("focusmute-mic".into())
+            .spawn(move || {
+                #[cfg(windows)]
+                let _com = match audio::com_enter() {
+                    Ok(com) => com,
+                    Err(e) => {
+                        let _ = ready.send(Err(e));
+                        return;
+                    }
+                };
+                let monitor = match open() {
+                    Ok(m) => {
+                        let _ = ready.send(Ok(()));
+                        m
+                    }
+                    Err(e) => {
+                        let _ = ready.send(Err(e));
+                        return;
+                    }
+                };
+                for reply in requests_rx {
+                    monitor.refresh();
+                    let _ = reply.send(monitor.is_muted());
+                }
+            })
+            .map_err(|e| audio::AudioError::InitFailed(e.to_string()))?;
+        match ready_rx.recv() {
+            Ok(Ok(())) => Ok(Self {
+                requests: Some(requests),
+                thread: Some(thread),
+            }),
+            Ok(Err(e)) => {
+                let _ = thread.join();
+                Err(e)
+            }
+            Err(_) => Err(audio::AudioError::InitFailed(
+                "microphone thread stopped".into(),
+            )),
+        }
+    }
+
+    fn is_muted(&self) -> Result<bool, &'static str> {
+        let (reply, reply_rx) = mpsc::sync_channel(1);
+        self.requests
+            .as_ref()
+            .and_then(|requests| requests.send(reply).ok())
+            .and_then(|()| reply_rx.recv().ok())
+            .ok_or("microphone thread stopped")
+    }
+
+    /// Query the microphone in `slot`, opening it there on first use (and
+    /// again after a failed open).
+    fn query(slot: &Mutex<Option<Mic>>) -> Result<bool, String> {
+        let mut slot = slot.lock().unwrap_or_else(|e| e.into_inner());
+        if slot.is_none() {
+            *slot = Some(Mic::spawn(platform_monitor).map_err(|e| e.to_string())?);
+        }
+        slot.as_ref()
+            .map_or(Err("microphone unavailable"), Mic::is_muted)
+            .map_err(str::to_string)
+    }
+}
+
+impl Drop for Mic {
+    fn drop(&mut self) {
+        // Closing the channel ends the thread's loop
+        self.requests.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// An open device with its resolved mute indicator. Opaque to C.
+pub struct FmDevice {
+    device: DeviceWorker,
+    indicator: MuteIndicator,
+    led_muted: AtomicBool,
+    name: CString,
+    /// This handle's microphone, opened on first query.
+    mic: Mutex<Option<Mic>>,
+}
+
+impl FmDevice {
+    /// Resolve the model and mute strategy (from `config`) for `device`.
+    fn new<D: ScarlettDevice + Send + 'static>(
+        device: D,
+        config: &Config,
+    ) -> focusmute_lib::error::Result<Self> {
+        let ctx = DeviceContext::resolve(&device, false)?;
+        let mut config = config.clone();
+        let (_mode, strategy, _warnings) = led::resolve_strategy_from_config(
+            &mut config,
+            ctx.input_count(),
+            ctx.profile,
+            ctx.predicted.as_ref(),
+        )
+        .map_err(FocusmuteError::Config)?;
+        let mut indicator =
+            MuteIndicator::new(1, false, led::mute_color_or_default(&config), strategy);
+        indicator.set_external_mute_color(led::external_mute_color(&config));
+        let name = CString::new(device.info().model().replace('\0', " ")).unwrap_or_default();
+        Ok(Self {
+            device: DeviceWorker::spawn(device),
+            indicator,
+            led_muted: AtomicBool::new(false),
+            name,
+            mic: Mutex::new(None),
+        })
+    }
+
+    fn set_mute_led(&self, muted: bool) -> focusmute_lib::device::Result<()> {
+        if muted {
+            self.indicator.apply_mute(&self.device)?;
+        } else {
+            self.indicator.clear_mute(&self.device)?;
+        }
+        self.led_muted.store(muted, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+/// Snapshot filled by [`fm_status`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FmStatus {
+    /// `1` muted, `0` live, `-1` unknown (no audio backend).
+    pub mic_muted: c_int,
+    /// Whether the mute indicator is currently shown on the device.
+    pub led_muted: bool,
+}
+
+/// Called with the new mute state and the caller's `user_data`.
+pub type FmMuteCallback = extern "C" fn(muted: bool, user_data: *mut c_void);
+
+struct Callback {
+    f: FmMuteCallback,
+    user_data: *mut c_void,
+}
+
+// The caller promises `user_data` may be used from the watch thread
+// (see `fm_watch_mute`).
+unsafe impl Send for Callback {}
+
+impl Callback {
+    fn call(&self, muted: bool) {
+        (self.f)(muted, self.user_data);
+    }
+}
+
+/// A running mute watch. Opaque to C.
+pub struct FmWatch {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+/// Library version, e.g. `"0.4.0"`. Static; never freed.
+#[unsafe(no_mangle)]
+pub extern "C" fn fm_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// Message for the last failure on this thread, or NULL. Valid until the next
+/// failing call on the same thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn fm_last_error() -> *const c_char {
+    panic::catch_unwind(|| {
+        LAST_ERROR.with(|e| e.borrow().as_ref().map_or(ptr::null(), |m| m.as_ptr()))
+    })
+    .unwrap_or(ptr::null())
+}
+
+/// Open the device with the given serial (NULL or `""` auto-selects), using
+/// the user's FocusMute config for mute color, inputs, and I/O timeouts.
+/// Close it with [`fm_device_close`].
+///
+/// # Safety
+///
+/// `serial` must be NULL or a valid NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fm_device_open(serial: *const c_char) -> *mut FmDevice {
+    guard(ptr::null_mut(), || {
+        let serial = if serial.is_null() {
+            String::new()
+        } else {
+            match unsafe { CStr::from_ptr(serial) }.to_str() {
+                Ok(s) => s.to_string(),
+                Err(_) => return fail_null("serial is not valid UTF-8"),
+            }
+        };
+        let config = Config::load();
+        let opened = open_device_with_options(&serial, config.device_options())
+            .map_err(FocusmuteError::from)
+            .and_then(|device| FmDevice::new(device, &config));
+        match opened {
+            Ok(dev) => Box::into_raw(Box::new(dev)),
+            Err(e) => fail_null(e),
+        }
+    })
+}
+
+/// Clear the mute indicator if shown and close the device. NULL is ignored.
+///
+/// # Safety
+///
+/// `dev` must be NULL or a handle from [`fm_device_open`] that has not been
+/// closed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fm_device_close(dev: *mut FmDevice) {
+    guard((), || {
+        if dev.is_null() {
+            return;
+        }
+        let dev = unsafe { Box::from_raw(dev) };
+        if dev.led_muted.load(Ordering::Relaxed)
+            && let Err(e) = dev.set_mute_led(false)
+        {
+            set_error(e);
+        }
+    })
+}
+
+/// Model name of the open device (e.g. `"Scarlett 2i2 4th Gen"`). Owned by
+/// the handle; valid until [`fm_device_close`].
+///
+/// # Safety
+///
+/// `dev` must be NULL or a live handle from [`fm_device_open`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fm_device_name(dev: *const FmDevice) -> *const c_char {
+    guard(ptr::null(), || match unsafe { dev.as_ref() } {
+        Some(dev) => dev.name.as_ptr(),
+        None => {
+            set_error("device handle is NULL");
+            ptr::null()
+        }
+    })
+}
+
+/// Show (`muted = true`) or clear the mute indicator on the device LEDs.
+/// Only the LEDs change — the OS microphone state is left alone.
+///
+/// # Safety
+///
+/// `dev` must be NULL or a live handle from [`fm_device_open`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fm_device_set_mute_led(dev: *const FmDevice, muted: bool) -> c_int {
+    guard(-1, || {
+        let Some(dev) = (unsafe { dev.as_ref() }) else {
+            return fail("device handle is NULL");
+        };
+        match dev.set_mute_led(muted) {
+            Ok(()) => 0,
+            Err(e) => fail(e),
+        }
+    })
+}
+
+/// Whether the default microphone is muted: `1` muted, `0` live, `-1` on
+/// failure. The audio backend is opened on the first call and kept for the
+/// process.
+#[unsafe(no_mangle)]
+pub extern "C" fn fm_mic_muted() -> c_int {
+    guard(-1, || match Mic::query(&SHARED_MIC) {
+        Ok(muted) => c_int::from(muted),
+        Err(e) => fail(e),
+    })
+}
+
+/// Fill `out` with the microphone and indicator state. `dev` may be NULL
+/// (then `led_muted` is false). The audio backend is opened on the first
+/// call and kept with `dev` (or for the process, without one).
+///
+/// # Safety
+///
+/// `dev` must be NULL or a live handle from [`fm_device_open`]; `out` must be
+/// NULL or point to writable memory for one `FmStatus`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fm_status(dev: *const FmDevice, out: *mut FmStatus) -> c_int {
+    guard(-1, || {
+        let Some(out) = (unsafe { out.as_mut() }) else {
+            return fail("status pointer is NULL");
+        };
+        let dev = unsafe { dev.as_ref() };
+        let led_muted = dev.is_some_and(|d| d.led_muted.load(Ordering::Relaxed));
+        let mic = Mic::query(dev.map_or(&SHARED_MIC, |d| &d.mic));
+        *out = FmStatus {
+            mic_muted: mic.map_or(-1, c_int::from),
+            led_muted,
+        };
+        0
+    })
+}
+
+/// Call `callback` on a background thread whenever the default microphone's
+/// mute state changes, starting with the current state. Stop with
+/// [`fm_unwatch`].
+///
+/// # Safety
+///
+/// `user_data` is passed back untouched from the watch thread; the caller
+/// must make that safe and keep it alive until [`fm_unwatch`] returns.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fm_watch_mute(
+    callback: Option<FmMuteCallback>,
+    user_data: *mut c_void,
+) -> *mut FmWatch {
+    guard(ptr::null_mut(), || {
+        let Some(f) = callback else {
+            return fail_null("callback is NULL");
+        };
+        let callback = Callback { f, user_data };
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_thread = Arc::clone(&stop);
+        let (ready, ready_rx) = mpsc::channel();
+        let spawned = std::thread::Builder::new()
+            .name("focusmute-watch".into())
+            .spawn(move || {
+                // The monitor lives on this thread (COM on Windows)
+                #[cfg(windows)]
+                let _com = match audio::com_enter() {
+                    Ok(com) => com,
+                    Err(e) => {
+                        let _ = ready.send(Err(e));
+                        return;
+                    }
+                };
+                let monitor = match platform_monitor() {
+                    Ok(m) => {
+                        let _ = ready.send(Ok(()));
+                        m
+                    }
+                    Err(e) => {
+                        let _ = ready.send(Err(e));
+                        return;
+                    }
+                };
+                let mut last = monitor.is_muted();
+                callback.call(last);
+                while !stop_thread.load(Ordering::Relaxed) {
+                    monitor.wait_for_change(WATCH_POLL);
+                    monitor.refresh();
+                    let muted = monitor.is_muted();
+                    if muted != last {
+                        last = muted;
+                        callback.call(muted);
+                    }
+                }
+            });
+        let thread = match spawned {
+            Ok(thread) => thread,
+            Err(e) => return fail_null(e),
+        };
+        match ready_rx.recv() {
+            Ok(Ok(())) => Box::into_raw(Box::new(FmWatch {
+                stop,
+                thread: Some(thread),
+            })),
+            Ok(Err(e)) => fail_null(e),
+            Err(_) => fail_null("mute watch thread stopped"),
+        }
+    })
+}
+
+/// Stop a watch and wait for its thread; no callback runs after this
+/// returns. NULL is ignored.
+///
+/// # Safety
+///
+/// `watch` must be NULL or a handle from [`fm_watch_mute`] that has not been
+/// stopped yet. Don't call it from inside the callback.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fm_unwatch(watch: *mut FmWatch) {
+    guard((), || {
+        if watch.is_null() {
+            return;
+        }
+        let mut watch = unsafe { Box::from_raw(watch) };
+        watch.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = watch.thread.take() {
+            let _ = thread.join();
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use focusmute_lib::device::mock::MockDevice;
+    use focusmute_lib::protocol::OFF_SELECTED_INPUT;
+
+    fn last_error() -> String {
+        let ptr = fm_last_error();
+        assert!(!ptr.is_null());
+        unsafe { CStr::from_ptr(ptr) }
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    fn open_mock() -> *mut FmDevice {
+        let mut dev = MockDevice::new();
+        dev.info_mut().device_name = "Scarlett 2i2 4th Gen-00031337".into();
+        dev.set_descriptor(OFF_SELECTED_INPUT, &[0]).unwrap();
+        Box::into_raw(Box::new(FmDevice::new(dev, &Config::default()).unwrap()))
+    }
+
+    #[test]
+    fn version_matches_crate() {
+        let version = unsafe { CStr::from_ptr(fm_version()) };
+        assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn null_handles_fail_with_message() {
+        assert_eq!(unsafe { fm_device_set_mute_led(ptr::null(), true) }, -1);
+        assert_eq!(last_error(), "device handle is NULL");
+        assert!(unsafe { fm_device_name(ptr::null()) }.is_null());
+        assert_eq!(unsafe { fm_status(ptr::null(), ptr::null_mut()) }, -1);
+        assert_eq!(last_error(), "status pointer is NULL");
+        assert!(unsafe { fm_watch_mute(None, ptr::null_mut()) }.is_null());
+        assert_eq!(last_error(), "callback is NULL");
+        unsafe {
+            fm_device_close(ptr::null_mut());
+            fm_unwatch(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn mute_led_follows_calls() {
+        let dev = open_mock();
+        let name = unsafe { CStr::from_ptr(fm_device_name(dev)) };
+        assert_eq!(name.to_str().unwrap(), "Scarlett 2i2 4th Gen");
+
+        assert_eq!(unsafe { fm_device_set_mute_led(dev, true) }, 0);
+        assert!(unsafe { &*dev }.led_muted.load(Ordering::Relaxed));
+        assert_eq!(unsafe { fm_device_set_mute_led(dev, false) }, 0);
+        assert!(!unsafe { &*dev }.led_muted.load(Ordering::Relaxed));
+        unsafe { fm_device_close(dev) };
+    }
+
+    /// Reports a fixed mute state.
+    struct FakeMonitor(bool);
+
+    impl MuteMonitor for FakeMonitor {
+        fn is_muted(&self) -> bool {
+            self.0
+        }
+        fn set_muted(&self, _muted: bool) -> audio::Result<()> {
+            Ok(())
+        }
+        fn wait_for_change(&self, _timeout: Duration) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn mic_opens_once_and_answers_queries() {
+        let opened = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let count = Arc::clone(&opened);
+        let mic = Mic::spawn(move || {
+            count.fetch_add(1, Ordering::SeqCst);
+            Ok(Box::new(FakeMonitor(true)) as Box<dyn MuteMonitor>)
+        })
+        .unwrap();
+        assert_eq!(mic.is_muted(), Ok(true));
+        assert_eq!(mic.is_muted(), Ok(true));
+        assert_eq!(opened.load(Ordering::SeqCst), 1);
+        drop(mic);
+    }
+
+    #[test]
+    fn mic_open_failure_is_reported() {
+        let err = Mic::spawn(|| Err(audio::AudioError::InitFailed("no backend".into())))
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("no backend"));
+    }
+
+    #[test]
+    fn panics_become_errors() {
+        let result = guard(-1, || -> c_int { panic!("boom") });
+        assert_eq!(result, -1);
+        assert_eq!(last_error(), "internal error (panic) in focusmute");
+    }
+}
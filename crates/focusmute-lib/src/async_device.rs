@@ -0,0 +1,152 @@
+//! Async device API (`async` feature) — drive the device from tokio.
+//!
+//! [`AsyncScarlettDevice`] wraps a blocking backend in a [`DeviceWorker`]:
+//! each call is queued on the worker thread and its reply comes back over a
+//! oneshot channel, so a REST or WebSocket service can `.await` device I/O
+//! without a thread (or `spawn_blocking`) per operation. Calls still run one
+//! at a time, in order — a long [`wait_notify`](AsyncScarlettDevice::wait_notify)
+//! delays everything queued behind it.
+
+use tokio::sync::oneshot;
+
+use crate::device::{DeviceInfo, DeviceOptions, Result, ScarlettDevice, open_device_with_options};
+use crate::device_worker::{DeviceWorker, stopped};
+
+/// Async handle to a device owned by a worker thread. Cheap to clone.
+#[derive(Clone)]
+pub struct AsyncScarlettDevice {
+    worker: DeviceWorker,
+}
+
+impl AsyncScarlettDevice {
+    /// Move `device` onto a new worker thread.
+    pub fn new<D: ScarlettDevice + Send + 'static>(device: D) -> Self {
+        Self::from_worker(DeviceWorker::spawn(device))
+    }
+
+    /// Share an existing worker, e.g. with blocking code on another thread.
+    pub fn from_worker(worker: DeviceWorker) -> Self {
+        Self { worker }
+    }
+
+    /// Open a device (see [`open_device_with_options`]) without blocking the
+    /// runtime. An empty `serial` auto-selects.
+    pub async fn open(serial: &str, options: DeviceOptions) -> Result<Self> {
+        let serial = serial.to_string();
+        let (reply, reply_rx) = oneshot::channel();
+        std::thread::Builder::new()
+            .name("device-open".into())
+            .spawn(move || {
+                let _ = reply.send(open_device_with_options(&serial, options));
+            })
+            .map_err(|_| stopped())?;
+        let device = reply_rx.await.map_err(|_| stopped())??;
+        Ok(Self::new(device))
+    }
+
+    /// The blocking handle to the same worker.
+    pub fn worker(&self) -> &DeviceWorker {
+        &self.worker
+    }
+
+    pub fn info(&self) -> &DeviceInfo {
+        self.worker.info()
+    }
+
+    pub fn supports_notify(&self) -> bool {
+        self.worker.supports_notify()
+    }
+
+    /// Run `job` on the worker thread and await its result.
+    ///
+    /// Fails with `TransactFailed` if the worker has stopped.
+    pub async fn run<R: Send + 'static>(
+        &self,
+        job: impl FnOnce(&dyn ScarlettDevice) -> R + Send + 'static,
+    ) -> Result<R> {
+        let (reply, reply_rx) = oneshot::channel();
+        self.worker.submit(move |dev| {
+            let _ = reply.send(job(dev));
+        })?;
+        reply_rx.await.map_err(|_| stopped())
+    }
+
+    pub async fn get_descriptor(&self, offset: u32, size: u32) -> Result<Vec<u8>> {
+        self.run(move |dev| dev.get_descriptor(offset, size))
+            .await?
+    }
+
+    pub async fn set_descriptor(&self, offset: u32, data: &[u8]) -> Result<()> {
+        let data = data.to_vec();
+        self.run(move |dev| dev.set_descriptor(offset, &data))
+            .await?
+    }
+
+    pub async fn data_notify(&self, event_id: u32) -> Result<()> {
+        self.run(move |dev| dev.data_notify(event_id)).await?
+    }
+
+    /// Send a raw TRANSACT command and return the response.
+    pub async fn transact(&self, cmd: u32, payload: &[u8], out_size: usize) -> Result<Vec<u8>> {
+        let payload = payload.to_vec();
+        self.run(move |dev| dev.transact(cmd, &payload, out_size))
+            .await?
+    }
+
+    /// Wait for a device notification. Holds the worker for up to
+    /// `timeout_ms`; other calls wait behind it.
+    pub async fn wait_notify(&self, timeout_ms: u64) -> Result<Vec<u8>> {
+        self.run(move |dev| dev.wait_notify(timeout_ms)).await?
+    }
+
+    /// Send a raw IOCTL (bypassing TRANSACT framing).
+    pub async fn raw_ioctl(&self, code: u32, input: &[u8], out_size: usize) -> Result<Vec<u8>> {
+        let input = input.to_vec();
+        self.run(move |dev| dev.raw_ioctl(code, &input, out_size))
+            .await?
+    }
+
+    /// Reset the device (see [`ScarlettDevice::reset`]). The worker's device
+    /// is unusable afterwards — open a new handle.
+    pub async fn reset(&self) -> Result<()> {
+        self.run(|dev| dev.reset()).await?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device::DeviceError;
+    use crate::device::mock::MockDevice;
+
+    #[tokio::test]
+    async fn forwards_calls_to_worker_thread() {
+        let dev = AsyncScarlettDevice::new(MockDevice::new());
+        dev.set_descriptor(92, &[1, 2, 3]).await.unwrap();
+        assert_eq!(dev.get_descriptor(92, 3).await.unwrap(), vec![1, 2, 3]);
+        dev.data_notify(8).await.unwrap();
+
+        let thread = dev
+            .run(|_| std::thread::current().name().map(String::from))
+            .await
+            .unwrap();
+        assert_eq!(thread.as_deref(), Some("device-worker"));
+    }
+
+    #[tokio::test]
+    async fn shares_worker_with_blocking_handle() {
+        let dev = AsyncScarlettDevice::new(MockDevice::new());
+        dev.worker().set_descriptor(10, &[7]).unwrap();
+        assert_eq!(dev.clone().get_descriptor(10, 1).await.unwrap(), vec![7]);
+    }
+
+    #[tokio::test]
+    async fn stopped_worker_fails() {
+        let dev = AsyncScarlettDevice::new(MockDevice::new());
+        assert!(dev.run(|_| -> () { panic!("job failed") }).await.is_err());
+        assert!(matches!(
+            dev.get_descriptor(0, 1).await,
+            Err(DeviceError::TransactFailed(_))
+        ));
+    }
+}
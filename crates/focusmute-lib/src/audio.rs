@@ -1,4 +1,4 @@
-//! Audio mute detection — trait + Windows WASAPI backend.
+//! Audio mute detection — trait + WASAPI, PulseAudio, and PipeWire backends.
 
 use std::fmt;
 
@@ -29,8 +29,81 @@ pub trait MuteMonitor {
     /// Returns `true` if woken by an event, `false` on timeout.
     fn wait_for_change(&self, timeout: std::time::Duration) -> bool;
     /// Refresh cached mute state from the underlying audio system.
-    /// Default is a no-op; PulseAudio overrides to re-query state.
+    /// Default is a no-op; PulseAudio overrides it with a periodic re-query
+    /// in case a subscription event was missed, WASAPI to move over to a new
+    /// default capture device.
     fn refresh(&self) {}
+    /// Wake a thread blocked in [`wait_for_change`](Self::wait_for_change)
+    /// early, e.g. so it notices shutdown. Default is a no-op; WASAPI, whose
+    /// waits are long, overrides it.
+    fn wake(&self) {}
+    /// Current input peak level per channel (0.0–1.0), taken before the
+    /// mute — so it still shows the user talking while muted. `None` when
+    /// the backend has no such meter (the default; PulseAudio delivers
+    /// silence from a muted source).
+    fn peak_levels(&self) -> Option<Vec<f32>> {
+        None
+    }
+    /// Whether some application is capturing from the device right now.
+    /// `None` when the backend can't tell (the default). May ask the sound
+    /// server, so poll it every [`IN_USE_POLL`] rather than every cycle.
+    fn in_use(&self) -> Option<bool> {
+        None
+    }
+    /// Process binaries (paths or file names) of the applications recording
+    /// from the device right now — how a call starting is noticed. `None`
+    /// when the backend can't tell (the default). Poll it like
+    /// [`in_use`](Self::in_use).
+    fn capture_applications(&self) -> Option<Vec<String>> {
+        None
+    }
+    /// Raise the device's capture volume by `step_db` dB (lower it, if
+    /// negative), within its range. Returns the new volume in dB.
+    /// Unsupported by default.
+    fn adjust_capture_volume(&self, step_db: f64) -> Result<f64> {
+        let _ = step_db;
+        Err(AudioError::OperationFailed(
+            "capture volume control is not supported by this audio backend".into(),
+        ))
+    }
+    /// Mute or unmute the capture streams of `apps` (see
+    /// [`matches_application`]) on this device, leaving the device itself
+    /// alone. Returns how many streams were set. Unsupported by default.
+    fn set_applications_muted(&self, apps: &[String], muted: bool) -> Result<usize> {
+        let _ = (apps, muted);
+        Err(AudioError::OperationFailed(
+            "per-application mute is not supported by this audio backend".into(),
+        ))
+    }
+}
+
+/// Lowest capture volume [`MuteMonitor::adjust_capture_volume`] steps down
+/// to where the backend reports no range of its own, in dB.
+pub const MIN_CAPTURE_VOLUME_DB: f64 = -60.0;
+
+/// `current` dB stepped by `step_db`, within `min..=max`. A silent (`-inf`)
+/// volume counts as `min`.
+fn step_volume_db(current: f64, step_db: f64, min: f64, max: f64) -> f64 {
+    let current = if current.is_finite() { current } else { min };
+    (current + step_db).clamp(min, max)
+}
+
+/// Whether the process `binary` (a path or file name) is one of `apps`.
+/// Compares file names, ignoring case and a trailing `.exe`.
+pub fn matches_application(apps: &[String], binary: &str) -> bool {
+    let stem = |name: &str| {
+        let name = name
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or(name)
+            .to_lowercase();
+        match name.strip_suffix(".exe") {
+            Some(stem) => stem.to_string(),
+            None => name,
+        }
+    };
+    let binary = stem(binary.trim());
+    !binary.is_empty() && apps.iter().any(|app| stem(app.trim()) == binary)
 }
 
 /// Wait on a `(Mutex<bool>, Condvar)` signal pair with a timeout.
@@ -62,6 +135,320 @@ fn wait_on_signal(
     }
 }
 
+/// Wake a thread in [`wait_on_signal`].
+fn raise_signal(signal: &(std::sync::Mutex<bool>, std::sync::Condvar)) {
+    if let Ok(mut changed) = signal.0.lock() {
+        *changed = true;
+        signal.1.notify_all();
+    }
+}
+
+// ── Capture device selection ──
+
+/// Whether the capture device `name` is on the `excluded` list — a
+/// case-insensitive substring match, so `"CABLE"` covers "CABLE Output (VB-Audio
+/// Virtual Cable)".
+pub fn is_excluded(name: &str, excluded: &[String]) -> bool {
+    let name = name.to_lowercase();
+    excluded
+        .iter()
+        .map(|p| p.trim())
+        .filter(|p| !p.is_empty())
+        .any(|p| name.contains(&p.to_lowercase()))
+}
+
+/// Choose a capture device to monitor instead of an excluded default.
+///
+/// Returns the index into `names`, or `None` to keep the default — it isn't
+/// excluded (or its name is unknown), or every other device is excluded too.
+/// Focusrite devices win over other candidates.
+#[cfg_attr(not(any(windows, target_os = "linux")), allow(dead_code))]
+pub(crate) fn pick_capture_device(
+    default: Option<&str>,
+    names: &[String],
+    excluded: &[String],
+) -> Option<usize> {
+    let default = default.filter(|d| is_excluded(d, excluded))?;
+    let candidates = || {
+        names
+            .iter()
+            .enumerate()
+            .filter(|(_, n)| !n.is_empty() && !is_excluded(n, excluded))
+    };
+    let is_focusrite = |name: &str| {
+        let name = name.to_lowercase();
+        name.contains("focusrite") || name.contains("scarlett")
+    };
+    let pick = candidates()
+        .find(|(_, n)| is_focusrite(n))
+        .or_else(|| candidates().next());
+    match pick {
+        Some((i, name)) => {
+            log::info!(
+                "[audio] default capture device \"{default}\" is excluded — monitoring \"{name}\""
+            );
+            Some(i)
+        }
+        None => {
+            log::warn!(
+                "[audio] default capture device \"{default}\" is excluded, but no other device is available"
+            );
+            None
+        }
+    }
+}
+
+// ── Backend selection ──
+
+/// Which audio system the mute monitor talks to on Linux (`audio_backend`
+/// setting). Windows has only WASAPI and ignores it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AudioBackend {
+    /// PipeWire if its daemon answers (and FocusMute was built with the
+    /// `pipewire` feature), PulseAudio otherwise.
+    #[default]
+    Auto,
+    /// PulseAudio — or PipeWire through its PulseAudio compatibility layer.
+    PulseAudio,
+    /// PipeWire's native API.
+    PipeWire,
+}
+
+impl AudioBackend {
+    /// Parse a config value (`"auto"`, `"pulseaudio"`, `"pipewire"`).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "auto" => Some(AudioBackend::Auto),
+            "pulseaudio" => Some(AudioBackend::PulseAudio),
+            "pipewire" => Some(AudioBackend::PipeWire),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AudioBackend::Auto => "auto",
+            AudioBackend::PulseAudio => "pulseaudio",
+            AudioBackend::PipeWire => "pipewire",
+        }
+    }
+}
+
+// ── Capture device groups ──
+
+/// How often callers check [`MuteMonitor::in_use`].
+pub const IN_USE_POLL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// How often a group of several monitors checks its members for changes.
+const GROUP_POLL: std::time::Duration = std::time::Duration::from_millis(20);
+
+/// How often muted application streams are muted again, to catch streams
+/// started since.
+const APPLICATION_REMUTE: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Per-application capture mute (`mute_applications`): FocusMute's mute
+/// silences only these applications' capture streams, leaving the device
+/// live for everything else.
+///
+/// The mute state is FocusMute's own — a stream muted or unmuted from the
+/// application or the OS mixer isn't noticed. Streams that start while
+/// muted are muted on the next [`refresh`](MuteMonitor::refresh).
+struct ApplicationMute {
+    apps: Vec<String>,
+    muted: std::sync::atomic::AtomicBool,
+    signal: (std::sync::Mutex<bool>, std::sync::Condvar),
+    /// When the streams were last muted.
+    applied: std::sync::Mutex<Option<std::time::Instant>>,
+}
+
+/// Several capture devices muted and unmuted together
+/// (`mute_all_capture_devices`).
+///
+/// The first member is the primary — the device FocusMute would monitor on
+/// its own — and supplies the device name and peak levels. The group reads
+/// as muted only while every member is, so unmuting any one of them shows
+/// the microphone live.
+pub struct MonitorGroup<M> {
+    members: Vec<M>,
+    applications: Option<ApplicationMute>,
+}
+
+impl<M: MuteMonitor> MonitorGroup<M> {
+    /// Group `members`, primary first.
+    pub fn new(members: Vec<M>) -> Self {
+        Self {
+            members,
+            applications: None,
+        }
+    }
+
+    /// Mute only the capture streams of `apps` (process names) rather than
+    /// the devices themselves. An empty list keeps the devices' own mute.
+    pub fn with_applications(mut self, apps: &[String]) -> Self {
+        let apps: Vec<String> = apps
+            .iter()
+            .map(|app| app.trim().to_string())
+            .filter(|app| !app.is_empty())
+            .collect();
+        self.applications = (!apps.is_empty()).then(|| ApplicationMute {
+            apps,
+            muted: std::sync::atomic::AtomicBool::new(false),
+            signal: (std::sync::Mutex::new(false), std::sync::Condvar::new()),
+            applied: std::sync::Mutex::new(None),
+        });
+        self
+    }
+
+    /// Set the application streams on every member, even after one fails;
+    /// returns the first error.
+    fn set_applications_muted(&self, apps: &ApplicationMute, muted: bool) -> Result<usize> {
+        let mut result = Ok(0);
+        for (i, member) in self.members.iter().enumerate() {
+            match member.set_applications_muted(&apps.apps, muted) {
+                Ok(n) => {
+                    if let Ok(total) = result.as_mut() {
+                        *total += n;
+                    }
+                }
+                Err(e) => {
+                    log::warn!("[audio] capture device {}: {e}", i + 1);
+                    if result.is_ok() {
+                        result = Err(e);
+                    }
+                }
+            }
+        }
+        if let Ok(mut applied) = apps.applied.lock() {
+            *applied = Some(std::time::Instant::now());
+        }
+        result
+    }
+
+    pub fn primary(&self) -> Option<&M> {
+        self.members.first()
+    }
+
+    pub fn members(&self) -> &[M] {
+        &self.members
+    }
+}
+
+impl<M: MuteMonitor> MuteMonitor for MonitorGroup<M> {
+    fn is_muted(&self) -> bool {
+        if let Some(apps) = &self.applications {
+            return apps.muted.load(std::sync::atomic::Ordering::SeqCst);
+        }
+        !self.members.is_empty() && self.members.iter().all(M::is_muted)
+    }
+
+    /// Mute or unmute every member, even after one fails; returns the
+    /// first error.
+    fn set_muted(&self, muted: bool) -> Result<()> {
+        if let Some(apps) = &self.applications {
+            let result = self.set_applications_muted(apps, muted);
+            if let Ok(n) = result {
+                let verb = if muted { "muted" } else { "unmuted" };
+                log::debug!("[audio] {verb} {n} application stream(s)");
+            }
+            if apps.muted.swap(muted, std::sync::atomic::Ordering::SeqCst) != muted {
+                raise_signal(&apps.signal);
+            }
+            return result.map(|_| ());
+        }
+        let mut result = Ok(());
+        for (i, member) in self.members.iter().enumerate() {
+            if let Err(e) = member.set_muted(muted) {
+                log::warn!("[audio] capture device {}: {e}", i + 1);
+                if result.is_ok() {
+                    result = Err(e);
+                }
+            }
+        }
+        result
+    }
+
+    fn wait_for_change(&self, timeout: std::time::Duration) -> bool {
+        if let Some(apps) = &self.applications {
+            return wait_on_signal(&apps.signal, timeout);
+        }
+        match self.members.as_slice() {
+            [] => {
+                std::thread::sleep(timeout);
+                false
+            }
+            [only] => only.wait_for_change(timeout),
+            members => {
+                let deadline = std::time::Instant::now() + timeout;
+                loop {
+                    // Check them all, so every member's signal is consumed
+                    let changed = members.iter().fold(false, |changed, m| {
+                        m.wait_for_change(std::time::Duration::ZERO) | changed
+                    });
+                    let now = std::time::Instant::now();
+                    if changed || now >= deadline {
+                        return changed;
+                    }
+                    std::thread::sleep(GROUP_POLL.min(deadline - now));
+                }
+            }
+        }
+    }
+
+    fn refresh(&self) {
+        for member in &self.members {
+            member.refresh();
+        }
+        if let Some(apps) = &self.applications
+            && apps.muted.load(std::sync::atomic::Ordering::SeqCst)
+            && apps
+                .applied
+                .lock()
+                .is_ok_and(|at| at.is_none_or(|t| t.elapsed() >= APPLICATION_REMUTE))
+        {
+            let _ = self.set_applications_muted(apps, true);
+        }
+    }
+
+    fn wake(&self) {
+        for member in &self.members {
+            member.wake();
+        }
+        if let Some(apps) = &self.applications {
+            raise_signal(&apps.signal);
+        }
+    }
+
+    fn peak_levels(&self) -> Option<Vec<f32>> {
+        self.primary()?.peak_levels()
+    }
+
+    /// Only the primary device's volume — the one the mic level is about.
+    fn adjust_capture_volume(&self, step_db: f64) -> Result<f64> {
+        self.primary()
+            .ok_or_else(|| AudioError::OperationFailed("no capture device".into()))?
+            .adjust_capture_volume(step_db)
+    }
+
+    /// In use if any member is; `None` if no member can tell.
+    fn in_use(&self) -> Option<bool> {
+        self.members
+            .iter()
+            .filter_map(M::in_use)
+            .reduce(|a, b| a || b)
+    }
+
+    /// Every member's recording applications; `None` if no member can tell.
+    fn capture_applications(&self) -> Option<Vec<String>> {
+        self.members
+            .iter()
+            .filter_map(M::capture_applications)
+            .reduce(|mut all, apps| {
+                all.extend(apps);
+                all
+            })
+    }
+}
+
 // ── Windows WASAPI implementation ──
 
 #[cfg(windows)]
@@ -72,12 +459,17 @@ mod wasapi {
     use std::time::Duration;
 
     use windows::Win32::Devices::FunctionDiscovery::PKEY_Device_FriendlyName;
+    use windows::Win32::Foundation::{CloseHandle, PROPERTYKEY};
     use windows::Win32::Media::Audio::Endpoints::*;
     use windows::Win32::Media::Audio::*;
     use windows::Win32::System::Com::STGM_READ;
     use windows::Win32::System::Com::*;
+    use windows::Win32::System::Threading::{
+        OpenProcess, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+        QueryFullProcessImageNameW,
+    };
     use windows::Win32::System::Variant::VT_LPWSTR;
-    use windows::core::implement;
+    use windows::core::{Interface, PCWSTR, PWSTR, implement};
 
     /// COM callback that receives volume/mute change notifications.
     #[implement(IAudioEndpointVolumeCallback)]
@@ -93,25 +485,169 @@ mod wasapi {
         ) -> windows::core::Result<()> {
             if !pnotify.is_null() {
                 let muted = unsafe { (*pnotify).bMuted.as_bool() };
-                self.muted.store(muted, Ordering::SeqCst);
-                if let Ok(mut changed) = self.signal.0.lock() {
-                    *changed = true;
-                    self.signal.1.notify_all();
+                // Volume changes arrive here too; only a mute flip wakes the
+                // waiting thread
+                if self.muted.swap(muted, Ordering::SeqCst) != muted {
+                    raise(&self.signal);
                 }
             }
             Ok(())
         }
     }
 
-    pub struct WasapiMonitor {
+    /// Executable path of process `pid`, if it can be queried.
+    fn process_path(pid: u32) -> Option<String> {
+        unsafe {
+            let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+            let mut buf = [0u16; 1024];
+            let mut len = buf.len() as u32;
+            let result = QueryFullProcessImageNameW(
+                process,
+                PROCESS_NAME_WIN32,
+                PWSTR(buf.as_mut_ptr()),
+                &mut len,
+            );
+            let _ = CloseHandle(process);
+            result.ok()?;
+            Some(String::from_utf16_lossy(&buf[..len as usize]))
+        }
+    }
+
+    fn raise(signal: &(Mutex<bool>, Condvar)) {
+        if let Ok(mut changed) = signal.0.lock() {
+            *changed = true;
+            signal.1.notify_all();
+        }
+    }
+
+    /// COM callback that notices the default capture device changing.
+    #[implement(IMMNotificationClient)]
+    struct DefaultDeviceCallback {
+        changed: Arc<AtomicBool>,
+        signal: Arc<(Mutex<bool>, Condvar)>,
+    }
+
+    impl IMMNotificationClient_Impl for DefaultDeviceCallback_Impl {
+        fn OnDeviceStateChanged(
+            &self,
+            _device: &PCWSTR,
+            _state: DEVICE_STATE,
+        ) -> windows::core::Result<()> {
+            Ok(())
+        }
+
+        fn OnDeviceAdded(&self, _device: &PCWSTR) -> windows::core::Result<()> {
+            Ok(())
+        }
+
+        fn OnDeviceRemoved(&self, _device: &PCWSTR) -> windows::core::Result<()> {
+            Ok(())
+        }
+
+        fn OnDefaultDeviceChanged(
+            &self,
+            flow: EDataFlow,
+            role: ERole,
+            _device: &PCWSTR,
+        ) -> windows::core::Result<()> {
+            if flow == eCapture && role == eConsole {
+                self.changed.store(true, Ordering::SeqCst);
+                raise(&self.signal);
+            }
+            Ok(())
+        }
+
+        fn OnPropertyValueChanged(
+            &self,
+            _device: &PCWSTR,
+            _key: &PROPERTYKEY,
+        ) -> windows::core::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// The endpoint a monitor is bound to, with its registered callback.
+    struct Endpoint {
         volume: IAudioEndpointVolume,
+        /// Peak meter of the same endpoint, if it has one.
+        meter: Option<IAudioMeterInformation>,
+        /// Audio sessions on the endpoint, to tell whether it's in use.
+        sessions: Option<IAudioSessionManager2>,
         device_name: Option<String>,
+        /// Must keep callback alive for the lifetime of the registration.
+        callback: IAudioEndpointVolumeCallback,
+    }
+
+    impl Endpoint {
+        /// Bind to `device`, storing its mute state in `muted` and raising
+        /// `signal` on every change.
+        fn bind(
+            device: &IMMDevice,
+            muted: &Arc<AtomicBool>,
+            signal: &Arc<(Mutex<bool>, Condvar)>,
+        ) -> Result<Self> {
+            unsafe {
+                let device_name = friendly_name(device);
+
+                let volume: IAudioEndpointVolume = device
+                    .Activate(CLSCTX_ALL, None)
+                    .map_err(|e| AudioError::InitFailed(format!("IAudioEndpointVolume: {e}")))?;
+
+                // Metering is optional — only talk-while-muted detection needs it
+                let meter: Option<IAudioMeterInformation> = device.Activate(CLSCTX_ALL, None).ok();
+                let sessions: Option<IAudioSessionManager2> =
+                    device.Activate(CLSCTX_ALL, None).ok();
+
+                // Read initial mute state
+                let initial_muted = volume.GetMute().map(|b| b.as_bool()).unwrap_or(false);
+                muted.store(initial_muted, Ordering::SeqCst);
+
+                // Register COM callback for mute/volume change events
+                let callback: IAudioEndpointVolumeCallback = MuteCallback {
+                    muted: Arc::clone(muted),
+                    signal: Arc::clone(signal),
+                }
+                .into();
+                volume.RegisterControlChangeNotify(&callback).map_err(|e| {
+                    AudioError::InitFailed(format!("RegisterControlChangeNotify: {e}"))
+                })?;
+
+                Ok(Endpoint {
+                    volume,
+                    meter,
+                    sessions,
+                    device_name,
+                    callback,
+                })
+            }
+        }
+    }
+
+    impl Drop for Endpoint {
+        fn drop(&mut self) {
+            unsafe {
+                let _ = self.volume.UnregisterControlChangeNotify(&self.callback);
+            }
+        }
+    }
+
+    /// Re-binds a monitor when the default capture device changes.
+    struct DefaultFollower {
+        enumerator: IMMDeviceEnumerator,
+        excluded: Vec<String>,
+        /// Set by the notification client, cleared by `refresh`.
+        changed: Arc<AtomicBool>,
+        client: IMMNotificationClient,
+    }
+
+    pub struct WasapiMonitor {
+        endpoint: Mutex<Endpoint>,
         /// Cached mute state, updated by COM callback.
         muted: Arc<AtomicBool>,
         /// Signaled by COM callback when mute state changes.
         signal: Arc<(Mutex<bool>, Condvar)>,
-        /// Must keep callback alive for the lifetime of the registration.
-        _callback: IAudioEndpointVolumeCallback,
+        /// Set when the monitor follows the default device.
+        follower: Option<DefaultFollower>,
     }
 
     // COM pointers are Send-safe with proper initialization per thread.
@@ -119,313 +655,1904 @@ mod wasapi {
     unsafe impl Send for WasapiMonitor {}
 
     // SAFETY: The shared fields accessed from background threads — `muted`
-    // (AtomicBool) and `signal` (Mutex+Condvar) — are inherently Sync.
-    // The COM `volume` pointer is only used by `set_muted()`, which is
-    // called exclusively from the main thread. `Drop` (which also touches
-    // COM) runs on the main thread because `run_core` joins the background
-    // thread before dropping the monitor.
+    // (AtomicBool) and `signal` (Mutex+Condvar) — are inherently Sync. The
+    // endpoint sits behind a mutex: the poll thread reads its meter and
+    // swaps it in `refresh()` when the default device changes, the main
+    // thread calls `set_muted()` (MMDevice API objects are free-threaded).
+    // `Drop` (which also touches COM) runs on the main thread because
+    // `run_core` joins the background thread before dropping the monitor.
     unsafe impl Sync for WasapiMonitor {}
 
+    /// Query the endpoint's friendly name from its property store.
+    fn friendly_name(device: &IMMDevice) -> Option<String> {
+        unsafe {
+            let store = device.OpenPropertyStore(STGM_READ).ok()?;
+            let prop = store.GetValue(&PKEY_Device_FriendlyName).ok()?;
+            if prop.Anonymous.Anonymous.vt == VT_LPWSTR {
+                prop.Anonymous.Anonymous.Anonymous.pwszVal.to_string().ok()
+            } else {
+                None
+            }
+        }
+    }
+
+    /// The endpoint's ID string, which identifies it across enumerations.
+    fn endpoint_id(device: &IMMDevice) -> Option<String> {
+        unsafe {
+            let id = device.GetId().ok()?;
+            let s = id.to_string().ok();
+            CoTaskMemFree(Some(id.0 as *const _));
+            s
+        }
+    }
+
+    /// The default endpoint, or a replacement if it is on the `excluded` list.
+    fn choose_endpoint(
+        enumerator: &IMMDeviceEnumerator,
+        default: IMMDevice,
+        excluded: &[String],
+    ) -> IMMDevice {
+        let Ok(collection) =
+            (unsafe { enumerator.EnumAudioEndpoints(eCapture, DEVICE_STATE_ACTIVE) })
+        else {
+            return default;
+        };
+        let count = unsafe { collection.GetCount() }.unwrap_or(0);
+        let devices: Vec<IMMDevice> = (0..count)
+            .filter_map(|i| unsafe { collection.Item(i) }.ok())
+            .collect();
+        let names: Vec<String> = devices
+            .iter()
+            .map(|d| friendly_name(d).unwrap_or_default())
+            .collect();
+        match super::pick_capture_device(friendly_name(&default).as_deref(), &names, excluded) {
+            Some(i) => devices[i].clone(),
+            None => default,
+        }
+    }
+
+    /// The endpoint to monitor: the default capture device, unless it is
+    /// on the `excluded` list.
+    fn default_endpoint(
+        enumerator: &IMMDeviceEnumerator,
+        excluded: &[String],
+    ) -> Result<IMMDevice> {
+        let device = unsafe { enumerator.GetDefaultAudioEndpoint(eCapture, eConsole) }
+            .map_err(|e| AudioError::InitFailed(format!("GetDefaultAudioEndpoint: {e}")))?;
+        if excluded.is_empty() {
+            return Ok(device);
+        }
+        Ok(choose_endpoint(enumerator, device, excluded))
+    }
+
+    fn device_enumerator() -> Result<IMMDeviceEnumerator> {
+        unsafe { CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL) }
+            .map_err(|e| AudioError::InitFailed(format!("MMDeviceEnumerator: {e}")))
+    }
+
     impl WasapiMonitor {
         /// Create a new monitor for the default capture (microphone) device.
         /// Caller must ensure COM is initialized on this thread.
         pub fn new() -> Result<Self> {
+            Self::with_exclusions(&[])
+        }
+
+        /// Like [`new`](Self::new), but if the default capture device is on
+        /// the `excluded` list (see [`is_excluded`](super::is_excluded)),
+        /// monitor another active one instead — a Focusrite device if any.
+        ///
+        /// When the user picks another default microphone, the next
+        /// [`refresh`](MuteMonitor::refresh) moves the monitor over to it.
+        pub fn with_exclusions(excluded: &[String]) -> Result<Self> {
+            let enumerator = device_enumerator()?;
+            let device = default_endpoint(&enumerator, excluded)?;
+            let mut monitor = Self::from_device(&device)?;
+
+            let changed = Arc::new(AtomicBool::new(false));
+            let client: IMMNotificationClient = DefaultDeviceCallback {
+                changed: Arc::clone(&changed),
+                signal: Arc::clone(&monitor.signal),
+            }
+            .into();
+            // Without the notification the monitor just stays on this device
+            match unsafe { enumerator.RegisterEndpointNotificationCallback(&client) } {
+                Ok(()) => {
+                    monitor.follower = Some(DefaultFollower {
+                        enumerator,
+                        excluded: excluded.to_vec(),
+                        changed,
+                        client,
+                    })
+                }
+                Err(e) => log::warn!("[audio] RegisterEndpointNotificationCallback: {e}"),
+            }
+            Ok(monitor)
+        }
+
+        /// One monitor per active capture device not on the `excluded`
+        /// list, the one [`with_exclusions`](Self::with_exclusions) picks
+        /// first. These stay on their devices. Caller must ensure COM is
+        /// initialized on this thread.
+        pub fn all_active(excluded: &[String]) -> Result<Vec<Self>> {
             unsafe {
-                let enumerator: IMMDeviceEnumerator =
-                    CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL)
-                        .map_err(|e| AudioError::InitFailed(format!("MMDeviceEnumerator: {e}")))?;
-
-                let device = enumerator
-                    .GetDefaultAudioEndpoint(eCapture, eConsole)
-                    .map_err(|e| AudioError::InitFailed(format!("GetDefaultAudioEndpoint: {e}")))?;
-
-                // Query device friendly name from property store
-                let device_name = match device.OpenPropertyStore(STGM_READ) {
-                    Ok(store) => match store.GetValue(&PKEY_Device_FriendlyName) {
-                        Ok(prop) => {
-                            if prop.Anonymous.Anonymous.vt == VT_LPWSTR {
-                                prop.Anonymous.Anonymous.Anonymous.pwszVal.to_string().ok()
-                            } else {
-                                None
-                            }
+                let enumerator = device_enumerator()?;
+                let collection = enumerator
+                    .EnumAudioEndpoints(eCapture, DEVICE_STATE_ACTIVE)
+                    .map_err(|e| AudioError::InitFailed(format!("EnumAudioEndpoints: {e}")))?;
+                let count = collection.GetCount().unwrap_or(0);
+                let mut devices: Vec<IMMDevice> = (0..count)
+                    .filter_map(|i| collection.Item(i).ok())
+                    .filter(|d| {
+                        !friendly_name(d).is_some_and(|name| super::is_excluded(&name, excluded))
+                    })
+                    .collect();
+                if let Ok(primary) = default_endpoint(&enumerator, excluded) {
+                    let primary_id = endpoint_id(&primary);
+                    devices.retain(|d| endpoint_id(d) != primary_id);
+                    devices.insert(0, primary);
+                }
+                let monitors: Vec<Self> = devices
+                    .iter()
+                    .filter_map(|device| match Self::from_device(device) {
+                        Ok(monitor) => Some(monitor),
+                        Err(e) => {
+                            log::warn!(
+                                "[audio] can't monitor \"{}\": {e}",
+                                friendly_name(device).unwrap_or_default()
+                            );
+                            None
                         }
-                        Err(_) => None,
-                    },
-                    Err(_) => None,
-                };
+                    })
+                    .collect();
+                if monitors.is_empty() {
+                    return Err(AudioError::InitFailed("no active capture devices".into()));
+                }
+                Ok(monitors)
+            }
+        }
 
-                let volume: IAudioEndpointVolume = device
-                    .Activate(CLSCTX_ALL, None)
-                    .map_err(|e| AudioError::InitFailed(format!("IAudioEndpointVolume: {e}")))?;
+        /// The monitor the mute toggle drives: every active capture device
+        /// when `all` is set (`mute_all_capture_devices`), otherwise the one
+        /// [`with_exclusions`](Self::with_exclusions) picks.
+        pub fn open_group(excluded: &[String], all: bool) -> Result<MonitorGroup<Self>> {
+            let members = if all {
+                Self::all_active(excluded)?
+            } else {
+                vec![Self::with_exclusions(excluded)?]
+            };
+            Ok(MonitorGroup::new(members))
+        }
 
-                // Read initial mute state
-                let initial_muted = volume.GetMute().map(|b| b.as_bool()).unwrap_or(false);
+        /// Monitor one endpoint.
+        fn from_device(device: &IMMDevice) -> Result<Self> {
+            let muted = Arc::new(AtomicBool::new(false));
+            let signal = Arc::new((Mutex::new(false), Condvar::new()));
+            let endpoint = Endpoint::bind(device, &muted, &signal)?;
+            Ok(WasapiMonitor {
+                endpoint: Mutex::new(endpoint),
+                muted,
+                signal,
+                follower: None,
+            })
+        }
+
+        fn endpoint(&self) -> std::sync::MutexGuard<'_, Endpoint> {
+            self.endpoint.lock().unwrap_or_else(|e| e.into_inner())
+        }
+
+        pub fn device_name(&self) -> Option<String> {
+            self.endpoint().device_name.clone()
+        }
+
+        /// Move over to the current default device.
+        fn follow_default(&self, follower: &DefaultFollower) {
+            let endpoint = default_endpoint(&follower.enumerator, &follower.excluded)
+                .and_then(|device| Endpoint::bind(&device, &self.muted, &self.signal));
+            match endpoint {
+                Ok(endpoint) => {
+                    log::info!(
+                        "[audio] default capture device changed — monitoring \"{}\"",
+                        endpoint.device_name.as_deref().unwrap_or("unknown")
+                    );
+                    let mut current = self.endpoint();
+                    // The old endpoint's callback may have fired since the bind
+                    let muted = unsafe { endpoint.volume.GetMute() }.map(|b| b.as_bool());
+                    *current = endpoint;
+                    if let Ok(muted) = muted {
+                        self.muted.store(muted, Ordering::SeqCst);
+                    }
+                }
+                Err(e) => log::warn!("[audio] could not follow the default capture device: {e}"),
+            }
+        }
+    }
+
+    impl MuteMonitor for WasapiMonitor {
+        fn is_muted(&self) -> bool {
+            self.muted.load(Ordering::SeqCst)
+        }
+
+        fn set_muted(&self, muted: bool) -> Result<()> {
+            unsafe {
+                self.endpoint()
+                    .volume
+                    .SetMute(muted, std::ptr::null())
+                    .map_err(|e| AudioError::OperationFailed(format!("SetMute: {e}")))
+            }
+        }
+
+        fn wait_for_change(&self, timeout: Duration) -> bool {
+            super::wait_on_signal(&self.signal, timeout)
+        }
+
+        /// Re-bind if the default capture device changed since the last call.
+        fn refresh(&self) {
+            if let Some(follower) = &self.follower
+                && follower.changed.swap(false, Ordering::SeqCst)
+            {
+                self.follow_default(follower);
+            }
+        }
+
+        fn wake(&self) {
+            raise(&self.signal);
+        }
+
+        fn peak_levels(&self) -> Option<Vec<f32>> {
+            let endpoint = self.endpoint();
+            let meter = endpoint.meter.as_ref()?;
+            unsafe {
+                let count = meter.GetMeteringChannelCount().ok()?;
+                let mut peaks = vec![0.0; count as usize];
+                meter.GetChannelsPeakValues(&mut peaks).ok()?;
+                Some(peaks)
+            }
+        }
 
-                let muted = Arc::new(AtomicBool::new(initial_muted));
-                let signal = Arc::new((Mutex::new(false), Condvar::new()));
+        /// In use while any audio session on the endpoint is active.
+        fn in_use(&self) -> Option<bool> {
+            let endpoint = self.endpoint();
+            let sessions = endpoint.sessions.as_ref()?;
+            unsafe {
+                let list = sessions.GetSessionEnumerator().ok()?;
+                let count = list.GetCount().ok()?;
+                Some((0..count).any(|i| {
+                    list.GetSession(i)
+                        .and_then(|session| session.GetState())
+                        .is_ok_and(|state| state == AudioSessionStateActive)
+                }))
+            }
+        }
+
+        /// Step the endpoint's master volume within its reported range.
+        fn adjust_capture_volume(&self, step_db: f64) -> Result<f64> {
+            let endpoint = self.endpoint();
+            unsafe {
+                let (mut min, mut max, mut increment) = (0.0f32, 0.0f32, 0.0f32);
+                endpoint
+                    .volume
+                    .GetVolumeRange(&mut min, &mut max, &mut increment)
+                    .map_err(|e| AudioError::OperationFailed(format!("GetVolumeRange: {e}")))?;
+                let current = endpoint.volume.GetMasterVolumeLevel().map_err(|e| {
+                    AudioError::OperationFailed(format!("GetMasterVolumeLevel: {e}"))
+                })?;
+                let db = super::step_volume_db(
+                    f64::from(current),
+                    step_db,
+                    f64::from(min),
+                    f64::from(max),
+                );
+                endpoint
+                    .volume
+                    .SetMasterVolumeLevel(db as f32, std::ptr::null())
+                    .map_err(|e| {
+                        AudioError::OperationFailed(format!("SetMasterVolumeLevel: {e}"))
+                    })?;
+                Ok(db)
+            }
+        }
+
+        /// Processes of the active audio sessions on the endpoint.
+        fn capture_applications(&self) -> Option<Vec<String>> {
+            let endpoint = self.endpoint();
+            let sessions = endpoint.sessions.as_ref()?;
+            unsafe {
+                let list = sessions.GetSessionEnumerator().ok()?;
+                let count = list.GetCount().ok()?;
+                Some(
+                    (0..count)
+                        .filter_map(|i| list.GetSession(i).ok())
+                        .filter(|session| {
+                            session
+                                .GetState()
+                                .is_ok_and(|state| state == AudioSessionStateActive)
+                        })
+                        .filter_map(|session| {
+                            let control = session.cast::<IAudioSessionControl2>().ok()?;
+                            process_path(control.GetProcessId().ok()?)
+                        })
+                        .collect(),
+                )
+            }
+        }
+
+        /// Set the audio sessions on the endpoint whose process is one of
+        /// `apps`.
+        fn set_applications_muted(&self, apps: &[String], muted: bool) -> Result<usize> {
+            let endpoint = self.endpoint();
+            let sessions = endpoint.sessions.as_ref().ok_or_else(|| {
+                AudioError::OperationFailed("no audio session manager for this device".into())
+            })?;
+            unsafe {
+                let list = sessions.GetSessionEnumerator().map_err(|e| {
+                    AudioError::OperationFailed(format!("GetSessionEnumerator: {e}"))
+                })?;
+                let count = list
+                    .GetCount()
+                    .map_err(|e| AudioError::OperationFailed(format!("GetCount: {e}")))?;
+                let mut matched = 0;
+                for i in 0..count {
+                    let Ok(session) = list.GetSession(i) else {
+                        continue;
+                    };
+                    let Ok(pid) = session
+                        .cast::<IAudioSessionControl2>()
+                        .and_then(|control| control.GetProcessId())
+                    else {
+                        continue;
+                    };
+                    if !process_path(pid)
+                        .is_some_and(|path| super::matches_application(apps, &path))
+                    {
+                        continue;
+                    }
+                    if let Ok(volume) = session.cast::<ISimpleAudioVolume>()
+                        && volume.SetMute(muted, std::ptr::null()).is_ok()
+                    {
+                        matched += 1;
+                    }
+                }
+                Ok(matched)
+            }
+        }
+    }
+
+    impl Drop for WasapiMonitor {
+        fn drop(&mut self) {
+            if let Some(follower) = &self.follower {
+                unsafe {
+                    let _ = follower
+                        .enumerator
+                        .UnregisterEndpointNotificationCallback(&follower.client);
+                }
+            }
+        }
+    }
+
+    /// Initialize COM for the current thread (apartment-threaded).
+    pub fn com_init() -> Result<()> {
+        unsafe {
+            CoInitializeEx(None, COINIT_APARTMENTTHREADED)
+                .ok()
+                .map_err(|e| AudioError::InitFailed(format!("CoInitializeEx: {e}")))
+        }
+    }
+
+    /// COM held for the current thread by [`com_enter`]; uninitializes on
+    /// drop when this guard initialized it. Must drop on the same thread.
+    pub struct ComGuard {
+        owned: bool,
+        _thread: std::marker::PhantomData<*const ()>,
+    }
+
+    /// Initialize COM for the current thread like [`com_init`], for code
+    /// running on a host's threads (the C ABI). A thread the host already
+    /// initialized with another concurrency model (`RPC_E_CHANGED_MODE`)
+    /// is usable as it is.
+    pub fn com_enter() -> Result<ComGuard> {
+        let hr = unsafe { CoInitializeEx(None, COINIT_APARTMENTTHREADED) };
+        if hr == windows::Win32::Foundation::RPC_E_CHANGED_MODE {
+            return Ok(ComGuard {
+                owned: false,
+                _thread: std::marker::PhantomData,
+            });
+        }
+        hr.ok()
+            .map_err(|e| AudioError::InitFailed(format!("CoInitializeEx: {e}")))?;
+        Ok(ComGuard {
+            owned: true,
+            _thread: std::marker::PhantomData,
+        })
+    }
+
+    impl Drop for ComGuard {
+        fn drop(&mut self) {
+            if self.owned {
+                unsafe { CoUninitialize() };
+            }
+        }
+    }
+}
+
+#[cfg(windows)]
+pub use wasapi::{ComGuard, WasapiMonitor, com_enter, com_init};
+
+// ── Linux PulseAudio implementation ──
+
+#[cfg(target_os = "linux")]
+mod pulse {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+    use std::sync::{Arc, Condvar, Mutex};
+    use std::time::{Duration, Instant};
+
+    use libpulse_binding::callbacks::ListResult;
+    use libpulse_binding::context::introspect::Introspector;
+    use libpulse_binding::context::subscribe::{
+        Facility, InterestMaskSet, Operation as SubscribeOperation,
+    };
+    use libpulse_binding::context::{Context, FlagSet as ContextFlagSet, State as ContextState};
+    use libpulse_binding::mainloop::threaded::Mainloop;
+    use libpulse_binding::operation::{Operation, State as OperationState};
+    use libpulse_binding::volume::{ChannelVolumes, Volume, VolumeDB};
+
+    struct PulseInner {
+        mainloop: Mainloop,
+        context: Context,
+    }
+
+    /// The server's default source, followed as it changes.
+    const DEFAULT_SOURCE: &str = "@DEFAULT_SOURCE@";
+
+    /// How often [`MuteMonitor::refresh`] re-queries the source anyway, in
+    /// case a subscription event was lost.
+    const HEARTBEAT: Duration = Duration::from_secs(5);
+
+    /// What is known about the monitored source, kept current by the
+    /// subscription callback on PulseAudio's mainloop thread.
+    #[derive(Clone)]
+    struct SourceCache {
+        muted: Arc<AtomicBool>,
+        /// Server index of the source (`u32::MAX` until first seen), to
+        /// pick its events out of the subscription.
+        index: Arc<AtomicU32>,
+        device_name: Arc<Mutex<Option<String>>>,
+        /// Signaled when the mute state or the device changes.
+        signal: Arc<(Mutex<bool>, Condvar)>,
+    }
+
+    impl SourceCache {
+        fn new() -> Self {
+            SourceCache {
+                muted: Arc::new(AtomicBool::new(false)),
+                index: Arc::new(AtomicU32::new(u32::MAX)),
+                device_name: Arc::new(Mutex::new(None)),
+                signal: Arc::new((Mutex::new(false), Condvar::new())),
+            }
+        }
+
+        /// Ask the server for `source` and store the answer.
+        fn query(&self, introspect: &Introspector, source: &str) {
+            let cache = self.clone();
+            introspect.get_source_info_by_name(source, move |result| {
+                if let ListResult::Item(info) = result {
+                    cache.store(info.mute, info.index, info.description.as_deref());
+                }
+            });
+        }
+
+        /// Record the source's state, waking `wait_for_change` only if the
+        /// mute state or the device actually changed.
+        fn store(&self, muted: bool, index: u32, description: Option<&str>) {
+            let mut changed = self.muted.swap(muted, Ordering::SeqCst) != muted;
+            changed |= self.index.swap(index, Ordering::SeqCst) != index;
+            if let Some(desc) = description
+                && let Ok(mut name) = self.device_name.lock()
+                && name.as_deref() != Some(desc)
+            {
+                *name = Some(desc.to_string());
+                changed = true;
+            }
+            if changed && let Ok(mut flag) = self.signal.0.lock() {
+                *flag = true;
+                self.signal.1.notify_all();
+            }
+        }
+
+        /// Whether a subscription event can concern the monitored source.
+        fn is_relevant(
+            &self,
+            facility: Option<Facility>,
+            operation: Option<SubscribeOperation>,
+            index: u32,
+        ) -> bool {
+            match facility {
+                // The default source may have moved
+                Some(Facility::Server) => true,
+                // A new source may be the one we follow (replugged, or the
+                // new default); otherwise only ours matters
+                Some(Facility::Source) => {
+                    operation == Some(SubscribeOperation::New)
+                        || index == self.index.load(Ordering::SeqCst)
+                }
+                _ => false,
+            }
+        }
+    }
+
+    pub struct PulseAudioMonitor {
+        inner: Mutex<PulseInner>,
+        /// Source to monitor: [`DEFAULT_SOURCE`], or a source name pinned
+        /// because the default is excluded.
+        source: String,
+        cache: SourceCache,
+        /// When the source was last queried outside the event callback.
+        last_query: Mutex<Instant>,
+    }
+
+    // PulseAudio threaded mainloop is designed for concurrent access.
+    // The Mutex<PulseInner> ensures safe mutable access from &self methods.
+    unsafe impl Send for PulseAudioMonitor {}
+    unsafe impl Sync for PulseAudioMonitor {}
+
+    /// Wait for a PulseAudio operation to finish (bounded, so a stuck server
+    /// can't hang startup).
+    fn wait_done<T: ?Sized>(mainloop: &mut Mainloop, op: &Operation<T>) {
+        for _ in 0..100 {
+            mainloop.lock();
+            let state = op.get_state();
+            mainloop.unlock();
+            if state != OperationState::Running {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    /// The default source's name and `(name, description)` of every capture
+    /// source. Monitor sources (sink loopbacks) are skipped.
+    fn list_sources(
+        mainloop: &mut Mainloop,
+        context: &Context,
+    ) -> (Option<String>, Vec<(String, String)>) {
+        let default_name: Arc<Mutex<Option<String>>> = Arc::default();
+        // (name, description) of every capture source
+        let sources: Arc<Mutex<Vec<(String, String)>>> = Arc::default();
+
+        mainloop.lock();
+        let introspect = context.introspect();
+        let default_cb = Arc::clone(&default_name);
+        let server_op = introspect.get_server_info(move |info| {
+            if let Ok(mut d) = default_cb.lock() {
+                *d = info.default_source_name.as_ref().map(|n| n.to_string());
+            }
+        });
+        let sources_cb = Arc::clone(&sources);
+        let list_op = introspect.get_source_info_list(move |result| {
+            if let ListResult::Item(info) = result
+                && info.monitor_of_sink.is_none()
+                && let Some(ref name) = info.name
+                && let Ok(mut s) = sources_cb.lock()
+            {
+                let desc = info.description.as_deref().unwrap_or(name);
+                s.push((name.to_string(), desc.to_string()));
+            }
+        });
+        mainloop.unlock();
+        wait_done(mainloop, &server_op);
+        wait_done(mainloop, &list_op);
+
+        let default_name = default_name.lock().ok().and_then(|d| d.clone());
+        let sources = sources.lock().map(|s| s.clone()).unwrap_or_default();
+        (default_name, sources)
+    }
+
+    /// Name of a source to use instead of the default, if the default is on
+    /// the `excluded` list.
+    fn replacement_source(
+        mainloop: &mut Mainloop,
+        context: &Context,
+        excluded: &[String],
+    ) -> Option<String> {
+        let (default_name, sources) = list_sources(mainloop, context);
+        let default_name = default_name?;
+        let default_desc = sources
+            .iter()
+            .find(|(name, _)| *name == default_name)
+            .map(|(_, desc)| desc.as_str());
+        let descriptions: Vec<String> = sources.iter().map(|(_, d)| d.clone()).collect();
+        super::pick_capture_device(default_desc, &descriptions, excluded)
+            .map(|i| sources[i].0.clone())
+    }
+
+    /// Connect to the server and start a threaded mainloop.
+    fn connect() -> Result<(Mainloop, Context)> {
+        let mut mainloop = Mainloop::new()
+            .ok_or_else(|| AudioError::InitFailed("PulseAudio mainloop creation failed".into()))?;
+
+        let mut context = Context::new(&mainloop, "focusmute")
+            .ok_or_else(|| AudioError::InitFailed("PulseAudio context creation failed".into()))?;
+
+        context
+            .connect(None, ContextFlagSet::NOFLAGS, None)
+            .map_err(|e| AudioError::InitFailed(format!("PulseAudio connect: {e}")))?;
+
+        mainloop
+            .start()
+            .map_err(|e| AudioError::InitFailed(format!("PulseAudio mainloop start: {e}")))?;
+
+        // Wait for context to be ready
+        loop {
+            mainloop.lock();
+            let state = context.get_state();
+            mainloop.unlock();
+            match state {
+                ContextState::Ready => break,
+                ContextState::Failed | ContextState::Terminated => {
+                    return Err(AudioError::InitFailed(
+                        "PulseAudio context connection failed".into(),
+                    ));
+                }
+                _ => {
+                    std::thread::sleep(Duration::from_millis(10));
+                }
+            }
+        }
+
+        Ok((mainloop, context))
+    }
+
+    impl PulseAudioMonitor {
+        /// Create a new monitor for the default PulseAudio/PipeWire source.
+        ///
+        /// Subscribes to source and server events and keeps the cached mute
+        /// state current from them — each event for the monitored source
+        /// re-reads it on the mainloop thread and signals the condvar if
+        /// anything changed, so there is nothing to poll.
+        pub fn new() -> Result<Self> {
+            Self::with_exclusions(&[])
+        }
+
+        /// Like [`new`](Self::new), but if the default source is on the
+        /// `excluded` list (see [`is_excluded`](super::is_excluded)), monitor
+        /// another one instead — a Focusrite device if any.
+        pub fn with_exclusions(excluded: &[String]) -> Result<Self> {
+            let (mut mainloop, context) = connect()?;
+            let source = if excluded.is_empty() {
+                None
+            } else {
+                replacement_source(&mut mainloop, &context, excluded)
+            }
+            .unwrap_or_else(|| DEFAULT_SOURCE.to_string());
+            Self::on_source(mainloop, context, source)
+        }
+
+        /// Monitor the source called `name`.
+        pub fn with_source(name: &str) -> Result<Self> {
+            let (mainloop, context) = connect()?;
+            Self::on_source(mainloop, context, name.to_string())
+        }
+
+        /// One monitor per capture source not on the `excluded` list, the
+        /// one [`with_exclusions`](Self::with_exclusions) picks first.
+        pub fn all_sources(excluded: &[String]) -> Result<Vec<Self>> {
+            let primary = Self::with_exclusions(excluded)?;
+            let (default_name, sources) = {
+                let mut inner = primary.inner.lock().map_err(|e| {
+                    AudioError::InitFailed(format!("PulseAudio mutex poisoned: {e}"))
+                })?;
+                let PulseInner { mainloop, context } = &mut *inner;
+                list_sources(mainloop, context)
+            };
+            let primary_name = if primary.source == DEFAULT_SOURCE {
+                default_name
+            } else {
+                Some(primary.source.clone())
+            };
+            let mut monitors = vec![primary];
+            for (name, desc) in sources {
+                if primary_name.as_deref() == Some(name.as_str())
+                    || super::is_excluded(&desc, excluded)
+                {
+                    continue;
+                }
+                match Self::with_source(&name) {
+                    Ok(monitor) => monitors.push(monitor),
+                    Err(e) => log::warn!("[audio] can't monitor \"{desc}\": {e}"),
+                }
+            }
+            Ok(monitors)
+        }
+
+        /// Subscribe to `source` on a connected context.
+        fn on_source(mut mainloop: Mainloop, mut context: Context, source: String) -> Result<Self> {
+            let cache = SourceCache::new();
+            {
+                mainloop.lock();
+
+                // Runs on the mainloop thread for every source or server
+                // event; the introspector is moved in because the context
+                // can't be borrowed from its own callback.
+                let cache_cb = cache.clone();
+                let source_cb = source.clone();
+                let introspect_cb = context.introspect();
+                context.set_subscribe_callback(Some(Box::new(
+                    move |facility, operation, index| {
+                        if cache_cb.is_relevant(facility, operation, index) {
+                            cache_cb.query(&introspect_cb, &source_cb);
+                        }
+                    },
+                )));
+
+                context.subscribe(
+                    InterestMaskSet::SOURCE | InterestMaskSet::SERVER,
+                    |_success| {},
+                );
+
+                // Initial query for the source's mute state and name
+                cache.query(&context.introspect(), &source);
+
+                mainloop.unlock();
+            }
+
+            Ok(PulseAudioMonitor {
+                inner: Mutex::new(PulseInner { mainloop, context }),
+                source,
+                cache,
+                last_query: Mutex::new(Instant::now()),
+            })
+        }
+
+        /// Re-query the source now. Subscription events keep the cached
+        /// state current on their own; this is for settling after connect
+        /// and as a safety net.
+        pub fn refresh(&self) {
+            let Ok(mut inner) = self.inner.lock() else {
+                return;
+            };
+            inner.mainloop.lock();
+            self.cache.query(&inner.context.introspect(), &self.source);
+            inner.mainloop.unlock();
+            if let Ok(mut last) = self.last_query.lock() {
+                *last = Instant::now();
+            }
+        }
+
+        pub fn device_name(&self) -> Option<String> {
+            self.cache.device_name.lock().ok().and_then(|n| n.clone())
+        }
+    }
+
+    impl MuteMonitor for PulseAudioMonitor {
+        fn is_muted(&self) -> bool {
+            self.cache.muted.load(Ordering::SeqCst)
+        }
+
+        fn set_muted(&self, muted: bool) -> Result<()> {
+            let mut inner = self.inner.lock().map_err(|e| {
+                AudioError::OperationFailed(format!("PulseAudio mutex poisoned: {e}"))
+            })?;
+            inner.mainloop.lock();
+            let mut introspect = inner.context.introspect();
+            introspect.set_source_mute_by_name(&self.source, muted, None);
+            inner.mainloop.unlock();
+            // The event this causes then finds nothing changed
+            self.cache.muted.store(muted, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn wait_for_change(&self, timeout: Duration) -> bool {
+            super::wait_on_signal(&self.cache.signal, timeout)
+        }
+
+        /// Re-query only every [`HEARTBEAT`]; events do the rest.
+        fn refresh(&self) {
+            let due = self
+                .last_query
+                .lock()
+                .is_ok_and(|last| last.elapsed() >= HEARTBEAT);
+            if due {
+                PulseAudioMonitor::refresh(self);
+            }
+        }
+
+        /// In use while an uncorked source output records from the source.
+        fn in_use(&self) -> Option<bool> {
+            let index = self.cache.index.load(Ordering::SeqCst);
+            if index == u32::MAX {
+                return None;
+            }
+            let mut inner = self.inner.lock().ok()?;
+            let PulseInner { mainloop, context } = &mut *inner;
+            let recording = Arc::new(AtomicBool::new(false));
+            let recording_cb = Arc::clone(&recording);
+            mainloop.lock();
+            let op = context
+                .introspect()
+                .get_source_output_info_list(move |result| {
+                    if let ListResult::Item(info) = result
+                        && info.source == index
+                        && !info.corked
+                    {
+                        recording_cb.store(true, Ordering::SeqCst);
+                    }
+                });
+            mainloop.unlock();
+            wait_done(mainloop, &op);
+            Some(recording.load(Ordering::SeqCst))
+        }
+
+        /// Step the source's volume up to 100%, keeping the balance
+        /// between its channels.
+        fn adjust_capture_volume(&self, step_db: f64) -> Result<f64> {
+            let mut inner = self.inner.lock().map_err(|e| {
+                AudioError::OperationFailed(format!("PulseAudio mutex poisoned: {e}"))
+            })?;
+            let PulseInner { mainloop, context } = &mut *inner;
+            let volume: Arc<Mutex<Option<ChannelVolumes>>> = Arc::default();
+            let volume_cb = Arc::clone(&volume);
+            mainloop.lock();
+            let op = context
+                .introspect()
+                .get_source_info_by_name(&self.source, move |result| {
+                    if let ListResult::Item(info) = result
+                        && let Ok(mut v) = volume_cb.lock()
+                    {
+                        *v = Some(info.volume);
+                    }
+                });
+            mainloop.unlock();
+            wait_done(mainloop, &op);
+
+            let mut channels = volume.lock().ok().and_then(|v| *v).ok_or_else(|| {
+                AudioError::OperationFailed(format!("PulseAudio source {} not found", self.source))
+            })?;
+            let db = super::step_volume_db(
+                VolumeDB::from(channels.max()).0,
+                step_db,
+                super::MIN_CAPTURE_VOLUME_DB,
+                0.0,
+            );
+            channels.scale(Volume::from(VolumeDB(db)));
+            mainloop.lock();
+            let op = context
+                .introspect()
+                .set_source_volume_by_name(&self.source, &channels, None);
+            mainloop.unlock();
+            wait_done(mainloop, &op);
+            Ok(db)
+        }
+
+        /// `application.process.binary` of the uncorked source outputs
+        /// recording from the source.
+        fn capture_applications(&self) -> Option<Vec<String>> {
+            let index = self.cache.index.load(Ordering::SeqCst);
+            if index == u32::MAX {
+                return None;
+            }
+            let mut inner = self.inner.lock().ok()?;
+            let PulseInner { mainloop, context } = &mut *inner;
+            let apps: Arc<Mutex<Vec<String>>> = Arc::default();
+            let apps_cb = Arc::clone(&apps);
+            mainloop.lock();
+            let op = context
+                .introspect()
+                .get_source_output_info_list(move |result| {
+                    if let ListResult::Item(info) = result
+                        && info.source == index
+                        && !info.corked
+                        && let Some(binary) = info.proplist.get_str("application.process.binary")
+                        && let Ok(mut a) = apps_cb.lock()
+                    {
+                        a.push(binary);
+                    }
+                });
+            mainloop.unlock();
+            wait_done(mainloop, &op);
+            apps.lock().ok().map(|a| a.clone())
+        }
+
+        /// Set the source outputs recording from the source whose
+        /// `application.process.binary` is one of `apps`.
+        fn set_applications_muted(&self, apps: &[String], muted: bool) -> Result<usize> {
+            let index = self.cache.index.load(Ordering::SeqCst);
+            if index == u32::MAX {
+                return Err(AudioError::OperationFailed(format!(
+                    "PulseAudio source {} not found",
+                    self.source
+                )));
+            }
+            let mut inner = self.inner.lock().map_err(|e| {
+                AudioError::OperationFailed(format!("PulseAudio mutex poisoned: {e}"))
+            })?;
+            let PulseInner { mainloop, context } = &mut *inner;
+            let streams: Arc<Mutex<Vec<u32>>> = Arc::default();
+            let streams_cb = Arc::clone(&streams);
+            let apps = apps.to_vec();
+            mainloop.lock();
+            let op = context
+                .introspect()
+                .get_source_output_info_list(move |result| {
+                    if let ListResult::Item(info) = result
+                        && info.source == index
+                        && info
+                            .proplist
+                            .get_str("application.process.binary")
+                            .is_some_and(|binary| super::matches_application(&apps, &binary))
+                        && let Ok(mut s) = streams_cb.lock()
+                    {
+                        s.push(info.index);
+                    }
+                });
+            mainloop.unlock();
+            wait_done(mainloop, &op);
+
+            let streams = streams.lock().map(|s| s.clone()).unwrap_or_default();
+            mainloop.lock();
+            let mut introspect = context.introspect();
+            let ops: Vec<_> = streams
+                .iter()
+                .map(|&stream| introspect.set_source_output_mute(stream, muted, None))
+                .collect();
+            mainloop.unlock();
+            for op in &ops {
+                wait_done(mainloop, op);
+            }
+            Ok(streams.len())
+        }
+    }
+
+    impl Drop for PulseAudioMonitor {
+        fn drop(&mut self) {
+            if let Ok(mut inner) = self.inner.lock() {
+                inner.mainloop.lock();
+                inner.context.disconnect();
+                inner.mainloop.unlock();
+                inner.mainloop.stop();
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn signaled(cache: &SourceCache) -> bool {
+            super::super::wait_on_signal(&cache.signal, Duration::ZERO)
+        }
+
+        #[test]
+        fn store_signals_only_on_change() {
+            let cache = SourceCache::new();
+            cache.store(false, 3, Some("Scarlett 2i2"));
+            assert!(signaled(&cache));
+            cache.store(false, 3, Some("Scarlett 2i2"));
+            assert!(!signaled(&cache));
+            cache.store(true, 3, Some("Scarlett 2i2"));
+            assert!(signaled(&cache));
+            assert!(cache.muted.load(Ordering::SeqCst));
+        }
+
+        #[test]
+        fn picks_out_events_for_the_source() {
+            let cache = SourceCache::new();
+            cache.store(false, 3, None);
+            let changed = Some(SubscribeOperation::Changed);
+            assert!(cache.is_relevant(Some(Facility::Source), changed, 3));
+            assert!(!cache.is_relevant(Some(Facility::Source), changed, 4));
+            assert!(cache.is_relevant(Some(Facility::Source), Some(SubscribeOperation::New), 4));
+            assert!(cache.is_relevant(Some(Facility::Server), changed, 0));
+            assert!(!cache.is_relevant(Some(Facility::Sink), changed, 3));
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub use pulse::PulseAudioMonitor;
+
+/// Stabilize a newly-created PulseAudio monitor.
+///
+/// PulseAudio needs a brief delay after connection before the cached
+/// mute state is reliable. This is a known PulseAudio quirk — the
+/// initial state query may return stale data without this stabilization.
+#[cfg(target_os = "linux")]
+pub fn stabilize_pulseaudio(monitor: &PulseAudioMonitor) {
+    std::thread::sleep(std::time::Duration::from_millis(50));
+    monitor.refresh();
+    std::thread::sleep(std::time::Duration::from_millis(50));
+}
+
+// ── Linux PipeWire implementation ──
+
+#[cfg(all(target_os = "linux", feature = "pipewire"))]
+mod pipewire_native {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+    use std::collections::HashMap;
+    use std::io::Cursor;
+    use std::rc::Rc;
+    use std::sync::{Arc, Condvar, Mutex, mpsc};
+    use std::thread::JoinHandle;
+    use std::time::Duration;
+
+    use pipewire as pw;
+    use pw::metadata::{Metadata, MetadataListener};
+    use pw::node::{Node, NodeListener};
+    use pw::spa::param::ParamType;
+    use pw::spa::pod::deserialize::PodDeserializer;
+    use pw::spa::pod::serialize::PodSerializer;
+    use pw::spa::pod::{Object, Pod, Property, Value};
+    use pw::spa::utils::SpaTypes;
+    use pw::types::ObjectType;
+
+    /// Metadata key naming the default capture node, as `{"name": "<node.name>"}`.
+    const DEFAULT_SOURCE_KEY: &str = "default.audio.source";
+
+    /// How long to wait for the PipeWire daemon to answer before giving up
+    /// (and, under `auto`, falling back to PulseAudio).
+    const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+    /// A capture node as last reported by PipeWire.
+    #[derive(Debug, Clone)]
+    struct SourceNode {
+        /// `node.name` — what the default-source metadata refers to.
+        name: String,
+        /// `node.description`, falling back to the name.
+        description: String,
+        muted: bool,
+    }
+
+    /// What the PipeWire thread has seen of the graph.
+    #[derive(Debug, Default)]
+    struct Graph {
+        sources: HashMap<u32, SourceNode>,
+        default_source: Option<String>,
+        excluded: Vec<String>,
+        /// `node.name` to monitor regardless of the default, if any.
+        pinned: Option<String>,
+        /// Node being monitored (see [`retarget`](Self::retarget)).
+        target: Option<u32>,
+        /// `(output, input)` nodes of every link, by link ID — a source
+        /// links to each capture stream recording from it.
+        links: HashMap<u32, (u32, u32)>,
+        /// `application.process.binary` of every capture stream, by node ID.
+        streams: HashMap<u32, String>,
+    }
+
+    impl Graph {
+        /// Pick the node to monitor: the default source, or another one if
+        /// the default is on the `excluded` list. Call when the sources or
+        /// the default change.
+        fn retarget(&mut self) {
+            if let Some(pinned) = &self.pinned {
+                self.target = self
+                    .sources
+                    .iter()
+                    .find(|(_, s)| s.name == *pinned)
+                    .map(|(&id, _)| id);
+                return;
+            }
+            let mut ids: Vec<u32> = self.sources.keys().copied().collect();
+            ids.sort_unstable();
+            let default = self
+                .default_source
+                .as_deref()
+                .and_then(|name| ids.iter().position(|id| self.sources[id].name == name));
+            let descriptions: Vec<String> = ids
+                .iter()
+                .map(|id| self.sources[id].description.clone())
+                .collect();
+            let default_desc = default.map(|i| descriptions[i].as_str());
+            self.target = super::pick_capture_device(default_desc, &descriptions, &self.excluded)
+                .or(default)
+                .map(|i| ids[i]);
+        }
+
+        fn target(&self) -> Option<&SourceNode> {
+            self.target.and_then(|id| self.sources.get(&id))
+        }
+
+        /// Whether anything is linked to the monitored node's output.
+        fn in_use(&self) -> bool {
+            self.target
+                .is_some_and(|target| self.links.values().any(|&(output, _)| output == target))
+        }
+
+        /// Binaries of the capture streams recording from the monitored node.
+        fn capture_applications(&self) -> Vec<String> {
+            let Some(target) = self.target else {
+                return Vec::new();
+            };
+            self.links
+                .values()
+                .filter(|&&(output, _)| output == target)
+                .filter_map(|(_, input)| self.streams.get(input).cloned())
+                .collect()
+        }
+
+        /// Capture streams of `apps` recording from the monitored node.
+        fn application_streams(&self, apps: &[String]) -> Vec<u32> {
+            let Some(target) = self.target else {
+                return Vec::new();
+            };
+            let mut ids: Vec<u32> = self
+                .links
+                .values()
+                .filter(|&&(output, _)| output == target)
+                .filter_map(|&(_, input)| {
+                    let binary = self.streams.get(&input)?;
+                    super::matches_application(apps, binary).then_some(input)
+                })
+                .collect();
+            ids.sort_unstable();
+            ids.dedup();
+            ids
+        }
+    }
+
+    /// Requests from the monitor to the PipeWire thread.
+    enum Command {
+        SetMute { node: u32, muted: bool },
+        Quit,
+    }
+
+    /// The `mute` flag of a `Props` param, if it carries one.
+    fn props_mute(pod: &Pod) -> Option<bool> {
+        let (_, value) = PodDeserializer::deserialize_any_from(pod.as_bytes()).ok()?;
+        let Value::Object(Object { properties, .. }) = value else {
+            return None;
+        };
+        properties
+            .into_iter()
+            .find(|p| p.key == pw::spa::sys::SPA_PROP_mute)
+            .and_then(|p| match p.value {
+                Value::Bool(muted) => Some(muted),
+                _ => None,
+            })
+    }
+
+    /// A serialized `Props` param that sets `mute`.
+    fn mute_props(muted: bool) -> Option<Vec<u8>> {
+        let props = Value::Object(Object {
+            type_: SpaTypes::ObjectParamProps.as_raw(),
+            id: ParamType::Props.as_raw(),
+            properties: vec![Property::new(
+                pw::spa::sys::SPA_PROP_mute,
+                Value::Bool(muted),
+            )],
+        });
+        PodSerializer::serialize(Cursor::new(Vec::new()), &props)
+            .ok()
+            .map(|(out, _)| out.into_inner())
+    }
+
+    /// The node name in a `default.audio.source` metadata value.
+    fn default_source_name(value: &str) -> Option<String> {
+        let json: serde_json::Value = serde_json::from_str(value).ok()?;
+        json.get("name")?.as_str().map(str::to_string)
+    }
+
+    fn notify(signal: &(Mutex<bool>, Condvar)) {
+        if let Ok(mut changed) = signal.0.lock() {
+            *changed = true;
+            signal.1.notify_all();
+        }
+    }
+
+    /// Body of the PipeWire thread: track capture nodes and the default
+    /// source until told to quit. Reports on `started` once the first
+    /// round-trips are done, so the initial mute state is known.
+    fn run(
+        graph: Arc<Mutex<Graph>>,
+        signal: Arc<(Mutex<bool>, Condvar)>,
+        commands: pw::channel::Receiver<Command>,
+        started: mpsc::Sender<Result<()>>,
+    ) {
+        pw::init();
+        let connect = || -> std::result::Result<_, pw::Error> {
+            let main_loop = pw::main_loop::MainLoop::new(None)?;
+            let context = pw::context::Context::new(&main_loop)?;
+            let core = context.connect(None)?;
+            let registry = Rc::new(core.get_registry()?);
+            Ok((main_loop, context, core, registry))
+        };
+        let (main_loop, _context, core, registry) = match connect() {
+            Ok(parts) => parts,
+            Err(e) => {
+                let _ = started.send(Err(AudioError::InitFailed(format!(
+                    "PipeWire connect: {e}"
+                ))));
+                return;
+            }
+        };
+
+        // Proxies and their listeners have to stay alive to keep reporting
+        let nodes: Rc<RefCell<HashMap<u32, (Node, NodeListener)>>> = Rc::default();
+        let metadata: Rc<RefCell<Option<(Metadata, MetadataListener)>>> = Rc::default();
+        let streams: Rc<RefCell<HashMap<u32, Node>>> = Rc::default();
+
+        let registry_weak = Rc::downgrade(&registry);
+        let nodes_add = Rc::clone(&nodes);
+        let graph_add = Arc::clone(&graph);
+        let signal_add = Arc::clone(&signal);
+        let streams_add = Rc::clone(&streams);
+        let streams_remove = Rc::clone(&streams);
+        let nodes_remove = Rc::clone(&nodes);
+        let graph_remove = Arc::clone(&graph);
+        let signal_remove = Arc::clone(&signal);
+        let _registry_listener = registry
+            .add_listener_local()
+            .global(move |global| {
+                let Some(registry) = registry_weak.upgrade() else {
+                    return;
+                };
+                let Some(props) = global.props else {
+                    return;
+                };
+                match global.type_ {
+                    ObjectType::Node if props.get("media.class") == Some("Stream/Input/Audio") => {
+                        // Capture streams, for per-application mute
+                        let Ok(node) = registry.bind::<Node, _>(global) else {
+                            return;
+                        };
+                        let binary = props
+                            .get("application.process.binary")
+                            .unwrap_or_default()
+                            .to_string();
+                        if let Ok(mut g) = graph_add.lock() {
+                            g.streams.insert(global.id, binary);
+                        }
+                        streams_add.borrow_mut().insert(global.id, node);
+                    }
+                    ObjectType::Node => {
+                        // Capture devices only — sink monitors are Audio/Sink
+                        if !props
+                            .get("media.class")
+                            .is_some_and(|c| c.starts_with("Audio/Source"))
+                        {
+                            return;
+                        }
+                        let Ok(node) = registry.bind::<Node, _>(global) else {
+                            return;
+                        };
+                        let id = global.id;
+                        let name = props.get("node.name").unwrap_or_default().to_string();
+                        let description = props
+                            .get("node.description")
+                            .map_or_else(|| name.clone(), str::to_string);
+                        if let Ok(mut g) = graph_add.lock() {
+                            g.sources.insert(
+                                id,
+                                SourceNode {
+                                    name,
+                                    description,
+                                    muted: false,
+                                },
+                            );
+                            g.retarget();
+                        }
+                        let graph_cb = Arc::clone(&graph_add);
+                        let signal_cb = Arc::clone(&signal_add);
+                        let listener = node
+                            .add_listener_local()
+                            .param(move |_seq, kind, _index, _next, param| {
+                                if kind != ParamType::Props {
+                                    return;
+                                }
+                                let Some(muted) = param.and_then(props_mute) else {
+                                    return;
+                                };
+                                if let Ok(mut g) = graph_cb.lock()
+                                    && let Some(source) = g.sources.get_mut(&id)
+                                {
+                                    source.muted = muted;
+                                }
+                                notify(&signal_cb);
+                            })
+                            .register();
+                        // Push-style: PipeWire sends Props again on every change
+                        node.subscribe_params(&[ParamType::Props]);
+                        node.enum_params(0, Some(ParamType::Props), 0, u32::MAX);
+                        nodes_add.borrow_mut().insert(id, (node, listener));
+                    }
+                    ObjectType::Link => {
+                        let node = |key: &str| props.get(key).and_then(|n| n.parse::<u32>().ok());
+                        if let Some(output) = node("link.output.node")
+                            && let Some(input) = node("link.input.node")
+                            && let Ok(mut g) = graph_add.lock()
+                        {
+                            g.links.insert(global.id, (output, input));
+                        }
+                    }
+                    ObjectType::Metadata if props.get("metadata.name") == Some("default") => {
+                        let Ok(proxy) = registry.bind::<Metadata, _>(global) else {
+                            return;
+                        };
+                        let graph_cb = Arc::clone(&graph_add);
+                        let signal_cb = Arc::clone(&signal_add);
+                        let listener = proxy
+                            .add_listener_local()
+                            .property(move |_subject, key, _type, value| {
+                                // A `None` key clears every property
+                                if key.is_none_or(|k| k == DEFAULT_SOURCE_KEY) {
+                                    if let Ok(mut g) = graph_cb.lock() {
+                                        g.default_source = value.and_then(default_source_name);
+                                        g.retarget();
+                                    }
+                                    notify(&signal_cb);
+                                }
+                                0
+                            })
+                            .register();
+                        *metadata.borrow_mut() = Some((proxy, listener));
+                    }
+                    _ => {}
+                }
+            })
+            .global_remove(move |id| {
+                if let Ok(mut g) = graph_remove.lock() {
+                    g.links.remove(&id);
+                    g.streams.remove(&id);
+                }
+                streams_remove.borrow_mut().remove(&id);
+                if nodes_remove.borrow_mut().remove(&id).is_some() {
+                    if let Ok(mut g) = graph_remove.lock() {
+                        g.sources.remove(&id);
+                        g.retarget();
+                    }
+                    notify(&signal_remove);
+                }
+            })
+            .register();
+
+        // Two round-trips: the first lists the globals, the second covers
+        // the binds and param queries they triggered.
+        let pending = Rc::new(Cell::new(core.sync(0).ok()));
+        let rounds = Rc::new(Cell::new(0u8));
+        let core_cb = core.clone();
+        let started_cb = started.clone();
+        let _core_listener = core
+            .add_listener_local()
+            .done(move |id, seq| {
+                if id != pw::core::PW_ID_CORE || pending.get() != Some(seq) {
+                    return;
+                }
+                rounds.set(rounds.get() + 1);
+                if rounds.get() < 2 {
+                    pending.set(core_cb.sync(0).ok());
+                } else {
+                    pending.set(None);
+                    let _ = started_cb.send(Ok(()));
+                }
+            })
+            .register();
+
+        let main_loop_weak = main_loop.downgrade();
+        let nodes_cmd = Rc::clone(&nodes);
+        let streams_cmd = Rc::clone(&streams);
+        let _commands = commands.attach(main_loop.loop_(), move |command| match command {
+            Command::SetMute { node, muted } => {
+                let nodes = nodes_cmd.borrow();
+                let streams = streams_cmd.borrow();
+                let proxy = nodes
+                    .get(&node)
+                    .map(|(proxy, _)| proxy)
+                    .or_else(|| streams.get(&node));
+                if let Some(proxy) = proxy
+                    && let Some(bytes) = mute_props(muted)
+                    && let Some(pod) = Pod::from_bytes(&bytes)
+                {
+                    proxy.set_param(ParamType::Props, 0, pod);
+                }
+            }
+            Command::Quit => {
+                if let Some(main_loop) = main_loop_weak.upgrade() {
+                    main_loop.quit();
+                }
+            }
+        });
+
+        main_loop.run();
+    }
+
+    /// Mute monitor that talks to PipeWire directly.
+    ///
+    /// A dedicated thread runs the PipeWire main loop, tracking every
+    /// capture node's `Props` and the `default.audio.source` metadata as
+    /// PipeWire pushes them, so mute changes arrive without polling.
+    pub struct PipeWireMonitor {
+        graph: Arc<Mutex<Graph>>,
+        /// Signaled when a capture node or the default source changes.
+        signal: Arc<(Mutex<bool>, Condvar)>,
+        commands: pw::channel::Sender<Command>,
+        thread: Option<JoinHandle<()>>,
+    }
+
+    impl PipeWireMonitor {
+        /// Connect to the PipeWire daemon and monitor the default source.
+        pub fn new() -> Result<Self> {
+            Self::with_exclusions(&[])
+        }
+
+        /// Like [`new`](Self::new), but if the default source is on the
+        /// `excluded` list (see [`is_excluded`](super::is_excluded)), monitor
+        /// another one instead — a Focusrite device if any. Unlike
+        /// PulseAudio, the choice follows the default as it changes.
+        pub fn with_exclusions(excluded: &[String]) -> Result<Self> {
+            Self::start(Graph {
+                excluded: excluded.to_vec(),
+                ..Graph::default()
+            })
+        }
+
+        /// Monitor the node called `name` (`node.name`).
+        pub fn with_source(name: &str) -> Result<Self> {
+            Self::start(Graph {
+                pinned: Some(name.to_string()),
+                ..Graph::default()
+            })
+        }
 
-                // Register COM callback for mute/volume change events
-                let callback: IAudioEndpointVolumeCallback = MuteCallback {
-                    muted: Arc::clone(&muted),
-                    signal: Arc::clone(&signal),
+        /// One monitor per capture node not on the `excluded` list, the one
+        /// [`with_exclusions`](Self::with_exclusions) picks first.
+        pub fn all_sources(excluded: &[String]) -> Result<Vec<Self>> {
+            let primary = Self::with_exclusions(excluded)?;
+            let others: Vec<(String, String)> = {
+                let graph = primary
+                    .graph
+                    .lock()
+                    .map_err(|e| AudioError::InitFailed(format!("PipeWire mutex poisoned: {e}")))?;
+                let mut others: Vec<_> = graph
+                    .sources
+                    .iter()
+                    .filter(|&(&id, s)| {
+                        graph.target != Some(id) && !super::is_excluded(&s.description, excluded)
+                    })
+                    .map(|(_, s)| (s.name.clone(), s.description.clone()))
+                    .collect();
+                others.sort();
+                others
+            };
+            let mut monitors = vec![primary];
+            for (name, desc) in others {
+                match Self::with_source(&name) {
+                    Ok(monitor) => monitors.push(monitor),
+                    Err(e) => log::warn!("[audio] can't monitor \"{desc}\": {e}"),
                 }
-                .into();
-                volume.RegisterControlChangeNotify(&callback).map_err(|e| {
-                    AudioError::InitFailed(format!("RegisterControlChangeNotify: {e}"))
-                })?;
+            }
+            Ok(monitors)
+        }
 
-                Ok(WasapiMonitor {
-                    volume,
-                    device_name,
-                    muted,
-                    signal,
-                    _callback: callback,
-                })
+        /// Run the PipeWire thread for `graph` and wait for its first report.
+        fn start(graph: Graph) -> Result<Self> {
+            let graph = Arc::new(Mutex::new(graph));
+            let signal = Arc::new((Mutex::new(false), Condvar::new()));
+            let (commands, receiver) = pw::channel::channel();
+            let (started_tx, started_rx) = mpsc::channel();
+            let thread = {
+                let graph = Arc::clone(&graph);
+                let signal = Arc::clone(&signal);
+                std::thread::Builder::new()
+                    .name("pipewire".into())
+                    .spawn(move || run(graph, signal, receiver, started_tx))
+                    .map_err(|e| AudioError::InitFailed(format!("PipeWire thread: {e}")))?
+            };
+            let monitor = PipeWireMonitor {
+                graph,
+                signal,
+                commands,
+                thread: Some(thread),
+            };
+            // Dropping `monitor` on error stops the thread
+            match started_rx.recv_timeout(CONNECT_TIMEOUT) {
+                Ok(Ok(())) => Ok(monitor),
+                Ok(Err(e)) => Err(e),
+                Err(_) => Err(AudioError::InitFailed(
+                    "PipeWire daemon did not answer".into(),
+                )),
             }
         }
-    }
 
-    impl WasapiMonitor {
-        pub fn device_name(&self) -> Option<&str> {
-            self.device_name.as_deref()
+        pub fn device_name(&self) -> Option<String> {
+            let graph = self.graph.lock().ok()?;
+            graph.target().map(|source| source.description.clone())
         }
     }
 
-    impl MuteMonitor for WasapiMonitor {
+    impl MuteMonitor for PipeWireMonitor {
         fn is_muted(&self) -> bool {
-            self.muted.load(Ordering::SeqCst)
+            self.graph
+                .lock()
+                .ok()
+                .and_then(|g| g.target().map(|source| source.muted))
+                .unwrap_or(false)
         }
 
         fn set_muted(&self, muted: bool) -> Result<()> {
-            unsafe {
-                self.volume
-                    .SetMute(muted, std::ptr::null())
-                    .map_err(|e| AudioError::OperationFailed(format!("SetMute: {e}")))
+            let mut graph = self.graph.lock().map_err(|e| {
+                AudioError::OperationFailed(format!("PipeWire mutex poisoned: {e}"))
+            })?;
+            let node = graph
+                .target
+                .ok_or_else(|| AudioError::OperationFailed("no PipeWire capture node".into()))?;
+            self.commands
+                .send(Command::SetMute { node, muted })
+                .map_err(|_| AudioError::OperationFailed("PipeWire thread stopped".into()))?;
+            if let Some(source) = graph.sources.get_mut(&node) {
+                source.muted = muted;
             }
+            Ok(())
         }
 
         fn wait_for_change(&self, timeout: Duration) -> bool {
             super::wait_on_signal(&self.signal, timeout)
         }
-    }
 
-    impl Drop for WasapiMonitor {
-        fn drop(&mut self) {
-            unsafe {
-                let _ = self.volume.UnregisterControlChangeNotify(&self._callback);
+        fn in_use(&self) -> Option<bool> {
+            Some(self.graph.lock().ok()?.in_use())
+        }
+
+        fn capture_applications(&self) -> Option<Vec<String>> {
+            Some(self.graph.lock().ok()?.capture_applications())
+        }
+
+        fn set_applications_muted(&self, apps: &[String], muted: bool) -> Result<usize> {
+            let streams = self
+                .graph
+                .lock()
+                .map_err(|e| AudioError::OperationFailed(format!("PipeWire mutex poisoned: {e}")))?
+                .application_streams(apps);
+            for &node in &streams {
+                self.commands
+                    .send(Command::SetMute { node, muted })
+                    .map_err(|_| AudioError::OperationFailed("PipeWire thread stopped".into()))?;
             }
+            Ok(streams.len())
         }
     }
 
-    /// Initialize COM for the current thread (apartment-threaded).
-    pub fn com_init() -> Result<()> {
-        unsafe {
-            CoInitializeEx(None, COINIT_APARTMENTTHREADED)
-                .ok()
-                .map_err(|e| AudioError::InitFailed(format!("CoInitializeEx: {e}")))
+    impl Drop for PipeWireMonitor {
+        fn drop(&mut self) {
+            let _ = self.commands.send(Command::Quit);
+            if let Some(thread) = self.thread.take() {
+                let _ = thread.join();
+            }
         }
     }
-}
 
-#[cfg(windows)]
-pub use wasapi::{WasapiMonitor, com_init};
+    #[cfg(test)]
+    mod tests {
+        use super::*;
 
-// ── Linux PulseAudio implementation ──
+        #[test]
+        fn mute_props_round_trip() {
+            for muted in [true, false] {
+                let bytes = mute_props(muted).unwrap();
+                assert_eq!(props_mute(Pod::from_bytes(&bytes).unwrap()), Some(muted));
+            }
+        }
 
-#[cfg(target_os = "linux")]
-mod pulse {
-    use super::*;
-    use std::sync::atomic::{AtomicBool, Ordering};
-    use std::sync::{Arc, Condvar, Mutex};
-    use std::time::Duration;
+        #[test]
+        fn parses_default_source_metadata() {
+            assert_eq!(
+                default_source_name(r#"{"name":"alsa_input.usb-Focusrite"}"#),
+                Some("alsa_input.usb-Focusrite".into())
+            );
+            assert_eq!(default_source_name("not json"), None);
+        }
 
-    use libpulse_binding::callbacks::ListResult;
-    use libpulse_binding::context::subscribe::InterestMaskSet;
-    use libpulse_binding::context::{Context, FlagSet as ContextFlagSet, State as ContextState};
-    use libpulse_binding::mainloop::threaded::Mainloop;
+        #[test]
+        fn target_follows_default_and_exclusions() {
+            let source = |name: &str, description: &str| SourceNode {
+                name: name.into(),
+                description: description.into(),
+                muted: false,
+            };
+            let mut graph = Graph {
+                sources: HashMap::from([
+                    (40, source("cable", "CABLE Output")),
+                    (41, source("scarlett", "Scarlett 2i2 4th Gen")),
+                ]),
+                default_source: Some("cable".into()),
+                ..Graph::default()
+            };
+            graph.retarget();
+            assert_eq!(graph.target().unwrap().name, "cable");
+            graph.excluded = vec!["cable".into()];
+            graph.retarget();
+            assert_eq!(graph.target().unwrap().name, "scarlett");
+            // A pinned node ignores the default
+            graph.pinned = Some("scarlett".into());
+            graph.default_source = None;
+            graph.retarget();
+            assert_eq!(graph.target().unwrap().name, "scarlett");
+            graph.pinned = None;
+            graph.default_source = Some("cable".into());
+            // Nothing else to monitor: keep the default
+            graph.sources.remove(&41);
+            graph.retarget();
+            assert_eq!(graph.target().unwrap().name, "cable");
+        }
 
-    struct PulseInner {
-        mainloop: Mainloop,
-        context: Context,
-    }
+        #[test]
+        fn in_use_when_linked_to_the_target() {
+            let mut graph = Graph {
+                target: Some(40),
+                ..Graph::default()
+            };
+            assert!(!graph.in_use());
+            graph.links.insert(90, (41, 60));
+            assert!(!graph.in_use());
+            graph.links.insert(91, (40, 61));
+            assert!(graph.in_use());
+        }
 
-    pub struct PulseAudioMonitor {
-        inner: Mutex<PulseInner>,
-        muted: Arc<AtomicBool>,
-        device_name: Arc<Mutex<Option<String>>>,
-        /// Signaled when PulseAudio delivers a source change event.
-        signal: Arc<(Mutex<bool>, Condvar)>,
+        #[test]
+        fn application_streams_recording_from_the_target() {
+            let mut graph = Graph {
+                target: Some(40),
+                ..Graph::default()
+            };
+            graph.streams.insert(60, "Discord".into());
+            graph.streams.insert(61, "/usr/bin/obs".into());
+            graph.streams.insert(62, "discord".into());
+            graph.links.insert(90, (40, 60));
+            graph.links.insert(91, (40, 61));
+            // Recording from another source
+            graph.links.insert(92, (41, 62));
+            assert_eq!(graph.application_streams(&["discord".into()]), vec![60]);
+            let mut capturing = graph.capture_applications();
+            capturing.sort();
+            assert_eq!(capturing, vec!["/usr/bin/obs", "Discord"]);
+            assert_eq!(
+                graph.application_streams(&["obs".into(), "Discord".into()]),
+                vec![60, 61]
+            );
+        }
     }
+}
 
-    // PulseAudio threaded mainloop is designed for concurrent access.
-    // The Mutex<PulseInner> ensures safe mutable access from &self methods.
-    unsafe impl Send for PulseAudioMonitor {}
-    unsafe impl Sync for PulseAudioMonitor {}
-
-    impl PulseAudioMonitor {
-        /// Create a new monitor for the default PulseAudio/PipeWire source.
-        ///
-        /// Subscribes to source events and maintains a cached mute state.
-        /// Source change events signal the condvar for event-driven wakeup.
-        pub fn new() -> Result<Self> {
-            let mut mainloop = Mainloop::new().ok_or_else(|| {
-                AudioError::InitFailed("PulseAudio mainloop creation failed".into())
-            })?;
-
-            let mut context = Context::new(&mainloop, "focusmute").ok_or_else(|| {
-                AudioError::InitFailed("PulseAudio context creation failed".into())
-            })?;
-
-            context
-                .connect(None, ContextFlagSet::NOFLAGS, None)
-                .map_err(|e| AudioError::InitFailed(format!("PulseAudio connect: {e}")))?;
+#[cfg(all(target_os = "linux", feature = "pipewire"))]
+pub use pipewire_native::PipeWireMonitor;
 
-            mainloop
-                .start()
-                .map_err(|e| AudioError::InitFailed(format!("PulseAudio mainloop start: {e}")))?;
+/// The Linux mute monitor, on the backend [`AudioBackend`] picks.
+#[cfg(target_os = "linux")]
+pub enum LinuxMonitor {
+    PulseAudio(PulseAudioMonitor),
+    #[cfg(feature = "pipewire")]
+    PipeWire(PipeWireMonitor),
+}
 
-            // Wait for context to be ready
-            loop {
-                mainloop.lock();
-                let state = context.get_state();
-                mainloop.unlock();
-                match state {
-                    ContextState::Ready => break,
-                    ContextState::Failed | ContextState::Terminated => {
-                        return Err(AudioError::InitFailed(
-                            "PulseAudio context connection failed".into(),
-                        ));
-                    }
-                    _ => {
-                        std::thread::sleep(Duration::from_millis(10));
-                    }
+#[cfg(target_os = "linux")]
+impl LinuxMonitor {
+    /// Open a monitor on `backend`, skipping `excluded` capture devices.
+    /// `Auto` tries PipeWire first and falls back to PulseAudio.
+    pub fn open(backend: AudioBackend, excluded: &[String]) -> Result<Self> {
+        match backend {
+            AudioBackend::PulseAudio => Ok(LinuxMonitor::PulseAudio(
+                PulseAudioMonitor::with_exclusions(excluded)?,
+            )),
+            #[cfg(feature = "pipewire")]
+            AudioBackend::PipeWire => Ok(LinuxMonitor::PipeWire(PipeWireMonitor::with_exclusions(
+                excluded,
+            )?)),
+            #[cfg(not(feature = "pipewire"))]
+            AudioBackend::PipeWire => Err(AudioError::InitFailed(
+                "PipeWire support is not built in (enable the `pipewire` feature)".into(),
+            )),
+            AudioBackend::Auto => {
+                #[cfg(feature = "pipewire")]
+                match PipeWireMonitor::with_exclusions(excluded) {
+                    Ok(monitor) => return Ok(LinuxMonitor::PipeWire(monitor)),
+                    Err(e) => log::info!("[audio] {e} — using PulseAudio"),
                 }
+                Self::open(AudioBackend::PulseAudio, excluded)
             }
-
-            let muted = Arc::new(AtomicBool::new(false));
-            let device_name: Arc<Mutex<Option<String>>> = Arc::new(Mutex::new(None));
-            let signal = Arc::new((Mutex::new(false), Condvar::new()));
-
-            // Subscribe to source events with callback that signals change
-            {
-                mainloop.lock();
-
-                // Set subscribe callback — fires when any subscribed source event arrives.
-                // Signals the condvar so the monitor loop wakes up immediately.
-                let signal_cb = Arc::clone(&signal);
-                context.set_subscribe_callback(Some(Box::new(
-                    move |_facility, _operation, _index| {
-                        if let Ok(mut changed) = signal_cb.0.lock() {
-                            *changed = true;
-                            signal_cb.1.notify_all();
-                        }
-                    },
-                )));
-
-                context.subscribe(InterestMaskSet::SOURCE, |_success| {});
-
-                // Initial query for default source mute state and name
-                let muted_init = Arc::clone(&muted);
-                let name_init = Arc::clone(&device_name);
-                let introspect = context.introspect();
-                introspect.get_source_info_by_name("@DEFAULT_SOURCE@", move |result| {
-                    if let ListResult::Item(info) = result {
-                        muted_init.store(info.mute, Ordering::SeqCst);
-                        if let Some(ref desc) = info.description
-                            && let Ok(mut n) = name_init.lock()
-                        {
-                            *n = Some(desc.to_string());
-                        }
-                    }
-                });
-
-                mainloop.unlock();
-            }
-
-            Ok(PulseAudioMonitor {
-                inner: Mutex::new(PulseInner { mainloop, context }),
-                muted,
-                device_name,
-                signal,
-            })
         }
+    }
 
-        /// Refresh the cached mute state by querying PulseAudio.
-        ///
-        /// Call this after `wait_for_change` returns or periodically as a heartbeat.
-        pub fn refresh(&self) {
-            let Ok(mut inner) = self.inner.lock() else {
-                return;
-            };
-            let muted_clone = Arc::clone(&self.muted);
-            let name_clone = Arc::clone(&self.device_name);
-            inner.mainloop.lock();
-            let introspect = inner.context.introspect();
-            introspect.get_source_info_by_name("@DEFAULT_SOURCE@", move |result| {
-                if let ListResult::Item(info) = result {
-                    muted_clone.store(info.mute, Ordering::SeqCst);
-                    if let Some(ref desc) = info.description
-                        && let Ok(mut n) = name_clone.lock()
-                    {
-                        *n = Some(desc.to_string());
-                    }
+    /// One monitor per capture device not on the `excluded` list, the one
+    /// [`open`](Self::open) would pick first.
+    pub fn open_all(backend: AudioBackend, excluded: &[String]) -> Result<Vec<Self>> {
+        match backend {
+            AudioBackend::PulseAudio => Ok(PulseAudioMonitor::all_sources(excluded)?
+                .into_iter()
+                .map(LinuxMonitor::PulseAudio)
+                .collect()),
+            #[cfg(feature = "pipewire")]
+            AudioBackend::PipeWire => Ok(PipeWireMonitor::all_sources(excluded)?
+                .into_iter()
+                .map(LinuxMonitor::PipeWire)
+                .collect()),
+            #[cfg(not(feature = "pipewire"))]
+            AudioBackend::PipeWire => Self::open(backend, excluded).map(|m| vec![m]),
+            AudioBackend::Auto => {
+                #[cfg(feature = "pipewire")]
+                match Self::open_all(AudioBackend::PipeWire, excluded) {
+                    Ok(monitors) => return Ok(monitors),
+                    Err(e) => log::info!("[audio] {e} — using PulseAudio"),
                 }
-            });
-            inner.mainloop.unlock();
+                Self::open_all(AudioBackend::PulseAudio, excluded)
+            }
         }
+    }
 
-        pub fn device_name(&self) -> Option<String> {
-            self.device_name.lock().ok().and_then(|n| n.clone())
+    /// The monitor the mute toggle drives: every capture device when `all`
+    /// is set (`mute_all_capture_devices`), otherwise just the one
+    /// [`open`](Self::open) picks.
+    pub fn open_group(
+        backend: AudioBackend,
+        excluded: &[String],
+        all: bool,
+    ) -> Result<MonitorGroup<Self>> {
+        let members = if all {
+            Self::open_all(backend, excluded)?
+        } else {
+            vec![Self::open(backend, excluded)?]
+        };
+        Ok(MonitorGroup::new(members))
+    }
+
+    /// The backend in use (never `Auto`).
+    pub fn backend(&self) -> AudioBackend {
+        match self {
+            LinuxMonitor::PulseAudio(_) => AudioBackend::PulseAudio,
+            #[cfg(feature = "pipewire")]
+            LinuxMonitor::PipeWire(_) => AudioBackend::PipeWire,
         }
     }
 
-    impl MuteMonitor for PulseAudioMonitor {
-        fn is_muted(&self) -> bool {
-            self.muted.load(Ordering::SeqCst)
+    /// Let a new monitor settle before trusting its state (see
+    /// [`stabilize_pulseaudio`]). PipeWire is ready once opened.
+    pub fn stabilize(&self) {
+        match self {
+            LinuxMonitor::PulseAudio(monitor) => stabilize_pulseaudio(monitor),
+            #[cfg(feature = "pipewire")]
+            LinuxMonitor::PipeWire(_) => {}
         }
+    }
 
-        fn set_muted(&self, muted: bool) -> Result<()> {
-            let mut inner = self.inner.lock().map_err(|e| {
-                AudioError::OperationFailed(format!("PulseAudio mutex poisoned: {e}"))
-            })?;
-            inner.mainloop.lock();
-            let mut introspect = inner.context.introspect();
-            introspect.set_source_mute_by_name("@DEFAULT_SOURCE@", muted, None);
-            inner.mainloop.unlock();
-            self.muted.store(muted, Ordering::SeqCst);
-            Ok(())
+    pub fn device_name(&self) -> Option<String> {
+        match self {
+            LinuxMonitor::PulseAudio(monitor) => monitor.device_name(),
+            #[cfg(feature = "pipewire")]
+            LinuxMonitor::PipeWire(monitor) => monitor.device_name(),
         }
+    }
 
-        fn wait_for_change(&self, timeout: Duration) -> bool {
-            super::wait_on_signal(&self.signal, timeout)
+    fn inner(&self) -> &dyn MuteMonitor {
+        match self {
+            LinuxMonitor::PulseAudio(monitor) => monitor,
+            #[cfg(feature = "pipewire")]
+            LinuxMonitor::PipeWire(monitor) => monitor,
         }
     }
+}
 
-    impl Drop for PulseAudioMonitor {
-        fn drop(&mut self) {
-            if let Ok(mut inner) = self.inner.lock() {
-                inner.mainloop.lock();
-                inner.context.disconnect();
-                inner.mainloop.unlock();
-                inner.mainloop.stop();
-            }
+#[cfg(target_os = "linux")]
+impl MonitorGroup<LinuxMonitor> {
+    /// [`stabilize`](LinuxMonitor::stabilize) every member.
+    pub fn stabilize(&self) {
+        for member in self.members() {
+            member.stabilize();
         }
     }
 }
 
 #[cfg(target_os = "linux")]
-pub use pulse::PulseAudioMonitor;
+impl MuteMonitor for LinuxMonitor {
+    fn is_muted(&self) -> bool {
+        self.inner().is_muted()
+    }
 
-/// Stabilize a newly-created PulseAudio monitor.
+    fn set_muted(&self, muted: bool) -> Result<()> {
+        self.inner().set_muted(muted)
+    }
+
+    fn wait_for_change(&self, timeout: std::time::Duration) -> bool {
+        self.inner().wait_for_change(timeout)
+    }
+
+    fn refresh(&self) {
+        match self {
+            // PulseAudio's occasional safety-net query; PipeWire needs none
+            LinuxMonitor::PulseAudio(monitor) => MuteMonitor::refresh(monitor),
+            #[cfg(feature = "pipewire")]
+            LinuxMonitor::PipeWire(_) => {}
+        }
+    }
+
+    fn peak_levels(&self) -> Option<Vec<f32>> {
+        self.inner().peak_levels()
+    }
+
+    fn in_use(&self) -> Option<bool> {
+        self.inner().in_use()
+    }
+
+    fn capture_applications(&self) -> Option<Vec<String>> {
+        self.inner().capture_applications()
+    }
+
+    fn adjust_capture_volume(&self, step_db: f64) -> Result<f64> {
+        self.inner().adjust_capture_volume(step_db)
+    }
+}
+
+// ── OS microphone privacy switch ──
+
+/// Registry key (under HKLM and HKCU) holding the Windows microphone consent.
+#[cfg(any(windows, test))]
+const MIC_CONSENT_KEY: &str =
+    r"Software\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\microphone";
+
+/// Whether the OS privacy settings block microphone capture for desktop apps.
 ///
-/// PulseAudio needs a brief delay after connection before the cached
-/// mute state is reliable. This is a known PulseAudio quirk — the
-/// initial state query may return stale data without this stabilization.
-#[cfg(target_os = "linux")]
-pub fn stabilize_pulseaudio(monitor: &PulseAudioMonitor) {
-    std::thread::sleep(std::time::Duration::from_millis(50));
-    monitor.refresh();
-    std::thread::sleep(std::time::Duration::from_millis(50));
+/// On Windows the Settings "Microphone access" toggles silence capture
+/// without touching the endpoint mute, so the mic reads "live" while apps
+/// hear nothing. Returns `None` where there is no such switch or it can't
+/// be read.
+pub fn microphone_access_blocked() -> Option<bool> {
+    #[cfg(windows)]
+    {
+        use windows::Win32::System::Registry::{HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE};
+        let user_key = format!("{MIC_CONSENT_KEY}\\NonPackaged");
+        let values = [
+            // Device-wide "Microphone access"
+            read_consent(HKEY_LOCAL_MACHINE, MIC_CONSENT_KEY),
+            // Per-user "Let apps access your microphone"
+            read_consent(HKEY_CURRENT_USER, MIC_CONSENT_KEY),
+            // "Let desktop apps access your microphone"
+            read_consent(HKEY_CURRENT_USER, &user_key),
+        ];
+        consent_blocked(&values)
+    }
+    #[cfg(not(windows))]
+    {
+        None
+    }
+}
+
+/// Read the `Value` string of a consent key. `None` if missing.
+#[cfg(windows)]
+fn read_consent(root: windows::Win32::System::Registry::HKEY, key: &str) -> Option<String> {
+    use windows::Win32::Foundation::ERROR_SUCCESS;
+    use windows::Win32::System::Registry::{RRF_RT_REG_SZ, RegGetValueW};
+    use windows::core::{PCWSTR, w};
+
+    let key: Vec<u16> = key.encode_utf16().chain(std::iter::once(0)).collect();
+    let mut buf = [0u16; 32];
+    let mut size = std::mem::size_of_val(&buf) as u32;
+    let status = unsafe {
+        RegGetValueW(
+            root,
+            PCWSTR(key.as_ptr()),
+            w!("Value"),
+            RRF_RT_REG_SZ,
+            None,
+            Some(buf.as_mut_ptr().cast()),
+            Some(&mut size),
+        )
+    };
+    if status != ERROR_SUCCESS {
+        return None;
+    }
+    let len = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+    Some(String::from_utf16_lossy(&buf[..len]))
+}
+
+/// Combine consent values: any `"Deny"` blocks capture. `None` if none of
+/// the keys could be read.
+#[cfg(any(windows, test))]
+fn consent_blocked(values: &[Option<String>]) -> Option<bool> {
+    let mut read_any = false;
+    for value in values.iter().flatten() {
+        read_any = true;
+        if value.eq_ignore_ascii_case("Deny") {
+            return Some(true);
+        }
+    }
+    read_any.then_some(false)
 }
 
 // ── Debounce filter ──
@@ -507,6 +2634,10 @@ pub mod stub {
     pub struct StubMonitor {
         muted: AtomicBool,
         signal: (Mutex<bool>, Condvar),
+        in_use: Mutex<Option<bool>>,
+        capturing: Mutex<Option<Vec<String>>>,
+        volume_db: Mutex<f64>,
+        applications_muted: Mutex<Option<bool>>,
     }
 
     impl StubMonitor {
@@ -515,6 +2646,31 @@ pub mod stub {
             Self {
                 muted: AtomicBool::new(initial_muted),
                 signal: (Mutex::new(false), Condvar::new()),
+                in_use: Mutex::new(None),
+                capturing: Mutex::new(None),
+                volume_db: Mutex::new(0.0),
+                applications_muted: Mutex::new(None),
+            }
+        }
+
+        /// What the last [`set_applications_muted`](MuteMonitor::set_applications_muted)
+        /// set the application streams to, if it was called.
+        pub fn applications_muted(&self) -> Option<bool> {
+            self.applications_muted.lock().ok().and_then(|m| *m)
+        }
+
+        /// Set what [`in_use`](MuteMonitor::in_use) reports.
+        pub fn set_in_use(&self, in_use: Option<bool>) {
+            if let Ok(mut current) = self.in_use.lock() {
+                *current = in_use;
+            }
+        }
+
+        /// Set what [`capture_applications`](MuteMonitor::capture_applications)
+        /// reports.
+        pub fn set_capture_applications(&self, apps: Option<Vec<String>>) {
+            if let Ok(mut current) = self.capturing.lock() {
+                *current = apps;
             }
         }
 
@@ -541,6 +2697,32 @@ pub mod stub {
         fn wait_for_change(&self, timeout: Duration) -> bool {
             wait_on_signal(&self.signal, timeout)
         }
+
+        fn in_use(&self) -> Option<bool> {
+            self.in_use.lock().ok().and_then(|in_use| *in_use)
+        }
+
+        fn capture_applications(&self) -> Option<Vec<String>> {
+            self.capturing.lock().ok().and_then(|apps| apps.clone())
+        }
+
+        /// Steps between [`MIN_CAPTURE_VOLUME_DB`] and 0 dB, from 0 dB.
+        fn adjust_capture_volume(&self, step_db: f64) -> Result<f64> {
+            let mut volume = self
+                .volume_db
+                .lock()
+                .map_err(|e| AudioError::OperationFailed(format!("stub mutex poisoned: {e}")))?;
+            *volume = step_volume_db(*volume, step_db, MIN_CAPTURE_VOLUME_DB, 0.0);
+            Ok(*volume)
+        }
+
+        /// Pretends one stream matched.
+        fn set_applications_muted(&self, _apps: &[String], muted: bool) -> Result<usize> {
+            if let Ok(mut current) = self.applications_muted.lock() {
+                *current = Some(muted);
+            }
+            Ok(1)
+        }
     }
 }
 
@@ -548,6 +2730,158 @@ pub mod stub {
 mod tests {
     use super::*;
 
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn group_is_muted_only_when_every_member_is() {
+        use std::time::Duration;
+        use stub::StubMonitor;
+
+        let group = MonitorGroup::new(vec![StubMonitor::new(true), StubMonitor::new(false)]);
+        assert!(!group.is_muted());
+        group.set_muted(true).unwrap();
+        assert!(group.members().iter().all(|m| m.is_muted()));
+        assert!(group.is_muted());
+        // Each member's own signal was raised; one wait consumes them all
+        assert!(group.wait_for_change(Duration::from_millis(50)));
+        assert!(!group.wait_for_change(Duration::from_millis(30)));
+
+        group.members()[1].set(false);
+        assert!(group.wait_for_change(Duration::from_millis(50)));
+        assert!(!group.is_muted());
+
+        // In use if any member that can tell is
+        assert_eq!(group.in_use(), None);
+        group.members()[0].set_in_use(Some(false));
+        assert_eq!(group.in_use(), Some(false));
+        group.members()[1].set_in_use(Some(true));
+        assert_eq!(group.in_use(), Some(true));
+
+        let empty: MonitorGroup<StubMonitor> = MonitorGroup::new(Vec::new());
+        assert!(!empty.is_muted());
+        assert!(empty.primary().is_none());
+    }
+
+    #[test]
+    fn application_mute_leaves_the_device_alone() {
+        use std::time::Duration;
+        use stub::StubMonitor;
+
+        let group = MonitorGroup::new(vec![StubMonitor::new(false)])
+            .with_applications(&names(&["Discord", " "]));
+        assert!(!group.is_muted());
+        group.set_muted(true).unwrap();
+        assert!(group.is_muted());
+        assert!(!group.members()[0].is_muted());
+        assert_eq!(group.members()[0].applications_muted(), Some(true));
+        assert!(group.wait_for_change(Duration::from_millis(50)));
+        // The device's own mute doesn't count
+        group.members()[0].set(true);
+        group.set_muted(false).unwrap();
+        assert!(!group.is_muted());
+        assert_eq!(group.members()[0].applications_muted(), Some(false));
+
+        // Blank names only — the device is muted as usual
+        let group =
+            MonitorGroup::new(vec![StubMonitor::new(false)]).with_applications(&names(&[""]));
+        group.set_muted(true).unwrap();
+        assert!(group.members()[0].is_muted());
+        assert_eq!(group.members()[0].applications_muted(), None);
+    }
+
+    #[test]
+    fn volume_steps_stay_in_range() {
+        assert_eq!(step_volume_db(-10.0, 3.0, -60.0, 0.0), -7.0);
+        assert_eq!(step_volume_db(-1.0, 3.0, -60.0, 0.0), 0.0);
+        assert_eq!(step_volume_db(-59.0, -3.0, -60.0, 0.0), -60.0);
+        // Silence steps up from the bottom of the range
+        assert_eq!(step_volume_db(f64::NEG_INFINITY, 3.0, -60.0, 0.0), -57.0);
+    }
+
+    #[test]
+    fn applications_match_by_file_name() {
+        let apps = names(&["Discord", "obs.exe"]);
+        assert!(matches_application(&apps, "discord"));
+        assert!(matches_application(&apps, "Discord.exe"));
+        assert!(matches_application(
+            &apps,
+            r"C:\Users\me\AppData\Local\Discord\app-1.0\Discord.exe"
+        ));
+        assert!(matches_application(&apps, "/usr/bin/obs"));
+        assert!(!matches_application(&apps, "discord-canary"));
+        assert!(!matches_application(&apps, ""));
+        assert!(!matches_application(&[], "discord"));
+    }
+
+    #[test]
+    fn exclusion_is_case_insensitive_substring() {
+        let excluded = names(&["cable output", "NVIDIA Broadcast", "  "]);
+        assert!(is_excluded(
+            "CABLE Output (VB-Audio Virtual Cable)",
+            &excluded
+        ));
+        assert!(is_excluded("Microphone (NVIDIA Broadcast)", &excluded));
+        assert!(!is_excluded(
+            "Analogue 1 + 2 (Focusrite USB Audio)",
+            &excluded
+        ));
+        assert!(!is_excluded("anything", &[]));
+    }
+
+    #[test]
+    fn audio_backend_parse() {
+        for backend in [
+            AudioBackend::Auto,
+            AudioBackend::PulseAudio,
+            AudioBackend::PipeWire,
+        ] {
+            assert_eq!(AudioBackend::parse(backend.as_str()), Some(backend));
+        }
+        assert_eq!(
+            AudioBackend::parse(" PipeWire "),
+            Some(AudioBackend::PipeWire)
+        );
+        assert_eq!(AudioBackend::parse("alsa"), None);
+    }
+
+    #[test]
+    fn picks_focusrite_over_excluded_default() {
+        let excluded = names(&["NVIDIA Broadcast", "CABLE"]);
+        let devices = names(&[
+            "Microphone (NVIDIA Broadcast)",
+            "Webcam Microphone",
+            "Analogue 1 + 2 (Focusrite USB Audio)",
+            "CABLE Output",
+        ]);
+        let default = Some("Microphone (NVIDIA Broadcast)");
+        assert_eq!(pick_capture_device(default, &devices, &excluded), Some(2));
+
+        // No Focusrite device: first one that isn't excluded
+        assert_eq!(
+            pick_capture_device(default, &devices[..2], &excluded),
+            Some(1)
+        );
+        // Default not excluded, or nothing else to pick: keep it
+        assert_eq!(
+            pick_capture_device(Some("Webcam Microphone"), &devices, &excluded),
+            None
+        );
+        assert_eq!(pick_capture_device(None, &devices, &excluded), None);
+        assert_eq!(pick_capture_device(default, &devices[..1], &excluded), None);
+    }
+
+    #[test]
+    fn consent_deny_anywhere_blocks() {
+        let allow = Some("Allow".to_string());
+        let deny = Some("Deny".to_string());
+        assert_eq!(consent_blocked(&[allow.clone(), None]), Some(false));
+        assert_eq!(consent_blocked(&[allow, None, deny]), Some(true));
+        assert_eq!(consent_blocked(&[None, None]), None);
+        assert!(MIC_CONSENT_KEY.ends_with(r"ConsentStore\microphone"));
+    }
+
     #[test]
     fn debouncer_needs_threshold_polls() {
         let mut d = MuteDebouncer::new(3, false);
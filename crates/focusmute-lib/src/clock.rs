@@ -0,0 +1,235 @@
+//! Clock status — sample rate, clock source, and sync lock.
+//!
+//! The firmware schema exposes the sample rate and sync source as plain
+//! APP_SPACE members (`standSampleRate`, `standSyncSource`); GET_SYNC reports
+//! whether the clock is locked. Changes are announced by the sync notification
+//! bit (see [`crate::device_events::DeviceEvent::ClockChanged`]) — re-read the
+//! status when it fires.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::device::{Result, ScarlettDevice};
+use crate::protocol::*;
+use crate::schema::SchemaConstants;
+
+/// Where the device takes its word clock from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClockSource {
+    Internal,
+    /// An external source, by its raw `standSyncSource` value.
+    External(u16),
+}
+
+impl ClockSource {
+    /// Decode the `standSyncSource` descriptor value.
+    pub fn from_raw(value: u16) -> Self {
+        match value {
+            0 => ClockSource::Internal,
+            n => ClockSource::External(n),
+        }
+    }
+}
+
+impl fmt::Display for ClockSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClockSource::Internal => write!(f, "internal"),
+            ClockSource::External(n) => write!(f, "external ({n})"),
+        }
+    }
+}
+
+/// Sample rate and clock state. Each field is `None` when the device or
+/// schema doesn't report it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ClockStatus {
+    /// Sample rate in Hz.
+    pub sample_rate: Option<u32>,
+    pub source: Option<ClockSource>,
+    /// Whether the clock is locked to its source.
+    pub locked: Option<bool>,
+}
+
+impl fmt::Display for ClockStatus {
+    /// e.g. `48 kHz, internal, locked`; `unknown` when nothing is reported.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(rate) = self.sample_rate {
+            parts.push(format_sample_rate(rate));
+        }
+        if let Some(source) = self.source {
+            parts.push(source.to_string());
+        }
+        if let Some(locked) = self.locked {
+            parts.push(if locked { "locked" } else { "unlocked" }.to_string());
+        }
+        if parts.is_empty() {
+            write!(f, "unknown")
+        } else {
+            write!(f, "{}", parts.join(", "))
+        }
+    }
+}
+
+impl ClockStatus {
+    /// What to tell the user when the clock has lost its source — audio from
+    /// an unlocked interface clicks or drops out. `None` while locked or unknown.
+    pub fn unlocked_warning(&self) -> Option<String> {
+        if self.locked != Some(false) {
+            return None;
+        }
+        Some(match self.source {
+            Some(ClockSource::External(_)) | None => {
+                "clock not locked — check the external clock source is connected and running".into()
+            }
+            Some(ClockSource::Internal) => {
+                "clock not locked to its internal source — try replugging the interface".into()
+            }
+        })
+    }
+}
+
+/// Format a sample rate for display, e.g. `44.1 kHz`, `48 kHz`.
+pub fn format_sample_rate(hz: u32) -> String {
+    if hz.is_multiple_of(1000) {
+        format!("{} kHz", hz / 1000)
+    } else {
+        format!("{:.1} kHz", hz as f64 / 1000.0)
+    }
+}
+
+fn read_u16(device: &impl ScarlettDevice, offset: u32) -> Result<u16> {
+    let data = device.get_descriptor(offset, 2)?;
+    Ok(match data.as_slice() {
+        [lo, hi, ..] => u16::from_le_bytes([*lo, *hi]),
+        _ => 0,
+    })
+}
+
+/// Read the sync lock state via GET_SYNC. `None` if the command fails.
+pub fn read_sync_locked(device: &impl ScarlettDevice) -> Option<bool> {
+    // Response payload (after 8-byte transact header): u32 LE, non-zero = locked
+    let resp = device.transact(CMD_GET_SYNC, &[], 12).ok()?;
+    let bytes = resp.get(8..12)?;
+    Some(u32::from_le_bytes(bytes.try_into().ok()?) != 0)
+}
+
+/// Read the current clock status.
+///
+/// The sample rate comes from the schema's `standSampleRate` member, falling
+/// back to the rate GET_CONFIG reported when the device was opened. The
+/// source needs `standSyncSource`; the lock state comes from GET_SYNC.
+pub fn read_clock_status(
+    device: &impl ScarlettDevice,
+    schema: Option<&SchemaConstants>,
+) -> Result<ClockStatus> {
+    let schema_rate = match schema.and_then(|sc| sc.sample_rate_offset) {
+        Some(offset) => Some(read_u16(device, offset)?).filter(|&r| r != 0),
+        None => None,
+    };
+    let source = match schema.and_then(|sc| sc.sync_source_offset) {
+        Some(offset) => Some(ClockSource::from_raw(read_u16(device, offset)?)),
+        None => None,
+    };
+    Ok(ClockStatus {
+        sample_rate: schema_rate
+            .map(u32::from)
+            .or_else(|| device.info().sample_rate()),
+        source,
+        locked: read_sync_locked(device),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device::mock::MockDevice;
+
+    fn schema_with_clock() -> SchemaConstants {
+        SchemaConstants {
+            product_name: "Scarlett 2i2 4th Gen".into(),
+            max_leds: 40,
+            max_inputs: 2,
+            max_outputs: 2,
+            gradient_count: 11,
+            gradient_offset: 384,
+            gradient_notify: 9,
+            direct_led_count: 40,
+            direct_led_offset: 92,
+            metering_segments: 25,
+            input_controls: vec![],
+            app_space_features: vec![],
+            firmware_version: String::new(),
+            phantom_power: None,
+            air: None,
+            inst_input: None,
+            preamp_gain: None,
+            clip_safe: None,
+            autogain: None,
+            autogain_status_offset: None,
+            sample_rate_offset: Some(70),
+            sync_source_offset: Some(68),
+            direct_monitoring_offset: None,
+            alt_speakers: None,
+            output_volume: None,
+        }
+    }
+
+    #[test]
+    fn reads_schema_members_and_sync() {
+        let dev = MockDevice::new();
+        dev.set_descriptor(70, &48_000u16.to_le_bytes()).unwrap();
+        dev.set_descriptor(68, &[0, 0]).unwrap();
+        let mut resp = vec![0u8; 8];
+        resp.extend_from_slice(&1u32.to_le_bytes());
+        dev.add_transact_response(CMD_GET_SYNC, resp);
+
+        let status = read_clock_status(&dev, Some(&schema_with_clock())).unwrap();
+        assert_eq!(
+            status,
+            ClockStatus {
+                sample_rate: Some(48_000),
+                source: Some(ClockSource::Internal),
+                locked: Some(true),
+            }
+        );
+        assert_eq!(status.to_string(), "48 kHz, internal, locked");
+    }
+
+    #[test]
+    fn without_schema_or_sync_reports_unknown() {
+        let dev = MockDevice::new();
+        let status = read_clock_status(&dev, None).unwrap();
+        assert_eq!(status, ClockStatus::default());
+        assert_eq!(status.to_string(), "unknown");
+    }
+
+    #[test]
+    fn warns_only_when_unlocked() {
+        let mut status = ClockStatus {
+            sample_rate: Some(48_000),
+            source: Some(ClockSource::External(1)),
+            locked: Some(true),
+        };
+        assert_eq!(status.unlocked_warning(), None);
+        status.locked = None;
+        assert_eq!(status.unlocked_warning(), None);
+        status.locked = Some(false);
+        assert!(status.unlocked_warning().unwrap().contains("external"));
+        status.source = Some(ClockSource::Internal);
+        assert!(status.unlocked_warning().unwrap().contains("internal"));
+    }
+
+    #[test]
+    fn format_fractional_rates() {
+        assert_eq!(format_sample_rate(44_100), "44.1 kHz");
+        assert_eq!(format_sample_rate(96_000), "96 kHz");
+        assert_eq!(
+            ClockSource::from_raw(2).to_string(),
+            "external (2)".to_string()
+        );
+    }
+}
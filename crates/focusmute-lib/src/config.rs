@@ -2,9 +2,17 @@
 
 use std::fmt;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use serde::{Deserialize, Serialize};
 
+use crate::audio::AudioBackend;
+use crate::device::DeviceOptions;
+use crate::device_events::{DeviceButton, DeviceEvent};
+use crate::led::MuteAnimation;
+use crate::monitor::{IdleScreensaver, TalkDetector};
+use crate::reconnect::ReconnectResync;
+
 use std::collections::HashMap;
 
 /// Header comment prepended to saved config files.
@@ -30,7 +38,7 @@ pub struct Config {
     pub autostart: bool,
 
     /// Which inputs to show mute indicator on. Default: "all".
-    /// Values: "all", "1", "2", "1,2", etc. (1-based input numbers).
+    /// Values: "all", "1", "2", "1,2", "1-4,7", "all except 2", etc. (1-based input numbers).
     #[serde(default = "default_mute_inputs")]
     pub mute_inputs: String,
 
@@ -59,9 +67,300 @@ pub struct Config {
     #[serde(default)]
     pub input_colors: HashMap<String, String>,
 
+    /// Per-input colors while live (1-based keys), shown on the number LED
+    /// instead of the firmware's. Inputs not listed keep the stock colors.
+    /// Example in TOML: `[input_live_colors]` / `1 = "#00FFFF"`
+    #[serde(default)]
+    pub input_live_colors: HashMap<String, String>,
+
     /// Show desktop notification on mute state change.
     #[serde(default)]
     pub notifications_enabled: bool,
+
+    /// Record mute state transitions to `history.csv` in the config directory.
+    #[serde(default)]
+    pub history_enabled: bool,
+
+    /// Show a desktop notification summarizing the previous day's mute history
+    /// (requires `history_enabled`). Days are UTC.
+    #[serde(default)]
+    pub daily_summary_enabled: bool,
+
+    /// Seconds to quietly wait for the device at startup before reporting it as
+    /// disconnected (the driver may still be loading on autostart). 0 = no wait.
+    #[serde(default = "default_startup_wait_for_device_secs")]
+    pub startup_wait_for_device_secs: u64,
+
+    /// Mute indicator color used when another app muted the mic (hex or name).
+    /// Empty = same as `mute_color` / `input_colors`.
+    #[serde(default)]
+    pub external_mute_color: String,
+
+    /// Named LED strategy preset for the connected model (e.g. "bank" on rack
+    /// units, "output-meter", "halo"). Replaces the per-input number LEDs selected by
+    /// `mute_inputs`. Empty = no preset.
+    #[serde(default)]
+    pub mute_preset: String,
+
+    /// Follow the OS do-not-disturb / Focus Assist state: "auto" (detect),
+    /// "on" (always quiet), or "off" (ignore it). While quiet, mute-change,
+    /// hook-failure, and daily-summary notifications are suppressed.
+    #[serde(default = "default_auto")]
+    pub do_not_disturb: String,
+
+    /// Follow the OS reduce-motion setting: "auto" (detect), "on", or "off".
+    /// When on, UI animations are turned off.
+    #[serde(default = "default_auto")]
+    pub reduce_motion: String,
+
+    /// Device transaction timeout in milliseconds (per IOCTL on Windows, per
+    /// USB control transfer on Linux). Raise it for slow hubs or docks.
+    #[serde(default = "default_io_timeout_ms")]
+    pub io_timeout_ms: u64,
+
+    /// Extra attempts after a failed device transaction.
+    #[serde(default = "default_io_retries")]
+    pub io_retries: u32,
+
+    /// Longest single wait for a device notification, in milliseconds.
+    #[serde(default = "default_notify_timeout_ms")]
+    pub notify_timeout_ms: u64,
+
+    /// Indicator-only mode: never change the OS mute state (hotkey and
+    /// Toggle Mute are disabled, no unmute on exit) and just mirror external
+    /// mute changes onto the LEDs. For users who mute from a DAW or mixer.
+    #[serde(default)]
+    pub indicator_only: bool,
+
+    /// Control-only mode: never open the device or write LEDs, but keep the
+    /// hotkey, sounds, notifications, and hooks. For machines without the
+    /// interface.
+    #[serde(default)]
+    pub control_only: bool,
+
+    /// Capture devices never to monitor (case-insensitive name substrings,
+    /// e.g. virtual cables). If the OS default matches, another device is
+    /// monitored instead — a Focusrite one if present.
+    #[serde(default)]
+    pub excluded_capture_devices: Vec<String>,
+
+    /// Audio system to monitor on Linux: "auto" (PipeWire if it's running
+    /// and built in, else PulseAudio), "pulseaudio", or "pipewire".
+    #[serde(default = "default_audio_backend")]
+    pub audio_backend: String,
+
+    /// Mute and unmute every active capture device together (the Scarlett,
+    /// a webcam mic, a headset), not just the monitored one. The mic shows
+    /// as muted only while all of them are.
+    #[serde(default)]
+    pub mute_all_capture_devices: bool,
+
+    /// Advanced: mute only these applications' capture streams (process
+    /// names, e.g. "Discord"), leaving the microphone live for everything
+    /// else. Empty = mute the capture device itself.
+    #[serde(default)]
+    pub mute_applications: Vec<String>,
+
+    /// Commands to run on hardware events, keyed by event name (see
+    /// [`DeviceEvent::hook_name`](crate::device_events::DeviceEvent::hook_name)).
+    /// Example in TOML: `[device_hooks]` / `on_phantom_changed = "notify-send 48V"`
+    #[serde(default)]
+    pub device_hooks: HashMap<String, String>,
+
+    /// Front-panel button that toggles mute: "select", "air", "inst", or
+    /// "direct_monitoring". The button keeps its normal function too. Works
+    /// while the workstation is locked, when the hotkey doesn't reach the
+    /// tray. Empty = none.
+    #[serde(default)]
+    pub mute_button: String,
+
+    /// Keep the `mute_button` ("air" or "inst", on Input 1) in step with
+    /// the mute state instead of toggling on each press: the switch is on
+    /// while muted, flipping it on the device mutes or unmutes the OS, and
+    /// mute changes from anywhere else flip it to match.
+    #[serde(default)]
+    pub mute_button_sync: bool,
+
+    /// How the mute indicator looks while muted: "static", "pulse", or
+    /// "blink". Turned off (static) when reduce motion is on.
+    #[serde(default = "default_mute_animation")]
+    pub mute_animation: String,
+
+    /// Mute animation speed multiplier (1.0 = default, 2.0 = twice as fast).
+    #[serde(default = "default_animation_speed")]
+    pub animation_speed: f64,
+
+    /// Mirror the mute state onto a USB busylight (Luxafor, Blynclight): the
+    /// mute color while muted.
+    #[serde(default)]
+    pub busylight_enabled: bool,
+
+    /// Busylight color while live (hex or name), e.g. green for "on air".
+    /// Empty = off.
+    #[serde(default)]
+    pub busylight_live_color: String,
+
+    /// Instance name, so several trays can run side by side (see
+    /// [`crate::instance`]). Scopes the single-instance lock, the IPC
+    /// endpoint, and the mute history. Empty = the default instance.
+    #[serde(default)]
+    pub instance_name: String,
+
+    /// Named LED patterns: LED index (`"5"`) or range (`"16-26"`) → color.
+    /// Example in TOML: `[led_patterns.onair]` / `16-26 = "red"`
+    #[serde(default)]
+    pub led_patterns: HashMap<String, HashMap<String, String>>,
+
+    /// `led_patterns` entry painted while muted, on top of the mute indicator.
+    /// Empty = none.
+    #[serde(default)]
+    pub mute_pattern: String,
+
+    /// `led_patterns` entry painted while live. Empty = none.
+    #[serde(default)]
+    pub live_pattern: String,
+
+    /// `led_patterns` entry painted while the device is connected but the
+    /// mute state can't be read. Empty = none.
+    #[serde(default)]
+    pub disconnected_pattern: String,
+
+    /// Named color preset ("deuteranopia", "high-contrast", "streamer-purple"),
+    /// see [`crate::led::COLOR_PRESETS`]. Replaces `mute_color`,
+    /// `external_mute_color`, and `busylight_live_color`; `input_colors` still
+    /// apply. Empty = use those colors.
+    #[serde(default)]
+    pub color_preset: String,
+
+    /// Flash the indicator (and notify, if notifications are on) when the
+    /// microphone picks up talking while muted. Needs an input peak meter
+    /// (Windows).
+    #[serde(default)]
+    pub talk_warning_enabled: bool,
+
+    /// Input peak level (dBFS, -60 to 0) that counts as talking.
+    #[serde(default = "default_talk_warning_threshold_db")]
+    pub talk_warning_threshold_db: f64,
+
+    /// Seconds of talking while muted before the warning (0.5 to 30).
+    #[serde(default = "default_talk_warning_secs")]
+    pub talk_warning_secs: f64,
+
+    /// What reconnecting does when the mute state changed while the device
+    /// was unplugged: "silent" (just show the final state), "flash" (blink
+    /// the indicator briefly first), or "notify" (report the missed changes).
+    #[serde(default = "default_reconnect_resync")]
+    pub reconnect_resync: String,
+
+    /// Color the number LEDs blink twice when the device reconnects (e.g.
+    /// after sleep), before the mute state is re-applied. Empty = no flash.
+    #[serde(default)]
+    pub reconnect_flash_color: String,
+
+    /// Color of the number LEDs while live and an application is recording
+    /// from the microphone. Empty = live looks the same either way.
+    #[serde(default)]
+    pub in_use_color: String,
+
+    /// Call profile: while one of `call_applications` records from the
+    /// microphone, the `call_*` settings below apply; they're undone when it
+    /// stops (tray).
+    #[serde(default)]
+    pub call_profile_enabled: bool,
+
+    /// Conferencing applications that start a call (process names, matched
+    /// like `mute_applications`).
+    #[serde(default = "default_call_applications")]
+    pub call_applications: Vec<String>,
+
+    /// Unmute when a call starts. If it was muted, it's muted again when the
+    /// call ends.
+    #[serde(default)]
+    pub call_unmute: bool,
+
+    /// Mute color during a call (hex or name), replacing `color_preset` and
+    /// `input_colors` too. Empty = no change.
+    #[serde(default)]
+    pub call_mute_color: String,
+
+    /// Warn about talking while muted during a call, even with
+    /// `talk_warning_enabled` off.
+    #[serde(default = "default_true")]
+    pub call_talk_warning: bool,
+
+    /// Lower the monitor/headphone outputs by this many dB while muted, as
+    /// a cue you can hear (tray; needs software output volume). They're put
+    /// back on unmute and on exit. 0 = off.
+    #[serde(default)]
+    pub output_duck_db: u32,
+
+    /// Hotkey that raises the mic level (see `gain_hotkey_target`), e.g.
+    /// "Ctrl+Shift+Up". Empty = none.
+    #[serde(default)]
+    pub gain_up_hotkey: String,
+
+    /// Hotkey that lowers the mic level. Empty = none.
+    #[serde(default)]
+    pub gain_down_hotkey: String,
+
+    /// What the mic level hotkeys adjust: "os" (the capture device's volume)
+    /// or "hardware" (the preamp gain of the `mute_inputs`).
+    #[serde(default = "default_gain_hotkey_target")]
+    pub gain_hotkey_target: String,
+
+    /// dB per mic level hotkey press (1 to 20).
+    #[serde(default = "default_gain_hotkey_step_db")]
+    pub gain_hotkey_step_db: u32,
+
+    /// MQTT broker to publish mute and device state to, as "host" or
+    /// "host:port" (tray; `mqtt` build feature). Empty = off.
+    #[serde(default)]
+    pub mqtt_broker: String,
+
+    /// Prefix of the MQTT topics (`<prefix>/state`, `<prefix>/device`,
+    /// `<prefix>/set`, ...).
+    #[serde(default = "default_mqtt_topic_prefix")]
+    pub mqtt_topic_prefix: String,
+
+    /// MQTT user name. Empty = connect anonymously.
+    #[serde(default)]
+    pub mqtt_username: String,
+
+    /// MQTT password, sent with `mqtt_username`. The client has no TLS, so
+    /// the password crosses the network in clear text — only use it with a
+    /// broker on a trusted network.
+    #[serde(default)]
+    pub mqtt_password: String,
+
+    /// Where to send OSC state messages, as "host:port" (tray). Empty = off.
+    #[serde(default)]
+    pub osc_target: String,
+
+    /// Address to take OSC commands on, as "host:port" (tray), e.g.
+    /// "127.0.0.1:9001". Empty = off.
+    #[serde(default)]
+    pub osc_listen: String,
+
+    /// Port of the local WebSocket control server on 127.0.0.1 (tray).
+    /// 0 = off.
+    #[serde(default)]
+    pub websocket_port: u16,
+
+    /// Token WebSocket clients authenticate with. Required when
+    /// `websocket_port` is set.
+    #[serde(default)]
+    pub websocket_token: String,
+
+    /// VU mode: FocusMute draws the input halo meters itself from live
+    /// levels, leaving the LEDs the mute indicator paints alone (`monitor`
+    /// command).
+    #[serde(default)]
+    pub vu_mode: bool,
+
+    /// Minutes without mute changes or input audio before the number LEDs
+    /// slowly cycle through colors while live (`monitor` command). 0 = off.
+    #[serde(default)]
+    pub idle_screensaver_mins: u64,
 }
 
 fn default_mute_color() -> String {
@@ -74,10 +373,73 @@ fn default_mute_inputs() -> String {
     "all".into()
 }
 
+fn default_startup_wait_for_device_secs() -> u64 {
+    10
+}
+
 fn default_true() -> bool {
     true
 }
 
+fn default_auto() -> String {
+    "auto".into()
+}
+fn default_mute_animation() -> String {
+    "static".into()
+}
+fn default_animation_speed() -> f64 {
+    1.0
+}
+fn default_talk_warning_threshold_db() -> f64 {
+    -30.0
+}
+fn default_talk_warning_secs() -> f64 {
+    2.0
+}
+fn default_audio_backend() -> String {
+    "auto".into()
+}
+fn default_reconnect_resync() -> String {
+    "silent".into()
+}
+fn default_gain_hotkey_target() -> String {
+    "os".into()
+}
+fn default_gain_hotkey_step_db() -> u32 {
+    2
+}
+fn default_mqtt_topic_prefix() -> String {
+    "focusmute".into()
+}
+fn default_call_applications() -> Vec<String> {
+    ["Teams", "ms-teams", "Zoom", "Discord"]
+        .map(String::from)
+        .to_vec()
+}
+
+fn default_io_timeout_ms() -> u64 {
+    DeviceOptions::DEFAULT_TRANSACT_TIMEOUT.as_millis() as u64
+}
+
+fn default_io_retries() -> u32 {
+    DeviceOptions::DEFAULT_RETRIES
+}
+
+fn default_notify_timeout_ms() -> u64 {
+    DeviceOptions::DEFAULT_NOTIFY_TIMEOUT.as_millis() as u64
+}
+
+/// Resolve an `"auto"` / `"on"` / `"off"` OS-preference setting. `"auto"`
+/// (and anything unrecognised) asks `detect`; an OS state that can't be read
+/// counts as off.
+pub fn resolve_os_preference(value: &str, detect: impl FnOnce() -> Option<bool>) -> bool {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "on" => true,
+        "off" => false,
+        _ => detect().unwrap_or(false),
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         Config {
@@ -92,11 +454,141 @@ impl Default for Config {
             on_mute_command: String::new(),
             on_unmute_command: String::new(),
             input_colors: HashMap::new(),
+            input_live_colors: HashMap::new(),
             notifications_enabled: false,
+            history_enabled: false,
+            daily_summary_enabled: false,
+            startup_wait_for_device_secs: default_startup_wait_for_device_secs(),
+            external_mute_color: String::new(),
+            mute_preset: String::new(),
+            do_not_disturb: default_auto(),
+            reduce_motion: default_auto(),
+            io_timeout_ms: default_io_timeout_ms(),
+            io_retries: default_io_retries(),
+            notify_timeout_ms: default_notify_timeout_ms(),
+            indicator_only: false,
+            control_only: false,
+            excluded_capture_devices: Vec::new(),
+            audio_backend: default_audio_backend(),
+            mute_all_capture_devices: false,
+            mute_applications: Vec::new(),
+            device_hooks: HashMap::new(),
+            mute_button: String::new(),
+            mute_button_sync: false,
+            mute_animation: default_mute_animation(),
+            animation_speed: default_animation_speed(),
+            busylight_enabled: false,
+            busylight_live_color: String::new(),
+            instance_name: String::new(),
+            led_patterns: HashMap::new(),
+            mute_pattern: String::new(),
+            live_pattern: String::new(),
+            disconnected_pattern: String::new(),
+            color_preset: String::new(),
+            talk_warning_enabled: false,
+            talk_warning_threshold_db: default_talk_warning_threshold_db(),
+            talk_warning_secs: default_talk_warning_secs(),
+            reconnect_resync: default_reconnect_resync(),
+            reconnect_flash_color: String::new(),
+            in_use_color: String::new(),
+            call_profile_enabled: false,
+            call_applications: default_call_applications(),
+            call_unmute: false,
+            call_mute_color: String::new(),
+            call_talk_warning: true,
+            output_duck_db: 0,
+            gain_up_hotkey: String::new(),
+            gain_down_hotkey: String::new(),
+            gain_hotkey_target: default_gain_hotkey_target(),
+            gain_hotkey_step_db: default_gain_hotkey_step_db(),
+            mqtt_broker: String::new(),
+            mqtt_topic_prefix: default_mqtt_topic_prefix(),
+            mqtt_username: String::new(),
+            mqtt_password: String::new(),
+            osc_target: String::new(),
+            osc_listen: String::new(),
+            websocket_port: 0,
+            websocket_token: String::new(),
+            vu_mode: false,
+            idle_screensaver_mins: 0,
         }
     }
 }
 
+/// Highest input number a `mute_inputs` range may expand to. Larger range ends
+/// are clamped (no Scarlett has anywhere near this many inputs).
+const MAX_RANGE_INPUT: usize = 64;
+
+/// Accepted `animation_speed` range.
+const MIN_ANIMATION_SPEED: f64 = 0.1;
+const MAX_ANIMATION_SPEED: f64 = 10.0;
+
+/// Accepted `talk_warning_threshold_db` and `talk_warning_secs` ranges.
+const MIN_TALK_THRESHOLD_DB: f64 = -60.0;
+const MAX_TALK_THRESHOLD_DB: f64 = 0.0;
+const MIN_TALK_SECS: f64 = 0.5;
+const MAX_TALK_SECS: f64 = 30.0;
+
+/// Accepted `gain_hotkey_step_db` range.
+const MIN_GAIN_HOTKEY_STEP_DB: u32 = 1;
+const MAX_GAIN_HOTKEY_STEP_DB: u32 = 20;
+
+/// Port of `mqtt_broker` when it doesn't give one.
+pub const MQTT_DEFAULT_PORT: u16 = 1883;
+
+/// Split a `"host"` or `"host:port"` address (an optional `scheme://` is
+/// dropped), using `default_port` when none is given. `None` for an empty
+/// host or a bad port.
+pub fn parse_host_port(s: &str, default_port: u16) -> Option<(String, u16)> {
+    let s = s.trim();
+    let s = s.split_once("://").map_or(s, |(_, rest)| rest);
+    let (host, port) = match s.rsplit_once(':') {
+        Some((host, port)) => (host, port.parse().ok().filter(|&p| p != 0)?),
+        None => (s, default_port),
+    };
+    let valid = !host.is_empty() && !host.contains(|c: char| c.is_whitespace() || c == '/');
+    valid.then(|| (host.to_string(), port))
+}
+
+/// Largest `output_duck_db` — the whole output volume range.
+const MAX_OUTPUT_DUCK_DB: u32 = crate::device_controls::MIN_OUTPUT_VOLUME_DB.unsigned_abs() as u32;
+
+/// Parse one `mute_inputs` entry — `"3"` or `"1-4"` — into an inclusive
+/// 1-based range. `None` for zero, reversed ranges, or garbage.
+fn parse_input_range(part: &str) -> Option<(usize, usize)> {
+    let part = part.trim();
+    match part.split_once('-') {
+        Some((a, b)) => {
+            let first = a.trim().parse::<usize>().ok()?;
+            let last = b.trim().parse::<usize>().ok()?.min(MAX_RANGE_INPUT);
+            (first >= 1 && first <= last).then_some((first, last))
+        }
+        None => {
+            let n = part.parse::<usize>().ok()?;
+            (n >= 1).then_some((n, n))
+        }
+    }
+}
+
+/// Parse a comma-separated list of inputs and ranges into sorted, deduplicated
+/// 0-indexed inputs. Invalid entries are skipped.
+fn parse_input_list(s: &str) -> Vec<usize> {
+    let mut inputs = Vec::new();
+    for part in s.split(',') {
+        let Some((first, last)) = parse_input_range(part) else {
+            continue;
+        };
+        for n in first..=last {
+            let idx = n - 1; // convert 1-based to 0-indexed
+            if !inputs.contains(&idx) {
+                inputs.push(idx);
+            }
+        }
+    }
+    inputs.sort();
+    inputs
+}
+
 /// Parsed mute input selection.
 #[derive(Debug, Clone, PartialEq)]
 pub enum MuteInputs {
@@ -104,6 +596,9 @@ pub enum MuteInputs {
     All,
     /// Specific inputs (0-indexed internally, parsed from 1-based user input).
     Specific(Vec<usize>),
+    /// All inputs except these (0-indexed). Resolved against the device's
+    /// input count when the strategy is built.
+    AllExcept(Vec<usize>),
 }
 
 impl std::fmt::Display for MuteInputs {
@@ -114,6 +609,31 @@ impl std::fmt::Display for MuteInputs {
                 let names: Vec<String> = inputs.iter().map(|i| format!("{}", i + 1)).collect();
                 write!(f, "{} (per-input)", names.join(", "))
             }
+            MuteInputs::AllExcept(inputs) => {
+                let names: Vec<String> = inputs.iter().map(|i| format!("{}", i + 1)).collect();
+                write!(f, "all except {}", names.join(", "))
+            }
+        }
+    }
+}
+
+/// What the mic level hotkeys adjust (`gain_hotkey_target` setting).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GainHotkeyTarget {
+    /// The OS capture volume of the monitored device.
+    #[default]
+    Os,
+    /// The device's preamp gain, on the `mute_inputs`.
+    Hardware,
+}
+
+impl GainHotkeyTarget {
+    /// Parse a config value (`"os"`, `"hardware"`).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "os" => Some(GainHotkeyTarget::Os),
+            "hardware" => Some(GainHotkeyTarget::Hardware),
+            _ => None,
         }
     }
 }
@@ -129,8 +649,65 @@ pub enum ValidationError {
     InvalidSoundPath { field: &'static str, reason: String },
     /// The `mute_inputs` field references inputs that don't exist on the device.
     InvalidMuteInputs(String),
-    /// An `input_colors` entry is invalid (bad color value or out-of-range key).
-    InvalidInputColor { input: String, reason: String },
+    /// An `input_colors` or `input_live_colors` (`field`) entry is invalid
+    /// (bad color value or out-of-range key).
+    InvalidInputColor {
+        field: &'static str,
+        input: String,
+        reason: String,
+    },
+    /// The `external_mute_color` field is set but could not be parsed.
+    InvalidExternalMuteColor(String),
+    /// A device timeout (`field`) is zero.
+    ZeroTimeout(&'static str),
+    /// A `device_hooks` key isn't a known event name.
+    UnknownDeviceHook(String),
+    /// The `mute_button` field isn't a known front-panel button.
+    InvalidMuteButton(String),
+    /// `mute_button_sync` is on, but `mute_button` has no on/off state.
+    MuteButtonNotSwitch(String),
+    /// The `mute_animation` field isn't a known animation.
+    InvalidMuteAnimation(String),
+    /// `animation_speed` is not between 0.1 and 10.
+    InvalidAnimationSpeed(f64),
+    /// The `busylight_live_color` field is set but could not be parsed.
+    InvalidBusylightColor(String),
+    /// The `instance_name` field is set but not a valid name.
+    InvalidInstanceName(String),
+    /// A `led_patterns` entry is invalid (bad LED index, range, or color).
+    InvalidLedPattern { name: String, reason: String },
+    /// A pattern field (`field`) names a pattern `led_patterns` doesn't define.
+    UnknownLedPattern { field: &'static str, name: String },
+    /// The `color_preset` field isn't a known preset.
+    UnknownColorPreset(String),
+    /// `talk_warning_threshold_db` is not between -60 and 0.
+    InvalidTalkWarningThreshold(f64),
+    /// `talk_warning_secs` is not between 0.5 and 30.
+    InvalidTalkWarningSecs(f64),
+    /// `output_duck_db` is more than the output volume range.
+    InvalidOutputDuck(u32),
+    /// The `gain_hotkey_target` field isn't a known target.
+    InvalidGainHotkeyTarget(String),
+    /// `gain_hotkey_step_db` is not between 1 and 20.
+    InvalidGainHotkeyStep(u32),
+    /// The `mqtt_broker` field is set but isn't a host or host:port.
+    InvalidMqttBroker(String),
+    /// The `mqtt_topic_prefix` field is empty or has MQTT wildcards.
+    InvalidMqttTopicPrefix(String),
+    /// An OSC address (`field`) is set but isn't host:port.
+    InvalidOscAddress { field: &'static str, value: String },
+    /// `websocket_port` is set but `websocket_token` is empty.
+    MissingWebsocketToken,
+    /// The `reconnect_resync` field isn't a known mode.
+    InvalidReconnectResync(String),
+    /// The `reconnect_flash_color` field is set but could not be parsed.
+    InvalidReconnectFlashColor(String),
+    /// The `in_use_color` field is set but could not be parsed.
+    InvalidInUseColor(String),
+    /// The `call_mute_color` field is set but could not be parsed.
+    InvalidCallMuteColor(String),
+    /// The `audio_backend` field isn't a known backend.
+    InvalidAudioBackend(String),
 }
 
 impl fmt::Display for ValidationError {
@@ -142,9 +719,110 @@ impl fmt::Display for ValidationError {
                 write!(f, "Invalid {field}: {reason}")
             }
             ValidationError::InvalidMuteInputs(e) => write!(f, "Invalid mute inputs: {e}"),
-            ValidationError::InvalidInputColor { input, reason } => {
-                write!(f, "Invalid input_colors[{input}]: {reason}")
+            ValidationError::InvalidInputColor {
+                field,
+                input,
+                reason,
+            } => {
+                write!(f, "Invalid {field}[{input}]: {reason}")
+            }
+            ValidationError::InvalidExternalMuteColor(e) => {
+                write!(f, "Invalid external mute color: {e}")
+            }
+            ValidationError::ZeroTimeout(field) => {
+                write!(f, "Invalid {field}: must be greater than 0")
+            }
+            ValidationError::UnknownDeviceHook(name) => {
+                write!(f, "Unknown device_hooks event \"{name}\"")
+            }
+            ValidationError::InvalidMuteButton(name) => write!(
+                f,
+                "Invalid mute_button \"{name}\": expected select, air, inst, or direct_monitoring"
+            ),
+            ValidationError::MuteButtonNotSwitch(name) => write!(
+                f,
+                "mute_button_sync needs mute_button = \"air\" or \"inst\", not \"{name}\""
+            ),
+            ValidationError::InvalidMuteAnimation(name) => write!(
+                f,
+                "Invalid mute_animation \"{name}\": expected static, pulse, or blink"
+            ),
+            ValidationError::InvalidBusylightColor(e) => {
+                write!(f, "Invalid busylight live color: {e}")
+            }
+            ValidationError::InvalidReconnectFlashColor(e) => {
+                write!(f, "Invalid reconnect flash color: {e}")
             }
+            ValidationError::InvalidInUseColor(e) => {
+                write!(f, "Invalid in-use color: {e}")
+            }
+            ValidationError::InvalidCallMuteColor(e) => {
+                write!(f, "Invalid call mute color: {e}")
+            }
+            ValidationError::InvalidAudioBackend(name) => write!(
+                f,
+                "Invalid audio_backend \"{name}\": expected auto, pulseaudio, or pipewire"
+            ),
+            ValidationError::InvalidInstanceName(e) => write!(f, "Invalid instance_name: {e}"),
+            ValidationError::InvalidLedPattern { name, reason } => {
+                write!(f, "Invalid led_patterns.{name}: {reason}")
+            }
+            ValidationError::UnknownColorPreset(name) => {
+                let known: Vec<&str> = crate::led::COLOR_PRESETS.iter().map(|p| p.name).collect();
+                write!(
+                    f,
+                    "Unknown color_preset \"{name}\": expected {}",
+                    known.join(", ")
+                )
+            }
+            ValidationError::InvalidTalkWarningThreshold(db) => write!(
+                f,
+                "Invalid talk_warning_threshold_db {db}: must be between {MIN_TALK_THRESHOLD_DB} and {MAX_TALK_THRESHOLD_DB}"
+            ),
+            ValidationError::InvalidTalkWarningSecs(secs) => write!(
+                f,
+                "Invalid talk_warning_secs {secs}: must be between {MIN_TALK_SECS} and {MAX_TALK_SECS}"
+            ),
+            ValidationError::InvalidOutputDuck(db) => write!(
+                f,
+                "Invalid output_duck_db {db}: must be at most {MAX_OUTPUT_DUCK_DB}"
+            ),
+            ValidationError::InvalidGainHotkeyTarget(name) => write!(
+                f,
+                "Invalid gain_hotkey_target \"{name}\": expected os or hardware"
+            ),
+            ValidationError::InvalidGainHotkeyStep(db) => write!(
+                f,
+                "Invalid gain_hotkey_step_db {db}: must be between {MIN_GAIN_HOTKEY_STEP_DB} and {MAX_GAIN_HOTKEY_STEP_DB}"
+            ),
+            ValidationError::InvalidMqttBroker(broker) => write!(
+                f,
+                "Invalid mqtt_broker \"{broker}\": expected host or host:port"
+            ),
+            ValidationError::InvalidMqttTopicPrefix(prefix) => write!(
+                f,
+                "Invalid mqtt_topic_prefix \"{prefix}\": must be non-empty without + or #"
+            ),
+            ValidationError::InvalidOscAddress { field, value } => {
+                write!(f, "Invalid {field} \"{value}\": expected host:port")
+            }
+            ValidationError::MissingWebsocketToken => {
+                write!(
+                    f,
+                    "websocket_port is set, but websocket_token is empty — the control server won't start"
+                )
+            }
+            ValidationError::InvalidReconnectResync(name) => write!(
+                f,
+                "Invalid reconnect_resync \"{name}\": expected silent, flash, or notify"
+            ),
+            ValidationError::UnknownLedPattern { field, name } => {
+                write!(f, "Invalid {field}: no LED pattern named \"{name}\"")
+            }
+            ValidationError::InvalidAnimationSpeed(speed) => write!(
+                f,
+                "Invalid animation_speed {speed}: must be between {MIN_ANIMATION_SPEED} and {MAX_ANIMATION_SPEED}"
+            ),
         }
     }
 }
@@ -164,12 +842,12 @@ impl Config {
 
     /// Full path to config file.
     pub fn path() -> Option<PathBuf> {
-        Self::dir().map(|d| d.join("config.toml"))
+        Self::dir().map(|d| d.join(crate::instance::config_file_name()))
     }
 
     /// Full path to the log file (tray app).
     pub fn log_path() -> Option<PathBuf> {
-        Self::dir().map(|d| d.join("focusmute.log"))
+        Self::dir().map(|d| d.join(crate::instance::scoped("focusmute.log")))
     }
 
     /// Load config from disk, or return defaults if not found.
@@ -247,29 +925,28 @@ impl Config {
     /// - `"all"` → `MuteInputs::All`
     /// - `"1"` → `MuteInputs::Specific(vec![0])`  (1-based → 0-indexed)
     /// - `"1,2"` → `MuteInputs::Specific(vec![0, 1])`
+    /// - `"1-4,7"` → `MuteInputs::Specific(vec![0, 1, 2, 3, 6])`
+    /// - `"all except 2"` → `MuteInputs::AllExcept(vec![1])`
     ///
-    /// Returns `MuteInputs::All` for empty or unparseable values.
+    /// Returns `MuteInputs::All` for empty or unparseable values, and for an
+    /// `"all except"` with no valid inputs after it.
     pub fn parse_mute_inputs(&self) -> MuteInputs {
-        let s = self.mute_inputs.trim();
-        if s.is_empty() || s.eq_ignore_ascii_case("all") {
+        let s = self.mute_inputs.trim().to_ascii_lowercase();
+        if s.is_empty() || s == "all" {
             return MuteInputs::All;
         }
-        let mut inputs = Vec::new();
-        for part in s.split(',') {
-            let part = part.trim();
-            if let Ok(n) = part.parse::<usize>()
-                && n >= 1
-            {
-                let idx = n - 1; // convert 1-based to 0-indexed
-                if !inputs.contains(&idx) {
-                    inputs.push(idx);
-                }
-            }
+        if let Some(rest) = s.strip_prefix("all except") {
+            let excluded = parse_input_list(rest);
+            return if excluded.is_empty() {
+                MuteInputs::All
+            } else {
+                MuteInputs::AllExcept(excluded)
+            };
         }
+        let inputs = parse_input_list(&s);
         if inputs.is_empty() {
             MuteInputs::All
         } else {
-            inputs.sort();
             MuteInputs::Specific(inputs)
         }
     }
@@ -332,6 +1009,50 @@ impl Config {
             errors.push(ValidationError::InvalidColor(e.to_string()));
         }
 
+        if !self.external_mute_color.trim().is_empty()
+            && let Err(e) = crate::led::parse_color(&self.external_mute_color)
+        {
+            errors.push(ValidationError::InvalidExternalMuteColor(e.to_string()));
+        }
+
+        if !self.busylight_live_color.trim().is_empty()
+            && let Err(e) = crate::led::parse_color(&self.busylight_live_color)
+        {
+            errors.push(ValidationError::InvalidBusylightColor(e.to_string()));
+        }
+
+        if !self.reconnect_flash_color.trim().is_empty()
+            && let Err(e) = crate::led::parse_color(&self.reconnect_flash_color)
+        {
+            errors.push(ValidationError::InvalidReconnectFlashColor(e.to_string()));
+        }
+
+        if !self.in_use_color.trim().is_empty()
+            && let Err(e) = crate::led::parse_color(&self.in_use_color)
+        {
+            errors.push(ValidationError::InvalidInUseColor(e.to_string()));
+        }
+
+        if !self.call_mute_color.trim().is_empty()
+            && let Err(e) = crate::led::parse_color(&self.call_mute_color)
+        {
+            errors.push(ValidationError::InvalidCallMuteColor(e.to_string()));
+        }
+
+        if !self.color_preset.trim().is_empty()
+            && crate::led::find_color_preset(&self.color_preset).is_none()
+        {
+            errors.push(ValidationError::UnknownColorPreset(
+                self.color_preset.clone(),
+            ));
+        }
+
+        if !self.instance_name.is_empty()
+            && let Err(e) = crate::instance::validate(&self.instance_name)
+        {
+            errors.push(ValidationError::InvalidInstanceName(e));
+        }
+
         // Validate hotkey
         if self.hotkey.trim().is_empty() {
             errors.push(ValidationError::EmptyHotkey);
@@ -351,6 +1072,15 @@ impl Config {
             });
         }
 
+        for (field, value) in [
+            ("io_timeout_ms", self.io_timeout_ms),
+            ("notify_timeout_ms", self.notify_timeout_ms),
+        ] {
+            if value == 0 {
+                errors.push(ValidationError::ZeroTimeout(field));
+            }
+        }
+
         // Validate mute inputs if input count is known
         if let Some(count) = input_count
             && let Err(e) = self.validate_mute_inputs(count)
@@ -358,36 +1088,148 @@ impl Config {
             errors.push(ValidationError::InvalidMuteInputs(e.to_string()));
         }
 
-        // Validate input_colors entries
-        for (key, value) in &self.input_colors {
-            if let Err(e) = crate::led::parse_color(value) {
-                errors.push(ValidationError::InvalidInputColor {
+        // Validate input_colors and input_live_colors entries
+        for (field, colors) in [
+            ("input_colors", &self.input_colors),
+            ("input_live_colors", &self.input_live_colors),
+        ] {
+            for (key, value) in colors {
+                let invalid = |reason: String| ValidationError::InvalidInputColor {
+                    field,
                     input: key.clone(),
-                    reason: e.to_string(),
-                });
-            }
-            if let Some(count) = input_count {
-                match key.parse::<usize>() {
-                    Ok(n) if n >= 1 && n <= count => {}
-                    Ok(n) => {
-                        errors.push(ValidationError::InvalidInputColor {
-                            input: key.clone(),
-                            reason: format!(
-                                "input {n} is out of range (device has {count} input{})",
-                                if count == 1 { "" } else { "s" }
-                            ),
-                        });
-                    }
-                    Err(_) => {
-                        errors.push(ValidationError::InvalidInputColor {
-                            input: key.clone(),
-                            reason: format!("key must be a 1-based input number, got \"{key}\""),
-                        });
+                    reason,
+                };
+                if let Err(e) = crate::led::parse_color(value) {
+                    errors.push(invalid(e.to_string()));
+                }
+                if let Some(count) = input_count {
+                    match key.parse::<usize>() {
+                        Ok(n) if n >= 1 && n <= count => {}
+                        Ok(n) => errors.push(invalid(format!(
+                            "input {n} is out of range (device has {count} input{})",
+                            if count == 1 { "" } else { "s" }
+                        ))),
+                        Err(_) => errors.push(invalid(format!(
+                            "key must be a 1-based input number, got \"{key}\""
+                        ))),
                     }
                 }
             }
         }
 
+        if !self.mute_button.trim().is_empty()
+            && DeviceEvent::from_button_name(&self.mute_button).is_none()
+        {
+            errors.push(ValidationError::InvalidMuteButton(self.mute_button.clone()));
+        } else if self.mute_button_sync
+            && !matches!(
+                DeviceEvent::from_button_name(&self.mute_button),
+                Some(DeviceEvent::ButtonPressed(
+                    DeviceButton::Air | DeviceButton::Inst
+                ))
+            )
+        {
+            errors.push(ValidationError::MuteButtonNotSwitch(
+                self.mute_button.clone(),
+            ));
+        }
+
+        if MuteAnimation::parse(&self.mute_animation).is_none() {
+            errors.push(ValidationError::InvalidMuteAnimation(
+                self.mute_animation.clone(),
+            ));
+        }
+        if !(MIN_ANIMATION_SPEED..=MAX_ANIMATION_SPEED).contains(&self.animation_speed) {
+            errors.push(ValidationError::InvalidAnimationSpeed(self.animation_speed));
+        }
+        if !(MIN_TALK_THRESHOLD_DB..=MAX_TALK_THRESHOLD_DB)
+            .contains(&self.talk_warning_threshold_db)
+        {
+            errors.push(ValidationError::InvalidTalkWarningThreshold(
+                self.talk_warning_threshold_db,
+            ));
+        }
+        if !(MIN_TALK_SECS..=MAX_TALK_SECS).contains(&self.talk_warning_secs) {
+            errors.push(ValidationError::InvalidTalkWarningSecs(
+                self.talk_warning_secs,
+            ));
+        }
+        if self.output_duck_db > MAX_OUTPUT_DUCK_DB {
+            errors.push(ValidationError::InvalidOutputDuck(self.output_duck_db));
+        }
+        if AudioBackend::parse(&self.audio_backend).is_none() {
+            errors.push(ValidationError::InvalidAudioBackend(
+                self.audio_backend.clone(),
+            ));
+        }
+        if ReconnectResync::parse(&self.reconnect_resync).is_none() {
+            errors.push(ValidationError::InvalidReconnectResync(
+                self.reconnect_resync.clone(),
+            ));
+        }
+        if GainHotkeyTarget::parse(&self.gain_hotkey_target).is_none() {
+            errors.push(ValidationError::InvalidGainHotkeyTarget(
+                self.gain_hotkey_target.clone(),
+            ));
+        }
+        if !(MIN_GAIN_HOTKEY_STEP_DB..=MAX_GAIN_HOTKEY_STEP_DB).contains(&self.gain_hotkey_step_db)
+        {
+            errors.push(ValidationError::InvalidGainHotkeyStep(
+                self.gain_hotkey_step_db,
+            ));
+        }
+        if !self.mqtt_broker.trim().is_empty() && self.mqtt_broker().is_none() {
+            errors.push(ValidationError::InvalidMqttBroker(self.mqtt_broker.clone()));
+        }
+        for (field, value, parsed) in [
+            ("osc_target", &self.osc_target, self.osc_target()),
+            ("osc_listen", &self.osc_listen, self.osc_listen()),
+        ] {
+            if !value.trim().is_empty() && parsed.is_none() {
+                errors.push(ValidationError::InvalidOscAddress {
+                    field,
+                    value: value.clone(),
+                });
+            }
+        }
+        if self.websocket_port != 0 && self.websocket_token.trim().is_empty() {
+            errors.push(ValidationError::MissingWebsocketToken);
+        }
+        let prefix = self.mqtt_topic_prefix.trim_matches('/');
+        if prefix.is_empty() || prefix.contains(['+', '#']) {
+            errors.push(ValidationError::InvalidMqttTopicPrefix(
+                self.mqtt_topic_prefix.clone(),
+            ));
+        }
+
+        for name in self.device_hooks.keys() {
+            if DeviceEvent::from_hook_name(name).is_none() {
+                errors.push(ValidationError::UnknownDeviceHook(name.clone()));
+            }
+        }
+
+        for (name, entries) in &self.led_patterns {
+            if let Err(reason) = crate::led::parse_led_pattern(entries) {
+                errors.push(ValidationError::InvalidLedPattern {
+                    name: name.clone(),
+                    reason,
+                });
+            }
+        }
+        for (field, name) in [
+            ("mute_pattern", &self.mute_pattern),
+            ("live_pattern", &self.live_pattern),
+            ("disconnected_pattern", &self.disconnected_pattern),
+        ] {
+            let name = name.trim();
+            if !name.is_empty() && !self.led_patterns.contains_key(name) {
+                errors.push(ValidationError::UnknownLedPattern {
+                    field,
+                    name: name.to_string(),
+                });
+            }
+        }
+
         if errors.is_empty() {
             Ok(())
         } else {
@@ -395,25 +1237,189 @@ impl Config {
         }
     }
 
+    /// The `mute_animation` to run, or static if it's unknown or
+    /// `reduce_motion` is on (`reduce_motion` is the OS detection for "auto").
+    pub fn mute_animation(&self, reduce_motion: impl FnOnce() -> Option<bool>) -> MuteAnimation {
+        if resolve_os_preference(&self.reduce_motion, reduce_motion) {
+            return MuteAnimation::Static;
+        }
+        MuteAnimation::parse(&self.mute_animation).unwrap_or_default()
+    }
+
+    /// The `call_applications` entry among `capturing` (the applications
+    /// recording from the microphone), if the call profile is on.
+    pub fn call_application<'a>(&self, capturing: &'a [String]) -> Option<&'a str> {
+        if !self.call_profile_enabled {
+            return None;
+        }
+        capturing
+            .iter()
+            .find(|app| crate::audio::matches_application(&self.call_applications, app))
+            .map(String::as_str)
+    }
+
+    /// These settings with the call profile applied.
+    pub fn during_call(&self) -> Config {
+        let mut config = self.clone();
+        if !self.call_mute_color.trim().is_empty() {
+            config.mute_color = self.call_mute_color.clone();
+            config.color_preset.clear();
+            config.input_colors.clear();
+        }
+        config.talk_warning_enabled |= self.call_talk_warning;
+        config
+    }
+
+    /// Talk-while-muted detector for `talk_warning_threshold_db` and
+    /// `talk_warning_secs` (out-of-range values are clamped).
+    pub fn talk_detector(&self) -> TalkDetector {
+        let secs = if self.talk_warning_secs.is_finite() {
+            self.talk_warning_secs.clamp(MIN_TALK_SECS, MAX_TALK_SECS)
+        } else {
+            default_talk_warning_secs()
+        };
+        let db = if self.talk_warning_threshold_db.is_finite() {
+            self.talk_warning_threshold_db
+                .clamp(MIN_TALK_THRESHOLD_DB, MAX_TALK_THRESHOLD_DB)
+        } else {
+            default_talk_warning_threshold_db()
+        };
+        TalkDetector::new(db, Duration::from_secs_f64(secs))
+    }
+
+    /// The idle screensaver for `idle_screensaver_mins`, idle from `now`
+    /// (`None` when it's off).
+    pub fn idle_screensaver(&self, now: Instant) -> Option<IdleScreensaver> {
+        (self.idle_screensaver_mins > 0).then(|| {
+            IdleScreensaver::new(
+                Duration::from_secs(self.idle_screensaver_mins.saturating_mul(60)),
+                now,
+            )
+        })
+    }
+
+    /// The `audio_backend` to use, or auto if it's unknown.
+    pub fn audio_backend(&self) -> AudioBackend {
+        AudioBackend::parse(&self.audio_backend).unwrap_or_default()
+    }
+
+    /// The `reconnect_resync` mode, or silent if it's unknown.
+    pub fn reconnect_resync(&self) -> ReconnectResync {
+        ReconnectResync::parse(&self.reconnect_resync).unwrap_or_default()
+    }
+
+    /// The `gain_hotkey_target`, or the OS volume if it's unknown.
+    pub fn gain_hotkey_target(&self) -> GainHotkeyTarget {
+        GainHotkeyTarget::parse(&self.gain_hotkey_target).unwrap_or_default()
+    }
+
+    /// Host and port of `mqtt_broker`; `None` while it's empty or invalid.
+    pub fn mqtt_broker(&self) -> Option<(String, u16)> {
+        parse_host_port(&self.mqtt_broker, MQTT_DEFAULT_PORT)
+    }
+
+    /// Host and port of `osc_target`; `None` while it's empty or invalid.
+    pub fn osc_target(&self) -> Option<(String, u16)> {
+        parse_host_port(&self.osc_target, 0).filter(|&(_, port)| port != 0)
+    }
+
+    /// Host and port of `osc_listen`; `None` while it's empty or invalid.
+    pub fn osc_listen(&self) -> Option<(String, u16)> {
+        parse_host_port(&self.osc_listen, 0).filter(|&(_, port)| port != 0)
+    }
+
+    /// Device I/O options from `io_timeout_ms`, `notify_timeout_ms`, and
+    /// `io_retries`. A zero timeout falls back to the built-in default.
+    pub fn device_options(&self) -> DeviceOptions {
+        let ms = |value: u64, default: Duration| {
+            if value == 0 {
+                default
+            } else {
+                Duration::from_millis(value)
+            }
+        };
+        DeviceOptions {
+            transact_timeout: ms(self.io_timeout_ms, DeviceOptions::DEFAULT_TRANSACT_TIMEOUT),
+            notify_timeout: ms(
+                self.notify_timeout_ms,
+                DeviceOptions::DEFAULT_NOTIFY_TIMEOUT,
+            ),
+            retries: self.io_retries,
+        }
+    }
+
     /// Validate mute_inputs against a model's input count.
-    /// Returns an error if any input number exceeds the model's capacity.
+    /// Returns an error if any input number exceeds the model's capacity, or if
+    /// an `"all except"` selection excludes every input.
     pub fn validate_mute_inputs(&self, input_count: usize) -> crate::error::Result<()> {
-        match self.parse_mute_inputs() {
-            MuteInputs::All => Ok(()),
-            MuteInputs::Specific(inputs) => {
-                for &idx in &inputs {
-                    if idx >= input_count {
-                        return Err(crate::FocusmuteError::Config(format!(
-                            "Input {} is out of range (device has {} input{})",
-                            idx + 1,
-                            input_count,
-                            if input_count == 1 { "" } else { "s" }
-                        )));
-                    }
-                }
-                Ok(())
+        let plural = if input_count == 1 { "" } else { "s" };
+        let (inputs, excluding) = match self.parse_mute_inputs() {
+            MuteInputs::All => return Ok(()),
+            MuteInputs::Specific(inputs) => (inputs, false),
+            MuteInputs::AllExcept(inputs) => (inputs, true),
+        };
+        for &idx in &inputs {
+            if idx >= input_count {
+                return Err(crate::FocusmuteError::Config(format!(
+                    "Input {} is out of range (device has {input_count} input{plural})",
+                    idx + 1,
+                )));
             }
         }
+        if excluding && inputs.len() >= input_count {
+            return Err(crate::FocusmuteError::Config(format!(
+                "mute_inputs excludes every input (device has {input_count} input{plural})"
+            )));
+        }
+        Ok(())
+    }
+}
+
+// ── Save throttling ──
+
+/// Coalesces bursts of config changes into one write.
+///
+/// Call [`mark`](Self::mark) on every change and save when [`due`](Self::due)
+/// says so: after `quiet` with no further changes, or `max_delay` after the
+/// first unsaved change so a steady stream of edits still gets written.
+#[derive(Debug)]
+pub struct SaveThrottle {
+    quiet: Duration,
+    max_delay: Duration,
+    /// (first unsaved change, latest change)
+    pending: Option<(Instant, Instant)>,
+}
+
+impl SaveThrottle {
+    pub fn new(quiet: Duration, max_delay: Duration) -> Self {
+        Self {
+            quiet,
+            max_delay,
+            pending: None,
+        }
+    }
+
+    /// Record a change that needs saving.
+    pub fn mark(&mut self, now: Instant) {
+        let first = self.pending.map_or(now, |(first, _)| first);
+        self.pending = Some((first, now));
+    }
+
+    /// `true` if there are unsaved changes.
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// `true` if pending changes should be written now.
+    pub fn due(&self, now: Instant) -> bool {
+        self.pending.is_some_and(|(first, last)| {
+            now.duration_since(last) >= self.quiet || now.duration_since(first) >= self.max_delay
+        })
+    }
+
+    /// Forget pending changes (call after saving).
+    pub fn clear(&mut self) {
+        self.pending = None;
     }
 }
 
@@ -440,6 +1446,12 @@ mod tests {
         assert_eq!(inputs.to_string(), "1 (per-input)");
     }
 
+    #[test]
+    fn display_mute_inputs_all_except() {
+        let inputs = MuteInputs::AllExcept(vec![1, 3]);
+        assert_eq!(inputs.to_string(), "all except 2, 4");
+    }
+
     // ── Config defaults ──
 
     #[test]
@@ -450,6 +1462,7 @@ mod tests {
         assert!(c.sound_enabled);
         assert!(!c.autostart);
         assert_eq!(c.mute_inputs, "all");
+        assert!(c.idle_screensaver(Instant::now()).is_none());
     }
 
     #[test]
@@ -597,71 +1610,167 @@ autostart = false
             mute_inputs: "1".into(),
             ..Config::default()
         };
-        assert_eq!(c.parse_mute_inputs(), MuteInputs::Specific(vec![0]));
+        assert_eq!(c.parse_mute_inputs(), MuteInputs::Specific(vec![0]));
+    }
+
+    #[test]
+    fn parse_mute_inputs_single_input_2() {
+        let c = Config {
+            mute_inputs: "2".into(),
+            ..Config::default()
+        };
+        assert_eq!(c.parse_mute_inputs(), MuteInputs::Specific(vec![1]));
+    }
+
+    #[test]
+    fn parse_mute_inputs_multiple() {
+        let c = Config {
+            mute_inputs: "1,2".into(),
+            ..Config::default()
+        };
+        assert_eq!(c.parse_mute_inputs(), MuteInputs::Specific(vec![0, 1]));
+    }
+
+    #[test]
+    fn parse_mute_inputs_with_spaces() {
+        let c = Config {
+            mute_inputs: " 1 , 2 ".into(),
+            ..Config::default()
+        };
+        assert_eq!(c.parse_mute_inputs(), MuteInputs::Specific(vec![0, 1]));
+    }
+
+    #[test]
+    fn parse_mute_inputs_deduplicates() {
+        let c = Config {
+            mute_inputs: "1,1,2,2".into(),
+            ..Config::default()
+        };
+        assert_eq!(c.parse_mute_inputs(), MuteInputs::Specific(vec![0, 1]));
+    }
+
+    #[test]
+    fn parse_mute_inputs_sorts() {
+        let c = Config {
+            mute_inputs: "2,1".into(),
+            ..Config::default()
+        };
+        assert_eq!(c.parse_mute_inputs(), MuteInputs::Specific(vec![0, 1]));
+    }
+
+    #[test]
+    fn parse_mute_inputs_zero_ignored() {
+        // 0 is invalid (1-based), so it should be ignored
+        let c = Config {
+            mute_inputs: "0".into(),
+            ..Config::default()
+        };
+        assert_eq!(c.parse_mute_inputs(), MuteInputs::All);
+    }
+
+    #[test]
+    fn parse_mute_inputs_garbage_is_all() {
+        let c = Config {
+            mute_inputs: "abc".into(),
+            ..Config::default()
+        };
+        assert_eq!(c.parse_mute_inputs(), MuteInputs::All);
     }
 
     #[test]
-    fn parse_mute_inputs_single_input_2() {
+    fn parse_mute_inputs_range() {
         let c = Config {
-            mute_inputs: "2".into(),
+            mute_inputs: "1-4,7".into(),
             ..Config::default()
         };
-        assert_eq!(c.parse_mute_inputs(), MuteInputs::Specific(vec![1]));
+        assert_eq!(
+            c.parse_mute_inputs(),
+            MuteInputs::Specific(vec![0, 1, 2, 3, 6])
+        );
     }
 
     #[test]
-    fn parse_mute_inputs_multiple() {
+    fn parse_mute_inputs_overlapping_ranges() {
         let c = Config {
-            mute_inputs: "1,2".into(),
+            mute_inputs: "5 - 6, 2-5".into(),
             ..Config::default()
         };
-        assert_eq!(c.parse_mute_inputs(), MuteInputs::Specific(vec![0, 1]));
+        assert_eq!(
+            c.parse_mute_inputs(),
+            MuteInputs::Specific(vec![1, 2, 3, 4, 5])
+        );
     }
 
     #[test]
-    fn parse_mute_inputs_with_spaces() {
+    fn parse_mute_inputs_bad_ranges_ignored() {
+        for s in ["4-1", "0-2", "1-", "-3", "1-x"] {
+            let c = Config {
+                mute_inputs: s.into(),
+                ..Config::default()
+            };
+            assert_eq!(c.parse_mute_inputs(), MuteInputs::All, "input {s:?}");
+        }
+    }
+
+    #[test]
+    fn parse_mute_inputs_huge_range_is_clamped() {
         let c = Config {
-            mute_inputs: " 1 , 2 ".into(),
+            mute_inputs: "1-1000000000".into(),
             ..Config::default()
         };
-        assert_eq!(c.parse_mute_inputs(), MuteInputs::Specific(vec![0, 1]));
+        match c.parse_mute_inputs() {
+            MuteInputs::Specific(v) => assert_eq!(v.len(), MAX_RANGE_INPUT),
+            other => panic!("expected Specific, got {other:?}"),
+        }
     }
 
     #[test]
-    fn parse_mute_inputs_deduplicates() {
+    fn validate_range_beyond_input_count_fails() {
         let c = Config {
-            mute_inputs: "1,1,2,2".into(),
+            mute_inputs: "1-4".into(),
             ..Config::default()
         };
-        assert_eq!(c.parse_mute_inputs(), MuteInputs::Specific(vec![0, 1]));
+        assert!(c.validate_mute_inputs(8).is_ok());
+        assert!(c.validate_mute_inputs(2).is_err());
     }
 
     #[test]
-    fn parse_mute_inputs_sorts() {
+    fn parse_mute_inputs_all_except() {
         let c = Config {
-            mute_inputs: "2,1".into(),
+            mute_inputs: "All Except 2, 5-6".into(),
             ..Config::default()
         };
-        assert_eq!(c.parse_mute_inputs(), MuteInputs::Specific(vec![0, 1]));
+        assert_eq!(c.parse_mute_inputs(), MuteInputs::AllExcept(vec![1, 4, 5]));
     }
 
     #[test]
-    fn parse_mute_inputs_zero_ignored() {
-        // 0 is invalid (1-based), so it should be ignored
+    fn parse_mute_inputs_all_except_nothing_is_all() {
+        for s in ["all except", "all except 0", "all except x"] {
+            let c = Config {
+                mute_inputs: s.into(),
+                ..Config::default()
+            };
+            assert_eq!(c.parse_mute_inputs(), MuteInputs::All, "input {s:?}");
+        }
+    }
+
+    #[test]
+    fn validate_all_except() {
         let c = Config {
-            mute_inputs: "0".into(),
+            mute_inputs: "all except 2".into(),
             ..Config::default()
         };
-        assert_eq!(c.parse_mute_inputs(), MuteInputs::All);
-    }
+        assert!(c.validate_mute_inputs(8).is_ok());
+        assert!(c.validate_mute_inputs(2).is_ok());
+        // Excluding the only input leaves nothing to indicate
+        assert!(c.validate_mute_inputs(1).is_err());
 
-    #[test]
-    fn parse_mute_inputs_garbage_is_all() {
         let c = Config {
-            mute_inputs: "abc".into(),
+            mute_inputs: "all except 1-2".into(),
             ..Config::default()
         };
-        assert_eq!(c.parse_mute_inputs(), MuteInputs::All);
+        let err = c.validate_mute_inputs(2).unwrap_err();
+        assert!(err.to_string().contains("excludes every input"));
     }
 
     // ── validate_mute_inputs ──
@@ -949,7 +2058,66 @@ mute_inputs = "all"
                 ("1".into(), "#FF0000".into()),
                 ("2".into(), "#0000FF".into()),
             ]),
+            input_live_colors: HashMap::from([("2".into(), "cyan".into())]),
             notifications_enabled: true,
+            history_enabled: true,
+            daily_summary_enabled: true,
+            startup_wait_for_device_secs: 30,
+            external_mute_color: "#0000FF".into(),
+            mute_preset: "output-meter".into(),
+            do_not_disturb: "on".into(),
+            reduce_motion: "off".into(),
+            io_timeout_ms: 3000,
+            io_retries: 2,
+            notify_timeout_ms: 250,
+            indicator_only: true,
+            control_only: true,
+            excluded_capture_devices: vec!["CABLE Output".into()],
+            audio_backend: "pipewire".into(),
+            mute_all_capture_devices: true,
+            mute_applications: vec!["Discord".into()],
+            device_hooks: HashMap::from([("on_phantom_changed".into(), "echo 48v".into())]),
+            mute_button: "direct_monitoring".into(),
+            mute_button_sync: true,
+            mute_animation: "pulse".into(),
+            animation_speed: 1.5,
+            busylight_enabled: true,
+            busylight_live_color: "#00FF00".into(),
+            instance_name: "seat2".into(),
+            led_patterns: HashMap::from([(
+                "onair".into(),
+                HashMap::from([("16-26".into(), "red".into())]),
+            )]),
+            mute_pattern: "onair".into(),
+            live_pattern: String::new(),
+            disconnected_pattern: "onair".into(),
+            color_preset: "deuteranopia".into(),
+            talk_warning_enabled: true,
+            talk_warning_threshold_db: -24.0,
+            talk_warning_secs: 3.5,
+            reconnect_resync: "flash".into(),
+            reconnect_flash_color: "#00FF00".into(),
+            in_use_color: "green".into(),
+            call_profile_enabled: true,
+            call_applications: vec!["Zoom".into()],
+            call_unmute: true,
+            call_mute_color: "orange".into(),
+            call_talk_warning: false,
+            output_duck_db: 20,
+            gain_up_hotkey: "Ctrl+Shift+Up".into(),
+            gain_down_hotkey: "Ctrl+Shift+Down".into(),
+            gain_hotkey_target: "hardware".into(),
+            gain_hotkey_step_db: 3,
+            mqtt_broker: "broker.lan:1884".into(),
+            mqtt_topic_prefix: "office/focusmute".into(),
+            mqtt_username: "desk".into(),
+            mqtt_password: "hunter2".into(),
+            osc_target: "127.0.0.1:8000".into(),
+            osc_listen: "127.0.0.1:9001".into(),
+            websocket_port: 9090,
+            websocket_token: "s3cret".into(),
+            vu_mode: true,
+            idle_screensaver_mins: 15,
         };
         let toml_str = toml::to_string_pretty(&config).unwrap();
         let loaded: Config = toml::from_str(&toml_str).unwrap();
@@ -964,7 +2132,70 @@ mute_inputs = "all"
         assert_eq!(loaded.on_mute_command, config.on_mute_command);
         assert_eq!(loaded.on_unmute_command, config.on_unmute_command);
         assert_eq!(loaded.input_colors, config.input_colors);
+        assert_eq!(loaded.input_live_colors, config.input_live_colors);
         assert_eq!(loaded.notifications_enabled, config.notifications_enabled);
+        assert_eq!(loaded.history_enabled, config.history_enabled);
+        assert_eq!(loaded.daily_summary_enabled, config.daily_summary_enabled);
+        assert_eq!(
+            loaded.startup_wait_for_device_secs,
+            config.startup_wait_for_device_secs
+        );
+        assert_eq!(loaded.external_mute_color, config.external_mute_color);
+        assert_eq!(loaded.mute_preset, config.mute_preset);
+        assert_eq!(loaded.do_not_disturb, config.do_not_disturb);
+        assert_eq!(loaded.reduce_motion, config.reduce_motion);
+        assert_eq!(loaded.device_options(), config.device_options());
+        assert_eq!(loaded.indicator_only, config.indicator_only);
+        assert_eq!(loaded.control_only, config.control_only);
+        assert_eq!(
+            loaded.excluded_capture_devices,
+            config.excluded_capture_devices
+        );
+        assert_eq!(loaded.audio_backend, config.audio_backend);
+        assert_eq!(
+            loaded.mute_all_capture_devices,
+            config.mute_all_capture_devices
+        );
+        assert_eq!(loaded.mute_applications, config.mute_applications);
+        assert_eq!(loaded.device_hooks, config.device_hooks);
+        assert_eq!(loaded.mute_button, config.mute_button);
+        assert_eq!(loaded.mute_button_sync, config.mute_button_sync);
+        assert_eq!(loaded.mute_animation, config.mute_animation);
+        assert_eq!(loaded.animation_speed, config.animation_speed);
+        assert_eq!(loaded.busylight_enabled, config.busylight_enabled);
+        assert_eq!(loaded.busylight_live_color, config.busylight_live_color);
+        assert_eq!(loaded.instance_name, config.instance_name);
+        assert_eq!(loaded.led_patterns, config.led_patterns);
+        assert_eq!(loaded.mute_pattern, config.mute_pattern);
+        assert_eq!(loaded.live_pattern, config.live_pattern);
+        assert_eq!(loaded.disconnected_pattern, config.disconnected_pattern);
+        assert_eq!(loaded.color_preset, config.color_preset);
+        assert!(loaded.talk_warning_enabled);
+        assert_eq!(loaded.talk_warning_threshold_db, -24.0);
+        assert_eq!(loaded.talk_warning_secs, 3.5);
+        assert_eq!(loaded.reconnect_resync(), ReconnectResync::Flash);
+        assert!(loaded.vu_mode);
+        assert_eq!(loaded.reconnect_flash_color, "#00FF00");
+        assert_eq!(loaded.in_use_color, "green");
+        assert_eq!(loaded.call_profile_enabled, config.call_profile_enabled);
+        assert_eq!(loaded.call_applications, config.call_applications);
+        assert_eq!(loaded.call_unmute, config.call_unmute);
+        assert_eq!(loaded.call_mute_color, config.call_mute_color);
+        assert_eq!(loaded.call_talk_warning, config.call_talk_warning);
+        assert_eq!(loaded.output_duck_db, config.output_duck_db);
+        assert_eq!(loaded.gain_up_hotkey, config.gain_up_hotkey);
+        assert_eq!(loaded.gain_down_hotkey, config.gain_down_hotkey);
+        assert_eq!(loaded.gain_hotkey_target, config.gain_hotkey_target);
+        assert_eq!(loaded.gain_hotkey_step_db, config.gain_hotkey_step_db);
+        assert_eq!(loaded.mqtt_broker, config.mqtt_broker);
+        assert_eq!(loaded.mqtt_topic_prefix, config.mqtt_topic_prefix);
+        assert_eq!(loaded.mqtt_username, config.mqtt_username);
+        assert_eq!(loaded.mqtt_password, config.mqtt_password);
+        assert_eq!(loaded.osc_target, config.osc_target);
+        assert_eq!(loaded.osc_listen, config.osc_listen);
+        assert_eq!(loaded.websocket_port, config.websocket_port);
+        assert_eq!(loaded.websocket_token, config.websocket_token);
+        assert_eq!(loaded.idle_screensaver_mins, 15);
     }
 
     #[test]
@@ -980,6 +2211,336 @@ mute_inputs = "all"
         assert!(c.device_serial.is_empty());
     }
 
+    #[test]
+    fn backward_compat_old_toml_without_history_fields() {
+        let c: Config = toml::from_str("mute_color = \"#FF0000\"\n").unwrap();
+        assert!(!c.history_enabled);
+        assert!(!c.daily_summary_enabled);
+    }
+
+    #[test]
+    fn validate_external_mute_color() {
+        let ok = Config {
+            external_mute_color: "blue".into(),
+            ..Config::default()
+        };
+        assert!(ok.validate(None, 10_000_000).is_ok());
+
+        let bad = Config {
+            external_mute_color: "not-a-color".into(),
+            ..Config::default()
+        };
+        let errs = bad.validate(None, 10_000_000).unwrap_err();
+        assert!(
+            errs.iter()
+                .any(|e| matches!(e, ValidationError::InvalidExternalMuteColor(_)))
+        );
+    }
+
+    #[test]
+    fn validate_busylight_live_color() {
+        let mut c = Config {
+            busylight_enabled: true,
+            busylight_live_color: "green".into(),
+            ..Config::default()
+        };
+        assert!(c.validate(None, 10_000_000).is_ok());
+        c.busylight_live_color = "not-a-color".into();
+        let errs = c.validate(None, 10_000_000).unwrap_err();
+        assert!(matches!(
+            errs.as_slice(),
+            [ValidationError::InvalidBusylightColor(_)]
+        ));
+    }
+
+    #[test]
+    fn validate_reconnect_flash_color() {
+        let mut c = Config {
+            reconnect_flash_color: "green".into(),
+            ..Config::default()
+        };
+        assert!(c.validate(None, 10_000_000).is_ok());
+        assert_eq!(crate::led::reconnect_flash_color(&c), Some(0x00FF_0000));
+        c.reconnect_flash_color = "not-a-color".into();
+        let errs = c.validate(None, 10_000_000).unwrap_err();
+        assert!(matches!(
+            errs.as_slice(),
+            [ValidationError::InvalidReconnectFlashColor(_)]
+        ));
+        assert_eq!(crate::led::reconnect_flash_color(&c), None);
+        c.reconnect_flash_color = String::new();
+        assert_eq!(crate::led::reconnect_flash_color(&c), None);
+    }
+
+    #[test]
+    fn validate_in_use_color() {
+        let mut c = Config {
+            in_use_color: "#00FF00".into(),
+            ..Config::default()
+        };
+        assert!(c.validate(None, 10_000_000).is_ok());
+        assert_eq!(crate::led::in_use_color(&c), Some(0x00FF_0000));
+        c.in_use_color = "not-a-color".into();
+        let errs = c.validate(None, 10_000_000).unwrap_err();
+        assert!(matches!(
+            errs.as_slice(),
+            [ValidationError::InvalidInUseColor(_)]
+        ));
+        assert_eq!(crate::led::in_use_color(&Config::default()), None);
+    }
+
+    #[test]
+    fn validate_call_mute_color() {
+        let mut c = Config {
+            call_mute_color: "orange".into(),
+            ..Config::default()
+        };
+        assert!(c.validate(None, 10_000_000).is_ok());
+        c.call_mute_color = "not-a-color".into();
+        let errs = c.validate(None, 10_000_000).unwrap_err();
+        assert!(matches!(
+            errs.as_slice(),
+            [ValidationError::InvalidCallMuteColor(_)]
+        ));
+    }
+
+    #[test]
+    fn call_profile_matches_and_overrides() {
+        let capturing = vec!["/usr/bin/obs".to_string(), r"C:\Zoom\bin\Zoom.exe".into()];
+        let mut c = Config::default();
+        assert_eq!(c.call_application(&capturing), None);
+        c.call_profile_enabled = true;
+        assert_eq!(c.call_application(&capturing), Some(capturing[1].as_str()));
+        assert_eq!(c.call_application(&capturing[..1]), None);
+
+        c.color_preset = "deuteranopia".into();
+        c.input_colors = HashMap::from([("1".into(), "blue".into())]);
+        // No call color: the colors stay, the talk warning comes on
+        let call = c.during_call();
+        assert_eq!(call.color_preset, "deuteranopia");
+        assert!(call.talk_warning_enabled);
+        c.call_mute_color = "orange".into();
+        c.call_talk_warning = false;
+        let call = c.during_call();
+        assert_eq!(call.mute_color, "orange");
+        assert!(call.color_preset.is_empty() && call.input_colors.is_empty());
+        assert!(!call.talk_warning_enabled);
+    }
+
+    #[test]
+    fn validate_instance_name() {
+        let mut c = Config {
+            instance_name: "seat-2".into(),
+            ..Config::default()
+        };
+        assert!(c.validate(None, 10_000_000).is_ok());
+        c.instance_name = "seat 2".into();
+        let errs = c.validate(None, 10_000_000).unwrap_err();
+        assert!(matches!(
+            errs.as_slice(),
+            [ValidationError::InvalidInstanceName(_)]
+        ));
+    }
+
+    #[test]
+    fn validate_color_preset() {
+        let mut c = Config {
+            color_preset: "high-contrast".into(),
+            ..Config::default()
+        };
+        assert!(c.validate(None, 10_000_000).is_ok());
+        c.color_preset = "rainbow".into();
+        let errs = c.validate(None, 10_000_000).unwrap_err();
+        assert_eq!(
+            errs,
+            [ValidationError::UnknownColorPreset("rainbow".into())]
+        );
+        assert!(errs[0].to_string().contains("deuteranopia"));
+    }
+
+    #[test]
+    fn validate_talk_warning_ranges() {
+        let mut c = Config {
+            talk_warning_threshold_db: -60.0,
+            talk_warning_secs: 30.0,
+            ..Config::default()
+        };
+        assert!(c.validate(None, 10_000_000).is_ok());
+        c.talk_warning_threshold_db = 3.0;
+        c.talk_warning_secs = 0.1;
+        assert_eq!(
+            c.validate(None, 10_000_000).unwrap_err(),
+            [
+                ValidationError::InvalidTalkWarningThreshold(3.0),
+                ValidationError::InvalidTalkWarningSecs(0.1),
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_output_duck() {
+        let mut c = Config {
+            output_duck_db: 127,
+            ..Config::default()
+        };
+        assert!(c.validate(None, 10_000_000).is_ok());
+        c.output_duck_db = 128;
+        assert_eq!(
+            c.validate(None, 10_000_000).unwrap_err(),
+            [ValidationError::InvalidOutputDuck(128)]
+        );
+    }
+
+    #[test]
+    fn validate_mqtt() {
+        let mut c = Config {
+            mqtt_broker: "mqtt://homeassistant.local".into(),
+            ..Config::default()
+        };
+        assert!(c.validate(None, 10_000_000).is_ok());
+        assert_eq!(
+            c.mqtt_broker(),
+            Some(("homeassistant.local".into(), MQTT_DEFAULT_PORT))
+        );
+        assert_eq!(
+            parse_host_port("10.0.0.2:8883", MQTT_DEFAULT_PORT),
+            Some(("10.0.0.2".into(), 8883))
+        );
+        c.mqtt_broker = "broker:http".into();
+        c.mqtt_topic_prefix = "home/#".into();
+        assert_eq!(
+            c.validate(None, 10_000_000).unwrap_err(),
+            [
+                ValidationError::InvalidMqttBroker("broker:http".into()),
+                ValidationError::InvalidMqttTopicPrefix("home/#".into()),
+            ]
+        );
+        assert_eq!(c.mqtt_broker(), None);
+    }
+
+    #[test]
+    fn validate_osc_addresses() {
+        let mut c = Config {
+            osc_target: "192.168.1.20:8000".into(),
+            osc_listen: "0.0.0.0:9001".into(),
+            ..Config::default()
+        };
+        assert!(c.validate(None, 10_000_000).is_ok());
+        assert_eq!(c.osc_listen(), Some(("0.0.0.0".into(), 9001)));
+        // OSC has no default port
+        c.osc_target = "mixer.local".into();
+        assert_eq!(
+            c.validate(None, 10_000_000).unwrap_err(),
+            [ValidationError::InvalidOscAddress {
+                field: "osc_target",
+                value: "mixer.local".into()
+            }]
+        );
+        assert_eq!(c.osc_target(), None);
+    }
+
+    #[test]
+    fn validate_websocket_token() {
+        let mut c = Config {
+            websocket_port: 8765,
+            ..Config::default()
+        };
+        assert_eq!(
+            c.validate(None, 10_000_000).unwrap_err(),
+            [ValidationError::MissingWebsocketToken]
+        );
+        c.websocket_token = "s3cret".into();
+        assert!(c.validate(None, 10_000_000).is_ok());
+    }
+
+    #[test]
+    fn validate_audio_backend() {
+        let mut c = Config {
+            audio_backend: "PipeWire".into(),
+            ..Config::default()
+        };
+        assert!(c.validate(None, 10_000_000).is_ok());
+        assert_eq!(c.audio_backend(), AudioBackend::PipeWire);
+        c.audio_backend = "jack".into();
+        assert_eq!(
+            c.validate(None, 10_000_000).unwrap_err(),
+            [ValidationError::InvalidAudioBackend("jack".into())]
+        );
+        assert_eq!(c.audio_backend(), AudioBackend::Auto);
+    }
+
+    #[test]
+    fn validate_reconnect_resync() {
+        let mut c = Config {
+            reconnect_resync: "Notify".into(),
+            ..Config::default()
+        };
+        assert!(c.validate(None, 10_000_000).is_ok());
+        assert_eq!(c.reconnect_resync(), ReconnectResync::Notify);
+        c.reconnect_resync = "beep".into();
+        assert_eq!(
+            c.validate(None, 10_000_000).unwrap_err(),
+            [ValidationError::InvalidReconnectResync("beep".into())]
+        );
+        assert_eq!(c.reconnect_resync(), ReconnectResync::Silent);
+    }
+
+    #[test]
+    fn validate_gain_hotkeys() {
+        let mut c = Config {
+            gain_hotkey_target: "Hardware".into(),
+            gain_hotkey_step_db: 20,
+            ..Config::default()
+        };
+        assert!(c.validate(None, 10_000_000).is_ok());
+        assert_eq!(c.gain_hotkey_target(), GainHotkeyTarget::Hardware);
+        c.gain_hotkey_target = "mixer".into();
+        c.gain_hotkey_step_db = 0;
+        assert_eq!(
+            c.validate(None, 10_000_000).unwrap_err(),
+            [
+                ValidationError::InvalidGainHotkeyTarget("mixer".into()),
+                ValidationError::InvalidGainHotkeyStep(0),
+            ]
+        );
+        assert_eq!(c.gain_hotkey_target(), GainHotkeyTarget::Os);
+    }
+
+    #[test]
+    fn validate_led_patterns() {
+        let mut c = Config {
+            led_patterns: HashMap::from([(
+                "onair".into(),
+                HashMap::from([("16-26".into(), "red".into())]),
+            )]),
+            mute_pattern: "onair".into(),
+            ..Config::default()
+        };
+        assert!(c.validate(None, 10_000_000).is_ok());
+        c.live_pattern = "idle".into();
+        c.led_patterns
+            .get_mut("onair")
+            .unwrap()
+            .insert("26-16".into(), "red".into());
+        let errs = c.validate(None, 10_000_000).unwrap_err();
+        assert_eq!(errs.len(), 2, "{errs:?}");
+        assert!(matches!(errs[0], ValidationError::InvalidLedPattern { .. }));
+        assert_eq!(
+            errs[1],
+            ValidationError::UnknownLedPattern {
+                field: "live_pattern",
+                name: "idle".into()
+            }
+        );
+    }
+
+    #[test]
+    fn startup_wait_defaults_to_ten_seconds() {
+        let c: Config = toml::from_str("").unwrap();
+        assert_eq!(c.startup_wait_for_device_secs, 10);
+        assert_eq!(Config::default().startup_wait_for_device_secs, 10);
+    }
+
     // ── input_colors validation ──
 
     #[test]
@@ -1033,6 +2594,23 @@ mute_inputs = "all"
         )));
     }
 
+    #[test]
+    fn validate_input_live_colors() {
+        let c = Config {
+            input_live_colors: HashMap::from([
+                ("1".into(), "cyan".into()),
+                ("3".into(), "#FF00FF".into()),
+            ]),
+            ..Config::default()
+        };
+        let errs = c.validate(Some(2), 10_000_000).unwrap_err();
+        assert!(matches!(
+            errs.as_slice(),
+            [e @ ValidationError::InvalidInputColor { field: "input_live_colors", input, .. }]
+                if input == "3" && e.to_string().starts_with("Invalid input_live_colors[3]")
+        ));
+    }
+
     #[test]
     fn validate_input_colors_key_range_skipped_without_input_count() {
         let c = Config {
@@ -1043,6 +2621,84 @@ mute_inputs = "all"
         assert!(c.validate(None, 10_000_000).is_ok());
     }
 
+    #[test]
+    fn validate_device_hooks_event_names() {
+        let c = Config {
+            device_hooks: HashMap::from([
+                ("on_phantom_changed".into(), "echo 48v".into()),
+                ("on_phantom".into(), "echo typo".into()),
+            ]),
+            ..Config::default()
+        };
+        let errs = c.validate(None, 10_000_000).unwrap_err();
+        assert_eq!(
+            errs,
+            vec![ValidationError::UnknownDeviceHook("on_phantom".into())]
+        );
+    }
+
+    #[test]
+    fn validate_mute_button() {
+        let mut c = Config {
+            mute_button: "air".into(),
+            ..Config::default()
+        };
+        assert!(c.validate(None, 10_000_000).is_ok());
+        c.mute_button = "phantom".into();
+        assert_eq!(
+            c.validate(None, 10_000_000).unwrap_err(),
+            vec![ValidationError::InvalidMuteButton("phantom".into())]
+        );
+        c.mute_button_sync = true;
+        c.mute_button = "inst".into();
+        assert!(c.validate(None, 10_000_000).is_ok());
+        c.mute_button = "select".into();
+        assert_eq!(
+            c.validate(None, 10_000_000).unwrap_err(),
+            vec![ValidationError::MuteButtonNotSwitch("select".into())]
+        );
+    }
+
+    #[test]
+    fn validate_mute_animation() {
+        let mut c = Config {
+            mute_animation: "pulse".into(),
+            animation_speed: 0.5,
+            ..Config::default()
+        };
+        assert!(c.validate(None, 10_000_000).is_ok());
+        c.mute_animation = "strobe".into();
+        c.animation_speed = 0.0;
+        assert_eq!(
+            c.validate(None, 10_000_000).unwrap_err(),
+            vec![
+                ValidationError::InvalidMuteAnimation("strobe".into()),
+                ValidationError::InvalidAnimationSpeed(0.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn mute_animation_honors_reduce_motion() {
+        let mut c = Config {
+            mute_animation: "blink".into(),
+            ..Config::default()
+        };
+        assert_eq!(c.mute_animation(|| None), MuteAnimation::Blink);
+        assert_eq!(c.mute_animation(|| Some(true)), MuteAnimation::Static);
+        c.reduce_motion = "off".into();
+        assert_eq!(c.mute_animation(|| Some(true)), MuteAnimation::Blink);
+        c.mute_animation = "strobe".into();
+        assert_eq!(c.mute_animation(|| None), MuteAnimation::Static);
+    }
+
+    #[test]
+    fn device_hooks_parse_from_toml_table() {
+        let c: Config =
+            toml::from_str("[device_hooks]\non_input_select = \"echo select\"\n").unwrap();
+        assert_eq!(c.device_hooks["on_input_select"], "echo select");
+    }
+
     #[test]
     fn load_ignores_header_comment() {
         // Config with header comment (as produced by save()) should parse fine
@@ -1066,6 +2722,43 @@ notifications_enabled = false
         assert!(!c.sound_enabled);
         assert!(c.autostart);
         assert_eq!(c.mute_inputs, "1,2");
+        // Fields missing from older files default to following the OS
+        assert_eq!(c.do_not_disturb, "auto");
+        assert_eq!(c.reduce_motion, "auto");
+        assert_eq!(c.device_options(), DeviceOptions::default());
+    }
+
+    #[test]
+    fn device_options_from_config() {
+        let c = Config {
+            io_timeout_ms: 2500,
+            io_retries: 7,
+            notify_timeout_ms: 0,
+            ..Config::default()
+        };
+        let options = c.device_options();
+        assert_eq!(options.transact_timeout, Duration::from_millis(2500));
+        assert_eq!(options.retries, 7);
+        assert_eq!(options.attempts(), 8);
+        // Zero falls back to the built-in default, but is reported
+        assert_eq!(
+            options.notify_timeout,
+            DeviceOptions::DEFAULT_NOTIFY_TIMEOUT
+        );
+        let errs = c.validate(None, 10_000_000).unwrap_err();
+        assert!(
+            errs.iter()
+                .any(|e| matches!(e, ValidationError::ZeroTimeout("notify_timeout_ms")))
+        );
+    }
+
+    #[test]
+    fn os_preference_overrides_detection() {
+        assert!(resolve_os_preference("on", || Some(false)));
+        assert!(!resolve_os_preference("OFF", || Some(true)));
+        assert!(resolve_os_preference("auto", || Some(true)));
+        assert!(!resolve_os_preference("auto", || None));
+        assert!(resolve_os_preference("bogus", || Some(true)));
     }
 
     // ── save_to / load_from ──
@@ -1090,7 +2783,63 @@ notifications_enabled = false
                 ("1".into(), "#FF0000".into()),
                 ("2".into(), "#0000FF".into()),
             ]),
+            input_live_colors: HashMap::from([("2".into(), "cyan".into())]),
             notifications_enabled: true,
+            history_enabled: true,
+            daily_summary_enabled: true,
+            startup_wait_for_device_secs: 30,
+            external_mute_color: "#0000FF".into(),
+            mute_preset: "output-meter".into(),
+            do_not_disturb: "on".into(),
+            reduce_motion: "off".into(),
+            io_timeout_ms: 3000,
+            io_retries: 2,
+            notify_timeout_ms: 250,
+            indicator_only: true,
+            control_only: true,
+            excluded_capture_devices: vec!["NVIDIA Broadcast".into()],
+            audio_backend: "pulseaudio".into(),
+            mute_all_capture_devices: true,
+            mute_applications: vec!["discord.exe".into(), "obs".into()],
+            device_hooks: HashMap::from([("on_input_select".into(), "echo select".into())]),
+            mute_button: "select".into(),
+            mute_button_sync: true,
+            mute_animation: "blink".into(),
+            animation_speed: 2.0,
+            busylight_enabled: true,
+            busylight_live_color: "green".into(),
+            instance_name: "studio".into(),
+            led_patterns: HashMap::new(),
+            mute_pattern: String::new(),
+            live_pattern: String::new(),
+            disconnected_pattern: String::new(),
+            color_preset: "streamer-purple".into(),
+            talk_warning_enabled: true,
+            talk_warning_threshold_db: -40.0,
+            talk_warning_secs: 1.0,
+            reconnect_resync: "notify".into(),
+            reconnect_flash_color: "green".into(),
+            in_use_color: "#00FF00".into(),
+            call_profile_enabled: true,
+            call_applications: vec!["Teams".into(), "zoom".into()],
+            call_unmute: true,
+            call_mute_color: "#FFA500".into(),
+            call_talk_warning: false,
+            output_duck_db: 12,
+            gain_up_hotkey: "Alt+Up".into(),
+            gain_down_hotkey: "Alt+Down".into(),
+            gain_hotkey_target: "os".into(),
+            gain_hotkey_step_db: 1,
+            mqtt_broker: "10.0.0.2".into(),
+            mqtt_topic_prefix: "focusmute".into(),
+            mqtt_username: String::new(),
+            mqtt_password: String::new(),
+            osc_target: String::new(),
+            osc_listen: "0.0.0.0:9001".into(),
+            websocket_port: 8765,
+            websocket_token: "other".into(),
+            vu_mode: true,
+            idle_screensaver_mins: 5,
         };
         config.save_to(&path).unwrap();
 
@@ -1107,7 +2856,71 @@ notifications_enabled = false
         assert_eq!(loaded.on_mute_command, config.on_mute_command);
         assert_eq!(loaded.on_unmute_command, config.on_unmute_command);
         assert_eq!(loaded.input_colors, config.input_colors);
+        assert_eq!(loaded.input_live_colors, config.input_live_colors);
         assert_eq!(loaded.notifications_enabled, config.notifications_enabled);
+        assert_eq!(loaded.history_enabled, config.history_enabled);
+        assert_eq!(loaded.daily_summary_enabled, config.daily_summary_enabled);
+        assert_eq!(
+            loaded.startup_wait_for_device_secs,
+            config.startup_wait_for_device_secs
+        );
+        assert_eq!(loaded.external_mute_color, config.external_mute_color);
+        assert_eq!(loaded.mute_preset, config.mute_preset);
+        assert_eq!(loaded.do_not_disturb, config.do_not_disturb);
+        assert_eq!(loaded.reduce_motion, config.reduce_motion);
+        assert_eq!(loaded.device_options(), config.device_options());
+        assert_eq!(loaded.indicator_only, config.indicator_only);
+        assert_eq!(loaded.control_only, config.control_only);
+        assert_eq!(
+            loaded.excluded_capture_devices,
+            config.excluded_capture_devices
+        );
+        assert_eq!(loaded.audio_backend, config.audio_backend);
+        assert_eq!(
+            loaded.mute_all_capture_devices,
+            config.mute_all_capture_devices
+        );
+        assert_eq!(loaded.mute_applications, config.mute_applications);
+        assert_eq!(loaded.device_hooks, config.device_hooks);
+        assert_eq!(loaded.mute_button, config.mute_button);
+        assert_eq!(loaded.mute_button_sync, config.mute_button_sync);
+        assert_eq!(loaded.mute_animation, config.mute_animation);
+        assert_eq!(loaded.animation_speed, config.animation_speed);
+        assert_eq!(loaded.busylight_enabled, config.busylight_enabled);
+        assert_eq!(loaded.busylight_live_color, config.busylight_live_color);
+        assert_eq!(loaded.instance_name, config.instance_name);
+        assert_eq!(loaded.led_patterns, config.led_patterns);
+        assert_eq!(loaded.mute_pattern, config.mute_pattern);
+        assert_eq!(loaded.color_preset, config.color_preset);
+        assert_eq!(loaded.talk_warning_enabled, config.talk_warning_enabled);
+        assert_eq!(
+            loaded.talk_warning_threshold_db,
+            config.talk_warning_threshold_db
+        );
+        assert_eq!(loaded.talk_warning_secs, config.talk_warning_secs);
+        assert_eq!(loaded.reconnect_resync, config.reconnect_resync);
+        assert_eq!(loaded.vu_mode, config.vu_mode);
+        assert_eq!(loaded.reconnect_flash_color, config.reconnect_flash_color);
+        assert_eq!(loaded.in_use_color, config.in_use_color);
+        assert_eq!(loaded.call_profile_enabled, config.call_profile_enabled);
+        assert_eq!(loaded.call_applications, config.call_applications);
+        assert_eq!(loaded.call_unmute, config.call_unmute);
+        assert_eq!(loaded.call_mute_color, config.call_mute_color);
+        assert_eq!(loaded.call_talk_warning, config.call_talk_warning);
+        assert_eq!(loaded.output_duck_db, config.output_duck_db);
+        assert_eq!(loaded.gain_up_hotkey, config.gain_up_hotkey);
+        assert_eq!(loaded.gain_down_hotkey, config.gain_down_hotkey);
+        assert_eq!(loaded.gain_hotkey_target, config.gain_hotkey_target);
+        assert_eq!(loaded.gain_hotkey_step_db, config.gain_hotkey_step_db);
+        assert_eq!(loaded.mqtt_broker, config.mqtt_broker);
+        assert_eq!(loaded.mqtt_topic_prefix, config.mqtt_topic_prefix);
+        assert_eq!(loaded.mqtt_username, config.mqtt_username);
+        assert_eq!(loaded.mqtt_password, config.mqtt_password);
+        assert_eq!(loaded.osc_target, config.osc_target);
+        assert_eq!(loaded.osc_listen, config.osc_listen);
+        assert_eq!(loaded.websocket_port, config.websocket_port);
+        assert_eq!(loaded.websocket_token, config.websocket_token);
+        assert_eq!(loaded.idle_screensaver_mins, config.idle_screensaver_mins);
     }
 
     #[test]
@@ -1155,4 +2968,34 @@ notifications_enabled = false
         assert!(warnings[0].contains("config parse error"));
         assert_eq!(config.mute_color, "#FF0000");
     }
+
+    // ── SaveThrottle ──
+
+    #[test]
+    fn save_throttle_waits_for_quiet_period() {
+        let mut t = SaveThrottle::new(Duration::from_millis(500), Duration::from_secs(5));
+        let start = Instant::now();
+        assert!(!t.due(start));
+        t.mark(start);
+        assert!(t.is_pending());
+        assert!(!t.due(start + Duration::from_millis(100)));
+        // A further change restarts the quiet period
+        t.mark(start + Duration::from_millis(400));
+        assert!(!t.due(start + Duration::from_millis(600)));
+        assert!(t.due(start + Duration::from_millis(900)));
+        t.clear();
+        assert!(!t.is_pending());
+        assert!(!t.due(start + Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn save_throttle_caps_delay_under_steady_changes() {
+        let mut t = SaveThrottle::new(Duration::from_millis(500), Duration::from_secs(2));
+        let start = Instant::now();
+        for i in 0..20 {
+            t.mark(start + Duration::from_millis(i * 100));
+        }
+        // Still changing every 100ms, but the first change is 2s old
+        assert!(t.due(start + Duration::from_millis(2000)));
+    }
 }
@@ -2,12 +2,15 @@
 //!
 //! Consolidates the repeated resolution pattern used by CLI commands and the
 //! tray app: detect profile → extract schema → compute offsets → predict layout.
+//! An imported shared layout (see [`crate::shared_layout`]) replaces the
+//! prediction for models without a hardcoded profile.
 
 use crate::device::{DeviceError, ScarlettDevice};
 use crate::layout::{self, PredictedLayout};
 use crate::models::{self, ModelProfile};
 use crate::offsets::DeviceOffsets;
 use crate::schema::{self, SchemaConstants};
+use crate::shared_layout;
 
 /// Resolved device context with model profile, schema, offsets, and layout.
 #[derive(Debug)]
@@ -38,17 +41,24 @@ impl DeviceContext {
 
         let offsets = if let Some(ref sc) = schema {
             DeviceOffsets::from_schema(sc)
-        } else if profile.is_none() {
-            return Err(DeviceError::UnsupportedDevice(device.info().model().to_string()).into());
+        } else if let Some(p) = profile {
+            // Known profile but no schema — default offsets, profile LED count
+            DeviceOffsets::for_profile(p)
         } else {
-            // Known profile but no schema — safe to use defaults
-            DeviceOffsets::default()
+            return Err(DeviceError::UnsupportedDevice(device.info().model().to_string()).into());
         };
 
         let predicted = if profile.is_none() {
-            schema
-                .as_ref()
-                .and_then(|sc| layout::predict_layout(sc).ok())
+            shared_layout::load_user_layout(device.info().model())
+                .map(|l| {
+                    log::info!("using imported LED layout for {}", l.model);
+                    l.to_predicted()
+                })
+                .or_else(|| {
+                    schema
+                        .as_ref()
+                        .and_then(|sc| layout::predict_layout(sc).ok())
+                })
         } else {
             None
         };
@@ -96,6 +106,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn known_4i4_uses_profile_led_count() {
+        let dev = mock_with_name("Scarlett 4i4 4th Gen-00031337");
+        let ctx = DeviceContext::resolve(&dev, false).unwrap();
+        assert_eq!(ctx.profile.unwrap().name, "Scarlett 4i4 4th Gen");
+        assert_eq!(ctx.offsets.direct_led_count, 56);
+        assert_eq!(ctx.input_count(), Some(4));
+    }
+
     #[test]
     fn known_model_force_schema_still_has_profile() {
         let dev = mock_with_name("Scarlett 2i2 4th Gen-00031337");
@@ -106,7 +125,7 @@ mod tests {
 
     #[test]
     fn unknown_model_no_schema_returns_error() {
-        let dev = mock_with_name("Scarlett Solo 4th Gen-00031337");
+        let dev = mock_with_name("Scarlett 16i16 4th Gen-00031337");
         let err = DeviceContext::resolve(&dev, false).unwrap_err();
         assert!(
             err.to_string().contains("Unsupported device"),
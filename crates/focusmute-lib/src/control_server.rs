@@ -0,0 +1,1053 @@
+//! Local WebSocket control server — for Stream Deck plugins, browser
+//! overlays, and custom dashboards.
+//!
+//! Listens on `127.0.0.1:<websocket_port>` only. A client authenticates
+//! with the `websocket_token`, either in the URL
+//! (`ws://127.0.0.1:<port>/?token=<token>`), as an `Authorization: Bearer`
+//! header, or in the `token` field of a command — within a few seconds of
+//! connecting, or the connection is closed. Authenticated clients get the
+//! last mute and device [`EventRecord`]s on connect and every event after
+//! that. At most 16 connections are served at once. The server won't start
+//! without a token, and requests whose `Origin` is a page on another host
+//! are refused, so a web page can't reach it through the browser.
+//!
+//! Commands are JSON text frames: `{"cmd": "toggle"}`, `mute`, `unmute`,
+//! or `status`. Each gets a reply — `{"reply": "ok"}`,
+//! `{"reply": "status", "state": ..., "muted": ..., "device": ...}`, or
+//! `{"reply": "error", "message": ...}`.
+//!
+//! Clients that would rather poll (Stream Deck plugins) can use plain HTTP
+//! on the same port: `GET /status`, and `POST /toggle`, `/mute`, or
+//! `/unmute`, authenticated like the WebSocket and answered with the same
+//! replies. `state` is what a button icon shows: `muted`, `live`, or
+//! `disconnected`. See `docs/17-stream-deck-integration.md`.
+
+use std::io::{ErrorKind, Read, Write};
+use std::net::{Ipv4Addr, Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender, SyncSender};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use crate::events::{DeviceState, Event, EventRecord, RemoteCommand};
+use crate::token::ct_eq;
+
+/// How often the accept loop checks whether it should stop.
+const ACCEPT_POLL: Duration = Duration::from_millis(100);
+
+/// Time a client gets to send its handshake, and a write may take.
+const IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Time a WebSocket client gets to authenticate after the handshake.
+const AUTH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Most connections served at once; more are closed right away.
+const MAX_CLIENTS: usize = 16;
+
+/// Frames a client may have waiting before it counts as stalled and is
+/// disconnected.
+const CLIENT_QUEUE: usize = 32;
+
+/// Largest handshake request and message accepted.
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+const MAX_MESSAGE_BYTES: usize = 64 * 1024;
+
+/// GUID the handshake hashes the client's key with (RFC 6455).
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+const OP_CONTINUATION: u8 = 0x0;
+const OP_TEXT: u8 = 0x1;
+const OP_BINARY: u8 = 0x2;
+const OP_CLOSE: u8 = 0x8;
+const OP_PING: u8 = 0x9;
+const OP_PONG: u8 = 0xA;
+
+/// A command sent by a client.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ControlRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    #[serde(flatten)]
+    pub command: ControlCommand,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum ControlCommand {
+    Toggle,
+    Mute,
+    Unmute,
+    /// The current mute and device state.
+    Status,
+}
+
+/// What a mute button shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IconState {
+    Muted,
+    Live,
+    /// No device, or the mute state isn't known yet.
+    Disconnected,
+}
+
+impl IconState {
+    /// The icon for a mute state and device state. `device` is `None` when
+    /// the tray runs without the device (`control_only`).
+    pub fn new(muted: Option<bool>, device: Option<DeviceState>) -> Self {
+        match (muted, device) {
+            (_, Some(DeviceState::Disconnected | DeviceState::InUse)) | (None, _) => {
+                IconState::Disconnected
+            }
+            (Some(true), _) => IconState::Muted,
+            (Some(false), _) => IconState::Live,
+        }
+    }
+}
+
+/// The server's reply to a [`ControlRequest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "reply", rename_all = "snake_case")]
+pub enum ControlReply {
+    Ok,
+    /// `None` fields aren't known yet.
+    Status {
+        state: IconState,
+        muted: Option<bool>,
+        device: Option<DeviceState>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// State shared by the broadcaster and the connection threads.
+#[derive(Default)]
+struct Shared {
+    /// Authenticated clients, by connection ID.
+    clients: Vec<(u64, Arc<Outbox>)>,
+    muted: Option<bool>,
+    device: Option<DeviceState>,
+    /// Last mute and device records, for clients that connect later.
+    last_mute: Option<String>,
+    last_device: Option<String>,
+}
+
+impl Shared {
+    fn record(&mut self, event: Event) -> String {
+        let json = EventRecord::now(event.clone()).to_json();
+        match event {
+            Event::Mute { muted, .. } => {
+                self.muted = Some(muted);
+                self.last_mute = Some(json.clone());
+            }
+            Event::Device { state, .. } => {
+                self.device = Some(state);
+                self.last_device = Some(json.clone());
+            }
+            _ => {}
+        }
+        json
+    }
+
+    fn status(&self) -> ControlReply {
+        ControlReply::Status {
+            state: IconState::new(self.muted, self.device),
+            muted: self.muted,
+            device: self.device,
+        }
+    }
+}
+
+fn lock(shared: &Mutex<Shared>) -> MutexGuard<'_, Shared> {
+    shared.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// The running server. Dropping it stops listening and disconnects every
+/// client.
+pub struct ControlServer {
+    tx: Sender<Event>,
+    addr: SocketAddr,
+    stop: Arc<AtomicBool>,
+    shared: Arc<Mutex<Shared>>,
+    accept: Option<std::thread::JoinHandle<()>>,
+}
+
+impl ControlServer {
+    /// Listen on `127.0.0.1:port` (0 picks a free port). `on_command` is
+    /// called, on a connection thread, for every mute command. Refuses an
+    /// empty `token`, which would let anyone in.
+    pub fn start<F>(port: u16, token: &str, on_command: F) -> std::io::Result<Self>
+    where
+        F: Fn(RemoteCommand) + Send + Sync + 'static,
+    {
+        if token.is_empty() {
+            return Err(std::io::Error::new(
+                ErrorKind::InvalidInput,
+                "websocket_token is empty",
+            ));
+        }
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+        listener.set_nonblocking(true)?;
+        let addr = listener.local_addr()?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let shared = Arc::new(Mutex::new(Shared::default()));
+        let (tx, rx) = mpsc::channel();
+
+        let broadcast_shared = Arc::clone(&shared);
+        std::thread::Builder::new()
+            .name("control-broadcast".into())
+            .spawn(move || broadcast(&rx, &broadcast_shared))?;
+
+        let context = Arc::new(Connection {
+            token: token.to_string(),
+            shared: Arc::clone(&shared),
+            on_command: Box::new(on_command),
+        });
+        let stopped = Arc::clone(&stop);
+        let accept = std::thread::Builder::new()
+            .name("control-server".into())
+            .spawn(move || {
+                let next_id = AtomicU64::new(1);
+                let active = Arc::new(AtomicUsize::new(0));
+                while !stopped.load(Ordering::Relaxed) {
+                    match listener.accept() {
+                        Ok((stream, _)) => {
+                            let Some(slot) = ClientSlot::take(&active) else {
+                                log::debug!("[control] too many clients, refusing one");
+                                let _ = stream.shutdown(Shutdown::Both);
+                                continue;
+                            };
+                            let context = Arc::clone(&context);
+                            let id = next_id.fetch_add(1, Ordering::Relaxed);
+                            let spawned = std::thread::Builder::new()
+                                .name("control-client".into())
+                                .spawn(move || {
+                                    let _slot = slot;
+                                    if let Err(e) = context.serve(id, stream) {
+                                        log::debug!("[control] client {id}: {e}");
+                                    }
+                                    lock(&context.shared).clients.retain(|(c, _)| *c != id);
+                                });
+                            if let Err(e) = spawned {
+                                log::warn!("[control] could not serve a client: {e}");
+                            }
+                        }
+                        Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                            std::thread::sleep(ACCEPT_POLL);
+                        }
+                        Err(e) => log::debug!("[control] accept failed: {e}"),
+                    }
+                }
+            })?;
+        Ok(Self {
+            tx,
+            addr,
+            stop,
+            shared,
+            accept: Some(accept),
+        })
+    }
+
+    /// The address actually bound.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Send `event` to every authenticated client.
+    pub fn publish(&self, event: Event) {
+        let _ = self.tx.send(event);
+    }
+}
+
+impl Drop for ControlServer {
+    /// Disconnect every client and wait for the accept loop (at most
+    /// [`ACCEPT_POLL`]), so the port is free again once this returns.
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        for (_, outbox) in lock(&self.shared).clients.drain(..) {
+            outbox.close();
+        }
+        if let Some(accept) = self.accept.take() {
+            let _ = accept.join();
+        }
+    }
+}
+
+/// One of the [`MAX_CLIENTS`] connection slots, given back on drop.
+struct ClientSlot(Arc<AtomicUsize>);
+
+impl ClientSlot {
+    fn take(active: &Arc<AtomicUsize>) -> Option<Self> {
+        active
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                (n < MAX_CLIENTS).then_some(n + 1)
+            })
+            .ok()
+            .map(|_| Self(Arc::clone(active)))
+    }
+}
+
+impl Drop for ClientSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// A client's outgoing frames. Its own writer thread drains the queue, so
+/// all frames to a client go out in order, and a slow client never holds up
+/// the broadcaster or the other clients.
+struct Outbox {
+    tx: SyncSender<Vec<u8>>,
+    /// For shutting the connection down.
+    stream: TcpStream,
+}
+
+impl Outbox {
+    fn open(stream: &TcpStream) -> std::io::Result<Self> {
+        let mut writer = stream.try_clone()?;
+        let (tx, rx) = mpsc::sync_channel::<Vec<u8>>(CLIENT_QUEUE);
+        std::thread::Builder::new()
+            .name("control-writer".into())
+            .spawn(move || {
+                for frame in rx {
+                    if writer.write_all(&frame).is_err() {
+                        let _ = writer.shutdown(Shutdown::Both);
+                        return;
+                    }
+                }
+            })?;
+        Ok(Self {
+            tx,
+            stream: stream.try_clone()?,
+        })
+    }
+
+    /// Queue a frame. A client whose queue is full is disconnected.
+    fn send(&self, frame: Vec<u8>) -> std::io::Result<()> {
+        self.tx.try_send(frame).map_err(|_| {
+            self.close();
+            std::io::Error::new(ErrorKind::WouldBlock, "client is not keeping up")
+        })
+    }
+
+    fn close(&self) {
+        let _ = self.stream.shutdown(Shutdown::Both);
+    }
+}
+
+/// Broadcaster thread: record each event and queue it for every client,
+/// dropping clients that can't take it.
+fn broadcast(rx: &Receiver<Event>, shared: &Mutex<Shared>) {
+    for event in rx {
+        let mut shared = lock(shared);
+        let frame = text_frame(&shared.record(event));
+        shared.clients.retain(|(id, outbox)| {
+            let sent = outbox.send(frame.clone());
+            if let Err(ref e) = sent {
+                log::debug!("[control] dropping client {id}: {e}");
+            }
+            sent.is_ok()
+        });
+    }
+}
+
+/// What each connection thread needs.
+struct Connection {
+    token: String,
+    shared: Arc<Mutex<Shared>>,
+    on_command: Box<dyn Fn(RemoteCommand) + Send + Sync>,
+}
+
+impl Connection {
+    /// Handshake, then answer commands until the client goes away.
+    fn serve(&self, id: u64, mut stream: TcpStream) -> std::io::Result<()> {
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(IO_TIMEOUT))?;
+        stream.set_write_timeout(Some(IO_TIMEOUT))?;
+        let request = read_request(&mut stream)?;
+        // Web pages can reach 127.0.0.1 too; only local pages may connect
+        if request
+            .header("origin")
+            .is_some_and(|o| !is_local_origin(o))
+        {
+            return write_http(&mut stream, "403 Forbidden", None);
+        }
+        let Some(key) = request.header("sec-websocket-key") else {
+            return self.serve_http(&request, &mut stream);
+        };
+        let offered = request.token();
+        if request.method != "GET" || offered.is_some() && !self.token_matches(offered.as_deref()) {
+            return write_http(&mut stream, "403 Forbidden", None);
+        }
+        write!(
+            stream,
+            "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\n\
+             Connection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+            accept_key(key)
+        )?;
+
+        let outbox = Arc::new(Outbox::open(&stream)?);
+
+        // Idle clients are fine once authenticated; the rest get AUTH_TIMEOUT
+        let deadline = Instant::now() + AUTH_TIMEOUT;
+        let mut authenticated = false;
+        if offered.is_some() {
+            authenticated = self.authenticate(id, &outbox)?;
+        }
+        let mut message = Vec::new();
+        let mut message_op = OP_TEXT;
+        loop {
+            let timeout = if authenticated {
+                None
+            } else {
+                let left = deadline.saturating_duration_since(Instant::now());
+                if left.is_zero() {
+                    return Err(std::io::Error::new(
+                        ErrorKind::TimedOut,
+                        "not authenticated in time",
+                    ));
+                }
+                Some(left)
+            };
+            stream.set_read_timeout(timeout)?;
+            let (fin, opcode, payload) = read_frame(&mut stream)?;
+            match opcode {
+                OP_CLOSE => {
+                    let _ = outbox.send(frame(OP_CLOSE, &[]));
+                    return Ok(());
+                }
+                OP_PING => outbox.send(frame(OP_PONG, &payload))?,
+                OP_PONG => {}
+                OP_TEXT | OP_BINARY => {
+                    message = payload;
+                    message_op = opcode;
+                }
+                OP_CONTINUATION => {
+                    if message.len() + payload.len() > MAX_MESSAGE_BYTES {
+                        return Err(std::io::Error::new(
+                            ErrorKind::InvalidData,
+                            "message too large",
+                        ));
+                    }
+                    message.extend_from_slice(&payload);
+                }
+                _ => {
+                    return Err(std::io::Error::new(
+                        ErrorKind::InvalidData,
+                        format!("unknown opcode {opcode:#x}"),
+                    ));
+                }
+            }
+            if !fin || !matches!(opcode, OP_TEXT | OP_BINARY | OP_CONTINUATION) {
+                continue;
+            }
+            let text = std::mem::take(&mut message);
+            if message_op != OP_TEXT {
+                continue;
+            }
+            let reply = match serde_json::from_slice::<ControlRequest>(&text) {
+                Ok(request) => {
+                    if !authenticated && self.token_matches(request.token.as_deref()) {
+                        authenticated = self.authenticate(id, &outbox)?;
+                    }
+                    if authenticated {
+                        self.handle(request.command)
+                    } else {
+                        ControlReply::Error {
+                            message: "invalid token".into(),
+                        }
+                    }
+                }
+                Err(e) => ControlReply::Error {
+                    message: format!("malformed request: {e}"),
+                },
+            };
+            let json = serde_json::to_string(&reply).unwrap_or_default();
+            outbox.send(text_frame(&json))?;
+        }
+    }
+
+    /// Answer a plain HTTP request: `GET /status`, or `POST /toggle`,
+    /// `/mute`, `/unmute`.
+    fn serve_http(&self, request: &Request, stream: &mut TcpStream) -> std::io::Result<()> {
+        if !self.token_matches(request.token().as_deref()) {
+            return write_http(stream, "403 Forbidden", None);
+        }
+        let path = request.target.split('?').next().unwrap_or_default();
+        let (method, command) = match path.trim_end_matches('/') {
+            "/status" => ("GET", ControlCommand::Status),
+            "/toggle" => ("POST", ControlCommand::Toggle),
+            "/mute" => ("POST", ControlCommand::Mute),
+            "/unmute" => ("POST", ControlCommand::Unmute),
+            _ => return write_http(stream, "404 Not Found", None),
+        };
+        if request.method != method {
+            return write_http(stream, "405 Method Not Allowed", None);
+        }
+        let json = serde_json::to_string(&self.handle(command)).unwrap_or_default();
+        write_http(stream, "200 OK", Some(&json))
+    }
+
+    /// Whether `offered` is the configured token, compared in constant time.
+    fn token_matches(&self, offered: Option<&str>) -> bool {
+        offered.is_some_and(|t| ct_eq(t.as_bytes(), self.token.as_bytes()))
+    }
+
+    /// Add the client to the broadcast list and catch it up on the state.
+    fn authenticate(&self, id: u64, outbox: &Arc<Outbox>) -> std::io::Result<bool> {
+        let mut shared = lock(&self.shared);
+        for json in [&shared.last_mute, &shared.last_device]
+            .into_iter()
+            .flatten()
+        {
+            outbox.send(text_frame(json))?;
+        }
+        shared.clients.push((id, Arc::clone(outbox)));
+        Ok(true)
+    }
+
+    fn handle(&self, command: ControlCommand) -> ControlReply {
+        let remote = match command {
+            ControlCommand::Status => return lock(&self.shared).status(),
+            ControlCommand::Toggle => RemoteCommand::Toggle,
+            ControlCommand::Mute => RemoteCommand::Mute,
+            ControlCommand::Unmute => RemoteCommand::Unmute,
+        };
+        log::info!("[control] command: {remote:?}");
+        (self.on_command)(remote);
+        ControlReply::Ok
+    }
+}
+
+// ── HTTP ──
+
+/// The parts of a request the server looks at.
+struct Request {
+    method: String,
+    /// Path with query string.
+    target: String,
+    /// Header names (lowercase) and values.
+    headers: Vec<(String, String)>,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// The token from `?token=` or an `Authorization: Bearer` header.
+    fn token(&self) -> Option<String> {
+        let query = self.target.split_once('?').map(|(_, q)| q);
+        let from_query = query.and_then(|q| {
+            q.split('&')
+                .find_map(|pair| pair.strip_prefix("token="))
+                .map(percent_decode)
+        });
+        from_query.or_else(|| {
+            self.header("authorization")
+                .and_then(|v| v.strip_prefix("Bearer "))
+                .map(|t| t.trim().to_string())
+        })
+    }
+}
+
+/// Read the request line and headers (up to the blank line).
+fn read_request(stream: &mut TcpStream) -> std::io::Result<Request> {
+    let mut data = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !data.windows(4).any(|w| w == b"\r\n\r\n") {
+        if data.len() > MAX_REQUEST_BYTES {
+            return Err(std::io::Error::new(
+                ErrorKind::InvalidData,
+                "request too large",
+            ));
+        }
+        let n = stream.read(&mut chunk)?;
+        if n == 0 {
+            return Err(ErrorKind::UnexpectedEof.into());
+        }
+        data.extend_from_slice(&chunk[..n]);
+    }
+    parse_request(&String::from_utf8_lossy(&data))
+        .ok_or_else(|| std::io::Error::new(ErrorKind::InvalidData, "malformed request"))
+}
+
+fn parse_request(text: &str) -> Option<Request> {
+    let mut lines = text.split("\r\n");
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let target = request_line.next()?.to_string();
+    let headers = lines
+        .take_while(|line| !line.is_empty())
+        .filter_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            Some((name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        })
+        .collect();
+    Some(Request {
+        method,
+        target,
+        headers,
+    })
+}
+
+/// Write a response and close the connection. No CORS headers: browsers
+/// keep web pages from reading the replies or sending the `Authorization`
+/// header, so a leaked token can't be used from an arbitrary site.
+fn write_http(stream: &mut TcpStream, status: &str, json: Option<&str>) -> std::io::Result<()> {
+    let body = json.unwrap_or_default();
+    let content_type = if json.is_some() {
+        "Content-Type: application/json\r\n"
+    } else {
+        ""
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\n{content_type}Content-Length: {}\r\n\
+         Connection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+/// Whether an `Origin` header names a page served from this machine.
+fn is_local_origin(origin: &str) -> bool {
+    let Some(rest) = origin
+        .strip_prefix("http://")
+        .or_else(|| origin.strip_prefix("https://"))
+    else {
+        return false;
+    };
+    let host = match rest.strip_prefix('[') {
+        Some(v6) => v6.split_once(']').map_or("", |(host, _)| host),
+        None => rest.split(':').next().unwrap_or_default(),
+    };
+    matches!(
+        host.to_ascii_lowercase().as_str(),
+        "localhost" | "127.0.0.1" | "::1"
+    )
+}
+
+/// Decode `%XX` escapes and `+` in a query value.
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                    Some(b) => {
+                        out.push(b);
+                        i += 3;
+                        continue;
+                    }
+                    None => out.push(b'%'),
+                }
+            }
+            b'+' => out.push(b' '),
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// `Sec-WebSocket-Accept` for a client's `Sec-WebSocket-Key`.
+fn accept_key(key: &str) -> String {
+    let digest = sha1_smol::Sha1::from(format!("{}{WEBSOCKET_GUID}", key.trim())).digest();
+    base64::engine::general_purpose::STANDARD.encode(digest.bytes())
+}
+
+// ── Frames ──
+
+/// An unmasked (server-to-client) frame.
+fn frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x80 | opcode];
+    match payload.len() {
+        len @ 0..=125 => out.push(len as u8),
+        len @ 126..=0xFFFF => {
+            out.push(126);
+            out.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            out.push(127);
+            out.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    out.extend_from_slice(payload);
+    out
+}
+
+fn text_frame(text: &str) -> Vec<u8> {
+    frame(OP_TEXT, text.as_bytes())
+}
+
+/// Read one frame: FIN flag, opcode, and unmasked payload.
+fn read_frame(stream: &mut impl Read) -> std::io::Result<(bool, u8, Vec<u8>)> {
+    let mut head = [0u8; 2];
+    stream.read_exact(&mut head)?;
+    let fin = head[0] & 0x80 != 0;
+    let opcode = head[0] & 0x0F;
+    let masked = head[1] & 0x80 != 0;
+    let len = match head[1] & 0x7F {
+        126 => {
+            let mut ext = [0u8; 2];
+            stream.read_exact(&mut ext)?;
+            u64::from(u16::from_be_bytes(ext))
+        }
+        127 => {
+            let mut ext = [0u8; 8];
+            stream.read_exact(&mut ext)?;
+            u64::from_be_bytes(ext)
+        }
+        len => u64::from(len),
+    };
+    if len > MAX_MESSAGE_BYTES as u64 {
+        return Err(std::io::Error::new(
+            ErrorKind::InvalidData,
+            "frame too large",
+        ));
+    }
+    let mut mask = [0u8; 4];
+    if masked {
+        stream.read_exact(&mut mask)?;
+    }
+    let mut payload = vec![0u8; len as usize];
+    stream.read_exact(&mut payload)?;
+    if masked {
+        for (i, byte) in payload.iter_mut().enumerate() {
+            *byte ^= mask[i % 4];
+        }
+    }
+    Ok((fin, opcode, payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A client-side (masked) frame.
+    fn client_frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+        let mask = [0x12, 0x34, 0x56, 0x78];
+        let mut out = vec![0x80 | opcode, 0x80 | payload.len() as u8];
+        out.extend_from_slice(&mask);
+        out.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+        out
+    }
+
+    /// Connect and complete the handshake at `path`; returns the stream and
+    /// the status line.
+    fn connect(addr: SocketAddr, path: &str) -> (TcpStream, String) {
+        connect_with(addr, path, "")
+    }
+
+    /// [`connect`] with extra header lines (each ending in `\r\n`).
+    fn connect_with(addr: SocketAddr, path: &str, headers: &str) -> (TcpStream, String) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        write!(
+            stream,
+            "GET {path} HTTP/1.1\r\nHost: localhost\r\nUpgrade: websocket\r\n\
+             Connection: Upgrade\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+             Sec-WebSocket-Version: 13\r\n{headers}\r\n"
+        )
+        .unwrap();
+        let mut response = Vec::new();
+        let mut byte = [0u8; 1];
+        while !response.ends_with(b"\r\n\r\n") {
+            if stream.read(&mut byte).unwrap() == 0 {
+                break;
+            }
+            response.push(byte[0]);
+        }
+        let response = String::from_utf8(response).unwrap();
+        let status = response.lines().next().unwrap_or_default().to_string();
+        (stream, status)
+    }
+
+    fn read_json(stream: &mut TcpStream) -> serde_json::Value {
+        let (fin, opcode, payload) = read_frame(stream).unwrap();
+        assert!(fin);
+        assert_eq!(opcode, OP_TEXT);
+        serde_json::from_slice(&payload).unwrap()
+    }
+
+    #[test]
+    fn accept_key_matches_rfc_example() {
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+
+    #[test]
+    fn frames_round_trip() {
+        for len in [0, 125, 126, 70_000] {
+            let payload = vec![7u8; len];
+            let encoded = frame(OP_BINARY, &payload);
+            if len > MAX_MESSAGE_BYTES {
+                assert!(read_frame(&mut encoded.as_slice()).is_err());
+            } else {
+                assert_eq!(
+                    read_frame(&mut encoded.as_slice()).unwrap(),
+                    (true, OP_BINARY, payload)
+                );
+            }
+        }
+        let masked = client_frame(OP_TEXT, b"hi");
+        assert_eq!(
+            read_frame(&mut masked.as_slice()).unwrap(),
+            (true, OP_TEXT, b"hi".to_vec())
+        );
+    }
+
+    #[test]
+    fn handshake_token_sources() {
+        let request =
+            parse_request("GET /?x=1&token=a%20b HTTP/1.1\r\nAuthorization: Bearer other\r\n\r\n")
+                .unwrap();
+        assert_eq!(request.token().as_deref(), Some("a b"));
+        let request =
+            parse_request("GET / HTTP/1.1\r\nauthorization: Bearer secret\r\n\r\n").unwrap();
+        assert_eq!(request.token().as_deref(), Some("secret"));
+        let request = parse_request("POST /toggle HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(request.method, "POST");
+        assert!(parse_request("GET\r\n\r\n").is_none());
+    }
+
+    #[test]
+    fn empty_token_is_refused() {
+        let err = ControlServer::start(0, "", |_| {}).err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn only_local_origins_may_connect() {
+        assert!(is_local_origin("http://localhost:8080"));
+        assert!(is_local_origin("https://127.0.0.1"));
+        assert!(is_local_origin("http://[::1]:3000"));
+        assert!(!is_local_origin("https://evil.example"));
+        assert!(!is_local_origin("http://localhost.evil.example"));
+        assert!(!is_local_origin("null"));
+
+        let server = ControlServer::start(0, "s3cret", |_| {}).unwrap();
+        let addr = server.local_addr();
+        let (_stream, status) =
+            connect_with(addr, "/?token=s3cret", "Origin: https://evil.example\r\n");
+        assert!(status.contains("403"), "{status}");
+        let (_stream, status) =
+            connect_with(addr, "/?token=s3cret", "Origin: http://localhost:8080\r\n");
+        assert!(status.contains("101"), "{status}");
+    }
+
+    #[test]
+    fn icon_state_follows_mute_and_device() {
+        use DeviceState::*;
+        assert_eq!(
+            IconState::new(Some(true), Some(Connected)),
+            IconState::Muted
+        );
+        assert_eq!(IconState::new(Some(false), None), IconState::Live);
+        assert_eq!(
+            IconState::new(Some(true), Some(Disconnected)),
+            IconState::Disconnected
+        );
+        assert_eq!(
+            IconState::new(Some(false), Some(InUse)),
+            IconState::Disconnected
+        );
+        assert_eq!(
+            IconState::new(None, Some(Connected)),
+            IconState::Disconnected
+        );
+    }
+
+    /// Send a plain HTTP request; returns the status line and headers, and
+    /// the body.
+    fn http(addr: SocketAddr, method: &str, path: &str) -> (String, String) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        write!(
+            stream,
+            "{method} {path} HTTP/1.1\r\nHost: localhost\r\n\r\n"
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        (head.to_string(), body.to_string())
+    }
+
+    #[test]
+    fn http_status_and_toggle_for_stream_deck() {
+        let (tx, rx) = mpsc::channel();
+        let server = ControlServer::start(0, "s3cret", move |c| {
+            let _ = tx.send(c);
+        })
+        .unwrap();
+        server.publish(Event::device(DeviceState::Connected, None));
+        server.publish(Event::Mute {
+            muted: false,
+            origin: "focusmute".into(),
+        });
+        while lock(&server.shared).last_mute.is_none() {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        let addr = server.local_addr();
+
+        let (status, body) = http(addr, "GET", "/status?token=s3cret");
+        assert!(status.contains("200"), "{status}");
+        assert!(!status.contains("Access-Control"), "{status}");
+        assert_eq!(
+            serde_json::from_str::<ControlReply>(&body).unwrap(),
+            ControlReply::Status {
+                state: IconState::Live,
+                muted: Some(false),
+                device: Some(DeviceState::Connected),
+            }
+        );
+        let (status, body) = http(addr, "POST", "/toggle?token=s3cret");
+        assert!(status.contains("200"), "{status}");
+        assert_eq!(body, r#"{"reply":"ok"}"#);
+        assert_eq!(
+            rx.recv_timeout(Duration::from_secs(5)).unwrap(),
+            RemoteCommand::Toggle
+        );
+
+        assert!(http(addr, "GET", "/status").0.contains("403"));
+        assert!(http(addr, "GET", "/toggle?token=s3cret").0.contains("405"));
+        assert!(http(addr, "GET", "/nope?token=s3cret").0.contains("404"));
+        // No CORS preflight support
+        assert!(http(addr, "OPTIONS", "/toggle").0.contains("403"));
+        assert!(
+            http(addr, "OPTIONS", "/toggle?token=s3cret")
+                .0
+                .contains("405")
+        );
+    }
+
+    /// Whether the server closed `stream` (EOF or reset) before its read
+    /// timeout.
+    fn closed_by_server(stream: &mut TcpStream) -> bool {
+        let mut byte = [0u8; 1];
+        match stream.read(&mut byte) {
+            Ok(0) => true,
+            Err(e) => !matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut),
+            Ok(_) => false,
+        }
+    }
+
+    #[test]
+    fn idle_clients_are_capped_and_timed_out() {
+        let server = ControlServer::start(0, "s3cret", |_| {}).unwrap();
+        let addr = server.local_addr();
+        let mut idle: Vec<_> = (0..MAX_CLIENTS)
+            .map(|_| {
+                let (stream, status) = connect(addr, "/");
+                assert!(status.contains("101"), "{status}");
+                stream
+            })
+            .collect();
+        let mut refused = TcpStream::connect(addr).unwrap();
+        refused
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        assert!(closed_by_server(&mut refused));
+
+        // Never authenticated: dropped after AUTH_TIMEOUT, freeing the slots
+        for stream in &mut idle {
+            stream.set_read_timeout(Some(AUTH_TIMEOUT * 2)).unwrap();
+            assert!(closed_by_server(stream));
+        }
+        let (_, status) = connect(addr, "/?token=s3cret");
+        assert!(status.contains("101"), "{status}");
+    }
+
+    #[test]
+    fn stalled_client_is_dropped_without_blocking() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let _peer = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (stream, _) = listener.accept().unwrap();
+        stream.set_write_timeout(Some(IO_TIMEOUT)).unwrap();
+        let outbox = Outbox::open(&stream).unwrap();
+
+        // The peer never reads: the socket buffers fill, then the queue
+        let started = Instant::now();
+        let big = frame(OP_BINARY, &[0u8; 60_000]);
+        let overflowed = (0..10_000).any(|_| outbox.send(big.clone()).is_err());
+        assert!(overflowed);
+        assert!(started.elapsed() < IO_TIMEOUT, "{:?}", started.elapsed());
+    }
+
+    #[test]
+    fn server_streams_events_and_takes_commands() {
+        let (tx, rx) = mpsc::channel();
+        let server = ControlServer::start(0, "s3cret", move |c| {
+            let _ = tx.send(c);
+        })
+        .unwrap();
+        server.publish(Event::Mute {
+            muted: true,
+            origin: "external".into(),
+        });
+        while lock(&server.shared).last_mute.is_none() {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        let addr = server.local_addr();
+
+        let (_, status) = connect(addr, "/?token=wrong");
+        assert!(status.contains("403"), "{status}");
+
+        // Authenticated in the URL: caught up on the state, then commands
+        let (mut client, status) = connect(addr, "/?token=s3cret");
+        assert!(status.contains("101"), "{status}");
+        assert_eq!(read_json(&mut client)["muted"], true);
+        client
+            .write_all(&client_frame(OP_TEXT, br#"{"cmd":"toggle"}"#))
+            .unwrap();
+        assert_eq!(read_json(&mut client)["reply"], "ok");
+        assert_eq!(
+            rx.recv_timeout(Duration::from_secs(5)).unwrap(),
+            RemoteCommand::Toggle
+        );
+        client
+            .write_all(&client_frame(OP_TEXT, br#"{"cmd":"status"}"#))
+            .unwrap();
+        let status = read_json(&mut client);
+        assert_eq!(
+            (&status["reply"], &status["muted"]),
+            (&"status".into(), &true.into())
+        );
+
+        // Without a token in the URL, commands carry it
+        let (mut other, _) = connect(addr, "/");
+        other
+            .write_all(&client_frame(OP_TEXT, br#"{"cmd":"mute"}"#))
+            .unwrap();
+        assert_eq!(read_json(&mut other)["message"], "invalid token");
+        other
+            .write_all(&client_frame(
+                OP_TEXT,
+                br#"{"cmd":"unmute","token":"s3cret"}"#,
+            ))
+            .unwrap();
+        assert_eq!(read_json(&mut other)["muted"], true);
+        assert_eq!(read_json(&mut other)["reply"], "ok");
+
+        server.publish(Event::device(DeviceState::Disconnected, None));
+        assert_eq!(read_json(&mut client)["state"], "disconnected");
+        assert_eq!(read_json(&mut other)["state"], "disconnected");
+    }
+}
@@ -0,0 +1,129 @@
+//! Descriptor write batching — fewer USB round-trips per update.
+//!
+//! Each `set_descriptor` is its own USB transaction. A [`DescriptorBatch`]
+//! collects writes, merges touching or overlapping ones into single
+//! contiguous writes (later bytes win), and sends one DATA_NOTIFY at the end.
+//!
+//! Only batch writes whose effect the firmware applies on the notify: the
+//! merged writes go out in ascending offset order, not the order they were
+//! added. A single-LED update (`directLEDColour` + `directLEDIndex` +
+//! DATA_NOTIFY(8)) is one batch — several LEDs still need one batch each,
+//! since the firmware applies only the last colour/index pair per notify.
+
+use std::collections::BTreeMap;
+
+use crate::device::{Result, ScarlettDevice};
+
+/// Builder for a group of descriptor writes committed together.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DescriptorBatch {
+    /// Pending bytes by descriptor offset.
+    bytes: BTreeMap<u32, u8>,
+    notify: Option<u32>,
+}
+
+impl DescriptorBatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a write of `data` at `offset`. Overlapping bytes from earlier
+    /// writes are replaced.
+    pub fn write(mut self, offset: u32, data: &[u8]) -> Self {
+        for (addr, &byte) in (offset..).zip(data) {
+            self.bytes.insert(addr, byte);
+        }
+        self
+    }
+
+    /// Send DATA_NOTIFY(`event_id`) after the writes. A later call replaces
+    /// the event.
+    pub fn notify(mut self, event_id: u32) -> Self {
+        self.notify = Some(event_id);
+        self
+    }
+
+    /// `true` if nothing would be written or notified.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty() && self.notify.is_none()
+    }
+
+    /// The merged writes, as `(offset, data)` in ascending offset order.
+    pub fn writes(&self) -> Vec<(u32, Vec<u8>)> {
+        let mut runs: Vec<(u32, Vec<u8>)> = Vec::new();
+        for (&addr, &byte) in &self.bytes {
+            match runs.last_mut() {
+                Some((start, data)) if *start + data.len() as u32 == addr => data.push(byte),
+                _ => runs.push((addr, vec![byte])),
+            }
+        }
+        runs
+    }
+
+    /// Perform the merged writes, then the notify. Stops at the first error.
+    pub fn commit(&self, device: &impl ScarlettDevice) -> Result<()> {
+        for (offset, data) in self.writes() {
+            device.set_descriptor(offset, &data)?;
+        }
+        if let Some(event_id) = self.notify {
+            device.data_notify(event_id)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device::mock::MockDevice;
+
+    #[test]
+    fn merges_adjacent_writes() {
+        let batch = DescriptorBatch::new()
+            .write(84, &[1, 2, 3, 4])
+            .write(88, &[5])
+            .write(100, &[9]);
+        assert_eq!(
+            batch.writes(),
+            vec![(84, vec![1, 2, 3, 4, 5]), (100, vec![9])]
+        );
+    }
+
+    #[test]
+    fn later_writes_win_and_sort_by_offset() {
+        let batch = DescriptorBatch::new()
+            .write(12, &[7, 7])
+            .write(10, &[1, 2, 3]);
+        assert_eq!(batch.writes(), vec![(10, vec![1, 2, 3, 7])]);
+    }
+
+    #[test]
+    fn commit_writes_once_then_notifies() {
+        let dev = MockDevice::new();
+        DescriptorBatch::new()
+            .write(84, &0x00FF_0000u32.to_le_bytes())
+            .write(88, &[3])
+            .notify(8)
+            .commit(&dev)
+            .unwrap();
+        assert_eq!(dev.descriptors.borrow().len(), 1);
+        assert_eq!(dev.get_descriptor(88, 1).unwrap(), vec![3]);
+        assert_eq!(*dev.notifies.borrow(), vec![8]);
+    }
+
+    #[test]
+    fn commit_stops_on_write_error() {
+        let dev = MockDevice::new();
+        dev.fail_set_descriptor.set(true);
+        let batch = DescriptorBatch::new().write(0, &[1]).notify(8);
+        assert!(batch.commit(&dev).is_err());
+        assert!(dev.notifies.borrow().is_empty());
+    }
+
+    #[test]
+    fn empty_batch() {
+        assert!(DescriptorBatch::new().is_empty());
+        assert!(!DescriptorBatch::new().notify(8).is_empty());
+        assert!(DescriptorBatch::new().writes().is_empty());
+    }
+}
@@ -1,8 +1,9 @@
 //! Device communication — trait + Windows backend.
 
 use std::fmt;
+use std::time::Duration;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::protocol::*;
 
@@ -21,6 +22,11 @@ pub enum DeviceError {
     InitFailed(String),
     TransactFailed(String),
     UnsupportedDevice(String),
+    /// Another FocusMute process (the tray app) owns the device.
+    Busy(String),
+    /// Another application holds the device exclusively (with the holding
+    /// process, if it could be found). Usually temporary — keep retrying.
+    InUse(Option<String>),
 }
 
 impl fmt::Display for DeviceError {
@@ -36,6 +42,9 @@ impl fmt::Display for DeviceError {
                     "Unsupported device: {name} (no profile or schema available)"
                 )
             }
+            DeviceError::Busy(e) => write!(f, "Device busy: {e}"),
+            DeviceError::InUse(Some(holder)) => write!(f, "Device in use by {holder}"),
+            DeviceError::InUse(None) => write!(f, "Device in use by another application"),
         }
     }
 }
@@ -44,9 +53,43 @@ impl std::error::Error for DeviceError {}
 
 pub type Result<T> = std::result::Result<T, DeviceError>;
 
+/// How a process holding the device is named in [`DeviceError::InUse`].
+#[cfg(any(windows, target_os = "linux"))]
+fn process_label(name: &str, pid: u32) -> String {
+    if name.is_empty() {
+        format!("PID {pid}")
+    } else {
+        format!("{name} (PID {pid})")
+    }
+}
+
+/// A process other than this one with `node` open, found by reading the
+/// `fd` links under `proc_root` (normally `/proc`).
+#[cfg(target_os = "linux")]
+fn process_holding(proc_root: &std::path::Path, node: &std::path::Path) -> Option<String> {
+    let own = std::process::id();
+    std::fs::read_dir(proc_root)
+        .ok()?
+        .flatten()
+        .filter_map(|entry| {
+            let pid: u32 = entry.file_name().to_str()?.parse().ok()?;
+            (pid != own).then_some((pid, entry.path()))
+        })
+        .find(|(_, dir)| {
+            std::fs::read_dir(dir.join("fd")).is_ok_and(|fds| {
+                fds.flatten()
+                    .any(|fd| std::fs::read_link(fd.path()).is_ok_and(|target| target == node))
+            })
+        })
+        .map(|(pid, dir)| {
+            let name = std::fs::read_to_string(dir.join("comm")).unwrap_or_default();
+            process_label(name.trim(), pid)
+        })
+}
+
 // ── Device info ──
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DeviceInfo {
     pub path: String,
     /// Raw GET_CONFIG response (96 bytes).
@@ -61,9 +104,70 @@ pub struct DeviceInfo {
     pub firmware: FirmwareVersion,
     /// Serial number (from USB device instance ID, if available).
     pub serial: Option<String>,
+    /// USB product ID (e.g. `0x8219` for the 2i2 4th Gen), if available.
+    pub product_id: Option<u16>,
+    /// Negotiated USB bus speed, if the platform reports it.
+    pub usb_speed: Option<UsbSpeed>,
+    /// Installed Focusrite driver version (Windows only).
+    pub driver_version: Option<String>,
+}
+
+/// Negotiated USB connection speed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UsbSpeed {
+    Low,
+    Full,
+    High,
+    Super,
+    SuperPlus,
+}
+
+impl fmt::Display for UsbSpeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UsbSpeed::Low => write!(f, "Low Speed (1.5 Mbit/s)"),
+            UsbSpeed::Full => write!(f, "Full Speed (12 Mbit/s)"),
+            UsbSpeed::High => write!(f, "High Speed (480 Mbit/s)"),
+            UsbSpeed::Super => write!(f, "SuperSpeed (5 Gbit/s)"),
+            UsbSpeed::SuperPlus => write!(f, "SuperSpeed+ (10 Gbit/s)"),
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl UsbSpeed {
+    fn from_nusb(speed: nusb::Speed) -> Option<Self> {
+        match speed {
+            nusb::Speed::Low => Some(UsbSpeed::Low),
+            nusb::Speed::Full => Some(UsbSpeed::Full),
+            nusb::Speed::High => Some(UsbSpeed::High),
+            nusb::Speed::Super => Some(UsbSpeed::Super),
+            nusb::Speed::SuperPlus => Some(UsbSpeed::SuperPlus),
+            _ => None,
+        }
+    }
+}
+
+/// Parse a Windows USB instance ID (`USB\VID_1235&PID_8219\SERIAL`) into
+/// `(product_id, serial)`. Either part is `None` if missing or malformed.
+pub fn parse_usb_instance_id(id: &str) -> (Option<u16>, Option<String>) {
+    let parts: Vec<&str> = id.split('\\').collect();
+    let product_id = parts.get(1).and_then(|ids| {
+        ids.split('&').find_map(|part| {
+            let upper = part.to_uppercase();
+            let hex = upper.strip_prefix("PID_")?;
+            u16::from_str_radix(hex.get(..4)?, 16).ok()
+        })
+    });
+    let serial = parts
+        .get(2)
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string());
+    (product_id, serial)
 }
 
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FirmwareVersion {
     pub major: u16,
     pub minor: u16,
@@ -116,6 +220,15 @@ impl DeviceInfo {
         }
     }
 
+    /// Sample rate in Hz reported by GET_CONFIG when the device was opened.
+    pub fn sample_rate(&self) -> Option<u32> {
+        let bytes = self
+            .config_raw
+            .get(CONFIG_SAMPLE_RATE_OFFSET..CONFIG_SAMPLE_RATE_OFFSET + 4)?;
+        let rate = u32::from_le_bytes(bytes.try_into().ok()?);
+        (rate != 0).then_some(rate)
+    }
+
     /// Model name extracted from device_name (before the last dash-serial suffix).
     pub fn model(&self) -> &str {
         self.device_name
@@ -124,6 +237,66 @@ impl DeviceInfo {
             .unwrap_or(&self.device_name)
             .trim()
     }
+
+    /// Fall back to the known product name for `product_id` when the
+    /// descriptor name is empty.
+    pub fn fill_missing_name(&mut self) {
+        if !self.device_name.trim().is_empty() {
+            return;
+        }
+        if let Some(p) = self.product_id.and_then(crate::models::product_by_id) {
+            self.device_name = p.name.to_string();
+        }
+    }
+}
+
+// ── I/O options ──
+
+/// I/O tuning for an opened device — for flaky hubs and slow docks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeviceOptions {
+    /// How long one transaction may take: the IOCTL on Windows, each USB
+    /// control transfer on Linux.
+    pub transact_timeout: Duration,
+    /// Longest a single [`ScarlettDevice::wait_notify`] may block; longer
+    /// requests are capped.
+    pub notify_timeout: Duration,
+    /// Extra attempts after a failed transaction. Linux retries short replies
+    /// and device error codes; Windows retries IOCTL failures and timeouts.
+    pub retries: u32,
+}
+
+impl DeviceOptions {
+    /// Built-in transaction timeout: 5 s per IOCTL on Windows,
+    /// [`USB_TIMEOUT_MS`] per control transfer elsewhere.
+    pub const DEFAULT_TRANSACT_TIMEOUT: Duration = if cfg!(windows) {
+        Duration::from_secs(5)
+    } else {
+        Duration::from_millis(USB_TIMEOUT_MS)
+    };
+    pub const DEFAULT_NOTIFY_TIMEOUT: Duration = Duration::from_secs(5);
+    /// Built-in retries: none on Windows, [`USB_MAX_RETRIES`] attempts in
+    /// total on Linux.
+    pub const DEFAULT_RETRIES: u32 = if cfg!(windows) {
+        0
+    } else {
+        USB_MAX_RETRIES as u32 - 1
+    };
+
+    /// Total attempts per transaction (the first one plus retries).
+    pub fn attempts(&self) -> u32 {
+        self.retries.saturating_add(1)
+    }
+}
+
+impl Default for DeviceOptions {
+    fn default() -> Self {
+        Self {
+            transact_timeout: Self::DEFAULT_TRANSACT_TIMEOUT,
+            notify_timeout: Self::DEFAULT_NOTIFY_TIMEOUT,
+            retries: Self::DEFAULT_RETRIES,
+        }
+    }
 }
 
 // ── Trait ──
@@ -148,6 +321,11 @@ pub trait ScarlettDevice {
         ))
     }
 
+    /// Whether [`wait_notify`](Self::wait_notify) is implemented on this backend.
+    fn supports_notify(&self) -> bool {
+        false
+    }
+
     /// Send a raw IOCTL (bypassing TRANSACT framing).
     /// Default: not supported on this platform.
     fn raw_ioctl(&self, _code: u32, _input: &[u8], _out_size: usize) -> Result<Vec<u8>> {
@@ -155,17 +333,29 @@ pub trait ScarlettDevice {
             "raw_ioctl not supported on this platform".into(),
         ))
     }
+
+    /// Reinitialize the device when transactions keep failing — a USB port
+    /// reset on Linux, a driver re-init on Windows. The handle is unusable
+    /// afterwards: drop it and open the device again.
+    /// Default: not supported on this platform.
+    fn reset(&self) -> Result<()> {
+        Err(DeviceError::TransactFailed(
+            "reset not supported on this platform".into(),
+        ))
+    }
 }
 
 // ── Windows shared helpers ──
 
-/// Shared SetupDi enumeration helpers used by both `WindowsDevice` and `enumerate_devices_windows`.
+/// Shared SetupDi enumeration helpers used by `WindowsDevice`, `enumerate_devices_windows`,
+/// and the busylight indicator.
 #[cfg(windows)]
-mod win_enum {
+pub(crate) mod win_enum {
     use crate::protocol::FOCUSRITE_GUID;
     use std::mem;
     use windows::Win32::Devices::DeviceAndDriverInstallation::*;
-    use windows::core::PCWSTR;
+    use windows::Win32::Devices::Properties::{DEVPKEY_Device_DriverVersion, DEVPROPTYPE};
+    use windows::core::{GUID, PCWSTR};
 
     /// Extract a null-terminated UTF-16 path from SP_DEVICE_INTERFACE_DETAIL_DATA_W.
     ///
@@ -189,36 +379,50 @@ mod win_enum {
     /// Calls `callback(path)` for each discovered path. If the callback returns
     /// `Some(T)`, enumeration stops and returns that value.
     pub fn enumerate_pal_paths<T>(mut callback: impl FnMut(String) -> Option<T>) -> Option<T> {
+        enumerate_interface_paths(&FOCUSRITE_GUID, |path| {
+            if path.to_lowercase().ends_with("\\pal") {
+                callback(path)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Enumerate the paths of present device interfaces of class `guid`
+    /// (e.g. HID), stopping at the first `Some` from `callback`.
+    pub fn enumerate_interface_paths<T>(
+        guid: &GUID,
+        mut callback: impl FnMut(String) -> Option<T>,
+    ) -> Option<T> {
         unsafe {
             let dev_info = SetupDiGetClassDevsW(
-                Some(&FOCUSRITE_GUID),
+                Some(guid),
                 PCWSTR::null(),
                 None,
                 DIGCF_PRESENT | DIGCF_DEVICEINTERFACE,
             )
             .ok()?;
 
-            let result = enumerate_pal_paths_inner(dev_info, &mut callback);
+            let result = enumerate_interface_paths_inner(dev_info, guid, &mut callback);
             let _ = SetupDiDestroyDeviceInfoList(dev_info);
             result
         }
     }
 
-    unsafe fn enumerate_pal_paths_inner<T>(
+    unsafe fn enumerate_interface_paths_inner<T>(
         dev_info: HDEVINFO,
+        guid: &GUID,
         callback: &mut impl FnMut(String) -> Option<T>,
     ) -> Option<T> {
-        for index in 0..32 {
+        for index in 0..256 {
             let mut iface = SP_DEVICE_INTERFACE_DATA {
                 cbSize: mem::size_of::<SP_DEVICE_INTERFACE_DATA>() as u32,
                 ..Default::default()
             };
             // SAFETY: dev_info is a valid handle from SetupDiGetClassDevsW.
             // iface is properly sized and initialized above.
-            if unsafe {
-                SetupDiEnumDeviceInterfaces(dev_info, None, &FOCUSRITE_GUID, index, &mut iface)
-            }
-            .is_err()
+            if unsafe { SetupDiEnumDeviceInterfaces(dev_info, None, guid, index, &mut iface) }
+                .is_err()
             {
                 break;
             }
@@ -243,15 +447,95 @@ mod win_enum {
             {
                 // SAFETY: detail was just filled by SetupDiGetDeviceInterfaceDetailW.
                 let path = unsafe { extract_path(detail) };
-                if path.to_lowercase().ends_with("\\pal") {
-                    if let Some(result) = callback(path) {
-                        return Some(result);
-                    }
+                if let Some(result) = callback(path) {
+                    return Some(result);
                 }
             }
         }
         None
     }
+
+    /// Read the driver version (`DEVPKEY_Device_DriverVersion`) of the first
+    /// Focusrite device node, e.g. `"4.143.0.261"`.
+    pub fn find_driver_version() -> Option<String> {
+        unsafe {
+            let dev_info = SetupDiGetClassDevsW(
+                Some(&FOCUSRITE_GUID),
+                PCWSTR::null(),
+                None,
+                DIGCF_PRESENT | DIGCF_DEVICEINTERFACE,
+            )
+            .ok()?;
+
+            let result = find_driver_version_inner(dev_info);
+            let _ = SetupDiDestroyDeviceInfoList(dev_info);
+            result
+        }
+    }
+
+    unsafe fn find_driver_version_inner(dev_info: HDEVINFO) -> Option<String> {
+        let mut iface = SP_DEVICE_INTERFACE_DATA {
+            cbSize: mem::size_of::<SP_DEVICE_INTERFACE_DATA>() as u32,
+            ..Default::default()
+        };
+        // SAFETY: dev_info is a valid handle from SetupDiGetClassDevsW.
+        unsafe { SetupDiEnumDeviceInterfaces(dev_info, None, &FOCUSRITE_GUID, 0, &mut iface) }
+            .ok()?;
+
+        let mut req: u32 = 0;
+        // SAFETY: first call with NULL buffer to query required size.
+        let _ = unsafe {
+            SetupDiGetDeviceInterfaceDetailW(dev_info, &iface, None, 0, Some(&mut req), None)
+        };
+        if req == 0 {
+            return None;
+        }
+        let mut buf = vec![0u8; req as usize];
+        // SAFETY: buf is req bytes, large enough for the detail struct.
+        let detail = unsafe { &mut *(buf.as_mut_ptr() as *mut SP_DEVICE_INTERFACE_DETAIL_DATA_W) };
+        detail.cbSize = mem::size_of::<SP_DEVICE_INTERFACE_DETAIL_DATA_W>() as u32;
+        let mut dev_data = SP_DEVINFO_DATA {
+            cbSize: mem::size_of::<SP_DEVINFO_DATA>() as u32,
+            ..Default::default()
+        };
+        // SAFETY: detail is properly sized and dev_data.cbSize is set.
+        unsafe {
+            SetupDiGetDeviceInterfaceDetailW(
+                dev_info,
+                &iface,
+                Some(detail),
+                req,
+                None,
+                Some(&mut dev_data),
+            )
+        }
+        .ok()?;
+
+        let mut prop_type = DEVPROPTYPE::default();
+        let mut value = [0u8; 128];
+        // SAFETY: dev_data was filled above; value is a valid output buffer.
+        unsafe {
+            SetupDiGetDevicePropertyW(
+                dev_info,
+                &dev_data,
+                &DEVPKEY_Device_DriverVersion,
+                &mut prop_type,
+                Some(&mut value),
+                None,
+                0,
+            )
+        }
+        .ok()?;
+
+        // DEVPROP_TYPE_STRING: null-terminated UTF-16
+        let wide: Vec<u16> = value
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .take_while(|&c| c != 0)
+            .collect();
+        let version = String::from_utf16_lossy(&wide);
+        (!version.is_empty()).then_some(version)
+    }
 }
 
 // ── Windows implementation ──
@@ -270,6 +554,8 @@ mod windows_impl {
     use windows::Win32::System::Threading::{CreateEventW, WaitForSingleObject};
     use windows::core::PCWSTR;
 
+    use crate::trace;
+
     /// Request/response pair for the I/O worker thread.
     struct IoctlRequest {
         ioctl: u32,
@@ -284,6 +570,7 @@ mod windows_impl {
         token: u64,
         /// Channel to the dedicated I/O worker thread.
         io_tx: std::sync::mpsc::Sender<IoctlRequest>,
+        options: DeviceOptions,
     }
 
     // HANDLE is Send-safe for our usage (single-owner, sync I/O pattern)
@@ -335,22 +622,41 @@ mod windows_impl {
             std::thread::spawn(move || {
                 let h = HANDLE(handle_val as *mut _);
                 while let Ok(req) = rx.recv() {
-                    let result = Self::ioctl_overlapped(h, req.ioctl, &req.input, req.out_size);
+                    let result = trace::traced(
+                        || format!("IOCTL 0x{:08X}", req.ioctl),
+                        &req.input,
+                        || Self::ioctl_overlapped(h, req.ioctl, &req.input, req.out_size),
+                    );
                     let _ = req.reply.send(result);
                 }
             });
             tx
         }
 
-        /// Send an IOCTL via the dedicated I/O worker thread with a 5-second timeout.
+        /// Send an IOCTL via the dedicated I/O worker thread, retrying failures
+        /// up to `options.retries` times.
         fn ioctl_async(
             &self,
             ioctl: u32,
             input: &[u8],
             out_size: usize,
         ) -> std::result::Result<Vec<u8>, String> {
-            use std::time::Duration;
+            let mut result = self.ioctl_async_once(ioctl, input, out_size);
+            for attempt in 1..self.options.attempts() {
+                let Err(ref e) = result else { break };
+                log::debug!("IOCTL 0x{ioctl:08X} failed ({e}), retry {attempt}");
+                result = self.ioctl_async_once(ioctl, input, out_size);
+            }
+            result
+        }
 
+        /// One IOCTL via the worker thread, cancelled after `transact_timeout`.
+        fn ioctl_async_once(
+            &self,
+            ioctl: u32,
+            input: &[u8],
+            out_size: usize,
+        ) -> std::result::Result<Vec<u8>, String> {
             let (reply_tx, reply_rx) = std::sync::mpsc::channel();
             self.io_tx
                 .send(IoctlRequest {
@@ -361,14 +667,15 @@ mod windows_impl {
                 })
                 .map_err(|_| "I/O worker thread has exited".to_string())?;
 
-            match reply_rx.recv_timeout(Duration::from_secs(5)) {
+            let timeout = self.options.transact_timeout;
+            match reply_rx.recv_timeout(timeout) {
                 Ok(result) => result,
                 Err(_) => {
                     // Timeout — cancel ALL pending I/O on this handle.
                     unsafe {
                         let _ = CancelIoEx(self.handle, None);
                     }
-                    Err("IOCTL timed out after 5s".into())
+                    Err(format!("IOCTL timed out after {timeout:?}"))
                 }
             }
         }
@@ -544,21 +851,24 @@ mod windows_impl {
                 .map_err(DeviceError::TransactFailed)
         }
 
-        /// Find the \pal device path and USB serial number.
-        fn find_device() -> Option<(String, Option<String>)> {
+        /// Find the \pal device path and USB `(product_id, serial)`.
+        fn find_device() -> Option<(String, Option<UsbIdentity>)> {
             super::win_enum::enumerate_pal_paths(|path| {
-                let serial = find_usb_serial();
-                Some((path, serial))
+                let usb = find_usb_identity();
+                Some((path, usb))
             })
         }
     }
 
-    /// Find the Focusrite USB device serial by enumerating USB devices.
+    /// `(product_id, serial)` parsed from a USB instance ID.
+    pub(super) type UsbIdentity = (Option<u16>, Option<String>);
+
+    /// Find the Focusrite USB device product ID and serial by enumerating USB devices.
     ///
-    /// Searches SetupDi for `VID_1235` (Focusrite) and extracts the serial from
-    /// the instance ID.  Returns the first match — sufficient for single-device
+    /// Searches SetupDi for `VID_1235` (Focusrite) and parses the instance ID.
+    /// Returns the first match with a serial — sufficient for single-device
     /// setups; multi-device would need PAL↔USB path correlation.
-    pub(super) fn find_usb_serial() -> Option<String> {
+    pub(super) fn find_usb_identity() -> Option<UsbIdentity> {
         // Search for USB devices with VID_1235 (Focusrite) in their instance ID
         let usb_enumerator: Vec<u16> = "USB".encode_utf16().chain(std::iter::once(0)).collect();
         unsafe {
@@ -586,11 +896,10 @@ mod windows_impl {
                     let id_upper = id.to_uppercase();
                     // Match Focusrite USB devices: USB\VID_1235&PID_xxxx\SERIAL
                     if id_upper.contains("VID_1235") {
-                        // Serial is the third segment after the second backslash
-                        let parts: Vec<&str> = id.split('\\').collect();
-                        if parts.len() >= 3 && !parts[2].is_empty() {
+                        let (product_id, serial) = parse_usb_instance_id(id);
+                        if serial.is_some() {
                             let _ = SetupDiDestroyDeviceInfoList(dev_info);
-                            return Some(parts[2].to_string());
+                            return Some((product_id, serial));
                         }
                     }
                 }
@@ -600,9 +909,68 @@ mod windows_impl {
         None
     }
 
-    impl ScarlettDevice for WindowsDevice {
-        fn open() -> Result<Self> {
-            let (path, serial) = Self::find_device().ok_or(DeviceError::NotFound)?;
+    impl WindowsDevice {
+        /// Open the PAL interface at `path` (from [`super::enumerate_devices`]).
+        pub fn open_path(path: &str) -> Result<Self> {
+            Self::open_path_with(path, DeviceOptions::default())
+        }
+
+        /// [`open_path`](Self::open_path) with explicit I/O options.
+        pub fn open_path_with(path: &str, options: DeviceOptions) -> Result<Self> {
+            Self::open_at(path.to_string(), find_usb_identity(), options)
+        }
+
+        /// Open the first device found, with explicit I/O options.
+        pub fn open_with(options: DeviceOptions) -> Result<Self> {
+            let (path, usb) = Self::find_device().ok_or(DeviceError::NotFound)?;
+            Self::open_at(path, usb, options)
+        }
+
+        /// The process holding the PAL interface at `path` (NUL-terminated)
+        /// open, asked of the Restart Manager. Best effort — `None` if it
+        /// can't tell.
+        fn find_holder(path: &[u16]) -> Option<String> {
+            use windows::Win32::System::RestartManager::{
+                CCH_RM_SESSION_KEY, RM_PROCESS_INFO, RmEndSession, RmGetList, RmRegisterResources,
+                RmStartSession,
+            };
+            use windows::core::PWSTR;
+
+            let mut session = 0u32;
+            let mut key = [0u16; CCH_RM_SESSION_KEY as usize + 1];
+            unsafe {
+                if RmStartSession(&mut session, None, PWSTR(key.as_mut_ptr())) != ERROR_SUCCESS {
+                    return None;
+                }
+                let mut holder = None;
+                let files = [PCWSTR(path.as_ptr())];
+                if RmRegisterResources(session, Some(&files[..]), None, None) == ERROR_SUCCESS {
+                    let mut infos = [RM_PROCESS_INFO::default(); 4];
+                    let (mut needed, mut count, mut reasons) = (0u32, infos.len() as u32, 0u32);
+                    if RmGetList(
+                        session,
+                        &mut needed,
+                        &mut count,
+                        Some(infos.as_mut_ptr()),
+                        &mut reasons,
+                    ) == ERROR_SUCCESS
+                    {
+                        holder = infos[..(count as usize).min(infos.len())]
+                            .iter()
+                            .find(|i| i.Process.dwProcessId != std::process::id())
+                            .map(|i| {
+                                let name = String::from_utf16_lossy(&i.strAppName);
+                                process_label(name.trim_end_matches('\0'), i.Process.dwProcessId)
+                            });
+                    }
+                }
+                let _ = RmEndSession(session);
+                holder
+            }
+        }
+
+        fn open_at(path: String, usb: Option<UsbIdentity>, options: DeviceOptions) -> Result<Self> {
+            let (product_id, serial) = usb.unwrap_or_default();
 
             let wide: Vec<u16> = path.encode_utf16().chain(std::iter::once(0)).collect();
             let handle = unsafe {
@@ -616,19 +984,37 @@ mod windows_impl {
                     None,
                 )
             }
-            .map_err(|e| DeviceError::OpenFailed(format!("CreateFileW: {e}")))?;
+            .map_err(|e| {
+                if e.code() == ERROR_SHARING_VIOLATION.to_hresult() {
+                    DeviceError::InUse(Self::find_holder(&wide))
+                } else {
+                    DeviceError::OpenFailed(format!("CreateFileW: {e}"))
+                }
+            })?;
 
             // Init sequence (use ioctl_overlapped directly — worker not yet spawned)
-            Self::ioctl_sync(handle, IOCTL_INIT, &[], 16)
-                .map_err(|e| DeviceError::InitFailed(format!("IOCTL_INIT: {e}")))?;
+            trace::traced(
+                || "IOCTL_INIT".into(),
+                &[],
+                || Self::ioctl_sync(handle, IOCTL_INIT, &[], 16),
+            )
+            .map_err(|e| DeviceError::InitFailed(format!("IOCTL_INIT: {e}")))?;
 
             let init_buf = Self::transact_buf(0, CMD_USB_INIT, &[]);
-            let init_raw = Self::ioctl_overlapped(handle, IOCTL_TRANSACT, &init_buf, 100)
-                .map_err(|e| DeviceError::InitFailed(format!("USB_INIT: {e}")))?;
+            let init_raw = trace::traced(
+                || "IOCTL_TRANSACT (USB_INIT)".into(),
+                &init_buf,
+                || Self::ioctl_overlapped(handle, IOCTL_TRANSACT, &init_buf, 100),
+            )
+            .map_err(|e| DeviceError::InitFailed(format!("USB_INIT: {e}")))?;
 
             let config_buf = Self::transact_buf(0, CMD_GET_CONFIG, &[]);
-            let config_raw = Self::ioctl_overlapped(handle, IOCTL_TRANSACT, &config_buf, 96)
-                .map_err(|e| DeviceError::InitFailed(format!("GET_CONFIG: {e}")))?;
+            let config_raw = trace::traced(
+                || "IOCTL_TRANSACT (GET_CONFIG)".into(),
+                &config_buf,
+                || Self::ioctl_overlapped(handle, IOCTL_TRANSACT, &config_buf, 96),
+            )
+            .map_err(|e| DeviceError::InitFailed(format!("GET_CONFIG: {e}")))?;
 
             if config_raw.len() < 16 {
                 return Err(DeviceError::InitFailed(
@@ -651,9 +1037,14 @@ mod windows_impl {
                     device_name: String::new(),
                     firmware: FirmwareVersion::default(),
                     serial,
+                    product_id,
+                    // Not exposed by the PAL interface; would need a USB hub IOCTL.
+                    usb_speed: None,
+                    driver_version: super::win_enum::find_driver_version(),
                 },
                 token,
                 io_tx,
+                options,
             };
 
             // Read firmware version from descriptor header (offset 0, 16 bytes)
@@ -665,9 +1056,16 @@ mod windows_impl {
             if let Ok(name_bytes) = dev.get_descriptor(16, 32) {
                 dev.info.device_name = parse_device_name(&name_bytes);
             }
+            dev.info.fill_missing_name();
 
             Ok(dev)
         }
+    }
+
+    impl ScarlettDevice for WindowsDevice {
+        fn open() -> Result<Self> {
+            Self::open_with(DeviceOptions::default())
+        }
 
         fn info(&self) -> &DeviceInfo {
             &self.info
@@ -707,14 +1105,43 @@ mod windows_impl {
         fn wait_notify(&self, timeout_ms: u64) -> Result<Vec<u8>> {
             // Direct overlapped I/O — bypasses the I/O worker thread
             // to avoid blocking concurrent TRANSACT operations.
-            Self::ioctl_overlapped_timeout(self.handle, IOCTL_NOTIFY, &[], 16, timeout_ms as u32)
-                .map_err(DeviceError::TransactFailed)
+            trace::traced(
+                || "IOCTL_NOTIFY".into(),
+                &[],
+                || {
+                    let cap = self.options.notify_timeout.as_millis() as u64;
+                    Self::ioctl_overlapped_timeout(
+                        self.handle,
+                        IOCTL_NOTIFY,
+                        &[],
+                        16,
+                        timeout_ms.min(cap) as u32,
+                    )
+                },
+            )
+            .map_err(DeviceError::TransactFailed)
+        }
+
+        fn supports_notify(&self) -> bool {
+            true
         }
 
         fn raw_ioctl(&self, code: u32, input: &[u8], out_size: usize) -> Result<Vec<u8>> {
             self.ioctl_async(code, input, out_size)
                 .map_err(DeviceError::TransactFailed)
         }
+
+        fn reset(&self) -> Result<()> {
+            // No port reset through the PAL interface — re-run the driver
+            // init so the next open starts from a clean session.
+            trace::traced(
+                || "IOCTL_INIT (reset)".into(),
+                &[],
+                || self.ioctl_async(IOCTL_INIT, &[], 16),
+            )
+            .map(drop)
+            .map_err(|e| DeviceError::TransactFailed(format!("IOCTL_INIT: {e}")))
+        }
     }
 
     impl Drop for WindowsDevice {
@@ -734,28 +1161,100 @@ pub use windows_impl::WindowsDevice;
 #[cfg(target_os = "linux")]
 mod linux_impl {
     use super::*;
+    use std::future::Future;
+    use std::pin::Pin;
     use std::sync::atomic::{AtomicU16, Ordering};
-    use std::time::Duration;
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Poll, Wake};
+    use std::time::{Duration, Instant};
 
     use nusb::transfer::Control;
     use nusb::transfer::ControlType;
+    use nusb::transfer::Direction;
+    use nusb::transfer::EndpointType;
+    use nusb::transfer::Queue;
     use nusb::transfer::Recipient;
+    use nusb::transfer::RequestBuffer;
 
     use crate::protocol::{
         FOCUSRITE_VID, USB_BREQUEST_INIT, USB_BREQUEST_RX, USB_BREQUEST_TX, USB_CMD_INIT_1,
-        USB_CMD_INIT_2, USB_HEADER_SIZE, USB_MAX_RETRIES, USB_TIMEOUT_MS, swroot_to_usb_cmd,
+        USB_CMD_INIT_2, USB_HEADER_SIZE, swroot_to_usb_cmd,
     };
+    use crate::trace;
 
     pub struct LinuxDevice {
+        /// Kept for [`ScarlettDevice::reset`]; transfers go through `interface`.
+        device: nusb::Device,
         interface: nusb::Interface,
         info: DeviceInfo,
         seq: AtomicU16,
         interface_number: u16,
+        /// Notification endpoint; `None` if the interface has none.
+        notify: Option<Mutex<NotifyEndpoint>>,
+        options: DeviceOptions,
     }
 
     // nusb::Interface is Send-safe; AtomicU16 is Send+Sync.
     unsafe impl Send for LinuxDevice {}
 
+    /// The vendor interface's interrupt IN endpoint, which carries the same
+    /// notifications the Windows driver hands out through IOCTL_NOTIFY.
+    struct NotifyEndpoint {
+        /// Keeps one transfer pending between [`ScarlettDevice::wait_notify`]
+        /// calls, so a notification that arrives in between isn't lost.
+        queue: Queue<RequestBuffer>,
+        packet_size: usize,
+    }
+
+    /// Address and packet size of the interface's interrupt IN endpoint.
+    fn find_notify_endpoint(interface: &nusb::Interface) -> Option<(u8, usize)> {
+        for alt in interface.descriptors() {
+            for ep in alt.endpoints() {
+                if ep.transfer_type() == EndpointType::Interrupt && ep.direction() == Direction::In
+                {
+                    return Some((ep.address(), ep.max_packet_size()));
+                }
+            }
+        }
+        None
+    }
+
+    /// Lay an interrupt packet (LE32 bitmask first) out like an
+    /// IOCTL_NOTIFY payload, so [`crate::device_events`] decodes both.
+    pub fn notify_payload_from_interrupt(data: &[u8]) -> Vec<u8> {
+        let mut payload = vec![0u8; NOTIFY_MASK_OFFSET + 12];
+        let n = data.len().min(4);
+        payload[NOTIFY_MASK_OFFSET..NOTIFY_MASK_OFFSET + n].copy_from_slice(&data[..n]);
+        payload
+    }
+
+    /// Wakes the thread blocked in [`block_on_timeout`].
+    struct ThreadWaker(std::thread::Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    /// Poll `future` on this thread until it's ready or `timeout` passes.
+    /// Dropping a pending nusb queue future leaves its transfer queued.
+    fn block_on_timeout<F: Future + Unpin>(mut future: F, timeout: Duration) -> Option<F::Output> {
+        let waker = Arc::new(ThreadWaker(std::thread::current())).into();
+        let mut cx = Context::from_waker(&waker);
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Poll::Ready(output) = Pin::new(&mut future).poll(&mut cx) {
+                return Some(output);
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return None;
+            }
+            std::thread::park_timeout(deadline - now);
+        }
+    }
+
     /// Build a raw USB packet: 16-byte header + payload.
     pub fn build_usb_packet(cmd: u32, seq: u16, payload: &[u8]) -> Vec<u8> {
         let size = payload.len() as u16;
@@ -775,6 +1274,7 @@ mod linux_impl {
             brequest: u8,
             windex: u16,
             data: &[u8],
+            timeout: Duration,
         ) -> std::result::Result<(), String> {
             let control = Control {
                 control_type: ControlType::Class,
@@ -783,9 +1283,14 @@ mod linux_impl {
                 value: 0,
                 index: windex,
             };
+            let label = format!("control_out(bRequest={brequest})");
+            trace::transfer(&label, "->", data);
             interface
-                .control_out_blocking(control, data, Duration::from_millis(USB_TIMEOUT_MS))
-                .map_err(|e| format!("control_out(bRequest={brequest}): {e}"))?;
+                .control_out_blocking(control, data, timeout)
+                .map_err(|e| {
+                    trace::failure(&label, &e.to_string());
+                    format!("{label}: {e}")
+                })?;
             Ok(())
         }
 
@@ -794,6 +1299,7 @@ mod linux_impl {
             brequest: u8,
             windex: u16,
             length: usize,
+            timeout: Duration,
         ) -> std::result::Result<Vec<u8>, String> {
             let control = Control {
                 control_type: ControlType::Class,
@@ -803,21 +1309,28 @@ mod linux_impl {
                 index: windex,
             };
             let mut buf = vec![0u8; length];
+            let label = format!("control_in(bRequest={brequest})");
             let n = interface
-                .control_in_blocking(control, &mut buf, Duration::from_millis(USB_TIMEOUT_MS))
-                .map_err(|e| format!("control_in(bRequest={brequest}): {e}"))?;
+                .control_in_blocking(control, &mut buf, timeout)
+                .map_err(|e| {
+                    trace::failure(&label, &e.to_string());
+                    format!("{label}: {e}")
+                })?;
             buf.truncate(n);
+            trace::transfer(&label, "<-", &buf);
             Ok(buf)
         }
 
         /// Send a raw USB command and receive the response.
         ///
-        /// Handles sequence counting and retry logic.
+        /// Handles sequence counting and retry logic (`options.retries`).
         /// Returns the response payload (after the 16-byte header).
         fn usb_transact(&self, cmd: u32, payload: &[u8], resp_size: usize) -> Result<Vec<u8>> {
             let seq = self.seq.load(Ordering::Relaxed);
+            let attempts = self.options.attempts();
+            let timeout = self.options.transact_timeout;
 
-            for attempt in 0..USB_MAX_RETRIES {
+            for attempt in 0..attempts {
                 let pkt = build_usb_packet(cmd, seq, payload);
 
                 // TX
@@ -826,6 +1339,7 @@ mod linux_impl {
                     USB_BREQUEST_TX,
                     self.interface_number,
                     &pkt,
+                    timeout,
                 )
                 .map_err(DeviceError::TransactFailed)?;
 
@@ -836,11 +1350,12 @@ mod linux_impl {
                     USB_BREQUEST_RX,
                     self.interface_number,
                     rx_size,
+                    timeout,
                 )
                 .map_err(DeviceError::TransactFailed)?;
 
                 if resp.len() < USB_HEADER_SIZE {
-                    if attempt + 1 < USB_MAX_RETRIES {
+                    if attempt + 1 < attempts {
                         let delay = 5 * (1 << attempt);
                         std::thread::sleep(Duration::from_millis(delay));
                         continue;
@@ -872,7 +1387,7 @@ mod linux_impl {
 
                 // Check error code
                 if resp_error != 0 {
-                    if attempt + 1 < USB_MAX_RETRIES {
+                    if attempt + 1 < attempts {
                         let delay = 5 * (1 << attempt);
                         std::thread::sleep(Duration::from_millis(delay));
                         continue;
@@ -893,15 +1408,54 @@ mod linux_impl {
         }
     }
 
-    impl ScarlettDevice for LinuxDevice {
-        fn open() -> Result<Self> {
-            // Find device
+    impl LinuxDevice {
+        /// Open the device at `path` (from [`super::enumerate_devices`],
+        /// `usb:BBB/DDD [vvvv:pppp]`); only the bus/address prefix is matched.
+        pub fn open_path(path: &str) -> Result<Self> {
+            Self::open_path_with(path, DeviceOptions::default())
+        }
+
+        /// [`open_path`](Self::open_path) with explicit I/O options.
+        pub fn open_path_with(path: &str, options: DeviceOptions) -> Result<Self> {
+            let device_info = nusb::list_devices()
+                .map_err(|e| DeviceError::OpenFailed(format!("USB enumeration: {e}")))?
+                .find(|dev| {
+                    dev.vendor_id() == FOCUSRITE_VID
+                        && path.starts_with(&format!(
+                            "usb:{:03}/{:03}",
+                            dev.bus_number(),
+                            dev.device_address()
+                        ))
+                })
+                .ok_or(DeviceError::NotFound)?;
+            Self::open_info(device_info, options)
+        }
+
+        /// Open the first device found, with explicit I/O options.
+        pub fn open_with(options: DeviceOptions) -> Result<Self> {
             let device_info = nusb::list_devices()
                 .map_err(|e| DeviceError::OpenFailed(format!("USB enumeration: {e}")))?
                 .find(|dev| dev.vendor_id() == FOCUSRITE_VID)
                 .ok_or(DeviceError::NotFound)?;
+            Self::open_info(device_info, options)
+        }
 
+        /// The process holding the device node of `device_info` open, found
+        /// by scanning `/proc`. Best effort — processes of other users can't
+        /// be inspected without root.
+        fn find_holder(device_info: &nusb::DeviceInfo) -> Option<String> {
+            let node = format!(
+                "/dev/bus/usb/{:03}/{:03}",
+                device_info.bus_number(),
+                device_info.device_address()
+            );
+            process_holding(std::path::Path::new("/proc"), std::path::Path::new(&node))
+        }
+
+        fn open_info(device_info: nusb::DeviceInfo, options: DeviceOptions) -> Result<Self> {
             let serial = device_info.serial_number().map(|s| s.to_string());
+            let product_id = Some(device_info.product_id());
+            let usb_speed = device_info.speed().and_then(UsbSpeed::from_nusb);
             let product = device_info.product_string().unwrap_or_default().to_string();
             let bus_path = format!(
                 "usb:{:03}/{:03}",
@@ -922,19 +1476,36 @@ mod linux_impl {
 
             // Claim interface (nusb auto-detaches kernel driver)
             let interface = usb_device.claim_interface(iface_num).map_err(|e| {
-                DeviceError::OpenFailed(format!("claim interface {iface_num}: {e}"))
+                if e.kind() == std::io::ErrorKind::ResourceBusy {
+                    DeviceError::InUse(Self::find_holder(&device_info))
+                } else {
+                    DeviceError::OpenFailed(format!("claim interface {iface_num}: {e}"))
+                }
             })?;
 
             let windex = iface_num as u16;
+            let notify = find_notify_endpoint(&interface).map(|(address, packet_size)| {
+                Mutex::new(NotifyEndpoint {
+                    queue: interface.interrupt_in_queue(address),
+                    packet_size,
+                })
+            });
 
             // Step 0 — "cargo cult" init read (bRequest=0, 24 bytes)
-            let _ = Self::control_in(&interface, USB_BREQUEST_INIT, windex, 24);
+            let _ = Self::control_in(
+                &interface,
+                USB_BREQUEST_INIT,
+                windex,
+                24,
+                options.transact_timeout,
+            );
 
             // Sleep 20ms to let pending ACKs drain
             std::thread::sleep(Duration::from_millis(20));
 
             // Create device with placeholder info
             let mut dev = LinuxDevice {
+                device: usb_device,
                 interface,
                 info: DeviceInfo {
                     path: bus_path,
@@ -943,9 +1514,14 @@ mod linux_impl {
                     device_name: product,
                     firmware: FirmwareVersion::default(),
                     serial,
+                    product_id,
+                    usb_speed,
+                    driver_version: None,
                 },
                 seq: AtomicU16::new(1), // set to 1 before init steps
                 interface_number: windex,
+                notify,
+                options,
             };
 
             // Step 1 — INIT_1 (cmd=0x00000000, seq=1)
@@ -965,13 +1541,24 @@ mod linux_impl {
                 dev.info.firmware = FirmwareVersion::from_descriptor_bytes(&hdr);
             }
 
-            // Read device name from descriptor (offset 16, 32 bytes)
+            // Read device name from descriptor (offset 16, 32 bytes); keep the
+            // USB product string if the descriptor name is blank
             if let Ok(name_bytes) = dev.get_descriptor(16, 32) {
-                dev.info.device_name = parse_device_name(&name_bytes);
+                let name = parse_device_name(&name_bytes);
+                if !name.trim().is_empty() {
+                    dev.info.device_name = name;
+                }
             }
+            dev.info.fill_missing_name();
 
             Ok(dev)
         }
+    }
+
+    impl ScarlettDevice for LinuxDevice {
+        fn open() -> Result<Self> {
+            Self::open_with(DeviceOptions::default())
+        }
 
         fn info(&self) -> &DeviceInfo {
             &self.info
@@ -1021,6 +1608,42 @@ mod linux_impl {
             compat.extend_from_slice(&raw_resp);
             Ok(compat)
         }
+
+        fn wait_notify(&self, timeout_ms: u64) -> Result<Vec<u8>> {
+            let Some(ref notify) = self.notify else {
+                return Err(DeviceError::TransactFailed(
+                    "device has no notification endpoint".into(),
+                ));
+            };
+            let mut notify = notify.lock().unwrap_or_else(|e| e.into_inner());
+            if notify.queue.pending() == 0 {
+                let size = notify.packet_size;
+                notify.queue.submit(RequestBuffer::new(size));
+            }
+            let timeout = Duration::from_millis(timeout_ms).min(self.options.notify_timeout);
+            let completion =
+                block_on_timeout(notify.queue.next_complete(), timeout).ok_or_else(|| {
+                    DeviceError::TransactFailed(format!("notify timed out after {timeout_ms}ms"))
+                })?;
+            // Listen for the next one right away
+            let size = notify.packet_size;
+            notify.queue.submit(RequestBuffer::new(size));
+            let data = completion
+                .into_result()
+                .map_err(|e| DeviceError::TransactFailed(format!("interrupt_in: {e}")))?;
+            Ok(notify_payload_from_interrupt(&data))
+        }
+
+        fn supports_notify(&self) -> bool {
+            self.notify.is_some()
+        }
+
+        fn reset(&self) -> Result<()> {
+            log::info!("resetting USB device {}", self.info.path);
+            self.device
+                .reset()
+                .map_err(|e| DeviceError::TransactFailed(format!("USB reset: {e}")))
+        }
     }
 }
 
@@ -1034,6 +1657,19 @@ pub use linux_impl::LinuxDevice;
 #[cfg(not(any(windows, target_os = "linux")))]
 pub struct StubDevice;
 
+#[cfg(not(any(windows, target_os = "linux")))]
+impl StubDevice {
+    pub fn open_path(_path: &str) -> Result<Self> {
+        Err(DeviceError::NotFound)
+    }
+    pub fn open_path_with(_path: &str, _options: DeviceOptions) -> Result<Self> {
+        Err(DeviceError::NotFound)
+    }
+    pub fn open_with(_options: DeviceOptions) -> Result<Self> {
+        Err(DeviceError::NotFound)
+    }
+}
+
 #[cfg(not(any(windows, target_os = "linux")))]
 impl ScarlettDevice for StubDevice {
     fn open() -> Result<Self> {
@@ -1065,6 +1701,21 @@ pub struct DiscoveredDevice {
     pub path: String,
     /// USB serial number, if available.
     pub serial: Option<String>,
+    /// USB product ID, if available.
+    pub product_id: Option<u16>,
+    /// Product name looked up from `product_id` (see [`crate::models::KNOWN_PRODUCTS`]).
+    pub model: Option<&'static str>,
+    /// Negotiated USB bus speed, if the platform reports it.
+    pub usb_speed: Option<UsbSpeed>,
+    /// Installed Focusrite driver version (Windows only).
+    pub driver_version: Option<String>,
+}
+
+/// Product name for a USB product ID, if it is a known interface.
+fn known_model(product_id: Option<u16>) -> Option<&'static str> {
+    product_id
+        .and_then(crate::models::product_by_id)
+        .map(|p| p.name)
 }
 
 /// Enumerate all Focusrite device interfaces.
@@ -1088,12 +1739,17 @@ pub fn enumerate_devices() -> Vec<DiscoveredDevice> {
 
 #[cfg(windows)]
 fn enumerate_devices_windows() -> Vec<DiscoveredDevice> {
-    let serial = windows_impl::find_usb_serial();
+    let (product_id, serial) = windows_impl::find_usb_identity().unwrap_or_default();
+    let driver_version = win_enum::find_driver_version();
     let mut devices = Vec::new();
     win_enum::enumerate_pal_paths(|path| {
         devices.push(DiscoveredDevice {
             path,
             serial: serial.clone(),
+            product_id,
+            model: known_model(product_id),
+            usb_speed: None,
+            driver_version: driver_version.clone(),
         });
         None::<()> // continue enumerating
     });
@@ -1123,7 +1779,14 @@ fn enumerate_devices_linux() -> Vec<DiscoveredDevice> {
                 dev.product_id(),
             );
             let serial = dev.serial_number().map(|s| s.to_string());
-            DiscoveredDevice { path, serial }
+            DiscoveredDevice {
+                path,
+                serial,
+                product_id: Some(dev.product_id()),
+                model: known_model(Some(dev.product_id())),
+                usb_speed: dev.speed().and_then(UsbSpeed::from_nusb),
+                driver_version: None,
+            }
         })
         .collect()
 }
@@ -1145,14 +1808,24 @@ pub fn open_device() -> Result<PlatformDevice> {
     PlatformDevice::open()
 }
 
+/// Open the device at an enumerated path (see [`DiscoveredDevice::path`]).
+pub fn open_device_at(path: &str) -> Result<PlatformDevice> {
+    PlatformDevice::open_path(path)
+}
+
 /// Open a device matching the given serial number.
 ///
 /// If `serial` is empty, delegates to [`open_device`] (auto-select).
 /// Otherwise enumerates devices and opens the one with a matching serial.
 pub fn open_device_by_serial(serial: &str) -> Result<PlatformDevice> {
+    open_device_with_options(serial, DeviceOptions::default())
+}
+
+/// [`open_device_by_serial`] with explicit I/O timeouts and retries.
+pub fn open_device_with_options(serial: &str, options: DeviceOptions) -> Result<PlatformDevice> {
     let serial = serial.trim();
     if serial.is_empty() {
-        return open_device();
+        return PlatformDevice::open_with(options);
     }
     let devices = enumerate_devices();
     let matched = devices.iter().find(|d| {
@@ -1161,16 +1834,7 @@ pub fn open_device_by_serial(serial: &str) -> Result<PlatformDevice> {
             .is_some_and(|s| s.eq_ignore_ascii_case(serial))
     });
     if let Some(matched_dev) = matched {
-        // Verify the matched device is the first one — multi-device selection
-        // (path-based open) isn't yet supported, so reject ambiguous cases.
-        if devices.len() > 1 && devices[0].path != matched_dev.path {
-            return Err(DeviceError::OpenFailed(format!(
-                "device with serial '{serial}' found but multi-device selection is not yet supported \
-                 (found {} devices; matched device is not the first)",
-                devices.len()
-            )));
-        }
-        open_device()
+        PlatformDevice::open_path_with(&matched_dev.path, options)
     } else if devices.is_empty() {
         Err(DeviceError::NotFound)
     } else {
@@ -1194,7 +1858,7 @@ pub fn open_device_by_serial(serial: &str) -> Result<PlatformDevice> {
 pub mod mock {
     use super::*;
     use std::cell::{Cell, RefCell};
-    use std::collections::HashMap;
+    use std::collections::{HashMap, VecDeque};
 
     /// In-memory device for unit tests. Stores descriptor data in a HashMap
     /// keyed by offset; `set_descriptor` writes, `get_descriptor` reads.
@@ -1213,6 +1877,12 @@ pub mod mock {
         pub get_descriptor_max_len: Cell<Option<usize>>,
         /// If true, `set_descriptor` returns an error.
         pub fail_set_descriptor: Cell<bool>,
+        /// Queued `wait_notify` payloads; an empty queue behaves like a timeout.
+        pub pending_notifications: RefCell<VecDeque<Vec<u8>>>,
+        /// Value returned by `supports_notify` (default true).
+        pub notify_supported: Cell<bool>,
+        /// Number of `reset` calls.
+        pub resets: Cell<u32>,
     }
 
     impl Default for MockDevice {
@@ -1236,6 +1906,9 @@ pub mod mock {
                         build_nr: 4,
                     },
                     serial: Some("MOCK123".into()),
+                    product_id: Some(0x8219),
+                    usb_speed: Some(UsbSpeed::High),
+                    driver_version: None,
                 },
                 descriptors: RefCell::new(HashMap::new()),
                 notifies: RefCell::new(Vec::new()),
@@ -1243,6 +1916,9 @@ pub mod mock {
                 transact_payloads: RefCell::new(Vec::new()),
                 get_descriptor_max_len: Cell::new(None),
                 fail_set_descriptor: Cell::new(false),
+                pending_notifications: RefCell::new(VecDeque::new()),
+                notify_supported: Cell::new(true),
+                resets: Cell::new(0),
             }
         }
 
@@ -1260,6 +1936,14 @@ pub mod mock {
                 .or_default()
                 .push(response);
         }
+
+        /// Queue a notification with the given IOCTL_NOTIFY bitmask.
+        pub fn push_notification(&self, mask: u32) {
+            let mut payload = vec![0u8; 16];
+            payload[NOTIFY_MASK_OFFSET..NOTIFY_MASK_OFFSET + 4]
+                .copy_from_slice(&mask.to_le_bytes());
+            self.pending_notifications.borrow_mut().push_back(payload);
+        }
     }
 
     impl ScarlettDevice for MockDevice {
@@ -1323,6 +2007,24 @@ pub mod mock {
                 "no mock handler for cmd 0x{cmd:08X}"
             )))
         }
+
+        fn wait_notify(&self, timeout_ms: u64) -> Result<Vec<u8>> {
+            self.pending_notifications
+                .borrow_mut()
+                .pop_front()
+                .ok_or_else(|| {
+                    DeviceError::TransactFailed(format!("IOCTL timed out after {timeout_ms}ms"))
+                })
+        }
+
+        fn supports_notify(&self) -> bool {
+            self.notify_supported.get()
+        }
+
+        fn reset(&self) -> Result<()> {
+            self.resets.set(self.resets.get() + 1);
+            Ok(())
+        }
     }
 }
 
@@ -1346,6 +2048,9 @@ mod tests {
                 build_nr: 0,
             },
             serial: Some("ABC123".into()),
+            product_id: None,
+            usb_speed: None,
+            driver_version: None,
         };
         let json = serde_json::to_string(&info).expect("serialize DeviceInfo");
         assert!(json.contains("\"path\""), "should contain path");
@@ -1364,6 +2069,10 @@ mod tests {
         let d = DiscoveredDevice {
             path: r"\\?\usb#vid_1235&pid_8215#pal".into(),
             serial: Some("ABCD1234".into()),
+            product_id: None,
+            model: None,
+            usb_speed: None,
+            driver_version: None,
         };
         let json = serde_json::to_string(&d).expect("serialize DiscoveredDevice");
         assert!(json.contains("\"path\""));
@@ -1371,6 +2080,92 @@ mod tests {
         assert!(json.contains("ABCD1234"));
     }
 
+    #[test]
+    fn device_info_serializes_usb_extras() {
+        let dev = mock::MockDevice::new();
+        let json = serde_json::to_value(dev.info()).expect("serialize DeviceInfo");
+        assert_eq!(json["product_id"], 0x8219);
+        assert_eq!(json["usb_speed"], "high");
+        assert!(json["driver_version"].is_null());
+    }
+
+    // ── Device in use ──
+
+    #[test]
+    fn in_use_names_the_holder() {
+        assert_eq!(
+            DeviceError::InUse(Some("ableton (PID 42)".into())).to_string(),
+            "Device in use by ableton (PID 42)"
+        );
+        assert_eq!(
+            DeviceError::InUse(None).to_string(),
+            "Device in use by another application"
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn process_holding_finds_fd_link() {
+        let proc_root = tempfile::tempdir().unwrap();
+        let node = proc_root.path().join("bus-usb-001-004");
+        std::fs::write(&node, b"").unwrap();
+        for (pid, name, target) in [
+            ("17", "pipewire\n", proc_root.path().join("other")),
+            ("4242", "ableton\n", node.clone()),
+        ] {
+            let dir = proc_root.path().join(pid);
+            std::fs::create_dir_all(dir.join("fd")).unwrap();
+            std::fs::write(dir.join("comm"), name).unwrap();
+            std::os::unix::fs::symlink(&target, dir.join("fd").join("3")).unwrap();
+        }
+        std::fs::create_dir(proc_root.path().join("self")).unwrap();
+
+        assert_eq!(
+            process_holding(proc_root.path(), &node).as_deref(),
+            Some("ableton (PID 4242)")
+        );
+        assert_eq!(
+            process_holding(proc_root.path(), &proc_root.path().join("none")),
+            None
+        );
+    }
+
+    // ── USB extras ──
+
+    #[test]
+    fn usb_speed_display() {
+        assert_eq!(UsbSpeed::High.to_string(), "High Speed (480 Mbit/s)");
+        assert_eq!(UsbSpeed::Full.to_string(), "Full Speed (12 Mbit/s)");
+    }
+
+    #[test]
+    fn usb_speed_serializes_snake_case() {
+        let json = serde_json::to_string(&UsbSpeed::SuperPlus).unwrap();
+        assert_eq!(json, "\"super_plus\"");
+    }
+
+    #[test]
+    fn parse_usb_instance_id_full() {
+        let (pid, serial) = parse_usb_instance_id(r"USB\VID_1235&PID_8219\S2ABCDEF123456");
+        assert_eq!(pid, Some(0x8219));
+        assert_eq!(serial.as_deref(), Some("S2ABCDEF123456"));
+    }
+
+    #[test]
+    fn parse_usb_instance_id_lowercase_and_mi_suffix() {
+        let (pid, _) = parse_usb_instance_id(r"USB\vid_1235&pid_821a&mi_00\7&1234&0&0000");
+        assert_eq!(pid, Some(0x821A));
+    }
+
+    #[test]
+    fn parse_usb_instance_id_missing_parts() {
+        assert_eq!(parse_usb_instance_id(r"USB\VID_1235"), (None, None));
+        assert_eq!(
+            parse_usb_instance_id(r"USB\VID_1235&PID_ZZZZ\"),
+            (None, None)
+        );
+    }
+
     // ── FirmwareVersion ──
 
     #[test]
@@ -1390,6 +2185,31 @@ mod tests {
         assert_eq!(v.to_string(), "0.0.0.0");
     }
 
+    // ── Known products ──
+
+    #[test]
+    fn known_model_from_product_id() {
+        assert_eq!(known_model(Some(0x821D)), Some("Scarlett 18i20 4th Gen"));
+        assert_eq!(known_model(Some(0x8219)), Some("Scarlett 2i2 4th Gen"));
+        assert_eq!(known_model(Some(0xFFFF)), None);
+        assert_eq!(known_model(None), None);
+    }
+
+    #[test]
+    fn fill_missing_name_uses_product_id() {
+        let mut dev = mock::MockDevice::new();
+        dev.info_mut().device_name = String::new();
+        dev.info_mut().product_id = Some(0x821A);
+        dev.info_mut().fill_missing_name();
+        assert_eq!(dev.info().model(), "Scarlett 4i4 4th Gen");
+
+        // A descriptor name is never overwritten
+        let mut dev = mock::MockDevice::new();
+        dev.info_mut().product_id = Some(0x821A);
+        dev.info_mut().fill_missing_name();
+        assert_eq!(dev.info().model(), "Scarlett 2i2 4th Gen");
+    }
+
     // ── DeviceInfo::token ──
 
     #[test]
@@ -1404,10 +2224,33 @@ mod tests {
             device_name: String::new(),
             firmware: FirmwareVersion::default(),
             serial: None,
+            product_id: None,
+            usb_speed: None,
+            driver_version: None,
         };
         assert_eq!(info.token(), 0xDEAD_BEEF_CAFE_BABE);
     }
 
+    #[test]
+    fn sample_rate_from_config() {
+        let mut config = vec![0u8; 96];
+        config[20..24].copy_from_slice(&48_000u32.to_le_bytes());
+        let mut info = DeviceInfo {
+            path: String::new(),
+            config_raw: config,
+            init_raw: vec![],
+            device_name: String::new(),
+            firmware: FirmwareVersion::default(),
+            serial: None,
+            product_id: None,
+            usb_speed: None,
+            driver_version: None,
+        };
+        assert_eq!(info.sample_rate(), Some(48_000));
+        info.config_raw.truncate(16);
+        assert_eq!(info.sample_rate(), None);
+    }
+
     #[test]
     fn token_from_short_config_returns_zero() {
         let info = DeviceInfo {
@@ -1417,6 +2260,9 @@ mod tests {
             device_name: String::new(),
             firmware: FirmwareVersion::default(),
             serial: None,
+            product_id: None,
+            usb_speed: None,
+            driver_version: None,
         };
         assert_eq!(info.token(), 0);
     }
@@ -1430,6 +2276,9 @@ mod tests {
             device_name: String::new(),
             firmware: FirmwareVersion::default(),
             serial: None,
+            product_id: None,
+            usb_speed: None,
+            driver_version: None,
         };
         assert_eq!(info.token(), 0);
     }
@@ -1445,6 +2294,9 @@ mod tests {
             device_name: "Scarlett 2i2 4th Gen-0003186a".into(),
             firmware: FirmwareVersion::default(),
             serial: None,
+            product_id: None,
+            usb_speed: None,
+            driver_version: None,
         };
         assert_eq!(info.model(), "Scarlett 2i2 4th Gen");
     }
@@ -1458,6 +2310,9 @@ mod tests {
             device_name: "Scarlett Solo".into(),
             firmware: FirmwareVersion::default(),
             serial: None,
+            product_id: None,
+            usb_speed: None,
+            driver_version: None,
         };
         assert_eq!(info.model(), "Scarlett Solo");
     }
@@ -1471,6 +2326,9 @@ mod tests {
             device_name: String::new(),
             firmware: FirmwareVersion::default(),
             serial: None,
+            product_id: None,
+            usb_speed: None,
+            driver_version: None,
         };
         assert_eq!(info.model(), "");
     }
@@ -1484,6 +2342,9 @@ mod tests {
             device_name: "  Scarlett 2i2  -serial".into(),
             firmware: FirmwareVersion::default(),
             serial: None,
+            product_id: None,
+            usb_speed: None,
+            driver_version: None,
         };
         assert_eq!(info.model(), "Scarlett 2i2");
     }
@@ -1497,6 +2358,9 @@ mod tests {
             device_name: "Scarlett 4i4-Pro-0003186a".into(),
             firmware: FirmwareVersion::default(),
             serial: None,
+            product_id: None,
+            usb_speed: None,
+            driver_version: None,
         };
         assert_eq!(info.model(), "Scarlett 4i4-Pro");
     }
@@ -1510,6 +2374,9 @@ mod tests {
             device_name: "-".into(),
             firmware: FirmwareVersion::default(),
             serial: None,
+            product_id: None,
+            usb_speed: None,
+            driver_version: None,
         };
         assert_eq!(info.model(), "");
     }
@@ -1609,6 +2476,25 @@ mod tests {
         assert_eq!(&pkt[16..], &payload);
     }
 
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn interrupt_packet_becomes_notify_payload() {
+        let payload = linux_impl::notify_payload_from_interrupt(&[0, 0, 0, 0x02, 0, 0, 0, 0]);
+        assert_eq!(payload.len(), 16);
+        assert_eq!(
+            crate::device_events::decode_notification(&payload),
+            crate::device_events::decode_notification(&{
+                let mut p = vec![0u8; 16];
+                p[NOTIFY_MASK_OFFSET..NOTIFY_MASK_OFFSET + 4]
+                    .copy_from_slice(&0x0200_0000u32.to_le_bytes());
+                p
+            })
+        );
+        assert!(!crate::device_events::decode_notification(&payload).is_empty());
+        // Short packets don't panic
+        assert_eq!(linux_impl::notify_payload_from_interrupt(&[1]).len(), 16);
+    }
+
     // ── enumerate_devices ──
 
     #[test]
@@ -1623,6 +2509,10 @@ mod tests {
         let d = DiscoveredDevice {
             path: r"\\?\usb#vid_1235&pid_8215#pal".into(),
             serial: Some("ABCD1234".into()),
+            product_id: None,
+            model: None,
+            usb_speed: None,
+            driver_version: None,
         };
         assert!(d.path.contains("pal"));
         assert_eq!(d.serial.as_deref(), Some("ABCD1234"));
@@ -0,0 +1,913 @@
+//! Device controls — read and switch front-panel features from software.
+//!
+//! 48V phantom power, Air, instrument/line level, input gain, Clip Safe, Auto
+//! Gain, and output volume. The firmware schema describes each control as an
+//! APP_SPACE member (`enablePhantomPower`, `inputAir`, `instInput`,
+//! `preampInputGain`, `clipSafe`, `autogainInProgress`, `outputVol`); like
+//! most front-panel controls the u8 ones are flagged `set-via-parameter-buffer`,
+//! so a write places the channel in `parameterChannel` and the value in
+//! `parameterValue`, then sends the member's DATA_NOTIFY. Output volume (i16
+//! dB) is written to its own offset. Reads come straight from the member.
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::device::{DeviceError, Result, ScarlettDevice};
+use crate::device_events::{DeviceButton, DeviceEvent};
+use crate::protocol::*;
+use crate::schema::{ControlMember, SchemaConstants, VolumeMember};
+
+/// Air mode of an input. Models with a plain on/off Air switch only accept
+/// `Off` and `Presence`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AirMode {
+    Off,
+    /// Air "Presence" — high-frequency lift.
+    Presence,
+    /// Air "Presence + Drive" — adds harmonic drive.
+    PresenceDrive,
+}
+
+impl AirMode {
+    /// Decode the `inputAir` descriptor byte.
+    pub fn from_raw(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(AirMode::Off),
+            1 => Some(AirMode::Presence),
+            2 => Some(AirMode::PresenceDrive),
+            _ => None,
+        }
+    }
+
+    /// The `inputAir` descriptor byte for this mode.
+    pub fn raw(self) -> u8 {
+        match self {
+            AirMode::Off => 0,
+            AirMode::Presence => 1,
+            AirMode::PresenceDrive => 2,
+        }
+    }
+}
+
+impl fmt::Display for AirMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AirMode::Off => write!(f, "off"),
+            AirMode::Presence => write!(f, "presence"),
+            AirMode::PresenceDrive => write!(f, "drive"),
+        }
+    }
+}
+
+impl FromStr for AirMode {
+    type Err = String;
+
+    /// Parse `off`, `presence` (or `on`), or `drive` (or `presence-drive`).
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "off" => Ok(AirMode::Off),
+            "presence" | "on" => Ok(AirMode::Presence),
+            "drive" | "presence-drive" => Ok(AirMode::PresenceDrive),
+            other => Err(format!(
+                "unknown Air mode \"{other}\" (expected off, presence, or drive)"
+            )),
+        }
+    }
+}
+
+/// Highest `preampInputGain` value (the Linux scarlett2 driver's
+/// `SCARLETT2_MAX_GAIN_VALUE`). One step is roughly 1 dB.
+pub const MAX_GAIN: u8 = 70;
+
+/// `enablePhantomPower` on the Scarlett 2i2 4th Gen, used when its schema
+/// could not be read.
+pub const PHANTOM_POWER_2I2: ControlMember = ControlMember {
+    offset: OFF_PHANTOM_POWER,
+    count: PHANTOM_POWER_COUNT,
+    notify: NOTIFY_PHANTOM_POWER,
+    via_parameter_buffer: true,
+};
+
+/// The phantom power control for a device.
+///
+/// Uses the schema's `enablePhantomPower` member. Without a schema, only the
+/// Scarlett 2i2 4th Gen falls back to [`PHANTOM_POWER_2I2`]; other models
+/// return `None` rather than guess at offsets.
+pub fn phantom_power_control(
+    schema: Option<&SchemaConstants>,
+    model_name: &str,
+) -> Option<ControlMember> {
+    match schema {
+        Some(sc) => sc.phantom_power,
+        None if model_name.eq_ignore_ascii_case("Scarlett 2i2 4th Gen") => Some(PHANTOM_POWER_2I2),
+        None => None,
+    }
+}
+
+/// The Air control for a device — the schema's `inputAir` member. There is
+/// no hardcoded fallback.
+pub fn air_control(schema: Option<&SchemaConstants>) -> Option<ControlMember> {
+    schema.and_then(|sc| sc.air)
+}
+
+/// The instrument/line control for a device — the schema's `instInput`
+/// member. There is no hardcoded fallback.
+pub fn inst_input_control(schema: Option<&SchemaConstants>) -> Option<ControlMember> {
+    schema.and_then(|sc| sc.inst_input)
+}
+
+fn check_channel(name: &str, control: &ControlMember, channel: usize) -> Result<()> {
+    if channel >= control.count {
+        return Err(DeviceError::TransactFailed(format!(
+            "{name}: channel {} out of range (device has {})",
+            channel + 1,
+            control.count
+        )));
+    }
+    Ok(())
+}
+
+fn read_control(
+    device: &impl ScarlettDevice,
+    name: &str,
+    control: &ControlMember,
+    channel: usize,
+) -> Result<u8> {
+    check_channel(name, control, channel)?;
+    let data = device.get_descriptor(control.offset + channel as u32, 1)?;
+    Ok(data.first().copied().unwrap_or(0))
+}
+
+fn write_control(
+    device: &impl ScarlettDevice,
+    name: &str,
+    control: &ControlMember,
+    channel: usize,
+    value: u8,
+) -> Result<()> {
+    check_channel(name, control, channel)?;
+    if control.via_parameter_buffer {
+        device.set_descriptor(OFF_PARAMETER_CHANNEL, &[channel as u8])?;
+        device.set_descriptor(OFF_PARAMETER_VALUE, &[value])?;
+    } else {
+        device.set_descriptor(control.offset + channel as u32, &[value])?;
+    }
+    device.data_notify(control.notify)
+}
+
+/// Whether 48V is on for phantom power switch `channel` (0-indexed).
+pub fn get_phantom_power(
+    device: &impl ScarlettDevice,
+    control: &ControlMember,
+    channel: usize,
+) -> Result<bool> {
+    Ok(read_control(device, "enablePhantomPower", control, channel)? != 0)
+}
+
+/// Switch 48V on or off for phantom power switch `channel` (0-indexed).
+pub fn set_phantom_power(
+    device: &impl ScarlettDevice,
+    control: &ControlMember,
+    channel: usize,
+    on: bool,
+) -> Result<()> {
+    write_control(device, "enablePhantomPower", control, channel, u8::from(on))
+}
+
+/// Current Air mode of `input` (0-indexed).
+pub fn get_air(
+    device: &impl ScarlettDevice,
+    control: &ControlMember,
+    input: usize,
+) -> Result<AirMode> {
+    let raw = read_control(device, "inputAir", control, input)?;
+    AirMode::from_raw(raw)
+        .ok_or_else(|| DeviceError::TransactFailed(format!("inputAir: unknown value {raw}")))
+}
+
+/// Set the Air mode of `input` (0-indexed).
+pub fn set_air(
+    device: &impl ScarlettDevice,
+    control: &ControlMember,
+    input: usize,
+    mode: AirMode,
+) -> Result<()> {
+    write_control(device, "inputAir", control, input, mode.raw())
+}
+
+/// The input gain control for a device — the schema's `preampInputGain`
+/// member. There is no hardcoded fallback.
+pub fn gain_control(schema: Option<&SchemaConstants>) -> Option<ControlMember> {
+    schema.and_then(|sc| sc.preamp_gain)
+}
+
+/// Current gain of `input` (0-indexed), `0..=MAX_GAIN`.
+pub fn get_gain(device: &impl ScarlettDevice, control: &ControlMember, input: usize) -> Result<u8> {
+    read_control(device, "preampInputGain", control, input)
+}
+
+/// Set the gain of `input` (0-indexed). Values above [`MAX_GAIN`] are
+/// clamped; returns the value written.
+pub fn set_gain(
+    device: &impl ScarlettDevice,
+    control: &ControlMember,
+    input: usize,
+    value: u8,
+) -> Result<u8> {
+    let value = value.min(MAX_GAIN);
+    write_control(device, "preampInputGain", control, input, value)?;
+    Ok(value)
+}
+
+/// Whether `input` (0-indexed) is set to instrument level (`false` = line).
+pub fn get_instrument_mode(
+    device: &impl ScarlettDevice,
+    control: &ControlMember,
+    input: usize,
+) -> Result<bool> {
+    Ok(read_control(device, "instInput", control, input)? != 0)
+}
+
+/// Switch `input` (0-indexed) to instrument (`true`) or line (`false`) level.
+pub fn set_instrument_mode(
+    device: &impl ScarlettDevice,
+    control: &ControlMember,
+    input: usize,
+    instrument: bool,
+) -> Result<()> {
+    write_control(device, "instInput", control, input, u8::from(instrument))
+}
+
+/// The Clip Safe control for a device — the schema's `clipSafe` member.
+/// There is no hardcoded fallback.
+pub fn clip_safe_control(schema: Option<&SchemaConstants>) -> Option<ControlMember> {
+    schema.and_then(|sc| sc.clip_safe)
+}
+
+/// Whether Clip Safe is on for `input` (0-indexed).
+pub fn get_clip_safe(
+    device: &impl ScarlettDevice,
+    control: &ControlMember,
+    input: usize,
+) -> Result<bool> {
+    Ok(read_control(device, "clipSafe", control, input)? != 0)
+}
+
+/// Switch Clip Safe on or off for `input` (0-indexed).
+pub fn set_clip_safe(
+    device: &impl ScarlettDevice,
+    control: &ControlMember,
+    input: usize,
+    on: bool,
+) -> Result<()> {
+    write_control(device, "clipSafe", control, input, u8::from(on))
+}
+
+/// The speaker switching control — the schema's `activeMonitorGroup` member.
+/// There is no hardcoded fallback.
+pub fn alt_speakers_control(schema: Option<&SchemaConstants>) -> Option<ControlMember> {
+    schema.and_then(|sc| sc.alt_speakers)
+}
+
+/// Whether the alt speakers are active (`false` = main speakers).
+pub fn get_alt_speakers(device: &impl ScarlettDevice, control: &ControlMember) -> Result<bool> {
+    Ok(read_control(device, "activeMonitorGroup", control, 0)? != 0)
+}
+
+/// Switch to the alt speakers (`true`) or back to the main speakers.
+pub fn set_alt_speakers(
+    device: &impl ScarlettDevice,
+    control: &ControlMember,
+    alt: bool,
+) -> Result<()> {
+    write_control(device, "activeMonitorGroup", control, 0, u8::from(alt))
+}
+
+/// Lowest output volume in dB (the scarlett2 driver's volume bias); 0 dB is
+/// the maximum.
+pub const MIN_OUTPUT_VOLUME_DB: i16 = -127;
+
+/// The output volume control for a device — the schema's `outputVol`
+/// member. There is no hardcoded fallback.
+pub fn output_volume_control(schema: Option<&SchemaConstants>) -> Option<VolumeMember> {
+    schema.and_then(|sc| sc.output_volume)
+}
+
+fn check_output(control: &VolumeMember, output: usize) -> Result<()> {
+    if output >= control.count {
+        return Err(DeviceError::TransactFailed(format!(
+            "outputVol: output {} out of range (device has {})",
+            output + 1,
+            control.count
+        )));
+    }
+    Ok(())
+}
+
+/// Current volume of `output` (0-indexed) in dB.
+pub fn get_output_volume(
+    device: &impl ScarlettDevice,
+    control: &VolumeMember,
+    output: usize,
+) -> Result<i16> {
+    check_output(control, output)?;
+    let data = device.get_descriptor(control.offset + 2 * output as u32, 2)?;
+    Ok(match data.as_slice() {
+        [lo, hi, ..] => i16::from_le_bytes([*lo, *hi]),
+        _ => 0,
+    })
+}
+
+/// Set the volume of `output` (0-indexed) in dB. Values outside
+/// `MIN_OUTPUT_VOLUME_DB..=0` are clamped; returns the value written.
+///
+/// Fails without writing when the member has no notify ID — the output is
+/// then set by a hardware knob and the firmware would ignore the write.
+pub fn set_output_volume(
+    device: &impl ScarlettDevice,
+    control: &VolumeMember,
+    output: usize,
+    db: i16,
+) -> Result<i16> {
+    check_output(control, output)?;
+    let notify = control.notify.ok_or_else(|| {
+        DeviceError::TransactFailed(
+            "outputVol is controlled by the hardware knob on this model".into(),
+        )
+    })?;
+    let db = db.clamp(MIN_OUTPUT_VOLUME_DB, 0);
+    device.set_descriptor(control.offset + 2 * output as u32, &db.to_le_bytes())?;
+    device.data_notify(notify)?;
+    Ok(db)
+}
+
+/// Outputs lowered by [`duck_outputs`], with the volumes to put back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputDuck {
+    /// `(output, original dB, ducked dB)` for each output.
+    outputs: Vec<(usize, i16, i16)>,
+}
+
+impl OutputDuck {
+    /// Put the outputs back to their volumes from before the duck, except
+    /// any turned since (by hand, or from Focusrite Control 2). Returns how
+    /// many were restored.
+    pub fn restore(&self, device: &impl ScarlettDevice, control: &VolumeMember) -> Result<usize> {
+        let mut restored = 0;
+        for &(output, original, ducked) in &self.outputs {
+            if get_output_volume(device, control, output)? == ducked {
+                set_output_volume(device, control, output, original)?;
+                restored += 1;
+            }
+        }
+        Ok(restored)
+    }
+}
+
+/// Lower every output by `db` dB (down to [`MIN_OUTPUT_VOLUME_DB`]),
+/// remembering the volumes for [`OutputDuck::restore`]. If a write fails,
+/// the outputs already lowered are put back.
+pub fn duck_outputs(
+    device: &impl ScarlettDevice,
+    control: &VolumeMember,
+    db: u32,
+) -> Result<OutputDuck> {
+    let originals = (0..control.count)
+        .map(|output| get_output_volume(device, control, output))
+        .collect::<Result<Vec<i16>>>()?;
+    let lower = i16::try_from(db).unwrap_or(i16::MAX);
+    let mut duck = OutputDuck {
+        outputs: Vec::with_capacity(originals.len()),
+    };
+    for (output, original) in originals.into_iter().enumerate() {
+        match set_output_volume(device, control, output, original.saturating_sub(lower)) {
+            Ok(ducked) => duck.outputs.push((output, original, ducked)),
+            Err(e) => {
+                let _ = duck.restore(device, control);
+                return Err(e);
+            }
+        }
+    }
+    Ok(duck)
+}
+
+/// How often [`wait_for_autogain`] re-reads `autogainInProgress`.
+pub const AUTOGAIN_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// How an Auto Gain run ended — the firmware's `AutogainResult` enum, read
+/// from `autogainExitStatus`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutogainResult {
+    Success,
+    /// Succeeded, but the signal's dynamic range was higher than expected.
+    SuccessDynamicRangeOver,
+    /// Gain was set, but limited by the minimum gain.
+    MinGainLimit,
+    /// The signal was too quiet to measure.
+    DynamicRangeUnder,
+    /// The signal was too quiet even at maximum gain.
+    MaxGainLimit,
+    Clipped,
+    Cancelled,
+    /// A code this version doesn't know.
+    Other(u8),
+}
+
+impl AutogainResult {
+    /// Decode the `autogainExitStatus` descriptor byte.
+    pub fn from_raw(value: u8) -> Self {
+        match value {
+            0 => AutogainResult::Success,
+            1 => AutogainResult::SuccessDynamicRangeOver,
+            2 => AutogainResult::MinGainLimit,
+            3 => AutogainResult::DynamicRangeUnder,
+            4 => AutogainResult::MaxGainLimit,
+            5 => AutogainResult::Clipped,
+            6 => AutogainResult::Cancelled,
+            n => AutogainResult::Other(n),
+        }
+    }
+
+    /// Whether Auto Gain left a usable gain setting.
+    pub fn is_success(self) -> bool {
+        matches!(
+            self,
+            AutogainResult::Success
+                | AutogainResult::SuccessDynamicRangeOver
+                | AutogainResult::MinGainLimit
+        )
+    }
+}
+
+impl fmt::Display for AutogainResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AutogainResult::Success => write!(f, "success"),
+            AutogainResult::SuccessDynamicRangeOver => {
+                write!(f, "success (signal dynamic range was high)")
+            }
+            AutogainResult::MinGainLimit => write!(f, "set, limited by minimum gain"),
+            AutogainResult::DynamicRangeUnder => write!(f, "failed — signal too quiet"),
+            AutogainResult::MaxGainLimit => write!(f, "failed — too quiet even at maximum gain"),
+            AutogainResult::Clipped => write!(f, "failed — signal clipped"),
+            AutogainResult::Cancelled => write!(f, "cancelled"),
+            AutogainResult::Other(n) => write!(f, "unknown result {n}"),
+        }
+    }
+}
+
+/// The Auto Gain control for a device — the schema's `autogainInProgress`
+/// member. There is no hardcoded fallback.
+pub fn autogain_control(schema: Option<&SchemaConstants>) -> Option<ControlMember> {
+    schema.and_then(|sc| sc.autogain)
+}
+
+/// Start Auto Gain on `input` (0-indexed). The device listens for a few
+/// seconds, sets the gain, and clears `autogainInProgress`.
+pub fn start_autogain(
+    device: &impl ScarlettDevice,
+    control: &ControlMember,
+    input: usize,
+) -> Result<()> {
+    write_control(device, "autogainInProgress", control, input, 1)
+}
+
+/// Cancel a running Auto Gain on `input` (0-indexed).
+pub fn cancel_autogain(
+    device: &impl ScarlettDevice,
+    control: &ControlMember,
+    input: usize,
+) -> Result<()> {
+    write_control(device, "autogainInProgress", control, input, 0)
+}
+
+/// Whether Auto Gain is running on `input` (0-indexed).
+pub fn autogain_in_progress(
+    device: &impl ScarlettDevice,
+    control: &ControlMember,
+    input: usize,
+) -> Result<bool> {
+    Ok(read_control(device, "autogainInProgress", control, input)? != 0)
+}
+
+/// Whether Auto Gain is running on any input. One read covers every input.
+pub fn any_autogain_in_progress(
+    device: &impl ScarlettDevice,
+    control: &ControlMember,
+) -> Result<bool> {
+    let data = device.get_descriptor(control.offset, control.count as u32)?;
+    Ok(data.iter().any(|&b| b != 0))
+}
+
+/// Poll `autogainInProgress` until it clears. `progress` is called with the
+/// elapsed time before each wait and may return `false` to stop waiting.
+///
+/// Returns `Ok(true)` once the run has finished, `Ok(false)` on timeout or
+/// when `progress` stopped the wait (the run may still be going).
+pub fn wait_for_autogain(
+    device: &impl ScarlettDevice,
+    control: &ControlMember,
+    input: usize,
+    timeout: Duration,
+    mut progress: impl FnMut(Duration) -> bool,
+) -> Result<bool> {
+    let start = Instant::now();
+    loop {
+        if !autogain_in_progress(device, control, input)? {
+            return Ok(true);
+        }
+        let elapsed = start.elapsed();
+        if elapsed >= timeout || !progress(elapsed) {
+            return Ok(false);
+        }
+        std::thread::sleep(AUTOGAIN_POLL_INTERVAL);
+    }
+}
+
+/// How the last Auto Gain run on `input` (0-indexed) ended, from the
+/// `autogainExitStatus` member at `status_offset`.
+pub fn autogain_result(
+    device: &impl ScarlettDevice,
+    status_offset: u32,
+    input: usize,
+) -> Result<AutogainResult> {
+    let data = device.get_descriptor(status_offset + input as u32, 1)?;
+    Ok(AutogainResult::from_raw(data.first().copied().unwrap_or(0)))
+}
+
+/// A front-panel switch kept in step with the mute state
+/// (`mute_button_sync`): Input 1's Air or Inst, on while muted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MuteSwitch {
+    pub button: DeviceButton,
+    control: ControlMember,
+}
+
+impl MuteSwitch {
+    /// The switch for a `mute_button` name. `None` for buttons without an
+    /// on/off state (Select, Direct Monitor) or when the schema lacks the
+    /// member.
+    pub fn for_button(name: &str, schema: Option<&SchemaConstants>) -> Option<Self> {
+        let button = match DeviceEvent::from_button_name(name)? {
+            DeviceEvent::ButtonPressed(button @ (DeviceButton::Air | DeviceButton::Inst)) => button,
+            _ => return None,
+        };
+        let control = match button {
+            DeviceButton::Air => air_control(schema),
+            _ => inst_input_control(schema),
+        }?;
+        Some(Self { button, control })
+    }
+
+    /// The event the watcher reports when the switch is flipped.
+    pub fn event(&self) -> DeviceEvent {
+        DeviceEvent::ButtonPressed(self.button)
+    }
+
+    /// Whether the switch is on (any Air mode counts).
+    pub fn get(&self, device: &impl ScarlettDevice) -> Result<bool> {
+        match self.button {
+            DeviceButton::Air => Ok(get_air(device, &self.control, 0)? != AirMode::Off),
+            _ => get_instrument_mode(device, &self.control, 0),
+        }
+    }
+
+    /// Turn the switch on or off, unless it's already there. Returns whether
+    /// it was written.
+    pub fn set(&self, device: &impl ScarlettDevice, on: bool) -> Result<bool> {
+        if self.get(device)? == on {
+            return Ok(false);
+        }
+        match self.button {
+            DeviceButton::Air => {
+                let mode = if on { AirMode::Presence } else { AirMode::Off };
+                set_air(device, &self.control, 0, mode)?;
+            }
+            _ => set_instrument_mode(device, &self.control, 0, on)?,
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device::mock::MockDevice;
+
+    #[test]
+    fn set_via_parameter_buffer() {
+        let dev = MockDevice::new();
+        set_phantom_power(&dev, &PHANTOM_POWER_2I2, 0, true).unwrap();
+
+        let descs = dev.descriptors.borrow();
+        assert_eq!(descs[&OFF_PARAMETER_CHANNEL], vec![0]);
+        assert_eq!(descs[&OFF_PARAMETER_VALUE], vec![1]);
+        assert!(!descs.contains_key(&OFF_PHANTOM_POWER));
+        assert_eq!(dev.notifies.borrow().as_slice(), &[NOTIFY_PHANTOM_POWER]);
+    }
+
+    #[test]
+    fn set_direct_write_when_not_parameter_buffered() {
+        let dev = MockDevice::new();
+        let control = ControlMember {
+            offset: 100,
+            count: 2,
+            notify: 11,
+            via_parameter_buffer: false,
+        };
+        set_phantom_power(&dev, &control, 1, true).unwrap();
+        assert_eq!(dev.descriptors.borrow()[&101], vec![1]);
+        assert!(get_phantom_power(&dev, &control, 1).unwrap());
+    }
+
+    #[test]
+    fn get_reads_member_offset() {
+        let dev = MockDevice::new();
+        dev.set_descriptor(OFF_PHANTOM_POWER, &[1]).unwrap();
+        assert!(get_phantom_power(&dev, &PHANTOM_POWER_2I2, 0).unwrap());
+        dev.set_descriptor(OFF_PHANTOM_POWER, &[0]).unwrap();
+        assert!(!get_phantom_power(&dev, &PHANTOM_POWER_2I2, 0).unwrap());
+    }
+
+    #[test]
+    fn out_of_range_switch_writes_nothing() {
+        let dev = MockDevice::new();
+        assert!(set_phantom_power(&dev, &PHANTOM_POWER_2I2, 1, true).is_err());
+        assert!(dev.descriptors.borrow().is_empty());
+        assert!(dev.notifies.borrow().is_empty());
+    }
+
+    fn air_2i2() -> ControlMember {
+        ControlMember {
+            offset: 62,
+            count: 2,
+            notify: 11,
+            via_parameter_buffer: true,
+        }
+    }
+
+    #[test]
+    fn air_mode_parse_and_display_round_trip() {
+        for mode in [AirMode::Off, AirMode::Presence, AirMode::PresenceDrive] {
+            assert_eq!(mode.to_string().parse::<AirMode>().unwrap(), mode);
+            assert_eq!(AirMode::from_raw(mode.raw()), Some(mode));
+        }
+        assert_eq!("ON".parse::<AirMode>().unwrap(), AirMode::Presence);
+        assert!("loud".parse::<AirMode>().is_err());
+        assert_eq!(AirMode::from_raw(3), None);
+    }
+
+    #[test]
+    fn set_air_writes_channel_and_mode() {
+        let dev = MockDevice::new();
+        set_air(&dev, &air_2i2(), 1, AirMode::PresenceDrive).unwrap();
+        let descs = dev.descriptors.borrow();
+        assert_eq!(descs[&OFF_PARAMETER_CHANNEL], vec![1]);
+        assert_eq!(descs[&OFF_PARAMETER_VALUE], vec![2]);
+        assert_eq!(dev.notifies.borrow().as_slice(), &[11]);
+    }
+
+    #[test]
+    fn mute_switch_follows_air_or_inst() {
+        // Needs the schema member, and a button with an on/off state
+        assert!(MuteSwitch::for_button("air", None).is_none());
+        assert!(MuteSwitch::for_button("select", None).is_none());
+        let switch = MuteSwitch {
+            button: DeviceButton::Air,
+            control: air_2i2(),
+        };
+        assert_eq!(
+            switch.event(),
+            DeviceEvent::ButtonPressed(DeviceButton::Air)
+        );
+
+        let dev = MockDevice::new();
+        dev.set_descriptor(62, &[2]).unwrap();
+        assert!(switch.get(&dev).unwrap());
+        assert!(!switch.set(&dev, true).unwrap());
+        assert!(switch.set(&dev, false).unwrap());
+        assert_eq!(dev.descriptors.borrow()[&OFF_PARAMETER_VALUE], vec![0]);
+        assert_eq!(dev.notifies.borrow().as_slice(), &[11]);
+    }
+
+    #[test]
+    fn get_air_decodes_and_rejects_unknown() {
+        let dev = MockDevice::new();
+        dev.set_descriptor(63, &[1]).unwrap();
+        assert_eq!(get_air(&dev, &air_2i2(), 1).unwrap(), AirMode::Presence);
+        dev.set_descriptor(63, &[7]).unwrap();
+        assert!(get_air(&dev, &air_2i2(), 1).is_err());
+    }
+
+    #[test]
+    fn set_gain_clamps_to_max() {
+        let dev = MockDevice::new();
+        let control = ControlMember {
+            offset: 75,
+            count: 2,
+            notify: 12,
+            via_parameter_buffer: true,
+        };
+        assert_eq!(set_gain(&dev, &control, 0, 40).unwrap(), 40);
+        assert_eq!(set_gain(&dev, &control, 1, 200).unwrap(), MAX_GAIN);
+        assert_eq!(
+            dev.descriptors.borrow()[&OFF_PARAMETER_VALUE],
+            vec![MAX_GAIN]
+        );
+        assert_eq!(dev.notifies.borrow().as_slice(), &[12, 12]);
+
+        dev.set_descriptor(76, &[33]).unwrap();
+        assert_eq!(get_gain(&dev, &control, 1).unwrap(), 33);
+        assert!(get_gain(&dev, &control, 2).is_err());
+        assert_eq!(gain_control(None), None);
+    }
+
+    #[test]
+    fn air_requires_schema() {
+        assert_eq!(air_control(None), None);
+    }
+
+    #[test]
+    fn instrument_mode_round_trip() {
+        let dev = MockDevice::new();
+        let control = ControlMember {
+            offset: 60,
+            count: 2,
+            notify: 11,
+            via_parameter_buffer: true,
+        };
+        set_instrument_mode(&dev, &control, 1, true).unwrap();
+        assert_eq!(dev.descriptors.borrow()[&OFF_PARAMETER_CHANNEL], vec![1]);
+        assert_eq!(dev.descriptors.borrow()[&OFF_PARAMETER_VALUE], vec![1]);
+        assert!(!get_instrument_mode(&dev, &control, 1).unwrap());
+        dev.set_descriptor(61, &[1]).unwrap();
+        assert!(get_instrument_mode(&dev, &control, 1).unwrap());
+        assert!(set_instrument_mode(&dev, &control, 2, false).is_err());
+        assert_eq!(inst_input_control(None), None);
+    }
+
+    #[test]
+    fn control_falls_back_only_for_2i2() {
+        assert_eq!(
+            phantom_power_control(None, "Scarlett 2i2 4th Gen"),
+            Some(PHANTOM_POWER_2I2)
+        );
+        assert_eq!(phantom_power_control(None, "Scarlett 4i4 4th Gen"), None);
+    }
+
+    #[test]
+    fn clip_safe_round_trip() {
+        let dev = MockDevice::new();
+        let control = ControlMember {
+            offset: 327,
+            count: 2,
+            notify: 14,
+            via_parameter_buffer: true,
+        };
+        set_clip_safe(&dev, &control, 0, true).unwrap();
+        assert_eq!(dev.descriptors.borrow()[&OFF_PARAMETER_CHANNEL], vec![0]);
+        assert_eq!(dev.descriptors.borrow()[&OFF_PARAMETER_VALUE], vec![1]);
+        assert_eq!(dev.notifies.borrow().as_slice(), &[14]);
+        dev.set_descriptor(327, &[1]).unwrap();
+        assert!(get_clip_safe(&dev, &control, 0).unwrap());
+        assert!(set_clip_safe(&dev, &control, 2, true).is_err());
+        assert_eq!(clip_safe_control(None), None);
+    }
+
+    #[test]
+    fn alt_speakers_round_trip() {
+        let dev = MockDevice::new();
+        let control = ControlMember {
+            offset: 340,
+            count: 1,
+            notify: 15,
+            via_parameter_buffer: false,
+        };
+        assert!(!get_alt_speakers(&dev, &control).unwrap());
+        set_alt_speakers(&dev, &control, true).unwrap();
+        assert_eq!(dev.descriptors.borrow()[&340], vec![1]);
+        assert_eq!(dev.notifies.borrow().as_slice(), &[15]);
+        assert!(get_alt_speakers(&dev, &control).unwrap());
+        assert_eq!(alt_speakers_control(None), None);
+    }
+
+    #[test]
+    fn output_volume_read_write() {
+        let dev = MockDevice::new();
+        let control = VolumeMember {
+            offset: 50,
+            count: 2,
+            notify: Some(28),
+        };
+        assert_eq!(set_output_volume(&dev, &control, 1, -200).unwrap(), -127);
+        assert_eq!(dev.descriptors.borrow()[&52], (-127i16).to_le_bytes());
+        assert_eq!(dev.notifies.borrow().as_slice(), &[28]);
+        assert_eq!(get_output_volume(&dev, &control, 1).unwrap(), -127);
+        assert_eq!(set_output_volume(&dev, &control, 0, 6).unwrap(), 0);
+        assert!(get_output_volume(&dev, &control, 2).is_err());
+    }
+
+    #[test]
+    fn ducking_lowers_and_restores_untouched_outputs() {
+        let dev = MockDevice::new();
+        let control = VolumeMember {
+            offset: 50,
+            count: 2,
+            notify: Some(28),
+        };
+        dev.set_descriptor(50, &(-10i16).to_le_bytes()).unwrap();
+        dev.set_descriptor(52, &(-120i16).to_le_bytes()).unwrap();
+        let duck = duck_outputs(&dev, &control, 20).unwrap();
+        assert_eq!(get_output_volume(&dev, &control, 0).unwrap(), -30);
+        assert_eq!(get_output_volume(&dev, &control, 1).unwrap(), -127);
+
+        // Output 2 turned up by hand while ducked keeps its new volume
+        set_output_volume(&dev, &control, 1, -40).unwrap();
+        assert_eq!(duck.restore(&dev, &control).unwrap(), 1);
+        assert_eq!(get_output_volume(&dev, &control, 0).unwrap(), -10);
+        assert_eq!(get_output_volume(&dev, &control, 1).unwrap(), -40);
+
+        let knob = VolumeMember {
+            notify: None,
+            ..control
+        };
+        assert!(duck_outputs(&dev, &knob, 20).is_err());
+        assert_eq!(get_output_volume(&dev, &control, 0).unwrap(), -10);
+    }
+
+    #[test]
+    fn knob_controlled_volume_is_read_only() {
+        let dev = MockDevice::new();
+        let control = VolumeMember {
+            offset: 50,
+            count: 2,
+            notify: None,
+        };
+        dev.set_descriptor(50, &(-20i16).to_le_bytes()).unwrap();
+        assert_eq!(get_output_volume(&dev, &control, 0).unwrap(), -20);
+        dev.descriptors.borrow_mut().clear();
+        assert!(set_output_volume(&dev, &control, 0, -10).is_err());
+        assert!(dev.descriptors.borrow().is_empty());
+        assert!(dev.notifies.borrow().is_empty());
+    }
+
+    fn autogain_2i2() -> ControlMember {
+        ControlMember {
+            offset: 309,
+            count: 2,
+            notify: 10,
+            via_parameter_buffer: true,
+        }
+    }
+
+    #[test]
+    fn start_autogain_writes_parameter_buffer() {
+        let dev = MockDevice::new();
+        start_autogain(&dev, &autogain_2i2(), 1).unwrap();
+        let descs = dev.descriptors.borrow();
+        assert_eq!(descs[&OFF_PARAMETER_CHANNEL], vec![1]);
+        assert_eq!(descs[&OFF_PARAMETER_VALUE], vec![1]);
+        assert_eq!(dev.notifies.borrow().as_slice(), &[10]);
+    }
+
+    #[test]
+    fn wait_for_autogain_stops_on_completion_or_timeout() {
+        let dev = MockDevice::new();
+        dev.set_descriptor(309, &[1]).unwrap();
+        let mut polls = 0;
+        let done = wait_for_autogain(&dev, &autogain_2i2(), 0, Duration::from_secs(5), |_| {
+            polls += 1;
+            false
+        })
+        .unwrap();
+        assert!(!done);
+        assert_eq!(polls, 1);
+
+        dev.set_descriptor(309, &[0]).unwrap();
+        let done = wait_for_autogain(&dev, &autogain_2i2(), 0, Duration::ZERO, |_| {
+            panic!("finished run should not report progress")
+        })
+        .unwrap();
+        assert!(done);
+    }
+
+    #[test]
+    fn any_autogain_in_progress_checks_every_input() {
+        let dev = MockDevice::new();
+        assert!(!any_autogain_in_progress(&dev, &autogain_2i2()).unwrap());
+        dev.set_descriptor(310, &[1]).unwrap();
+        assert!(any_autogain_in_progress(&dev, &autogain_2i2()).unwrap());
+    }
+
+    #[test]
+    fn autogain_result_decodes_exit_status() {
+        let dev = MockDevice::new();
+        dev.set_descriptor(312, &[5]).unwrap();
+        let result = autogain_result(&dev, 311, 1).unwrap();
+        assert_eq!(result, AutogainResult::Clipped);
+        assert!(!result.is_success());
+        assert!(AutogainResult::from_raw(1).is_success());
+        assert_eq!(AutogainResult::from_raw(42), AutogainResult::Other(42));
+    }
+}
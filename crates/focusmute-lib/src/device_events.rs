@@ -0,0 +1,235 @@
+//! Hardware events — decode device notifications into typed [`DeviceEvent`]s.
+//!
+//! The firmware reports front-panel changes (Select, Air, Inst, 48V, …)
+//! through IOCTL_NOTIFY: bytes 4-7 of the 16-byte response are an
+//! `eDEV_FCP_NOTIFY_MESSAGE_TYPE` bitmask, and several bits can be set in one
+//! notification. The payload only says *what* changed — read the descriptor
+//! (e.g. `OFF_SELECTED_INPUT`) for the new value.
+
+use crate::device::ScarlettDevice;
+use crate::protocol::*;
+
+/// A front-panel button with its own notification bit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceButton {
+    Air,
+    Inst,
+    DirectMonitoring,
+}
+
+/// A hardware-side change reported by the device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceEvent {
+    /// Select button pressed — `selectedInput` changed. The firmware repaints
+    /// the number LEDs when this happens. Also fired for channel-linking changes.
+    SelectedInputChanged,
+    /// A front-panel button was toggled.
+    ButtonPressed(DeviceButton),
+    /// 48V phantom power toggled.
+    PhantomPowerChanged,
+    /// A jack was plugged into or removed from an input.
+    InputJackChanged,
+    /// Input gain changed.
+    InputGainChanged,
+    /// Auto gain started, finished, or failed.
+    AutogainChanged,
+    /// Clip Safe state changed.
+    ClipSafeChanged,
+    /// Low supply voltage detected.
+    LowVoltage,
+    /// Clock sync status changed — sample rate switch or lock lost/regained.
+    /// Read [`crate::clock::read_clock_status`] for the new state.
+    ClockChanged,
+}
+
+/// Notification bits in decode order.
+const EVENT_BITS: [(u32, DeviceEvent); 11] = [
+    (NOTIFY_MASK_SELECT, DeviceEvent::SelectedInputChanged),
+    (
+        NOTIFY_MASK_AIR,
+        DeviceEvent::ButtonPressed(DeviceButton::Air),
+    ),
+    (
+        NOTIFY_MASK_INST,
+        DeviceEvent::ButtonPressed(DeviceButton::Inst),
+    ),
+    (
+        NOTIFY_MASK_DIRECT_MONITORING,
+        DeviceEvent::ButtonPressed(DeviceButton::DirectMonitoring),
+    ),
+    (NOTIFY_MASK_PHANTOM_POWER, DeviceEvent::PhantomPowerChanged),
+    (NOTIFY_MASK_TRS_INPUT, DeviceEvent::InputJackChanged),
+    (NOTIFY_MASK_INPUT_GAIN, DeviceEvent::InputGainChanged),
+    (NOTIFY_MASK_AUTOGAIN, DeviceEvent::AutogainChanged),
+    (NOTIFY_MASK_CLIPSAFE, DeviceEvent::ClipSafeChanged),
+    (NOTIFY_MASK_LOW_VOLTAGE, DeviceEvent::LowVoltage),
+    (NOTIFY_MASK_SYNC, DeviceEvent::ClockChanged),
+];
+
+impl DeviceEvent {
+    /// Every event, in decode order.
+    pub fn all() -> impl Iterator<Item = DeviceEvent> {
+        EVENT_BITS.iter().map(|&(_, event)| event)
+    }
+
+    /// Name of the `[device_hooks]` entry that runs on this event.
+    pub fn hook_name(self) -> &'static str {
+        match self {
+            DeviceEvent::SelectedInputChanged => "on_input_select",
+            DeviceEvent::ButtonPressed(DeviceButton::Air) => "on_air_changed",
+            DeviceEvent::ButtonPressed(DeviceButton::Inst) => "on_inst_changed",
+            DeviceEvent::ButtonPressed(DeviceButton::DirectMonitoring) => {
+                "on_direct_monitoring_changed"
+            }
+            DeviceEvent::PhantomPowerChanged => "on_phantom_changed",
+            DeviceEvent::InputJackChanged => "on_jack_changed",
+            DeviceEvent::InputGainChanged => "on_gain_changed",
+            DeviceEvent::AutogainChanged => "on_autogain_changed",
+            DeviceEvent::ClipSafeChanged => "on_clip_safe_changed",
+            DeviceEvent::LowVoltage => "on_low_voltage",
+            DeviceEvent::ClockChanged => "on_clock_changed",
+        }
+    }
+
+    /// The press event for a front-panel button named in the `mute_button`
+    /// setting: `"select"`, `"air"`, `"inst"`, or `"direct_monitoring"`.
+    pub fn from_button_name(name: &str) -> Option<DeviceEvent> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "select" => Some(DeviceEvent::SelectedInputChanged),
+            "air" => Some(DeviceEvent::ButtonPressed(DeviceButton::Air)),
+            "inst" => Some(DeviceEvent::ButtonPressed(DeviceButton::Inst)),
+            "direct_monitoring" => Some(DeviceEvent::ButtonPressed(DeviceButton::DirectMonitoring)),
+            _ => None,
+        }
+    }
+
+    /// Look up an event by its [`hook_name`](Self::hook_name).
+    pub fn from_hook_name(name: &str) -> Option<DeviceEvent> {
+        Self::all().find(|e| e.hook_name() == name)
+    }
+}
+
+/// Decode an IOCTL_NOTIFY payload into events. Unknown bits (e.g. the
+/// low-order ACK bits) are ignored; a short payload yields no events.
+pub fn decode_notification(payload: &[u8]) -> Vec<DeviceEvent> {
+    let Some(bytes) = payload.get(NOTIFY_MASK_OFFSET..NOTIFY_MASK_OFFSET + 4) else {
+        return Vec::new();
+    };
+    let mask = u32::from_le_bytes(bytes.try_into().unwrap_or_default());
+    EVENT_BITS
+        .iter()
+        .filter(|(bit, _)| mask & bit != 0)
+        .map(|&(_, event)| event)
+        .collect()
+}
+
+/// Wait up to `timeout_ms` for a notification and decode it.
+///
+/// Returns no events on timeout, on error, or when the backend has no
+/// notification support (see [`ScarlettDevice::supports_notify`]) — callers
+/// poll this from their event loop and detect device loss elsewhere.
+pub fn poll_events(device: &impl ScarlettDevice, timeout_ms: u64) -> Vec<DeviceEvent> {
+    if !device.supports_notify() {
+        return Vec::new();
+    }
+    match device.wait_notify(timeout_ms) {
+        Ok(payload) => decode_notification(&payload),
+        Err(e) => {
+            log::trace!("[device] no notification: {e}");
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device::mock::MockDevice;
+
+    fn payload(mask: u32) -> Vec<u8> {
+        let mut p = vec![0u8; 16];
+        p[4..8].copy_from_slice(&mask.to_le_bytes());
+        p
+    }
+
+    #[test]
+    fn decode_select_press() {
+        assert_eq!(
+            decode_notification(&payload(0x0200_0000)),
+            vec![DeviceEvent::SelectedInputChanged]
+        );
+    }
+
+    #[test]
+    fn decode_multiple_bits() {
+        // Inst press observed as 0x44000000 (Inst + gain change)
+        assert_eq!(
+            decode_notification(&payload(0x4400_0000)),
+            vec![
+                DeviceEvent::ButtonPressed(DeviceButton::Inst),
+                DeviceEvent::InputGainChanged,
+            ]
+        );
+    }
+
+    #[test]
+    fn decode_phantom_toggle() {
+        assert_eq!(
+            decode_notification(&payload(NOTIFY_MASK_PHANTOM_POWER)),
+            vec![DeviceEvent::PhantomPowerChanged]
+        );
+    }
+
+    #[test]
+    fn decode_clock_change() {
+        assert_eq!(
+            decode_notification(&payload(NOTIFY_MASK_SYNC | 0x0000_0001)),
+            vec![DeviceEvent::ClockChanged]
+        );
+    }
+
+    #[test]
+    fn decode_ignores_unknown_bits_and_short_payloads() {
+        assert!(decode_notification(&payload(0x0000_0001)).is_empty());
+        assert!(decode_notification(&[0u8; 6]).is_empty());
+        assert!(decode_notification(&[]).is_empty());
+    }
+
+    #[test]
+    fn hook_names_round_trip() {
+        for event in DeviceEvent::all() {
+            assert_eq!(DeviceEvent::from_hook_name(event.hook_name()), Some(event));
+        }
+        assert_eq!(
+            DeviceEvent::from_hook_name("on_phantom_changed"),
+            Some(DeviceEvent::PhantomPowerChanged)
+        );
+        assert_eq!(DeviceEvent::from_hook_name("on_mute"), None);
+    }
+
+    #[test]
+    fn button_names() {
+        assert_eq!(
+            DeviceEvent::from_button_name("Direct_Monitoring"),
+            Some(DeviceEvent::ButtonPressed(DeviceButton::DirectMonitoring))
+        );
+        assert_eq!(
+            DeviceEvent::from_button_name("select"),
+            Some(DeviceEvent::SelectedInputChanged)
+        );
+        assert_eq!(DeviceEvent::from_button_name("48v"), None);
+        assert_eq!(DeviceEvent::from_button_name(""), None);
+    }
+
+    #[test]
+    fn poll_events_drains_mock_queue() {
+        let dev = MockDevice::new();
+        dev.push_notification(NOTIFY_MASK_AIR);
+        assert_eq!(
+            poll_events(&dev, 0),
+            vec![DeviceEvent::ButtonPressed(DeviceButton::Air)]
+        );
+        // Queue empty — behaves like a timeout
+        assert!(poll_events(&dev, 0).is_empty());
+    }
+}
@@ -0,0 +1,152 @@
+//! Shared device handle — one device, many threads, locked transactions.
+//!
+//! [`DeviceHandle`] puts the device behind an `Arc<Mutex<_>>`. Handles are
+//! `Clone + Send + Sync`, so the hotkey thread, a monitor thread, and the IPC
+//! server can each hold one. Every [`ScarlettDevice`] call takes the lock, so
+//! transactions never interleave; [`DeviceHandle::with`] holds it across a
+//! whole sequence (e.g. LED colour + index + DATA_NOTIFY).
+//!
+//! Unlike [`DeviceWorker`](crate::device_worker::DeviceWorker), calls run on
+//! the caller's thread — no extra thread or channel hop — but a caller may
+//! block while another holds the lock.
+
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use crate::device::{DeviceInfo, PlatformDevice, Result, ScarlettDevice};
+
+/// Cloneable, thread-safe handle to one device.
+pub struct DeviceHandle<D = PlatformDevice> {
+    device: Arc<Mutex<D>>,
+    /// Snapshot taken at creation — device info never changes while open.
+    info: Arc<DeviceInfo>,
+}
+
+impl<D> Clone for DeviceHandle<D> {
+    fn clone(&self) -> Self {
+        Self {
+            device: Arc::clone(&self.device),
+            info: Arc::clone(&self.info),
+        }
+    }
+}
+
+impl<D: ScarlettDevice> DeviceHandle<D> {
+    pub fn new(device: D) -> Self {
+        let info = Arc::new(device.info().clone());
+        Self {
+            device: Arc::new(Mutex::new(device)),
+            info,
+        }
+    }
+
+    /// Lock the device for a sequence of calls. Other handles block until the
+    /// guard is dropped.
+    ///
+    /// A panic in another holder doesn't poison the handle — the device
+    /// itself has no state that a half-finished call could corrupt.
+    pub fn lock(&self) -> MutexGuard<'_, D> {
+        self.device.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Run `f` with the device locked for its whole duration.
+    pub fn with<R>(&self, f: impl FnOnce(&D) -> R) -> R {
+        f(&self.lock())
+    }
+
+    /// Number of live handles to this device.
+    pub fn handle_count(&self) -> usize {
+        Arc::strong_count(&self.device)
+    }
+}
+
+impl<D: ScarlettDevice> ScarlettDevice for DeviceHandle<D> {
+    fn open() -> Result<Self> {
+        D::open().map(Self::new)
+    }
+
+    fn info(&self) -> &DeviceInfo {
+        &self.info
+    }
+
+    fn get_descriptor(&self, offset: u32, size: u32) -> Result<Vec<u8>> {
+        self.lock().get_descriptor(offset, size)
+    }
+
+    fn set_descriptor(&self, offset: u32, data: &[u8]) -> Result<()> {
+        self.lock().set_descriptor(offset, data)
+    }
+
+    fn data_notify(&self, event_id: u32) -> Result<()> {
+        self.lock().data_notify(event_id)
+    }
+
+    fn transact(&self, cmd: u32, payload: &[u8], out_size: usize) -> Result<Vec<u8>> {
+        self.lock().transact(cmd, payload, out_size)
+    }
+
+    /// Holds the lock for up to `timeout_ms` — keep it short when other
+    /// threads share the handle.
+    fn wait_notify(&self, timeout_ms: u64) -> Result<Vec<u8>> {
+        self.lock().wait_notify(timeout_ms)
+    }
+
+    fn supports_notify(&self) -> bool {
+        self.lock().supports_notify()
+    }
+
+    fn raw_ioctl(&self, code: u32, input: &[u8], out_size: usize) -> Result<Vec<u8>> {
+        self.lock().raw_ioctl(code, input, out_size)
+    }
+
+    fn reset(&self) -> Result<()> {
+        self.lock().reset()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device::mock::MockDevice;
+
+    #[test]
+    fn clones_share_one_device_across_threads() {
+        let handle = DeviceHandle::new(MockDevice::new());
+        let threads: Vec<_> = (0..4u8)
+            .map(|i| {
+                let h = handle.clone();
+                std::thread::spawn(move || h.set_descriptor(100 + u32::from(i), &[i]).unwrap())
+            })
+            .collect();
+        for t in threads {
+            t.join().unwrap();
+        }
+        assert_eq!(handle.get_descriptor(100, 4).unwrap(), vec![0, 1, 2, 3]);
+        assert_eq!(handle.handle_count(), 1);
+    }
+
+    #[test]
+    fn with_holds_lock_for_sequence() {
+        let handle = DeviceHandle::new(MockDevice::new());
+        handle
+            .with(|dev| {
+                dev.set_descriptor(84, &[1, 2, 3, 4])?;
+                dev.data_notify(8)
+            })
+            .unwrap();
+        assert_eq!(*handle.lock().notifies.borrow(), vec![8]);
+        assert_eq!(
+            handle.info().device_name,
+            MockDevice::new().info().device_name
+        );
+    }
+
+    #[test]
+    fn survives_panicking_holder() {
+        let handle = DeviceHandle::new(MockDevice::new());
+        let other = handle.clone();
+        let panicked = std::thread::spawn(move || other.with(|_| panic!("holder failed"))).join();
+        assert!(panicked.is_err());
+        handle.set_descriptor(0, &[9]).unwrap();
+        assert_eq!(handle.get_descriptor(0, 1).unwrap(), vec![9]);
+    }
+}
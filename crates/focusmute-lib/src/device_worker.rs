@@ -0,0 +1,161 @@
+//! Device worker — a dedicated thread that owns the device.
+//!
+//! [`DeviceWorker::spawn`] moves a device onto its own thread; every
+//! [`ScarlettDevice`] call on the returned handle is sent over a channel and
+//! runs there, in order. Handles are `Clone + Send`, so any thread (event loop,
+//! settings dialog, IPC) can talk to the device without sharing the raw handle
+//! or racing another caller's descriptor writes. The device is dropped on the
+//! worker thread once the last handle is gone.
+
+use std::sync::Arc;
+use std::sync::mpsc;
+
+use crate::device::{DeviceError, DeviceInfo, PlatformDevice, Result, ScarlettDevice};
+
+type Job = Box<dyn FnOnce(&dyn ScarlettDevice) + Send>;
+
+/// Handle to a device owned by a worker thread.
+#[derive(Clone)]
+pub struct DeviceWorker {
+    jobs: mpsc::Sender<Job>,
+    /// Snapshot taken at spawn — device info never changes while open.
+    info: Arc<DeviceInfo>,
+    supports_notify: bool,
+}
+
+pub(crate) fn stopped() -> DeviceError {
+    DeviceError::TransactFailed("device worker stopped".into())
+}
+
+impl DeviceWorker {
+    /// Move `device` onto a new worker thread and return a handle to it.
+    pub fn spawn<D: ScarlettDevice + Send + 'static>(device: D) -> Self {
+        let info = Arc::new(device.info().clone());
+        let supports_notify = device.supports_notify();
+        let (jobs, rx) = mpsc::channel::<Job>();
+        std::thread::Builder::new()
+            .name("device-worker".into())
+            .spawn(move || {
+                for job in rx {
+                    job(&device);
+                }
+                log::debug!("[device] worker stopped, closing device");
+            })
+            .expect("failed to spawn device worker thread");
+        Self {
+            jobs,
+            info,
+            supports_notify,
+        }
+    }
+
+    /// Run `job` on the worker thread and wait for its result.
+    ///
+    /// Fails with `TransactFailed` if the worker has stopped (e.g. a previous
+    /// job panicked).
+    pub fn run<R: Send + 'static>(
+        &self,
+        job: impl FnOnce(&dyn ScarlettDevice) -> R + Send + 'static,
+    ) -> Result<R> {
+        let (reply, reply_rx) = mpsc::channel();
+        self.jobs
+            .send(Box::new(move |dev| {
+                let _ = reply.send(job(dev));
+            }))
+            .map_err(|_| stopped())?;
+        reply_rx.recv().map_err(|_| stopped())
+    }
+
+    /// Queue `job` on the worker thread without waiting for it.
+    pub fn submit(&self, job: impl FnOnce(&dyn ScarlettDevice) + Send + 'static) -> Result<()> {
+        self.jobs.send(Box::new(job)).map_err(|_| stopped())
+    }
+}
+
+impl ScarlettDevice for DeviceWorker {
+    fn open() -> Result<Self> {
+        PlatformDevice::open().map(Self::spawn)
+    }
+
+    fn info(&self) -> &DeviceInfo {
+        &self.info
+    }
+
+    fn get_descriptor(&self, offset: u32, size: u32) -> Result<Vec<u8>> {
+        self.run(move |dev| dev.get_descriptor(offset, size))?
+    }
+
+    fn set_descriptor(&self, offset: u32, data: &[u8]) -> Result<()> {
+        let data = data.to_vec();
+        self.run(move |dev| dev.set_descriptor(offset, &data))?
+    }
+
+    fn data_notify(&self, event_id: u32) -> Result<()> {
+        self.run(move |dev| dev.data_notify(event_id))?
+    }
+
+    fn transact(&self, cmd: u32, payload: &[u8], out_size: usize) -> Result<Vec<u8>> {
+        let payload = payload.to_vec();
+        self.run(move |dev| dev.transact(cmd, &payload, out_size))?
+    }
+
+    fn wait_notify(&self, timeout_ms: u64) -> Result<Vec<u8>> {
+        self.run(move |dev| dev.wait_notify(timeout_ms))?
+    }
+
+    fn supports_notify(&self) -> bool {
+        self.supports_notify
+    }
+
+    fn raw_ioctl(&self, code: u32, input: &[u8], out_size: usize) -> Result<Vec<u8>> {
+        let input = input.to_vec();
+        self.run(move |dev| dev.raw_ioctl(code, &input, out_size))?
+    }
+
+    fn reset(&self) -> Result<()> {
+        self.run(|dev| dev.reset())?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device::mock::MockDevice;
+
+    #[test]
+    fn forwards_calls_to_worker_thread() {
+        let worker = DeviceWorker::spawn(MockDevice::new());
+        worker.set_descriptor(92, &[1, 2, 3]).unwrap();
+        assert_eq!(worker.get_descriptor(92, 3).unwrap(), vec![1, 2, 3]);
+        worker.data_notify(8).unwrap();
+
+        let thread = worker
+            .run(|_| std::thread::current().name().map(String::from))
+            .unwrap();
+        assert_eq!(thread.as_deref(), Some("device-worker"));
+    }
+
+    #[test]
+    fn clones_share_one_device() {
+        let worker = DeviceWorker::spawn(MockDevice::new());
+        let other = worker.clone();
+        std::thread::spawn(move || other.set_descriptor(10, &[7]).unwrap())
+            .join()
+            .unwrap();
+        assert_eq!(worker.get_descriptor(10, 1).unwrap(), vec![7]);
+        assert_eq!(
+            worker.info().device_name,
+            MockDevice::new().info().device_name
+        );
+    }
+
+    #[test]
+    fn panicking_job_stops_worker() {
+        let worker = DeviceWorker::spawn(MockDevice::new());
+        assert!(worker.run(|_| -> () { panic!("job failed") }).is_err());
+        assert!(matches!(
+            worker.get_descriptor(0, 1),
+            Err(DeviceError::TransactFailed(_))
+        ));
+    }
+}
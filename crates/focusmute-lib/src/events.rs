@@ -0,0 +1,325 @@
+//! Event model — the one JSON shape every FocusMute event stream uses.
+//!
+//! An [`EventRecord`] is a versioned, timestamped [`Event`], serialized as a
+//! single JSON object with a `type` tag (`mute`, `device`, `hotkey`, `hook`,
+//! or `error`). `focusmute-cli monitor --json` writes one per line; other
+//! surfaces emit the same objects so integrators need a single parser.
+//! [`RemoteCommand`] is the other direction: what those surfaces may ask of
+//! the tray.
+//!
+//! [`json_schema`] describes the format as JSON Schema. A copy is checked in
+//! as `docs/events.schema.json` and a test keeps it in sync with the types
+//! here. Adding an optional field is backwards compatible; anything else
+//! bumps [`EVENT_FORMAT_VERSION`].
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::hooks::HookOutcome;
+use crate::monitor::{MonitorAction, MuteOrigin};
+
+/// Version of the event format, sent as `version` in every record.
+pub const EVENT_FORMAT_VERSION: u32 = 1;
+
+/// Connection state reported by [`Event::Device`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeviceState {
+    /// Opened (at startup or after a reconnect); `detail` is the model.
+    Connected,
+    /// Lost; `detail` is the error, if any.
+    Disconnected,
+    /// Another application holds it; `detail` is the holder, if known.
+    InUse,
+    /// A front-panel or hardware setting changed; `detail` is the hook name
+    /// (e.g. `on_phantom_changed`).
+    Changed,
+}
+
+impl DeviceState {
+    const ALL: [DeviceState; 4] = [
+        DeviceState::Connected,
+        DeviceState::Disconnected,
+        DeviceState::InUse,
+        DeviceState::Changed,
+    ];
+}
+
+/// Something FocusMute noticed or did.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event {
+    /// The microphone was muted or unmuted. `origin` is `focusmute` or
+    /// `external` (see [`MuteOrigin::as_str`]).
+    Mute { muted: bool, origin: String },
+    /// The interface connected, disconnected, or changed.
+    Device {
+        state: DeviceState,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        detail: Option<String>,
+    },
+    /// A hotkey was pressed; `action` is what it did (e.g. `toggle_mute`).
+    Hotkey { action: String },
+    /// A hook command finished.
+    Hook {
+        command: String,
+        success: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    /// Something went wrong that the user should know about.
+    Error { message: String },
+}
+
+impl Event {
+    /// The mute event for a state change; `None` for `NoChange`.
+    pub fn from_action(action: MonitorAction, origin: MuteOrigin) -> Option<Self> {
+        let muted = match action {
+            MonitorAction::ApplyMute => true,
+            MonitorAction::ClearMute => false,
+            MonitorAction::NoChange => return None,
+        };
+        Some(Event::Mute {
+            muted,
+            origin: origin.as_str().to_string(),
+        })
+    }
+
+    pub fn device(state: DeviceState, detail: Option<String>) -> Self {
+        Event::Device { state, detail }
+    }
+
+    pub fn hook(outcome: &HookOutcome) -> Self {
+        Event::Hook {
+            command: outcome.command.clone(),
+            success: outcome.result.is_ok(),
+            error: outcome.result.as_ref().err().cloned(),
+        }
+    }
+}
+
+/// What a remote control surface (MQTT, ...) asks the tray to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteCommand {
+    Toggle,
+    Mute,
+    Unmute,
+}
+
+impl RemoteCommand {
+    /// Parse a command payload, ignoring case and surrounding whitespace:
+    /// `toggle`, `mute`, `unmute`, or a switch state (`on`/`1`/`true` mutes,
+    /// `off`/`0`/`false` unmutes).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "toggle" => Some(RemoteCommand::Toggle),
+            "mute" | "on" | "1" | "true" => Some(RemoteCommand::Mute),
+            "unmute" | "off" | "0" | "false" => Some(RemoteCommand::Unmute),
+            _ => None,
+        }
+    }
+
+    /// The mute state this command asks for, given the current one.
+    pub fn target(self, muted: bool) -> bool {
+        match self {
+            RemoteCommand::Toggle => !muted,
+            RemoteCommand::Mute => true,
+            RemoteCommand::Unmute => false,
+        }
+    }
+}
+
+/// An [`Event`] as sent: format version, Unix time in milliseconds, and the
+/// event's own fields.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventRecord {
+    pub version: u32,
+    pub timestamp_ms: u64,
+    #[serde(flatten)]
+    pub event: Event,
+}
+
+impl EventRecord {
+    /// Stamp `event` with the current time.
+    pub fn now(event: Event) -> Self {
+        let timestamp_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as u64);
+        Self {
+            version: EVENT_FORMAT_VERSION,
+            timestamp_ms,
+            event,
+        }
+    }
+
+    /// One line of JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+/// Schema of one event type: its `type` tag plus required and optional
+/// fields.
+fn variant_schema(tag: &str, required: &[(&str, Value)], optional: &[(&str, Value)]) -> Value {
+    let mut properties = serde_json::Map::new();
+    properties.insert("type".into(), json!({ "const": tag }));
+    for (name, schema) in required.iter().chain(optional) {
+        properties.insert((*name).into(), schema.clone());
+    }
+    let mut names = vec!["type"];
+    names.extend(required.iter().map(|(name, _)| *name));
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": names,
+    })
+}
+
+/// JSON Schema (draft 2020-12) for an [`EventRecord`].
+pub fn json_schema() -> Value {
+    let string = || json!({ "type": "string" });
+    let boolean = || json!({ "type": "boolean" });
+    let states: Vec<Value> = DeviceState::ALL
+        .iter()
+        .map(|s| serde_json::to_value(s).unwrap_or_default())
+        .collect();
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "FocusMute event",
+        "type": "object",
+        "properties": {
+            "version": { "const": EVENT_FORMAT_VERSION },
+            "timestamp_ms": { "type": "integer", "minimum": 0 },
+        },
+        "required": ["version", "timestamp_ms", "type"],
+        "oneOf": [
+            variant_schema(
+                "mute",
+                &[
+                    ("muted", boolean()),
+                    ("origin", json!({ "enum": ["focusmute", "external"] })),
+                ],
+                &[],
+            ),
+            variant_schema(
+                "device",
+                &[("state", json!({ "enum": states }))],
+                &[("detail", string())],
+            ),
+            variant_schema("hotkey", &[("action", string())], &[]),
+            variant_schema(
+                "hook",
+                &[("command", string()), ("success", boolean())],
+                &[("error", string())],
+            ),
+            variant_schema("error", &[("message", string())], &[]),
+        ],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples() -> Vec<Event> {
+        let mut events = vec![
+            Event::from_action(MonitorAction::ApplyMute, MuteOrigin::External).unwrap(),
+            Event::Hotkey {
+                action: "toggle_mute".into(),
+            },
+            Event::hook(&HookOutcome {
+                command: "obs-mute".into(),
+                result: Err("exit code 1".into()),
+            }),
+            Event::hook(&HookOutcome {
+                command: "obs-mute".into(),
+                result: Ok(()),
+            }),
+            Event::Error {
+                message: "device lost".into(),
+            },
+        ];
+        events.extend(
+            DeviceState::ALL
+                .iter()
+                .map(|&state| Event::device(state, Some("x".into()))),
+        );
+        events.push(Event::device(DeviceState::Disconnected, None));
+        events
+    }
+
+    #[test]
+    fn records_round_trip_as_flat_objects() {
+        for event in samples() {
+            let record = EventRecord::now(event);
+            let line = record.to_json();
+            assert!(!line.contains('\n'));
+            let value: Value = serde_json::from_str(&line).unwrap();
+            assert_eq!(value["version"], EVENT_FORMAT_VERSION);
+            assert!(value["type"].is_string());
+            assert_eq!(serde_json::from_str::<EventRecord>(&line).unwrap(), record);
+        }
+        assert_eq!(
+            Event::from_action(MonitorAction::NoChange, MuteOrigin::FocusMute),
+            None
+        );
+    }
+
+    /// Every serialized event matches exactly one `oneOf` branch: its tag,
+    /// all required fields present, no fields the schema doesn't list.
+    #[test]
+    fn schema_matches_the_types() {
+        let schema = json_schema();
+        let variants = schema["oneOf"].as_array().unwrap();
+        let top = schema["properties"].as_object().unwrap();
+        for event in samples() {
+            let value = serde_json::to_value(EventRecord::now(event)).unwrap();
+            let object = value.as_object().unwrap();
+            let branch = variants
+                .iter()
+                .find(|v| v["properties"]["type"]["const"] == value["type"])
+                .unwrap_or_else(|| panic!("no schema for {value}"));
+            for name in branch["required"].as_array().unwrap() {
+                assert!(object.contains_key(name.as_str().unwrap()), "{value}");
+            }
+            for (key, field) in object {
+                if top.contains_key(key) {
+                    continue;
+                }
+                let expected = &branch["properties"][key];
+                assert!(!expected.is_null(), "{key} missing from schema");
+                if let Some(choices) = expected.get("enum") {
+                    assert!(choices.as_array().unwrap().contains(field), "{value}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn remote_commands_parse() {
+        assert_eq!(
+            RemoteCommand::parse(" Toggle\n"),
+            Some(RemoteCommand::Toggle)
+        );
+        assert_eq!(RemoteCommand::parse("ON"), Some(RemoteCommand::Mute));
+        assert_eq!(RemoteCommand::parse("0"), Some(RemoteCommand::Unmute));
+        assert_eq!(RemoteCommand::parse("louder"), None);
+        assert!(RemoteCommand::Toggle.target(false));
+        assert!(!RemoteCommand::Toggle.target(true));
+        assert!(RemoteCommand::Mute.target(true));
+        assert!(!RemoteCommand::Unmute.target(true));
+    }
+
+    #[test]
+    fn checked_in_schema_is_current() {
+        let checked_in: Value =
+            serde_json::from_str(include_str!("../../../docs/events.schema.json")).unwrap();
+        assert_eq!(
+            checked_in,
+            json_schema(),
+            "docs/events.schema.json is stale — regenerate it with \
+             `focusmute-cli event-schema > docs/events.schema.json`"
+        );
+    }
+}
@@ -0,0 +1,497 @@
+//! Mute history — append-only log of mute state transitions, export, and daily summaries.
+//!
+//! Each transition is stored as one CSV line (`<unix seconds>,muted|live`) in
+//! `history.csv` next to the config file (`history-<name>.csv` for a named
+//! [instance](crate::instance), so several trays never share one log).
+//! Timestamps and exports are UTC; the daily summary follows local days.
+
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chrono::{Days, Local, NaiveDate, Offset, TimeZone};
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::monitor::MonitorAction;
+
+/// Seconds in one day.
+pub const SECS_PER_DAY: u64 = 86_400;
+
+/// A single recorded mute state transition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct HistoryEvent {
+    /// Unix timestamp (seconds).
+    pub timestamp: u64,
+    /// `true` = muted, `false` = live.
+    pub muted: bool,
+}
+
+impl HistoryEvent {
+    /// Parse one `timestamp,muted|live` line. Returns `None` for malformed lines.
+    fn parse_line(line: &str) -> Option<Self> {
+        let (ts, state) = line.trim().split_once(',')?;
+        let timestamp = ts.trim().parse().ok()?;
+        let muted = match state.trim() {
+            "muted" => true,
+            "live" => false,
+            _ => return None,
+        };
+        Some(HistoryEvent { timestamp, muted })
+    }
+
+    fn state_str(&self) -> &'static str {
+        if self.muted { "muted" } else { "live" }
+    }
+}
+
+/// Current Unix time in seconds.
+pub fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Full path to the current instance's history file.
+pub fn history_path() -> Option<PathBuf> {
+    Config::dir().map(|d| d.join(crate::instance::scoped("history.csv")))
+}
+
+// ── Recording ──
+
+/// Append an event to the history file at `path`, creating it if needed.
+pub fn append_event_to(path: &Path, event: &HistoryEvent) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{},{}", event.timestamp, event.state_str())
+}
+
+/// Record a mute state change if `history_enabled` is set. `NoChange` is ignored.
+pub fn record_action(action: MonitorAction, config: &Config) {
+    if !config.history_enabled {
+        return;
+    }
+    match action {
+        MonitorAction::ApplyMute => record(true),
+        MonitorAction::ClearMute => record(false),
+        MonitorAction::NoChange => {}
+    }
+}
+
+/// Record a mute state transition at the current time (best effort — failures are logged).
+pub fn record(muted: bool) {
+    let Some(path) = history_path() else {
+        return;
+    };
+    let event = HistoryEvent {
+        timestamp: now_unix(),
+        muted,
+    };
+    if let Err(e) = append_event_to(&path, &event) {
+        log::warn!("[history] failed to write {}: {e}", path.display());
+    }
+}
+
+// ── Loading ──
+
+/// Load all events from `path`, skipping malformed lines.
+///
+/// A missing file is treated as an empty history.
+pub fn load_from(path: &Path) -> std::io::Result<Vec<HistoryEvent>> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut events: Vec<HistoryEvent> = contents
+        .lines()
+        .filter_map(HistoryEvent::parse_line)
+        .collect();
+    events.sort_by_key(|e| e.timestamp);
+    Ok(events)
+}
+
+/// Parse a relative duration such as `"7d"`, `"12h"`, `"30m"`, `"2w"` into seconds.
+pub fn parse_since(s: &str) -> Result<u64, String> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(|| format!("missing unit in \"{s}\" (expected m, h, d or w)"))?;
+    let (num, unit) = s.split_at(split);
+    let n: u64 = num
+        .parse()
+        .map_err(|_| format!("invalid duration \"{s}\""))?;
+    let mult = match unit {
+        "m" => 60,
+        "h" => 3600,
+        "d" => SECS_PER_DAY,
+        "w" => 7 * SECS_PER_DAY,
+        _ => return Err(format!("unknown unit \"{unit}\" (expected m, h, d or w)")),
+    };
+    n.checked_mul(mult)
+        .ok_or_else(|| format!("duration \"{s}\" is too large"))
+}
+
+// ── Export ──
+
+/// Output format for [`export`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "json" => Ok(ExportFormat::Json),
+            _ => Err(format!("unknown format \"{s}\" (expected csv or json)")),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ExportRow<'a> {
+    timestamp: u64,
+    time: String,
+    state: &'a str,
+}
+
+/// Render events in the given format (CSV with a header row, or a JSON array).
+pub fn export(events: &[HistoryEvent], format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Csv => {
+            let mut out = String::from("timestamp,time,state\n");
+            for e in events {
+                out.push_str(&format!(
+                    "{},{},{}\n",
+                    e.timestamp,
+                    format_utc(e.timestamp),
+                    e.state_str()
+                ));
+            }
+            out
+        }
+        ExportFormat::Json => {
+            let rows: Vec<ExportRow> = events
+                .iter()
+                .map(|e| ExportRow {
+                    timestamp: e.timestamp,
+                    time: format_utc(e.timestamp),
+                    state: e.state_str(),
+                })
+                .collect();
+            serde_json::to_string_pretty(&rows).unwrap_or_else(|_| "[]".into())
+        }
+    }
+}
+
+/// Format a Unix timestamp as an ISO 8601 UTC string (`2024-01-31T12:00:00Z`).
+pub fn format_utc(ts: u64) -> String {
+    let days = ts / SECS_PER_DAY;
+    let secs = ts % SECS_PER_DAY;
+    let (y, m, d) = civil_from_days(days);
+    format!(
+        "{y:04}-{m:02}-{d:02}T{:02}:{:02}:{:02}Z",
+        secs / 3600,
+        (secs % 3600) / 60,
+        secs % 60
+    )
+}
+
+/// Convert days since 1970-01-01 to a (year, month, day) civil date.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    // Howard Hinnant's algorithm, restricted to dates on or after the epoch.
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + u64::from(m <= 2);
+    (y, m, d)
+}
+
+// ── Summary ──
+
+/// Aggregated mute statistics over a time window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub muted_secs: u64,
+    pub live_secs: u64,
+    pub toggles: u32,
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Muted {}, live {}, {} toggle{}",
+            format_duration(self.muted_secs),
+            format_duration(self.live_secs),
+            self.toggles,
+            if self.toggles == 1 { "" } else { "s" }
+        )
+    }
+}
+
+/// Summarize time spent muted/live in `[start, end)`.
+///
+/// The state at `start` is taken from the last event before it. Time before
+/// the first known event is not counted. `events` must be sorted by timestamp.
+pub fn summarize(events: &[HistoryEvent], start: u64, end: u64) -> Summary {
+    let mut summary = Summary::default();
+    let mut state = events
+        .iter()
+        .take_while(|e| e.timestamp < start)
+        .last()
+        .map(|e| e.muted);
+    let mut cursor = start;
+
+    for e in events
+        .iter()
+        .filter(|e| e.timestamp >= start && e.timestamp < end)
+    {
+        add_span(&mut summary, state, e.timestamp - cursor);
+        if state.is_some_and(|s| s != e.muted) {
+            summary.toggles += 1;
+        }
+        state = Some(e.muted);
+        cursor = e.timestamp;
+    }
+    add_span(&mut summary, state, end.saturating_sub(cursor));
+    summary
+}
+
+/// Summarize a single UTC day (`day` = days since the Unix epoch).
+pub fn summarize_day(events: &[HistoryEvent], day: u64) -> Summary {
+    summarize(events, day * SECS_PER_DAY, (day + 1) * SECS_PER_DAY)
+}
+
+/// Summarize a single local calendar day (see [`local_day`]).
+pub fn summarize_local_day(events: &[HistoryEvent], day: u64) -> Summary {
+    summarize_day_in(&Local, events, day)
+}
+
+/// The local calendar day containing `ts`, as days since 1970-01-01.
+pub fn local_day(ts: u64) -> u64 {
+    day_in(&Local, ts)
+}
+
+/// Unix time at which local calendar day `day` begins.
+pub fn local_day_start(day: u64) -> u64 {
+    day_start_in(&Local, day)
+}
+
+fn summarize_day_in<Tz: TimeZone>(tz: &Tz, events: &[HistoryEvent], day: u64) -> Summary {
+    summarize(events, day_start_in(tz, day), day_start_in(tz, day + 1))
+}
+
+fn day_in<Tz: TimeZone>(tz: &Tz, ts: u64) -> u64 {
+    let offset = i64::try_from(ts)
+        .ok()
+        .and_then(|t| tz.timestamp_opt(t, 0).single())
+        .map_or(0, |t| i64::from(t.offset().fix().local_minus_utc()));
+    (ts as i64).saturating_add(offset).max(0) as u64 / SECS_PER_DAY
+}
+
+/// Start of `day` in `tz`. Falls back to UTC midnight for a day whose local
+/// midnight doesn't exist (a DST jump at midnight).
+fn day_start_in<Tz: TimeZone>(tz: &Tz, day: u64) -> u64 {
+    NaiveDate::from_ymd_opt(1970, 1, 1)
+        .and_then(|epoch| epoch.checked_add_days(Days::new(day)))
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .and_then(|midnight| tz.from_local_datetime(&midnight).earliest())
+        .map_or(day * SECS_PER_DAY, |t| t.timestamp().max(0) as u64)
+}
+
+fn add_span(summary: &mut Summary, state: Option<bool>, secs: u64) {
+    match state {
+        Some(true) => summary.muted_secs += secs,
+        Some(false) => summary.live_secs += secs,
+        None => {}
+    }
+}
+
+/// Format seconds as a compact duration (`"2h 05m"`, `"12m"`, `"40s"`).
+pub fn format_duration(secs: u64) -> String {
+    let h = secs / 3600;
+    let m = (secs % 3600) / 60;
+    if h > 0 {
+        format!("{h}h {m:02}m")
+    } else if m > 0 {
+        format!("{m}m")
+    } else {
+        format!("{secs}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ev(timestamp: u64, muted: bool) -> HistoryEvent {
+        HistoryEvent { timestamp, muted }
+    }
+
+    #[test]
+    fn append_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.csv");
+        append_event_to(&path, &ev(100, true)).unwrap();
+        append_event_to(&path, &ev(200, false)).unwrap();
+        let events = load_from(&path).unwrap();
+        assert_eq!(events, vec![ev(100, true), ev(200, false)]);
+    }
+
+    #[test]
+    fn load_missing_file_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let events = load_from(&dir.path().join("nope.csv")).unwrap();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn load_skips_malformed_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.csv");
+        std::fs::write(&path, "100,muted\ngarbage\n150,maybe\n,live\n200,live\n").unwrap();
+        let events = load_from(&path).unwrap();
+        assert_eq!(events, vec![ev(100, true), ev(200, false)]);
+    }
+
+    #[test]
+    fn parse_since_units() {
+        assert_eq!(parse_since("30m"), Ok(1800));
+        assert_eq!(parse_since("12h"), Ok(43_200));
+        assert_eq!(parse_since("7d"), Ok(604_800));
+        assert_eq!(parse_since("2w"), Ok(1_209_600));
+    }
+
+    #[test]
+    fn parse_since_rejects_invalid() {
+        assert!(parse_since("7").is_err());
+        assert!(parse_since("d").is_err());
+        assert!(parse_since("7y").is_err());
+        assert!(parse_since("").is_err());
+    }
+
+    #[test]
+    fn export_format_from_str() {
+        assert_eq!("csv".parse(), Ok(ExportFormat::Csv));
+        assert_eq!("JSON".parse(), Ok(ExportFormat::Json));
+        assert!("xml".parse::<ExportFormat>().is_err());
+    }
+
+    #[test]
+    fn export_csv_has_header_and_rows() {
+        let out = export(&[ev(0, true), ev(86_461, false)], ExportFormat::Csv);
+        assert_eq!(
+            out,
+            "timestamp,time,state\n\
+             0,1970-01-01T00:00:00Z,muted\n\
+             86461,1970-01-02T00:01:01Z,live\n"
+        );
+    }
+
+    #[test]
+    fn export_json_is_array() {
+        let out = export(&[ev(0, true)], ExportFormat::Json);
+        let v: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(v[0]["timestamp"], 0);
+        assert_eq!(v[0]["time"], "1970-01-01T00:00:00Z");
+        assert_eq!(v[0]["state"], "muted");
+    }
+
+    #[test]
+    fn format_utc_known_dates() {
+        assert_eq!(format_utc(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(format_utc(1_700_000_000), "2023-11-14T22:13:20Z");
+    }
+
+    #[test]
+    fn summarize_carries_state_from_before_window() {
+        let events = [ev(50, true), ev(150, false), ev(180, true)];
+        let s = summarize(&events, 100, 200);
+        assert_eq!(s.muted_secs, 50 + 20);
+        assert_eq!(s.live_secs, 30);
+        assert_eq!(s.toggles, 2);
+    }
+
+    #[test]
+    fn summarize_ignores_time_before_first_event() {
+        let events = [ev(150, true)];
+        let s = summarize(&events, 100, 200);
+        assert_eq!(s.muted_secs, 50);
+        assert_eq!(s.live_secs, 0);
+        assert_eq!(s.toggles, 0);
+    }
+
+    #[test]
+    fn summarize_does_not_count_repeated_state_as_toggle() {
+        let events = [ev(100, true), ev(120, true), ev(150, false)];
+        let s = summarize(&events, 100, 200);
+        assert_eq!(s.toggles, 1);
+        assert_eq!(s.muted_secs, 50);
+        assert_eq!(s.live_secs, 50);
+    }
+
+    #[test]
+    fn summarize_day_uses_utc_boundaries() {
+        let events = [ev(SECS_PER_DAY - 100, true), ev(SECS_PER_DAY + 3600, false)];
+        let s = summarize_day(&events, 1);
+        assert_eq!(s.muted_secs, 3600);
+        assert_eq!(s.live_secs, SECS_PER_DAY - 3600);
+        assert_eq!(s.toggles, 1);
+    }
+
+    #[test]
+    fn local_days_follow_the_utc_offset() {
+        let east = chrono::FixedOffset::east_opt(2 * 3600).unwrap();
+        let west = chrono::FixedOffset::west_opt(5 * 3600).unwrap();
+        // 23:00 UTC on day 0 is already day 1 at UTC+2, still day 0 at UTC-5
+        assert_eq!(day_in(&east, SECS_PER_DAY - 3600), 1);
+        assert_eq!(day_in(&west, SECS_PER_DAY - 3600), 0);
+        assert_eq!(day_in(&west, SECS_PER_DAY + 3600), 0);
+        assert_eq!(day_start_in(&east, 1), SECS_PER_DAY - 2 * 3600);
+        assert_eq!(day_start_in(&west, 1), SECS_PER_DAY + 5 * 3600);
+        assert_eq!(day_start_in(&chrono::Utc, 3), 3 * SECS_PER_DAY);
+    }
+
+    #[test]
+    fn summarize_local_day_uses_local_midnight() {
+        let east = chrono::FixedOffset::east_opt(2 * 3600).unwrap();
+        // Muted from 22:30 to 23:30 UTC on day 0 — local day 1 at UTC+2
+        let events = [
+            ev(SECS_PER_DAY - 5400, true),
+            ev(SECS_PER_DAY - 1800, false),
+        ];
+        let s = summarize_day_in(&east, &events, 1);
+        assert_eq!(s.muted_secs, 3600);
+        assert_eq!(s.toggles, 1);
+        assert_eq!(summarize_day_in(&east, &events, 0).muted_secs, 0);
+    }
+
+    #[test]
+    fn summary_display() {
+        let s = Summary {
+            muted_secs: 7500,
+            live_secs: 40,
+            toggles: 1,
+        };
+        assert_eq!(s.to_string(), "Muted 2h 05m, live 40s, 1 toggle");
+    }
+}
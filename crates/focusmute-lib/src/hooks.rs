@@ -1,4 +1,4 @@
-//! Mute state change hooks — run user-defined commands on mute/unmute events.
+//! Hooks — run user-defined commands on mute/unmute and hardware events.
 
 use std::io;
 use std::process::ExitStatus;
@@ -6,7 +6,8 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
 use crate::config::Config;
-use crate::monitor::MonitorAction;
+use crate::device_events::DeviceEvent;
+use crate::monitor::{MonitorAction, MuteOrigin};
 
 /// Guard preventing concurrent hook execution (shared across mute/unmute hooks).
 static HOOK_RUNNING: AtomicBool = AtomicBool::new(false);
@@ -27,21 +28,42 @@ const HOOK_TIMEOUT: Duration = Duration::from_secs(30);
 /// Poll interval when waiting for a hook process to exit.
 const POLL_INTERVAL: Duration = Duration::from_millis(100);
 
+/// A finished hook command and how it went.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookOutcome {
+    pub command: String,
+    /// `Err` carries a short description (non-zero exit, spawn failure).
+    pub result: std::result::Result<(), String>,
+}
+
 /// Run the appropriate hook command for a mute state change.
 ///
 /// Spawns the command in a background thread so it doesn't block the event loop.
 /// Empty commands are silently ignored. Only one hook can run at a time — if a
 /// previous hook is still running, the new one is skipped with a warning.
-pub fn run_action_hook(action: MonitorAction, config: &Config) {
+///
+/// The command sees `FOCUSMUTE_ORIGIN=focusmute|external` in its environment.
+pub fn run_action_hook(action: MonitorAction, origin: MuteOrigin, config: &Config) {
+    run_action_hook_with(action, origin, config, |_| {});
+}
+
+/// Like [`run_action_hook`], but calls `on_done` from the hook thread once the
+/// command has finished. Not called for skipped or empty hooks.
+pub fn run_action_hook_with(
+    action: MonitorAction,
+    origin: MuteOrigin,
+    config: &Config,
+    on_done: impl FnOnce(HookOutcome) + Send + 'static,
+) {
     match action {
-        MonitorAction::ApplyMute => run_hook(&config.on_mute_command),
-        MonitorAction::ClearMute => run_hook(&config.on_unmute_command),
+        MonitorAction::ApplyMute => run_hook(&config.on_mute_command, origin, on_done),
+        MonitorAction::ClearMute => run_hook(&config.on_unmute_command, origin, on_done),
         MonitorAction::NoChange => {}
     }
 }
 
 /// Spawn a shell command in a background thread. Empty commands are ignored.
-fn run_hook(command: &str) {
+fn run_hook(command: &str, origin: MuteOrigin, on_done: impl FnOnce(HookOutcome) + Send + 'static) {
     let command = command.trim();
     if command.is_empty() {
         return;
@@ -56,28 +78,97 @@ fn run_hook(command: &str) {
     let command = command.to_string();
     std::thread::spawn(move || {
         let _guard = HookGuard;
-        let result = run_hook_with_timeout(&command, HOOK_TIMEOUT);
-        match result {
-            Ok(s) if !s.success() => {
-                log::warn!("hook command exited with {s}: {command}");
-            }
-            Err(e) => {
-                log::warn!("hook command failed: {e}: {command}");
-            }
-            _ => {}
+        let status = run_hook_with_timeout(&command, origin, HOOK_TIMEOUT);
+        on_done(outcome(command, status));
+    });
+}
+
+/// Run the `[device_hooks]` commands for a batch of hardware events.
+///
+/// The commands run one after another in a background thread, each with
+/// `FOCUSMUTE_EVENT=<event name>` in its environment. They share the
+/// one-hook-at-a-time guard with the mute hooks, so a burst of events (e.g.
+/// turning a gain knob) runs the hook once rather than once per step.
+pub fn run_device_hooks(events: &[DeviceEvent], config: &Config) {
+    run_device_hooks_with(events, config, |_| {});
+}
+
+/// Like [`run_device_hooks`], but calls `on_done` from the hook thread after
+/// each command has finished.
+pub fn run_device_hooks_with(
+    events: &[DeviceEvent],
+    config: &Config,
+    mut on_done: impl FnMut(HookOutcome) + Send + 'static,
+) {
+    let commands: Vec<(&'static str, String)> = events
+        .iter()
+        .filter_map(|event| {
+            let name = event.hook_name();
+            let command = config.device_hooks.get(name)?.trim();
+            (!command.is_empty()).then(|| (name, command.to_string()))
+        })
+        .collect();
+    if commands.is_empty() {
+        return;
+    }
+    if HOOK_RUNNING
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
+        log::debug!("device hooks skipped (previous hook still running)");
+        return;
+    }
+    std::thread::spawn(move || {
+        let _guard = HookGuard;
+        for (name, command) in commands {
+            let status =
+                run_command_with_timeout(&command, ("FOCUSMUTE_EVENT", name), HOOK_TIMEOUT);
+            on_done(outcome(command, status));
         }
     });
 }
 
-/// Run a shell command with a timeout. Kills the process if it exceeds the deadline.
-fn run_hook_with_timeout(command: &str, timeout: Duration) -> io::Result<ExitStatus> {
+/// Log a finished hook command and describe how it went.
+fn outcome(command: String, status: io::Result<ExitStatus>) -> HookOutcome {
+    let result = match status {
+        Ok(s) if !s.success() => {
+            log::warn!("hook command exited with {s}: {command}");
+            Err(format!("exited with {s}"))
+        }
+        Err(e) => {
+            log::warn!("hook command failed: {e}: {command}");
+            Err(e.to_string())
+        }
+        Ok(_) => Ok(()),
+    };
+    HookOutcome { command, result }
+}
+
+/// Run a mute hook command with `FOCUSMUTE_ORIGIN` set.
+fn run_hook_with_timeout(
+    command: &str,
+    origin: MuteOrigin,
+    timeout: Duration,
+) -> io::Result<ExitStatus> {
+    run_command_with_timeout(command, ("FOCUSMUTE_ORIGIN", origin.as_str()), timeout)
+}
+
+/// Run a shell command with a timeout and one extra environment variable.
+/// Kills the process if it exceeds the deadline.
+fn run_command_with_timeout(
+    command: &str,
+    (key, value): (&str, &str),
+    timeout: Duration,
+) -> io::Result<ExitStatus> {
     let mut child = if cfg!(windows) {
         std::process::Command::new("cmd")
             .args(["/C", command])
+            .env(key, value)
             .spawn()?
     } else {
         std::process::Command::new("sh")
             .args(["-c", command])
+            .env(key, value)
             .spawn()?
     };
 
@@ -106,34 +197,52 @@ mod tests {
     #[test]
     fn run_hook_empty_command_is_noop() {
         // Should not spawn any process or panic
-        run_hook("");
-        run_hook("   ");
+        run_hook("", MuteOrigin::External, |_| panic!("empty hook reported"));
+        run_hook("   ", MuteOrigin::External, |_| {
+            panic!("empty hook reported")
+        });
     }
 
     #[test]
     fn run_action_hook_no_change_is_noop() {
         let config = Config::default();
         // NoChange should not run anything
-        run_action_hook(MonitorAction::NoChange, &config);
+        run_action_hook(MonitorAction::NoChange, MuteOrigin::External, &config);
     }
 
     #[test]
     fn run_action_hook_with_empty_commands_is_noop() {
         let config = Config::default();
         // Default config has empty commands — should be fine
-        run_action_hook(MonitorAction::ApplyMute, &config);
-        run_action_hook(MonitorAction::ClearMute, &config);
+        run_action_hook(MonitorAction::ApplyMute, MuteOrigin::External, &config);
+        run_action_hook(MonitorAction::ClearMute, MuteOrigin::External, &config);
     }
 
     #[test]
     fn run_hook_with_timeout_completes() {
         // A fast command should succeed within the timeout
         let cmd = if cfg!(windows) { "echo ok" } else { "true" };
-        let result = run_hook_with_timeout(cmd, Duration::from_secs(5));
+        let result = run_hook_with_timeout(cmd, MuteOrigin::External, Duration::from_secs(5));
         assert!(result.is_ok());
         assert!(result.unwrap().success());
     }
 
+    #[test]
+    fn run_hook_exposes_origin_env() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("origin.txt");
+        let cmd = if cfg!(windows) {
+            format!("echo %FOCUSMUTE_ORIGIN%> {}", marker.display())
+        } else {
+            format!("echo $FOCUSMUTE_ORIGIN > {}", marker.display())
+        };
+        let status =
+            run_hook_with_timeout(&cmd, MuteOrigin::FocusMute, Duration::from_secs(5)).unwrap();
+        assert!(status.success());
+        let content = std::fs::read_to_string(&marker).unwrap();
+        assert_eq!(content.trim(), "focusmute");
+    }
+
     #[test]
     fn run_hook_with_timeout_kills_on_timeout() {
         // A long-running command should be killed after a short timeout
@@ -142,7 +251,7 @@ mod tests {
         } else {
             "sleep 60"
         };
-        let result = run_hook_with_timeout(cmd, Duration::from_secs(1));
+        let result = run_hook_with_timeout(cmd, MuteOrigin::External, Duration::from_secs(1));
         // The process was killed — the exit status should indicate abnormal termination
         assert!(result.is_ok(), "should still return Ok after kill+wait");
         let status = result.unwrap();
@@ -158,7 +267,7 @@ mod tests {
         // Set the guard to simulate a running hook
         HOOK_RUNNING.store(true, Ordering::SeqCst);
         // run_hook should skip immediately (no spawn)
-        run_hook("echo should-not-run");
+        run_hook("echo should-not-run", MuteOrigin::External, |_| {});
         // Clean up
         HOOK_RUNNING.store(false, Ordering::SeqCst);
     }
@@ -199,7 +308,7 @@ mod tests {
             on_mute_command: mute_cmd,
             ..Config::default()
         };
-        run_action_hook(MonitorAction::ApplyMute, &config);
+        run_action_hook(MonitorAction::ApplyMute, MuteOrigin::External, &config);
         wait_for_file(&mute_marker);
         wait_for_hook_idle();
 
@@ -216,7 +325,7 @@ mod tests {
             on_unmute_command: unmute_cmd,
             ..Config::default()
         };
-        run_action_hook(MonitorAction::ClearMute, &config);
+        run_action_hook(MonitorAction::ClearMute, MuteOrigin::External, &config);
         wait_for_file(&unmute_marker);
         wait_for_hook_idle();
 
@@ -227,6 +336,68 @@ mod tests {
         );
     }
 
+    #[test]
+    fn run_action_hook_with_reports_failure() {
+        let _lock = HOOK_TEST_LOCK.lock().unwrap();
+        wait_for_hook_idle();
+
+        let config = Config {
+            on_mute_command: "exit 3".into(),
+            ..Config::default()
+        };
+        let (tx, rx) = std::sync::mpsc::channel();
+        run_action_hook_with(
+            MonitorAction::ApplyMute,
+            MuteOrigin::External,
+            &config,
+            move |outcome| tx.send(outcome).unwrap(),
+        );
+        let outcome = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        wait_for_hook_idle();
+
+        assert_eq!(outcome.command, "exit 3");
+        assert!(outcome.result.is_err());
+    }
+
+    #[test]
+    fn run_device_hooks_runs_matching_events() {
+        let _lock = HOOK_TEST_LOCK.lock().unwrap();
+        wait_for_hook_idle();
+
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("event.txt");
+        let cmd = if cfg!(windows) {
+            format!("echo %FOCUSMUTE_EVENT%> {}", marker.display())
+        } else {
+            format!("echo $FOCUSMUTE_EVENT > {}", marker.display())
+        };
+        let config = Config {
+            device_hooks: [
+                ("on_phantom_changed".to_string(), cmd),
+                ("on_clock_changed".to_string(), "exit 1".to_string()),
+            ]
+            .into(),
+            ..Config::default()
+        };
+        let (tx, rx) = std::sync::mpsc::channel();
+        run_device_hooks_with(
+            &[
+                DeviceEvent::SelectedInputChanged,
+                DeviceEvent::PhantomPowerChanged,
+            ],
+            &config,
+            move |outcome| tx.send(outcome).unwrap(),
+        );
+        let outcome = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        wait_for_hook_idle();
+
+        assert!(outcome.result.is_ok());
+        // Only the phantom hook matched
+        assert!(rx.try_recv().is_err());
+        let content = std::fs::read_to_string(&marker).unwrap();
+        assert_eq!(content.trim(), "on_phantom_changed");
+    }
+
     #[test]
     fn hook_guard_resets_on_panic() {
         let _lock = HOOK_TEST_LOCK.lock().unwrap();
@@ -0,0 +1,367 @@
+//! Extra mute indicators — mirror the mute state beyond the Scarlett's LEDs.
+//!
+//! The mute strategy paints the interface's number LEDs; an
+//! [`IndicatorSink`] shows the same muted/live state somewhere else, such as
+//! a USB busylight on the desk or door. [`Indicators`] fans each change out
+//! to every configured sink and logs (rather than fails on) sinks that have
+//! gone away, so a missing light never affects the main indicator.
+
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::led::busylight_live_color;
+use crate::monitor::MonitorAction;
+
+/// Something that can show the mute state.
+pub trait IndicatorSink: Send {
+    /// Short name for logs, e.g. `"busylight"`.
+    fn name(&self) -> &str;
+
+    /// Show muted in `mute_color` (`0xRRGGBB00`), or live.
+    fn show(&mut self, muted: bool, mute_color: u32) -> io::Result<()>;
+
+    /// Turn the indicator off (on exit).
+    fn clear(&mut self) -> io::Result<()>;
+}
+
+/// All configured [`IndicatorSink`]s.
+#[derive(Default)]
+pub struct Indicators {
+    sinks: Vec<Box<dyn IndicatorSink>>,
+}
+
+impl Indicators {
+    /// Sinks enabled in `config` (`busylight_enabled`). Devices are opened on
+    /// first use, so a light plugged in later is picked up.
+    pub fn from_config(config: &Config) -> Self {
+        let mut indicators = Self::default();
+        if config.busylight_enabled {
+            indicators.push(Box::new(Busylight::new(busylight_live_color(config))));
+        }
+        indicators
+    }
+
+    pub fn push(&mut self, sink: Box<dyn IndicatorSink>) {
+        self.sinks.push(sink);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sinks.is_empty()
+    }
+
+    /// Show the mute state on every sink.
+    pub fn show(&mut self, muted: bool, mute_color: u32) {
+        for sink in &mut self.sinks {
+            if let Err(e) = sink.show(muted, mute_color) {
+                report(sink.name(), &e);
+            }
+        }
+    }
+
+    /// Mirror a mute-state change (nothing for `NoChange`).
+    pub fn apply(&mut self, action: MonitorAction, mute_color: u32) {
+        match action {
+            MonitorAction::ApplyMute => self.show(true, mute_color),
+            MonitorAction::ClearMute => self.show(false, mute_color),
+            MonitorAction::NoChange => {}
+        }
+    }
+
+    /// Turn every sink off.
+    pub fn clear(&mut self) {
+        for sink in &mut self.sinks {
+            if let Err(e) = sink.clear() {
+                report(sink.name(), &e);
+            }
+        }
+    }
+}
+
+fn report(name: &str, e: &io::Error) {
+    // An unplugged light is expected, not worth a warning on every toggle
+    if e.kind() == io::ErrorKind::NotFound {
+        log::debug!("[indicator] {name}: {e}");
+    } else {
+        log::warn!("[indicator] {name}: {e}");
+    }
+}
+
+// ── USB busylights ──
+
+/// Supported USB HID busylight families.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BusylightModel {
+    /// Luxafor Flag / Orb / Bluetooth dongle.
+    Luxafor,
+    /// Embrava Blynclight (Standard, Plus, Mini).
+    Blynclight,
+}
+
+/// Known (vendor ID, product ID) pairs.
+const BUSYLIGHT_IDS: &[(u16, u16, BusylightModel)] = &[
+    (0x04D8, 0xF372, BusylightModel::Luxafor),
+    (0x2C0D, 0x0001, BusylightModel::Blynclight),
+    (0x2C0D, 0x000C, BusylightModel::Blynclight),
+    (0x2C0D, 0x0010, BusylightModel::Blynclight),
+    (0x0E53, 0x2516, BusylightModel::Blynclight),
+    (0x0E53, 0x2517, BusylightModel::Blynclight),
+];
+
+impl BusylightModel {
+    /// The model for a USB vendor/product ID pair, if it's a known busylight.
+    pub fn from_ids(vendor_id: u16, product_id: u16) -> Option<Self> {
+        BUSYLIGHT_IDS
+            .iter()
+            .find(|&&(v, p, _)| v == vendor_id && p == product_id)
+            .map(|&(_, _, model)| model)
+    }
+
+    /// HID output report that shows `color` (`0xRRGGBB00`; 0 = off),
+    /// prefixed with report ID 0 as both hidraw and Windows expect.
+    pub fn report(self, color: u32) -> [u8; 9] {
+        let [r, g, b, _] = color.to_be_bytes();
+        match self {
+            // Command 1 = static color, 0xFF = all LEDs
+            BusylightModel::Luxafor => [0, 0x01, 0xFF, r, g, b, 0, 0, 0],
+            // Note the R, B, G order; bit 0 of byte 4 switches the light off
+            BusylightModel::Blynclight => {
+                let off = u8::from(color & 0xFFFF_FF00 == 0);
+                [0, r, b, g, off, 0, 0, 0xFF, 0x22]
+            }
+        }
+    }
+}
+
+/// A USB busylight: the mute color while muted, `live_color` (or off)
+/// while live.
+pub struct Busylight {
+    live_color: Option<u32>,
+    device: Option<(File, BusylightModel)>,
+}
+
+impl Busylight {
+    pub fn new(live_color: Option<u32>) -> Self {
+        Self {
+            live_color,
+            device: None,
+        }
+    }
+
+    fn write(&mut self, color: u32) -> io::Result<()> {
+        if self.device.is_none() {
+            let (path, model) = find_busylight()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no busylight connected"))?;
+            let file = File::options().write(true).open(&path)?;
+            log::info!(
+                "[indicator] using {model:?} busylight at {}",
+                path.display()
+            );
+            self.device = Some((file, model));
+        }
+        let Some((file, model)) = self.device.as_mut() else {
+            return Ok(());
+        };
+        let result = file.write_all(&model.report(color));
+        if result.is_err() {
+            // Unplugged — look for it again next time
+            self.device = None;
+        }
+        result
+    }
+}
+
+impl IndicatorSink for Busylight {
+    fn name(&self) -> &str {
+        "busylight"
+    }
+
+    fn show(&mut self, muted: bool, mute_color: u32) -> io::Result<()> {
+        let color = if muted {
+            mute_color
+        } else {
+            self.live_color.unwrap_or(0)
+        };
+        self.write(color)
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        if self.device.is_none() {
+            return Ok(());
+        }
+        self.write(0)
+    }
+}
+
+/// Find the first connected busylight's HID device path.
+fn find_busylight() -> Option<(PathBuf, BusylightModel)> {
+    #[cfg(target_os = "linux")]
+    {
+        let entries = std::fs::read_dir("/sys/class/hidraw").ok()?;
+        entries.flatten().find_map(|entry| {
+            let uevent = std::fs::read_to_string(entry.path().join("device/uevent")).ok()?;
+            let (vid, pid) = parse_hid_id(&uevent)?;
+            let model = BusylightModel::from_ids(vid, pid)?;
+            Some((PathBuf::from("/dev").join(entry.file_name()), model))
+        })
+    }
+    #[cfg(windows)]
+    {
+        /// `GUID_DEVINTERFACE_HID`
+        const HID_GUID: windows::core::GUID =
+            windows::core::GUID::from_u128(0x4D1E55B2_F16F_11CF_88CB_001111000030);
+        crate::device::win_enum::enumerate_interface_paths(&HID_GUID, |path| {
+            let (vid, pid) = parse_vid_pid(&path)?;
+            let model = BusylightModel::from_ids(vid, pid)?;
+            Some((PathBuf::from(path), model))
+        })
+    }
+    #[cfg(not(any(windows, target_os = "linux")))]
+    {
+        None
+    }
+}
+
+/// Vendor and product ID from a hidraw `uevent` (`HID_ID=0003:000004D8:0000F372`).
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_hid_id(uevent: &str) -> Option<(u16, u16)> {
+    let value = uevent
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("HID_ID="))?;
+    let mut parts = value.split(':').skip(1);
+    let vid = u32::from_str_radix(parts.next()?, 16).ok()?;
+    let pid = u32::from_str_radix(parts.next()?, 16).ok()?;
+    Some((u16::try_from(vid).ok()?, u16::try_from(pid).ok()?))
+}
+
+/// Vendor and product ID from a Windows device path (`...#vid_04d8&pid_f372#...`).
+#[cfg_attr(not(windows), allow(dead_code))]
+fn parse_vid_pid(path: &str) -> Option<(u16, u16)> {
+    let lower = path.to_ascii_lowercase();
+    let hex_after = |tag: &str| {
+        let start = lower.find(tag)? + tag.len();
+        u16::from_str_radix(lower.get(start..start + 4)?, 16).ok()
+    };
+    Some((hex_after("vid_")?, hex_after("pid_")?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    /// `show` calls as `Some((muted, color))`, `clear` calls as `None`.
+    type Calls = Arc<Mutex<Vec<Option<(bool, u32)>>>>;
+
+    /// Records every call; fails `show` when `fail` is set.
+    struct Recorder {
+        calls: Calls,
+        fail: bool,
+    }
+
+    impl IndicatorSink for Recorder {
+        fn name(&self) -> &str {
+            "recorder"
+        }
+
+        fn show(&mut self, muted: bool, mute_color: u32) -> io::Result<()> {
+            self.calls.lock().unwrap().push(Some((muted, mute_color)));
+            if self.fail {
+                return Err(io::Error::other("unplugged"));
+            }
+            Ok(())
+        }
+
+        fn clear(&mut self) -> io::Result<()> {
+            self.calls.lock().unwrap().push(None);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn fans_out_to_every_sink_despite_failures() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let mut indicators = Indicators::default();
+        for fail in [true, false] {
+            indicators.push(Box::new(Recorder {
+                calls: Arc::clone(&calls),
+                fail,
+            }));
+        }
+
+        indicators.apply(MonitorAction::ApplyMute, 0xFF00_0000);
+        indicators.apply(MonitorAction::NoChange, 0xFF00_0000);
+        indicators.apply(MonitorAction::ClearMute, 0xFF00_0000);
+        indicators.clear();
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![
+                Some((true, 0xFF00_0000)),
+                Some((true, 0xFF00_0000)),
+                Some((false, 0xFF00_0000)),
+                Some((false, 0xFF00_0000)),
+                None,
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn from_config_adds_busylight_only_when_enabled() {
+        assert!(Indicators::from_config(&Config::default()).is_empty());
+        let config = Config {
+            busylight_enabled: true,
+            ..Config::default()
+        };
+        assert!(!Indicators::from_config(&config).is_empty());
+    }
+
+    #[test]
+    fn busylight_reports() {
+        assert_eq!(
+            BusylightModel::Luxafor.report(0xFF80_4000),
+            [0, 1, 0xFF, 0xFF, 0x80, 0x40, 0, 0, 0]
+        );
+        assert_eq!(
+            BusylightModel::Blynclight.report(0xFF80_4000),
+            [0, 0xFF, 0x40, 0x80, 0, 0, 0, 0xFF, 0x22]
+        );
+        assert_eq!(BusylightModel::Blynclight.report(0)[4], 1);
+    }
+
+    #[test]
+    fn recognises_busylight_ids() {
+        assert_eq!(
+            BusylightModel::from_ids(0x04D8, 0xF372),
+            Some(BusylightModel::Luxafor)
+        );
+        assert_eq!(
+            BusylightModel::from_ids(0x2C0D, 0x000C),
+            Some(BusylightModel::Blynclight)
+        );
+        assert_eq!(BusylightModel::from_ids(0x1235, 0x8219), None);
+    }
+
+    #[test]
+    fn parses_hidraw_uevent() {
+        let uevent = "DRIVER=hid-generic\nHID_ID=0003:000004D8:0000F372\nHID_NAME=LUXAFOR FLAG\n";
+        assert_eq!(parse_hid_id(uevent), Some((0x04D8, 0xF372)));
+        assert_eq!(parse_hid_id("HID_NAME=x\n"), None);
+    }
+
+    #[test]
+    fn parses_windows_hid_path() {
+        assert_eq!(
+            parse_vid_pid(
+                r"\\?\hid#vid_04d8&pid_f372#7&2a8c&0&0000#{4d1e55b2-f16f-11cf-88cb-001111000030}"
+            ),
+            Some((0x04D8, 0xF372))
+        );
+        assert_eq!(
+            parse_vid_pid(r"\\?\HID#VID_2C0D&PID_000C#x"),
+            Some((0x2C0D, 0x000C))
+        );
+        assert_eq!(parse_vid_pid(r"\\?\root#system#0000"), None);
+    }
+}
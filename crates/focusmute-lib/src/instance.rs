@@ -0,0 +1,145 @@
+//! Named instances — several independent FocusMute trays on one machine.
+//!
+//! By default there is one tray per machine: the single-instance lock and the
+//! IPC endpoint file have fixed names. An instance name (`--instance-name`
+//! or the `instance_name` config key) scopes both, plus the per-instance
+//! state files, so e.g. two seats can each run a tray bound to their own
+//! device serial and hotkey.
+//!
+//! The name is set once at startup, before any of those paths are used.
+//! Given on the command line it also selects the instance's own config file
+//! (`config-<name>.toml`); read from a config's `instance_name` key, that
+//! config file stays in use.
+
+use std::sync::OnceLock;
+
+/// Longest accepted instance name.
+pub const MAX_NAME_LEN: usize = 32;
+
+struct Instance {
+    name: String,
+    /// Set from the command line — the config file is scoped too.
+    scopes_config: bool,
+}
+
+static INSTANCE: OnceLock<Instance> = OnceLock::new();
+
+/// Check an instance name: 1–32 ASCII letters, digits, `-` or `_` (it ends
+/// up in file names and OS lock names).
+pub fn validate(name: &str) -> Result<(), String> {
+    if name.is_empty() || name.len() > MAX_NAME_LEN {
+        return Err(format!("must be 1-{MAX_NAME_LEN} characters"));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err("may only contain letters, digits, '-' and '_'".into());
+    }
+    Ok(())
+}
+
+fn set(name: &str, scopes_config: bool) -> Result<(), String> {
+    validate(name).map_err(|e| format!("invalid instance name \"{name}\": {e}"))?;
+    INSTANCE
+        .set(Instance {
+            name: name.to_string(),
+            scopes_config,
+        })
+        .map_err(|_| "instance name already set".to_string())
+}
+
+/// Use the instance named on the command line (`--instance-name`),
+/// including its own config file.
+pub fn set_from_flag(name: &str) -> Result<(), String> {
+    set(name, true)
+}
+
+/// Use the instance named by a loaded config's `instance_name` key. Empty
+/// keeps the default instance.
+pub fn set_from_config(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Ok(());
+    }
+    set(name, false)
+}
+
+/// The current instance name; `None` for the default instance.
+pub fn name() -> Option<&'static str> {
+    INSTANCE.get().map(|i| i.name.as_str())
+}
+
+/// `file` (e.g. `tray-ipc.json`) with `-<name>` inserted before the
+/// extension, or unchanged for `None`.
+pub fn scoped_file_name(file: &str, name: Option<&str>) -> String {
+    let Some(name) = name else {
+        return file.to_string();
+    };
+    match file.rsplit_once('.') {
+        Some((stem, ext)) => format!("{stem}-{name}.{ext}"),
+        None => format!("{file}-{name}"),
+    }
+}
+
+/// `file` scoped to the current instance.
+pub fn scoped(file: &str) -> String {
+    scoped_file_name(file, name())
+}
+
+/// Whether the name came from `--instance-name` — relaunching this
+/// instance needs the flag again.
+pub fn is_from_flag() -> bool {
+    INSTANCE.get().is_some_and(|i| i.scopes_config)
+}
+
+/// Config file name for the current instance.
+pub fn config_file_name() -> String {
+    scoped_file_name("config.toml", name().filter(|_| is_from_flag()))
+}
+
+/// Name of the OS single-instance lock.
+pub fn lock_id() -> String {
+    match name() {
+        Some(name) => format!("focusmute-{name}"),
+        None => "focusmute".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_names() {
+        assert!(validate("seat2").is_ok());
+        assert!(validate("studio_B-1").is_ok());
+        assert!(validate("").is_err());
+        assert!(validate("two words").is_err());
+        assert!(validate("../etc").is_err());
+        assert!(validate(&"x".repeat(MAX_NAME_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn scopes_file_names() {
+        assert_eq!(scoped_file_name("tray-ipc.json", None), "tray-ipc.json");
+        assert_eq!(
+            scoped_file_name("tray-ipc.json", Some("seat2")),
+            "tray-ipc-seat2.json"
+        );
+        assert_eq!(
+            scoped_file_name("led_baseline.json", Some("a")),
+            "led_baseline-a.json"
+        );
+        assert_eq!(
+            scoped_file_name("history.csv", Some("seat2")),
+            "history-seat2.csv"
+        );
+        assert_eq!(scoped_file_name("lock", Some("a")), "lock-a");
+    }
+
+    #[test]
+    fn empty_config_key_keeps_default_instance() {
+        assert!(set_from_config("").is_ok());
+        assert!(set_from_config("bad name").is_err());
+    }
+}
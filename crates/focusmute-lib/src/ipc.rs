@@ -0,0 +1,1076 @@
+//! Tray ↔ CLI IPC — lets CLI commands route device requests through a running tray.
+//!
+//! The tray owns the device handle while it runs. Instead of opening a second
+//! handle and racing on LED/descriptor writes, the CLI connects to the tray and
+//! asks it to perform the operation — or to mute, quit, or reload its config.
+//! The channel is a Unix socket in `$XDG_RUNTIME_DIR` (mode 0600) or, on
+//! Windows, a named pipe whose DACL admits only the current user. The tray
+//! publishes the socket path or pipe name and a per-session token in
+//! `tray-ipc.json` (config directory, readable by the current user only); each
+//! request carries the token. Messages are one JSON object per line.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::device::{DeviceError, DeviceInfo, ScarlettDevice};
+use crate::events::RemoteCommand;
+use crate::monitor::MuteOrigin;
+use crate::token::ct_eq;
+
+/// Connect/read timeout used by the CLI client.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Idle timeout for a server-side connection, so a stalled client can't block others.
+const SERVER_IDLE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Longest request or reply line accepted. Descriptor reads are the largest
+/// messages, at a few characters per byte.
+const MAX_LINE_BYTES: usize = 1024 * 1024;
+
+/// A request the CLI sends to the tray.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum IpcRequest {
+    /// Liveness check.
+    Ping,
+    /// Read `size` descriptor bytes starting at `offset`.
+    GetDescriptor { offset: u32, size: u32 },
+    /// The CLI is about to set the OS mute state — attribute it to FocusMute.
+    AnnounceMute { muted: bool },
+    /// Query the tray's confirmed mute state and its origin.
+    GetMuteState,
+    /// Inject a synthetic state into the tray (demos, screenshots, UI tests).
+    Simulate { state: SimulatedState },
+    /// Change the OS mute state through the tray. Replies with the state it
+    /// switches to.
+    SetMute { command: RemoteCommand },
+    /// Quit the tray.
+    Quit,
+    /// Re-read the config file and apply it.
+    ReloadConfig,
+    /// Describe the tray's device (for [`TrayDevice`]).
+    GetDeviceInfo,
+    /// Write `data` to the descriptor at `offset`.
+    SetDescriptor { offset: u32, data: Vec<u8> },
+    /// Send a DATA_NOTIFY so the firmware applies a descriptor write.
+    DataNotify { event_id: u32 },
+    /// Send a raw TRANSACT command.
+    Transact {
+        command: u32,
+        payload: Vec<u8>,
+        out_size: usize,
+    },
+    /// Reinitialize the device; the tray reopens it afterwards.
+    ResetDevice,
+}
+
+/// A synthetic tray state for [`IpcRequest::Simulate`].
+///
+/// While a simulation is active the tray ignores the real microphone;
+/// `Off` hands control back to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimulatedState {
+    Muted,
+    Live,
+    /// Release the device and show it as disconnected.
+    Disconnect,
+    Off,
+}
+
+/// The tray's reply to an [`IpcRequest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum IpcResponse {
+    Pong,
+    Ok,
+    Descriptor {
+        data: Vec<u8>,
+    },
+    MuteState {
+        muted: bool,
+        origin: MuteOrigin,
+    },
+    DeviceInfo {
+        info: DeviceInfo,
+        /// [`DeviceInfo::config_raw`], which `info` leaves out.
+        config_raw: Vec<u8>,
+        /// [`DeviceInfo::init_raw`], which `info` leaves out.
+        init_raw: Vec<u8>,
+    },
+    /// Reply to [`IpcRequest::Transact`].
+    Transact {
+        data: Vec<u8>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+struct Envelope {
+    token: String,
+    #[serde(flatten)]
+    request: IpcRequest,
+}
+
+/// Contents of the endpoint file published by the tray.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IpcEndpoint {
+    /// Unix socket path, or named pipe name (`\\.\pipe\…`) on Windows.
+    pub path: PathBuf,
+    pub token: String,
+    pub pid: u32,
+}
+
+/// Full path to the tray endpoint file (per instance, see [`crate::instance`]).
+pub fn endpoint_path() -> Option<PathBuf> {
+    Config::dir().map(|d| d.join(crate::instance::scoped("tray-ipc.json")))
+}
+
+/// Where the tray listens: `focusmute.sock` (per instance) in
+/// `$XDG_RUNTIME_DIR`, falling back to the config directory.
+#[cfg(unix)]
+pub fn listen_path() -> Option<PathBuf> {
+    dirs::runtime_dir()
+        .or_else(Config::dir)
+        .map(|d| d.join(crate::instance::scoped("focusmute.sock")))
+}
+
+/// Where the tray listens: a named pipe unique to this process.
+#[cfg(windows)]
+pub fn listen_path() -> Option<PathBuf> {
+    Some(PathBuf::from(format!(
+        r"\\.\pipe\focusmute-ipc-{}",
+        std::process::id()
+    )))
+}
+
+/// Generate a 256-bit session token from the OS random source.
+fn new_token() -> std::io::Result<String> {
+    let mut bytes = [0u8; 32];
+    getrandom::fill(&mut bytes)?;
+    Ok(bytes.iter().map(|b| format!("{b:02x}")).collect())
+}
+
+/// Write `contents` to a fresh file at `path` that only the current user can
+/// read — mode 0600 on Unix, a DACL granting the user alone on Windows. An
+/// existing file is replaced, not reused, so its permissions don't carry over.
+fn write_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+        _ => {}
+    }
+    create_private(path)?.write_all(contents)
+}
+
+#[cfg(unix)]
+fn create_private(path: &Path) -> std::io::Result<std::fs::File> {
+    use std::os::unix::fs::OpenOptionsExt;
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(path)
+}
+
+#[cfg(windows)]
+fn create_private(path: &Path) -> std::io::Result<std::fs::File> {
+    use std::os::windows::ffi::OsStrExt;
+    use std::os::windows::io::FromRawHandle;
+    use windows::Win32::Foundation::GENERIC_WRITE;
+    use windows::Win32::Storage::FileSystem::{
+        CREATE_NEW, CreateFileW, FILE_ATTRIBUTE_NORMAL, FILE_SHARE_READ,
+    };
+    use windows::core::PCWSTR;
+
+    let security = win::UserOnly::new()?;
+    let attributes = security.attributes();
+    let wide: Vec<u16> = path
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    let handle = unsafe {
+        CreateFileW(
+            PCWSTR(wide.as_ptr()),
+            GENERIC_WRITE.0,
+            FILE_SHARE_READ,
+            Some(&attributes),
+            CREATE_NEW,
+            FILE_ATTRIBUTE_NORMAL,
+            None,
+        )
+    }
+    .map_err(std::io::Error::other)?;
+    Ok(unsafe { std::fs::File::from_raw_handle(handle.0) })
+}
+
+#[cfg(windows)]
+mod win {
+    use std::io;
+
+    use windows::Win32::Foundation::{CloseHandle, HANDLE, HLOCAL, LocalFree};
+    use windows::Win32::Security::Authorization::{
+        ConvertSidToStringSidW, ConvertStringSecurityDescriptorToSecurityDescriptorW,
+        SDDL_REVISION_1,
+    };
+    use windows::Win32::Security::{
+        GetTokenInformation, PSECURITY_DESCRIPTOR, SECURITY_ATTRIBUTES, TOKEN_QUERY, TOKEN_USER,
+        TokenUser,
+    };
+    use windows::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
+    use windows::core::{PCWSTR, PWSTR};
+
+    /// Security descriptor with a protected DACL that grants the current
+    /// user full access and nobody else anything.
+    pub struct UserOnly(PSECURITY_DESCRIPTOR);
+
+    impl UserOnly {
+        pub fn new() -> io::Result<Self> {
+            let sddl = format!("D:P(A;;GA;;;{})", current_user_sid()?);
+            let wide: Vec<u16> = sddl.encode_utf16().chain(std::iter::once(0)).collect();
+            let mut descriptor = PSECURITY_DESCRIPTOR(std::ptr::null_mut());
+            unsafe {
+                ConvertStringSecurityDescriptorToSecurityDescriptorW(
+                    PCWSTR(wide.as_ptr()),
+                    SDDL_REVISION_1,
+                    &mut descriptor,
+                    None,
+                )
+            }
+            .map_err(io::Error::other)?;
+            Ok(UserOnly(descriptor))
+        }
+
+        /// Attributes for `CreateFileW` / `CreateNamedPipeW`; borrow `self`
+        /// for as long as they're used.
+        pub fn attributes(&self) -> SECURITY_ATTRIBUTES {
+            SECURITY_ATTRIBUTES {
+                nLength: std::mem::size_of::<SECURITY_ATTRIBUTES>() as u32,
+                lpSecurityDescriptor: self.0.0,
+                bInheritHandle: false.into(),
+            }
+        }
+    }
+
+    impl Drop for UserOnly {
+        fn drop(&mut self) {
+            unsafe { LocalFree(Some(HLOCAL(self.0.0))) };
+        }
+    }
+
+    /// The current process user's SID as a string (`S-1-5-21-…`).
+    fn current_user_sid() -> io::Result<String> {
+        unsafe {
+            let mut token = HANDLE::default();
+            OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token)
+                .map_err(io::Error::other)?;
+            let mut len = 0u32;
+            let _ = GetTokenInformation(token, TokenUser, None, 0, &mut len);
+            // u64 elements keep the TOKEN_USER pointer aligned
+            let mut buf = vec![0u64; (len as usize).div_ceil(8)];
+            let queried = GetTokenInformation(
+                token,
+                TokenUser,
+                Some(buf.as_mut_ptr().cast()),
+                len,
+                &mut len,
+            );
+            let _ = CloseHandle(token);
+            queried.map_err(io::Error::other)?;
+            let user = &*buf.as_ptr().cast::<TOKEN_USER>();
+            let mut sid = PWSTR::null();
+            ConvertSidToStringSidW(user.User.Sid, &mut sid).map_err(io::Error::other)?;
+            let text = sid.to_string().map_err(io::Error::other);
+            LocalFree(Some(HLOCAL(sid.0.cast())));
+            text
+        }
+    }
+}
+
+// ── Transport ──
+
+/// Unix socket transport.
+#[cfg(unix)]
+mod transport {
+    use std::io;
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::path::Path;
+    use std::time::Duration;
+
+    pub type Stream = UnixStream;
+
+    pub struct Listener(UnixListener);
+
+    impl Listener {
+        /// Bind the socket at `path`, readable and writable by the user only.
+        pub fn bind(path: &Path) -> io::Result<Self> {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            // A socket left behind by a tray that crashed; a live tray holds
+            // the single-instance lock, so nothing else is listening on it
+            match std::fs::remove_file(path) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+                _ => {}
+            }
+            let listener = UnixListener::bind(path)?;
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+            Ok(Listener(listener))
+        }
+
+        pub fn accept(&mut self) -> io::Result<Stream> {
+            self.0.accept().map(|(stream, _)| stream)
+        }
+    }
+
+    pub fn connect(path: &Path, timeout: Duration) -> io::Result<Stream> {
+        let stream = UnixStream::connect(path)?;
+        stream.set_read_timeout(Some(timeout))?;
+        stream.set_write_timeout(Some(timeout))?;
+        Ok(stream)
+    }
+
+    pub fn set_idle_timeout(stream: &Stream, timeout: Duration) -> io::Result<()> {
+        stream.set_read_timeout(Some(timeout))
+    }
+
+    /// The socket's read timeout, set in [`connect`], already bounds the wait.
+    pub fn wait_readable(_stream: &Stream, _timeout: Duration) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Remove the socket file once the server is gone.
+    pub fn cleanup(path: &Path) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// Named pipe transport.
+#[cfg(windows)]
+mod transport {
+    use std::fs::File;
+    use std::io;
+    use std::os::windows::ffi::OsStrExt;
+    use std::os::windows::io::{AsRawHandle, FromRawHandle};
+    use std::path::Path;
+    use std::time::{Duration, Instant};
+
+    use windows::Win32::Foundation::{ERROR_PIPE_BUSY, ERROR_PIPE_CONNECTED, HANDLE};
+    use windows::Win32::Storage::FileSystem::{
+        FILE_FLAG_FIRST_PIPE_INSTANCE, FILE_FLAGS_AND_ATTRIBUTES, PIPE_ACCESS_DUPLEX,
+    };
+    use windows::Win32::System::Pipes::{
+        ConnectNamedPipe, CreateNamedPipeW, PIPE_READMODE_BYTE, PIPE_REJECT_REMOTE_CLIENTS,
+        PIPE_TYPE_BYTE, PIPE_UNLIMITED_INSTANCES, PIPE_WAIT, PeekNamedPipe,
+    };
+    use windows::core::PCWSTR;
+
+    use super::win::UserOnly;
+
+    /// Pipe handles are plain synchronous file handles.
+    pub type Stream = File;
+
+    pub struct Listener {
+        name: Vec<u16>,
+        /// The next instance, created ahead of `accept`.
+        pending: Option<File>,
+    }
+
+    impl Listener {
+        /// Create the first pipe instance, failing if another process already
+        /// owns the name.
+        pub fn bind(path: &Path) -> io::Result<Self> {
+            let name: Vec<u16> = path
+                .as_os_str()
+                .encode_wide()
+                .chain(std::iter::once(0))
+                .collect();
+            let first = create_instance(&name, FILE_FLAG_FIRST_PIPE_INSTANCE)?;
+            Ok(Listener {
+                name,
+                pending: Some(first),
+            })
+        }
+
+        pub fn accept(&mut self) -> io::Result<Stream> {
+            let pipe = match self.pending.take() {
+                Some(pipe) => pipe,
+                None => create_instance(&self.name, FILE_FLAGS_AND_ATTRIBUTES(0))?,
+            };
+            match unsafe { ConnectNamedPipe(HANDLE(pipe.as_raw_handle()), None) } {
+                Ok(()) => Ok(pipe),
+                // The client connected between create and connect
+                Err(e) if e.code() == ERROR_PIPE_CONNECTED.to_hresult() => Ok(pipe),
+                Err(e) => Err(io::Error::other(e)),
+            }
+        }
+    }
+
+    fn create_instance(name: &[u16], flags: FILE_FLAGS_AND_ATTRIBUTES) -> io::Result<File> {
+        let security = UserOnly::new()?;
+        let attributes = security.attributes();
+        let handle = unsafe {
+            CreateNamedPipeW(
+                PCWSTR(name.as_ptr()),
+                PIPE_ACCESS_DUPLEX | flags,
+                PIPE_TYPE_BYTE | PIPE_READMODE_BYTE | PIPE_WAIT | PIPE_REJECT_REMOTE_CLIENTS,
+                PIPE_UNLIMITED_INSTANCES,
+                4096,
+                4096,
+                0,
+                Some(&attributes),
+            )
+        };
+        if handle.is_invalid() {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(unsafe { File::from_raw_handle(handle.0) })
+    }
+
+    /// Open the pipe, waiting up to `timeout` while all instances are busy.
+    /// Synchronous pipes have no read timeout: see [`wait_readable`].
+    pub fn connect(path: &Path, timeout: Duration) -> io::Result<Stream> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            match std::fs::OpenOptions::new()
+                .read(true)
+                .write(true)
+                .open(path)
+            {
+                Err(e)
+                    if e.raw_os_error() == Some(ERROR_PIPE_BUSY.0 as i32)
+                        && Instant::now() < deadline =>
+                {
+                    std::thread::sleep(Duration::from_millis(20));
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Pipes have no read timeout; each connection is served on its own
+    /// thread instead, so a stalled client blocks nobody else.
+    pub fn set_idle_timeout(_stream: &Stream, _timeout: Duration) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Wait up to `timeout` for the tray to start replying, so a stalled
+    /// tray doesn't hang the CLI in a blocking read.
+    pub fn wait_readable(stream: &Stream, timeout: Duration) -> io::Result<()> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let mut available = 0u32;
+            unsafe {
+                PeekNamedPipe(
+                    HANDLE(stream.as_raw_handle()),
+                    None,
+                    0,
+                    None,
+                    Some(&mut available),
+                    None,
+                )
+            }
+            .map_err(io::Error::other)?;
+            if available > 0 {
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                return Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    "the tray app did not reply",
+                ));
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    /// The pipe name disappears with its last handle.
+    pub fn cleanup(_path: &Path) {}
+}
+
+// ── Server (tray side) ──
+
+/// Listening IPC endpoint. Removes the endpoint file (and socket) when dropped.
+pub struct IpcServer {
+    listener: transport::Listener,
+    path: PathBuf,
+    token: String,
+    cleanup: Cleanup,
+}
+
+/// Files removed when the server goes away.
+struct Cleanup {
+    socket: PathBuf,
+    endpoint_file: Option<PathBuf>,
+}
+
+impl Drop for Cleanup {
+    fn drop(&mut self) {
+        if let Some(ref path) = self.endpoint_file {
+            let _ = std::fs::remove_file(path);
+        }
+        transport::cleanup(&self.socket);
+    }
+}
+
+impl IpcServer {
+    /// Listen at [`listen_path`].
+    pub fn bind() -> std::io::Result<Self> {
+        let path = listen_path().ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "no directory for the IPC socket",
+            )
+        })?;
+        Self::bind_at(&path)
+    }
+
+    /// Listen on the Unix socket or named pipe at `path`.
+    pub fn bind_at(path: &Path) -> std::io::Result<Self> {
+        let listener = transport::Listener::bind(path)?;
+        Ok(IpcServer {
+            listener,
+            path: path.to_path_buf(),
+            token: new_token()?,
+            cleanup: Cleanup {
+                socket: path.to_path_buf(),
+                endpoint_file: None,
+            },
+        })
+    }
+
+    /// The endpoint description for this server.
+    pub fn endpoint(&self) -> IpcEndpoint {
+        IpcEndpoint {
+            path: self.path.clone(),
+            token: self.token.clone(),
+            pid: std::process::id(),
+        }
+    }
+
+    /// Write the endpoint file so CLI clients can find this server. Only the
+    /// current user can read it, since it holds the token.
+    pub fn publish(&mut self, path: &Path) -> std::io::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string(&self.endpoint()).map_err(std::io::Error::other)?;
+        write_private(path, json.as_bytes())?;
+        self.cleanup.endpoint_file = Some(path.to_path_buf());
+        Ok(())
+    }
+
+    /// Serve requests on background threads, one per connection.
+    ///
+    /// `handler` is called for every authenticated request except `Ping`.
+    pub fn spawn<F>(self, handler: F) -> std::io::Result<IpcServerHandle>
+    where
+        F: Fn(IpcRequest) -> IpcResponse + Send + Sync + 'static,
+    {
+        let IpcServer {
+            mut listener,
+            token,
+            cleanup,
+            ..
+        } = self;
+        let handler = std::sync::Arc::new(handler);
+        let token = std::sync::Arc::new(token);
+        std::thread::Builder::new()
+            .name("ipc-accept".into())
+            .spawn(move || {
+                loop {
+                    let stream = match listener.accept() {
+                        Ok(s) => s,
+                        Err(e) => {
+                            log::debug!("[ipc] accept failed: {e}");
+                            continue;
+                        }
+                    };
+                    let handler = std::sync::Arc::clone(&handler);
+                    let token = std::sync::Arc::clone(&token);
+                    let spawned =
+                        std::thread::Builder::new()
+                            .name("ipc-client".into())
+                            .spawn(move || {
+                                if let Err(e) = serve_connection(stream, &token, &*handler) {
+                                    log::debug!("[ipc] connection error: {e}");
+                                }
+                            });
+                    if let Err(e) = spawned {
+                        log::debug!("[ipc] could not serve connection: {e}");
+                    }
+                }
+            })?;
+        Ok(IpcServerHandle { _cleanup: cleanup })
+    }
+}
+
+/// Keeps the endpoint file (and socket) alive while the server thread runs.
+pub struct IpcServerHandle {
+    _cleanup: Cleanup,
+}
+
+fn serve_connection<F>(stream: transport::Stream, token: &str, handler: &F) -> std::io::Result<()>
+where
+    F: Fn(IpcRequest) -> IpcResponse,
+{
+    transport::set_idle_timeout(&stream, SERVER_IDLE_TIMEOUT)?;
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+    while let Some(line) = read_line(&mut reader)? {
+        let response = match serde_json::from_str::<Envelope>(&line) {
+            Ok(env) if !ct_eq(env.token.as_bytes(), token.as_bytes()) => IpcResponse::Error {
+                message: "invalid token".into(),
+            },
+            Ok(Envelope {
+                request: IpcRequest::Ping,
+                ..
+            }) => IpcResponse::Pong,
+            Ok(env) => handler(env.request),
+            Err(e) => IpcResponse::Error {
+                message: format!("malformed request: {e}"),
+            },
+        };
+        let json = serde_json::to_string(&response).map_err(std::io::Error::other)?;
+        writeln!(writer, "{json}")?;
+    }
+    Ok(())
+}
+
+/// Read one line of at most [`MAX_LINE_BYTES`]; `None` at end of stream.
+fn read_line(reader: &mut impl BufRead) -> std::io::Result<Option<String>> {
+    let mut line = String::new();
+    let n = reader
+        .by_ref()
+        .take(MAX_LINE_BYTES as u64 + 1)
+        .read_line(&mut line)?;
+    if n > MAX_LINE_BYTES {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "line too long",
+        ));
+    }
+    Ok((n > 0).then_some(line))
+}
+
+// ── Client (CLI side) ──
+
+/// Connection to a running tray instance.
+pub struct IpcClient {
+    reader: BufReader<transport::Stream>,
+    writer: transport::Stream,
+    token: String,
+}
+
+impl IpcClient {
+    /// Connect to the tray described by `endpoint` and verify it answers a ping.
+    pub fn connect(endpoint: &IpcEndpoint) -> std::io::Result<Self> {
+        let stream = transport::connect(&endpoint.path, CLIENT_TIMEOUT)?;
+        let mut client = IpcClient {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+            token: endpoint.token.clone(),
+        };
+        match client.request(IpcRequest::Ping)? {
+            IpcResponse::Pong => Ok(client),
+            other => Err(std::io::Error::other(format!(
+                "unexpected ping reply: {other:?}"
+            ))),
+        }
+    }
+
+    /// Send a request and wait for the reply.
+    pub fn request(&mut self, request: IpcRequest) -> std::io::Result<IpcResponse> {
+        let env = Envelope {
+            token: self.token.clone(),
+            request,
+        };
+        let json = serde_json::to_string(&env).map_err(std::io::Error::other)?;
+        writeln!(self.writer, "{json}")?;
+        if self.reader.buffer().is_empty() {
+            transport::wait_readable(self.reader.get_ref(), CLIENT_TIMEOUT)?;
+        }
+        let line = read_line(&mut self.reader)?.ok_or(std::io::ErrorKind::UnexpectedEof)?;
+        serde_json::from_str(&line).map_err(std::io::Error::other)
+    }
+}
+
+/// Connect to the running tray, if any. Returns `None` when no endpoint file
+/// exists or the tray does not answer (e.g. a stale file after a crash).
+pub fn connect_tray_at(path: &Path) -> Option<IpcClient> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    let endpoint: IpcEndpoint = serde_json::from_str(&contents).ok()?;
+    match IpcClient::connect(&endpoint) {
+        Ok(c) => Some(c),
+        Err(e) => {
+            log::debug!(
+                "[ipc] tray endpoint {} not reachable: {e}",
+                endpoint.path.display()
+            );
+            None
+        }
+    }
+}
+
+/// Connect to the running tray using the default endpoint path.
+pub fn connect_tray() -> Option<IpcClient> {
+    connect_tray_at(&endpoint_path()?)
+}
+
+/// The running tray's device, driven over IPC: every call is forwarded to
+/// the tray, which performs it on the handle it owns.
+pub struct TrayDevice {
+    client: std::sync::Mutex<IpcClient>,
+    info: DeviceInfo,
+}
+
+impl TrayDevice {
+    /// Use the device of the tray behind `client`. Fails if the tray has
+    /// no device connected.
+    pub fn connect(client: IpcClient) -> crate::device::Result<Self> {
+        let client = std::sync::Mutex::new(client);
+        let info = match call(&client, IpcRequest::GetDeviceInfo)? {
+            IpcResponse::DeviceInfo {
+                mut info,
+                config_raw,
+                init_raw,
+            } => {
+                info.config_raw = config_raw;
+                info.init_raw = init_raw;
+                info
+            }
+            other => return Err(unexpected(other)),
+        };
+        Ok(TrayDevice { client, info })
+    }
+
+    fn request(&self, request: IpcRequest) -> crate::device::Result<IpcResponse> {
+        call(&self.client, request)
+    }
+}
+
+/// Send `request`, turning transport failures and error replies into
+/// device errors.
+fn call(
+    client: &std::sync::Mutex<IpcClient>,
+    request: IpcRequest,
+) -> crate::device::Result<IpcResponse> {
+    let mut client = client.lock().unwrap_or_else(|e| e.into_inner());
+    match client.request(request) {
+        Ok(IpcResponse::Error { message }) => {
+            Err(DeviceError::TransactFailed(format!("via tray: {message}")))
+        }
+        Ok(response) => Ok(response),
+        Err(e) => Err(DeviceError::TransactFailed(format!("via tray: {e}"))),
+    }
+}
+
+fn unexpected(response: IpcResponse) -> DeviceError {
+    DeviceError::TransactFailed(format!("via tray: unexpected reply {response:?}"))
+}
+
+impl ScarlettDevice for TrayDevice {
+    fn open() -> crate::device::Result<Self> {
+        Self::connect(connect_tray().ok_or(DeviceError::NotFound)?)
+    }
+
+    fn info(&self) -> &DeviceInfo {
+        &self.info
+    }
+
+    fn get_descriptor(&self, offset: u32, size: u32) -> crate::device::Result<Vec<u8>> {
+        match self.request(IpcRequest::GetDescriptor { offset, size })? {
+            IpcResponse::Descriptor { data } => Ok(data),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    fn set_descriptor(&self, offset: u32, data: &[u8]) -> crate::device::Result<()> {
+        let data = data.to_vec();
+        match self.request(IpcRequest::SetDescriptor { offset, data })? {
+            IpcResponse::Ok => Ok(()),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    fn data_notify(&self, event_id: u32) -> crate::device::Result<()> {
+        match self.request(IpcRequest::DataNotify { event_id })? {
+            IpcResponse::Ok => Ok(()),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    fn transact(
+        &self,
+        cmd: u32,
+        payload: &[u8],
+        out_size: usize,
+    ) -> crate::device::Result<Vec<u8>> {
+        let payload = payload.to_vec();
+        match self.request(IpcRequest::Transact {
+            command: cmd,
+            payload,
+            out_size,
+        })? {
+            IpcResponse::Transact { data } => Ok(data),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    fn reset(&self) -> crate::device::Result<()> {
+        match self.request(IpcRequest::ResetDevice)? {
+            IpcResponse::Ok => Ok(()),
+            other => Err(unexpected(other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A listen path no other test uses.
+    #[cfg(unix)]
+    fn listen_at(dir: &Path) -> PathBuf {
+        dir.join("focusmute.sock")
+    }
+
+    #[cfg(windows)]
+    fn listen_at(_dir: &Path) -> PathBuf {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        PathBuf::from(format!(
+            r"\\.\pipe\focusmute-test-{}-{}",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ))
+    }
+
+    fn start_server(dir: &Path) -> (IpcServerHandle, PathBuf) {
+        let path = dir.join("tray-ipc.json");
+        let mut server = IpcServer::bind_at(&listen_at(dir)).unwrap();
+        server.publish(&path).unwrap();
+        let handle = server
+            .spawn(|req| match req {
+                IpcRequest::GetDescriptor { offset, size } => IpcResponse::Descriptor {
+                    data: vec![offset as u8; size as usize],
+                },
+                IpcRequest::GetMuteState => IpcResponse::MuteState {
+                    muted: true,
+                    origin: MuteOrigin::External,
+                },
+                _ => IpcResponse::Ok,
+            })
+            .unwrap();
+        (handle, path)
+    }
+
+    #[test]
+    fn client_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let (_handle, path) = start_server(dir.path());
+
+        let mut client = connect_tray_at(&path).expect("tray should be reachable");
+        let resp = client
+            .request(IpcRequest::GetDescriptor { offset: 7, size: 3 })
+            .unwrap();
+        assert_eq!(resp, IpcResponse::Descriptor { data: vec![7; 3] });
+    }
+
+    #[test]
+    fn mute_state_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let (_handle, path) = start_server(dir.path());
+
+        let mut client = connect_tray_at(&path).unwrap();
+        assert_eq!(
+            client.request(IpcRequest::GetMuteState).unwrap(),
+            IpcResponse::MuteState {
+                muted: true,
+                origin: MuteOrigin::External
+            }
+        );
+        assert_eq!(
+            client
+                .request(IpcRequest::AnnounceMute { muted: false })
+                .unwrap(),
+            IpcResponse::Ok
+        );
+    }
+
+    #[test]
+    fn wrong_token_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let (_handle, path) = start_server(dir.path());
+
+        let mut endpoint: IpcEndpoint =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        endpoint.token = "nope".into();
+        assert!(IpcClient::connect(&endpoint).is_err());
+    }
+
+    #[test]
+    fn overlong_lines_are_rejected() {
+        let short = b"{}\n".to_vec();
+        assert_eq!(
+            read_line(&mut short.as_slice()).unwrap().as_deref(),
+            Some("{}\n")
+        );
+        assert_eq!(read_line(&mut &b""[..]).unwrap(), None);
+        let long = vec![b'x'; MAX_LINE_BYTES + 10];
+        let err = read_line(&mut long.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn tokens_are_random_and_256_bit() {
+        let a = new_token().unwrap();
+        let b = new_token().unwrap();
+        assert_eq!(a.len(), 64);
+        assert!(a.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(a, b);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn endpoint_file_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tray-ipc.json");
+        // A stale file with loose permissions is replaced, not reused
+        std::fs::write(&path, "stale").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        let mut server = IpcServer::bind_at(&listen_at(dir.path())).unwrap();
+        server.publish(&path).unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn socket_is_private_and_removed_on_drop() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().unwrap();
+        let socket = listen_at(dir.path());
+        // A socket left by a crashed tray doesn't stop the next one binding
+        std::fs::write(&socket, "").unwrap();
+        let (handle, path) = start_server(dir.path());
+        let mode = std::fs::metadata(&socket).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        let endpoint: IpcEndpoint =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(endpoint.path, socket);
+        drop(handle);
+        assert!(!socket.exists());
+    }
+
+    #[test]
+    fn concurrent_clients_are_served() {
+        let dir = tempfile::tempdir().unwrap();
+        let (_handle, path) = start_server(dir.path());
+
+        // An idle connection doesn't hold up the next client
+        let _idle = connect_tray_at(&path).unwrap();
+        let mut client = connect_tray_at(&path).unwrap();
+        assert_eq!(
+            client
+                .request(IpcRequest::AnnounceMute { muted: true })
+                .unwrap(),
+            IpcResponse::Ok
+        );
+    }
+
+    #[test]
+    fn missing_endpoint_file_means_no_tray() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(connect_tray_at(&dir.path().join("tray-ipc.json")).is_none());
+    }
+
+    #[test]
+    fn endpoint_file_removed_on_drop() {
+        let dir = tempfile::tempdir().unwrap();
+        let (handle, path) = start_server(dir.path());
+        assert!(path.exists());
+        drop(handle);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn tray_device_forwards_device_calls() {
+        use crate::device::mock::MockDevice;
+        use std::sync::{Arc, Mutex};
+
+        let mut mock = MockDevice::new();
+        mock.info_mut().device_name = "Scarlett 2i2 4th Gen-00031337".into();
+        let mock = Arc::new(Mutex::new(mock));
+        let tray = Arc::clone(&mock);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tray-ipc.json");
+        let mut server = IpcServer::bind_at(&listen_at(dir.path())).unwrap();
+        server.publish(&path).unwrap();
+        let _handle = server
+            .spawn(move |req| {
+                let dev = tray.lock().unwrap();
+                let done = |r: crate::device::Result<()>| match r {
+                    Ok(()) => IpcResponse::Ok,
+                    Err(e) => IpcResponse::Error {
+                        message: e.to_string(),
+                    },
+                };
+                match req {
+                    IpcRequest::GetDeviceInfo => IpcResponse::DeviceInfo {
+                        info: dev.info().clone(),
+                        config_raw: vec![7],
+                        init_raw: Vec::new(),
+                    },
+                    IpcRequest::GetDescriptor { offset, size } => IpcResponse::Descriptor {
+                        data: dev.get_descriptor(offset, size).unwrap(),
+                    },
+                    IpcRequest::SetDescriptor { offset, data } => {
+                        done(dev.set_descriptor(offset, &data))
+                    }
+                    IpcRequest::DataNotify { event_id } => done(dev.data_notify(event_id)),
+                    _ => IpcResponse::Error {
+                        message: "not supported here".into(),
+                    },
+                }
+            })
+            .unwrap();
+
+        let device = TrayDevice::connect(connect_tray_at(&path).unwrap()).unwrap();
+        assert_eq!(device.info().model(), "Scarlett 2i2 4th Gen");
+        assert_eq!(device.info().config_raw, [7]);
+        device.set_descriptor(40, &[1, 2]).unwrap();
+        device.data_notify(5).unwrap();
+        assert_eq!(device.get_descriptor(40, 2).unwrap(), [1, 2]);
+        assert_eq!(*mock.lock().unwrap().notifies.borrow(), [5]);
+        let err = device.reset().unwrap_err().to_string();
+        assert!(err.contains("via tray: not supported here"), "{err}");
+    }
+
+    #[test]
+    fn request_json_format() {
+        let json =
+            serde_json::to_string(&IpcRequest::GetDescriptor { offset: 1, size: 2 }).unwrap();
+        assert_eq!(json, r#"{"cmd":"get_descriptor","offset":1,"size":2}"#);
+        let json = serde_json::to_string(&IpcRequest::Simulate {
+            state: SimulatedState::Disconnect,
+        })
+        .unwrap();
+        assert_eq!(json, r#"{"cmd":"simulate","state":"disconnect"}"#);
+        let json = serde_json::to_string(&IpcRequest::SetMute {
+            command: RemoteCommand::Toggle,
+        })
+        .unwrap();
+        assert_eq!(json, r#"{"cmd":"set_mute","command":"toggle"}"#);
+        let json = serde_json::to_string(&IpcRequest::ReloadConfig).unwrap();
+        assert_eq!(json, r#"{"cmd":"reload_config"}"#);
+    }
+}
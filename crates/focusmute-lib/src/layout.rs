@@ -10,9 +10,14 @@
 //! - Input halos: `max_inputs × 8 LEDs/input` (1 number indicator + 7 halo segments)
 //! - Output halo: `metering_segments - (max_inputs × 7)` segments
 //! - Buttons: remaining LEDs after all halos
+//!
+//! Rack units (16i16 / 18i16 / 18i20) have no per-input halo rings: each input
+//! is predicted as a single number/select LED at indices `0..max_inputs`, with
+//! no output halo, and every remaining LED is an unlabeled front-panel LED.
 
 use serde::{Deserialize, Serialize};
 
+use crate::models;
 use crate::schema::SchemaConstants;
 
 /// Halo ring segments per input — hardware constant across all Scarlett 4th Gen.
@@ -21,6 +26,10 @@ pub const HALO_SEGMENTS_PER_INPUT: usize = 7;
 /// LEDs per input: 1 number indicator + 7 halo segments.
 pub const LEDS_PER_INPUT: usize = 1 + HALO_SEGMENTS_PER_INPUT;
 
+/// Most inputs a desktop (halo-ring) unit has. Schemas with more inputs are
+/// treated as rack units even if the product name is not recognized.
+pub const MAX_HALO_INPUTS: usize = 4;
+
 /// Confidence level for a predicted LED label.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Confidence {
@@ -84,7 +93,6 @@ pub struct PredictedLayout {
 const MEDIUM_CONFIDENCE_COUNT: usize = 9;
 
 fn known_button_labels() -> Vec<(&'static str, Confidence)> {
-    use crate::models;
     let profile = models::detect_model("Scarlett 2i2 4th Gen")
         .expect("2i2 profile must exist for known_button_labels");
     profile
@@ -102,10 +110,19 @@ fn known_button_labels() -> Vec<(&'static str, Confidence)> {
         .collect()
 }
 
+/// Whether `schema` describes a rack unit (no per-input halo rings).
+pub fn is_rack_layout(schema: &SchemaConstants) -> bool {
+    models::is_rack_model(&schema.product_name) || schema.max_inputs > MAX_HALO_INPUTS
+}
+
 /// Predict the LED layout from schema constants.
 ///
 /// Returns an error if the computed halo layout exceeds the total LED count.
 pub fn predict_layout(schema: &SchemaConstants) -> crate::error::Result<PredictedLayout> {
+    if is_rack_layout(schema) {
+        return predict_rack_layout(schema);
+    }
+
     let total_leds = schema.max_leds;
     let input_count = schema.max_inputs;
     let total_input_leds = input_count * LEDS_PER_INPUT;
@@ -193,6 +210,45 @@ pub fn predict_layout(schema: &SchemaConstants) -> crate::error::Result<Predicte
     })
 }
 
+/// Rack-unit prediction: one number/select LED per input, no halos.
+fn predict_rack_layout(schema: &SchemaConstants) -> crate::error::Result<PredictedLayout> {
+    let total_leds = schema.max_leds;
+    let input_count = schema.max_inputs;
+    if input_count > total_leds {
+        return Err(crate::FocusmuteError::Layout(format!(
+            "input number LEDs ({input_count}) exceed total LEDs ({total_leds})",
+        )));
+    }
+
+    let mut leds = Vec::with_capacity(total_leds);
+    for input_idx in 0..input_count {
+        leds.push(PredictedLed {
+            index: input_idx,
+            label: format!("Input {} — \"{}\" number", input_idx + 1, input_idx + 1),
+            confidence: Confidence::Medium,
+            zone: LedZone::InputNumber,
+        });
+    }
+    for index in input_count..total_leds {
+        leds.push(PredictedLed {
+            index,
+            label: format!("Front panel LED {}", index - input_count + 1),
+            confidence: Confidence::Low,
+            zone: LedZone::Button,
+        });
+    }
+
+    Ok(PredictedLayout {
+        product_name: schema.product_name.clone(),
+        total_leds,
+        input_count,
+        output_halo_segments: 0,
+        first_button_index: input_count,
+        button_count: total_leds - input_count,
+        leds,
+    })
+}
+
 /// Infer button labels based on the button count and available schema controls.
 fn infer_button_labels(
     button_count: usize,
@@ -281,25 +337,35 @@ pub fn generate_model_profile_code(layout: &PredictedLayout) -> String {
 
     let mut code = String::new();
 
+    // Rack layouts have no halo segments: one LED per input.
+    let halo_segments = layout
+        .leds
+        .iter()
+        .filter(|l| l.zone == LedZone::InputHalo)
+        .count()
+        .checked_div(layout.input_count)
+        .unwrap_or(0);
+    let leds_per_input = 1 + halo_segments;
+
     // Input halos array
     code.push_str(&format!(
         "static {ident}_INPUT_HALOS: [HaloRange; {}] = [\n",
         layout.input_count
     ));
     for i in 0..layout.input_count {
-        let base = i * LEDS_PER_INPUT;
+        let base = i * leds_per_input;
         code.push_str(&format!(
             "    HaloRange {{ number_led: {}, segments: {}..{} }},  // Input {}\n",
             base,
             base + 1,
-            base + 1 + HALO_SEGMENTS_PER_INPUT,
+            base + 1 + halo_segments,
             i + 1,
         ));
     }
     code.push_str("];\n\n");
 
     // ModelProfile
-    let output_start = layout.input_count * LEDS_PER_INPUT;
+    let output_start = layout.input_count * leds_per_input;
     let output_end = output_start + layout.output_halo_segments;
     code.push_str(&format!("static {ident}: ModelProfile = ModelProfile {{\n"));
     code.push_str(&format!("    name: \"{}\",\n", layout.product_name));
@@ -310,6 +376,7 @@ pub fn generate_model_profile_code(layout: &PredictedLayout) -> String {
         "    output_halo_segments: {output_start}..{output_end},\n"
     ));
     code.push_str("    button_labels: &[], // TODO: fill in button labels\n");
+    code.push_str("    strategy_presets: &[], // optional named mute presets\n");
     code.push_str("};\n");
 
     code
@@ -381,6 +448,18 @@ mod tests {
             ],
             app_space_features: vec!["directMonitoring".into(), "selectedInput".into()],
             firmware_version: "2.0.2417.0".into(),
+            phantom_power: None,
+            air: None,
+            inst_input: None,
+            preamp_gain: None,
+            clip_safe: None,
+            autogain: None,
+            autogain_status_offset: None,
+            sample_rate_offset: None,
+            sync_source_offset: None,
+            direct_monitoring_offset: None,
+            alt_speakers: None,
+            output_volume: None,
         }
     }
 
@@ -446,6 +525,18 @@ mod tests {
             input_controls: vec!["air".into(), "instrument".into()],
             app_space_features: vec!["directMonitoring".into()],
             firmware_version: String::new(),
+            phantom_power: None,
+            air: None,
+            inst_input: None,
+            preamp_gain: None,
+            clip_safe: None,
+            autogain: None,
+            autogain_status_offset: None,
+            sample_rate_offset: None,
+            sync_source_offset: None,
+            direct_monitoring_offset: None,
+            alt_speakers: None,
+            output_volume: None,
         };
         let layout = predict_layout(&schema).unwrap();
         assert_eq!(layout.input_count, 4);
@@ -479,6 +570,18 @@ mod tests {
             input_controls: vec!["air".into(), "instrument".into()],
             app_space_features: vec![],
             firmware_version: String::new(),
+            phantom_power: None,
+            air: None,
+            inst_input: None,
+            preamp_gain: None,
+            clip_safe: None,
+            autogain: None,
+            autogain_status_offset: None,
+            sample_rate_offset: None,
+            sync_source_offset: None,
+            direct_monitoring_offset: None,
+            alt_speakers: None,
+            output_volume: None,
         };
         let layout = predict_layout(&schema).unwrap();
         assert_eq!(layout.input_count, 1);
@@ -508,6 +611,18 @@ mod tests {
             input_controls: vec![],
             app_space_features: vec![],
             firmware_version: String::new(),
+            phantom_power: None,
+            air: None,
+            inst_input: None,
+            preamp_gain: None,
+            clip_safe: None,
+            autogain: None,
+            autogain_status_offset: None,
+            sample_rate_offset: None,
+            sync_source_offset: None,
+            direct_monitoring_offset: None,
+            alt_speakers: None,
+            output_volume: None,
         };
         let layout = predict_layout(&schema).unwrap();
         assert_eq!(layout.output_halo_segments, 11); // gradient_count fallback
@@ -531,6 +646,18 @@ mod tests {
             input_controls: vec![],
             app_space_features: vec![],
             firmware_version: String::new(),
+            phantom_power: None,
+            air: None,
+            inst_input: None,
+            preamp_gain: None,
+            clip_safe: None,
+            autogain: None,
+            autogain_status_offset: None,
+            sample_rate_offset: None,
+            sync_source_offset: None,
+            direct_monitoring_offset: None,
+            alt_speakers: None,
+            output_volume: None,
         };
         let result = predict_layout(&schema);
         assert!(result.is_err());
@@ -558,6 +685,18 @@ mod tests {
             input_controls: vec![],
             app_space_features: vec![],
             firmware_version: String::new(),
+            phantom_power: None,
+            air: None,
+            inst_input: None,
+            preamp_gain: None,
+            clip_safe: None,
+            autogain: None,
+            autogain_status_offset: None,
+            sample_rate_offset: None,
+            sync_source_offset: None,
+            direct_monitoring_offset: None,
+            alt_speakers: None,
+            output_volume: None,
         };
         let result = predict_layout(&schema);
         assert!(result.is_err());
@@ -585,6 +724,18 @@ mod tests {
             input_controls: vec![],
             app_space_features: vec![],
             firmware_version: String::new(),
+            phantom_power: None,
+            air: None,
+            inst_input: None,
+            preamp_gain: None,
+            clip_safe: None,
+            autogain: None,
+            autogain_status_offset: None,
+            sample_rate_offset: None,
+            sync_source_offset: None,
+            direct_monitoring_offset: None,
+            alt_speakers: None,
+            output_volume: None,
         };
         let layout = predict_layout(&schema).unwrap();
         // With no control info, button labels fall back to known_button_labels()
@@ -647,6 +798,80 @@ mod tests {
         assert_eq!(labels[2].0, "LED 2");
     }
 
+    fn schema_18i20() -> SchemaConstants {
+        SchemaConstants {
+            product_name: "Scarlett 18i20 4th Gen".into(),
+            max_leds: 60,
+            max_inputs: 8,
+            max_outputs: 10,
+            gradient_count: 11,
+            gradient_offset: 384,
+            gradient_notify: 9,
+            direct_led_count: 60,
+            direct_led_offset: 92,
+            metering_segments: 0,
+            input_controls: vec!["phantom".into(), "air".into()],
+            app_space_features: vec!["selectedInput".into()],
+            firmware_version: String::new(),
+            phantom_power: None,
+            air: None,
+            inst_input: None,
+            preamp_gain: None,
+            clip_safe: None,
+            autogain: None,
+            autogain_status_offset: None,
+            sample_rate_offset: None,
+            sync_source_offset: None,
+            direct_monitoring_offset: None,
+            alt_speakers: None,
+            output_volume: None,
+        }
+    }
+
+    #[test]
+    fn predict_rack_one_number_led_per_input() {
+        let layout = predict_layout(&schema_18i20()).unwrap();
+        assert_eq!(layout.input_count, 8);
+        assert_eq!(layout.output_halo_segments, 0);
+        assert_eq!(layout.first_button_index, 8);
+        assert_eq!(layout.button_count, 52);
+        assert_eq!(layout.leds.len(), 60);
+        for i in 0..8 {
+            assert_eq!(layout.leds[i].zone, LedZone::InputNumber);
+            assert_eq!(layout.leds[i].index, i);
+        }
+        assert!(layout.leds.iter().all(|l| l.zone != LedZone::InputHalo));
+        assert_eq!(layout.leds[8].label, "Front panel LED 1");
+        assert_eq!(layout.leds[8].confidence, Confidence::Low);
+    }
+
+    #[test]
+    fn predict_unknown_many_inputs_uses_rack_layout() {
+        let schema = SchemaConstants {
+            product_name: "Scarlett 24i24 5th Gen".into(),
+            ..schema_18i20()
+        };
+        assert!(is_rack_layout(&schema));
+        assert_eq!(predict_layout(&schema).unwrap().first_button_index, 8);
+    }
+
+    #[test]
+    fn predict_rack_too_few_leds_returns_error() {
+        let schema = SchemaConstants {
+            max_leds: 4,
+            ..schema_18i20()
+        };
+        assert!(predict_layout(&schema).is_err());
+    }
+
+    #[test]
+    fn generate_code_rack_has_empty_halos() {
+        let layout = predict_layout(&schema_18i20()).unwrap();
+        let code = generate_model_profile_code(&layout);
+        assert!(code.contains("HaloRange { number_led: 7, segments: 8..8 }"));
+        assert!(code.contains("output_halo_segments: 8..8"));
+    }
+
     #[test]
     fn predict_zero_buttons() {
         // Exactly as many LEDs as halos — no buttons
@@ -664,6 +889,18 @@ mod tests {
             input_controls: vec![],
             app_space_features: vec![],
             firmware_version: String::new(),
+            phantom_power: None,
+            air: None,
+            inst_input: None,
+            preamp_gain: None,
+            clip_safe: None,
+            autogain: None,
+            autogain_status_offset: None,
+            sample_rate_offset: None,
+            sync_source_offset: None,
+            direct_monitoring_offset: None,
+            alt_speakers: None,
+            output_volume: None,
         };
         let layout = predict_layout(&schema).unwrap();
         assert_eq!(layout.button_count, 0);
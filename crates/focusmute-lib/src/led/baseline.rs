@@ -0,0 +1,270 @@
+//! LED baseline — the device's own LED colors, saved before the first mute
+//! and put back exactly on unmute and exit.
+//!
+//! The generic restore repaints number LEDs with the stock selected /
+//! unselected colors, which clobbers a custom Focusrite Control 2 setup.
+//! [`LedBaseline`] reads `directLEDValues` and the `LEDcolors` gradient once,
+//! persists them, and the restore commands use those colors instead. The file
+//! outlives a crash, so the next start restores the colors from before the
+//! mute rather than capturing the leftover mute indicator.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::context::DeviceContext;
+use crate::descriptor_batch::DescriptorBatch;
+use crate::device::{Result, ScarlettDevice};
+use crate::schema::SchemaConstants;
+
+/// LED colors (`0xRRGGBB00`) as the device showed them before any mute.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LedBaseline {
+    /// Serial of the device this was read from; a baseline is only reused
+    /// on the same unit.
+    pub serial: Option<String>,
+    /// `directLEDValues`, one color per LED.
+    pub direct_values: Vec<u32>,
+    /// `LEDcolors` metering gradient — empty without the firmware schema.
+    pub gradient: Vec<u32>,
+}
+
+fn read_colors(device: &impl ScarlettDevice, offset: u32, count: usize) -> Result<Vec<u32>> {
+    let data = device.get_descriptor(offset, (count * 4) as u32)?;
+    Ok(data
+        .chunks_exact(4)
+        .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+        .collect())
+}
+
+fn color_bytes(colors: &[u32]) -> Vec<u8> {
+    colors.iter().flat_map(|c| c.to_le_bytes()).collect()
+}
+
+impl LedBaseline {
+    /// Read the current LED state from the device.
+    pub fn capture(device: &impl ScarlettDevice, ctx: &DeviceContext) -> Result<Self> {
+        let gradient = match ctx.schema.as_ref() {
+            Some(sc) => read_colors(device, sc.gradient_offset, sc.gradient_count)?,
+            None => Vec::new(),
+        };
+        Ok(LedBaseline {
+            serial: device.info().serial.clone(),
+            direct_values: read_colors(
+                device,
+                ctx.offsets.direct_led_values,
+                ctx.offsets.direct_led_count,
+            )?,
+            gradient,
+        })
+    }
+
+    /// Default file the baseline is persisted to.
+    pub fn path() -> Option<PathBuf> {
+        Config::dir().map(|d| d.join(crate::instance::scoped("led_baseline.json")))
+    }
+
+    /// Load a persisted baseline; `None` if missing or unreadable.
+    pub fn load_from(path: &Path) -> Option<Self> {
+        let contents = std::fs::read_to_string(path).ok()?;
+        match serde_json::from_str(&contents) {
+            Ok(baseline) => Some(baseline),
+            Err(e) => {
+                log::warn!(
+                    "[led] ignoring unreadable LED baseline {}: {e}",
+                    path.display()
+                );
+                None
+            }
+        }
+    }
+
+    pub fn save_to(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let contents = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, contents)
+    }
+
+    /// Delete the persisted baseline after a clean restore, so the next start
+    /// reads the (possibly reconfigured) device afresh.
+    pub fn remove_saved(path: &Path) {
+        if let Err(e) = std::fs::remove_file(path)
+            && e.kind() != std::io::ErrorKind::NotFound
+        {
+            log::warn!("[led] could not remove {}: {e}", path.display());
+        }
+    }
+
+    /// Reuse the baseline persisted at `path` if it belongs to this device —
+    /// it predates a mute an unclean exit left on the LEDs — otherwise
+    /// capture a new one and persist it. `None` keeps it in memory only.
+    pub fn load_or_capture(
+        device: &impl ScarlettDevice,
+        ctx: &DeviceContext,
+        path: Option<&Path>,
+    ) -> Result<Self> {
+        if let Some(saved) = path.and_then(Self::load_from)
+            && saved.serial == device.info().serial
+            && saved.direct_values.len() == ctx.offsets.direct_led_count
+        {
+            log::info!("[led] restoring the LED baseline saved before the last exit");
+            return Ok(saved);
+        }
+        let baseline = Self::capture(device, ctx)?;
+        if let Some(path) = path
+            && let Err(e) = baseline.save_to(path)
+        {
+            log::warn!("[led] could not save the LED baseline: {e}");
+        }
+        Ok(baseline)
+    }
+
+    /// Saved color of the LED at `index`.
+    pub fn color(&self, index: u8) -> Option<u32> {
+        self.direct_values.get(usize::from(index)).copied()
+    }
+
+    /// Write the saved metering gradient back if the device's differs.
+    /// Returns whether anything was written.
+    pub fn restore_gradient(
+        &self,
+        device: &impl ScarlettDevice,
+        schema: Option<&SchemaConstants>,
+    ) -> Result<bool> {
+        let Some(sc) = schema else {
+            return Ok(false);
+        };
+        if self.gradient.is_empty() || self.gradient.len() != sc.gradient_count {
+            return Ok(false);
+        }
+        if read_colors(device, sc.gradient_offset, sc.gradient_count)? == self.gradient {
+            return Ok(false);
+        }
+        DescriptorBatch::new()
+            .write(sc.gradient_offset, &color_bytes(&self.gradient))
+            .notify(sc.gradient_notify)
+            .commit(device)?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device::mock::MockDevice;
+    use crate::offsets::DeviceOffsets;
+
+    fn ctx() -> DeviceContext {
+        let profile = crate::models::detect_model("Scarlett 2i2 4th Gen").unwrap();
+        DeviceContext {
+            profile: Some(profile),
+            schema: None,
+            offsets: DeviceOffsets::for_profile(profile),
+            predicted: None,
+        }
+    }
+
+    fn schema() -> SchemaConstants {
+        SchemaConstants {
+            product_name: "Scarlett 2i2 4th Gen".into(),
+            max_leds: 40,
+            max_inputs: 2,
+            max_outputs: 2,
+            gradient_count: 2,
+            gradient_offset: 384,
+            gradient_notify: 9,
+            direct_led_count: 40,
+            direct_led_offset: 92,
+            metering_segments: 25,
+            input_controls: vec![],
+            app_space_features: vec![],
+            firmware_version: String::new(),
+            phantom_power: None,
+            air: None,
+            inst_input: None,
+            preamp_gain: None,
+            clip_safe: None,
+            autogain: None,
+            autogain_status_offset: None,
+            sample_rate_offset: None,
+            sync_source_offset: None,
+            direct_monitoring_offset: None,
+            alt_speakers: None,
+            output_volume: None,
+        }
+    }
+
+    #[test]
+    fn capture_reads_direct_values() {
+        let dev = MockDevice::new();
+        let ctx = ctx();
+        dev.set_descriptor(
+            ctx.offsets.direct_led_values + 8 * 4,
+            &0x00FF_0000u32.to_le_bytes(),
+        )
+        .unwrap();
+        let baseline = LedBaseline::capture(&dev, &ctx).unwrap();
+        assert_eq!(baseline.direct_values.len(), ctx.offsets.direct_led_count);
+        assert_eq!(baseline.color(8), Some(0x00FF_0000));
+        assert!(baseline.gradient.is_empty());
+    }
+
+    #[test]
+    fn load_or_capture_prefers_saved_baseline_for_same_device() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("led_baseline.json");
+        let dev = MockDevice::new();
+        let ctx = ctx();
+
+        let first = LedBaseline::load_or_capture(&dev, &ctx, Some(&path)).unwrap();
+        assert!(path.exists());
+
+        // The LEDs changed (e.g. a mute left on by a crash) — the saved copy wins
+        dev.set_descriptor(ctx.offsets.direct_led_values, &0xFF00_0000u32.to_le_bytes())
+            .unwrap();
+        let again = LedBaseline::load_or_capture(&dev, &ctx, Some(&path)).unwrap();
+        assert_eq!(again, first);
+
+        LedBaseline::remove_saved(&path);
+        let fresh = LedBaseline::load_or_capture(&dev, &ctx, Some(&path)).unwrap();
+        assert_eq!(fresh.color(0), Some(0xFF00_0000));
+    }
+
+    #[test]
+    fn saved_baseline_from_another_unit_is_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("led_baseline.json");
+        let dev = MockDevice::new();
+        let ctx = ctx();
+        let other = LedBaseline {
+            serial: Some("OTHER".into()),
+            direct_values: vec![0x1234_5600; ctx.offsets.direct_led_count],
+            gradient: Vec::new(),
+        };
+        other.save_to(&path).unwrap();
+        let baseline = LedBaseline::load_or_capture(&dev, &ctx, Some(&path)).unwrap();
+        assert_eq!(baseline.color(0), Some(0));
+    }
+
+    #[test]
+    fn restore_gradient_writes_only_changes() {
+        let dev = MockDevice::new();
+        let sc = schema();
+        let baseline = LedBaseline {
+            serial: None,
+            direct_values: Vec::new(),
+            gradient: vec![0x00FF_0000, 0xFF00_0000],
+        };
+        assert!(baseline.restore_gradient(&dev, Some(&sc)).unwrap());
+        assert_eq!(
+            dev.get_descriptor(sc.gradient_offset, 8).unwrap(),
+            color_bytes(&baseline.gradient)
+        );
+        assert_eq!(*dev.notifies.borrow(), vec![sc.gradient_notify]);
+        assert!(!baseline.restore_gradient(&dev, Some(&sc)).unwrap());
+        assert!(!baseline.restore_gradient(&dev, None).unwrap());
+    }
+}
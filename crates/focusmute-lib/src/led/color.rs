@@ -36,6 +36,59 @@ pub fn parse_color(s: &str) -> crate::error::Result<u32> {
     Ok(val << 8) // shift to 0xRRGGBB00
 }
 
+/// A named set of indicator colors (`color_preset` setting), so users don't
+/// have to hand-pick hex values they may not be able to tell apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColorPreset {
+    /// Config value, e.g. `"deuteranopia"`.
+    pub name: &'static str,
+    /// Shown in the settings dialog.
+    pub label: &'static str,
+    /// Mute indicator color.
+    pub mute: u32,
+    /// Mute indicator color when another app muted the mic.
+    pub external_mute: u32,
+    /// Busylight color while live.
+    pub live: u32,
+}
+
+/// Built-in color presets.
+///
+/// `deuteranopia` keeps muted and live on the orange–blue axis, which
+/// red–green color blindness leaves intact (colors from the Okabe–Ito
+/// palette).
+pub const COLOR_PRESETS: &[ColorPreset] = &[
+    ColorPreset {
+        name: "deuteranopia",
+        label: "Colorblind-friendly (vermillion / blue)",
+        mute: 0xD55E_0000,
+        external_mute: 0xCC79_A700,
+        live: 0x0072_B200,
+    },
+    ColorPreset {
+        name: "high-contrast",
+        label: "High contrast (red / yellow / white)",
+        mute: 0xFF00_0000,
+        external_mute: 0xFFFF_0000,
+        live: 0xFFFF_FF00,
+    },
+    ColorPreset {
+        name: "streamer-purple",
+        label: "Streamer purple",
+        mute: 0x9146_FF00,
+        external_mute: 0xFF00_FF00,
+        live: 0x00FF_FF00,
+    },
+];
+
+/// Look up a color preset by name (case-insensitive).
+pub fn find_color_preset(name: &str) -> Option<&'static ColorPreset> {
+    let name = name.trim();
+    COLOR_PRESETS
+        .iter()
+        .find(|p| p.name.eq_ignore_ascii_case(name))
+}
+
 /// Format a device color value as `#RRGGBB`.
 pub fn format_color(val: u32) -> String {
     let r = (val >> 24) & 0xFF;
@@ -128,6 +181,27 @@ mod tests {
         assert!(parse_color("#GGHHII").is_err());
     }
 
+    // ── color presets ──
+
+    #[test]
+    fn finds_color_presets() {
+        assert_eq!(
+            find_color_preset(" Deuteranopia ").map(|p| p.mute),
+            Some(0xD55E_0000)
+        );
+        assert!(find_color_preset("high-contrast").is_some());
+        assert!(find_color_preset("").is_none());
+        assert!(find_color_preset("rainbow").is_none());
+    }
+
+    #[test]
+    fn color_presets_keep_states_apart() {
+        for preset in COLOR_PRESETS {
+            assert_ne!(preset.mute, preset.live, "{}", preset.name);
+            assert_ne!(preset.mute, preset.external_mute, "{}", preset.name);
+        }
+    }
+
     // ── format_color ──
 
     #[test]
@@ -0,0 +1,507 @@
+//! LED commands — pure description of number-LED writes, plus the executor
+//! that performs them on a device.
+//!
+//! The mute indicator decides *what* the LEDs should show without touching
+//! the device; [`execute`] is the only place that turns commands into
+//! DATA_NOTIFY(8) writes. This keeps indicator policy unit-testable without a
+//! mock device and lets other front ends drive the writes themselves.
+
+use crate::device::{Result, ScarlettDevice};
+use crate::protocol;
+
+use super::baseline::LedBaseline;
+use super::ops::set_single_led;
+use super::strategy::MuteStrategy;
+
+/// A single number-LED write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LedCommand {
+    /// Set the LED at `index` to `color` (`0xRRGGBB00`).
+    Set { index: u8, color: u32 },
+    /// Restore the number LED of `input` to its firmware color — `selected_color`
+    /// if it is the device's currently selected input, `unselected_color` otherwise.
+    Restore {
+        input: usize,
+        index: u8,
+        selected_color: u32,
+        unselected_color: u32,
+    },
+}
+
+impl LedCommand {
+    /// The LED this command writes.
+    pub fn index(&self) -> u8 {
+        match *self {
+            LedCommand::Set { index, .. } | LedCommand::Restore { index, .. } => index,
+        }
+    }
+}
+
+/// Color `pattern` gives the LED at `index`, if any.
+fn pattern_color(pattern: &[(u8, u32)], index: u8) -> Option<u32> {
+    pattern.iter().find(|&&(i, _)| i == index).map(|&(_, c)| c)
+}
+
+/// Commands that paint a fixed LED pattern (see [`LedPatterns`](super::LedPatterns)).
+pub fn pattern_commands(pattern: &[(u8, u32)]) -> Vec<LedCommand> {
+    pattern
+        .iter()
+        .map(|&(index, color)| LedCommand::Set { index, color })
+        .collect()
+}
+
+/// Commands that show the mute indicator for `strategy`.
+///
+/// Per-input colors from `strategy.mute_colors` take precedence over `mute_color`,
+/// and a muted pattern's color over both. Group LEDs always use `mute_color`;
+/// the rest of the muted pattern is painted last.
+pub fn mute_commands(strategy: &MuteStrategy, mute_color: u32) -> Vec<LedCommand> {
+    let pattern = &strategy.patterns.muted;
+    let numbers = strategy
+        .number_leds
+        .iter()
+        .enumerate()
+        .map(|(i, &index)| LedCommand::Set {
+            index,
+            color: pattern_color(pattern, index)
+                .or_else(|| strategy.mute_colors.get(i).copied())
+                .unwrap_or(mute_color),
+        });
+    let group = strategy.group_leds.iter().map(|&index| LedCommand::Set {
+        index,
+        color: mute_color,
+    });
+    let extra = pattern
+        .iter()
+        .filter(|(index, _)| !strategy.number_leds.contains(index))
+        .map(|&(index, color)| LedCommand::Set { index, color });
+    numbers.chain(group).chain(extra).collect()
+}
+
+/// Commands that put the strategy's number LEDs back to their firmware
+/// colors and switch its group LEDs and any of `extra` off.
+fn reset_commands(strategy: &MuteStrategy, extra: impl Iterator<Item = u8>) -> Vec<LedCommand> {
+    let numbers = strategy
+        .input_indices
+        .iter()
+        .zip(strategy.number_leds.iter())
+        .map(|(&input, &index)| LedCommand::Restore {
+            input,
+            index,
+            selected_color: strategy.selected_color,
+            unselected_color: strategy.unselected_color,
+        });
+    let mut off: Vec<u8> = Vec::new();
+    for index in strategy.group_leds.iter().copied().chain(extra) {
+        if !strategy.number_leds.contains(&index) && !off.contains(&index) {
+            off.push(index);
+        }
+    }
+    numbers
+        .chain(
+            off.into_iter()
+                .map(|index| LedCommand::Set { index, color: 0 }),
+        )
+        .collect()
+}
+
+/// Commands that restore the number LEDs targeted by `strategy`, switch
+/// its group LEDs and muted pattern off, and paint the live pattern.
+pub fn restore_commands(strategy: &MuteStrategy) -> Vec<LedCommand> {
+    let live = &strategy.patterns.live;
+    let mut commands = reset_commands(strategy, strategy.patterns.muted.iter().map(|&(i, _)| i));
+    commands.retain(|c| pattern_color(live, c.index()).is_none());
+    commands.extend(pattern_commands(live));
+    commands
+}
+
+/// Commands that return every LED `strategy` may have painted in any state
+/// to normal — for shutdown, where the live pattern has to go too.
+pub fn exit_commands(strategy: &MuteStrategy) -> Vec<LedCommand> {
+    let p = &strategy.patterns;
+    let patterned = p.muted.iter().chain(&p.live).chain(&p.disconnected);
+    reset_commands(strategy, patterned.map(|&(i, _)| i))
+}
+
+/// Commands that take the LEDs from one mute indicator to another in a
+/// single pass, without clearing in between.
+///
+/// `shown` are the writes that painted the current indicator and `reset`
+/// the ones that would clear it. LEDs `target` paints are written once,
+/// straight to their new color — or not at all if `shown` already left
+/// them that way — and only LEDs `target` no longer paints are reset.
+pub fn transition_commands(
+    shown: &[LedCommand],
+    reset: &[LedCommand],
+    target: &[LedCommand],
+) -> Vec<LedCommand> {
+    let painted = |index: u8| target.iter().any(|c| c.index() == index);
+    reset
+        .iter()
+        .filter(|c| !painted(c.index()))
+        .chain(target.iter().filter(|c| !shown.contains(c)))
+        .copied()
+        .collect()
+}
+
+/// Like [`restore_commands`], but puts back the colors saved in `baseline`.
+///
+/// A number LED gets its saved color only if that was a custom color —
+/// stock selected / unselected colors still follow the currently selected
+/// input, and a saved mute color (a leftover from a crash) is never restored.
+/// Group LEDs get their saved color instead of switching off; the live
+/// pattern is painted as configured.
+pub fn restore_commands_from(
+    strategy: &MuteStrategy,
+    baseline: &LedBaseline,
+    mute_color: u32,
+) -> Vec<LedCommand> {
+    from_baseline(
+        restore_commands(strategy),
+        &strategy.patterns.live,
+        strategy,
+        baseline,
+        mute_color,
+    )
+}
+
+/// Like [`exit_commands`], but puts back the colors saved in `baseline`
+/// (see [`restore_commands_from`]).
+pub fn exit_commands_from(
+    strategy: &MuteStrategy,
+    baseline: &LedBaseline,
+    mute_color: u32,
+) -> Vec<LedCommand> {
+    from_baseline(exit_commands(strategy), &[], strategy, baseline, mute_color)
+}
+
+/// Swap reset commands for saved baseline colors, leaving the LEDs of
+/// `keep` alone.
+fn from_baseline(
+    commands: Vec<LedCommand>,
+    keep: &[(u8, u32)],
+    strategy: &MuteStrategy,
+    baseline: &LedBaseline,
+    mute_color: u32,
+) -> Vec<LedCommand> {
+    let is_mute_color = |c: u32| {
+        c == mute_color
+            || strategy.mute_colors.contains(&c)
+            || strategy.patterns.muted.iter().any(|&(_, p)| p == c)
+    };
+    commands
+        .into_iter()
+        .map(|command| match command {
+            _ if pattern_color(keep, command.index()).is_some() => command,
+            LedCommand::Restore {
+                index,
+                selected_color,
+                unselected_color,
+                ..
+            } => match baseline.color(index) {
+                Some(color)
+                    if color != 0
+                        && color != selected_color
+                        && color != unselected_color
+                        && !is_mute_color(color) =>
+                {
+                    LedCommand::Set { index, color }
+                }
+                _ => command,
+            },
+            LedCommand::Set { index, .. } => LedCommand::Set {
+                index,
+                color: baseline
+                    .color(index)
+                    .filter(|&c| !is_mute_color(c))
+                    .unwrap_or(0),
+            },
+        })
+        .collect()
+}
+
+/// Execute LED commands on the device, in order.
+///
+/// `selectedInput` is read once, before the first write, if any command is a
+/// [`LedCommand::Restore`].
+pub fn execute(device: &impl ScarlettDevice, commands: &[LedCommand]) -> Result<()> {
+    let needs_selected = commands
+        .iter()
+        .any(|c| matches!(c, LedCommand::Restore { .. }));
+    let selected_input = if needs_selected {
+        device
+            .get_descriptor(protocol::OFF_SELECTED_INPUT, 1)?
+            .first()
+            .copied()
+            .unwrap_or(0) as usize
+    } else {
+        0
+    };
+
+    for command in commands {
+        match *command {
+            LedCommand::Set { index, color } => set_single_led(device, index, color)?,
+            LedCommand::Restore {
+                input,
+                index,
+                selected_color,
+                unselected_color,
+            } => {
+                let color = if input == selected_input {
+                    selected_color
+                } else {
+                    unselected_color
+                };
+                set_single_led(device, index, color)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device::mock::MockDevice;
+    use crate::led::LedPatterns;
+    use crate::protocol::*;
+
+    fn strategy() -> MuteStrategy {
+        MuteStrategy {
+            input_indices: vec![0, 1],
+            number_leds: vec![0, 8],
+            mute_colors: vec![0x00FF_0000],
+            selected_color: 0x20FF_0000,
+            unselected_color: 0x88FF_FF00,
+            group_leds: Vec::new(),
+            patterns: LedPatterns::default(),
+        }
+    }
+
+    #[test]
+    fn mute_commands_fall_back_to_global_color() {
+        assert_eq!(
+            mute_commands(&strategy(), 0xFF00_0000),
+            vec![
+                LedCommand::Set {
+                    index: 0,
+                    color: 0x00FF_0000
+                },
+                LedCommand::Set {
+                    index: 8,
+                    color: 0xFF00_0000
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn restore_commands_cover_every_input() {
+        let cmds = restore_commands(&strategy());
+        assert_eq!(cmds.len(), 2);
+        assert_eq!(
+            cmds[1],
+            LedCommand::Restore {
+                input: 1,
+                index: 8,
+                selected_color: 0x20FF_0000,
+                unselected_color: 0x88FF_FF00,
+            }
+        );
+    }
+
+    #[test]
+    fn group_leds_lit_then_switched_off() {
+        let strategy = MuteStrategy {
+            group_leds: vec![16, 17],
+            ..strategy()
+        };
+        let mute = mute_commands(&strategy, 0xFF00_0000);
+        assert_eq!(mute.len(), 4);
+        assert_eq!(
+            mute[3],
+            LedCommand::Set {
+                index: 17,
+                color: 0xFF00_0000
+            }
+        );
+        let restore = restore_commands(&strategy);
+        assert_eq!(
+            restore[2],
+            LedCommand::Set {
+                index: 16,
+                color: 0
+            }
+        );
+    }
+
+    #[test]
+    fn restore_from_baseline_keeps_custom_colors() {
+        let strategy = MuteStrategy {
+            group_leds: vec![16, 17],
+            ..strategy()
+        };
+        let mut direct_values = vec![0; 40];
+        direct_values[0] = 0x0000_FF00; // custom blue "1"
+        direct_values[8] = 0x88FF_FF00; // stock unselected "2"
+        direct_values[16] = 0x1010_1000;
+        direct_values[17] = 0xFF00_0000; // leftover mute color
+        let baseline = LedBaseline {
+            serial: None,
+            direct_values,
+            gradient: Vec::new(),
+        };
+        let cmds = restore_commands_from(&strategy, &baseline, 0xFF00_0000);
+        assert_eq!(
+            cmds,
+            vec![
+                LedCommand::Set {
+                    index: 0,
+                    color: 0x0000_FF00
+                },
+                restore_commands(&strategy)[1],
+                LedCommand::Set {
+                    index: 16,
+                    color: 0x1010_1000
+                },
+                LedCommand::Set {
+                    index: 17,
+                    color: 0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn patterns_override_and_extend_the_indicator() {
+        let strategy = MuteStrategy {
+            patterns: LedPatterns {
+                muted: vec![(8, 0x0000_FF00), (30, 0xFF00_0000)],
+                live: vec![(30, 0x00FF_0000)],
+                disconnected: vec![(31, 0xFFFF_0000)],
+            },
+            ..strategy()
+        };
+        let mute = mute_commands(&strategy, 0xFF00_0000);
+        assert_eq!(
+            mute[1..],
+            [
+                LedCommand::Set {
+                    index: 8,
+                    color: 0x0000_FF00
+                },
+                LedCommand::Set {
+                    index: 30,
+                    color: 0xFF00_0000
+                },
+            ]
+        );
+
+        // LED 30 goes straight to its live color instead of off
+        let restore = restore_commands(&strategy);
+        assert_eq!(restore.len(), 3);
+        assert_eq!(
+            restore[2],
+            LedCommand::Set {
+                index: 30,
+                color: 0x00FF_0000
+            }
+        );
+
+        // On exit every patterned LED is switched off
+        let exit: Vec<u8> = exit_commands(&strategy)
+            .iter()
+            .map(LedCommand::index)
+            .collect();
+        assert_eq!(exit, vec![0, 8, 30, 31]);
+        assert!(matches!(
+            exit_commands(&strategy)[2],
+            LedCommand::Set { color: 0, .. }
+        ));
+    }
+
+    #[test]
+    fn transition_rewrites_only_changed_leds() {
+        let old = MuteStrategy {
+            group_leds: vec![16],
+            ..strategy()
+        };
+        let new = MuteStrategy {
+            input_indices: vec![1],
+            number_leds: vec![8],
+            mute_colors: Vec::new(),
+            ..strategy()
+        };
+        let shown = mute_commands(&old, 0xFF00_0000);
+        let cmds = transition_commands(
+            &shown,
+            &restore_commands(&old),
+            &mute_commands(&new, 0xFF00_0000),
+        );
+        // LED 8 already shows the mute color; input 1 and the group LED go back
+        assert_eq!(
+            cmds,
+            vec![
+                LedCommand::Restore {
+                    input: 0,
+                    index: 0,
+                    selected_color: 0x20FF_0000,
+                    unselected_color: 0x88FF_FF00,
+                },
+                LedCommand::Set {
+                    index: 16,
+                    color: 0
+                },
+            ]
+        );
+
+        // Same LEDs, new color — repainted once, never reset
+        let recolored = transition_commands(
+            &shown,
+            &restore_commands(&old),
+            &mute_commands(&old, 0x0000_FF00),
+        );
+        assert!(recolored.iter().all(|c| matches!(
+            c,
+            LedCommand::Set {
+                color: 0x0000_FF00,
+                ..
+            }
+        )));
+        assert_eq!(recolored.len(), 2);
+    }
+
+    #[test]
+    fn execute_restore_uses_selected_input() {
+        let dev = MockDevice::new();
+        dev.set_descriptor(OFF_SELECTED_INPUT, &[1]).unwrap();
+        execute(&dev, &restore_commands(&strategy())).unwrap();
+
+        // Input 2 (LED 8) is selected and written last
+        assert_eq!(
+            dev.get_descriptor(OFF_DIRECT_LED_INDEX, 1).unwrap(),
+            vec![8]
+        );
+        assert_eq!(
+            dev.get_descriptor(OFF_DIRECT_LED_COLOUR, 4).unwrap(),
+            0x20FF_0000u32.to_le_bytes().to_vec()
+        );
+    }
+
+    #[test]
+    fn execute_set_writes_each_led() {
+        let dev = MockDevice::new();
+        execute(&dev, &mute_commands(&strategy(), 0xFF00_0000)).unwrap();
+        assert_eq!(
+            dev.notifies.borrow().as_slice(),
+            &[NOTIFY_DIRECT_LED_COLOUR; 2]
+        );
+    }
+
+    #[test]
+    fn execute_empty_is_noop() {
+        let dev = MockDevice::new();
+        execute(&dev, &[]).unwrap();
+        assert!(dev.notifies.borrow().is_empty());
+    }
+}
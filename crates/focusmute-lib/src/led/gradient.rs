@@ -0,0 +1,138 @@
+//! Metering gradient — the `LEDcolors` table the halo meters are drawn with.
+//!
+//! The firmware schema gives the table's offset, length, and notify ID.
+//! Colors are `0xRRGGBB00`, lowest meter segment first. Writing it lets the
+//! halo meter be recolored without Focusrite Control 2.
+
+use crate::descriptor_batch::DescriptorBatch;
+use crate::device::{Result, ScarlettDevice};
+use crate::schema::SchemaConstants;
+
+/// Read the metering gradient from the device.
+pub fn get_gradient(device: &impl ScarlettDevice, schema: &SchemaConstants) -> Result<Vec<u32>> {
+    let data = device.get_descriptor(schema.gradient_offset, (schema.gradient_count * 4) as u32)?;
+    Ok(data
+        .chunks_exact(4)
+        .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+        .collect())
+}
+
+/// Write the metering gradient. `colors` is stretched over the whole table
+/// (see [`stretch_gradient`]), so a few stops are enough; an empty list
+/// writes nothing.
+pub fn set_gradient(
+    device: &impl ScarlettDevice,
+    schema: &SchemaConstants,
+    colors: &[u32],
+) -> Result<()> {
+    let table = stretch_gradient(colors, schema.gradient_count);
+    if table.is_empty() {
+        return Ok(());
+    }
+    let bytes: Vec<u8> = table.iter().flat_map(|c| c.to_le_bytes()).collect();
+    DescriptorBatch::new()
+        .write(schema.gradient_offset, &bytes)
+        .notify(schema.gradient_notify)
+        .commit(device)
+}
+
+/// `count` colors blending evenly from the first stop to the last.
+pub fn stretch_gradient(stops: &[u32], count: usize) -> Vec<u32> {
+    match stops {
+        [] => Vec::new(),
+        [only] => vec![*only; count],
+        _ => (0..count)
+            .map(|i| {
+                let last = (stops.len() - 1) as f64;
+                let pos = if count > 1 {
+                    i as f64 * last / (count - 1) as f64
+                } else {
+                    0.0
+                };
+                let lo = (pos.floor() as usize).min(stops.len() - 2);
+                blend(stops[lo], stops[lo + 1], pos - lo as f64)
+            })
+            .collect(),
+    }
+}
+
+/// `t` of the way from `a` to `b`, per channel.
+fn blend(a: u32, b: u32, t: f64) -> u32 {
+    [24, 16, 8].iter().fold(0, |out, &shift| {
+        let ca = f64::from((a >> shift) & 0xFF);
+        let cb = f64::from((b >> shift) & 0xFF);
+        out | (((ca + (cb - ca) * t).round() as u32) << shift)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device::mock::MockDevice;
+
+    fn schema() -> SchemaConstants {
+        SchemaConstants {
+            product_name: "Scarlett 2i2 4th Gen".into(),
+            max_leds: 40,
+            max_inputs: 2,
+            max_outputs: 2,
+            gradient_count: 5,
+            gradient_offset: 384,
+            gradient_notify: 9,
+            direct_led_count: 40,
+            direct_led_offset: 92,
+            metering_segments: 25,
+            input_controls: vec![],
+            app_space_features: vec![],
+            firmware_version: String::new(),
+            phantom_power: None,
+            air: None,
+            inst_input: None,
+            preamp_gain: None,
+            clip_safe: None,
+            autogain: None,
+            autogain_status_offset: None,
+            sample_rate_offset: None,
+            sync_source_offset: None,
+            direct_monitoring_offset: None,
+            alt_speakers: None,
+            output_volume: None,
+        }
+    }
+
+    #[test]
+    fn stretches_stops_over_the_table() {
+        assert_eq!(
+            stretch_gradient(&[0x00FF_0000, 0xFF00_0000], 5),
+            vec![
+                0x00FF_0000,
+                0x40BF_0000,
+                0x8080_0000,
+                0xBF40_0000,
+                0xFF00_0000
+            ]
+        );
+        assert_eq!(stretch_gradient(&[0x0000_FF00], 3), vec![0x0000_FF00; 3]);
+        assert!(stretch_gradient(&[], 3).is_empty());
+        // More stops than segments keeps the ends
+        assert_eq!(
+            stretch_gradient(&[1 << 8, 2 << 8, 3 << 8, 4 << 8], 2),
+            vec![1 << 8, 4 << 8]
+        );
+    }
+
+    #[test]
+    fn set_then_get_round_trips() {
+        let dev = MockDevice::new();
+        let sc = schema();
+        set_gradient(&dev, &sc, &[0x00FF_0000, 0xFF00_0000]).unwrap();
+        assert_eq!(
+            get_gradient(&dev, &sc).unwrap(),
+            stretch_gradient(&[0x00FF_0000, 0xFF00_0000], 5)
+        );
+        assert_eq!(*dev.notifies.borrow(), vec![sc.gradient_notify]);
+
+        set_gradient(&dev, &sc, &[]).unwrap();
+        assert_eq!(dev.notifies.borrow().len(), 1);
+    }
+}
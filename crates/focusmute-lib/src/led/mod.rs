@@ -1,12 +1,30 @@
-//! LED control — single-LED update, mute indicator apply/clear/restore.
+//! LED control — single-LED update, mute indicator apply/clear/restore,
+//! mute animations, the saved LED baseline, the metering gradient, and VU
+//! mode.
 
+mod baseline;
 mod color;
+mod command;
+mod gradient;
 mod ops;
 mod strategy;
+mod vu;
 
-pub use color::{format_color, parse_color};
+pub use baseline::LedBaseline;
+pub use color::{COLOR_PRESETS, ColorPreset, find_color_preset, format_color, parse_color};
+pub use command::{
+    LedCommand, execute, exit_commands, exit_commands_from, mute_commands, pattern_commands,
+    restore_commands, restore_commands_from, transition_commands,
+};
+pub use gradient::{get_gradient, set_gradient, stretch_gradient};
 pub use ops::{
-    apply_mute_indicator, clear_mute_indicator, refresh_after_reconnect, restore_on_exit,
-    set_single_led,
+    ANIMATION_FRAME, FULL_REFRESH_INTERVAL, LedWriteCache, MuteAnimation, StartupReconcile,
+    apply_mute_indicator, clear_mute_indicator, color_wheel, dim_color, flash_reconnect,
+    flash_resync, reconcile_on_startup, refresh_after_reconnect, restore_on_exit, set_single_led,
+    spawn_frame_ticker,
+};
+pub use strategy::{
+    LedPatterns, MuteStrategy, busylight_live_color, external_mute_color, in_use_color,
+    mute_color_or_default, parse_led_pattern, reconnect_flash_color, resolve_strategy_from_config,
 };
-pub use strategy::{MuteStrategy, mute_color_or_default, resolve_strategy_from_config};
+pub use vu::{DEFAULT_VU_GRADIENT, VU_FLOOR_DB, VU_FRAME, VuMeter, lit_segments};
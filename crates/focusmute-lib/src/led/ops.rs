@@ -1,8 +1,16 @@
 //! LED device operations — single-LED mute indicator apply/clear/restore.
 
+use std::collections::HashMap;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::descriptor_batch::DescriptorBatch;
 use crate::device::{Result, ScarlettDevice};
 use crate::protocol;
 
+use super::command::{
+    LedCommand, execute, exit_commands, mute_commands, pattern_commands, restore_commands,
+};
 use super::strategy::MuteStrategy;
 
 // ── Single-LED update (DATA_NOTIFY(8)) ──
@@ -13,11 +21,12 @@ use super::strategy::MuteStrategy;
 /// Works in mode 0 (normal metering mode) without any mode change.
 /// Metering continues unaffected on all halo ring segments.
 pub fn set_single_led(device: &impl ScarlettDevice, index: u8, color: u32) -> Result<()> {
-    // Ordering matters: colour must be written before index.
-    device.set_descriptor(protocol::OFF_DIRECT_LED_COLOUR, &color.to_le_bytes())?;
-    device.set_descriptor(protocol::OFF_DIRECT_LED_INDEX, &[index])?;
-    device.data_notify(protocol::NOTIFY_DIRECT_LED_COLOUR)?;
-    Ok(())
+    // Colour and index are adjacent, so this is one write plus the notify.
+    DescriptorBatch::new()
+        .write(protocol::OFF_DIRECT_LED_COLOUR, &color.to_le_bytes())
+        .write(protocol::OFF_DIRECT_LED_INDEX, &[index])
+        .notify(protocol::NOTIFY_DIRECT_LED_COLOUR)
+        .commit(device)
 }
 
 /// Restore number LEDs to their firmware-expected colors.
@@ -26,25 +35,7 @@ pub fn set_single_led(device: &impl ScarlettDevice, index: u8, color: u32) -> Re
 /// selected, then sets each number LED to the appropriate firmware color
 /// (green for selected, off for unselected) via DATA_NOTIFY(8).
 fn restore_number_leds(device: &impl ScarlettDevice, strategy: &MuteStrategy) -> Result<()> {
-    let selected_input = device
-        .get_descriptor(protocol::OFF_SELECTED_INPUT, 1)?
-        .first()
-        .copied()
-        .unwrap_or(0) as usize;
-
-    for (input_idx, &led_idx) in strategy
-        .input_indices
-        .iter()
-        .zip(strategy.number_leds.iter())
-    {
-        let color = if *input_idx == selected_input {
-            strategy.selected_color
-        } else {
-            strategy.unselected_color
-        };
-        set_single_led(device, led_idx, color)?;
-    }
-    Ok(())
+    execute(device, &restore_commands(strategy))
 }
 
 // ── Mute indicator operations ──
@@ -58,11 +49,7 @@ pub fn apply_mute_indicator(
     strategy: &MuteStrategy,
     mute_color: u32,
 ) -> Result<()> {
-    for (i, &led_idx) in strategy.number_leds.iter().enumerate() {
-        let color = strategy.mute_colors.get(i).copied().unwrap_or(mute_color);
-        set_single_led(device, led_idx, color)?;
-    }
-    Ok(())
+    execute(device, &mute_commands(strategy, mute_color))
 }
 
 /// Clear the mute indicator and restore normal LED state.
@@ -70,12 +57,14 @@ pub fn clear_mute_indicator(device: &impl ScarlettDevice, strategy: &MuteStrateg
     restore_number_leds(device, strategy)
 }
 
-/// Restore LED state on application exit.
+/// Restore LED state on application exit, including LEDs painted by the
+/// live or disconnected pattern.
 pub fn restore_on_exit(device: &impl ScarlettDevice, strategy: &MuteStrategy) -> Result<()> {
-    restore_number_leds(device, strategy)
+    execute(device, &exit_commands(strategy))
 }
 
-/// Re-apply mute indicator after reconnecting, if currently muted.
+/// Re-apply mute indicator after reconnecting, if currently muted — or the
+/// live pattern, if there is one.
 ///
 /// The caller is responsible for the `open_device()` call and logging —
 /// this extracts only the post-connect mute re-application.
@@ -87,14 +76,346 @@ pub fn refresh_after_reconnect(
 ) -> Result<()> {
     if is_muted {
         apply_mute_indicator(device, strategy, mute_color)?;
+    } else {
+        execute(device, &pattern_commands(&strategy.patterns.live))?;
+    }
+    Ok(())
+}
+
+// ── Write cache ──
+
+/// Shortest time between two full rewrites in [`LedWriteCache::refresh`].
+pub const FULL_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The colors last written to each LED, so a reconnect storm doesn't rewrite
+/// LEDs that already show the right color.
+///
+/// Only writes made through the cache are known, so it is for the refresh
+/// after a reconnect: the first refresh, and any refresh
+/// [`FULL_REFRESH_INTERVAL`] after the last full one, rewrites every LED;
+/// refreshes in between only write the LEDs whose color changed. Call
+/// [`invalidate`](Self::invalidate) when the LEDs may have been repainted
+/// behind its back (the device was unplugged, the mute state changed while
+/// disconnected).
+#[derive(Debug, Default)]
+pub struct LedWriteCache {
+    written: HashMap<u8, u32>,
+    last_full: Option<Instant>,
+}
+
+impl LedWriteCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget what was written; the next refresh rewrites every LED.
+    pub fn invalidate(&mut self) {
+        self.written.clear();
+        self.last_full = None;
+    }
+
+    /// Whether the next refresh at `now` rewrites every LED.
+    pub fn full_refresh_due(&self, now: Instant) -> bool {
+        self.last_full
+            .is_none_or(|t| now.duration_since(t) >= FULL_REFRESH_INTERVAL)
+    }
+
+    /// Execute `commands`, skipping `Set` writes of the color the LED was
+    /// last set to. Returns how many commands were executed. A failed write
+    /// forgets everything, since the device state is unknown.
+    pub fn execute(
+        &mut self,
+        device: &impl ScarlettDevice,
+        commands: &[LedCommand],
+    ) -> Result<usize> {
+        let pending: Vec<LedCommand> = commands
+            .iter()
+            .filter(|c| match **c {
+                LedCommand::Set { index, color } => self.written.get(&index) != Some(&color),
+                LedCommand::Restore { .. } => true,
+            })
+            .copied()
+            .collect();
+        if let Err(e) = execute(device, &pending) {
+            self.invalidate();
+            return Err(e);
+        }
+        for command in &pending {
+            match *command {
+                LedCommand::Set { index, color } => {
+                    self.written.insert(index, color);
+                }
+                // The restored color depends on the selected input
+                LedCommand::Restore { index, .. } => {
+                    self.written.remove(&index);
+                }
+            }
+        }
+        Ok(pending.len())
+    }
+
+    /// [`refresh_after_reconnect`] through the cache, rate-limited as
+    /// described on [`LedWriteCache`]. Returns how many LEDs were written.
+    pub fn refresh(
+        &mut self,
+        device: &impl ScarlettDevice,
+        strategy: &MuteStrategy,
+        mute_color: u32,
+        is_muted: bool,
+        now: Instant,
+    ) -> Result<usize> {
+        if self.full_refresh_due(now) {
+            self.written.clear();
+            self.last_full = Some(now);
+        }
+        let commands = if is_muted {
+            mute_commands(strategy, mute_color)
+        } else {
+            pattern_commands(&strategy.patterns.live)
+        };
+        self.execute(device, &commands)
+    }
+}
+
+/// Mute-color blinks in [`flash_resync`].
+const RESYNC_BLINKS: u32 = 2;
+
+/// How long each half of a [`flash_resync`] blink lasts.
+const RESYNC_BLINK_STEP: Duration = Duration::from_millis(120);
+
+/// Blink the mute indicator a couple of times, then show the state for
+/// `is_muted` — a reconnect catching up on mute changes it missed. Blocks
+/// for about half a second.
+pub fn flash_resync(
+    device: &impl ScarlettDevice,
+    strategy: &MuteStrategy,
+    mute_color: u32,
+    is_muted: bool,
+) -> Result<()> {
+    for _ in 0..RESYNC_BLINKS {
+        apply_mute_indicator(device, strategy, mute_color)?;
+        std::thread::sleep(RESYNC_BLINK_STEP);
+        clear_mute_indicator(device, strategy)?;
+        std::thread::sleep(RESYNC_BLINK_STEP);
+    }
+    refresh_after_reconnect(device, strategy, mute_color, is_muted)
+}
+
+/// Blink the number LEDs in `color` a couple of times and switch them off —
+/// confirmation that the device is back. The caller re-applies the mute
+/// state afterwards. Blocks for about half a second.
+pub fn flash_reconnect(
+    device: &impl ScarlettDevice,
+    strategy: &MuteStrategy,
+    color: u32,
+) -> Result<()> {
+    let on: Vec<LedCommand> = strategy
+        .number_leds
+        .iter()
+        .map(|&index| LedCommand::Set { index, color })
+        .collect();
+    let off: Vec<LedCommand> = strategy
+        .number_leds
+        .iter()
+        .map(|&index| LedCommand::Set { index, color: 0 })
+        .collect();
+    for _ in 0..RESYNC_BLINKS {
+        execute(device, &on)?;
+        std::thread::sleep(RESYNC_BLINK_STEP);
+        execute(device, &off)?;
+        std::thread::sleep(RESYNC_BLINK_STEP);
     }
     Ok(())
 }
 
+// ── Startup safety check ──
+
+/// What [`reconcile_on_startup`] found and fixed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StartupReconcile {
+    /// Device LED state already matched the OS mute state.
+    Consistent,
+    /// Mic is live but a number LED still showed a leftover mute color
+    /// (e.g. after a crash) — number LEDs were restored.
+    RestoredLeftover,
+    /// Mic is muted but the indicator was not showing — mute was re-applied.
+    ReappliedMute,
+}
+
+/// Read back the last single-LED write (`directLEDColour` + `directLEDIndex`).
+fn last_single_led(device: &impl ScarlettDevice) -> Result<(u8, u32)> {
+    let colour = device.get_descriptor(protocol::OFF_DIRECT_LED_COLOUR, 4)?;
+    let index = device.get_descriptor(protocol::OFF_DIRECT_LED_INDEX, 1)?;
+    let colour = colour
+        .get(..4)
+        .map(|b| u32::from_le_bytes(b.try_into().unwrap()))
+        .unwrap_or(0);
+    Ok((index.first().copied().unwrap_or(0), colour))
+}
+
+/// Reconcile device LEDs with the actual OS mute state at startup.
+///
+/// If a previous run exited uncleanly, the LEDs may still show the mute
+/// indicator while the mic is live, or only part of it while muted. Only the
+/// last single-LED write can be read back from the descriptor, so it is
+/// checked against every LED of the mute indicator — number LEDs, group
+/// LEDs, and the muted pattern — with the color the indicator gives each.
+///
+/// - Live + an indicator LED still in its mute color (for number LEDs: any
+///   color but the firmware's selected and unselected ones) → restored.
+/// - Live → the live pattern, if any, is painted.
+/// - Muted → consistent, and left alone, only if the last write is the
+///   indicator's final LED in its color: the indicator is written in a fixed
+///   order, so anything else means it was cut short or partly cleared, and
+///   it is applied again.
+pub fn reconcile_on_startup(
+    device: &impl ScarlettDevice,
+    strategy: &MuteStrategy,
+    mute_color: u32,
+    is_muted: bool,
+) -> Result<StartupReconcile> {
+    let (index, colour) = last_single_led(device)?;
+    let indicator = mute_commands(strategy, mute_color);
+    let shows = |cmd: &LedCommand| matches!(*cmd, LedCommand::Set { index: i, color: c } if i == index && c == colour);
+
+    if is_muted {
+        if indicator.last().is_some_and(shows) {
+            return Ok(StartupReconcile::Consistent);
+        }
+        execute(device, &indicator)?;
+        return Ok(StartupReconcile::ReappliedMute);
+    }
+
+    let leftover = colour != 0
+        && if strategy.number_leds.contains(&index) {
+            colour != strategy.selected_color && colour != strategy.unselected_color
+        } else {
+            indicator.iter().any(shows)
+        };
+    if leftover {
+        restore_number_leds(device, strategy)?;
+        return Ok(StartupReconcile::RestoredLeftover);
+    }
+    execute(device, &pattern_commands(&strategy.patterns.live))?;
+    Ok(StartupReconcile::Consistent)
+}
+
+// ── Mute animations ──
+
+/// Time between animation frames.
+pub const ANIMATION_FRAME: Duration = Duration::from_millis(100);
+
+/// Brightness steps in a pulse — frames that land on the same step are
+/// skipped, so a slow pulse doesn't write the LEDs every frame.
+const PULSE_STEPS: u32 = 16;
+
+/// Dimmest pulse step, so a pulsing LED never looks switched off.
+const PULSE_FLOOR: u32 = 3;
+
+/// How the mute indicator looks while muted (`mute_animation` setting).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MuteAnimation {
+    /// Solid mute color.
+    #[default]
+    Static,
+    /// Fade down and back up — one cycle every 2 s at speed 1.
+    Pulse,
+    /// On/off — one cycle every second at speed 1.
+    Blink,
+}
+
+impl MuteAnimation {
+    /// Parse a config value (`"static"`, `"pulse"`, `"blink"`).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "static" => Some(MuteAnimation::Static),
+            "pulse" => Some(MuteAnimation::Pulse),
+            "blink" => Some(MuteAnimation::Blink),
+            _ => None,
+        }
+    }
+
+    /// Length of one cycle at `speed` (1.0 = default, higher is faster).
+    /// Never shorter than two frames.
+    pub fn period(self, speed: f64) -> Duration {
+        let base = match self {
+            MuteAnimation::Static | MuteAnimation::Blink => Duration::from_secs(1),
+            MuteAnimation::Pulse => Duration::from_secs(2),
+        };
+        let speed = if speed.is_finite() && speed > 0.0 {
+            speed
+        } else {
+            1.0
+        };
+        base.div_f64(speed).max(ANIMATION_FRAME * 2)
+    }
+
+    /// Brightness `elapsed` into the animation: 255 is the full mute color,
+    /// 0 is off. Static is always 255.
+    pub fn level(self, elapsed: Duration, period: Duration) -> u8 {
+        let period = period.as_secs_f64();
+        if period <= 0.0 {
+            return u8::MAX;
+        }
+        let phase = (elapsed.as_secs_f64() % period) / period;
+        match self {
+            MuteAnimation::Static => u8::MAX,
+            MuteAnimation::Blink if phase < 0.5 => u8::MAX,
+            MuteAnimation::Blink => 0,
+            MuteAnimation::Pulse => {
+                // Full at the start of each cycle, dimmest halfway through
+                let brightness = 0.5 + 0.5 * (phase * std::f64::consts::TAU).cos();
+                let step = ((brightness * f64::from(PULSE_STEPS)).round() as u32)
+                    .clamp(PULSE_FLOOR, PULSE_STEPS);
+                (step * u32::from(u8::MAX) / PULSE_STEPS) as u8
+            }
+        }
+    }
+}
+
+/// Scale the RGB channels of `color` (`0xRRGGBB00`) by `level` / 255.
+pub fn dim_color(color: u32, level: u8) -> u32 {
+    let scale = |shift: u32| {
+        let channel = (color >> shift) & 0xFF;
+        (channel * u32::from(level) / 255) << shift
+    };
+    scale(24) | scale(16) | scale(8)
+}
+
+/// Fully saturated color `phase` (0.0–1.0) of the way round the color wheel:
+/// red, green, blue, and back to red.
+pub fn color_wheel(phase: f64) -> u32 {
+    let position = phase.rem_euclid(1.0) * 3.0;
+    let rising = ((position - position.floor()) * 255.0).round() as u32;
+    let falling = 255 - rising;
+    let (r, g, b) = match position as u32 {
+        0 => (falling, rising, 0),
+        1 => (0, falling, rising),
+        _ => (rising, 0, falling),
+    };
+    (r << 24) | (g << 16) | (b << 8)
+}
+
+/// Call `tick` every [`ANIMATION_FRAME`] on a background thread until it
+/// returns `false`. Front ends post a frame message from `tick` and draw the
+/// frame on the thread that owns the device.
+pub fn spawn_frame_ticker(
+    mut tick: impl FnMut() -> bool + Send + 'static,
+) -> std::io::Result<JoinHandle<()>> {
+    std::thread::Builder::new()
+        .name("led-animation".into())
+        .spawn(move || {
+            while tick() {
+                std::thread::sleep(ANIMATION_FRAME);
+            }
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::device::mock::MockDevice;
+    use crate::led::LedPatterns;
     use crate::protocol::*;
 
     /// Helper to set up a mock device with selectedInput for restore tests.
@@ -109,6 +430,8 @@ mod tests {
             mute_colors: vec![],
             selected_color: 0x20FF_0000,
             unselected_color: 0x88FF_FF00,
+            group_leds: Vec::new(),
+            patterns: LedPatterns::default(),
         }
     }
 
@@ -119,9 +442,85 @@ mod tests {
             mute_colors: vec![],
             selected_color: 0x20FF_0000,
             unselected_color: 0x88FF_FF00,
+            group_leds: Vec::new(),
+            patterns: LedPatterns::default(),
         }
     }
 
+    // ── LedWriteCache ──
+
+    #[test]
+    fn write_cache_skips_unchanged_leds_between_full_refreshes() {
+        let dev = MockDevice::new();
+        let strategy = make_strategy_both_inputs();
+        let mut cache = LedWriteCache::new();
+        let t0 = Instant::now();
+
+        assert_eq!(
+            cache
+                .refresh(&dev, &strategy, 0xFF00_0000, true, t0)
+                .unwrap(),
+            2
+        );
+        // A reconnect storm: nothing changed, nothing written
+        let soon = t0 + Duration::from_millis(500);
+        assert_eq!(
+            cache
+                .refresh(&dev, &strategy, 0xFF00_0000, true, soon)
+                .unwrap(),
+            0
+        );
+        // A new color is still written
+        assert_eq!(
+            cache
+                .refresh(&dev, &strategy, 0x0000_FF00, true, soon)
+                .unwrap(),
+            2
+        );
+        // After the interval everything is rewritten
+        let later = soon + FULL_REFRESH_INTERVAL;
+        assert_eq!(
+            cache
+                .refresh(&dev, &strategy, 0x0000_FF00, true, later)
+                .unwrap(),
+            2
+        );
+
+        cache.invalidate();
+        assert!(cache.full_refresh_due(later));
+        assert_eq!(
+            cache
+                .refresh(&dev, &strategy, 0x0000_FF00, true, later)
+                .unwrap(),
+            2
+        );
+    }
+
+    #[test]
+    fn write_cache_forgets_after_failed_write() {
+        let dev = MockDevice::new();
+        let strategy = make_strategy_both_inputs();
+        let mut cache = LedWriteCache::new();
+        let t0 = Instant::now();
+        cache
+            .refresh(&dev, &strategy, 0xFF00_0000, true, t0)
+            .unwrap();
+
+        dev.fail_set_descriptor.set(true);
+        assert!(
+            cache
+                .refresh(&dev, &strategy, 0x0000_FF00, true, t0)
+                .is_err()
+        );
+        dev.fail_set_descriptor.set(false);
+        assert_eq!(
+            cache
+                .refresh(&dev, &strategy, 0xFF00_0000, true, t0)
+                .unwrap(),
+            2
+        );
+    }
+
     // ── set_single_led ──
 
     #[test]
@@ -137,7 +536,7 @@ mod tests {
         assert_eq!(u32::from_le_bytes(colour[..4].try_into().unwrap()), color);
 
         // directLEDIndex should be written
-        let index = descs.get(&OFF_DIRECT_LED_INDEX).unwrap();
+        let index = &dev.get_descriptor(OFF_DIRECT_LED_INDEX, 1).unwrap();
         assert_eq!(index, &[0]);
 
         // Should have sent NOTIFY_DIRECT_LED_COLOUR (8)
@@ -172,7 +571,7 @@ mod tests {
         assert_eq!(u32::from_le_bytes(colour[..4].try_into().unwrap()), color);
 
         // directLEDIndex should be 0 (input 1 number LED)
-        let index = descs.get(&OFF_DIRECT_LED_INDEX).unwrap();
+        let index = &dev.get_descriptor(OFF_DIRECT_LED_INDEX, 1).unwrap();
         assert_eq!(index, &[0]);
 
         // Mode should NOT be changed
@@ -216,6 +615,8 @@ mod tests {
             mute_colors: vec![0x00FF_0000, 0x0000_FF00],
             selected_color: 0x20FF_0000,
             unselected_color: 0x88FF_FF00,
+            group_leds: Vec::new(),
+            patterns: LedPatterns::default(),
         };
 
         apply_mute_indicator(&dev, &strategy, 0xFF00_0000).unwrap();
@@ -302,7 +703,7 @@ mod tests {
 
         // Last LED written was index 8 (input 2, unselected → white)
         let descs = dev.descriptors.borrow();
-        let index = descs.get(&OFF_DIRECT_LED_INDEX).unwrap();
+        let index = &dev.get_descriptor(OFF_DIRECT_LED_INDEX, 1).unwrap();
         assert_eq!(index, &[8]);
         let colour = descs.get(&OFF_DIRECT_LED_COLOUR).unwrap();
         assert_eq!(
@@ -391,4 +792,214 @@ mod tests {
         let result = refresh_after_reconnect(&dev, &strategy, 0xFF00_0000, true);
         assert!(result.is_err(), "should return Err when apply fails");
     }
+
+    #[test]
+    fn flash_resync_ends_on_final_state() {
+        let dev = MockDevice::new();
+        let strategy = make_strategy_both_inputs();
+        let mute_color = 0xFF00_0000u32;
+
+        flash_resync(&dev, &strategy, mute_color, true).unwrap();
+
+        let descs = dev.descriptors.borrow();
+        let colour = descs.get(&OFF_DIRECT_LED_COLOUR).unwrap();
+        assert_eq!(
+            u32::from_le_bytes(colour[..4].try_into().unwrap()),
+            mute_color
+        );
+        // Two LEDs × (2 blinks × (mute + restore) + final mute)
+        let notifies = dev.notifies.borrow();
+        assert_eq!(
+            notifies
+                .iter()
+                .filter(|&&n| n == NOTIFY_DIRECT_LED_COLOUR)
+                .count(),
+            10
+        );
+    }
+
+    #[test]
+    fn flash_reconnect_blinks_number_leds_and_leaves_them_off() {
+        let dev = MockDevice::new();
+        let strategy = make_strategy_both_inputs();
+
+        flash_reconnect(&dev, &strategy, 0x00FF_0000).unwrap();
+
+        let descs = dev.descriptors.borrow();
+        let colour = descs.get(&OFF_DIRECT_LED_COLOUR).unwrap();
+        assert_eq!(u32::from_le_bytes(colour[..4].try_into().unwrap()), 0);
+        // Colour and index are written together; the last LED was input 2's
+        assert_eq!(colour.get(4), Some(&8));
+        // Two LEDs × 2 blinks × (on + off)
+        assert_eq!(dev.notifies.borrow().len(), 8);
+    }
+
+    // ── reconcile_on_startup ──
+
+    #[test]
+    fn reconcile_live_restores_leftover_mute_color() {
+        let dev = MockDevice::new();
+        let strategy = make_strategy_both_inputs();
+        setup_device_with_selected_input(&dev, 0);
+        // Previous run crashed while muted: LED 8 still red
+        set_single_led(&dev, 8, 0xFF00_0000).unwrap();
+        dev.notifies.borrow_mut().clear();
+
+        let result = reconcile_on_startup(&dev, &strategy, 0xFF00_0000, false).unwrap();
+        assert_eq!(result, StartupReconcile::RestoredLeftover);
+
+        // Both number LEDs rewritten, last one (input 2, unselected) restored
+        assert_eq!(dev.notifies.borrow().len(), 2);
+        let descs = dev.descriptors.borrow();
+        let colour = descs.get(&OFF_DIRECT_LED_COLOUR).unwrap();
+        assert_eq!(
+            u32::from_le_bytes(colour[..4].try_into().unwrap()),
+            strategy.unselected_color
+        );
+    }
+
+    #[test]
+    fn reconcile_live_clean_state_writes_nothing() {
+        let dev = MockDevice::new();
+        let strategy = make_strategy_both_inputs();
+        setup_device_with_selected_input(&dev, 0);
+        set_single_led(&dev, 8, strategy.unselected_color).unwrap();
+        dev.notifies.borrow_mut().clear();
+
+        let result = reconcile_on_startup(&dev, &strategy, 0xFF00_0000, false).unwrap();
+        assert_eq!(result, StartupReconcile::Consistent);
+        assert!(dev.notifies.borrow().is_empty());
+    }
+
+    #[test]
+    fn reconcile_live_ignores_non_number_led() {
+        let dev = MockDevice::new();
+        let strategy = make_strategy_both_inputs();
+        // Last write went to a halo LED, not a number LED
+        set_single_led(&dev, 3, 0xFF00_0000).unwrap();
+        dev.notifies.borrow_mut().clear();
+
+        let result = reconcile_on_startup(&dev, &strategy, 0xFF00_0000, false).unwrap();
+        assert_eq!(result, StartupReconcile::Consistent);
+        assert!(dev.notifies.borrow().is_empty());
+    }
+
+    #[test]
+    fn reconcile_muted_reapplies_when_missing() {
+        let dev = MockDevice::new();
+        let strategy = make_strategy_both_inputs();
+        set_single_led(&dev, 8, strategy.unselected_color).unwrap();
+        dev.notifies.borrow_mut().clear();
+
+        let result = reconcile_on_startup(&dev, &strategy, 0xFF00_0000, true).unwrap();
+        assert_eq!(result, StartupReconcile::ReappliedMute);
+        assert_eq!(dev.notifies.borrow().len(), 2);
+    }
+
+    #[test]
+    fn reconcile_muted_already_showing_is_consistent() {
+        let dev = MockDevice::new();
+        let strategy = make_strategy_both_inputs();
+        set_single_led(&dev, 8, 0xFF00_0000).unwrap();
+
+        dev.notifies.borrow_mut().clear();
+
+        let result = reconcile_on_startup(&dev, &strategy, 0xFF00_0000, true).unwrap();
+        assert_eq!(result, StartupReconcile::Consistent);
+        assert!(dev.notifies.borrow().is_empty(), "no LED rewritten");
+    }
+
+    #[test]
+    fn reconcile_muted_reapplies_partial_indicator() {
+        let dev = MockDevice::new();
+        let strategy = MuteStrategy {
+            group_leds: vec![30],
+            ..make_strategy_both_inputs()
+        };
+        // Cut short after the number LEDs: group LED 30 was never lit
+        set_single_led(&dev, 8, 0xFF00_0000).unwrap();
+        dev.notifies.borrow_mut().clear();
+
+        let result = reconcile_on_startup(&dev, &strategy, 0xFF00_0000, true).unwrap();
+        assert_eq!(result, StartupReconcile::ReappliedMute);
+        assert_eq!(dev.notifies.borrow().len(), 3);
+
+        // The full indicator is now showing
+        dev.notifies.borrow_mut().clear();
+        let result = reconcile_on_startup(&dev, &strategy, 0xFF00_0000, true).unwrap();
+        assert_eq!(result, StartupReconcile::Consistent);
+        assert!(dev.notifies.borrow().is_empty());
+    }
+
+    #[test]
+    fn reconcile_live_restores_leftover_group_led() {
+        let dev = MockDevice::new();
+        let strategy = MuteStrategy {
+            group_leds: vec![30],
+            ..make_strategy_both_inputs()
+        };
+        setup_device_with_selected_input(&dev, 0);
+        set_single_led(&dev, 30, 0xFF00_0000).unwrap();
+
+        let result = reconcile_on_startup(&dev, &strategy, 0xFF00_0000, false).unwrap();
+        assert_eq!(result, StartupReconcile::RestoredLeftover);
+    }
+
+    // ── Mute animations ──
+
+    #[test]
+    fn parses_mute_animation() {
+        assert_eq!(MuteAnimation::parse("pulse"), Some(MuteAnimation::Pulse));
+        assert_eq!(MuteAnimation::parse(" Blink "), Some(MuteAnimation::Blink));
+        assert_eq!(MuteAnimation::parse("static"), Some(MuteAnimation::Static));
+        assert_eq!(MuteAnimation::parse("strobe"), None);
+    }
+
+    #[test]
+    fn animation_period_scales_with_speed() {
+        assert_eq!(MuteAnimation::Pulse.period(1.0), Duration::from_secs(2));
+        assert_eq!(MuteAnimation::Blink.period(2.0), Duration::from_millis(500));
+        assert_eq!(MuteAnimation::Blink.period(0.0), Duration::from_secs(1));
+        assert_eq!(MuteAnimation::Blink.period(100.0), ANIMATION_FRAME * 2);
+    }
+
+    #[test]
+    fn blink_alternates_full_and_off() {
+        let period = Duration::from_secs(1);
+        let at = |ms| MuteAnimation::Blink.level(Duration::from_millis(ms), period);
+        assert_eq!(at(0), 255);
+        assert_eq!(at(400), 255);
+        assert_eq!(at(600), 0);
+        assert_eq!(at(1100), 255);
+    }
+
+    #[test]
+    fn pulse_dims_halfway_without_going_dark() {
+        let period = Duration::from_secs(2);
+        let at = |ms| MuteAnimation::Pulse.level(Duration::from_millis(ms), period);
+        assert_eq!(at(0), 255);
+        let dimmest = at(1000);
+        assert!(dimmest > 0 && dimmest < 64, "{dimmest}");
+        assert!(at(500) > dimmest && at(500) < 255);
+        assert_eq!(at(2000), 255);
+        assert_eq!(
+            MuteAnimation::Static.level(Duration::from_millis(1000), period),
+            255
+        );
+    }
+
+    #[test]
+    fn dim_color_scales_rgb_channels() {
+        assert_eq!(dim_color(0xFF80_4000, 255), 0xFF80_4000);
+        assert_eq!(dim_color(0xFF80_4000, 0), 0);
+        assert_eq!(dim_color(0xFF80_40AA, 128), 0x8040_2000);
+    }
+
+    #[test]
+    fn color_wheel_cycles_through_primaries() {
+        assert_eq!(color_wheel(0.0), 0xFF00_0000);
+        assert_eq!(color_wheel(1.0 / 3.0), 0x00FF_0000);
+        assert_eq!(color_wheel(2.0 / 3.0), 0x0000_FF00);
+        assert_eq!(color_wheel(1.0), color_wheel(0.0));
+    }
 }
@@ -4,15 +4,17 @@ use std::collections::HashMap;
 
 use crate::config::{Config, MuteInputs};
 use crate::layout::{LedZone, PredictedLayout};
-use crate::models::{self, ModelProfile};
+use crate::models::{self, ModelProfile, PresetTarget, StrategyPreset};
 
-use super::color::parse_color;
+use super::color::{find_color_preset, parse_color};
 
 /// Resolved mute visualization strategy.
 ///
 /// Targets specific input number LEDs via single-LED update (DATA_NOTIFY(8)).
 /// Only the number indicator LEDs ("1", "2") change color — the metering
-/// halo rings and all other LEDs are completely untouched.
+/// halo rings and all other LEDs are completely untouched, unless a strategy
+/// preset adds `group_leds` (the output meter, or each muted input's gain
+/// halo for the `halo` preset).
 #[derive(Debug, Clone)]
 pub struct MuteStrategy {
     /// 0-indexed input indices to indicate as muted.
@@ -27,6 +29,140 @@ pub struct MuteStrategy {
     pub selected_color: u32,
     /// Firmware color for unselected input number LEDs (for restore).
     pub unselected_color: u32,
+    /// Extra LEDs lit with the mute color while muted and switched off on
+    /// restore (strategy presets that target the output meter).
+    pub group_leds: Vec<u8>,
+    /// User-defined LED patterns (`led_patterns`) for each state.
+    pub patterns: LedPatterns,
+}
+
+/// Fixed `(LED index, color)` lists from the `led_patterns` config, one per
+/// state. Empty means the state has no pattern.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LedPatterns {
+    /// Painted while muted, after the strategy's own LEDs. A number LED in
+    /// the pattern takes the pattern's color instead of the mute color.
+    pub muted: Vec<(u8, u32)>,
+    /// Painted while live.
+    pub live: Vec<(u8, u32)>,
+    /// Painted while the device is connected but the mute state can't be
+    /// read (no audio monitor).
+    pub disconnected: Vec<(u8, u32)>,
+}
+
+/// Parse one `led_patterns` entry. Keys are an LED index (`"5"`) or an
+/// inclusive range (`"16-26"`); values are colors. Sorted by LED index.
+pub fn parse_led_pattern(entries: &HashMap<String, String>) -> Result<Vec<(u8, u32)>, String> {
+    let index = |s: &str, key: &str| {
+        s.trim()
+            .parse::<u8>()
+            .map_err(|_| format!("\"{key}\" is not an LED index or range"))
+    };
+    let mut leds = Vec::new();
+    for (key, value) in entries {
+        let color = parse_color(value).map_err(|e| format!("LED {key}: {e}"))?;
+        let (first, last) = match key.split_once('-') {
+            Some((a, b)) => (index(a, key)?, index(b, key)?),
+            None => (index(key, key)?, index(key, key)?),
+        };
+        if first > last {
+            return Err(format!("LED range \"{key}\" is reversed"));
+        }
+        leds.extend((first..=last).map(|i| (i, color)));
+    }
+    leds.sort_by_key(|&(i, _)| i);
+    if let Some(w) = leds.windows(2).find(|w| w[0].0 == w[1].0) {
+        return Err(format!("LED {} is listed twice", w[0].0));
+    }
+    Ok(leds)
+}
+
+/// Compile the patterns named by `mute_pattern`, `live_pattern`, and
+/// `disconnected_pattern`. Unknown names, bad entries, and LEDs past
+/// `led_count` are skipped with a warning.
+fn compile_led_patterns(
+    config: &Config,
+    led_count: Option<usize>,
+    warnings: &mut Vec<String>,
+) -> LedPatterns {
+    let mut compile = |field: &str, name: &str| {
+        let name = name.trim();
+        if name.is_empty() {
+            return Vec::new();
+        }
+        let Some(entries) = config.led_patterns.get(name) else {
+            warnings.push(format!("{field}: no LED pattern named \"{name}\""));
+            return Vec::new();
+        };
+        let mut leds = match parse_led_pattern(entries) {
+            Ok(leds) => leds,
+            Err(e) => {
+                warnings.push(format!("LED pattern \"{name}\": {e}"));
+                return Vec::new();
+            }
+        };
+        if let Some(count) = led_count
+            && leds.iter().any(|&(i, _)| usize::from(i) >= count)
+        {
+            warnings.push(format!(
+                "LED pattern \"{name}\": this device has {count} LEDs — skipping the rest"
+            ));
+            leds.retain(|&(i, _)| usize::from(i) < count);
+        }
+        leds
+    };
+    LedPatterns {
+        muted: compile("mute_pattern", &config.mute_pattern),
+        live: compile("live_pattern", &config.live_pattern),
+        disconnected: compile("disconnected_pattern", &config.disconnected_pattern),
+    }
+}
+
+/// Add the `input_live_colors` to the live pattern: each input's number LED
+/// rests in its color while live. A `live_pattern` entry for the same LED
+/// wins; inputs the device doesn't have are skipped with a warning.
+fn merge_input_live_colors(
+    live: &mut Vec<(u8, u32)>,
+    input_live_colors: &HashMap<String, String>,
+    profile: Option<&ModelProfile>,
+    predicted: Option<&PredictedLayout>,
+    warnings: &mut Vec<String>,
+) {
+    if input_live_colors.is_empty() {
+        return;
+    }
+    let number_leds: Vec<u8> = match (profile, predicted) {
+        (Some(p), _) => p
+            .input_halos
+            .iter()
+            .filter_map(|h| u8::try_from(h.number_led).ok())
+            .collect(),
+        (None, Some(p)) => number_leds_from_predicted(p)
+            .map(|(_, leds)| leds)
+            .unwrap_or_default(),
+        (None, None) => Vec::new(),
+    };
+    let mut keys: Vec<&String> = input_live_colors.keys().collect();
+    keys.sort();
+    for key in keys {
+        let Ok(color) = parse_color(&input_live_colors[key]) else {
+            continue;
+        };
+        let led = key
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .and_then(|n| n.checked_sub(1))
+            .and_then(|i| number_leds.get(i));
+        match led {
+            Some(&index) if !live.iter().any(|&(i, _)| i == index) => live.push((index, color)),
+            Some(_) => {}
+            None => warnings.push(format!(
+                "input_live_colors: this device has no input \"{key}\" — skipping it"
+            )),
+        }
+    }
+    live.sort_by_key(|&(i, _)| i);
 }
 
 /// Extract number LED indices from a predicted layout.
@@ -68,6 +204,32 @@ pub fn resolve_mute_strategy(
     input_colors: &HashMap<String, String>,
 ) -> Result<(MuteStrategy, Option<String>), String> {
     match mute_inputs {
+        MuteInputs::AllExcept(excluded) => {
+            // Expand against the device's inputs, then resolve as a specific selection.
+            let all_indices: Vec<usize> = if let Some(profile) = profile {
+                (0..profile.input_halos.len()).collect()
+            } else if let Some(predicted) = predicted {
+                number_leds_from_predicted(predicted)?.0
+            } else {
+                return Err(
+                    "unknown device with no schema; cannot determine number LED indices".into(),
+                );
+            };
+            let remaining: Vec<usize> = all_indices
+                .into_iter()
+                .filter(|i| !excluded.contains(i))
+                .collect();
+            if remaining.is_empty() {
+                return Err("mute_inputs excludes every input on this device".into());
+            }
+            resolve_mute_strategy(
+                &MuteInputs::Specific(remaining),
+                profile,
+                predicted,
+                mute_color,
+                input_colors,
+            )
+        }
         MuteInputs::All => {
             if let Some(profile) = profile {
                 // Known device: target all input number LEDs via DATA_NOTIFY(8).
@@ -94,6 +256,8 @@ pub fn resolve_mute_strategy(
                         mute_colors,
                         selected_color: profile.number_led_selected,
                         unselected_color: profile.number_led_unselected,
+                        group_leds: Vec::new(),
+                        patterns: LedPatterns::default(),
                     },
                     None,
                 ))
@@ -113,6 +277,8 @@ pub fn resolve_mute_strategy(
                         mute_colors,
                         selected_color: models::DEFAULT_NUMBER_LED_SELECTED,
                         unselected_color: models::DEFAULT_NUMBER_LED_UNSELECTED,
+                        group_leds: Vec::new(),
+                        patterns: LedPatterns::default(),
                     },
                     Some("using predicted LED layout (no hardcoded profile)".into()),
                 ))
@@ -153,6 +319,8 @@ pub fn resolve_mute_strategy(
                         mute_colors,
                         selected_color: profile.number_led_selected,
                         unselected_color: profile.number_led_unselected,
+                        group_leds: Vec::new(),
+                        patterns: LedPatterns::default(),
                     },
                     None,
                 ))
@@ -181,6 +349,8 @@ pub fn resolve_mute_strategy(
                         mute_colors,
                         selected_color: models::DEFAULT_NUMBER_LED_SELECTED,
                         unselected_color: models::DEFAULT_NUMBER_LED_UNSELECTED,
+                        group_leds: Vec::new(),
+                        patterns: LedPatterns::default(),
                     },
                     Some("using predicted LED layout (no hardcoded profile)".into()),
                 ))
@@ -193,6 +363,124 @@ pub fn resolve_mute_strategy(
     }
 }
 
+/// Resolve a [`MuteStrategy`] for a named [`StrategyPreset`].
+///
+/// `InputNumbers` presets resolve like a specific `mute_inputs` selection;
+/// `OutputMeter` presets light the output halo LEDs as `group_leds`;
+/// `InputHalos` presets resolve `mute_inputs` and add those inputs' halo
+/// segments as `group_leds`.
+pub fn resolve_preset_strategy(
+    preset: &StrategyPreset,
+    mute_inputs: &MuteInputs,
+    profile: Option<&ModelProfile>,
+    predicted: Option<&PredictedLayout>,
+    mute_color: u32,
+    input_colors: &HashMap<String, String>,
+) -> Result<(MuteStrategy, Option<String>), String> {
+    match preset.target {
+        PresetTarget::InputNumbers(inputs) => resolve_mute_strategy(
+            &MuteInputs::Specific(inputs.to_vec()),
+            profile,
+            predicted,
+            mute_color,
+            input_colors,
+        ),
+        PresetTarget::InputHalos => {
+            let (mut strategy, warning) =
+                resolve_mute_strategy(mute_inputs, profile, predicted, mute_color, input_colors)?;
+            let leds = input_halo_leds(&strategy.input_indices, profile, predicted);
+            if leds.is_empty() {
+                return Err("device layout has no input halo LEDs".into());
+            }
+            strategy.group_leds = leds
+                .into_iter()
+                .map(|i| u8::try_from(i).map_err(|_| format!("halo LED {i} exceeds u8 range")))
+                .collect::<Result<Vec<u8>, String>>()?;
+            Ok((strategy, warning))
+        }
+        PresetTarget::OutputMeter => {
+            let (leds, selected_color, unselected_color, warning): (Vec<usize>, _, _, _) =
+                if let Some(profile) = profile {
+                    (
+                        profile.output_halo_segments.clone().collect(),
+                        profile.number_led_selected,
+                        profile.number_led_unselected,
+                        None,
+                    )
+                } else if let Some(predicted) = predicted {
+                    (
+                        predicted
+                            .leds
+                            .iter()
+                            .filter(|l| l.zone == LedZone::OutputHalo)
+                            .map(|l| l.index)
+                            .collect(),
+                        models::DEFAULT_NUMBER_LED_SELECTED,
+                        models::DEFAULT_NUMBER_LED_UNSELECTED,
+                        Some("using predicted LED layout (no hardcoded profile)".into()),
+                    )
+                } else {
+                    return Err(
+                        "unknown device with no schema; cannot determine output meter LEDs".into(),
+                    );
+                };
+            if leds.is_empty() {
+                return Err("device layout has no output meter LEDs".into());
+            }
+            let group_leds = leds
+                .into_iter()
+                .map(|i| u8::try_from(i).map_err(|_| format!("output LED {i} exceeds u8 range")))
+                .collect::<Result<Vec<u8>, String>>()?;
+            Ok((
+                MuteStrategy {
+                    input_indices: vec![],
+                    number_leds: vec![],
+                    mute_colors: vec![],
+                    selected_color,
+                    unselected_color,
+                    group_leds,
+                    patterns: LedPatterns::default(),
+                },
+                warning,
+            ))
+        }
+    }
+}
+
+/// Halo segment LED indices of the given 0-indexed inputs.
+///
+/// From the profile when known; otherwise each input owns the predicted
+/// `InputHalo` LEDs (sized from the schema's `metering_segments`) that follow
+/// its number LED.
+pub(super) fn input_halo_leds(
+    inputs: &[usize],
+    profile: Option<&ModelProfile>,
+    predicted: Option<&PredictedLayout>,
+) -> Vec<usize> {
+    if let Some(profile) = profile {
+        return inputs
+            .iter()
+            .filter_map(|&i| profile.input_halos.get(i))
+            .flat_map(|h| h.segments.clone())
+            .collect();
+    }
+    let Some(predicted) = predicted else {
+        return Vec::new();
+    };
+    let mut input = None;
+    let mut leds = Vec::new();
+    for led in &predicted.leds {
+        match led.zone {
+            LedZone::InputNumber => input = Some(input.map_or(0, |i| i + 1)),
+            LedZone::InputHalo if input.is_some_and(|i| inputs.contains(&i)) => {
+                leds.push(led.index)
+            }
+            _ => {}
+        }
+    }
+    leds
+}
+
 /// Build per-input mute colors from config `input_colors` map, falling back to global color.
 ///
 /// `input_indices` are 0-indexed; `input_colors` keys are 1-based strings (e.g. "1", "2").
@@ -216,12 +504,65 @@ fn build_mute_colors(
 // ── Shared helpers (DRY across cli.rs / tray.rs) ──
 
 /// Parse the mute color from config, falling back to red on invalid input.
+/// A `color_preset` replaces `mute_color`.
 pub fn mute_color_or_default(config: &Config) -> u32 {
+    if let Some(preset) = find_color_preset(&config.color_preset) {
+        return preset.mute;
+    }
     parse_color(&config.mute_color).unwrap_or(0xFF00_0000)
 }
 
+/// Parse the external-mute color from config. `None` if unset or invalid
+/// (external mutes then use the normal mute colors). A `color_preset`
+/// replaces `external_mute_color`.
+pub fn external_mute_color(config: &Config) -> Option<u32> {
+    if let Some(preset) = find_color_preset(&config.color_preset) {
+        return Some(preset.external_mute);
+    }
+    let c = config.external_mute_color.trim();
+    if c.is_empty() {
+        return None;
+    }
+    parse_color(c).ok()
+}
+
+/// Parse the busylight live color from config. `None` if unset or invalid
+/// (the light is switched off while live). A `color_preset` replaces
+/// `busylight_live_color`.
+pub fn busylight_live_color(config: &Config) -> Option<u32> {
+    if let Some(preset) = find_color_preset(&config.color_preset) {
+        return Some(preset.live);
+    }
+    parse_color(&config.busylight_live_color).ok()
+}
+
+/// Parse the reconnect flash color from config. `None` if unset or invalid
+/// (reconnecting doesn't flash).
+pub fn reconnect_flash_color(config: &Config) -> Option<u32> {
+    let c = config.reconnect_flash_color.trim();
+    if c.is_empty() {
+        return None;
+    }
+    parse_color(c).ok()
+}
+
+/// Parse the in-use color from config. `None` if unset or invalid (live
+/// looks the same whether or not an application is recording).
+pub fn in_use_color(config: &Config) -> Option<u32> {
+    let c = config.in_use_color.trim();
+    if c.is_empty() {
+        return None;
+    }
+    parse_color(c).ok()
+}
+
 /// Validate mute-inputs config, parse it, and resolve the mute strategy.
 ///
+/// A `mute_preset` known for the device's model takes precedence over
+/// `mute_inputs`; an unknown or unusable preset is reported as a warning and
+/// `mute_inputs` is used instead. The configured LED patterns are compiled
+/// into the strategy either way.
+///
 /// Returns `Ok((mute_mode, strategy, warnings))` or `Err` if the device is unsupported.
 pub fn resolve_strategy_from_config(
     config: &mut Config,
@@ -233,11 +574,63 @@ pub fn resolve_strategy_from_config(
     if let Some(ic) = input_count
         && let Err(e) = config.validate_mute_inputs(ic)
     {
-        warnings.push(format!("{e} — falling back to all inputs"));
+        if ic == 1 {
+            // Single-input device (e.g. Solo): any selection can only mean input 1.
+            warnings.push(format!(
+                "single-input device — mute_inputs \"{}\" treated as \"all\"",
+                config.mute_inputs.trim()
+            ));
+        } else {
+            warnings.push(format!("{e} — falling back to all inputs"));
+        }
         config.mute_inputs = "all".into();
     }
     let mute_mode = config.parse_mute_inputs();
     let mute_color = mute_color_or_default(config);
+    let led_count = profile
+        .map(|p| p.led_count)
+        .or_else(|| predicted.map(|p| p.total_leds));
+    let mut patterns = compile_led_patterns(config, led_count, &mut warnings);
+    merge_input_live_colors(
+        &mut patterns.live,
+        &config.input_live_colors,
+        profile,
+        predicted,
+        &mut warnings,
+    );
+
+    let preset_name = config.mute_preset.trim();
+    if !preset_name.is_empty() {
+        let model = profile
+            .map(|p| p.name)
+            .or_else(|| predicted.map(|p| p.product_name.as_str()));
+        match model.and_then(|m| models::find_strategy_preset(m, preset_name)) {
+            Some(preset) => match resolve_preset_strategy(
+                preset,
+                &mute_mode,
+                profile,
+                predicted,
+                mute_color,
+                &config.input_colors,
+            ) {
+                Ok((strategy, strategy_warning)) => {
+                    warnings.extend(strategy_warning);
+                    let strategy = MuteStrategy {
+                        patterns,
+                        ..strategy
+                    };
+                    return Ok((mute_mode, strategy, warnings));
+                }
+                Err(e) => warnings.push(format!(
+                    "mute_preset \"{preset_name}\": {e} — using mute_inputs"
+                )),
+            },
+            None => warnings.push(format!(
+                "mute_preset \"{preset_name}\" is not available for this device — using mute_inputs"
+            )),
+        }
+    }
+
     let (strategy, strategy_warning) = resolve_mute_strategy(
         &mute_mode,
         profile,
@@ -248,6 +641,10 @@ pub fn resolve_strategy_from_config(
     if let Some(w) = strategy_warning {
         warnings.push(w);
     }
+    let strategy = MuteStrategy {
+        patterns,
+        ..strategy
+    };
     Ok((mute_mode, strategy, warnings))
 }
 
@@ -435,6 +832,35 @@ mod tests {
         assert_eq!(mute_color_or_default(&config), 0x00FF_0000);
     }
 
+    #[test]
+    fn external_mute_color_empty_is_none() {
+        assert_eq!(external_mute_color(&Config::default()), None);
+    }
+
+    #[test]
+    fn external_mute_color_parses_value() {
+        let config = Config {
+            external_mute_color: "#0000FF".into(),
+            ..Config::default()
+        };
+        assert_eq!(external_mute_color(&config), Some(0x0000_FF00));
+    }
+
+    #[test]
+    fn color_preset_replaces_configured_colors() {
+        let config = Config {
+            color_preset: "deuteranopia".into(),
+            mute_color: "#00FF00".into(),
+            external_mute_color: "#0000FF".into(),
+            ..Config::default()
+        };
+        let preset = crate::led::find_color_preset("deuteranopia").unwrap();
+        assert_eq!(mute_color_or_default(&config), preset.mute);
+        assert_eq!(external_mute_color(&config), Some(preset.external_mute));
+        assert_eq!(busylight_live_color(&config), Some(preset.live));
+        assert_eq!(busylight_live_color(&Config::default()), None);
+    }
+
     #[test]
     fn mute_color_or_default_invalid_returns_red() {
         let mut config = Config::load();
@@ -478,6 +904,33 @@ mod tests {
         assert_eq!(config.mute_inputs, "all");
     }
 
+    #[test]
+    fn resolve_strategy_single_input_collapses_to_all() {
+        let mut config = Config::load();
+        config.mute_inputs = "1,2".into(); // e.g. config carried over from a 2i2
+        let profile = models::detect_model("Scarlett Solo 4th Gen").unwrap();
+        let (mode, strategy, warnings) =
+            resolve_strategy_from_config(&mut config, Some(1), Some(profile), None).unwrap();
+        assert!(matches!(mode, MuteInputs::All));
+        assert_eq!(strategy.input_indices, &[0]);
+        assert_eq!(strategy.number_leds, &[0]);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("single-input"));
+        assert_eq!(config.mute_inputs, "all");
+    }
+
+    #[test]
+    fn resolve_strategy_single_input_keeps_input_1() {
+        let mut config = Config::load();
+        config.mute_inputs = "1".into();
+        let profile = models::detect_model("Scarlett Solo 4th Gen").unwrap();
+        let (_, strategy, warnings) =
+            resolve_strategy_from_config(&mut config, Some(1), Some(profile), None).unwrap();
+        assert_eq!(strategy.input_indices, &[0]);
+        assert!(warnings.is_empty());
+        assert_eq!(config.mute_inputs, "1");
+    }
+
     #[test]
     fn resolve_strategy_with_predicted_layout() {
         let mut config = Config::load();
@@ -490,6 +943,271 @@ mod tests {
         assert!(!warnings.is_empty()); // "using predicted" warning
     }
 
+    #[test]
+    fn resolve_strategy_rack_unit_with_range() {
+        let schema = crate::schema::SchemaConstants {
+            product_name: "Scarlett 16i16 4th Gen".into(),
+            max_leds: 48,
+            max_inputs: 8,
+            max_outputs: 8,
+            gradient_count: 11,
+            gradient_offset: 384,
+            gradient_notify: 9,
+            direct_led_count: 48,
+            direct_led_offset: 92,
+            metering_segments: 0,
+            input_controls: vec![],
+            app_space_features: vec![],
+            firmware_version: String::new(),
+            phantom_power: None,
+            air: None,
+            inst_input: None,
+            preamp_gain: None,
+            clip_safe: None,
+            autogain: None,
+            autogain_status_offset: None,
+            sample_rate_offset: None,
+            sync_source_offset: None,
+            direct_monitoring_offset: None,
+            alt_speakers: None,
+            output_volume: None,
+        };
+        let predicted = crate::layout::predict_layout(&schema).unwrap();
+        let mut config = Config::load();
+        config.mute_inputs = "1-4,7".into();
+        let (_, strategy, _) =
+            resolve_strategy_from_config(&mut config, Some(8), None, Some(&predicted)).unwrap();
+        assert_eq!(strategy.input_indices, &[0, 1, 2, 3, 6]);
+        assert_eq!(strategy.number_leds, &[0, 1, 2, 3, 6]);
+        assert_eq!(config.mute_inputs, "1-4,7");
+    }
+
+    #[test]
+    fn resolve_all_except_with_profile() {
+        let profile = models::detect_model("Scarlett 4i4 4th Gen").unwrap();
+        let (strategy, _) = resolve_mute_strategy(
+            &MuteInputs::AllExcept(vec![1]),
+            Some(profile),
+            None,
+            RED,
+            &no_input_colors(),
+        )
+        .unwrap();
+        assert_eq!(strategy.input_indices, &[0, 2, 3]);
+        assert_eq!(strategy.number_leds, &[0, 16, 24]);
+    }
+
+    #[test]
+    fn resolve_all_except_with_predicted() {
+        let predicted = make_predicted_layout(3);
+        let (strategy, warning) = resolve_mute_strategy(
+            &MuteInputs::AllExcept(vec![0]),
+            None,
+            Some(&predicted),
+            RED,
+            &no_input_colors(),
+        )
+        .unwrap();
+        assert!(warning.is_some());
+        assert_eq!(strategy.input_indices, &[1, 2]);
+    }
+
+    #[test]
+    fn resolve_all_except_everything_is_error() {
+        let profile = models::detect_model("Scarlett 2i2 4th Gen").unwrap();
+        let result = resolve_mute_strategy(
+            &MuteInputs::AllExcept(vec![0, 1]),
+            Some(profile),
+            None,
+            RED,
+            &no_input_colors(),
+        );
+        assert!(result.unwrap_err().contains("excludes every input"));
+    }
+
+    #[test]
+    fn resolve_preset_output_meter_with_profile() {
+        let profile = models::detect_model("Scarlett 2i2 4th Gen").unwrap();
+        let mut config = Config {
+            mute_preset: "output-meter".into(),
+            ..Config::default()
+        };
+        let (_, strategy, warnings) =
+            resolve_strategy_from_config(&mut config, Some(2), Some(profile), None).unwrap();
+        assert!(warnings.is_empty());
+        assert!(strategy.number_leds.is_empty());
+        assert_eq!(strategy.group_leds, (16..27).collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn resolve_preset_bank_on_rack_unit() {
+        let mut predicted = make_predicted_layout(8);
+        predicted.product_name = "Scarlett 18i20 4th Gen".into();
+        let mut config = Config {
+            mute_inputs: "all".into(),
+            mute_preset: "bank".into(),
+            ..Config::default()
+        };
+        let (_, strategy, _) =
+            resolve_strategy_from_config(&mut config, Some(8), None, Some(&predicted)).unwrap();
+        assert_eq!(strategy.input_indices, &[0]);
+        assert!(strategy.group_leds.is_empty());
+    }
+
+    #[test]
+    fn resolve_unknown_preset_falls_back_to_mute_inputs() {
+        let profile = models::detect_model("Scarlett 2i2 4th Gen").unwrap();
+        let mut config = Config {
+            mute_preset: "bank".into(),
+            ..Config::default()
+        };
+        let (_, strategy, warnings) =
+            resolve_strategy_from_config(&mut config, Some(2), Some(profile), None).unwrap();
+        assert_eq!(strategy.input_indices, &[0, 1]);
+        assert!(warnings[0].contains("not available"), "{warnings:?}");
+    }
+
+    #[test]
+    fn resolve_preset_halo_with_profile() {
+        let profile = models::detect_model("Scarlett 2i2 4th Gen").unwrap();
+        let mut config = Config {
+            mute_preset: "halo".into(),
+            ..Config::default()
+        };
+        let (_, strategy, warnings) =
+            resolve_strategy_from_config(&mut config, Some(2), Some(profile), None).unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(strategy.number_leds, &[0, 8]);
+        let expected: Vec<u8> = (1..8).chain(9..16).collect();
+        assert_eq!(strategy.group_leds, expected);
+    }
+
+    #[test]
+    fn resolve_preset_halo_respects_mute_inputs() {
+        let profile = models::detect_model("Scarlett 2i2 4th Gen").unwrap();
+        let mut config = Config {
+            mute_preset: "halo".into(),
+            mute_inputs: "2".into(),
+            ..Config::default()
+        };
+        let (_, strategy, _) =
+            resolve_strategy_from_config(&mut config, Some(2), Some(profile), None).unwrap();
+        assert_eq!(strategy.number_leds, &[8]);
+        assert_eq!(strategy.group_leds, (9..16).collect::<Vec<u8>>());
+    }
+
+    // ── LED patterns ──
+
+    #[test]
+    fn parses_led_pattern_indices_and_ranges() {
+        let pattern = parse_led_pattern(&HashMap::from([
+            ("3".into(), "red".into()),
+            ("0-1".into(), "#0000FF".into()),
+        ]))
+        .unwrap();
+        assert_eq!(
+            pattern,
+            vec![(0, 0x0000_FF00), (1, 0x0000_FF00), (3, 0xFF00_0000)]
+        );
+
+        for bad in ["x", "5-2", "1-", "300"] {
+            let entries = HashMap::from([(bad.to_string(), "red".to_string())]);
+            assert!(parse_led_pattern(&entries).is_err(), "{bad}");
+        }
+        let overlap = HashMap::from([
+            ("0-4".to_string(), "red".to_string()),
+            ("4".to_string(), "blue".to_string()),
+        ]);
+        assert!(parse_led_pattern(&overlap).unwrap_err().contains("LED 4"));
+    }
+
+    #[test]
+    fn resolve_compiles_named_patterns() {
+        let profile = models::detect_model("Scarlett 2i2 4th Gen").unwrap();
+        let mut config = Config {
+            led_patterns: HashMap::from([
+                (
+                    "onair".into(),
+                    HashMap::from([("16-17".into(), "red".into()), ("60".into(), "red".into())]),
+                ),
+                (
+                    "idle".into(),
+                    HashMap::from([("16".into(), "green".into())]),
+                ),
+            ]),
+            mute_pattern: "onair".into(),
+            live_pattern: "idle".into(),
+            disconnected_pattern: "missing".into(),
+            ..Config::default()
+        };
+        let (_, strategy, warnings) =
+            resolve_strategy_from_config(&mut config, Some(2), Some(profile), None).unwrap();
+        assert_eq!(
+            strategy.patterns.muted,
+            vec![(16, 0xFF00_0000), (17, 0xFF00_0000)]
+        );
+        assert_eq!(strategy.patterns.live, vec![(16, 0x00FF_0000)]);
+        assert!(strategy.patterns.disconnected.is_empty());
+        assert_eq!(warnings.len(), 2, "{warnings:?}");
+        assert!(warnings[0].contains("40 LEDs"), "{warnings:?}");
+        assert!(warnings[1].contains("no LED pattern named"), "{warnings:?}");
+    }
+
+    #[test]
+    fn resolve_merges_input_live_colors_into_live_pattern() {
+        let profile = models::detect_model("Scarlett 2i2 4th Gen").unwrap();
+        let mut config = Config {
+            led_patterns: HashMap::from([(
+                "idle".into(),
+                HashMap::from([("8".into(), "white".into())]),
+            )]),
+            live_pattern: "idle".into(),
+            input_live_colors: HashMap::from([
+                ("1".into(), "cyan".into()),
+                ("2".into(), "blue".into()),
+                ("5".into(), "red".into()),
+            ]),
+            ..Config::default()
+        };
+        let (_, strategy, warnings) =
+            resolve_strategy_from_config(&mut config, Some(2), Some(profile), None).unwrap();
+        // Input 2's number LED (8) keeps the live_pattern color
+        assert_eq!(
+            strategy.patterns.live,
+            vec![(0, 0x00FF_FF00), (8, 0xFFFF_FF00)]
+        );
+        assert!(strategy.patterns.muted.is_empty());
+        assert_eq!(warnings.len(), 1, "{warnings:?}");
+        assert!(warnings[0].contains("no input \"5\""), "{warnings:?}");
+
+        // Unmuting restores input 1 to its live color, not the firmware's
+        let restore = crate::led::restore_commands(&strategy);
+        assert!(restore.contains(&crate::led::LedCommand::Set {
+            index: 0,
+            color: 0x00FF_FF00
+        }));
+    }
+
+    #[test]
+    fn resolve_preset_halo_with_predicted() {
+        let predicted = make_predicted_layout(2);
+        let (strategy, _) = resolve_preset_strategy(
+            &StrategyPreset {
+                name: "halo",
+                description: "",
+                target: PresetTarget::InputHalos,
+            },
+            &MuteInputs::Specific(vec![1]),
+            None,
+            Some(&predicted),
+            RED,
+            &no_input_colors(),
+        )
+        .unwrap();
+        assert_eq!(strategy.number_leds, &[8]);
+        assert_eq!(strategy.group_leds, (9..16).collect::<Vec<u8>>());
+    }
+
     // ── Test helpers ──
 
     /// Create a minimal predicted layout with the given input count.
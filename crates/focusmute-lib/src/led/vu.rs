@@ -0,0 +1,310 @@
+//! VU mode — FocusMute draws the input halo meters itself.
+//!
+//! The firmware normally lights the input halos from the signal level and
+//! repaints any segment FocusMute writes. VU mode hands the halos to
+//! software (`enableDirectLEDMode` = 2) and redraws them from the live
+//! [`meter`](crate::meter) levels each frame, so LEDs the mute indicator
+//! owns (the `halo` preset, LED patterns) keep its colors while the rest of
+//! the halo keeps metering. Stopping hands the halos back to the firmware.
+
+use std::time::Duration;
+
+use crate::descriptor_batch::DescriptorBatch;
+use crate::device::{Result, ScarlettDevice};
+use crate::layout::PredictedLayout;
+use crate::meter;
+use crate::models::ModelProfile;
+use crate::protocol;
+use crate::schema::SchemaConstants;
+
+use super::command::LedCommand;
+use super::gradient::stretch_gradient;
+use super::ops::LedWriteCache;
+use super::strategy::input_halo_leds;
+
+/// How often the halos are redrawn — about the rate Focusrite Control 2
+/// polls the meters at.
+pub const VU_FRAME: Duration = Duration::from_millis(45);
+
+/// Level at the bottom of the halo; anything quieter lights nothing.
+pub const VU_FLOOR_DB: f64 = -60.0;
+
+/// Halo colors when the device's own gradient can't be read: green through
+/// yellow to red.
+pub const DEFAULT_VU_GRADIENT: [u32; 3] = [0x00FF_0000, 0xFFFF_0000, 0xFF00_0000];
+
+/// How many of a halo's `segments` a meter `level` lights.
+pub fn lit_segments(level: u32, segments: usize) -> usize {
+    let db = meter::level_db(level);
+    if db <= VU_FLOOR_DB {
+        return 0;
+    }
+    let fraction = (db - VU_FLOOR_DB) / -VU_FLOOR_DB;
+    ((fraction * segments as f64).ceil() as usize).min(segments)
+}
+
+/// Input halo meters drawn by FocusMute.
+#[derive(Debug)]
+pub struct VuMeter {
+    /// Halo segment LEDs of each input, lowest first.
+    halos: Vec<Vec<u8>>,
+    /// Color of each segment, per input.
+    colors: Vec<Vec<u32>>,
+    /// LEDs left to the mute indicator in the last frame.
+    reserved: Vec<u8>,
+    /// Metering points the device reports (read by [`start`](Self::start)).
+    meters: usize,
+    /// Input levels read by the last [`poll`](Self::poll).
+    levels: Vec<u32>,
+    cache: LedWriteCache,
+}
+
+impl VuMeter {
+    /// Meters over `halos` (each input's segment LEDs, lowest first),
+    /// colored with `gradient` stretched over each halo.
+    pub fn new(halos: Vec<Vec<u8>>, gradient: &[u32]) -> Self {
+        let gradient = if gradient.is_empty() {
+            &DEFAULT_VU_GRADIENT[..]
+        } else {
+            gradient
+        };
+        let colors = halos
+            .iter()
+            .map(|h| stretch_gradient(gradient, h.len()))
+            .collect();
+        Self {
+            halos,
+            colors,
+            reserved: Vec::new(),
+            meters: 0,
+            levels: Vec::new(),
+            cache: LedWriteCache::new(),
+        }
+    }
+
+    /// Meters for the device's input halos, from the profile or predicted
+    /// layout. `None` if it has none (rack units).
+    pub fn for_device(
+        input_count: usize,
+        profile: Option<&ModelProfile>,
+        predicted: Option<&PredictedLayout>,
+        gradient: &[u32],
+    ) -> Option<Self> {
+        let halos: Vec<Vec<u8>> = (0..input_count)
+            .map(|i| {
+                input_halo_leds(&[i], profile, predicted)
+                    .into_iter()
+                    .filter_map(|led| u8::try_from(led).ok())
+                    .collect()
+            })
+            .collect();
+        if halos.iter().all(Vec::is_empty) {
+            return None;
+        }
+        Some(Self::new(halos, gradient))
+    }
+
+    /// Number of inputs drawn — the meter levels each frame needs.
+    pub fn input_count(&self) -> usize {
+        self.halos.len()
+    }
+
+    /// Take the halos over from the firmware. Call again after a reconnect.
+    pub fn start(&mut self, device: &impl ScarlettDevice) -> Result<()> {
+        self.cache.invalidate();
+        // GET_METER asks for every metering point; the inputs come first
+        self.meters = meter::meter_count(device)?.max(self.input_count());
+        DescriptorBatch::new()
+            .write(
+                protocol::OFF_ENABLE_DIRECT_LED,
+                &[protocol::DIRECT_LED_MODE_HALOS],
+            )
+            .notify(protocol::NOTIFY_DIRECT_LED_VALUES)
+            .commit(device)
+    }
+
+    /// Give the halos back to the firmware's own metering.
+    pub fn stop(
+        &mut self,
+        device: &impl ScarlettDevice,
+        schema: Option<&SchemaConstants>,
+    ) -> Result<()> {
+        self.cache.invalidate();
+        DescriptorBatch::new()
+            .write(
+                protocol::OFF_ENABLE_DIRECT_LED,
+                &[protocol::DIRECT_LED_MODE_OFF],
+            )
+            .notify(protocol::NOTIFY_DIRECT_LED_VALUES)
+            .commit(device)?;
+        // The gradient notify puts the halos back on the meter
+        device.data_notify(schema.map_or(protocol::NOTIFY_LED_COLORS, |sc| sc.gradient_notify))
+    }
+
+    /// Commands that draw `levels` (one per input, 0–4095), leaving the
+    /// `reserved` LEDs alone.
+    pub fn frame(&mut self, levels: &[u32], reserved: &[u8]) -> Vec<LedCommand> {
+        if reserved != self.reserved {
+            // LEDs changing hands were painted behind the cache's back
+            self.cache.invalidate();
+            self.reserved = reserved.to_vec();
+        }
+        let mut commands = Vec::new();
+        for ((halo, colors), &level) in self.halos.iter().zip(&self.colors).zip(levels) {
+            let lit = lit_segments(level, halo.len());
+            for (i, (&index, &color)) in halo.iter().zip(colors).enumerate() {
+                if reserved.contains(&index) {
+                    continue;
+                }
+                let color = if i < lit { color } else { 0 };
+                commands.push(LedCommand::Set { index, color });
+            }
+        }
+        commands
+    }
+
+    /// Draw one frame, writing only the segments that changed. Returns how
+    /// many LEDs were written.
+    pub fn draw(
+        &mut self,
+        device: &impl ScarlettDevice,
+        levels: &[u32],
+        reserved: &[u8],
+    ) -> Result<usize> {
+        let commands = self.frame(levels, reserved);
+        self.cache.execute(device, &commands)
+    }
+
+    /// Read the input levels and [`draw`](Self::draw) them.
+    pub fn poll(&mut self, device: &impl ScarlettDevice, reserved: &[u8]) -> Result<usize> {
+        let mut levels = meter::read_levels(device, self.meters.max(self.input_count()))?;
+        levels.truncate(self.input_count());
+        let written = self.draw(device, &levels, reserved);
+        self.levels = levels;
+        written
+    }
+
+    /// Input levels read by the last [`poll`](Self::poll).
+    pub fn levels(&self) -> &[u32] {
+        &self.levels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device::mock::MockDevice;
+    use crate::models;
+    use crate::protocol::*;
+
+    fn two_inputs() -> VuMeter {
+        VuMeter::new(vec![vec![1, 2, 3], vec![9, 10, 11]], &[0x00FF_0000])
+    }
+
+    #[test]
+    fn level_maps_onto_segments() {
+        assert_eq!(lit_segments(0, 7), 0);
+        assert_eq!(lit_segments(1, 7), 0);
+        assert_eq!(lit_segments(meter::METER_FULL_SCALE, 7), 7);
+        // -6 dBFS is most of the way up
+        assert_eq!(lit_segments(meter::METER_FULL_SCALE / 2, 7), 7);
+        // -30 dBFS is half way
+        assert_eq!(lit_segments(129, 7), 4);
+    }
+
+    #[test]
+    fn frame_lights_segments_and_skips_reserved() {
+        let mut vu = two_inputs();
+        let commands = vu.frame(&[meter::METER_FULL_SCALE, 0], &[2]);
+        let set = |index, color| LedCommand::Set { index, color };
+        assert_eq!(
+            commands,
+            vec![
+                set(1, 0x00FF_0000),
+                set(3, 0x00FF_0000),
+                set(9, 0),
+                set(10, 0),
+                set(11, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn draw_writes_only_changes() {
+        let dev = MockDevice::new();
+        let mut vu = two_inputs();
+        assert_eq!(vu.draw(&dev, &[0, 0], &[]).unwrap(), 6);
+        assert_eq!(vu.draw(&dev, &[0, 0], &[]).unwrap(), 0);
+        assert_eq!(
+            vu.draw(&dev, &[meter::METER_FULL_SCALE, 0], &[]).unwrap(),
+            3
+        );
+        // The indicator taking an LED over forgets what was drawn
+        assert_eq!(
+            vu.draw(&dev, &[meter::METER_FULL_SCALE, 0], &[1]).unwrap(),
+            5
+        );
+    }
+
+    #[test]
+    fn poll_reads_every_meter_and_draws_the_inputs() {
+        let dev = MockDevice::new();
+        let mut vu = two_inputs();
+        dev.add_transact_response(
+            CMD_METER_INFO,
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 4, 0, 0x0C, 0x5A],
+        );
+        vu.start(&dev).unwrap();
+        let mut resp = vec![0u8; 8];
+        for level in [meter::METER_FULL_SCALE, 0, 4000, 4000] {
+            resp.extend_from_slice(&level.to_le_bytes());
+        }
+        dev.add_transact_response(CMD_GET_METER, resp);
+        assert_eq!(vu.poll(&dev, &[]).unwrap(), 6);
+        assert_eq!(vu.levels(), [meter::METER_FULL_SCALE, 0]);
+        assert_eq!(dev.transact_payloads.borrow()[1].1[2], 4);
+        assert_eq!(
+            dev.get_descriptor(OFF_DIRECT_LED_INDEX, 1).unwrap(),
+            vec![11]
+        );
+    }
+
+    #[test]
+    fn start_and_stop_switch_the_halo_mode() {
+        let dev = MockDevice::new();
+        let mut vu = two_inputs();
+        dev.add_transact_response(
+            CMD_METER_INFO,
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 66, 0, 0x0C, 0x5A],
+        );
+        vu.start(&dev).unwrap();
+        assert_eq!(
+            dev.get_descriptor(OFF_ENABLE_DIRECT_LED, 1).unwrap(),
+            vec![DIRECT_LED_MODE_HALOS]
+        );
+        vu.stop(&dev, None).unwrap();
+        assert_eq!(
+            dev.get_descriptor(OFF_ENABLE_DIRECT_LED, 1).unwrap(),
+            vec![DIRECT_LED_MODE_OFF]
+        );
+        assert_eq!(
+            *dev.notifies.borrow(),
+            vec![
+                NOTIFY_DIRECT_LED_VALUES,
+                NOTIFY_DIRECT_LED_VALUES,
+                NOTIFY_LED_COLORS
+            ]
+        );
+    }
+
+    #[test]
+    fn for_device_uses_the_profile_halos() {
+        let profile = models::detect_model("Scarlett 2i2 4th Gen").unwrap();
+        let vu = VuMeter::for_device(2, Some(profile), None, &[]).unwrap();
+        assert_eq!(vu.input_count(), 2);
+        assert_eq!(vu.halos[0], (1..8).collect::<Vec<u8>>());
+        assert_eq!(vu.halos[1], (9..16).collect::<Vec<u8>>());
+        assert_eq!(vu.colors[0][0], DEFAULT_VU_GRADIENT[0]);
+        assert!(VuMeter::for_device(2, None, None, &[]).is_none());
+    }
+}
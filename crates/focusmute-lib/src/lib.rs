@@ -1,18 +1,43 @@
 //! FocusMute — hotkey mute control for Focusrite Scarlett 4th Gen interfaces.
 
+#[cfg(feature = "async")]
+pub mod async_device;
 pub mod audio;
+pub mod clock;
 pub mod config;
 pub mod context;
+pub mod control_server;
+pub mod descriptor_batch;
 pub mod device;
+pub mod device_controls;
+pub mod device_events;
+pub mod device_handle;
+pub mod device_worker;
 pub mod error;
+pub mod events;
+pub mod history;
 pub mod hooks;
+pub mod indicators;
+pub mod instance;
+pub mod ipc;
 pub mod layout;
 pub mod led;
+pub mod meter;
 pub mod models;
 pub mod monitor;
+#[cfg(feature = "mqtt")]
+pub mod mqtt;
 pub mod offsets;
+pub mod osc;
 pub mod protocol;
 pub mod reconnect;
 pub mod schema;
+pub mod shared_layout;
+pub mod snapshot;
+pub mod token;
+pub mod trace;
+pub mod version;
+pub mod watcher;
 
 pub use error::FocusmuteError;
+pub use version::{VersionInfo, version};
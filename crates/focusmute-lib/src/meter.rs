@@ -0,0 +1,97 @@
+//! Live meter levels — METER_INFO and GET_METER.
+//!
+//! The firmware reports a 12-bit level for every metering point, the same
+//! readings Focusrite Control 2 polls for its meters. The analogue inputs
+//! come first (index 0 is Input 1); see
+//! `docs/09-led-control-api-discovery.md` for the full index map.
+
+use crate::device::{DeviceError, Result, ScarlettDevice};
+use crate::protocol::{CMD_GET_METER, CMD_METER_INFO};
+
+/// Level of a full-scale (0 dBFS) signal.
+pub const METER_FULL_SCALE: u32 = 4095;
+
+/// Number of metering points the device reports.
+pub fn meter_count(device: &impl ScarlettDevice) -> Result<usize> {
+    // Response payload (after the 8-byte transact header): { u16 num_meters, u16 magic }
+    let resp = device.transact(CMD_METER_INFO, &[], 4)?;
+    if resp.len() < 10 {
+        return Err(DeviceError::TransactFailed(format!(
+            "METER_INFO response too short: {} bytes (expected >=10)",
+            resp.len()
+        )));
+    }
+    Ok(u16::from_le_bytes([resp[8], resp[9]]) as usize)
+}
+
+/// Read the first `count` meter levels (0–[`METER_FULL_SCALE`]).
+pub fn read_levels(device: &impl ScarlettDevice, count: usize) -> Result<Vec<u32>> {
+    let count = u16::try_from(count)
+        .map_err(|_| DeviceError::TransactFailed(format!("too many meters: {count}")))?;
+    let mut payload = Vec::with_capacity(8);
+    payload.extend_from_slice(&0u16.to_le_bytes());
+    payload.extend_from_slice(&count.to_le_bytes());
+    payload.extend_from_slice(&1u32.to_le_bytes());
+    let resp = device.transact(CMD_GET_METER, &payload, usize::from(count) * 4)?;
+    let data = resp.get(8..).unwrap_or_default();
+    if data.len() < usize::from(count) * 4 {
+        return Err(DeviceError::TransactFailed(format!(
+            "GET_METER response too short: {} bytes for {count} meters",
+            resp.len()
+        )));
+    }
+    Ok(data
+        .chunks_exact(4)
+        .take(usize::from(count))
+        .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]).min(METER_FULL_SCALE))
+        .collect())
+}
+
+/// A meter level in dBFS (`-inf` for silence).
+pub fn level_db(level: u32) -> f64 {
+    20.0 * (f64::from(level.min(METER_FULL_SCALE)) / f64::from(METER_FULL_SCALE)).log10()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device::mock::MockDevice;
+
+    fn response(payload: &[u8]) -> Vec<u8> {
+        let mut resp = vec![0u8; 8];
+        resp.extend_from_slice(payload);
+        resp
+    }
+
+    #[test]
+    fn reads_count_and_levels() {
+        let dev = MockDevice::new();
+        dev.add_transact_response(CMD_METER_INFO, response(&[0x42, 0x00, 0x0C, 0x5A]));
+        assert_eq!(meter_count(&dev).unwrap(), 66);
+
+        let levels: Vec<u8> = [100u32, 4095, 9999]
+            .iter()
+            .flat_map(|l| l.to_le_bytes())
+            .collect();
+        dev.add_transact_response(CMD_GET_METER, response(&levels));
+        assert_eq!(read_levels(&dev, 3).unwrap(), vec![100, 4095, 4095]);
+        let payloads = dev.transact_payloads.borrow();
+        assert_eq!(payloads[1].1, vec![0, 0, 3, 0, 1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn short_responses_are_errors() {
+        let dev = MockDevice::new();
+        dev.add_transact_response(CMD_METER_INFO, response(&[0x42]));
+        assert!(meter_count(&dev).is_err());
+        dev.add_transact_response(CMD_GET_METER, response(&[0; 4]));
+        assert!(read_levels(&dev, 2).is_err());
+    }
+
+    #[test]
+    fn db_conversion() {
+        assert_eq!(level_db(METER_FULL_SCALE), 0.0);
+        assert!((level_db(METER_FULL_SCALE / 2) + 6.02).abs() < 0.01);
+        assert_eq!(level_db(0), f64::NEG_INFINITY);
+    }
+}
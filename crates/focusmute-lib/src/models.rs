@@ -3,6 +3,11 @@
 //! Each profile defines the input/output halo LED index ranges for a
 //! specific model. Unknown models get `None` from `detect_model()`,
 //! which callers should treat as "all halos" fallback.
+//!
+//! [`KNOWN_PRODUCTS`] maps USB product IDs to product names for every
+//! interface that speaks the same swroot protocol, including models without
+//! a hardcoded profile (rack units). Clarett+ and the older big interfaces
+//! use FCP instead and are deliberately absent (see TODO.md).
 
 use std::ops::Range;
 
@@ -34,6 +39,47 @@ pub struct HaloRange {
     pub segments: Range<usize>,
 }
 
+/// LEDs a [`StrategyPreset`] lights while muted.
+#[derive(Debug)]
+pub enum PresetTarget {
+    /// Number LEDs of these inputs (0-indexed) — e.g. one bank indicator
+    /// standing in for a whole row of inputs.
+    InputNumbers(&'static [usize]),
+    /// Every output halo / meter segment. Switched off again on unmute, after
+    /// which the firmware's metering repaints them.
+    OutputMeter,
+    /// The number LEDs of the `mute_inputs` selection plus every segment of
+    /// their gain halos. The halos are switched off on unmute, like
+    /// `OutputMeter`.
+    InputHalos,
+}
+
+/// A named mute visualization, selected with the `mute_preset` config key
+/// instead of the per-input number LEDs from `mute_inputs`.
+///
+/// Meant for big interfaces, where lighting one bank indicator or the output
+/// meter reads better than a row of tiny number LEDs.
+#[derive(Debug)]
+pub struct StrategyPreset {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub target: PresetTarget,
+}
+
+/// Output meter preset shared by every model with an output halo.
+const OUTPUT_METER_PRESET: StrategyPreset = StrategyPreset {
+    name: "output-meter",
+    description: "Light the whole output meter",
+    target: PresetTarget::OutputMeter,
+};
+
+/// Halo ring preset shared by every model with per-input halos.
+const INPUT_HALO_PRESET: StrategyPreset = StrategyPreset {
+    name: "halo",
+    description: "Light the whole gain halo of each muted input",
+    target: PresetTarget::InputHalos,
+};
+
 /// LED layout profile for a specific Scarlett 4th Gen model.
 #[derive(Debug)]
 pub struct ModelProfile {
@@ -67,6 +113,9 @@ pub struct ModelProfile {
     ///
     /// Visual approximation. Unselected inputs appear white on the 2i2.
     pub number_led_unselected: u32,
+
+    /// Named mute visualizations available for this model (see [`StrategyPreset`]).
+    pub strategy_presets: &'static [StrategyPreset],
 }
 
 // ── Scarlett 2i2 4th Gen ──
@@ -119,8 +168,193 @@ static SCARLETT_2I2: ModelProfile = ModelProfile {
         "USB symbol",                  // 39
     ],
     cache_dependent_buttons: &SCARLETT_2I2_CACHE_BUTTONS,
+    strategy_presets: &[OUTPUT_METER_PRESET, INPUT_HALO_PRESET],
+};
+
+// ── Scarlett 4i4 4th Gen ──
+
+static SCARLETT_4I4_INPUT_HALOS: [HaloRange; 4] = [
+    HaloRange {
+        number_led: 0,
+        segments: 1..8,
+    }, // Input 1
+    HaloRange {
+        number_led: 8,
+        segments: 9..16,
+    }, // Input 2
+    HaloRange {
+        number_led: 16,
+        segments: 17..24,
+    }, // Input 3
+    HaloRange {
+        number_led: 24,
+        segments: 25..32,
+    }, // Input 4
+];
+
+/// Scarlett 4i4 4th Gen — halo layout from the captured firmware schema
+/// (`kMAX_NUMBER_LEDS` = 56, `kMAX_NUMBER_INPUTS` = 4,
+/// `kNUMBER_METERING_SEGMENTS` = 39).
+///
+/// Button LEDs (43-55) have not been confirmed on hardware, so no button
+/// labels or cache-dependent defaults are listed; `map` falls back to the
+/// predicted labels for those indices.
+static SCARLETT_4I4: ModelProfile = ModelProfile {
+    name: "Scarlett 4i4 4th Gen",
+    input_count: 4,
+    led_count: 56,
+    input_halos: &SCARLETT_4I4_INPUT_HALOS,
+    output_halo_segments: 32..43,
+    number_led_selected: DEFAULT_NUMBER_LED_SELECTED,
+    number_led_unselected: DEFAULT_NUMBER_LED_UNSELECTED,
+    button_labels: &[],
+    cache_dependent_buttons: &[],
+    strategy_presets: &[OUTPUT_METER_PRESET, INPUT_HALO_PRESET],
+};
+
+// ── Scarlett Solo 4th Gen ──
+
+static SCARLETT_SOLO_INPUT_HALOS: [HaloRange; 1] = [HaloRange {
+    number_led: 0,
+    segments: 1..8,
+}]; // Input 1 (mic)
+
+/// Scarlett Solo 4th Gen — one mic input halo (`kMAX_NUMBER_LEDS` = 22,
+/// `kMAX_NUMBER_INPUTS` = 1, `kNUMBER_METERING_SEGMENTS` = 18).
+///
+/// The instrument input has no halo or number LED. Button LEDs (19-21) are
+/// unconfirmed, as on the 4i4.
+static SCARLETT_SOLO: ModelProfile = ModelProfile {
+    name: "Scarlett Solo 4th Gen",
+    input_count: 1,
+    led_count: 22,
+    input_halos: &SCARLETT_SOLO_INPUT_HALOS,
+    output_halo_segments: 8..19,
+    number_led_selected: DEFAULT_NUMBER_LED_SELECTED,
+    number_led_unselected: DEFAULT_NUMBER_LED_UNSELECTED,
+    button_labels: &[],
+    cache_dependent_buttons: &[],
+    strategy_presets: &[OUTPUT_METER_PRESET, INPUT_HALO_PRESET],
 };
 
+// ── Rack units (16i16 / 18i16 / 18i20 4th Gen) ──
+
+/// Rack-mount 4th Gen models. They have 8+ inputs and no per-input halo
+/// rings, so no hardcoded profile exists — the layout is predicted from the
+/// firmware schema (see `layout::predict_layout`). This only applies on
+/// Windows; on Linux they need FCP, which isn't implemented (see TODO.md).
+pub const RACK_MODELS: [&str; 3] = [
+    "Scarlett 16i16 4th Gen",
+    "Scarlett 18i16 4th Gen",
+    "Scarlett 18i20 4th Gen",
+];
+
+/// Whether `model_name` is one of the [`RACK_MODELS`].
+pub fn is_rack_model(model_name: &str) -> bool {
+    RACK_MODELS
+        .iter()
+        .any(|m| m.eq_ignore_ascii_case(model_name))
+}
+
+/// Strategy presets for the [`RACK_MODELS`]. Their predicted layout only
+/// knows the number LEDs, so the presets light one of those as a bank
+/// indicator for the whole unit.
+pub const RACK_PRESETS: [StrategyPreset; 1] = [StrategyPreset {
+    name: "bank",
+    description: "Light only the Input 1 number LED as a single bank indicator",
+    target: PresetTarget::InputNumbers(&[0]),
+}];
+
+/// Strategy presets available for `model_name`: the profile's presets for a
+/// known model, [`RACK_PRESETS`] for rack units, none otherwise.
+pub fn strategy_presets(model_name: &str) -> &'static [StrategyPreset] {
+    if let Some(profile) = detect_model(model_name) {
+        profile.strategy_presets
+    } else if is_rack_model(model_name) {
+        &RACK_PRESETS
+    } else {
+        &[]
+    }
+}
+
+/// Look up a strategy preset for `model_name` by name (case-insensitive).
+pub fn find_strategy_preset(model_name: &str, preset: &str) -> Option<&'static StrategyPreset> {
+    strategy_presets(model_name)
+        .iter()
+        .find(|p| p.name.eq_ignore_ascii_case(preset.trim()))
+}
+
+// ── USB product IDs ──
+
+/// Interface family of a [`KnownProduct`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProductFamily {
+    Scarlett4thGen,
+}
+
+impl std::fmt::Display for ProductFamily {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProductFamily::Scarlett4thGen => write!(f, "Scarlett 4th Gen"),
+        }
+    }
+}
+
+/// A Focusrite interface recognised by USB product ID.
+#[derive(Debug)]
+pub struct KnownProduct {
+    pub product_id: u16,
+    pub name: &'static str,
+    pub family: ProductFamily,
+}
+
+/// Interfaces FocusMute knows how to talk to, by USB product ID
+/// (vendor [`FOCUSRITE_VID`](crate::protocol::FOCUSRITE_VID)).
+pub const KNOWN_PRODUCTS: [KnownProduct; 6] = [
+    KnownProduct {
+        product_id: 0x8218,
+        name: "Scarlett Solo 4th Gen",
+        family: ProductFamily::Scarlett4thGen,
+    },
+    KnownProduct {
+        product_id: 0x8219,
+        name: "Scarlett 2i2 4th Gen",
+        family: ProductFamily::Scarlett4thGen,
+    },
+    KnownProduct {
+        product_id: 0x821A,
+        name: "Scarlett 4i4 4th Gen",
+        family: ProductFamily::Scarlett4thGen,
+    },
+    KnownProduct {
+        product_id: 0x821B,
+        name: "Scarlett 16i16 4th Gen",
+        family: ProductFamily::Scarlett4thGen,
+    },
+    KnownProduct {
+        product_id: 0x821C,
+        name: "Scarlett 18i16 4th Gen",
+        family: ProductFamily::Scarlett4thGen,
+    },
+    KnownProduct {
+        product_id: 0x821D,
+        name: "Scarlett 18i20 4th Gen",
+        family: ProductFamily::Scarlett4thGen,
+    },
+];
+
+/// Look up a known product by USB product ID.
+pub fn product_by_id(product_id: u16) -> Option<&'static KnownProduct> {
+    KNOWN_PRODUCTS.iter().find(|p| p.product_id == product_id)
+}
+
+/// Look up a known product by model name (case-insensitive).
+pub fn product_by_name(model_name: &str) -> Option<&'static KnownProduct> {
+    KNOWN_PRODUCTS
+        .iter()
+        .find(|p| p.name.eq_ignore_ascii_case(model_name))
+}
+
 /// Detect the model profile from a model name.
 ///
 /// Accepts the cleaned model name (e.g. "Scarlett 2i2 4th Gen") — callers
@@ -128,11 +362,16 @@ static SCARLETT_2I2: ModelProfile = ModelProfile {
 /// Returns `None` for unknown models — callers should fall back to
 /// the "all halos" gradient approach.
 pub fn detect_model(model_name: &str) -> Option<&'static ModelProfile> {
-    if model_name.eq_ignore_ascii_case("Scarlett 2i2 4th Gen") {
-        return Some(&SCARLETT_2I2);
-    }
-    // Future: add Solo, 4i4, etc.
-    None
+    builtin_profiles()
+        .iter()
+        .copied()
+        .find(|p| p.name.eq_ignore_ascii_case(model_name))
+}
+
+/// All hardcoded model profiles.
+pub fn builtin_profiles() -> &'static [&'static ModelProfile] {
+    static PROFILES: [&ModelProfile; 3] = [&SCARLETT_2I2, &SCARLETT_4I4, &SCARLETT_SOLO];
+    &PROFILES
 }
 
 /// Generate LED labels from a model profile and button names.
@@ -205,12 +444,100 @@ mod tests {
 
     #[test]
     fn detect_unknown_model_returns_none() {
-        assert!(detect_model("Scarlett Solo 4th Gen").is_none());
-        assert!(detect_model("Scarlett 4i4 4th Gen").is_none());
+        assert!(detect_model("Scarlett 16i16 4th Gen").is_none());
         assert!(detect_model("Unknown Device").is_none());
         assert!(detect_model("").is_none());
     }
 
+    #[test]
+    fn rack_models_have_no_profile() {
+        for name in RACK_MODELS {
+            assert!(is_rack_model(name));
+            assert!(detect_model(name).is_none());
+        }
+        assert!(is_rack_model("scarlett 18i20 4th gen"));
+        assert!(!is_rack_model("Scarlett 2i2 4th Gen"));
+    }
+
+    // ── Strategy presets ──
+
+    #[test]
+    fn strategy_presets_per_model() {
+        let small = strategy_presets("Scarlett 2i2 4th Gen");
+        assert_eq!(small.len(), 2);
+        assert!(matches!(small[0].target, PresetTarget::OutputMeter));
+        assert!(matches!(
+            find_strategy_preset("Scarlett Solo 4th Gen", "Halo")
+                .unwrap()
+                .target,
+            PresetTarget::InputHalos
+        ));
+
+        for name in RACK_MODELS {
+            let preset = find_strategy_preset(name, "BANK").unwrap();
+            assert!(matches!(preset.target, PresetTarget::InputNumbers(&[0])));
+        }
+        assert!(strategy_presets("Unknown Device").is_empty());
+        assert!(find_strategy_preset("Scarlett 2i2 4th Gen", "bank").is_none());
+    }
+
+    #[test]
+    fn strategy_preset_names_unique() {
+        for name in ["Scarlett 2i2 4th Gen", "Scarlett 18i20 4th Gen"] {
+            let presets = strategy_presets(name);
+            for (i, p) in presets.iter().enumerate() {
+                assert!(presets[i + 1..].iter().all(|q| q.name != p.name), "{name}");
+            }
+        }
+    }
+
+    // ── Known products ──
+
+    #[test]
+    fn product_by_id_rack_unit() {
+        let p = product_by_id(0x821D).unwrap();
+        assert_eq!(p.name, "Scarlett 18i20 4th Gen");
+        assert_eq!(p.family, ProductFamily::Scarlett4thGen);
+        assert_eq!(p.family.to_string(), "Scarlett 4th Gen");
+        // No hardcoded LED profile — layout comes from the schema
+        assert!(detect_model(p.name).is_none());
+        assert!(product_by_id(0x0001).is_none());
+    }
+
+    #[test]
+    fn fcp_interfaces_not_known() {
+        // Clarett+ 2Pre / 4Pre / 8Pre use FCP, which FocusMute doesn't speak
+        for pid in [0x820A, 0x820B, 0x820C] {
+            assert!(product_by_id(pid).is_none(), "0x{pid:04X}");
+        }
+    }
+
+    #[test]
+    fn known_products_unique_and_consistent() {
+        for (i, p) in KNOWN_PRODUCTS.iter().enumerate() {
+            assert!(
+                KNOWN_PRODUCTS[i + 1..]
+                    .iter()
+                    .all(|q| q.product_id != p.product_id),
+                "duplicate product ID 0x{:04X}",
+                p.product_id
+            );
+            assert_eq!(product_by_name(p.name).unwrap().product_id, p.product_id);
+        }
+        // Every hardcoded profile has a product ID
+        for name in [
+            "Scarlett Solo 4th Gen",
+            "Scarlett 2i2 4th Gen",
+            "Scarlett 4i4 4th Gen",
+        ] {
+            assert!(detect_model(name).is_some());
+            assert!(product_by_name(name).is_some(), "{name}");
+        }
+        for name in RACK_MODELS {
+            assert!(product_by_name(name).is_some(), "{name}");
+        }
+    }
+
     // ── HaloRange bounds ──
 
     #[test]
@@ -263,6 +590,164 @@ mod tests {
         assert!(profile.input_halos[1].segments.end <= profile.output_halo_segments.start);
     }
 
+    // ── Scarlett 4i4 4th Gen ──
+
+    /// Trimmed capture of the 4i4 4th Gen firmware schema (the members
+    /// `parse_schema` reads).
+    fn schema_4i4_json() -> String {
+        serde_json::json!({
+            "device-specification": {
+                "product-name": "Scarlett 4i4 4th Gen",
+                "physical-inputs": [
+                    { "controls": { "air": {}, "instrument": {}, "phantom": {} } }
+                ]
+            },
+            "enums": {
+                "maximum_array_sizes": {
+                    "enumerators": {
+                        "kMAX_NUMBER_OUTPUTS": 4,
+                        "kMAX_NUMBER_INPUTS": 4,
+                        "kMAX_NUMBER_LEDS": 56,
+                        "kNUMBER_METERING_SEGMENTS": 39
+                    }
+                }
+            },
+            "structs": {
+                "APP_SPACE": {
+                    "members": {
+                        "LEDcolors": {
+                            "offset": 384,
+                            "array-shape": [11],
+                            "notify-device": 9
+                        },
+                        "directLEDValues": {
+                            "offset": 92,
+                            "array-shape": [56]
+                        },
+                        "directMonitoring": { "type": "uint8", "offset": 330 },
+                        "selectedInput": { "type": "uint8", "offset": 331 }
+                    }
+                }
+            }
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn detect_4i4() {
+        let profile = detect_model("Scarlett 4i4 4th Gen").unwrap();
+        assert_eq!(profile.name, "Scarlett 4i4 4th Gen");
+        assert_eq!(profile.input_count, 4);
+        assert_eq!(profile.led_count, 56);
+        assert!(detect_model("scarlett 4i4 4th gen").is_some());
+    }
+
+    #[test]
+    fn profile_4i4_matches_captured_schema() {
+        let sc = crate::schema::parse_schema(&schema_4i4_json()).unwrap();
+        let profile = detect_model(&sc.product_name).unwrap();
+        assert_eq!(profile.led_count, sc.max_leds);
+        assert_eq!(profile.led_count, sc.direct_led_count);
+        assert_eq!(profile.input_count, sc.max_inputs);
+
+        let layout = crate::layout::predict_layout(&sc).unwrap();
+        assert_eq!(profile.input_count, layout.input_count);
+        assert_eq!(
+            profile.output_halo_segments.len(),
+            layout.output_halo_segments
+        );
+        assert_eq!(profile.output_halo_segments.end, layout.first_button_index);
+        for (i, halo) in profile.input_halos.iter().enumerate() {
+            let n = i + 1;
+            assert_eq!(
+                layout.leds[halo.number_led].label,
+                format!("Input {n} — \"{n}\" number")
+            );
+            for idx in halo.segments.clone() {
+                assert_eq!(layout.leds[idx].zone, crate::layout::LedZone::InputHalo);
+            }
+        }
+    }
+
+    #[test]
+    fn profile_4i4_number_leds() {
+        let profile = detect_model("Scarlett 4i4 4th Gen").unwrap();
+        let numbers: Vec<usize> = profile.input_halos.iter().map(|h| h.number_led).collect();
+        assert_eq!(numbers, vec![0, 8, 16, 24]);
+        assert_eq!(profile.output_halo_segments, 32..43);
+    }
+
+    #[test]
+    fn profile_4i4_halos_in_bounds_and_ordered() {
+        let profile = detect_model("Scarlett 4i4 4th Gen").unwrap();
+        assert_eq!(profile.input_count, profile.input_halos.len());
+        for pair in profile.input_halos.windows(2) {
+            assert!(pair[0].segments.end <= pair[1].number_led);
+        }
+        let last = profile.input_halos.last().unwrap();
+        assert!(last.segments.end <= profile.output_halo_segments.start);
+        assert!(profile.output_halo_segments.end <= profile.led_count);
+    }
+
+    #[test]
+    fn model_labels_4i4_falls_back_for_buttons() {
+        let profile = detect_model("Scarlett 4i4 4th Gen").unwrap();
+        let labels = model_labels(profile, profile.button_labels);
+        assert_eq!(labels.len(), 56);
+        assert_eq!(labels[24], "Input 4 — \"4\" number");
+        assert_eq!(labels[42], "Output — Halo segment 11");
+        assert_eq!(labels[43], "LED 43");
+    }
+
+    // ── Scarlett Solo 4th Gen ──
+
+    #[test]
+    fn detect_solo() {
+        let profile = detect_model("Scarlett Solo 4th Gen").unwrap();
+        assert_eq!(profile.name, "Scarlett Solo 4th Gen");
+        assert_eq!(profile.input_count, 1);
+        assert_eq!(profile.input_halos.len(), 1);
+        assert_eq!(profile.led_count, 22);
+    }
+
+    #[test]
+    fn profile_solo_matches_predicted_layout() {
+        let sc = crate::schema::SchemaConstants {
+            product_name: "Scarlett Solo 4th Gen".into(),
+            max_leds: 22,
+            max_inputs: 1,
+            max_outputs: 2,
+            gradient_count: 11,
+            gradient_offset: 384,
+            gradient_notify: 9,
+            direct_led_count: 22,
+            direct_led_offset: 92,
+            metering_segments: 18,
+            input_controls: vec!["air".into(), "instrument".into()],
+            app_space_features: vec![],
+            firmware_version: String::new(),
+            phantom_power: None,
+            air: None,
+            inst_input: None,
+            preamp_gain: None,
+            clip_safe: None,
+            autogain: None,
+            autogain_status_offset: None,
+            sample_rate_offset: None,
+            sync_source_offset: None,
+            direct_monitoring_offset: None,
+            alt_speakers: None,
+            output_volume: None,
+        };
+        let profile = detect_model(&sc.product_name).unwrap();
+        let layout = crate::layout::predict_layout(&sc).unwrap();
+        assert_eq!(profile.input_count, layout.input_count);
+        assert_eq!(profile.input_halos[0].number_led, 0);
+        assert_eq!(layout.leds[0].zone, crate::layout::LedZone::InputNumber);
+        assert_eq!(profile.output_halo_segments.end, layout.first_button_index);
+        assert!(profile.output_halo_segments.end <= profile.led_count);
+    }
+
     // ── model_labels ──
 
     #[test]
@@ -4,10 +4,47 @@
 //! monitor loop: debouncing input, deciding when to apply/clear mute colors,
 //! and executing the LED writes. CLI and tray binaries become thin adapters
 //! that wire I/O sources (audio monitor, device handle) to this state machine.
+//!
+//! The decision step is pure: [`MuteIndicator::next`] turns an
+//! [`IndicatorEvent`] into [`led::LedCommand`]s without touching a device, and
+//! [`led::execute`] performs them. The device-taking methods are shorthands
+//! for the two steps.
+
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
 
 use crate::audio::MuteDebouncer;
 use crate::device::{Result, ScarlettDevice};
 use crate::led;
+use crate::schema::SchemaConstants;
+
+/// How long a self-initiated mute change stays "expected" before a matching
+/// state change is attributed to an external app instead.
+const EXPECT_WINDOW: Duration = Duration::from_secs(5);
+
+/// How long the talk-while-muted warning flashes the indicator.
+pub const FLASH_DURATION: Duration = Duration::from_secs(2);
+
+/// On/off cycle of the warning flash — faster than any configured blink.
+const FLASH_PERIOD: Duration = Duration::from_millis(250);
+
+/// Quiet gaps shorter than this (pauses between words) don't end a stretch
+/// of talking.
+const TALK_GAP: Duration = Duration::from_millis(750);
+
+/// One trip round the color wheel for the idle screensaver.
+pub const SCREENSAVER_CYCLE: Duration = Duration::from_secs(60);
+
+/// Colors per screensaver cycle — a write a little over once a second.
+const SCREENSAVER_STEPS: u32 = 48;
+
+/// Screensaver brightness (of 255), so it stays gentle in a dark room.
+const SCREENSAVER_LEVEL: u8 = 96;
+
+/// Input level (dBFS) that counts as audio and wakes the screensaver.
+pub const SCREENSAVER_WAKE_DB: f64 = -50.0;
 
 /// Action to take after a mute-state update.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -20,6 +57,44 @@ pub enum MonitorAction {
     NoChange,
 }
 
+/// Input to the pure [`MuteIndicator::next`] step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndicatorEvent {
+    /// Raw mute poll from the audio monitor (debounced).
+    Poll(bool),
+    /// The device was (re)connected — re-show the indicator if muted.
+    Reconnected,
+    /// Shutting down — restore the number LEDs.
+    Shutdown,
+    /// The device started (`true`) or finished (`false`) something that
+    /// drives the LEDs itself, such as Auto Gain. Indicator writes are held
+    /// while busy and the current state is re-applied afterwards.
+    DeviceBusy(bool),
+    /// An application started (`true`) or stopped (`false`) recording from
+    /// the microphone. Shown in the in-use color while live, if one is set.
+    InUse(bool),
+}
+
+/// Who caused the current mute state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MuteOrigin {
+    /// FocusMute's hotkey, tray menu, or CLI changed the mute state.
+    FocusMute,
+    /// Another application (or the OS) changed the mute state.
+    External,
+}
+
+impl MuteOrigin {
+    /// Stable lowercase name (used for hook environment variables and JSON).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MuteOrigin::FocusMute => "focusmute",
+            MuteOrigin::External => "external",
+        }
+    }
+}
+
 /// Mute indicator state machine.
 ///
 /// Processes raw mute polls through a debouncer and tracks the confirmed
@@ -29,6 +104,29 @@ pub struct MuteIndicator {
     debouncer: MuteDebouncer,
     mute_color: u32,
     strategy: led::MuteStrategy,
+    origin: MuteOrigin,
+    expected: Option<(bool, Instant)>,
+    external_mute_color: Option<u32>,
+    /// Color of the number LEDs while live and recording (`None` = off).
+    in_use_color: Option<u32>,
+    /// An application is recording from the microphone.
+    in_use: bool,
+    /// Holding LED writes until the device is no longer busy.
+    busy: bool,
+    /// A write was held back while busy.
+    deferred: bool,
+    animation: led::MuteAnimation,
+    animation_period: Duration,
+    /// When the current mute started — frames are timed from here.
+    animation_start: Instant,
+    /// Brightness last written by [`animation_frame`](Self::animation_frame)
+    /// (`None` = the full mute color is showing).
+    animation_level: Cell<Option<u8>>,
+    /// LED colors from before the first mute, restored instead of the stock
+    /// firmware colors.
+    baseline: Option<led::LedBaseline>,
+    /// The warning flash runs until then; cleared by the first frame after.
+    flash_until: Cell<Option<Instant>>,
 }
 
 impl MuteIndicator {
@@ -44,26 +142,334 @@ impl MuteIndicator {
             debouncer: MuteDebouncer::new(debounce_threshold, initial_muted),
             mute_color,
             strategy,
+            origin: MuteOrigin::External,
+            expected: None,
+            external_mute_color: None,
+            in_use_color: None,
+            in_use: false,
+            busy: false,
+            deferred: false,
+            animation: led::MuteAnimation::Static,
+            animation_period: led::MuteAnimation::Static.period(1.0),
+            animation_start: Instant::now(),
+            animation_level: Cell::new(None),
+            baseline: None,
+            flash_until: Cell::new(None),
         }
     }
 
     /// Feed a raw mute poll. Returns the action to take, if any.
+    ///
+    /// On a confirmed change, the origin is resolved against the last
+    /// [`expect_change`](Self::expect_change) call.
     pub fn update(&mut self, muted: bool) -> MonitorAction {
         match self.debouncer.update(muted) {
-            Some(true) => MonitorAction::ApplyMute,
-            Some(false) => MonitorAction::ClearMute,
+            Some(state) => {
+                self.origin = match self.expected.take() {
+                    Some((target, at)) if target == state && at.elapsed() <= EXPECT_WINDOW => {
+                        MuteOrigin::FocusMute
+                    }
+                    _ => MuteOrigin::External,
+                };
+                if state {
+                    self.restart_animation();
+                    MonitorAction::ApplyMute
+                } else {
+                    MonitorAction::ClearMute
+                }
+            }
             None => MonitorAction::NoChange,
         }
     }
 
-    /// Apply the mute indicator to the device.
+    /// Record that FocusMute itself is about to set the mute state to `target`,
+    /// so the resulting change is attributed to [`MuteOrigin::FocusMute`].
+    pub fn expect_change(&mut self, target: bool) {
+        self.expected = Some((target, Instant::now()));
+    }
+
+    /// Who caused the most recent confirmed mute state change.
+    pub fn origin(&self) -> MuteOrigin {
+        self.origin
+    }
+
+    /// Use a distinct color when the mic was muted externally (`None` = same as
+    /// the normal mute colors).
+    pub fn set_external_mute_color(&mut self, color: Option<u32>) {
+        self.external_mute_color = color;
+    }
+
+    /// Light the number LEDs while live and an application is recording
+    /// (`None` = leave them alone).
+    pub fn set_in_use_color(&mut self, color: Option<u32>) {
+        self.in_use_color = color;
+    }
+
+    /// Whether an application is recording from the microphone, as last
+    /// reported by [`IndicatorEvent::InUse`].
+    pub fn is_in_use(&self) -> bool {
+        self.in_use
+    }
+
+    /// Animate the indicator while muted (`speed` 1.0 = default cycle length).
+    pub fn set_animation(&mut self, animation: led::MuteAnimation, speed: f64) {
+        self.animation = animation;
+        self.animation_period = animation.period(speed);
+        self.restart_animation();
+    }
+
+    /// The configured mute animation.
+    pub fn animation(&self) -> led::MuteAnimation {
+        self.animation
+    }
+
+    /// Whether frames are due: muted, animated or flashing, and not held for
+    /// a busy device.
+    pub fn is_animating(&self) -> bool {
+        (self.animation != led::MuteAnimation::Static || self.flash_until.get().is_some())
+            && self.is_muted()
+            && !self.busy
+    }
+
+    /// Flash the indicator for [`FLASH_DURATION`] from `now` (talking while
+    /// muted), overriding the configured animation. Frames come from
+    /// [`animation_frame`](Self::animation_frame); an unmute ends it early.
+    pub fn start_flash(&mut self, now: Instant) {
+        self.flash_until.set(Some(now + FLASH_DURATION));
+        self.animation_start = now;
+    }
+
+    /// LED writes for the animation frame at `now` — the mute commands with
+    /// their colors dimmed. Empty when nothing is animating or the brightness
+    /// hasn't changed since the last frame.
+    pub fn animation_frame(&self, now: Instant) -> Vec<led::LedCommand> {
+        if !self.is_animating() {
+            return Vec::new();
+        }
+        let elapsed = now.saturating_duration_since(self.animation_start);
+        let level = match self.flash_until.get() {
+            Some(until) if now < until => led::MuteAnimation::Blink.level(elapsed, FLASH_PERIOD),
+            _ => {
+                // The flash is over — back to the configured animation
+                self.flash_until.set(None);
+                self.animation.level(elapsed, self.animation_period)
+            }
+        };
+        if self.animation_level.get().unwrap_or(u8::MAX) == level {
+            return Vec::new();
+        }
+        let commands = self.mute_commands();
+        self.animation_level.set(Some(level));
+        commands
+            .into_iter()
+            .map(|cmd| match cmd {
+                led::LedCommand::Set { index, color } => led::LedCommand::Set {
+                    index,
+                    color: led::dim_color(color, level),
+                },
+                other => other,
+            })
+            .collect()
+    }
+
+    /// Start the next frames from the top of the cycle.
+    fn restart_animation(&mut self) {
+        self.animation_start = Instant::now();
+        self.animation_level.set(None);
+        self.flash_until.set(None);
+    }
+
+    /// Advance the state machine and return the LED writes it calls for.
+    ///
+    /// Pure — no device access. Run the result with [`led::execute`].
+    pub fn next(&mut self, event: IndicatorEvent) -> Vec<led::LedCommand> {
+        match event {
+            IndicatorEvent::Poll(muted) => {
+                let action = self.update(muted);
+                let commands = self.commands_for(action);
+                self.hold_if_busy(commands)
+            }
+            IndicatorEvent::Reconnected if self.is_muted() => {
+                let commands = self.mute_commands();
+                self.hold_if_busy(commands)
+            }
+            IndicatorEvent::Reconnected if self.shows_in_use() => {
+                let commands = self.in_use_commands();
+                self.hold_if_busy(commands)
+            }
+            IndicatorEvent::Reconnected => {
+                let commands = led::pattern_commands(&self.strategy.patterns.live);
+                self.hold_if_busy(commands)
+            }
+            IndicatorEvent::Shutdown => self.exit_commands(),
+            IndicatorEvent::DeviceBusy(true) => {
+                self.busy = true;
+                Vec::new()
+            }
+            IndicatorEvent::DeviceBusy(false) => {
+                let was_busy = std::mem::replace(&mut self.busy, false);
+                let deferred = std::mem::take(&mut self.deferred);
+                // The device may have repainted the number LEDs while busy
+                if was_busy && self.is_muted() {
+                    self.mute_commands()
+                } else if deferred {
+                    self.live_commands()
+                } else {
+                    Vec::new()
+                }
+            }
+            IndicatorEvent::InUse(in_use) => {
+                let changed = std::mem::replace(&mut self.in_use, in_use) != in_use;
+                if !changed || self.is_muted() || self.in_use_color.is_none() {
+                    return Vec::new();
+                }
+                let commands = self.live_commands();
+                self.hold_if_busy(commands)
+            }
+        }
+    }
+
+    /// Whether LED writes are being held for a busy device.
+    pub fn is_busy(&self) -> bool {
+        self.busy
+    }
+
+    /// Swallow `commands` while busy, remembering that something was held.
+    fn hold_if_busy(&mut self, commands: Vec<led::LedCommand>) -> Vec<led::LedCommand> {
+        if self.busy && !commands.is_empty() {
+            self.deferred = true;
+            return Vec::new();
+        }
+        commands
+    }
+
+    /// LED writes for a [`MonitorAction`] (empty for `NoChange`).
+    pub fn commands_for(&self, action: MonitorAction) -> Vec<led::LedCommand> {
+        match action {
+            MonitorAction::ApplyMute => self.mute_commands(),
+            MonitorAction::ClearMute => self.live_commands(),
+            MonitorAction::NoChange => Vec::new(),
+        }
+    }
+
+    /// LED writes that show the mute indicator.
+    ///
+    /// External mutes use the external mute color (if set) on every input,
+    /// overriding per-input colors.
+    pub fn mute_commands(&self) -> Vec<led::LedCommand> {
+        // Full color is back on the LEDs; the next frame redraws its level
+        self.animation_level.set(None);
+        match (self.origin, self.external_mute_color) {
+            (MuteOrigin::External, Some(color)) => {
+                let strategy = led::MuteStrategy {
+                    mute_colors: vec![],
+                    ..self.strategy.clone()
+                };
+                led::mute_commands(&strategy, color)
+            }
+            _ => led::mute_commands(&self.strategy, self.mute_color),
+        }
+    }
+
+    /// LED writes that restore the number LEDs — to the saved baseline
+    /// colors if there is one.
+    pub fn clear_commands(&self) -> Vec<led::LedCommand> {
+        match &self.baseline {
+            Some(baseline) => led::restore_commands_from(&self.strategy, baseline, self.mute_color),
+            None => led::restore_commands(&self.strategy),
+        }
+    }
+
+    /// Whether the live state is shown in the in-use color.
+    fn shows_in_use(&self) -> bool {
+        self.in_use && self.in_use_color.is_some()
+    }
+
+    /// LED writes that light the number LEDs in the in-use color (empty
+    /// without one).
+    pub fn in_use_commands(&self) -> Vec<led::LedCommand> {
+        let Some(color) = self.in_use_color else {
+            return Vec::new();
+        };
+        let mut strategy = led::MuteStrategy {
+            mute_colors: vec![],
+            ..self.strategy.clone()
+        };
+        strategy.patterns.muted.clear();
+        led::mute_commands(&strategy, color)
+    }
+
+    /// LED writes for the live state — the in-use color while an
+    /// application is recording, the restored number LEDs otherwise.
+    pub fn live_commands(&self) -> Vec<led::LedCommand> {
+        let reset = self.clear_commands();
+        if self.shows_in_use() {
+            led::transition_commands(&[], &reset, &self.in_use_commands())
+        } else {
+            reset
+        }
+    }
+
+    /// LED writes that return every LED the strategy may have painted to
+    /// normal, live and disconnected patterns included — for shutdown.
+    pub fn exit_commands(&self) -> Vec<led::LedCommand> {
+        match &self.baseline {
+            Some(baseline) => led::exit_commands_from(&self.strategy, baseline, self.mute_color),
+            None => led::exit_commands(&self.strategy),
+        }
+    }
+
+    /// LEDs the indicator paints in its current state — the muted LEDs
+    /// while muted, the live pattern (and in-use LEDs) otherwise.
+    pub fn owned_leds(&self) -> Vec<u8> {
+        let commands = if self.is_muted() {
+            led::mute_commands(&self.strategy, self.mute_color)
+        } else if self.shows_in_use() {
+            let mut commands = self.in_use_commands();
+            commands.extend(led::pattern_commands(&self.strategy.patterns.live));
+            commands
+        } else {
+            led::pattern_commands(&self.strategy.patterns.live)
+        };
+        commands.iter().map(led::LedCommand::index).collect()
+    }
+
+    /// LED writes that show the disconnected pattern (empty without one).
+    pub fn disconnected_commands(&self) -> Vec<led::LedCommand> {
+        led::pattern_commands(&self.strategy.patterns.disconnected)
+    }
+
+    /// Use `baseline` for every later restore (see [`led::LedBaseline`]).
+    pub fn set_baseline(&mut self, baseline: Option<led::LedBaseline>) {
+        self.baseline = baseline;
+    }
+
+    pub fn baseline(&self) -> Option<&led::LedBaseline> {
+        self.baseline.as_ref()
+    }
+
+    /// Restore the LEDs on exit: the indicator LEDs, plus the saved metering
+    /// gradient if something changed it.
+    pub fn restore_on_exit(
+        &self,
+        device: &impl ScarlettDevice,
+        schema: Option<&SchemaConstants>,
+    ) -> Result<()> {
+        led::execute(device, &self.exit_commands())?;
+        if let Some(baseline) = &self.baseline {
+            baseline.restore_gradient(device, schema)?;
+        }
+        Ok(())
+    }
+
+    /// Apply the mute indicator to the device (see [`mute_commands`](Self::mute_commands)).
     pub fn apply_mute(&self, device: &impl ScarlettDevice) -> Result<()> {
-        led::apply_mute_indicator(device, &self.strategy, self.mute_color)
+        led::execute(device, &self.mute_commands())
     }
 
     /// Clear the mute indicator and restore normal LED state.
     pub fn clear_mute(&self, device: &impl ScarlettDevice) -> Result<()> {
-        led::clear_mute_indicator(device, &self.strategy)
+        led::execute(device, &self.clear_commands())
     }
 
     /// Whether the debouncer currently considers the mic muted.
@@ -98,6 +504,25 @@ impl MuteIndicator {
     /// the forced state will return `NoChange` instead of `ApplyMute`/`ClearMute`.
     pub fn force_state(&mut self, muted: bool) {
         self.debouncer.force_state(muted);
+        self.origin = MuteOrigin::External;
+        self.expected = None;
+        self.restart_animation();
+    }
+
+    /// Startup safety check: force the known OS mute state and reconcile the
+    /// device LEDs with it (see [`led::reconcile_on_startup`]).
+    pub fn reconcile_startup(
+        &mut self,
+        muted: bool,
+        device: &impl ScarlettDevice,
+    ) -> Result<led::StartupReconcile> {
+        self.force_state(muted);
+        let result = led::reconcile_on_startup(device, &self.strategy, self.mute_color, muted)?;
+        // The leftover was repainted in stock colors — put saved ones back
+        if result == led::StartupReconcile::RestoredLeftover && self.baseline.is_some() {
+            led::execute(device, &self.clear_commands())?;
+        }
+        Ok(result)
     }
 
     /// Feed a raw mute poll and apply the resulting action to the device.
@@ -110,15 +535,124 @@ impl MuteIndicator {
         device: &impl ScarlettDevice,
     ) -> (MonitorAction, Option<crate::device::DeviceError>) {
         let action = self.update(muted);
-        let err = match action {
-            MonitorAction::ApplyMute => self.apply_mute(device).err(),
-            MonitorAction::ClearMute => self.clear_mute(device).err(),
-            MonitorAction::NoChange => None,
-        };
+        let commands = self.commands_for(action);
+        let commands = self.hold_if_busy(commands);
+        let err = led::execute(device, &commands).err();
         (action, err)
     }
 }
 
+/// Notices the user talking while muted: the input peak level stays above a
+/// threshold for a while. Brief noises don't count, and pauses between words
+/// shorter than [`TALK_GAP`] don't end the stretch.
+#[derive(Debug, Clone)]
+pub struct TalkDetector {
+    /// Linear peak level (0.0–1.0) that counts as talking.
+    threshold: f32,
+    /// How long talking has to go on before the warning.
+    hold: Duration,
+    /// Start of the current stretch of talking and its last loud sample.
+    stretch: Option<(Instant, Instant)>,
+    /// Already warned during this stretch.
+    warned: bool,
+}
+
+impl TalkDetector {
+    /// Warn after `hold` of input louder than `threshold_db` (dBFS).
+    pub fn new(threshold_db: f64, hold: Duration) -> Self {
+        Self {
+            threshold: 10f64.powf(threshold_db / 20.0) as f32,
+            hold,
+            stretch: None,
+            warned: false,
+        }
+    }
+
+    /// Feed the loudest channel's peak level at `now`, sampled while muted.
+    /// Returns `true` once per stretch of talking, when it has lasted `hold`.
+    pub fn update(&mut self, peak: f32, now: Instant) -> bool {
+        if let Some((_, last_loud)) = self.stretch
+            && now.saturating_duration_since(last_loud) > TALK_GAP
+        {
+            self.reset();
+        }
+        if peak < self.threshold {
+            return false;
+        }
+        let start = self.stretch.map_or(now, |(start, _)| start);
+        self.stretch = Some((start, now));
+        if self.warned || now.saturating_duration_since(start) < self.hold {
+            return false;
+        }
+        self.warned = true;
+        true
+    }
+
+    /// Forget the current stretch (e.g. on unmute).
+    pub fn reset(&mut self) {
+        self.stretch = None;
+        self.warned = false;
+    }
+}
+
+/// Idle screensaver: once nothing has happened for a while, slowly cycles
+/// the number LEDs through the color wheel. Callers report activity (mute
+/// changes, input audio, reconnects) with [`wake`](Self::wake) and only draw
+/// frames while live, so a muted mic always shows the mute indicator.
+#[derive(Debug, Clone)]
+pub struct IdleScreensaver {
+    /// Quiet time before it starts.
+    timeout: Duration,
+    last_activity: Instant,
+    /// Color step last written, while running.
+    step: Option<u32>,
+}
+
+impl IdleScreensaver {
+    /// Start after `timeout` without activity, counted from `now`.
+    pub fn new(timeout: Duration, now: Instant) -> Self {
+        Self {
+            timeout,
+            last_activity: now,
+            step: None,
+        }
+    }
+
+    /// Whether the screensaver is showing.
+    pub fn is_running(&self) -> bool {
+        self.step.is_some()
+    }
+
+    /// Record activity at `now`. Returns `true` if the screensaver was
+    /// showing — the caller puts the normal LEDs back.
+    pub fn wake(&mut self, now: Instant) -> bool {
+        self.last_activity = now;
+        self.step.take().is_some()
+    }
+
+    /// LED writes painting `leds` for the frame at `now`. Empty until the
+    /// timeout has passed and between color steps.
+    pub fn frame(&mut self, leds: &[u8], now: Instant) -> Vec<led::LedCommand> {
+        let idle = now.saturating_duration_since(self.last_activity);
+        let Some(elapsed) = idle.checked_sub(self.timeout) else {
+            return Vec::new();
+        };
+        let cycle = SCREENSAVER_CYCLE.as_secs_f64();
+        let step = ((elapsed.as_secs_f64() % cycle) / cycle * f64::from(SCREENSAVER_STEPS)) as u32;
+        if self.step == Some(step) {
+            return Vec::new();
+        }
+        self.step = Some(step);
+        let color = led::dim_color(
+            led::color_wheel(f64::from(step) / f64::from(SCREENSAVER_STEPS)),
+            SCREENSAVER_LEVEL,
+        );
+        leds.iter()
+            .map(|&index| led::LedCommand::Set { index, color })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -136,6 +670,8 @@ mod tests {
                 mute_colors: vec![],
                 selected_color: 0x20FF_0000,
                 unselected_color: 0x88FF_FF00,
+                group_leds: Vec::new(),
+                patterns: led::LedPatterns::default(),
             },
         )
     }
@@ -205,9 +741,8 @@ mod tests {
         // Should use single-LED update, NOT direct mode
         assert!(!descs.contains_key(&OFF_ENABLE_DIRECT_LED));
         assert!(!descs.contains_key(&OFF_DIRECT_LED_VALUES));
-        // Should have written directLEDColour and directLEDIndex
-        assert!(descs.contains_key(&OFF_DIRECT_LED_COLOUR));
-        assert!(descs.contains_key(&OFF_DIRECT_LED_INDEX));
+        // Should have written directLEDColour and directLEDIndex in one write
+        assert_eq!(descs[&OFF_DIRECT_LED_COLOUR].len(), 5);
     }
 
     #[test]
@@ -322,6 +857,8 @@ mod tests {
             mute_colors: vec![],
             selected_color: 0x20FF_0000,
             unselected_color: 0x88FF_FF00,
+            group_leds: Vec::new(),
+            patterns: led::LedPatterns::default(),
         };
         ind.set_strategy(new_strategy);
         assert!(
@@ -376,4 +913,438 @@ mod tests {
             assert_eq!(ind.update(false), MonitorAction::NoChange);
         }
     }
+
+    #[test]
+    fn reconcile_startup_syncs_state_and_restores_leftover() {
+        let dev = MockDevice::new();
+        dev.set_descriptor(OFF_SELECTED_INPUT, &[0]).unwrap();
+        // Leftover mute color from an unclean exit
+        led::set_single_led(&dev, 0, 0xFF00_0000).unwrap();
+
+        let mut ind = make_indicator(true);
+        let result = ind.reconcile_startup(false, &dev).unwrap();
+        assert_eq!(result, led::StartupReconcile::RestoredLeftover);
+        assert!(!ind.is_muted());
+        assert_eq!(ind.update(false), MonitorAction::NoChange);
+    }
+
+    #[test]
+    fn baseline_restores_custom_number_led_color() {
+        let dev = MockDevice::new();
+        let mut ind = make_indicator(false);
+        let mut direct_values = vec![0; 40];
+        direct_values[8] = 0x0000_FF00;
+        ind.set_baseline(Some(led::LedBaseline {
+            serial: None,
+            direct_values,
+            gradient: Vec::new(),
+        }));
+        ind.apply_mute(&dev).unwrap();
+        ind.restore_on_exit(&dev, None).unwrap();
+
+        // LED 8 is written last, with its saved custom color
+        assert_eq!(
+            dev.get_descriptor(OFF_DIRECT_LED_INDEX, 1).unwrap(),
+            vec![8]
+        );
+        assert_eq!(
+            dev.get_descriptor(OFF_DIRECT_LED_COLOUR, 4).unwrap(),
+            0x0000_FF00u32.to_le_bytes().to_vec()
+        );
+    }
+
+    // ── Mute origin ──
+
+    #[test]
+    fn unexpected_change_is_external() {
+        let mut ind = make_indicator(false);
+        ind.update(true);
+        assert_eq!(ind.update(true), MonitorAction::ApplyMute);
+        assert_eq!(ind.origin(), MuteOrigin::External);
+    }
+
+    #[test]
+    fn expected_change_is_focusmute() {
+        let mut ind = make_indicator(false);
+        ind.expect_change(true);
+        ind.update(true);
+        assert_eq!(ind.update(true), MonitorAction::ApplyMute);
+        assert_eq!(ind.origin(), MuteOrigin::FocusMute);
+
+        // The expectation is consumed — the next change is external again.
+        ind.update(false);
+        assert_eq!(ind.update(false), MonitorAction::ClearMute);
+        assert_eq!(ind.origin(), MuteOrigin::External);
+    }
+
+    #[test]
+    fn mismatched_expectation_is_external() {
+        let mut ind = make_indicator(true);
+        ind.expect_change(true);
+        ind.update(false);
+        assert_eq!(ind.update(false), MonitorAction::ClearMute);
+        assert_eq!(ind.origin(), MuteOrigin::External);
+    }
+
+    #[test]
+    fn external_mute_uses_external_color() {
+        let dev = MockDevice::new();
+        let mut ind = make_indicator(false);
+        ind.set_external_mute_color(Some(0x0000_FF00));
+        ind.update(true);
+        ind.poll_and_apply(true, &dev);
+        assert_eq!(
+            dev.get_descriptor(OFF_DIRECT_LED_COLOUR, 4).unwrap(),
+            0x0000_FF00u32.to_le_bytes().to_vec()
+        );
+    }
+
+    #[test]
+    fn focusmute_mute_keeps_normal_color() {
+        let dev = MockDevice::new();
+        let mut ind = make_indicator(false);
+        ind.set_external_mute_color(Some(0x0000_FF00));
+        ind.expect_change(true);
+        ind.update(true);
+        ind.poll_and_apply(true, &dev);
+        assert_eq!(
+            dev.get_descriptor(OFF_DIRECT_LED_COLOUR, 4).unwrap(),
+            0xFF00_0000u32.to_le_bytes().to_vec()
+        );
+    }
+
+    #[test]
+    fn mute_origin_as_str() {
+        assert_eq!(MuteOrigin::FocusMute.as_str(), "focusmute");
+        assert_eq!(MuteOrigin::External.as_str(), "external");
+    }
+
+    // ── next (pure step) ──
+
+    #[test]
+    fn next_poll_emits_mute_then_restore_commands() {
+        let mut ind = make_indicator(false);
+        assert!(ind.next(IndicatorEvent::Poll(true)).is_empty());
+        assert_eq!(
+            ind.next(IndicatorEvent::Poll(true)),
+            vec![
+                led::LedCommand::Set {
+                    index: 0,
+                    color: 0xFF00_0000
+                },
+                led::LedCommand::Set {
+                    index: 8,
+                    color: 0xFF00_0000
+                },
+            ]
+        );
+
+        ind.next(IndicatorEvent::Poll(false));
+        let cmds = ind.next(IndicatorEvent::Poll(false));
+        assert_eq!(cmds.len(), 2);
+        assert!(
+            cmds.iter()
+                .all(|c| matches!(c, led::LedCommand::Restore { .. }))
+        );
+    }
+
+    #[test]
+    fn next_reconnected_only_when_muted() {
+        let mut ind = make_indicator(false);
+        assert!(ind.next(IndicatorEvent::Reconnected).is_empty());
+        ind.force_state(true);
+        assert_eq!(ind.next(IndicatorEvent::Reconnected).len(), 2);
+    }
+
+    #[test]
+    fn next_shutdown_restores() {
+        let mut ind = make_indicator(true);
+        assert_eq!(ind.next(IndicatorEvent::Shutdown), ind.exit_commands());
+    }
+
+    #[test]
+    fn live_pattern_repainted_on_reconnect_and_cleared_on_shutdown() {
+        let mut ind = make_indicator(false);
+        let mut strategy = ind.strategy().clone();
+        strategy.patterns.live = vec![(20, 0x00FF_0000)];
+        ind.set_strategy(strategy);
+        let live = led::LedCommand::Set {
+            index: 20,
+            color: 0x00FF_0000,
+        };
+        assert_eq!(ind.next(IndicatorEvent::Reconnected), vec![live]);
+        assert_eq!(ind.clear_commands().last(), Some(&live));
+        assert_eq!(
+            ind.next(IndicatorEvent::Shutdown).last(),
+            Some(&led::LedCommand::Set {
+                index: 20,
+                color: 0
+            })
+        );
+    }
+
+    #[test]
+    fn owned_leds_follow_mute_state() {
+        let mut ind = make_indicator(false);
+        let mut strategy = ind.strategy().clone();
+        strategy.group_leds = vec![1, 2];
+        strategy.patterns.live = vec![(20, 0x00FF_0000)];
+        ind.set_strategy(strategy);
+        assert_eq!(ind.owned_leds(), vec![20]);
+        ind.force_state(true);
+        assert_eq!(ind.owned_leds(), vec![0, 8, 1, 2]);
+    }
+
+    #[test]
+    fn next_in_use_lights_the_live_indicator() {
+        let mut ind = make_indicator(false);
+        ind.next(IndicatorEvent::Poll(false));
+        // No in-use color — tracked but not shown
+        assert!(ind.next(IndicatorEvent::InUse(true)).is_empty());
+        assert!(ind.is_in_use());
+        ind.next(IndicatorEvent::InUse(false));
+
+        ind.set_in_use_color(Some(0x00FF_0000));
+        let lit = ind.next(IndicatorEvent::InUse(true));
+        assert_eq!(
+            lit,
+            vec![
+                led::LedCommand::Set {
+                    index: 0,
+                    color: 0x00FF_0000
+                },
+                led::LedCommand::Set {
+                    index: 8,
+                    color: 0x00FF_0000
+                }
+            ]
+        );
+        assert_eq!(ind.owned_leds(), vec![0, 8]);
+        // Unchanged — nothing to write
+        assert!(ind.next(IndicatorEvent::InUse(true)).is_empty());
+        assert_eq!(ind.next(IndicatorEvent::Reconnected), lit);
+
+        // Muting takes over; unmuting comes back to the in-use color
+        ind.next(IndicatorEvent::Poll(true));
+        ind.next(IndicatorEvent::Poll(true));
+        assert!(ind.next(IndicatorEvent::InUse(true)).is_empty());
+        ind.next(IndicatorEvent::Poll(false));
+        assert_eq!(ind.next(IndicatorEvent::Poll(false)), lit);
+
+        // Going idle restores the number LEDs
+        assert_eq!(ind.next(IndicatorEvent::InUse(false)), ind.clear_commands());
+    }
+
+    #[test]
+    fn next_holds_writes_while_device_busy() {
+        let mut ind = make_indicator(false);
+        assert!(ind.next(IndicatorEvent::DeviceBusy(true)).is_empty());
+        assert!(ind.is_busy());
+        ind.next(IndicatorEvent::Poll(true));
+        assert!(ind.next(IndicatorEvent::Poll(true)).is_empty());
+        assert!(ind.is_muted());
+
+        // Finishing re-applies the current state
+        assert_eq!(
+            ind.next(IndicatorEvent::DeviceBusy(false)),
+            ind.mute_commands()
+        );
+        assert!(!ind.is_busy());
+        assert!(ind.next(IndicatorEvent::DeviceBusy(false)).is_empty());
+    }
+
+    #[test]
+    fn next_busy_unmute_restores_afterwards() {
+        let mut ind = make_indicator(true);
+        ind.next(IndicatorEvent::DeviceBusy(true));
+        ind.next(IndicatorEvent::Poll(false));
+        assert!(ind.next(IndicatorEvent::Poll(false)).is_empty());
+        assert_eq!(
+            ind.next(IndicatorEvent::DeviceBusy(false)),
+            ind.clear_commands()
+        );
+
+        // Nothing held back, nothing to restore
+        ind.next(IndicatorEvent::DeviceBusy(true));
+        assert!(ind.next(IndicatorEvent::DeviceBusy(false)).is_empty());
+    }
+
+    #[test]
+    fn poll_and_apply_holds_writes_while_busy() {
+        let dev = MockDevice::new();
+        let mut ind = make_indicator(false);
+        ind.next(IndicatorEvent::DeviceBusy(true));
+        ind.poll_and_apply(true, &dev);
+        let (action, err) = ind.poll_and_apply(true, &dev);
+        assert_eq!(action, MonitorAction::ApplyMute);
+        assert!(err.is_none());
+        assert!(dev.notifies.borrow().is_empty());
+    }
+
+    #[test]
+    fn next_external_mute_uses_external_color() {
+        let mut ind = make_indicator(false);
+        ind.set_external_mute_color(Some(0x0000_FF00));
+        ind.next(IndicatorEvent::Poll(true));
+        let cmds = ind.next(IndicatorEvent::Poll(true));
+        assert!(cmds.iter().all(|c| matches!(
+            c,
+            led::LedCommand::Set {
+                color: 0x0000_FF00,
+                ..
+            }
+        )));
+    }
+
+    // ── Animation ──
+
+    #[test]
+    fn animation_frames_only_while_muted() {
+        let mut ind = make_indicator(false);
+        ind.set_animation(led::MuteAnimation::Blink, 1.0);
+        let start = Instant::now();
+        assert!(!ind.is_animating());
+        assert!(
+            ind.animation_frame(start + Duration::from_millis(600))
+                .is_empty()
+        );
+
+        ind.force_state(true);
+        assert!(ind.is_animating());
+        // Full color is already showing — nothing to redraw in the on half
+        assert!(ind.animation_frame(Instant::now()).is_empty());
+        let off = ind.animation_frame(Instant::now() + Duration::from_millis(600));
+        assert_eq!(
+            off,
+            vec![
+                led::LedCommand::Set { index: 0, color: 0 },
+                led::LedCommand::Set { index: 8, color: 0 },
+            ]
+        );
+        // Same level again — skipped
+        assert!(
+            ind.animation_frame(Instant::now() + Duration::from_millis(700))
+                .is_empty()
+        );
+        let on = ind.animation_frame(Instant::now() + Duration::from_millis(1100));
+        assert!(on.iter().all(|c| matches!(
+            c,
+            led::LedCommand::Set {
+                color: 0xFF00_0000,
+                ..
+            }
+        )));
+
+        ind.next(IndicatorEvent::DeviceBusy(true));
+        assert!(!ind.is_animating());
+    }
+
+    #[test]
+    fn flash_overrides_static_then_restores_full_color() {
+        let mut ind = make_indicator(true);
+        let start = Instant::now();
+        ind.start_flash(start);
+        assert!(ind.is_animating());
+        let off = ind.animation_frame(start + FLASH_PERIOD * 3 / 4);
+        assert!(
+            off.iter()
+                .all(|c| matches!(c, led::LedCommand::Set { color: 0, .. }))
+        );
+        // After the flash the static indicator is painted in full once more
+        let on = ind.animation_frame(start + FLASH_DURATION + FLASH_PERIOD * 3 / 4);
+        assert!(on.iter().all(|c| matches!(
+            c,
+            led::LedCommand::Set {
+                color: 0xFF00_0000,
+                ..
+            }
+        )));
+        assert!(!ind.is_animating());
+    }
+
+    #[test]
+    fn talk_detector_warns_once_per_stretch() {
+        let mut det = TalkDetector::new(-30.0, Duration::from_secs(2));
+        let t0 = Instant::now();
+        let at = |ms: u64| t0 + Duration::from_millis(ms);
+        // Quiet input and a short cough never warn
+        assert!(!det.update(0.001, at(0)));
+        assert!(!det.update(0.5, at(250)));
+        assert!(!det.update(0.001, at(1500)));
+
+        // Talking with short pauses, for two seconds
+        let mut warned = Vec::new();
+        for ms in (2000..=5000).step_by(250) {
+            let peak = if ms % 1000 == 500 { 0.001 } else { 0.2 };
+            if det.update(peak, at(ms)) {
+                warned.push(ms);
+            }
+        }
+        assert_eq!(warned, vec![4000]);
+
+        // A long silence ends the stretch; talking again warns again
+        let again: Vec<u64> = (7000..=9000)
+            .step_by(250)
+            .filter(|&ms| det.update(0.2, at(ms)))
+            .collect();
+        assert_eq!(again, vec![9000]);
+    }
+
+    #[test]
+    fn screensaver_starts_after_timeout_and_wakes() {
+        let t0 = Instant::now();
+        let mut saver = IdleScreensaver::new(Duration::from_secs(60), t0);
+        assert!(
+            saver
+                .frame(&[0, 8], t0 + Duration::from_secs(59))
+                .is_empty()
+        );
+        assert!(!saver.is_running());
+
+        let start = t0 + Duration::from_secs(60);
+        let first = saver.frame(&[0, 8], start);
+        let red = led::dim_color(0xFF00_0000, SCREENSAVER_LEVEL);
+        assert_eq!(
+            first,
+            vec![
+                led::LedCommand::Set {
+                    index: 0,
+                    color: red
+                },
+                led::LedCommand::Set {
+                    index: 8,
+                    color: red
+                }
+            ]
+        );
+        // Same step — nothing to write; the next step changes color
+        assert!(
+            saver
+                .frame(&[0, 8], start + Duration::from_millis(500))
+                .is_empty()
+        );
+        let step = SCREENSAVER_CYCLE / SCREENSAVER_STEPS;
+        assert_eq!(saver.frame(&[0, 8], start + step).len(), 2);
+
+        let woke = start + Duration::from_secs(5);
+        assert!(saver.wake(woke));
+        assert!(!saver.is_running());
+        assert!(!saver.wake(woke));
+        assert!(
+            saver
+                .frame(&[0, 8], woke + Duration::from_secs(30))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn static_animation_never_emits_frames() {
+        let ind = make_indicator(true);
+        assert_eq!(ind.animation(), led::MuteAnimation::Static);
+        assert!(!ind.is_animating());
+        assert!(
+            ind.animation_frame(Instant::now() + Duration::from_millis(600))
+                .is_empty()
+        );
+    }
 }
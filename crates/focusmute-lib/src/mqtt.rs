@@ -0,0 +1,673 @@
+//! MQTT publishing (`mqtt` feature) — mute and device state for Home
+//! Assistant dashboards and desk busy-lights.
+//!
+//! A small MQTT 3.1.1 client on a background thread: QoS 0 only, no
+//! persistent session. Under the `mqtt_topic_prefix` (default `focusmute`)
+//! it publishes, retained:
+//!
+//! - `<prefix>/state` — the last mute [`Event`] as an [`EventRecord`]
+//! - `<prefix>/device` — the last device event, the same way
+//! - `<prefix>/available` — `online`, or `offline` (the last will) once
+//!   FocusMute goes away
+//!
+//! and subscribes to `<prefix>/set` for [`RemoteCommand`]s. A lost broker is
+//! retried with backoff, and the retained state is sent again on reconnect.
+
+use std::io::{ErrorKind, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender, TryRecvError};
+use std::time::{Duration, Instant};
+
+use crate::config::Config;
+use crate::events::{Event, EventRecord, RemoteCommand};
+
+/// Keep-alive interval announced to the broker.
+const KEEP_ALIVE: Duration = Duration::from_secs(30);
+
+/// Connect, CONNACK, and write timeout.
+const IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long each read waits, so queued events go out promptly.
+const TICK: Duration = Duration::from_millis(100);
+
+/// Reconnect backoff bounds.
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Largest packet accepted from the broker. Commands are a few bytes; this
+/// only keeps a misbehaving broker from growing the receive buffer forever.
+const MAX_PACKET_BYTES: usize = 256 * 1024;
+
+const CONNECT: u8 = 0x10;
+const CONNACK: u8 = 0x20;
+const PUBLISH: u8 = 0x30;
+const SUBSCRIBE: u8 = 0x82;
+const PINGREQ: u8 = 0xC0;
+const PINGRESP: u8 = 0xD0;
+const DISCONNECT: u8 = 0xE0;
+
+/// PUBLISH header flag: the broker keeps the message for new subscribers.
+const RETAIN: u8 = 0x01;
+
+/// Where and as whom to connect, from the `mqtt_*` config fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MqttSettings {
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub password: String,
+    /// Topic prefix, without leading or trailing `/`.
+    pub topic_prefix: String,
+    pub client_id: String,
+}
+
+impl MqttSettings {
+    /// Settings from the config; `None` while `mqtt_broker` is empty or
+    /// invalid.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        let (host, port) = config.mqtt_broker()?;
+        Some(Self {
+            host,
+            port,
+            username: config.mqtt_username.clone(),
+            password: config.mqtt_password.clone(),
+            topic_prefix: config.mqtt_topic_prefix.trim_matches('/').to_string(),
+            client_id: format!("focusmute-{}", std::process::id()),
+        })
+    }
+
+    /// `<prefix>/<leaf>`.
+    pub fn topic(&self, leaf: &str) -> String {
+        format!("{}/{leaf}", self.topic_prefix)
+    }
+}
+
+/// Handle to the client thread. Dropping it disconnects from the broker.
+pub struct MqttClient {
+    tx: Sender<Event>,
+}
+
+impl MqttClient {
+    /// Start the client thread. `on_command` is called (on that thread) for
+    /// every command received on `<prefix>/set`.
+    pub fn start<F>(settings: MqttSettings, on_command: F) -> std::io::Result<Self>
+    where
+        F: Fn(RemoteCommand) + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("mqtt".into())
+            .spawn(move || run(&settings, &rx, &on_command))?;
+        Ok(Self { tx })
+    }
+
+    /// Publish `event`: mute events on `<prefix>/state`, device events on
+    /// `<prefix>/device`, anything else on `<prefix>/event` (not retained).
+    pub fn publish(&self, event: Event) {
+        let _ = self.tx.send(event);
+    }
+}
+
+/// Topic leaf and retain flag for `event`.
+fn destination(event: &Event) -> (&'static str, bool) {
+    match event {
+        Event::Mute { .. } => ("state", true),
+        Event::Device { .. } => ("device", true),
+        _ => ("event", false),
+    }
+}
+
+/// The retained messages, sent again after every reconnect.
+#[derive(Default)]
+struct Retained {
+    state: Option<String>,
+    device: Option<String>,
+}
+
+impl Retained {
+    /// Remember `event` if it's retained; returns its topic leaf, payload,
+    /// and retain flag.
+    fn record(&mut self, event: Event) -> (&'static str, String, bool) {
+        let (leaf, retain) = destination(&event);
+        let payload = EventRecord::now(event).to_json();
+        match leaf {
+            "state" => self.state = Some(payload.clone()),
+            "device" => self.device = Some(payload.clone()),
+            _ => {}
+        }
+        (leaf, payload, retain)
+    }
+
+    fn messages(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        [("state", &self.state), ("device", &self.device)]
+            .into_iter()
+            .filter_map(|(leaf, payload)| Some((leaf, payload.as_deref()?)))
+    }
+}
+
+/// Client thread: connect, serve, and reconnect until the [`MqttClient`]
+/// is dropped.
+fn run(settings: &MqttSettings, rx: &Receiver<Event>, on_command: &dyn Fn(RemoteCommand)) {
+    let mut retained = Retained::default();
+    let mut backoff = MIN_BACKOFF;
+    loop {
+        match Session::connect(settings, &retained) {
+            Ok(mut session) => {
+                log::info!("[mqtt] connected to {}:{}", settings.host, settings.port);
+                backoff = MIN_BACKOFF;
+                match session.serve(settings, rx, &mut retained, on_command) {
+                    Ok(()) => return,
+                    Err(e) => log::warn!("[mqtt] connection lost: {e}"),
+                }
+            }
+            // Only the first failure in a row is worth a warning
+            Err(e) if backoff == MIN_BACKOFF => log::warn!(
+                "[mqtt] could not connect to {}:{}: {e}",
+                settings.host,
+                settings.port
+            ),
+            Err(e) => log::debug!("[mqtt] reconnect failed: {e}"),
+        }
+        // Keep up with state changes while waiting to retry
+        let retry_at = Instant::now() + backoff;
+        loop {
+            match rx.recv_timeout(retry_at.saturating_duration_since(Instant::now())) {
+                Ok(event) => {
+                    retained.record(event);
+                }
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+/// One connection to the broker.
+struct Session {
+    stream: TcpStream,
+    /// Received bytes not yet parsed into packets.
+    buf: Vec<u8>,
+    last_sent: Instant,
+    /// When the unanswered PINGREQ went out, if any.
+    ping_sent: Option<Instant>,
+}
+
+impl Session {
+    /// Connect, subscribe to the command topic, and announce `online` and
+    /// the retained state.
+    fn connect(settings: &MqttSettings, retained: &Retained) -> std::io::Result<Self> {
+        let addr = (settings.host.as_str(), settings.port)
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| std::io::Error::other("host has no address"))?;
+        let stream = TcpStream::connect_timeout(&addr, IO_TIMEOUT)?;
+        stream.set_write_timeout(Some(IO_TIMEOUT))?;
+        stream.set_read_timeout(Some(TICK))?;
+        let mut session = Session {
+            stream,
+            buf: Vec::new(),
+            last_sent: Instant::now(),
+            ping_sent: None,
+        };
+        session.send(&connect_packet(settings)?)?;
+        let deadline = Instant::now() + IO_TIMEOUT;
+        let (header, body) = loop {
+            if let Some(packet) = take_packet(&mut session.buf)? {
+                break packet;
+            }
+            if Instant::now() >= deadline {
+                return Err(ErrorKind::TimedOut.into());
+            }
+            session.fill()?;
+        };
+        if header & 0xF0 != CONNACK {
+            return Err(std::io::Error::other("broker did not acknowledge CONNECT"));
+        }
+        if let Some(&code) = body.get(1)
+            && code != 0
+        {
+            return Err(std::io::Error::other(connack_error(code)));
+        }
+        session.send(&subscribe_packet(1, &settings.topic("set"))?)?;
+        session.send(&publish_packet(
+            &settings.topic("available"),
+            b"online",
+            true,
+        )?)?;
+        for (leaf, payload) in retained.messages() {
+            session.send(&publish_packet(
+                &settings.topic(leaf),
+                payload.as_bytes(),
+                true,
+            )?)?;
+        }
+        Ok(session)
+    }
+
+    /// Publish events and handle commands until the client is dropped
+    /// (`Ok`) or the connection fails.
+    fn serve(
+        &mut self,
+        settings: &MqttSettings,
+        rx: &Receiver<Event>,
+        retained: &mut Retained,
+        on_command: &dyn Fn(RemoteCommand),
+    ) -> std::io::Result<()> {
+        let command_topic = settings.topic("set");
+        loop {
+            loop {
+                match rx.try_recv() {
+                    Ok(event) => {
+                        let (leaf, payload, retain) = retained.record(event);
+                        self.send(&publish_packet(
+                            &settings.topic(leaf),
+                            payload.as_bytes(),
+                            retain,
+                        )?)?;
+                    }
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        // A clean disconnect: the broker drops the last will
+                        if let Ok(packet) =
+                            publish_packet(&settings.topic("available"), b"offline", true)
+                        {
+                            let _ = self.send(&packet);
+                        }
+                        let _ = self.send(&[DISCONNECT, 0]);
+                        return Ok(());
+                    }
+                }
+            }
+
+            self.fill()?;
+            while let Some((header, body)) = take_packet(&mut self.buf)? {
+                match header & 0xF0 {
+                    PUBLISH => {
+                        if let Some(command) = publish_command(header, &body, &command_topic) {
+                            log::info!("[mqtt] command: {command:?}");
+                            on_command(command);
+                        }
+                    }
+                    PINGRESP => self.ping_sent = None,
+                    _ => {}
+                }
+            }
+
+            if let Some(sent) = self.ping_sent {
+                if sent.elapsed() >= KEEP_ALIVE / 2 {
+                    return Err(std::io::Error::new(
+                        ErrorKind::TimedOut,
+                        "broker stopped answering pings",
+                    ));
+                }
+            } else if self.last_sent.elapsed() >= KEEP_ALIVE / 2 {
+                self.send(&[PINGREQ, 0])?;
+                self.ping_sent = Some(Instant::now());
+            }
+        }
+    }
+
+    fn send(&mut self, packet: &[u8]) -> std::io::Result<()> {
+        self.stream.write_all(packet)?;
+        self.last_sent = Instant::now();
+        Ok(())
+    }
+
+    /// Read what has arrived, waiting up to [`TICK`].
+    fn fill(&mut self) -> std::io::Result<()> {
+        let mut chunk = [0u8; 1024];
+        match self.stream.read(&mut chunk) {
+            Ok(0) => Err(std::io::Error::new(
+                ErrorKind::UnexpectedEof,
+                "broker closed the connection",
+            )),
+            Ok(n) => {
+                self.buf.extend_from_slice(&chunk[..n]);
+                Ok(())
+            }
+            Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Why the broker refused the connection (CONNACK return code).
+fn connack_error(code: u8) -> String {
+    let reason = match code {
+        1 => "unsupported protocol version",
+        2 => "client ID rejected",
+        3 => "server unavailable",
+        4 => "bad user name or password",
+        5 => "not authorized",
+        _ => "unknown reason",
+    };
+    format!("broker refused the connection: {reason} ({code})")
+}
+
+// ── Packets ──
+
+/// Append the variable-length "remaining length" encoding of `len`.
+fn put_length(mut len: usize, out: &mut Vec<u8>) {
+    loop {
+        let mut byte = (len % 128) as u8;
+        len /= 128;
+        if len > 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if len == 0 {
+            break;
+        }
+    }
+}
+
+/// Append a length-prefixed string (or binary) field; fails for fields
+/// longer than the 16-bit length prefix allows.
+fn put_bytes(bytes: &[u8], out: &mut Vec<u8>) -> std::io::Result<()> {
+    let len = u16::try_from(bytes.len()).map_err(|_| {
+        std::io::Error::new(
+            ErrorKind::InvalidInput,
+            format!("MQTT field too long ({} bytes, at most 65535)", bytes.len()),
+        )
+    })?;
+    out.extend_from_slice(&len.to_be_bytes());
+    out.extend_from_slice(bytes);
+    Ok(())
+}
+
+fn packet(header: u8, body: &[u8]) -> Vec<u8> {
+    let mut out = vec![header];
+    put_length(body.len(), &mut out);
+    out.extend_from_slice(body);
+    out
+}
+
+/// CONNECT with a clean session and a retained `offline` last will.
+fn connect_packet(settings: &MqttSettings) -> std::io::Result<Vec<u8>> {
+    const CLEAN_SESSION: u8 = 0x02;
+    const WILL: u8 = 0x04;
+    const WILL_RETAIN: u8 = 0x20;
+    const PASSWORD: u8 = 0x40;
+    const USERNAME: u8 = 0x80;
+    let with_user = !settings.username.is_empty();
+    let mut flags = CLEAN_SESSION | WILL | WILL_RETAIN;
+    if with_user {
+        flags |= USERNAME | PASSWORD;
+    }
+    let mut body = Vec::new();
+    put_bytes(b"MQTT", &mut body)?;
+    body.push(4); // protocol level 3.1.1
+    body.push(flags);
+    body.extend_from_slice(&(KEEP_ALIVE.as_secs() as u16).to_be_bytes());
+    put_bytes(settings.client_id.as_bytes(), &mut body)?;
+    put_bytes(settings.topic("available").as_bytes(), &mut body)?;
+    put_bytes(b"offline", &mut body)?;
+    if with_user {
+        put_bytes(settings.username.as_bytes(), &mut body)?;
+        put_bytes(settings.password.as_bytes(), &mut body)?;
+    }
+    Ok(packet(CONNECT, &body))
+}
+
+/// QoS 0 PUBLISH.
+fn publish_packet(topic: &str, payload: &[u8], retain: bool) -> std::io::Result<Vec<u8>> {
+    let mut body = Vec::new();
+    put_bytes(topic.as_bytes(), &mut body)?;
+    body.extend_from_slice(payload);
+    let flags = if retain { RETAIN } else { 0 };
+    Ok(packet(PUBLISH | flags, &body))
+}
+
+/// SUBSCRIBE to one topic at QoS 0.
+fn subscribe_packet(packet_id: u16, topic: &str) -> std::io::Result<Vec<u8>> {
+    let mut body = packet_id.to_be_bytes().to_vec();
+    put_bytes(topic.as_bytes(), &mut body)?;
+    body.push(0);
+    Ok(packet(SUBSCRIBE, &body))
+}
+
+/// Take the next complete packet (first header byte and body) off the front
+/// of `buf`; `None` until one has fully arrived. Packets over
+/// [`MAX_PACKET_BYTES`] are an error, as soon as their length is known.
+fn take_packet(buf: &mut Vec<u8>) -> std::io::Result<Option<(u8, Vec<u8>)>> {
+    let Some(&header) = buf.first() else {
+        return Ok(None);
+    };
+    let mut len = 0usize;
+    let mut pos = 1;
+    loop {
+        let Some(&byte) = buf.get(pos) else {
+            return Ok(None);
+        };
+        len |= usize::from(byte & 0x7F) << (7 * (pos - 1));
+        pos += 1;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        if pos > 4 {
+            return Err(std::io::Error::new(
+                ErrorKind::InvalidData,
+                "malformed packet length",
+            ));
+        }
+    }
+    if len > MAX_PACKET_BYTES {
+        return Err(std::io::Error::new(
+            ErrorKind::InvalidData,
+            format!("packet too large ({len} bytes)"),
+        ));
+    }
+    if buf.len() < pos + len {
+        return Ok(None);
+    }
+    let body = buf[pos..pos + len].to_vec();
+    buf.drain(..pos + len);
+    Ok(Some((header, body)))
+}
+
+/// Topic and payload of a received PUBLISH.
+fn parse_publish(header: u8, body: &[u8]) -> Option<(String, &[u8])> {
+    let len = usize::from(u16::from_be_bytes([*body.first()?, *body.get(1)?]));
+    let topic = std::str::from_utf8(body.get(2..2 + len)?).ok()?;
+    // QoS 1 and 2 carry a packet ID before the payload
+    let qos = (header >> 1) & 0x03;
+    let start = 2 + len + if qos > 0 { 2 } else { 0 };
+    Some((topic.to_string(), body.get(start..)?))
+}
+
+/// The command a received PUBLISH carries, if it's on `command_topic`.
+/// Retained messages are ignored: the broker resends them on every
+/// (re)connect, and a retained `toggle` would flip the mute each time.
+fn publish_command(header: u8, body: &[u8], command_topic: &str) -> Option<RemoteCommand> {
+    let (topic, payload) = parse_publish(header, body)?;
+    if topic != command_topic {
+        return None;
+    }
+    let text = String::from_utf8_lossy(payload);
+    if header & RETAIN != 0 {
+        log::warn!(
+            "[mqtt] ignoring retained command \"{}\" on {topic} — publish commands without retain",
+            text.trim()
+        );
+        return None;
+    }
+    let command = RemoteCommand::parse(&text);
+    if command.is_none() {
+        log::warn!("[mqtt] unknown command \"{}\"", text.trim());
+    }
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, TcpListener};
+
+    fn settings(port: u16) -> MqttSettings {
+        MqttSettings {
+            host: "127.0.0.1".into(),
+            port,
+            username: String::new(),
+            password: String::new(),
+            topic_prefix: "desk".into(),
+            client_id: "focusmute-test".into(),
+        }
+    }
+
+    /// Read one whole packet from the client.
+    fn read_packet(stream: &mut TcpStream, buf: &mut Vec<u8>) -> (u8, Vec<u8>) {
+        loop {
+            if let Some(packet) = take_packet(buf).unwrap() {
+                return packet;
+            }
+            let mut chunk = [0u8; 256];
+            let n = stream.read(&mut chunk).unwrap();
+            assert!(n > 0, "client hung up");
+            buf.extend_from_slice(&chunk[..n]);
+        }
+    }
+
+    #[test]
+    fn remaining_length_encoding() {
+        for (len, encoded) in [
+            (0, vec![0x00]),
+            (127, vec![0x7F]),
+            (128, vec![0x80, 0x01]),
+            (16_383, vec![0xFF, 0x7F]),
+            (16_384, vec![0x80, 0x80, 0x01]),
+        ] {
+            let mut out = Vec::new();
+            put_length(len, &mut out);
+            assert_eq!(out, encoded);
+            let mut buf = vec![PUBLISH];
+            buf.extend_from_slice(&out);
+            buf.resize(buf.len() + len, 0);
+            assert_eq!(take_packet(&mut buf).unwrap().unwrap().1.len(), len);
+            assert!(buf.is_empty());
+        }
+        assert!(take_packet(&mut vec![PUBLISH, 0x80, 0x80, 0x80, 0x80, 0x01]).is_err());
+        assert!(take_packet(&mut vec![PUBLISH, 3, 0]).unwrap().is_none());
+        // A huge announced length is refused before its body arrives
+        let mut huge = vec![PUBLISH];
+        put_length(MAX_PACKET_BYTES + 1, &mut huge);
+        assert!(take_packet(&mut huge).is_err());
+    }
+
+    #[test]
+    fn oversized_fields_are_an_error() {
+        let topic = "t".repeat(usize::from(u16::MAX));
+        assert!(publish_packet(&topic, b"", false).is_ok());
+        let topic = "t".repeat(usize::from(u16::MAX) + 1);
+        assert!(publish_packet(&topic, b"", false).is_err());
+        let mut s = settings(1883);
+        s.username = "desk".into();
+        s.password = "x".repeat(70_000);
+        assert!(connect_packet(&s).is_err());
+    }
+
+    #[test]
+    fn connect_packet_sets_will_and_credentials() {
+        let mut s = settings(1883);
+        let anonymous = connect_packet(&s).unwrap();
+        assert_eq!(&anonymous[2..9], b"\x00\x04MQTT\x04");
+        assert_eq!(anonymous[9], 0x26);
+        s.username = "desk".into();
+        s.password = "hunter2".into();
+        let with_user = connect_packet(&s).unwrap();
+        assert_eq!(with_user[9], 0xE6);
+        assert!(with_user.ends_with(b"\x00\x04desk\x00\x07hunter2"));
+        assert_eq!(
+            connack_error(4),
+            "broker refused the connection: bad user name or password (4)"
+        );
+    }
+
+    #[test]
+    fn publish_round_trips() {
+        let mut buf = publish_packet("desk/set", b"toggle", true).unwrap();
+        assert_eq!(buf[0], 0x31);
+        let (header, body) = take_packet(&mut buf).unwrap().unwrap();
+        let (topic, payload) = parse_publish(header, &body).unwrap();
+        assert_eq!((topic.as_str(), payload), ("desk/set", &b"toggle"[..]));
+        // QoS 1: packet ID before the payload
+        let (topic, payload) = parse_publish(0x32, b"\x00\x01t\x00\x07on").unwrap();
+        assert_eq!((topic.as_str(), payload), ("t", &b"on"[..]));
+    }
+
+    #[test]
+    fn retained_commands_are_ignored() {
+        let mut buf = publish_packet("desk/set", b"toggle", true).unwrap();
+        let (header, body) = take_packet(&mut buf).unwrap().unwrap();
+        assert_eq!(publish_command(header, &body, "desk/set"), None);
+
+        let mut buf = publish_packet("desk/set", b"toggle", false).unwrap();
+        let (header, body) = take_packet(&mut buf).unwrap().unwrap();
+        assert_eq!(
+            publish_command(header, &body, "desk/set"),
+            Some(RemoteCommand::Toggle)
+        );
+        assert_eq!(publish_command(header, &body, "other/set"), None);
+    }
+
+    #[test]
+    fn client_publishes_state_and_takes_commands() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (cmd_tx, cmd_rx) = mpsc::channel();
+        let client = MqttClient::start(settings(port), move |c| {
+            let _ = cmd_tx.send(c);
+        })
+        .unwrap();
+        client.publish(Event::Mute {
+            muted: true,
+            origin: "focusmute".into(),
+        });
+
+        let (mut broker, _) = listener.accept().unwrap();
+        broker
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let mut buf = Vec::new();
+        let (header, _) = read_packet(&mut broker, &mut buf);
+        assert_eq!(header, CONNECT);
+        broker.write_all(&[CONNACK, 2, 0, 0]).unwrap();
+        let (header, body) = read_packet(&mut broker, &mut buf);
+        assert_eq!(header, SUBSCRIBE);
+        assert!(body.ends_with(b"\x00\x08desk/set\x00"));
+        let (header, body) = read_packet(&mut broker, &mut buf);
+        assert_eq!(
+            parse_publish(header, &body).unwrap(),
+            ("desk/available".into(), &b"online"[..])
+        );
+        let (header, body) = read_packet(&mut broker, &mut buf);
+        assert_eq!(header, 0x31);
+        let (topic, payload) = parse_publish(header, &body).unwrap();
+        assert_eq!(topic, "desk/state");
+        let record: EventRecord = serde_json::from_slice(payload).unwrap();
+        assert_eq!(
+            record.event,
+            Event::Mute {
+                muted: true,
+                origin: "focusmute".into()
+            }
+        );
+
+        broker
+            .write_all(&publish_packet("desk/set", b"unmute", false).unwrap())
+            .unwrap();
+        assert_eq!(
+            cmd_rx.recv_timeout(Duration::from_secs(5)).unwrap(),
+            RemoteCommand::Unmute
+        );
+
+        // Dropping the client says goodbye
+        drop(client);
+        let (header, body) = read_packet(&mut broker, &mut buf);
+        assert_eq!(
+            parse_publish(header, &body).unwrap(),
+            ("desk/available".into(), &b"offline"[..])
+        );
+        assert_eq!(read_packet(&mut broker, &mut buf).0, DISCONNECT);
+    }
+}
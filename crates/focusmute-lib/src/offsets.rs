@@ -5,6 +5,7 @@
 //! available, or defaults to Scarlett 2i2 4th Gen hardcoded values from
 //! `protocol.rs`.
 
+use crate::models::ModelProfile;
 use crate::protocol;
 use crate::schema::SchemaConstants;
 
@@ -39,6 +40,16 @@ impl DeviceOffsets {
         }
     }
 
+    /// Create offsets for a hardcoded model profile when no schema is available.
+    ///
+    /// Descriptor offsets match the defaults; the LED count comes from the profile.
+    pub fn for_profile(profile: &ModelProfile) -> Self {
+        Self {
+            direct_led_count: profile.led_count,
+            ..Self::default()
+        }
+    }
+
     /// Size of `directLEDValues` in bytes (count * 4).
     pub fn direct_led_size(&self) -> u32 {
         (self.direct_led_count * 4) as u32
@@ -73,6 +84,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn for_profile_uses_profile_led_count() {
+        let profile = crate::models::detect_model("Scarlett 4i4 4th Gen").unwrap();
+        let offsets = DeviceOffsets::for_profile(profile);
+        assert_eq!(offsets.direct_led_count, 56);
+        assert_eq!(offsets.direct_led_values, protocol::OFF_DIRECT_LED_VALUES);
+    }
+
     #[test]
     fn from_schema_uses_schema_values() {
         let sc = SchemaConstants {
@@ -89,6 +108,18 @@ mod tests {
             input_controls: vec![],
             app_space_features: vec![],
             firmware_version: String::new(),
+            phantom_power: None,
+            air: None,
+            inst_input: None,
+            preamp_gain: None,
+            clip_safe: None,
+            autogain: None,
+            autogain_status_offset: None,
+            sample_rate_offset: None,
+            sync_source_offset: None,
+            direct_monitoring_offset: None,
+            alt_speakers: None,
+            output_volume: None,
         };
         let offsets = DeviceOffsets::from_schema(&sc);
         assert_eq!(offsets.direct_led_values, 100);
@@ -117,6 +148,18 @@ mod tests {
             input_controls: vec![],
             app_space_features: vec![],
             firmware_version: "2.0.2417.0".into(),
+            phantom_power: None,
+            air: None,
+            inst_input: None,
+            preamp_gain: None,
+            clip_safe: None,
+            autogain: None,
+            autogain_status_offset: None,
+            sample_rate_offset: None,
+            sync_source_offset: None,
+            direct_monitoring_offset: None,
+            alt_speakers: None,
+            output_volume: None,
         };
         let from_schema = DeviceOffsets::from_schema(&sc);
         let default = DeviceOffsets::default();
@@ -157,6 +200,18 @@ mod tests {
             input_controls: vec![],
             app_space_features: vec![],
             firmware_version: String::new(),
+            phantom_power: None,
+            air: None,
+            inst_input: None,
+            preamp_gain: None,
+            clip_safe: None,
+            autogain: None,
+            autogain_status_offset: None,
+            sample_rate_offset: None,
+            sync_source_offset: None,
+            direct_monitoring_offset: None,
+            alt_speakers: None,
+            output_volume: None,
         });
         let cloned = offsets.clone();
         assert_eq!(cloned.enable_direct_led, offsets.enable_direct_led);
@@ -0,0 +1,389 @@
+//! OSC output and control — for streaming controllers and DAW control
+//! surfaces.
+//!
+//! [`OscSender`] sends state changes over UDP to `osc_target`:
+//!
+//! - `/focusmute/muted` `i` — 1 muted, 0 live
+//! - `/focusmute/device` `s` — `connected`, `disconnected`, `in_use`, or
+//!   `changed` (see [`DeviceState`])
+//!
+//! [`OscListener`] takes commands on `osc_listen`: `/focusmute/toggle`,
+//! `/focusmute/mute`, `/focusmute/unmute`, and `/focusmute/muted` with a
+//! number (non-zero mutes), so a button can both show and set the state.
+//! `/focusmute/mute` with a 0 argument unmutes. Bundles are unpacked.
+
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use crate::events::{DeviceState, Event, RemoteCommand};
+
+/// Address prefix of every FocusMute message.
+pub const OSC_PREFIX: &str = "/focusmute";
+
+/// How often the listener checks whether it should stop.
+const LISTEN_POLL: Duration = Duration::from_millis(250);
+
+/// An OSC argument.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OscArg {
+    Int(i32),
+    Float(f32),
+    Str(String),
+    Bool(bool),
+}
+
+impl OscArg {
+    /// The argument as a switch: non-zero numbers and `true` are on.
+    /// `None` for strings.
+    fn is_on(&self) -> Option<bool> {
+        match self {
+            OscArg::Int(i) => Some(*i != 0),
+            OscArg::Float(f) => Some(*f != 0.0),
+            OscArg::Bool(b) => Some(*b),
+            OscArg::Str(_) => None,
+        }
+    }
+}
+
+/// One OSC message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OscMessage {
+    pub address: String,
+    pub args: Vec<OscArg>,
+}
+
+impl OscMessage {
+    pub fn new(address: impl Into<String>, args: Vec<OscArg>) -> Self {
+        Self {
+            address: address.into(),
+            args,
+        }
+    }
+
+    /// The message as an OSC 1.0 packet.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        put_string(&self.address, &mut out);
+        let mut tags = String::from(",");
+        for arg in &self.args {
+            tags.push(match arg {
+                OscArg::Int(_) => 'i',
+                OscArg::Float(_) => 'f',
+                OscArg::Str(_) => 's',
+                OscArg::Bool(true) => 'T',
+                OscArg::Bool(false) => 'F',
+            });
+        }
+        put_string(&tags, &mut out);
+        for arg in &self.args {
+            match arg {
+                OscArg::Int(i) => out.extend_from_slice(&i.to_be_bytes()),
+                OscArg::Float(f) => out.extend_from_slice(&f.to_be_bytes()),
+                OscArg::Str(s) => put_string(s, &mut out),
+                OscArg::Bool(_) => {}
+            }
+        }
+        out
+    }
+
+    /// The messages in a packet — one, or all of a bundle's. `None` if it's
+    /// malformed or has argument types FocusMute doesn't read.
+    pub fn decode(packet: &[u8]) -> Option<Vec<OscMessage>> {
+        if let Some(mut rest) = packet.strip_prefix(b"#bundle\0") {
+            // Time tag, then size-prefixed elements
+            rest = rest.get(8..)?;
+            let mut messages = Vec::new();
+            while !rest.is_empty() {
+                let size = i32::from_be_bytes(rest.get(..4)?.try_into().ok()?);
+                let size = usize::try_from(size).ok()?;
+                messages.extend(Self::decode(rest.get(4..4 + size)?)?);
+                rest = &rest[4 + size..];
+            }
+            return Some(messages);
+        }
+        let (address, mut rest) = take_string(packet)?;
+        if !address.starts_with('/') {
+            return None;
+        }
+        // A message without a type tag string has no arguments
+        let tags = match take_string(rest) {
+            Some((tags, after)) if tags.starts_with(',') => {
+                rest = after;
+                tags
+            }
+            _ => ",",
+        };
+        let mut args = Vec::new();
+        for tag in tags.chars().skip(1) {
+            let arg = match tag {
+                'i' => {
+                    let (bytes, after) = rest.split_first_chunk::<4>()?;
+                    rest = after;
+                    OscArg::Int(i32::from_be_bytes(*bytes))
+                }
+                'f' => {
+                    let (bytes, after) = rest.split_first_chunk::<4>()?;
+                    rest = after;
+                    OscArg::Float(f32::from_be_bytes(*bytes))
+                }
+                's' => {
+                    let (s, after) = take_string(rest)?;
+                    rest = after;
+                    OscArg::Str(s.to_string())
+                }
+                'T' => OscArg::Bool(true),
+                'F' => OscArg::Bool(false),
+                _ => return None,
+            };
+            args.push(arg);
+        }
+        Some(vec![OscMessage::new(address, args)])
+    }
+
+    /// The command this message asks for, if it's one of FocusMute's.
+    pub fn command(&self) -> Option<RemoteCommand> {
+        let name = self.address.strip_prefix(OSC_PREFIX)?;
+        let on = self.args.first().and_then(OscArg::is_on);
+        let switch = |on: bool| {
+            if on {
+                RemoteCommand::Mute
+            } else {
+                RemoteCommand::Unmute
+            }
+        };
+        match name {
+            "/toggle" => Some(RemoteCommand::Toggle),
+            "/mute" => Some(switch(on.unwrap_or(true))),
+            "/unmute" => Some(RemoteCommand::Unmute),
+            "/muted" => on.map(switch),
+            _ => None,
+        }
+    }
+}
+
+/// Append a NUL-terminated string padded to a multiple of 4 bytes.
+fn put_string(s: &str, out: &mut Vec<u8>) {
+    out.extend_from_slice(s.as_bytes());
+    let pad = 4 - s.len() % 4;
+    out.resize(out.len() + pad, 0);
+}
+
+/// Take a padded OSC string off the front of `data`.
+fn take_string(data: &[u8]) -> Option<(&str, &[u8])> {
+    let end = data.iter().position(|&b| b == 0)?;
+    let s = std::str::from_utf8(&data[..end]).ok()?;
+    let padded = (end / 4 + 1) * 4;
+    Some((s, data.get(padded..)?))
+}
+
+/// The messages that report `event`; none for events OSC doesn't carry.
+pub fn messages_for(event: &Event) -> Vec<OscMessage> {
+    match event {
+        Event::Mute { muted, .. } => vec![OscMessage::new(
+            format!("{OSC_PREFIX}/muted"),
+            vec![OscArg::Int(i32::from(*muted))],
+        )],
+        Event::Device { state, .. } => {
+            let name = match state {
+                DeviceState::Connected => "connected",
+                DeviceState::Disconnected => "disconnected",
+                DeviceState::InUse => "in_use",
+                DeviceState::Changed => "changed",
+            };
+            vec![OscMessage::new(
+                format!("{OSC_PREFIX}/device"),
+                vec![OscArg::Str(name.into())],
+            )]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Resolve a `host:port` pair to one socket address.
+fn resolve(host: &str, port: u16) -> std::io::Result<SocketAddr> {
+    (host, port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| std::io::Error::other(format!("{host} has no address")))
+}
+
+/// Sends state changes to one OSC receiver.
+pub struct OscSender {
+    socket: UdpSocket,
+    target: SocketAddr,
+}
+
+impl OscSender {
+    /// A sender to `host:port`, resolved once.
+    pub fn new(host: &str, port: u16) -> std::io::Result<Self> {
+        let target = resolve(host, port)?;
+        let local: SocketAddr = if target.is_ipv4() {
+            ([0, 0, 0, 0], 0).into()
+        } else {
+            (std::net::Ipv6Addr::UNSPECIFIED, 0).into()
+        };
+        Ok(Self {
+            socket: UdpSocket::bind(local)?,
+            target,
+        })
+    }
+
+    /// Send the messages for `event`.
+    pub fn send(&self, event: &Event) {
+        for message in messages_for(event) {
+            if let Err(e) = self.socket.send_to(&message.encode(), self.target) {
+                log::debug!("[osc] send to {} failed: {e}", self.target);
+            }
+        }
+    }
+}
+
+/// Takes commands on a UDP port until dropped.
+pub struct OscListener {
+    stop: Arc<AtomicBool>,
+    addr: SocketAddr,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl OscListener {
+    /// Listen on `host:port`, calling `on_command` (on the listener thread)
+    /// for every command received.
+    pub fn start<F>(host: &str, port: u16, on_command: F) -> std::io::Result<Self>
+    where
+        F: Fn(RemoteCommand) + Send + 'static,
+    {
+        let socket = UdpSocket::bind(resolve(host, port)?)?;
+        socket.set_read_timeout(Some(LISTEN_POLL))?;
+        let addr = socket.local_addr()?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = Arc::clone(&stop);
+        let thread = std::thread::Builder::new()
+            .name("osc".into())
+            .spawn(move || {
+                let mut buf = [0u8; 1536];
+                while !stopped.load(Ordering::Relaxed) {
+                    let Ok((n, from)) = socket.recv_from(&mut buf) else {
+                        continue;
+                    };
+                    let Some(messages) = OscMessage::decode(&buf[..n]) else {
+                        log::debug!("[osc] malformed packet from {from}");
+                        continue;
+                    };
+                    for command in messages.iter().filter_map(OscMessage::command) {
+                        log::info!("[osc] command from {from}: {command:?}");
+                        on_command(command);
+                    }
+                }
+            })?;
+        Ok(Self {
+            stop,
+            addr,
+            thread: Some(thread),
+        })
+    }
+
+    /// The address actually bound (for port 0).
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+}
+
+impl Drop for OscListener {
+    /// Stop the thread and wait for it (at most [`LISTEN_POLL`]), so the port
+    /// is free again once this returns.
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn messages_round_trip() {
+        let message = OscMessage::new(
+            "/focusmute/device",
+            vec![
+                OscArg::Str("in_use".into()),
+                OscArg::Int(-2),
+                OscArg::Float(0.5),
+                OscArg::Bool(true),
+            ],
+        );
+        let packet = message.encode();
+        assert_eq!(packet.len() % 4, 0);
+        assert_eq!(&packet[..20], b"/focusmute/device\0\0\0");
+        assert_eq!(OscMessage::decode(&packet), Some(vec![message]));
+        assert_eq!(
+            messages_for(&Event::Mute {
+                muted: true,
+                origin: "focusmute".into()
+            })[0]
+                .encode(),
+            b"/focusmute/muted\0\0\0\0,i\0\0\0\0\0\x01"
+        );
+    }
+
+    #[test]
+    fn bundles_and_bare_messages_decode() {
+        let inner = OscMessage::new("/focusmute/toggle", vec![]).encode();
+        let mut bundle = b"#bundle\0".to_vec();
+        bundle.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 1]);
+        bundle.extend_from_slice(&(inner.len() as i32).to_be_bytes());
+        bundle.extend_from_slice(&inner);
+        let messages = OscMessage::decode(&bundle).unwrap();
+        assert_eq!(messages[0].command(), Some(RemoteCommand::Toggle));
+        // No type tags at all
+        assert_eq!(
+            OscMessage::decode(b"/focusmute/mute\0").unwrap()[0].command(),
+            Some(RemoteCommand::Mute)
+        );
+        assert_eq!(OscMessage::decode(b"not osc\0"), None);
+        assert_eq!(OscMessage::decode(b"/x\0\0,b\0\0"), None);
+    }
+
+    #[test]
+    fn commands_from_addresses_and_arguments() {
+        let command = |address: &str, args| OscMessage::new(address, args).command();
+        assert_eq!(
+            command("/focusmute/mute", vec![OscArg::Float(0.0)]),
+            Some(RemoteCommand::Unmute)
+        );
+        assert_eq!(
+            command("/focusmute/muted", vec![OscArg::Int(1)]),
+            Some(RemoteCommand::Mute)
+        );
+        assert_eq!(command("/focusmute/muted", vec![]), None);
+        assert_eq!(
+            command("/focusmute/unmute", vec![]),
+            Some(RemoteCommand::Unmute)
+        );
+        assert_eq!(command("/other/toggle", vec![]), None);
+    }
+
+    #[test]
+    fn listener_takes_commands_and_sender_reports_state() {
+        let (tx, rx) = mpsc::channel();
+        let listener = OscListener::start("127.0.0.1", 0, move |c| {
+            let _ = tx.send(c);
+        })
+        .unwrap();
+        let port = listener.local_addr().port();
+        let sender = OscSender::new("127.0.0.1", port).unwrap();
+        sender.send(&Event::Mute {
+            muted: false,
+            origin: "external".into(),
+        });
+        assert_eq!(
+            rx.recv_timeout(Duration::from_secs(5)).unwrap(),
+            RemoteCommand::Unmute
+        );
+    }
+}
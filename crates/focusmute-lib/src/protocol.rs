@@ -16,6 +16,11 @@
 //! - `DESCRIPTOR_SIZE` (720) — total descriptor size
 //! - LED index-to-physical mapping (see `OFF_DIRECT_LED_VALUES` doc comment)
 
+/// Revision of the device protocol described here (framing, command codes,
+/// descriptor layout). Bumped when a change would break callers that talk to
+/// the device through this crate; reported by [`crate::version()`].
+pub const PROTOCOL_REVISION: u32 = 1;
+
 // ── IOCTL codes ──
 
 /// Synchronous init handshake — must be sent before any TRANSACT.
@@ -40,6 +45,9 @@ pub const CMD_USB_INIT: u32 = 0x0001_0400;
 /// Get device configuration — returns session token in bytes 8..16.
 pub const CMD_GET_CONFIG: u32 = 0x0004_0400;
 
+/// Byte offset of the u32 LE sample rate in the GET_CONFIG response.
+pub const CONFIG_SAMPLE_RATE_OFFSET: usize = 20;
+
 /// Read descriptor memory: payload = `[offset:u32, size:u32]`.
 pub const CMD_GET_DESCR: u32 = 0x0000_0800;
 
@@ -137,6 +145,9 @@ pub const USB_CMD_INFO_DEVMAP: u32 = 0x0080_000C;
 /// GET_DEVMAP — get schema data page (1024 bytes per page).
 pub const USB_CMD_GET_DEVMAP: u32 = 0x0080_000D;
 
+/// GET_SYNC — clock sync status, u32 (non-zero = locked).
+pub const USB_CMD_GET_SYNC: u32 = 0x0000_6004;
+
 // ── USB control transfer parameters ──
 
 /// `bRequest` for init step 0 (read 24 bytes).
@@ -180,6 +191,7 @@ pub const FOCUSRITE_GUID: windows::core::GUID = windows::core::GUID {
 /// - `CMD_DATA_NOTIFY` (0x0002_0800) → `USB_CMD_DATA_CMD` (0x0080_0002)
 /// - `CMD_INFO_DEVMAP` (0x000C_0800) → `USB_CMD_INFO_DEVMAP` (0x0080_000C)
 /// - `CMD_GET_DEVMAP` (0x000D_0800) → `USB_CMD_GET_DEVMAP` (0x0080_000D)
+/// - `CMD_GET_SYNC` (0x0004_0006) → `USB_CMD_GET_SYNC` (0x0000_6004)
 ///
 /// Returns `None` for unrecognised commands (e.g. `CMD_USB_INIT`, `CMD_GET_CONFIG`
 /// which are SwRoot-only and have no direct USB equivalent).
@@ -192,15 +204,30 @@ pub fn swroot_to_usb_cmd(swroot_cmd: u32) -> Option<u32> {
         CMD_GET_DEVMAP => Some(USB_CMD_GET_DEVMAP),
         CMD_METER_INFO => Some(USB_CMD_METER_INFO),
         CMD_GET_METER => Some(USB_CMD_GET_METER),
+        CMD_GET_SYNC => Some(USB_CMD_GET_SYNC),
         _ => None,
     }
 }
 
 // ── Descriptor offsets (Scarlett 4th Gen, shared across models) ──
 
+/// `enablePhantomPower` — u8 per phantom power switch starting at this offset.
+/// 1 = 48V on. Schema: notify-device=11, set-via-parameter-buffer=true.
+/// From the Linux scarlett2 driver (2i2); prefer the schema member's offset.
+pub const OFF_PHANTOM_POWER: u32 = 72;
+
+/// Number of phantom power switches on the 2i2 (one switch feeds both inputs).
+pub const PHANTOM_POWER_COUNT: usize = 1;
+
 /// `enableDirectLEDMode` — u8 at this offset. 0=normal, 2=halo override.
 pub const OFF_ENABLE_DIRECT_LED: u32 = 77;
 
+/// `enableDirectLEDMode` value for normal firmware metering.
+pub const DIRECT_LED_MODE_OFF: u8 = 0;
+
+/// `enableDirectLEDMode` value that hands the halo LEDs to software.
+pub const DIRECT_LED_MODE_HALOS: u8 = 2;
+
 /// `directLEDColour` — u32 color for single-LED update via DATA_NOTIFY(8).
 /// Must be written before `directLEDIndex`. Format: `0xRRGGBB00`.
 pub const OFF_DIRECT_LED_COLOUR: u32 = 84;
@@ -269,9 +296,55 @@ pub const NOTIFY_DIRECT_LED_VALUES: u32 = 5;
 /// Notify after writing `directLEDColour` / `directLEDIndex`.
 pub const NOTIFY_DIRECT_LED_COLOUR: u32 = 8;
 
+/// Notify after writing `LEDcolors` — also re-activates the metering
+/// gradient on the halos. Prefer the schema's `gradient_notify`.
+pub const NOTIFY_LED_COLORS: u32 = 9;
+
+/// Notify after a phantom power write through the parameter buffer.
+pub const NOTIFY_PHANTOM_POWER: u32 = 11;
+
 /// Notify after writing brightness.
 pub const NOTIFY_BRIGHTNESS: u32 = 37;
 
+// ── IOCTL_NOTIFY bitmask (eDEV_FCP_NOTIFY_MESSAGE_TYPE) ──
+
+/// Byte offset of the u32 LE notification bitmask in the IOCTL_NOTIFY response.
+pub const NOTIFY_MASK_OFFSET: usize = 4;
+
+/// Clock sync status changed (lock lost/regained, sample rate switch).
+pub const NOTIFY_MASK_SYNC: u32 = 0x0000_0008;
+
+/// Clip Safe state changed.
+pub const NOTIFY_MASK_CLIPSAFE: u32 = 0x0020_0000;
+
+/// Auto gain started, finished, or failed.
+pub const NOTIFY_MASK_AUTOGAIN: u32 = 0x0040_0000;
+
+/// Air button toggled.
+pub const NOTIFY_MASK_AIR: u32 = 0x0080_0000;
+
+/// Direct monitoring toggled.
+pub const NOTIFY_MASK_DIRECT_MONITORING: u32 = 0x0100_0000;
+
+/// Select button pressed (`selectedInput` changed). Shared with
+/// FCP_NOTIFY_CHANNEL_LINKING_CHANGE.
+pub const NOTIFY_MASK_SELECT: u32 = 0x0200_0000;
+
+/// Inst button toggled.
+pub const NOTIFY_MASK_INST: u32 = 0x0400_0000;
+
+/// 48V phantom power toggled.
+pub const NOTIFY_MASK_PHANTOM_POWER: u32 = 0x0800_0000;
+
+/// Jack inserted into / removed from an input (see `OFF_INPUT_TRS_PRESENT`).
+pub const NOTIFY_MASK_TRS_INPUT: u32 = 0x2000_0000;
+
+/// Input gain changed.
+pub const NOTIFY_MASK_INPUT_GAIN: u32 = 0x4000_0000;
+
+/// Low supply voltage detected.
+pub const NOTIFY_MASK_LOW_VOLTAGE: u32 = 0x8000_0000;
+
 // ── Descriptor total size ──
 
 /// Full descriptor size for a bulk read (confirmed on 2i2; may differ on other models).
@@ -317,6 +390,7 @@ mod tests {
         let events = [
             NOTIFY_DIRECT_LED_VALUES,
             NOTIFY_DIRECT_LED_COLOUR,
+            NOTIFY_LED_COLORS,
             NOTIFY_BRIGHTNESS,
         ];
         for i in 0..events.len() {
@@ -329,6 +403,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn notify_masks_are_single_distinct_bits() {
+        let masks = [
+            NOTIFY_MASK_SYNC,
+            NOTIFY_MASK_CLIPSAFE,
+            NOTIFY_MASK_AUTOGAIN,
+            NOTIFY_MASK_AIR,
+            NOTIFY_MASK_DIRECT_MONITORING,
+            NOTIFY_MASK_SELECT,
+            NOTIFY_MASK_INST,
+            NOTIFY_MASK_PHANTOM_POWER,
+            NOTIFY_MASK_TRS_INPUT,
+            NOTIFY_MASK_INPUT_GAIN,
+            NOTIFY_MASK_LOW_VOLTAGE,
+        ];
+        let mut seen = 0u32;
+        for mask in masks {
+            assert_eq!(mask.count_ones(), 1, "0x{mask:08X} is not a single bit");
+            assert_eq!(seen & mask, 0, "0x{mask:08X} collides");
+            seen |= mask;
+        }
+    }
+
     #[test]
     fn ioctl_codes_distinct() {
         let ioctls = [IOCTL_INIT, IOCTL_TRANSACT, IOCTL_NOTIFY, IOCTL_PROBE];
@@ -344,6 +441,8 @@ mod tests {
 
     #[test]
     fn descriptor_offsets_no_overlap() {
+        // enablePhantomPower (1 byte at 72) should not overlap enableDirectLEDMode (1 byte at 77)
+        const { assert!(OFF_PHANTOM_POWER + (PHANTOM_POWER_COUNT as u32) <= OFF_ENABLE_DIRECT_LED) };
         // enableDirectLEDMode (1 byte at 77) should not overlap directLEDValues (160 bytes at 92)
         const { assert!(OFF_ENABLE_DIRECT_LED < OFF_DIRECT_LED_VALUES) };
         // directLEDValues (160 bytes at 92) should not overlap parameterValue (1 byte at 252)
@@ -418,6 +517,7 @@ mod tests {
     fn swroot_to_usb_maps_meter_commands() {
         assert_eq!(swroot_to_usb_cmd(CMD_METER_INFO), Some(USB_CMD_METER_INFO));
         assert_eq!(swroot_to_usb_cmd(CMD_GET_METER), Some(USB_CMD_GET_METER));
+        assert_eq!(swroot_to_usb_cmd(CMD_GET_SYNC), Some(USB_CMD_GET_SYNC));
     }
 
     #[test]
@@ -3,9 +3,25 @@
 //! When the USB device becomes unreachable (unplugged, driver restart, etc.),
 //! the reconnection state machine manages retry timing with exponential
 //! backoff to avoid hammering the system with reconnect attempts.
+//!
+//! On Linux, [`HotplugMonitor`] listens for USB hotplug uevents (netlink) and
+//! cuts the backoff short as soon as a Focusrite device appears. Where such
+//! notifications arrive, [`ReconnectState::set_hotplug_driven`] also stops
+//! probing on a timer once a few attempts have failed.
+//!
+//! The mute state can change while the device is gone. Only the final state
+//! is applied on reconnect; [`ReconnectResync`] picks whether that happens
+//! silently, with a brief flash, or with the missed changes reported.
+//!
+//! Refreshes after a reconnect go through a [`LedWriteCache`], so a device
+//! that keeps dropping and coming back isn't rewritten in full every time.
 
+use std::sync::mpsc;
 use std::time::{Duration, Instant};
 
+use crate::device::{DeviceError, DeviceOptions};
+use crate::led::LedWriteCache;
+
 /// Configuration for reconnection backoff.
 #[derive(Debug, Clone)]
 pub struct ReconnectConfig {
@@ -27,6 +43,48 @@ impl Default for ReconnectConfig {
     }
 }
 
+/// Attempts allowed after a loss or hotplug arrival before a hotplug-driven
+/// [`ReconnectState`] waits for the next arrival (1 + 2 + 4 + 8 + 16 s).
+const HOTPLUG_ATTEMPTS: u32 = 5;
+
+/// Longest delay between attempts while another application holds the
+/// device — releasing it raises no hotplug event, so keep checking.
+const IN_USE_MAX_DELAY: Duration = Duration::from_secs(5);
+
+/// What a reconnect does when the mute state changed while the device was
+/// disconnected (`reconnect_resync` setting).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReconnectResync {
+    /// Just apply the final state.
+    #[default]
+    Silent,
+    /// Blink the indicator briefly, ending on the final state.
+    Flash,
+    /// Apply the final state and let the caller report the missed changes.
+    Notify,
+}
+
+impl ReconnectResync {
+    /// Parse a config value (`"silent"`, `"flash"`, `"notify"`).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "silent" => Some(ReconnectResync::Silent),
+            "flash" => Some(ReconnectResync::Flash),
+            "notify" => Some(ReconnectResync::Notify),
+            _ => None,
+        }
+    }
+}
+
+/// Mute changes that happened while the device was disconnected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MissedMuteChanges {
+    /// Mute state the LEDs still showed from before the disconnect.
+    pub shown_muted: bool,
+    /// Number of changes since.
+    pub count: u32,
+}
+
 /// Reconnection state machine with exponential backoff.
 #[derive(Debug)]
 pub struct ReconnectState {
@@ -34,6 +92,19 @@ pub struct ReconnectState {
     current_delay: Duration,
     last_attempt: Option<Instant>,
     consecutive_failures: u32,
+    /// Hotplug notifications announce new devices, so the timer only runs
+    /// for the first few attempts.
+    hotplug_driven: bool,
+    /// Attempts left before a hotplug-driven state waits for an arrival.
+    attempts_left: u32,
+    /// Why the last attempt failed.
+    last_error: Option<DeviceError>,
+    /// Mute changes the device didn't see.
+    missed: Option<MissedMuteChanges>,
+    /// What the refreshes after reconnecting last wrote.
+    led_cache: LedWriteCache,
+    /// Number LED color blinked on every reconnect (`None` = no flash).
+    flash_color: Option<u32>,
 }
 
 impl ReconnectState {
@@ -44,6 +115,12 @@ impl ReconnectState {
             config,
             last_attempt: None,
             consecutive_failures: 0,
+            hotplug_driven: false,
+            attempts_left: HOTPLUG_ATTEMPTS,
+            last_error: None,
+            missed: None,
+            led_cache: LedWriteCache::new(),
+            flash_color: None,
         }
     }
 
@@ -55,8 +132,12 @@ impl ReconnectState {
     /// Check if enough time has elapsed to attempt reconnection.
     ///
     /// Returns `true` if no attempt has been made yet, or if the
-    /// backoff delay has elapsed since the last attempt.
+    /// backoff delay has elapsed since the last attempt. Hotplug-driven
+    /// states also return `false` once their attempts are used up.
     pub fn should_attempt(&self) -> bool {
+        if self.hotplug_driven && self.attempts_left == 0 {
+            return false;
+        }
         match self.last_attempt {
             None => true,
             Some(last) => last.elapsed() >= self.current_delay,
@@ -67,17 +148,42 @@ impl ReconnectState {
     pub fn record_failure(&mut self) {
         self.consecutive_failures += 1;
         self.last_attempt = Some(Instant::now());
+        self.attempts_left = self.attempts_left.saturating_sub(1);
 
         // Advance backoff: current_delay *= multiplier, capped at max_delay
         let next = self.current_delay.as_secs_f64() * self.config.multiplier;
         self.current_delay = Duration::from_secs_f64(next).min(self.config.max_delay);
     }
 
+    /// Record a failed attempt and why it failed. A device another
+    /// application holds ([`DeviceError::InUse`]) is retried at least every
+    /// few seconds, and hotplug-driven attempts aren't used up on it.
+    pub fn record_error(&mut self, error: DeviceError) {
+        self.record_failure();
+        if matches!(error, DeviceError::InUse(_)) {
+            self.current_delay = self.current_delay.min(IN_USE_MAX_DELAY);
+            self.attempts_left = HOTPLUG_ATTEMPTS;
+        }
+        self.last_error = Some(error);
+    }
+
+    /// Why the last attempt failed (`None` before any failure and after a
+    /// success or reset).
+    pub fn last_error(&self) -> Option<&DeviceError> {
+        self.last_error.as_ref()
+    }
+
     /// Record a successful reconnection and reset the backoff.
     pub fn record_success(&mut self) {
+        self.reset();
+    }
+
+    /// Start over: no failures, initial delay, and an attempt right away
+    /// (e.g. the user asked to reconnect).
+    pub fn reset(&mut self) {
         self.consecutive_failures = 0;
-        self.current_delay = self.config.initial_delay;
-        self.last_attempt = None;
+        self.last_error = None;
+        self.retry_now();
     }
 
     /// Number of consecutive failed attempts.
@@ -89,33 +195,83 @@ impl ReconnectState {
     pub fn current_delay(&self) -> Duration {
         self.current_delay
     }
+
+    /// Skip the remaining backoff — the next [`should_attempt`](Self::should_attempt)
+    /// returns `true` — and restart it from the initial delay, since the
+    /// device may need a moment after it appears.
+    pub fn retry_now(&mut self) {
+        self.current_delay = self.config.initial_delay;
+        self.last_attempt = None;
+        self.attempts_left = HOTPLUG_ATTEMPTS;
+    }
+
+    /// Record a mute change that couldn't reach the disconnected device.
+    pub fn record_missed_mute(&mut self, muted: bool) {
+        self.led_cache.invalidate();
+        match &mut self.missed {
+            Some(missed) => missed.count = missed.count.saturating_add(1),
+            None => {
+                self.missed = Some(MissedMuteChanges {
+                    shown_muted: !muted,
+                    count: 1,
+                })
+            }
+        }
+    }
+
+    /// Mute changes recorded since the last call.
+    pub fn take_missed_mutes(&mut self) -> Option<MissedMuteChanges> {
+        self.missed.take()
+    }
+
+    /// Blink the number LEDs in `color` whenever the device comes back,
+    /// before the mute state is re-applied (`None` = don't).
+    pub fn set_flash_color(&mut self, color: Option<u32>) {
+        self.flash_color = color;
+    }
+
+    /// Rely on hotplug notifications: after a few failed attempts, stop
+    /// probing until [`retry_now`](Self::retry_now) reports an arrival.
+    pub fn set_hotplug_driven(&mut self, on: bool) {
+        self.hotplug_driven = on;
+    }
 }
 
 /// Attempt to reopen the device, respecting backoff timing.
 ///
 /// - `device_serial`: preferred serial number (empty = auto-select).
+/// - `options`: I/O timeouts and retries for the reopened device.
 /// - Returns `None` without attempting if the backoff timer hasn't elapsed.
 /// - On success, records success and returns the new device.
 /// - On failure, records failure, logs the backoff schedule, and returns `None`.
 pub fn try_reopen(
     state: &mut ReconnectState,
     device_serial: &str,
+    options: DeviceOptions,
 ) -> Option<crate::device::PlatformDevice> {
     if !state.should_attempt() {
         return None;
     }
-    match crate::device::open_device_by_serial(device_serial) {
+    match crate::device::open_device_with_options(device_serial, options) {
         Ok(dev) => {
             state.record_success();
             Some(dev)
         }
         Err(e) => {
-            state.record_failure();
-            log::warn!(
-                "reconnect failed: {e} (attempt {}, retry in {:.1}s)",
-                state.consecutive_failures(),
-                state.current_delay().as_secs_f64()
+            // Logged once while another application keeps holding the device
+            let repeated = matches!(
+                (&e, state.last_error()),
+                (DeviceError::InUse(a), Some(DeviceError::InUse(b))) if a == b
             );
+            let message = e.to_string();
+            state.record_error(e);
+            if !repeated {
+                log::warn!(
+                    "reconnect failed: {message} (attempt {}, retry in {:.1}s)",
+                    state.consecutive_failures(),
+                    state.current_delay().as_secs_f64()
+                );
+            }
             None
         }
     }
@@ -123,26 +279,251 @@ pub fn try_reopen(
 
 /// Attempt to reopen the device and re-apply mute indicator after reconnection.
 ///
-/// Combines `try_reopen()` with `led::refresh_after_reconnect()` into a single
-/// call. Returns the new device on success.
+/// Combines `try_reopen()` with a refresh of the mute indicator — through
+/// the state's [`LedWriteCache`] — into a single call, or `led::flash_resync()` when `resync` is
+/// [`Flash`](ReconnectResync::Flash) and mute changes were missed. With a
+/// [flash color](ReconnectState::set_flash_color) set, `led::flash_reconnect()`
+/// confirms the reconnect first. Returns
+/// the new device on success, with the mute changes recorded while it was
+/// gone (for [`Notify`](ReconnectResync::Notify)).
 pub fn try_reconnect_and_refresh(
     reconnect: &mut ReconnectState,
     strategy: &crate::led::MuteStrategy,
     mute_color: u32,
     is_muted: bool,
     device_serial: &str,
-) -> Option<crate::device::PlatformDevice> {
-    let dev = try_reopen(reconnect, device_serial)?;
-    if let Err(e) = crate::led::refresh_after_reconnect(&dev, strategy, mute_color, is_muted) {
+    options: DeviceOptions,
+    resync: ReconnectResync,
+) -> Option<(crate::device::PlatformDevice, Option<MissedMuteChanges>)> {
+    // Gone from the bus — it may have been power-cycled and lost its LEDs
+    if matches!(reconnect.last_error(), Some(DeviceError::NotFound)) {
+        reconnect.led_cache.invalidate();
+    }
+    let dev = try_reopen(reconnect, device_serial, options)?;
+    let missed = reconnect.take_missed_mutes();
+    if let Some(m) = missed {
+        log::info!(
+            "[reconnect] {} mute change(s) while disconnected, now {}",
+            m.count,
+            if is_muted { "muted" } else { "live" }
+        );
+    }
+    if let Some(color) = reconnect.flash_color {
+        if let Err(e) = crate::led::flash_reconnect(&dev, strategy, color) {
+            log::warn!("could not flash the LEDs after reconnect: {e}");
+        }
+        // The flash repainted the number LEDs behind the cache's back
+        reconnect.led_cache.invalidate();
+    }
+    let result = if resync == ReconnectResync::Flash && missed.is_some() {
+        crate::led::flash_resync(&dev, strategy, mute_color, is_muted)
+    } else {
+        reconnect
+            .led_cache
+            .refresh(&dev, strategy, mute_color, is_muted, Instant::now())
+            .map(|written| log::debug!("[reconnect] refreshed {written} LED(s)"))
+    };
+    if let Err(e) = result {
         log::warn!("could not re-apply mute indicator after reconnect: {e}");
     }
-    Some(dev)
+    Some((dev, missed))
+}
+
+// ── Hotplug ──
+
+/// A Focusrite USB device appeared or disappeared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HotplugEvent {
+    Arrived,
+    Removed,
+}
+
+/// OS hotplug notifications for Focusrite devices, watched on a background
+/// thread. With it, a replugged device is reopened right away instead of
+/// after the backoff delay; the backoff loop stays as the fallback.
+pub struct HotplugMonitor {
+    rx: mpsc::Receiver<HotplugEvent>,
+}
+
+impl HotplugMonitor {
+    /// Start watching. `None` if the platform has no hotplug support here
+    /// (Windows) or the watch can't be set up.
+    pub fn start() -> Option<Self> {
+        let (tx, rx) = mpsc::channel();
+        match spawn_hotplug_thread(tx) {
+            Ok(()) => Some(Self { rx }),
+            Err(e) => {
+                log::debug!("[hotplug] not available: {e}");
+                None
+            }
+        }
+    }
+
+    /// Pending events, without blocking.
+    pub fn poll(&self) -> Vec<HotplugEvent> {
+        self.rx.try_iter().collect()
+    }
+
+    /// Drain pending events and, if a device arrived, let `state` retry now.
+    /// Returns `true` if it did.
+    pub fn apply(&self, state: &mut ReconnectState) -> bool {
+        let arrived = self.poll().contains(&HotplugEvent::Arrived);
+        if arrived {
+            state.retry_now();
+        }
+        arrived
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn spawn_hotplug_thread(tx: mpsc::Sender<HotplugEvent>) -> std::io::Result<()> {
+    use std::collections::HashSet;
+
+    use crate::protocol::FOCUSRITE_VID;
+    use nusb::hotplug::HotplugEvent as UsbEvent;
+
+    // Watch before listing so a device plugged in between isn't missed
+    let watch = nusb::watch_devices()?;
+    // Removal events carry only the device ID, so remember which are ours
+    let mut focusrite: HashSet<nusb::DeviceId> = nusb::list_devices()?
+        .filter(|d| d.vendor_id() == FOCUSRITE_VID)
+        .map(|d| d.id())
+        .collect();
+    std::thread::Builder::new()
+        .name("usb-hotplug".into())
+        .spawn(move || {
+            for event in futures_lite::stream::block_on(watch) {
+                let event = match event {
+                    UsbEvent::Connected(d) if d.vendor_id() == FOCUSRITE_VID => {
+                        focusrite.insert(d.id());
+                        HotplugEvent::Arrived
+                    }
+                    UsbEvent::Disconnected(id) if focusrite.remove(&id) => HotplugEvent::Removed,
+                    _ => continue,
+                };
+                log::debug!("[hotplug] Focusrite device {event:?}");
+                if tx.send(event).is_err() {
+                    break;
+                }
+            }
+        })?;
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn spawn_hotplug_thread(_tx: mpsc::Sender<HotplugEvent>) -> std::io::Result<()> {
+    Err(std::io::ErrorKind::Unsupported.into())
+}
+
+// ── Startup wait ──
+
+/// Grace period after launch during which a missing device is not yet
+/// reported as disconnected (drivers may still be loading on autostart).
+#[derive(Debug)]
+pub struct StartupWait {
+    deadline: Option<Instant>,
+}
+
+impl StartupWait {
+    /// Start a grace period of `wait`. A zero duration disables waiting.
+    pub fn new(wait: Duration) -> Self {
+        Self {
+            deadline: (!wait.is_zero()).then(|| Instant::now() + wait),
+        }
+    }
+
+    /// `true` while the grace period is still running.
+    pub fn is_waiting(&self) -> bool {
+        self.deadline.is_some_and(|d| Instant::now() < d)
+    }
+
+    /// End the grace period early (e.g. the device appeared).
+    pub fn finish(&mut self) {
+        self.deadline = None;
+    }
+
+    /// Returns `true` exactly once, on the first call after the deadline passes.
+    pub fn poll_expired(&mut self) -> bool {
+        match self.deadline {
+            Some(d) if Instant::now() >= d => {
+                self.deadline = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Open the device, quietly retrying for up to `wait` before giving up.
+///
+/// Returns the last open error if the device never appears.
+pub fn open_with_startup_wait(
+    device_serial: &str,
+    options: DeviceOptions,
+    wait: Duration,
+) -> Result<crate::device::PlatformDevice, crate::device::DeviceError> {
+    const RETRY_INTERVAL: Duration = Duration::from_millis(500);
+    let startup = StartupWait::new(wait);
+    loop {
+        match crate::device::open_device_with_options(device_serial, options) {
+            Ok(dev) => return Ok(dev),
+            Err(e) if startup.is_waiting() => {
+                log::debug!("device not ready yet ({e}), retrying");
+                std::thread::sleep(RETRY_INTERVAL);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// How long [`reset_and_reopen`] waits for the device to re-enumerate.
+pub const RESET_REOPEN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Reset `device` (see [`ScarlettDevice::reset`](crate::device::ScarlettDevice::reset)),
+/// then open the same unit again by serial once it has re-enumerated.
+pub fn reset_and_reopen(
+    device: impl crate::device::ScarlettDevice,
+    options: DeviceOptions,
+) -> Result<crate::device::PlatformDevice, crate::device::DeviceError> {
+    let serial = device.info().serial.clone().unwrap_or_default();
+    device.reset()?;
+    drop(device);
+    // Give the old interface time to disappear before probing for the new one.
+    std::thread::sleep(Duration::from_millis(500));
+    open_with_startup_wait(&serial, options, RESET_REOPEN_TIMEOUT)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn startup_wait_zero_is_disabled() {
+        let mut w = StartupWait::new(Duration::ZERO);
+        assert!(!w.is_waiting());
+        assert!(!w.poll_expired());
+    }
+
+    #[test]
+    fn startup_wait_expires_once() {
+        let mut w = StartupWait::new(Duration::from_millis(20));
+        assert!(w.is_waiting());
+        assert!(!w.poll_expired());
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(!w.is_waiting());
+        assert!(w.poll_expired());
+        assert!(!w.poll_expired());
+    }
+
+    #[test]
+    fn startup_wait_finish_cancels_expiry() {
+        let mut w = StartupWait::new(Duration::from_millis(10));
+        w.finish();
+        assert!(!w.is_waiting());
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(!w.poll_expired());
+    }
+
     #[test]
     fn default_config_values() {
         let config = ReconnectConfig::default();
@@ -275,4 +656,108 @@ mod tests {
         assert_eq!(state.consecutive_failures(), 0);
         assert_eq!(state.current_delay(), Duration::from_secs(1));
     }
+
+    #[test]
+    fn hotplug_arrival_skips_backoff() {
+        let (tx, rx) = mpsc::channel();
+        let monitor = HotplugMonitor { rx };
+        let mut state = ReconnectState::with_defaults();
+        state.record_failure();
+        state.record_failure();
+        assert!(!state.should_attempt());
+
+        tx.send(HotplugEvent::Removed).unwrap();
+        assert!(!monitor.apply(&mut state));
+        assert!(!state.should_attempt());
+
+        tx.send(HotplugEvent::Arrived).unwrap();
+        assert!(monitor.apply(&mut state));
+        assert!(state.should_attempt());
+        assert_eq!(state.current_delay(), Duration::from_secs(1));
+        assert_eq!(state.consecutive_failures(), 2);
+        assert!(monitor.poll().is_empty());
+    }
+
+    #[test]
+    fn device_in_use_keeps_retrying() {
+        let mut state = ReconnectState::with_defaults();
+        state.set_hotplug_driven(true);
+        for _ in 0..HOTPLUG_ATTEMPTS * 2 {
+            state.record_error(DeviceError::InUse(Some("daw (PID 7)".into())));
+        }
+        assert_eq!(state.current_delay(), IN_USE_MAX_DELAY);
+        assert!(matches!(state.last_error(), Some(DeviceError::InUse(_))));
+
+        // Once released, the device may be gone — normal backoff again
+        state.record_error(DeviceError::NotFound);
+        assert_eq!(state.current_delay(), IN_USE_MAX_DELAY * 2);
+        state.record_success();
+        assert!(state.last_error().is_none());
+    }
+
+    #[test]
+    fn missed_mutes_remember_the_shown_state() {
+        let mut state = ReconnectState::with_defaults();
+        assert_eq!(state.take_missed_mutes(), None);
+        state.record_missed_mute(true);
+        state.record_missed_mute(false);
+        state.record_missed_mute(true);
+        // A reconnect doesn't clear them before they're taken
+        state.record_success();
+        assert_eq!(
+            state.take_missed_mutes(),
+            Some(MissedMuteChanges {
+                shown_muted: false,
+                count: 3
+            })
+        );
+        assert_eq!(state.take_missed_mutes(), None);
+    }
+
+    #[test]
+    fn parses_resync_modes() {
+        assert_eq!(
+            ReconnectResync::parse(" Flash "),
+            Some(ReconnectResync::Flash)
+        );
+        assert_eq!(
+            ReconnectResync::parse("notify"),
+            Some(ReconnectResync::Notify)
+        );
+        assert_eq!(
+            ReconnectResync::parse("silent"),
+            Some(ReconnectResync::Silent)
+        );
+        assert_eq!(ReconnectResync::parse("loud"), None);
+    }
+
+    #[test]
+    fn hotplug_driven_stops_probing_until_arrival() {
+        let mut state = ReconnectState::new(ReconnectConfig {
+            initial_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+            multiplier: 2.0,
+        });
+        state.set_hotplug_driven(true);
+        for _ in 0..HOTPLUG_ATTEMPTS {
+            assert!(state.should_attempt());
+            state.record_failure();
+        }
+        assert!(!state.should_attempt());
+
+        state.retry_now();
+        assert!(state.should_attempt());
+
+        // Without hotplug notifications the timer keeps going
+        state.set_hotplug_driven(false);
+        for _ in 0..HOTPLUG_ATTEMPTS + 1 {
+            state.record_failure();
+        }
+        assert!(state.should_attempt());
+
+        state.set_hotplug_driven(true);
+        state.reset();
+        assert!(state.should_attempt());
+        assert_eq!(state.consecutive_failures(), 0);
+    }
 }
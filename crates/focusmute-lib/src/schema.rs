@@ -62,10 +62,119 @@ pub struct SchemaConstants {
     /// Used for cache invalidation when firmware is updated.
     #[serde(default)]
     pub firmware_version: String,
+
+    /// `enablePhantomPower` APP_SPACE member, if the model has switchable 48V.
+    #[serde(default)]
+    pub phantom_power: Option<ControlMember>,
+
+    /// `inputAir` APP_SPACE member, if the model has Air.
+    #[serde(default)]
+    pub air: Option<ControlMember>,
+
+    /// `instInput` APP_SPACE member, if the model has instrument inputs.
+    #[serde(default)]
+    pub inst_input: Option<ControlMember>,
+
+    /// `preampInputGain` APP_SPACE member, if the model has software-controlled gain.
+    #[serde(default)]
+    pub preamp_gain: Option<ControlMember>,
+
+    /// `clipSafe` APP_SPACE member, if the model has Clip Safe.
+    #[serde(default)]
+    pub clip_safe: Option<ControlMember>,
+
+    /// `autogainInProgress` APP_SPACE member, if the model has Auto Gain.
+    #[serde(default)]
+    pub autogain: Option<ControlMember>,
+
+    /// `autogainExitStatus` offset in descriptor (u8 per input, read-only).
+    #[serde(default)]
+    pub autogain_status_offset: Option<u32>,
+
+    /// `standSampleRate` offset in descriptor (u16, Hz).
+    #[serde(default)]
+    pub sample_rate_offset: Option<u32>,
+
+    /// `standSyncSource` offset in descriptor (u16, 0 = internal clock).
+    #[serde(default)]
+    pub sync_source_offset: Option<u32>,
+
+    /// `directMonitoring` offset in descriptor (u8, 0 = off).
+    #[serde(default)]
+    pub direct_monitoring_offset: Option<u32>,
+
+    /// `activeMonitorGroup` APP_SPACE member (0 = main speakers, 1 = alt), if
+    /// the model has speaker switching.
+    #[serde(default)]
+    pub alt_speakers: Option<ControlMember>,
+
+    /// `outputVol` APP_SPACE member (i16 dB per output).
+    #[serde(default)]
+    pub output_volume: Option<VolumeMember>,
+}
+
+/// An i16-per-channel volume member. Writable only when the firmware lists a
+/// notify ID for it — otherwise the value follows a hardware knob.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VolumeMember {
+    /// Descriptor offset of the first channel.
+    pub offset: u32,
+    /// array-shape[0] — number of outputs.
+    pub count: usize,
+    /// notify-device event ID sent after a write, if software can set it.
+    pub notify: Option<u32>,
+}
+
+/// An APP_SPACE member that switches a device feature (one u8 per channel).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ControlMember {
+    /// Descriptor offset of the first channel.
+    pub offset: u32,
+    /// array-shape[0] — number of channels (1 for scalar members).
+    pub count: usize,
+    /// notify-device event ID sent after a write.
+    pub notify: u32,
+    /// set-via-parameter-buffer — writes go through `parameterChannel` /
+    /// `parameterValue` instead of the member's own offset.
+    pub via_parameter_buffer: bool,
 }
 
 /// Read raw schema pages from device, concatenate payloads.
 pub fn read_schema_raw(device: &impl ScarlettDevice) -> Result<Vec<u8>> {
+    read_schema_raw_with_page_size(device, DEVMAP_PAGE_SIZE)
+}
+
+/// Like [`read_schema_raw`], but requests `page_size` payload bytes per
+/// GET_DEVMAP page. Used by `probe --bench-schema` to experiment with page
+/// sizes; the firmware defines the page granularity, so sizes other than
+/// [`DEVMAP_PAGE_SIZE`] may yield data that fails to decode.
+pub fn read_schema_raw_with_page_size(
+    device: &impl ScarlettDevice,
+    page_size: usize,
+) -> Result<Vec<u8>> {
+    let total_size = read_devmap_len(device)?;
+    let mut raw = Vec::with_capacity(total_size);
+    for page in 0..devmap_page_count(total_size, page_size)? {
+        raw.extend_from_slice(&read_devmap_page(device, page, page_size)?);
+    }
+
+    // Trim to exact total_size
+    raw.truncate(total_size);
+    Ok(raw)
+}
+
+/// Number of GET_DEVMAP pages needed to cover `total_size` bytes.
+pub fn devmap_page_count(total_size: usize, page_size: usize) -> Result<usize> {
+    if page_size == 0 {
+        return Err(DeviceError::TransactFailed(
+            "devmap page size must be non-zero".into(),
+        ));
+    }
+    Ok(total_size.div_ceil(page_size))
+}
+
+/// Read the schema content length (`config_len`) via INFO_DEVMAP.
+pub fn read_devmap_len(device: &impl ScarlettDevice) -> Result<usize> {
     // Step 1: Get schema content length via INFO_DEVMAP.
     // Response payload (after 8-byte transact header): { u16 unknown, u16 config_len }
     let info_resp = device.transact(CMD_INFO_DEVMAP, &[], 12)?;
@@ -81,26 +190,24 @@ pub fn read_schema_raw(device: &impl ScarlettDevice) -> Result<Vec<u8>> {
             "INFO_DEVMAP returned config_len 0".into(),
         ));
     }
+    Ok(total_size)
+}
 
-    // Step 2: Read pages
-    let page_count = total_size.div_ceil(DEVMAP_PAGE_SIZE);
-    let mut raw = Vec::with_capacity(total_size);
-
-    for page in 0..page_count {
-        let payload = (page as u32).to_le_bytes();
-        let resp = device.transact(CMD_GET_DEVMAP, &payload, DEVMAP_RESPONSE_SIZE)?;
-        if resp.len() <= 8 {
-            return Err(DeviceError::TransactFailed(format!(
-                "GET_DEVMAP page {page} response too short: {} bytes",
-                resp.len()
-            )));
-        }
-        raw.extend_from_slice(&resp[8..]);
+/// Read one GET_DEVMAP page, returning its payload without the 8-byte header.
+pub fn read_devmap_page(
+    device: &impl ScarlettDevice,
+    page: usize,
+    page_size: usize,
+) -> Result<Vec<u8>> {
+    let payload = (page as u32).to_le_bytes();
+    let resp = device.transact(CMD_GET_DEVMAP, &payload, 8 + page_size)?;
+    if resp.len() <= 8 {
+        return Err(DeviceError::TransactFailed(format!(
+            "GET_DEVMAP page {page} response too short: {} bytes",
+            resp.len()
+        )));
     }
-
-    // Trim to exact total_size
-    raw.truncate(total_size);
-    Ok(raw)
+    Ok(resp[8..].to_vec())
 }
 
 /// Decode raw schema bytes into a JSON string.
@@ -260,6 +367,57 @@ pub fn parse_schema(json: &str) -> crate::error::Result<SchemaConstants> {
         features
     };
 
+    // Extract enablePhantomPower member (optional — absent on models without 48V)
+    let phantom_power = root
+        .pointer("/structs/APP_SPACE/members/enablePhantomPower")
+        .and_then(control_member);
+
+    // Extract inputAir member (optional — absent on models without Air)
+    let air = root
+        .pointer("/structs/APP_SPACE/members/inputAir")
+        .and_then(control_member);
+
+    // Extract instInput member (optional — absent on models without Inst inputs)
+    let inst_input = root
+        .pointer("/structs/APP_SPACE/members/instInput")
+        .and_then(control_member);
+
+    // Extract preampInputGain member (optional — absent on models without digital gain)
+    let preamp_gain = root
+        .pointer("/structs/APP_SPACE/members/preampInputGain")
+        .and_then(control_member);
+
+    // Extract clipSafe member (optional — absent on models without Clip Safe)
+    let clip_safe = root
+        .pointer("/structs/APP_SPACE/members/clipSafe")
+        .and_then(control_member);
+
+    // Extract autogainInProgress member (optional — absent on models without Auto Gain)
+    let autogain = root
+        .pointer("/structs/APP_SPACE/members/autogainInProgress")
+        .and_then(control_member);
+
+    // Extract clock and Auto Gain status members (optional — plain offsets, read-only)
+    let member_offset = |name: &str| {
+        root.pointer(&format!("/structs/APP_SPACE/members/{name}/offset"))
+            .and_then(|v| v.as_u64())
+            .map(|v| v as u32)
+    };
+    let sample_rate_offset = member_offset("standSampleRate");
+    let sync_source_offset = member_offset("standSyncSource");
+    let direct_monitoring_offset = member_offset("directMonitoring");
+    let autogain_status_offset = member_offset("autogainExitStatus");
+
+    // Extract activeMonitorGroup member (optional — only larger models switch speakers)
+    let alt_speakers = root
+        .pointer("/structs/APP_SPACE/members/activeMonitorGroup")
+        .and_then(control_member);
+
+    // Extract outputVol member (optional — notify-device absent when knob-controlled)
+    let output_volume = root
+        .pointer("/structs/APP_SPACE/members/outputVol")
+        .and_then(volume_member);
+
     Ok(SchemaConstants {
         product_name,
         max_leds,
@@ -274,6 +432,18 @@ pub fn parse_schema(json: &str) -> crate::error::Result<SchemaConstants> {
         input_controls,
         app_space_features,
         firmware_version: String::new(),
+        phantom_power,
+        air,
+        inst_input,
+        preamp_gain,
+        clip_safe,
+        autogain,
+        autogain_status_offset,
+        sample_rate_offset,
+        sync_source_offset,
+        direct_monitoring_offset,
+        alt_speakers,
+        output_volume,
     })
 }
 
@@ -291,15 +461,45 @@ pub fn cache_path() -> Option<PathBuf> {
     crate::config::Config::dir().map(|d| d.join("schema_cache.json"))
 }
 
+/// On-disk cache entry: the constants plus an integrity checksum.
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    #[serde(flatten)]
+    constants: SchemaConstants,
+    /// [`cache_checksum`] of `constants`. Absent in caches written before
+    /// checksums were added; those are still accepted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    checksum: Option<String>,
+}
+
+/// FNV-1a (64-bit) over the compact JSON of `constants`. Stable across builds,
+/// unlike `DefaultHasher`.
+fn cache_checksum(constants: &SchemaConstants) -> String {
+    let json = serde_json::to_vec(constants).unwrap_or_default();
+    let hash = json.iter().fold(0xcbf2_9ce4_8422_2325u64, |h, &b| {
+        (h ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    format!("{hash:016x}")
+}
+
 /// Save SchemaConstants to cache file.
 pub fn save_cache(constants: &SchemaConstants) -> std::io::Result<()> {
     let path = cache_path()
         .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "No config directory"))?;
+    save_cache_to(&path, constants)
+}
+
+/// Save SchemaConstants to a specific path, with an integrity checksum.
+pub fn save_cache_to(path: &std::path::Path, constants: &SchemaConstants) -> std::io::Result<()> {
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent)?;
     }
-    let json = serde_json::to_string_pretty(constants).map_err(std::io::Error::other)?;
-    std::fs::write(&path, json)
+    let entry = CacheEntry {
+        checksum: Some(cache_checksum(constants)),
+        constants: constants.clone(),
+    };
+    let json = serde_json::to_string_pretty(&entry).map_err(std::io::Error::other)?;
+    std::fs::write(path, json)
 }
 
 /// Load SchemaConstants from cache file, if it exists and matches the model name + firmware.
@@ -312,13 +512,32 @@ pub fn load_cache(model_name: &str, firmware_version: &str) -> Option<SchemaCons
 }
 
 /// Load SchemaConstants from a specific path. Testable without relying on platform config dirs.
+///
+/// Unreadable or corrupted caches (bad JSON or checksum mismatch) yield `None`,
+/// so the caller re-extracts from the device and overwrites them.
 pub fn load_cache_from(
     path: &std::path::Path,
     model_name: &str,
     firmware_version: &str,
 ) -> Option<SchemaConstants> {
     let data = std::fs::read_to_string(path).ok()?;
-    let cached: SchemaConstants = serde_json::from_str(&data).ok()?;
+    let entry: CacheEntry = match serde_json::from_str(&data) {
+        Ok(entry) => entry,
+        Err(e) => {
+            log::warn!("ignoring corrupted schema cache {}: {e}", path.display());
+            return None;
+        }
+    };
+    if let Some(ref checksum) = entry.checksum
+        && *checksum != cache_checksum(&entry.constants)
+    {
+        log::warn!(
+            "ignoring schema cache {}: checksum mismatch",
+            path.display()
+        );
+        return None;
+    }
+    let cached = entry.constants;
     if !cached.product_name.eq_ignore_ascii_case(model_name) {
         return None;
     }
@@ -342,8 +561,127 @@ pub fn extract_or_cached(device: &impl ScarlettDevice) -> crate::error::Result<S
     Ok(constants)
 }
 
+/// Re-extract the schema from the device and overwrite the cache, e.g. when
+/// the cached copy is stale.
+pub fn refresh_cache(device: &impl ScarlettDevice) -> crate::error::Result<SchemaConstants> {
+    let mut constants = extract_schema(device)?;
+    constants.firmware_version = device.info().firmware.to_string();
+    save_cache(&constants)?;
+    Ok(constants)
+}
+
+/// Delete the schema cache. Returns `false` if there was none.
+pub fn clear_cache() -> std::io::Result<bool> {
+    match cache_path() {
+        Some(path) => clear_cache_at(&path),
+        None => Ok(false),
+    }
+}
+
+/// Delete the schema cache at `path`. Returns `false` if there was none.
+pub fn clear_cache_at(path: &std::path::Path) -> std::io::Result<bool> {
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// What the schema cache holds, for display.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheStatus {
+    pub model: String,
+    /// Empty for caches written before the firmware version was recorded.
+    pub firmware: String,
+    /// Time since the cache was written, if the file time is readable.
+    pub age: Option<std::time::Duration>,
+}
+
+impl std::fmt::Display for CacheStatus {
+    /// `Scarlett 2i2 4th Gen, firmware 2.0.2417.0, 3 days old`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let firmware = if self.firmware.is_empty() {
+            "unknown"
+        } else {
+            &self.firmware
+        };
+        write!(f, "{}, firmware {firmware}", self.model)?;
+        if let Some(age) = self.age {
+            let plural =
+                |n: u64, unit: &str| format!("{n} {unit}{}", if n == 1 { "" } else { "s" });
+            let secs = age.as_secs();
+            let age = match secs {
+                0..60 => "under a minute".to_string(),
+                60..3600 => plural(secs / 60, "minute"),
+                3600..86400 => plural(secs / 3600, "hour"),
+                _ => plural(secs / 86400, "day"),
+            };
+            write!(f, ", {age} old")?;
+        }
+        Ok(())
+    }
+}
+
+/// Model, firmware, and age of the schema cache. `None` if there is no
+/// readable cache.
+pub fn cache_status() -> Option<CacheStatus> {
+    cache_status_from(&cache_path()?)
+}
+
+/// [`cache_status`] for the cache file at `path`.
+pub fn cache_status_from(path: &std::path::Path) -> Option<CacheStatus> {
+    let json = std::fs::read_to_string(path).ok()?;
+    let entry: CacheEntry = serde_json::from_str(&json).ok()?;
+    let age = std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.elapsed().ok());
+    Some(CacheStatus {
+        model: entry.constants.product_name,
+        firmware: entry.constants.firmware_version,
+        age,
+    })
+}
+
 // ── Helpers ──
 
+/// Parse an APP_SPACE control member. `None` unless it has both an offset and
+/// a notify-device ID.
+fn control_member(member: &serde_json::Value) -> Option<ControlMember> {
+    let count = member
+        .get("array-shape")
+        .and_then(|v| v.as_array())
+        .and_then(|a| a.first())
+        .and_then(|v| v.as_u64())
+        .unwrap_or(1) as usize;
+    Some(ControlMember {
+        offset: member.get("offset")?.as_u64()? as u32,
+        count,
+        notify: member.get("notify-device")?.as_u64()? as u32,
+        via_parameter_buffer: member
+            .get("set-via-parameter-buffer")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+    })
+}
+
+fn volume_member(member: &serde_json::Value) -> Option<VolumeMember> {
+    let count = member
+        .get("array-shape")
+        .and_then(|v| v.as_array())
+        .and_then(|a| a.first())
+        .and_then(|v| v.as_u64())
+        .unwrap_or(1) as usize;
+    Some(VolumeMember {
+        offset: member.get("offset")?.as_u64()? as u32,
+        count,
+        notify: member
+            .get("notify-device")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as u32),
+    })
+}
+
 fn extract_enum_value(enumerators: &serde_json::Value, key: &str) -> crate::error::Result<usize> {
     enumerators
         .get(key)
@@ -544,6 +882,141 @@ mod tests {
         assert_eq!(constants.direct_led_count, 40);
     }
 
+    #[test]
+    fn parse_schema_control_members() {
+        assert_eq!(
+            parse_schema(&test_schema_json()).unwrap().phantom_power,
+            None
+        );
+
+        let mut root: serde_json::Value = serde_json::from_str(&test_schema_json()).unwrap();
+        root["structs"]["APP_SPACE"]["members"]["enablePhantomPower"] = serde_json::json!({
+            "offset": 72,
+            "array-shape": [1],
+            "notify-device": 11,
+            "set-via-parameter-buffer": true
+        });
+        root["structs"]["APP_SPACE"]["members"]["inputAir"] = serde_json::json!({
+            "offset": 62,
+            "array-shape": [2],
+            "notify-device": 11,
+            "set-via-parameter-buffer": true
+        });
+        root["structs"]["APP_SPACE"]["members"]["instInput"] = serde_json::json!({
+            "offset": 60,
+            "array-shape": [2],
+            "notify-device": 11,
+            "set-via-parameter-buffer": true
+        });
+        let constants = parse_schema(&root.to_string()).unwrap();
+        assert_eq!(constants.air.map(|a| (a.offset, a.count)), Some((62, 2)));
+        assert_eq!(
+            constants.inst_input.map(|a| (a.offset, a.count)),
+            Some((60, 2))
+        );
+        assert_eq!(constants.sample_rate_offset, None);
+        assert_eq!(constants.preamp_gain, None);
+        assert_eq!(constants.autogain, None);
+        assert_eq!(constants.clip_safe, None);
+        assert_eq!(constants.output_volume, None);
+        assert_eq!(constants.alt_speakers, None);
+        root["structs"]["APP_SPACE"]["members"]["preampInputGain"] = serde_json::json!({
+            "offset": 75,
+            "array-shape": [2],
+            "notify-device": 12,
+            "set-via-parameter-buffer": true
+        });
+        root["structs"]["APP_SPACE"]["members"]["autogainInProgress"] = serde_json::json!({
+            "offset": 309,
+            "array-shape": [2],
+            "notify-device": 10,
+            "set-via-parameter-buffer": true
+        });
+        root["structs"]["APP_SPACE"]["members"]["clipSafe"] = serde_json::json!({
+            "offset": 327,
+            "array-shape": [2],
+            "notify-device": 14,
+            "set-via-parameter-buffer": true
+        });
+        root["structs"]["APP_SPACE"]["members"]["outputVol"] = serde_json::json!({
+            "type": "int16",
+            "offset": 50,
+            "array-shape": [2],
+            "notify-device": null,
+            "set-via-parameter-buffer": false
+        });
+        root["structs"]["APP_SPACE"]["members"]["autogainExitStatus"] = serde_json::json!({
+            "offset": 311,
+            "array-shape": [2],
+            "notify-device": 10,
+            "set-via-parameter-buffer": false
+        });
+        root["structs"]["APP_SPACE"]["members"]["activeMonitorGroup"] = serde_json::json!({
+            "offset": 340,
+            "notify-device": 15,
+            "set-via-parameter-buffer": false
+        });
+        root["structs"]["APP_SPACE"]["members"]["standSampleRate"] = serde_json::json!({
+            "type": "uint16",
+            "offset": 70,
+            "size": 2
+        });
+        let constants = parse_schema(&root.to_string()).unwrap();
+        assert_eq!(constants.sample_rate_offset, Some(70));
+        assert_eq!(
+            constants.preamp_gain.map(|g| (g.offset, g.count, g.notify)),
+            Some((75, 2, 12))
+        );
+        assert_eq!(
+            constants.autogain.map(|a| (a.offset, a.count, a.notify)),
+            Some((309, 2, 10))
+        );
+        assert_eq!(constants.autogain_status_offset, Some(311));
+        assert_eq!(
+            constants
+                .alt_speakers
+                .map(|c| (c.offset, c.count, c.notify)),
+            Some((340, 1, 15))
+        );
+        assert_eq!(
+            constants.output_volume,
+            Some(VolumeMember {
+                offset: 50,
+                count: 2,
+                notify: None,
+            })
+        );
+        assert_eq!(
+            constants.clip_safe.map(|c| (c.offset, c.count, c.notify)),
+            Some((327, 2, 14))
+        );
+        assert_eq!(
+            constants.phantom_power,
+            Some(ControlMember {
+                offset: 72,
+                count: 1,
+                notify: 11,
+                via_parameter_buffer: true,
+            })
+        );
+    }
+
+    #[test]
+    fn zero_page_size_is_rejected() {
+        assert!(devmap_page_count(5333, 0).is_err());
+        assert_eq!(devmap_page_count(5333, DEVMAP_PAGE_SIZE).unwrap(), 6);
+        assert_eq!(devmap_page_count(5333, 512).unwrap(), 11);
+    }
+
+    #[test]
+    fn read_devmap_page_strips_header() {
+        let dev = MockDevice::new();
+        let mut resp = vec![0u8; 8];
+        resp.extend_from_slice(b"abcd");
+        dev.add_transact_response(CMD_GET_DEVMAP, resp);
+        assert_eq!(read_devmap_page(&dev, 0, 4).unwrap(), b"abcd");
+    }
+
     #[test]
     fn extract_schema_device_error_propagates() {
         let dev = MockDevice::new();
@@ -573,6 +1046,18 @@ mod tests {
             input_controls: vec!["air".into(), "instrument".into()],
             app_space_features: vec!["directMonitoring".into()],
             firmware_version: "2.0.2417.0".into(),
+            phantom_power: None,
+            air: None,
+            inst_input: None,
+            preamp_gain: None,
+            clip_safe: None,
+            autogain: None,
+            autogain_status_offset: None,
+            sample_rate_offset: None,
+            sync_source_offset: None,
+            direct_monitoring_offset: None,
+            alt_speakers: None,
+            output_volume: None,
         };
         let json = serde_json::to_string(&constants).unwrap();
         let restored: SchemaConstants = serde_json::from_str(&json).unwrap();
@@ -763,6 +1248,18 @@ mod tests {
             input_controls: vec![],
             app_space_features: vec![],
             firmware_version: "2.0.2417.0".into(),
+            phantom_power: None,
+            air: None,
+            inst_input: None,
+            preamp_gain: None,
+            clip_safe: None,
+            autogain: None,
+            autogain_status_offset: None,
+            sample_rate_offset: None,
+            sync_source_offset: None,
+            direct_monitoring_offset: None,
+            alt_speakers: None,
+            output_volume: None,
         };
         let json = serde_json::to_string(&constants).unwrap();
         let restored: SchemaConstants = serde_json::from_str(&json).unwrap();
@@ -795,9 +1292,54 @@ mod tests {
             input_controls: vec![],
             app_space_features: vec![],
             firmware_version: fw.into(),
+            phantom_power: None,
+            air: None,
+            inst_input: None,
+            preamp_gain: None,
+            clip_safe: None,
+            autogain: None,
+            autogain_status_offset: None,
+            sample_rate_offset: None,
+            sync_source_offset: None,
+            direct_monitoring_offset: None,
+            alt_speakers: None,
+            output_volume: None,
         }
     }
 
+    #[test]
+    fn cache_status_and_clear() {
+        let path = write_test_cache("cache_status", &test_constants("2.0.2417.0"));
+        let status = cache_status_from(&path).unwrap();
+        assert_eq!(status.model, "Scarlett 2i2 4th Gen");
+        assert_eq!(status.firmware, "2.0.2417.0");
+        assert!(status.age.is_some());
+
+        assert!(clear_cache_at(&path).unwrap());
+        assert!(!clear_cache_at(&path).unwrap());
+        assert!(cache_status_from(&path).is_none());
+    }
+
+    #[test]
+    fn cache_status_display() {
+        let mut status = CacheStatus {
+            model: "Scarlett 2i2 4th Gen".into(),
+            firmware: "2.0.2417.0".into(),
+            age: Some(std::time::Duration::from_secs(3 * 86400 + 5)),
+        };
+        assert_eq!(
+            status.to_string(),
+            "Scarlett 2i2 4th Gen, firmware 2.0.2417.0, 3 days old"
+        );
+        status.age = Some(std::time::Duration::from_secs(3600));
+        assert!(status.to_string().ends_with(", 1 hour old"));
+        status.age = Some(std::time::Duration::from_secs(10));
+        assert!(status.to_string().ends_with(", under a minute old"));
+        status.firmware.clear();
+        status.age = None;
+        assert_eq!(status.to_string(), "Scarlett 2i2 4th Gen, firmware unknown");
+    }
+
     #[test]
     fn load_cache_from_matching() {
         let path = write_test_cache("cache_match", &test_constants("2.0.2417.0"));
@@ -831,6 +1373,40 @@ mod tests {
         let _ = std::fs::remove_file(&path);
     }
 
+    #[test]
+    fn save_cache_to_writes_checksum_and_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("schema_cache.json");
+        save_cache_to(&path, &test_constants("2.0.2417.0")).unwrap();
+        assert!(
+            std::fs::read_to_string(&path)
+                .unwrap()
+                .contains("\"checksum\"")
+        );
+        let result = load_cache_from(&path, "Scarlett 2i2 4th Gen", "2.0.2417.0");
+        assert_eq!(result.unwrap().direct_led_count, 40);
+    }
+
+    #[test]
+    fn load_cache_from_checksum_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("schema_cache.json");
+        save_cache_to(&path, &test_constants("2.0.2417.0")).unwrap();
+        let tampered = std::fs::read_to_string(&path)
+            .unwrap()
+            .replace("\"direct_led_count\": 40", "\"direct_led_count\": 4");
+        std::fs::write(&path, tampered).unwrap();
+        assert!(load_cache_from(&path, "Scarlett 2i2 4th Gen", "2.0.2417.0").is_none());
+    }
+
+    #[test]
+    fn load_cache_from_truncated_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("schema_cache.json");
+        std::fs::write(&path, "{\"product_name\": \"Scarlett 2i2").unwrap();
+        assert!(load_cache_from(&path, "Scarlett 2i2 4th Gen", "2.0.2417.0").is_none());
+    }
+
     #[test]
     fn load_cache_from_nonexistent_file() {
         let path = PathBuf::from("/tmp/focusmute_nonexistent_cache.json");
@@ -0,0 +1,392 @@
+//! Shared LED layouts — a versioned JSON format for exchanging `map` results.
+//!
+//! `map --output` writes a [`SharedLayout`]; `layout import` validates one and
+//! stores it in the config directory's `layouts/` folder, keyed by model name.
+//! [`crate::context::DeviceContext`] loads an imported layout for models
+//! without a hardcoded profile in place of the schema prediction.
+//!
+//! ```json
+//! {
+//!   "format": "focusmute-layout",
+//!   "version": 1,
+//!   "model": "Scarlett 16i16 4th Gen",
+//!   "firmware": "2.0.2417.0",
+//!   "total_leds": 48,
+//!   "leds": [{ "index": 0, "label": "Input 1", "role": "InputNumber" }],
+//!   "halos": [{ "name": "output", "leds": [32, 33, 34] }]
+//! }
+//! ```
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::FocusmuteError;
+use crate::layout::{Confidence, LedZone, PredictedLayout, PredictedLed};
+
+/// Value of the `format` field.
+pub const FORMAT: &str = "focusmute-layout";
+
+/// Current format version. Files with a newer version are rejected.
+pub const VERSION: u32 = 1;
+
+/// One LED and its role.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SharedLed {
+    pub index: usize,
+    pub label: String,
+    pub role: LedZone,
+}
+
+/// A named halo ring and the LED indices that form it, in segment order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HaloGroup {
+    /// `input-1`, `input-2`, …, or `output`.
+    pub name: String,
+    pub leds: Vec<usize>,
+}
+
+/// A mapped LED layout in the shared format.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SharedLayout {
+    pub format: String,
+    pub version: u32,
+    /// Model name as reported by the device (e.g. `Scarlett 16i16 4th Gen`).
+    pub model: String,
+    /// Firmware version the mapping was made on.
+    #[serde(default)]
+    pub firmware: String,
+    pub total_leds: usize,
+    pub leds: Vec<SharedLed>,
+    #[serde(default)]
+    pub halos: Vec<HaloGroup>,
+}
+
+impl SharedLayout {
+    /// Build from a (possibly corrected) layout. Halo groups are derived from
+    /// the LED roles: each input number LED starts a new input halo.
+    pub fn from_layout(layout: &PredictedLayout, model: &str, firmware: &str) -> Self {
+        let mut leds: Vec<&PredictedLed> = layout.leds.iter().collect();
+        leds.sort_by_key(|l| l.index);
+
+        let mut halos: Vec<HaloGroup> = Vec::new();
+        let mut input = 0;
+        for led in &leds {
+            match led.zone {
+                LedZone::InputNumber => input += 1,
+                LedZone::InputHalo => {
+                    let name = format!("input-{}", input.max(1));
+                    push_halo(&mut halos, name, led.index);
+                }
+                LedZone::OutputHalo => push_halo(&mut halos, "output".into(), led.index),
+                LedZone::Button => {}
+            }
+        }
+
+        SharedLayout {
+            format: FORMAT.into(),
+            version: VERSION,
+            model: model.into(),
+            firmware: firmware.into(),
+            total_leds: layout.total_leds,
+            leds: leds
+                .into_iter()
+                .map(|l| SharedLed {
+                    index: l.index,
+                    label: l.label.clone(),
+                    role: l.zone,
+                })
+                .collect(),
+            halos,
+        }
+    }
+
+    /// Parse and validate a shared layout file.
+    pub fn parse(json: &str) -> crate::error::Result<Self> {
+        let layout: SharedLayout = serde_json::from_str(json)
+            .map_err(|e| FocusmuteError::Layout(format!("invalid layout file: {e}")))?;
+        layout.validate()?;
+        Ok(layout)
+    }
+
+    /// Check the format tag, version, and LED indices.
+    pub fn validate(&self) -> crate::error::Result<()> {
+        if self.format != FORMAT {
+            return Err(FocusmuteError::Layout(format!(
+                "not a FocusMute layout (format \"{}\", expected \"{FORMAT}\")",
+                self.format
+            )));
+        }
+        if self.version == 0 || self.version > VERSION {
+            return Err(FocusmuteError::Layout(format!(
+                "unsupported layout version {} (this build reads version {VERSION})",
+                self.version
+            )));
+        }
+        if self.model.trim().is_empty() {
+            return Err(FocusmuteError::Layout("layout has no model name".into()));
+        }
+        let mut seen = HashSet::new();
+        for led in &self.leds {
+            if led.index >= self.total_leds {
+                return Err(FocusmuteError::Layout(format!(
+                    "LED index {} out of range (total_leds = {})",
+                    led.index, self.total_leds
+                )));
+            }
+            if !seen.insert(led.index) {
+                return Err(FocusmuteError::Layout(format!(
+                    "LED index {} listed more than once",
+                    led.index
+                )));
+            }
+        }
+        if !self.leds.iter().any(|l| l.role == LedZone::InputNumber) {
+            return Err(FocusmuteError::Layout(
+                "layout has no InputNumber LEDs — nothing to show mute on".into(),
+            ));
+        }
+        for halo in &self.halos {
+            if let Some(&i) = halo.leds.iter().find(|&&i| i >= self.total_leds) {
+                return Err(FocusmuteError::Layout(format!(
+                    "halo \"{}\" LED index {i} out of range",
+                    halo.name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Convert to a [`PredictedLayout`] for strategy resolution. Every LED is
+    /// marked [`Confidence::High`] — the layout was mapped on hardware.
+    pub fn to_predicted(&self) -> PredictedLayout {
+        let count = |zone| self.leds.iter().filter(|l| l.role == zone).count();
+        let buttons: Vec<usize> = self
+            .leds
+            .iter()
+            .filter(|l| l.role == LedZone::Button)
+            .map(|l| l.index)
+            .collect();
+        let mut leds: Vec<PredictedLed> = self
+            .leds
+            .iter()
+            .map(|l| PredictedLed {
+                index: l.index,
+                label: l.label.clone(),
+                confidence: Confidence::High,
+                zone: l.role,
+            })
+            .collect();
+        leds.sort_by_key(|l| l.index);
+        PredictedLayout {
+            product_name: self.model.clone(),
+            total_leds: self.total_leds,
+            input_count: count(LedZone::InputNumber),
+            output_halo_segments: count(LedZone::OutputHalo),
+            first_button_index: buttons.iter().copied().min().unwrap_or(self.total_leds),
+            button_count: buttons.len(),
+            leds,
+        }
+    }
+}
+
+fn push_halo(halos: &mut Vec<HaloGroup>, name: String, index: usize) {
+    match halos.iter_mut().find(|h| h.name == name) {
+        Some(h) => h.leds.push(index),
+        None => halos.push(HaloGroup {
+            name,
+            leds: vec![index],
+        }),
+    }
+}
+
+/// Directory holding imported layouts (`<config dir>/layouts`).
+pub fn layouts_dir() -> Option<PathBuf> {
+    crate::config::Config::dir().map(|d| d.join("layouts"))
+}
+
+/// File name for a model's layout, e.g. `scarlett-16i16-4th-gen.json`.
+pub fn layout_file_name(model: &str) -> String {
+    let slug: String = model
+        .trim()
+        .to_ascii_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let slug: Vec<&str> = slug.split('-').filter(|s| !s.is_empty()).collect();
+    format!("{}.json", slug.join("-"))
+}
+
+/// Validate the layout at `src` and copy it into `dir`. Returns the parsed
+/// layout and the path it was stored at.
+pub fn import_to(src: &Path, dir: &Path) -> crate::error::Result<(SharedLayout, PathBuf)> {
+    let json = std::fs::read_to_string(src)?;
+    let layout = SharedLayout::parse(&json)?;
+    std::fs::create_dir_all(dir)?;
+    let dest = dir.join(layout_file_name(&layout.model));
+    std::fs::write(&dest, json)?;
+    Ok((layout, dest))
+}
+
+/// Load the imported layout for `model` from `dir`, if one exists. An invalid
+/// file is logged and ignored.
+pub fn load_from(dir: &Path, model: &str) -> Option<SharedLayout> {
+    let path = dir.join(layout_file_name(model));
+    let json = std::fs::read_to_string(&path).ok()?;
+    match SharedLayout::parse(&json) {
+        Ok(layout) if layout.model.eq_ignore_ascii_case(model.trim()) => Some(layout),
+        Ok(layout) => {
+            log::warn!(
+                "ignoring {}: it is for \"{}\", not \"{model}\"",
+                path.display(),
+                layout.model
+            );
+            None
+        }
+        Err(e) => {
+            log::warn!("ignoring {}: {e}", path.display());
+            None
+        }
+    }
+}
+
+/// Load the imported layout for `model` from [`layouts_dir`].
+pub fn load_user_layout(model: &str) -> Option<SharedLayout> {
+    load_from(&layouts_dir()?, model)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> SharedLayout {
+        SharedLayout {
+            format: FORMAT.into(),
+            version: VERSION,
+            model: "Scarlett 16i16 4th Gen".into(),
+            firmware: "2.0.2417.0".into(),
+            total_leds: 4,
+            leds: vec![
+                SharedLed {
+                    index: 0,
+                    label: "Input 1".into(),
+                    role: LedZone::InputNumber,
+                },
+                SharedLed {
+                    index: 1,
+                    label: "Input 2".into(),
+                    role: LedZone::InputNumber,
+                },
+                SharedLed {
+                    index: 3,
+                    label: "48V".into(),
+                    role: LedZone::Button,
+                },
+            ],
+            halos: vec![],
+        }
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let layout = sample();
+        let json = serde_json::to_string_pretty(&layout).unwrap();
+        assert_eq!(SharedLayout::parse(&json).unwrap(), layout);
+    }
+
+    #[test]
+    fn rejects_wrong_format_newer_version_and_bad_indices() {
+        let mut l = sample();
+        l.format = "something-else".into();
+        assert!(l.validate().is_err());
+
+        let mut l = sample();
+        l.version = VERSION + 1;
+        assert!(l.validate().unwrap_err().to_string().contains("version"));
+
+        let mut l = sample();
+        l.leds[2].index = 4;
+        assert!(l.validate().is_err());
+
+        let mut l = sample();
+        l.leds[1].index = 0;
+        assert!(
+            l.validate()
+                .unwrap_err()
+                .to_string()
+                .contains("more than once")
+        );
+    }
+
+    #[test]
+    fn to_predicted_counts_roles() {
+        let predicted = sample().to_predicted();
+        assert_eq!(predicted.input_count, 2);
+        assert_eq!(predicted.first_button_index, 3);
+        assert_eq!(predicted.button_count, 1);
+        assert!(
+            predicted
+                .leds
+                .iter()
+                .all(|l| l.confidence == Confidence::High)
+        );
+    }
+
+    #[test]
+    fn from_predicted_layout_groups_halos() {
+        let schema = crate::schema::SchemaConstants {
+            product_name: "Scarlett 2i2 4th Gen".into(),
+            max_leds: 40,
+            max_inputs: 2,
+            max_outputs: 2,
+            gradient_count: 11,
+            gradient_offset: 384,
+            gradient_notify: 9,
+            direct_led_count: 40,
+            direct_led_offset: 92,
+            metering_segments: 25,
+            input_controls: vec![],
+            app_space_features: vec![],
+            firmware_version: String::new(),
+            phantom_power: None,
+            air: None,
+            inst_input: None,
+            preamp_gain: None,
+            clip_safe: None,
+            autogain: None,
+            autogain_status_offset: None,
+            sample_rate_offset: None,
+            sync_source_offset: None,
+            direct_monitoring_offset: None,
+            alt_speakers: None,
+            output_volume: None,
+        };
+        let predicted = crate::layout::predict_layout(&schema).unwrap();
+        let shared = SharedLayout::from_layout(&predicted, "Scarlett 2i2 4th Gen", "2.0");
+        shared.validate().unwrap();
+        let names: Vec<&str> = shared.halos.iter().map(|h| h.name.as_str()).collect();
+        assert_eq!(names, ["input-1", "input-2", "output"]);
+        assert_eq!(shared.halos[1].leds, (9..16).collect::<Vec<_>>());
+        assert_eq!(shared.halos[2].leds.len(), 11);
+        assert_eq!(shared.to_predicted().input_count, 2);
+    }
+
+    #[test]
+    fn import_then_load_by_model() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("shared.json");
+        let dir = tmp.path().join("layouts");
+        std::fs::write(&src, serde_json::to_string(&sample()).unwrap()).unwrap();
+
+        let (_, dest) = import_to(&src, &dir).unwrap();
+        assert_eq!(dest, dir.join("scarlett-16i16-4th-gen.json"));
+        assert_eq!(load_from(&dir, "Scarlett 16i16 4th Gen").unwrap(), sample());
+        assert!(load_from(&dir, "Scarlett 18i20 4th Gen").is_none());
+    }
+
+    #[test]
+    fn file_name_slug() {
+        assert_eq!(layout_file_name(" Clarett+ 8Pre "), "clarett-8pre.json");
+    }
+}
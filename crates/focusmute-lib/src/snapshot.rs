@@ -0,0 +1,291 @@
+//! Device state snapshot — every known descriptor member, decoded.
+//!
+//! [`DeviceSnapshot::capture`] reads the LED state, selected input, clock,
+//! direct monitoring, and each input/output control the firmware schema
+//! describes into one serializable struct — for bug reports
+//! (`focusmute-cli snapshot --json`) and for diffing the device before and
+//! after a change. Controls the model doesn't have are left out.
+
+use serde::Serialize;
+
+use crate::clock::{self, ClockStatus};
+use crate::context::DeviceContext;
+use crate::device::{Result, ScarlettDevice};
+use crate::device_controls;
+use crate::led::format_color;
+use crate::protocol::{OFF_ENABLE_DIRECT_LED, OFF_SELECTED_INPUT};
+use crate::schema::ControlMember;
+
+/// Decoded device state at one point in time.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeviceSnapshot {
+    pub model: String,
+    pub serial: Option<String>,
+    pub firmware: String,
+    pub clock: ClockStatus,
+    /// `selectedInput`, 1-based.
+    pub selected_input: u8,
+    /// `directMonitoring` (0 = off), if the schema has it.
+    pub direct_monitoring: Option<u8>,
+    /// Alt speakers active (`activeMonitorGroup`), if the model switches speakers.
+    pub alt_speakers: Option<bool>,
+    /// 48V state per phantom power switch.
+    pub phantom_power: Vec<bool>,
+    pub leds: LedSnapshot,
+    pub inputs: Vec<InputSnapshot>,
+    pub outputs: Vec<OutputSnapshot>,
+}
+
+/// LED state. Colors are `#RRGGBB`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LedSnapshot {
+    /// `enableDirectLEDMode` (0 = firmware-driven metering).
+    pub direct_mode: u8,
+    /// `directLEDValues`, one color per LED.
+    pub direct_values: Vec<String>,
+    /// `LEDcolors` metering gradient (needs the schema).
+    pub gradient: Vec<String>,
+}
+
+/// Per-input controls; `None` where the model lacks the control.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InputSnapshot {
+    /// Input number, from 1.
+    pub input: usize,
+    pub gain: Option<u8>,
+    pub air: Option<String>,
+    pub instrument: Option<bool>,
+    pub clip_safe: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OutputSnapshot {
+    /// Output number, from 1.
+    pub output: usize,
+    pub volume_db: i16,
+}
+
+fn read_u8(device: &impl ScarlettDevice, offset: u32) -> Result<u8> {
+    Ok(device
+        .get_descriptor(offset, 1)?
+        .first()
+        .copied()
+        .unwrap_or(0))
+}
+
+fn read_colors(device: &impl ScarlettDevice, offset: u32, count: usize) -> Result<Vec<String>> {
+    let data = device.get_descriptor(offset, (count * 4) as u32)?;
+    Ok(data
+        .chunks_exact(4)
+        .map(|c| format_color(u32::from_le_bytes([c[0], c[1], c[2], c[3]])))
+        .collect())
+}
+
+impl DeviceSnapshot {
+    /// Resolve the device context (reading the schema if needed) and capture
+    /// a snapshot.
+    pub fn capture(device: &impl ScarlettDevice) -> crate::error::Result<Self> {
+        let ctx = DeviceContext::resolve(device, true)?;
+        Ok(Self::capture_with(device, &ctx)?)
+    }
+
+    /// Capture a snapshot using an already resolved context.
+    pub fn capture_with(device: &impl ScarlettDevice, ctx: &DeviceContext) -> Result<Self> {
+        let info = device.info();
+        let schema = ctx.schema.as_ref();
+
+        let phantom_power = match device_controls::phantom_power_control(schema, info.model()) {
+            Some(control) => (0..control.count)
+                .map(|ch| device_controls::get_phantom_power(device, &control, ch))
+                .collect::<Result<_>>()?,
+            None => Vec::new(),
+        };
+
+        let leds = LedSnapshot {
+            direct_mode: read_u8(device, OFF_ENABLE_DIRECT_LED)?,
+            direct_values: read_colors(
+                device,
+                ctx.offsets.direct_led_values,
+                ctx.offsets.direct_led_count,
+            )?,
+            gradient: match schema {
+                Some(sc) => read_colors(device, sc.gradient_offset, sc.gradient_count)?,
+                None => Vec::new(),
+            },
+        };
+
+        let gain = device_controls::gain_control(schema);
+        let air = device_controls::air_control(schema);
+        let inst = device_controls::inst_input_control(schema);
+        let clip_safe = device_controls::clip_safe_control(schema);
+        let input_count = [gain, air, inst, clip_safe]
+            .iter()
+            .flatten()
+            .map(|c| c.count)
+            .chain(ctx.input_count())
+            .max()
+            .unwrap_or(0);
+        let mut inputs = Vec::with_capacity(input_count);
+        for i in 0..input_count {
+            let has = |c: &ControlMember| i < c.count;
+            inputs.push(InputSnapshot {
+                input: i + 1,
+                gain: match gain.filter(has) {
+                    Some(c) => Some(device_controls::get_gain(device, &c, i)?),
+                    None => None,
+                },
+                air: match air.filter(has) {
+                    Some(c) => Some(device_controls::get_air(device, &c, i)?.to_string()),
+                    None => None,
+                },
+                instrument: match inst.filter(has) {
+                    Some(c) => Some(device_controls::get_instrument_mode(device, &c, i)?),
+                    None => None,
+                },
+                clip_safe: match clip_safe.filter(has) {
+                    Some(c) => Some(device_controls::get_clip_safe(device, &c, i)?),
+                    None => None,
+                },
+            });
+        }
+
+        let outputs = match device_controls::output_volume_control(schema) {
+            Some(control) => (0..control.count)
+                .map(|o| {
+                    Ok(OutputSnapshot {
+                        output: o + 1,
+                        volume_db: device_controls::get_output_volume(device, &control, o)?,
+                    })
+                })
+                .collect::<Result<_>>()?,
+            None => Vec::new(),
+        };
+
+        Ok(DeviceSnapshot {
+            model: info.model().to_string(),
+            serial: info.serial.clone(),
+            firmware: info.firmware.to_string(),
+            clock: clock::read_clock_status(device, schema)?,
+            selected_input: read_u8(device, OFF_SELECTED_INPUT)?.saturating_add(1),
+            direct_monitoring: match schema.and_then(|sc| sc.direct_monitoring_offset) {
+                Some(offset) => Some(read_u8(device, offset)?),
+                None => None,
+            },
+            alt_speakers: match device_controls::alt_speakers_control(schema) {
+                Some(c) => Some(device_controls::get_alt_speakers(device, &c)?),
+                None => None,
+            },
+            phantom_power,
+            leds,
+            inputs,
+            outputs,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device::mock::MockDevice;
+    use crate::offsets::DeviceOffsets;
+    use crate::schema::{SchemaConstants, VolumeMember};
+
+    fn member(offset: u32, count: usize) -> Option<ControlMember> {
+        Some(ControlMember {
+            offset,
+            count,
+            notify: 0,
+            via_parameter_buffer: true,
+        })
+    }
+
+    fn schema() -> SchemaConstants {
+        SchemaConstants {
+            product_name: "Scarlett 2i2 4th Gen".into(),
+            max_leds: 40,
+            max_inputs: 2,
+            max_outputs: 2,
+            gradient_count: 2,
+            gradient_offset: 384,
+            gradient_notify: 9,
+            direct_led_count: 3,
+            direct_led_offset: 92,
+            metering_segments: 25,
+            input_controls: vec![],
+            app_space_features: vec![],
+            firmware_version: String::new(),
+            phantom_power: member(72, 1),
+            air: member(600, 2),
+            inst_input: None,
+            preamp_gain: member(610, 2),
+            clip_safe: None,
+            autogain: None,
+            autogain_status_offset: None,
+            sample_rate_offset: None,
+            sync_source_offset: None,
+            direct_monitoring_offset: Some(620),
+            alt_speakers: None,
+            output_volume: Some(VolumeMember {
+                offset: 630,
+                count: 1,
+                notify: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn decodes_schema_members() {
+        let dev = MockDevice::new();
+        dev.set_descriptor(72, &[1]).unwrap();
+        dev.set_descriptor(92, &0xFF00_0000u32.to_le_bytes())
+            .unwrap();
+        dev.set_descriptor(388, &0x00FF_0000u32.to_le_bytes())
+            .unwrap();
+        dev.set_descriptor(601, &[1]).unwrap();
+        dev.set_descriptor(610, &[12, 40]).unwrap();
+        dev.set_descriptor(620, &[2]).unwrap();
+        dev.set_descriptor(630, &(-20i16).to_le_bytes()).unwrap();
+        dev.set_descriptor(OFF_SELECTED_INPUT, &[1]).unwrap();
+        let sc = schema();
+        let ctx = DeviceContext {
+            profile: None,
+            offsets: DeviceOffsets::from_schema(&sc),
+            schema: Some(sc),
+            predicted: None,
+        };
+
+        let snap = DeviceSnapshot::capture_with(&dev, &ctx).unwrap();
+        assert_eq!(snap.selected_input, 2);
+        assert_eq!(snap.direct_monitoring, Some(2));
+        assert_eq!(snap.alt_speakers, None);
+        assert_eq!(snap.phantom_power, vec![true]);
+        assert_eq!(snap.leds.direct_values, ["#FF0000", "#000000", "#000000"]);
+        assert_eq!(snap.leds.gradient, ["#000000", "#00FF00"]);
+        assert_eq!(snap.inputs.len(), 2);
+        assert_eq!(snap.inputs[1].gain, Some(40));
+        assert_eq!(snap.inputs[1].air.as_deref(), Some("presence"));
+        assert_eq!(snap.inputs[0].instrument, None);
+        assert_eq!(snap.outputs[0].volume_db, -20);
+    }
+
+    #[test]
+    fn without_schema_reads_profile_leds_only() {
+        let mut dev = MockDevice::new();
+        dev.info_mut().device_name = "Scarlett 2i2 4th Gen-00031337".into();
+        let profile = crate::models::detect_model("Scarlett 2i2 4th Gen").unwrap();
+        let ctx = DeviceContext {
+            profile: Some(profile),
+            schema: None,
+            offsets: DeviceOffsets::for_profile(profile),
+            predicted: None,
+        };
+
+        let snap = DeviceSnapshot::capture_with(&dev, &ctx).unwrap();
+        assert_eq!(snap.leds.direct_values.len(), profile.led_count);
+        assert!(snap.leds.gradient.is_empty());
+        assert_eq!(snap.phantom_power, vec![false]);
+        assert!(snap.inputs.iter().all(|i| i.gain.is_none()));
+        assert!(snap.outputs.is_empty());
+        assert!(serde_json::to_value(&snap).unwrap()["leds"].is_object());
+    }
+}
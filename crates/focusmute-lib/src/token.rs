@@ -0,0 +1,20 @@
+//! Secret token checks shared by the tray IPC endpoint and the control server.
+
+/// Byte comparison whose time doesn't depend on where `a` and `b` differ
+/// (only on their lengths).
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ct_eq_compares_bytes() {
+        assert!(ct_eq(b"s3cret", b"s3cret"));
+        assert!(ct_eq(b"", b""));
+        assert!(!ct_eq(b"s3cret", b"s3creT"));
+        assert!(!ct_eq(b"s3cret", b"s3cret!"));
+    }
+}
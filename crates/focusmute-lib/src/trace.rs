@@ -0,0 +1,128 @@
+//! Raw protocol tracing — hex dumps of every device transaction.
+//!
+//! Opt in with `FOCUSMUTE_TRACE_PROTOCOL=1` (or [`set_enabled`]). Both
+//! backends report each IOCTL / USB control transfer here, and it is logged
+//! at trace level under the `focusmute_lib::trace` target, so bringing up a
+//! new model doesn't need a USB analyzer. The binaries raise that target to
+//! trace level when tracing is on.
+
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Environment variable that turns tracing on (`1`, `true`, `yes`, `on`).
+pub const TRACE_ENV: &str = "FOCUSMUTE_TRACE_PROTOCOL";
+
+static FROM_ENV: OnceLock<bool> = OnceLock::new();
+static FORCED: AtomicBool = AtomicBool::new(false);
+
+/// Whether protocol tracing is on (environment variable or [`set_enabled`]).
+pub fn enabled() -> bool {
+    FORCED.load(Ordering::Relaxed)
+        || *FROM_ENV.get_or_init(|| std::env::var(TRACE_ENV).is_ok_and(|v| parse_flag(&v)))
+}
+
+/// Turn tracing on or off regardless of the environment variable.
+pub fn set_enabled(on: bool) {
+    FORCED.store(on, Ordering::Relaxed);
+}
+
+fn parse_flag(value: &str) -> bool {
+    matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "1" | "true" | "yes" | "on"
+    )
+}
+
+/// Run one transaction through `f`, logging `input` and the reply (or the
+/// error). `label` is only built when tracing is on.
+#[cfg_attr(not(windows), allow(dead_code))]
+pub(crate) fn traced<E: std::fmt::Display>(
+    label: impl FnOnce() -> String,
+    input: &[u8],
+    f: impl FnOnce() -> Result<Vec<u8>, E>,
+) -> Result<Vec<u8>, E> {
+    if !enabled() {
+        return f();
+    }
+    let label = label();
+    transfer(&label, "->", input);
+    let result = f();
+    match &result {
+        Ok(output) => transfer(&label, "<-", output),
+        Err(e) => failure(&label, &e.to_string()),
+    }
+    result
+}
+
+/// Log one direction of a transaction. `label` names the transfer (e.g.
+/// `"IOCTL 0x00222008"`), `direction` is `"->"` (to the device) or `"<-"`.
+pub(crate) fn transfer(label: &str, direction: &str, data: &[u8]) {
+    if !enabled() {
+        return;
+    }
+    log::trace!(
+        "{label} {direction} {} bytes\n{}",
+        data.len(),
+        hex_dump(data)
+    );
+}
+
+/// Log a failed transaction.
+pub(crate) fn failure(label: &str, error: &str) {
+    if enabled() {
+        log::trace!("{label} failed: {error}");
+    }
+}
+
+/// Classic hex dump: offset, 16 hex bytes, and the printable ASCII.
+pub fn hex_dump(data: &[u8]) -> String {
+    data.chunks(16)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let hex: Vec<String> = chunk.iter().map(|b| format!("{b:02x}")).collect();
+            let ascii: String = chunk
+                .iter()
+                .map(|&b| {
+                    if b.is_ascii_graphic() || b == b' ' {
+                        b as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            format!("  {:04x}: {:<47}  |{ascii}|", i * 16, hex.join(" "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_dump_formats_rows() {
+        let data: Vec<u8> = (0x41..0x41 + 18).collect();
+        let dump = hex_dump(&data);
+        let lines: Vec<&str> = dump.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("  0000: 41 42 43"));
+        assert!(lines[0].ends_with("|ABCDEFGHIJKLMNOP|"));
+        assert!(lines[1].starts_with("  0010: 51 52 "));
+        assert!(lines[1].ends_with("|QR|"));
+        assert_eq!(
+            hex_dump(&[0x00, 0x7f]),
+            format!("  0000: 00 7f{}  |..|", " ".repeat(42))
+        );
+        assert!(hex_dump(&[]).is_empty());
+    }
+
+    #[test]
+    fn parses_env_flag() {
+        assert!(parse_flag("1"));
+        assert!(parse_flag("TRUE"));
+        assert!(parse_flag(" on "));
+        assert!(!parse_flag("0"));
+        assert!(!parse_flag(""));
+    }
+}
@@ -0,0 +1,63 @@
+//! Library version and compatibility info for integrators.
+//!
+//! [`version()`] reports the crate version, the device protocol revision it
+//! speaks, and the models it has hardcoded LED profiles for. Models not listed
+//! still work through the firmware schema.
+
+use serde::Serialize;
+
+use crate::models;
+use crate::protocol::PROTOCOL_REVISION;
+
+/// A built-in model profile, summarized.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProfileInfo {
+    pub name: &'static str,
+    pub input_count: usize,
+    pub led_count: usize,
+}
+
+/// What this build of the library supports.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VersionInfo {
+    /// Crate version (`CARGO_PKG_VERSION`).
+    pub crate_version: &'static str,
+    /// See [`PROTOCOL_REVISION`].
+    pub protocol_revision: u32,
+    /// Models with a hardcoded profile.
+    pub profiles: Vec<ProfileInfo>,
+}
+
+/// Version and compatibility info for this build.
+pub fn version() -> VersionInfo {
+    VersionInfo {
+        crate_version: env!("CARGO_PKG_VERSION"),
+        protocol_revision: PROTOCOL_REVISION,
+        profiles: models::builtin_profiles()
+            .iter()
+            .map(|p| ProfileInfo {
+                name: p.name,
+                input_count: p.input_count,
+                led_count: p.led_count,
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_every_builtin_profile() {
+        let v = version();
+        assert_eq!(v.crate_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(v.protocol_revision, PROTOCOL_REVISION);
+        for p in &v.profiles {
+            let profile = models::detect_model(p.name).unwrap();
+            assert_eq!(profile.input_count, p.input_count);
+            assert_eq!(profile.led_count, p.led_count);
+        }
+        assert!(v.profiles.iter().any(|p| p.name == "Scarlett 2i2 4th Gen"));
+    }
+}
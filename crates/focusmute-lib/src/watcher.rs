@@ -0,0 +1,295 @@
+//! Descriptor watcher — notice changes made outside focusmute.
+//!
+//! Focusrite Control 2 and the front-panel buttons change the descriptor
+//! behind our back. Where the backend supports IOCTL_NOTIFY the firmware
+//! tells us (see [`crate::device_events`]); elsewhere [`DescriptorWatcher`]
+//! falls back to re-reading the watched members every
+//! [`DEFAULT_POLL_INTERVAL`] and diffing them against the last snapshot.
+//! Either way the caller gets the same [`DeviceEvent`]s.
+
+use std::time::{Duration, Instant};
+
+use crate::device::ScarlettDevice;
+use crate::device_events::{self, DeviceButton, DeviceEvent};
+use crate::protocol::OFF_SELECTED_INPUT;
+use crate::schema::SchemaConstants;
+
+/// How often the poll fallback re-reads the descriptor.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A descriptor range and the event its change maps to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatchedRegion {
+    pub offset: u32,
+    pub size: u32,
+    pub event: DeviceEvent,
+}
+
+/// Turns device notifications — or, without them, descriptor diffs — into
+/// [`DeviceEvent`]s.
+#[derive(Debug)]
+pub struct DescriptorWatcher {
+    regions: Vec<WatchedRegion>,
+    /// Last bytes read per region; `None` until the first successful read.
+    snapshots: Vec<Option<Vec<u8>>>,
+    poll_interval: Duration,
+    last_poll: Option<Instant>,
+}
+
+/// Regions worth watching for a device with `schema`. The selected input is
+/// always watched; per-input controls and clock members need the schema.
+pub fn default_regions(schema: Option<&SchemaConstants>) -> Vec<WatchedRegion> {
+    let mut regions = vec![WatchedRegion {
+        offset: OFF_SELECTED_INPUT,
+        size: 1,
+        event: DeviceEvent::SelectedInputChanged,
+    }];
+    let Some(sc) = schema else {
+        return regions;
+    };
+    let controls = [
+        (&sc.phantom_power, DeviceEvent::PhantomPowerChanged),
+        (&sc.air, DeviceEvent::ButtonPressed(DeviceButton::Air)),
+        (
+            &sc.inst_input,
+            DeviceEvent::ButtonPressed(DeviceButton::Inst),
+        ),
+        (&sc.preamp_gain, DeviceEvent::InputGainChanged),
+        (&sc.clip_safe, DeviceEvent::ClipSafeChanged),
+        (&sc.autogain, DeviceEvent::AutogainChanged),
+    ];
+    for (member, event) in controls {
+        if let Some(m) = member {
+            regions.push(WatchedRegion {
+                offset: m.offset,
+                size: m.count as u32,
+                event,
+            });
+        }
+    }
+    for offset in [sc.sample_rate_offset, sc.sync_source_offset]
+        .into_iter()
+        .flatten()
+    {
+        regions.push(WatchedRegion {
+            offset,
+            size: 2,
+            event: DeviceEvent::ClockChanged,
+        });
+    }
+    regions
+}
+
+impl DescriptorWatcher {
+    /// Watch the [`default_regions`] for `schema`.
+    pub fn new(schema: Option<&SchemaConstants>) -> Self {
+        Self::with_regions(default_regions(schema))
+    }
+
+    /// Watch an explicit set of regions.
+    pub fn with_regions(regions: Vec<WatchedRegion>) -> Self {
+        let snapshots = vec![None; regions.len()];
+        Self {
+            regions,
+            snapshots,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            last_poll: None,
+        }
+    }
+
+    /// Set how often the poll fallback re-reads the descriptor.
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    pub fn regions(&self) -> &[WatchedRegion] {
+        &self.regions
+    }
+
+    /// Forget the snapshots, e.g. after a reconnect. The next fallback poll
+    /// takes a fresh baseline instead of reporting the difference.
+    pub fn reset(&mut self) {
+        self.snapshots.iter_mut().for_each(|s| *s = None);
+        self.last_poll = None;
+    }
+
+    /// Check for changes.
+    ///
+    /// With notification support this waits up to `timeout_ms` for one and
+    /// decodes it. Without, it re-reads the watched regions at most once per
+    /// poll interval (never blocking) and reports each changed region's event
+    /// once, in region order. Read errors keep the previous snapshot.
+    pub fn poll(&mut self, device: &impl ScarlettDevice, timeout_ms: u64) -> Vec<DeviceEvent> {
+        if device.supports_notify() {
+            return device_events::poll_events(device, timeout_ms);
+        }
+        let now = Instant::now();
+        if self
+            .last_poll
+            .is_some_and(|t| now.duration_since(t) < self.poll_interval)
+        {
+            return Vec::new();
+        }
+        self.last_poll = Some(now);
+
+        let mut events = Vec::new();
+        for (region, snapshot) in self.regions.iter().zip(&mut self.snapshots) {
+            let data = match device.get_descriptor(region.offset, region.size) {
+                Ok(data) => data,
+                Err(e) => {
+                    log::trace!("[watcher] read at {} failed: {e}", region.offset);
+                    continue;
+                }
+            };
+            let changed = snapshot.as_ref().is_some_and(|old| *old != data);
+            *snapshot = Some(data);
+            if changed && !events.contains(&region.event) {
+                events.push(region.event);
+            }
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device::mock::MockDevice;
+    use crate::protocol::NOTIFY_MASK_PHANTOM_POWER;
+    use crate::schema::ControlMember;
+
+    fn polling_device() -> MockDevice {
+        let dev = MockDevice::new();
+        dev.notify_supported.set(false);
+        dev
+    }
+
+    fn schema() -> SchemaConstants {
+        SchemaConstants {
+            product_name: "Scarlett 2i2 4th Gen".into(),
+            max_leds: 40,
+            max_inputs: 2,
+            max_outputs: 2,
+            gradient_count: 11,
+            gradient_offset: 384,
+            gradient_notify: 9,
+            direct_led_count: 40,
+            direct_led_offset: 92,
+            metering_segments: 25,
+            input_controls: vec![],
+            app_space_features: vec![],
+            firmware_version: String::new(),
+            phantom_power: None,
+            air: member(200, 2),
+            inst_input: None,
+            preamp_gain: None,
+            clip_safe: None,
+            autogain: None,
+            autogain_status_offset: None,
+            sample_rate_offset: Some(64),
+            sync_source_offset: None,
+            direct_monitoring_offset: None,
+            alt_speakers: None,
+            output_volume: None,
+        }
+    }
+
+    fn member(offset: u32, count: usize) -> Option<ControlMember> {
+        Some(ControlMember {
+            offset,
+            count,
+            notify: 0,
+            via_parameter_buffer: false,
+        })
+    }
+
+    #[test]
+    fn default_regions_follow_schema() {
+        assert_eq!(default_regions(None).len(), 1);
+
+        let sc = schema();
+        let regions = default_regions(Some(&sc));
+        assert_eq!(regions.len(), 3);
+        assert!(regions.contains(&WatchedRegion {
+            offset: 200,
+            size: 2,
+            event: DeviceEvent::ButtonPressed(DeviceButton::Air),
+        }));
+        assert!(regions.contains(&WatchedRegion {
+            offset: 64,
+            size: 2,
+            event: DeviceEvent::ClockChanged,
+        }));
+    }
+
+    #[test]
+    fn uses_notifications_when_supported() {
+        let dev = MockDevice::new();
+        dev.push_notification(NOTIFY_MASK_PHANTOM_POWER);
+        let mut watcher = DescriptorWatcher::new(None);
+        assert_eq!(
+            watcher.poll(&dev, 0),
+            vec![DeviceEvent::PhantomPowerChanged]
+        );
+        // Descriptor changes alone are not reported in notify mode
+        dev.set_descriptor(OFF_SELECTED_INPUT, &[1]).unwrap();
+        assert!(watcher.poll(&dev, 0).is_empty());
+    }
+
+    #[test]
+    fn poll_fallback_reports_changes_after_baseline() {
+        let dev = polling_device();
+        let mut watcher = DescriptorWatcher::new(None).with_poll_interval(Duration::ZERO);
+
+        // First poll only takes the baseline
+        assert!(watcher.poll(&dev, 0).is_empty());
+        assert!(watcher.poll(&dev, 0).is_empty());
+
+        dev.set_descriptor(OFF_SELECTED_INPUT, &[1]).unwrap();
+        assert_eq!(
+            watcher.poll(&dev, 0),
+            vec![DeviceEvent::SelectedInputChanged]
+        );
+        assert!(watcher.poll(&dev, 0).is_empty());
+    }
+
+    #[test]
+    fn poll_fallback_dedupes_events_and_resets() {
+        let dev = polling_device();
+        let mut watcher = DescriptorWatcher::with_regions(vec![
+            WatchedRegion {
+                offset: 10,
+                size: 2,
+                event: DeviceEvent::ClockChanged,
+            },
+            WatchedRegion {
+                offset: 20,
+                size: 2,
+                event: DeviceEvent::ClockChanged,
+            },
+        ])
+        .with_poll_interval(Duration::ZERO);
+        watcher.poll(&dev, 0);
+
+        dev.set_descriptor(10, &[0x80, 0xBB]).unwrap();
+        dev.set_descriptor(20, &[1, 0]).unwrap();
+        assert_eq!(watcher.poll(&dev, 0), vec![DeviceEvent::ClockChanged]);
+
+        watcher.reset();
+        dev.set_descriptor(10, &[0x44, 0xAC]).unwrap();
+        assert!(
+            watcher.poll(&dev, 0).is_empty(),
+            "reset takes a new baseline"
+        );
+    }
+
+    #[test]
+    fn poll_fallback_respects_interval() {
+        let dev = polling_device();
+        let mut watcher = DescriptorWatcher::new(None).with_poll_interval(Duration::from_secs(60));
+        watcher.poll(&dev, 0);
+        dev.set_descriptor(OFF_SELECTED_INPUT, &[1]).unwrap();
+        assert!(watcher.poll(&dev, 0).is_empty());
+    }
+}
@@ -18,6 +18,8 @@ fn make_strategy_both() -> led::MuteStrategy {
         mute_colors: vec![],
         selected_color: 0x20FF_0000,
         unselected_color: 0x88FF_FF00,
+        group_leds: Vec::new(),
+        patterns: led::LedPatterns::default(),
     }
 }
 
@@ -29,6 +31,8 @@ fn make_strategy_input1() -> led::MuteStrategy {
         mute_colors: vec![],
         selected_color: 0x20FF_0000,
         unselected_color: 0x88FF_FF00,
+        group_leds: Vec::new(),
+        patterns: led::LedPatterns::default(),
     }
 }
 
@@ -73,7 +77,7 @@ fn full_mute_unmute_cycle() {
 
     let descs = dev.descriptors.borrow();
     // Last written should be input 2 (unselected → white)
-    let index = descs.get(&OFF_DIRECT_LED_INDEX).unwrap();
+    let index = &dev.get_descriptor(OFF_DIRECT_LED_INDEX, 1).unwrap();
     assert_eq!(index, &[8]);
     let colour = descs.get(&OFF_DIRECT_LED_COLOUR).unwrap();
     let restored_color = u32::from_le_bytes(colour[..4].try_into().unwrap());
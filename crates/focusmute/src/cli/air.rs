@@ -0,0 +1,45 @@
+//! `air` subcommand — show or set Air mode per input.
+
+use focusmute_lib::FocusmuteError;
+use focusmute_lib::device_controls::{self, AirMode};
+
+use super::{Result, ScarlettDevice, kv, kv_width, open_routed, schema};
+
+pub(super) fn cmd_air(
+    input: Option<usize>,
+    mode: Option<AirMode>,
+    force_direct: bool,
+) -> Result<()> {
+    let device = open_routed(force_direct)?;
+    let model = device.info().model().to_string();
+    let sc = schema::extract_or_cached(&device)
+        .map_err(|e| log::warn!("schema unavailable: {e}"))
+        .ok();
+    let control = device_controls::air_control(sc.as_ref()).ok_or_else(|| {
+        FocusmuteError::Schema(format!(
+            "{model} has no inputAir member — Air is not available"
+        ))
+    })?;
+
+    let inputs: Vec<usize> = match input {
+        Some(0) => {
+            return Err(FocusmuteError::Config("inputs are numbered from 1".into()));
+        }
+        Some(n) => vec![n - 1],
+        None => (0..control.count).collect(),
+    };
+
+    if let (Some(mode), Some(&channel)) = (mode, inputs.first()) {
+        device_controls::set_air(&device, &control, channel, mode)?;
+    }
+
+    let labels: Vec<String> = inputs.iter().map(|c| format!("Input {}:", c + 1)).collect();
+    let keys: Vec<&str> = labels.iter().map(String::as_str).collect();
+    let w = kv_width(&keys, &[]);
+    println!("Air:");
+    for (label, &channel) in labels.iter().zip(&inputs) {
+        let mode = device_controls::get_air(&device, &control, channel)?;
+        kv(label, mode, w);
+    }
+    Ok(())
+}
@@ -0,0 +1,37 @@
+//! `alt` subcommand — show or switch between the main and alt speakers.
+
+use focusmute_lib::FocusmuteError;
+use focusmute_lib::device_controls;
+
+use super::{Result, ScarlettDevice, open_routed, schema};
+
+fn speakers(alt: bool) -> &'static str {
+    if alt { "alt" } else { "main" }
+}
+
+/// `state` is `"on"` (alt speakers), `"off"` (main), or `"toggle"`.
+pub(super) fn cmd_alt(state: Option<&str>, force_direct: bool) -> Result<()> {
+    let device = open_routed(force_direct)?;
+    let model = device.info().model().to_string();
+    let sc = schema::extract_or_cached(&device)
+        .map_err(|e| log::warn!("schema unavailable: {e}"))
+        .ok();
+    let control = device_controls::alt_speakers_control(sc.as_ref()).ok_or_else(|| {
+        FocusmuteError::Schema(format!(
+            "{model} has no activeMonitorGroup member — speaker switching is not available"
+        ))
+    })?;
+
+    let current = device_controls::get_alt_speakers(&device, &control)?;
+    let target = match state {
+        Some("on") => true,
+        Some("off") => false,
+        Some(_) => !current,
+        None => current,
+    };
+    if target != current {
+        device_controls::set_alt_speakers(&device, &control, target)?;
+    }
+    println!("Speakers: {}", speakers(target));
+    Ok(())
+}
@@ -0,0 +1,107 @@
+//! `autogain` subcommand — run Auto Gain on an input and wait for the result.
+
+use std::io::Write;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use focusmute_lib::FocusmuteError;
+use focusmute_lib::device_controls;
+
+use super::{RUNNING, Result, ScarlettDevice, open_routed, schema};
+
+/// Roughly how long the firmware listens before setting the gain. Only used
+/// to scale the progress bar — completion comes from the device.
+const EXPECTED_DURATION: Duration = Duration::from_secs(10);
+
+/// Give up waiting after this long.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+const BAR_WIDTH: usize = 30;
+
+/// Render a progress bar for `elapsed`, capped just short of full until the
+/// device reports completion.
+fn progress_bar(elapsed: Duration) -> String {
+    let frac = (elapsed.as_secs_f64() / EXPECTED_DURATION.as_secs_f64()).min(0.97);
+    let filled = (frac * BAR_WIDTH as f64) as usize;
+    format!(
+        "[{}{}] {:>2}s",
+        "#".repeat(filled),
+        "-".repeat(BAR_WIDTH - filled),
+        elapsed.as_secs()
+    )
+}
+
+pub(super) fn cmd_autogain(input: usize, force_direct: bool) -> Result<()> {
+    let device = open_routed(force_direct)?;
+    let model = device.info().model().to_string();
+    let sc = schema::extract_or_cached(&device)
+        .map_err(|e| log::warn!("schema unavailable: {e}"))
+        .ok();
+    let control = device_controls::autogain_control(sc.as_ref()).ok_or_else(|| {
+        FocusmuteError::Schema(format!(
+            "{model} has no autogainInProgress member — Auto Gain is not available"
+        ))
+    })?;
+    if input == 0 {
+        return Err(FocusmuteError::Config("inputs are numbered from 1".into()));
+    }
+    let channel = input - 1;
+
+    println!("Auto Gain on input {input} — make some noise at your normal level...");
+    device_controls::start_autogain(&device, &control, channel)?;
+    let finished = device_controls::wait_for_autogain(&device, &control, channel, TIMEOUT, |e| {
+        print!("\r{}", progress_bar(e));
+        std::io::stdout().flush().ok();
+        RUNNING.load(Ordering::SeqCst)
+    })?;
+    println!();
+
+    if !finished {
+        device_controls::cancel_autogain(&device, &control, channel)?;
+        let why = if RUNNING.load(Ordering::SeqCst) {
+            "timed out"
+        } else {
+            "interrupted"
+        };
+        return Err(FocusmuteError::Config(format!(
+            "Auto Gain {why} — cancelled on input {input}"
+        )));
+    }
+
+    let result = match sc.as_ref().and_then(|sc| sc.autogain_status_offset) {
+        Some(offset) => Some(device_controls::autogain_result(&device, offset, channel)?),
+        None => None,
+    };
+    let gain = match device_controls::gain_control(sc.as_ref()) {
+        Some(gain) => Some(device_controls::get_gain(&device, &gain, channel)?),
+        None => None,
+    };
+
+    match (result, gain) {
+        (Some(r), Some(g)) => println!("Auto Gain {r} — input {input} gain is now {g}."),
+        (Some(r), None) => println!("Auto Gain {r}."),
+        (None, Some(g)) => println!("Auto Gain finished — input {input} gain is now {g}."),
+        (None, None) => println!("Auto Gain finished."),
+    }
+    match result {
+        Some(r) if !r.is_success() => Err(FocusmuteError::Config(format!("Auto Gain {r}"))),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_bar_fills_but_never_completes() {
+        assert_eq!(
+            progress_bar(Duration::ZERO),
+            format!("[{}]  0s", "-".repeat(BAR_WIDTH))
+        );
+        let half = progress_bar(EXPECTED_DURATION / 2);
+        assert_eq!(half.matches('#').count(), BAR_WIDTH / 2);
+        let late = progress_bar(EXPECTED_DURATION * 2);
+        assert!(late.contains('-'), "bar should not look done: {late}");
+    }
+}
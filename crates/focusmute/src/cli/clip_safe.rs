@@ -0,0 +1,45 @@
+//! `clip-safe` subcommand — show or switch Clip Safe per input.
+
+use focusmute_lib::FocusmuteError;
+use focusmute_lib::device_controls;
+
+use super::{Result, ScarlettDevice, kv, kv_width, open_routed, schema};
+
+pub(super) fn cmd_clip_safe(
+    input: Option<usize>,
+    on: Option<bool>,
+    force_direct: bool,
+) -> Result<()> {
+    let device = open_routed(force_direct)?;
+    let model = device.info().model().to_string();
+    let sc = schema::extract_or_cached(&device)
+        .map_err(|e| log::warn!("schema unavailable: {e}"))
+        .ok();
+    let control = device_controls::clip_safe_control(sc.as_ref()).ok_or_else(|| {
+        FocusmuteError::Schema(format!(
+            "{model} has no clipSafe member — Clip Safe is not available"
+        ))
+    })?;
+
+    let inputs: Vec<usize> = match input {
+        Some(0) => {
+            return Err(FocusmuteError::Config("inputs are numbered from 1".into()));
+        }
+        Some(n) => vec![n - 1],
+        None => (0..control.count).collect(),
+    };
+
+    if let (Some(on), Some(&channel)) = (on, inputs.first()) {
+        device_controls::set_clip_safe(&device, &control, channel, on)?;
+    }
+
+    let labels: Vec<String> = inputs.iter().map(|c| format!("Input {}:", c + 1)).collect();
+    let keys: Vec<&str> = labels.iter().map(String::as_str).collect();
+    let w = kv_width(&keys, &[]);
+    println!("Clip Safe:");
+    for (label, &channel) in labels.iter().zip(&inputs) {
+        let on = device_controls::get_clip_safe(&device, &control, channel)?;
+        kv(label, if on { "on" } else { "off" }, w);
+    }
+    Ok(())
+}
@@ -63,6 +63,9 @@ pub(super) fn cmd_config(json: bool, custom_path: Option<&Path>) -> Result<()> {
         Err(_) => format!("{} (invalid)", config.mute_color),
     };
     kv_indent("mute_color:", &color_display, w);
+    if !config.color_preset.is_empty() {
+        kv_indent("color_preset:", &config.color_preset, w);
+    }
     kv_indent("hotkey:", &config.hotkey, w);
     kv_indent("sound_enabled:", config.sound_enabled, w);
     kv_indent("autostart:", config.autostart, w);
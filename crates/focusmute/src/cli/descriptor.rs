@@ -1,17 +1,22 @@
 //! `descriptor` subcommand — dump raw descriptor bytes.
 
-use super::{Result, ScarlettDevice, open_device};
+use super::{Result, ScarlettDevice, open_routed};
 
-pub(super) fn cmd_descriptor(offset: u32, size: u32) -> Result<()> {
-    let device = open_device()?;
+pub(super) fn cmd_descriptor(offset: u32, size: u32, force_direct: bool) -> Result<()> {
+    let device = open_routed(force_direct)?;
     cmd_descriptor_inner(&device, offset, size)
 }
 
 fn cmd_descriptor_inner(device: &impl ScarlettDevice, offset: u32, size: u32) -> Result<()> {
     let data = device.get_descriptor(offset, size)?;
+    print_descriptor(offset, &data);
+    Ok(())
+}
+
+fn print_descriptor(offset: u32, data: &[u8]) {
     println!(
         "Descriptor [{offset}..{}] ({} bytes):",
-        offset + size,
+        offset as usize + data.len(),
         data.len()
     );
     for (i, chunk) in data.chunks(16).enumerate() {
@@ -22,7 +27,6 @@ fn cmd_descriptor_inner(device: &impl ScarlettDevice, offset: u32, size: u32) ->
         }
         println!();
     }
-    Ok(())
 }
 
 #[cfg(test)]
@@ -2,7 +2,7 @@
 
 use super::{DevicesOutput, Result, device};
 
-pub(super) fn cmd_devices(json: bool) -> Result<()> {
+pub(super) fn cmd_devices(json: bool, detail: bool) -> Result<()> {
     let devices = device::enumerate_devices();
 
     if json {
@@ -28,8 +28,28 @@ pub(super) fn cmd_devices(json: bool) -> Result<()> {
 
     for (i, dev) in devices.iter().enumerate() {
         println!("  [{}] {}", i + 1, dev.path);
+        if let Some(model) = dev.model {
+            println!("      Model:      {model}");
+        }
         if let Some(ref serial) = dev.serial {
-            println!("      Serial: {serial}");
+            println!("      Serial:     {serial}");
+        }
+        if detail {
+            let na = || "not available".to_string();
+            println!(
+                "      Product ID: {}",
+                dev.product_id
+                    .map(|p| format!("0x{p:04X}"))
+                    .unwrap_or_else(na)
+            );
+            println!(
+                "      USB speed:  {}",
+                dev.usb_speed.map(|s| s.to_string()).unwrap_or_else(na)
+            );
+            println!(
+                "      Driver:     {}",
+                dev.driver_version.clone().unwrap_or_else(na)
+            );
         }
     }
 
@@ -0,0 +1,248 @@
+//! `examples` subcommand — usage examples per command.
+//!
+//! The same table feeds the "Examples:" section of each subcommand's
+//! `--help`, so the two never drift apart.
+
+use focusmute_lib::FocusmuteError;
+
+use super::Result;
+
+/// One example invocation (arguments after `focusmute-cli`).
+pub(crate) struct Example {
+    /// Subcommand the example belongs to.
+    pub command: &'static str,
+    pub args: &'static str,
+    pub about: &'static str,
+}
+
+const fn ex(command: &'static str, args: &'static str, about: &'static str) -> Example {
+    Example {
+        command,
+        args,
+        about,
+    }
+}
+
+pub(crate) const EXAMPLES: &[Example] = &[
+    ex(
+        "status",
+        "status",
+        "Device, clock, microphone, and config summary",
+    ),
+    ex("status", "status --json", "The same, for scripts"),
+    ex(
+        "snapshot",
+        "snapshot --json > before.json",
+        "Save the device state for a bug report or diff",
+    ),
+    ex(
+        "resolve",
+        "resolve",
+        "Show which LEDs and colors the config targets",
+    ),
+    ex(
+        "devices",
+        "devices --detail",
+        "List interfaces with product ID and USB speed",
+    ),
+    ex(
+        "monitor",
+        "monitor",
+        "Show mute on the device LEDs until Ctrl+C",
+    ),
+    ex(
+        "monitor",
+        "monitor --json",
+        "Stream mute and device events as JSON lines",
+    ),
+    ex("mute", "mute", "Mute the default microphone"),
+    ex("unmute", "unmute", "Unmute it again"),
+    ex(
+        "toggle",
+        "toggle",
+        "Flip the mute state (through the tray when it runs)",
+    ),
+    ex(
+        "tray",
+        "tray reload-config",
+        "Apply config file edits to the tray",
+    ),
+    ex("tray", "tray quit", "Quit the tray app"),
+    ex(
+        "config",
+        "config --json",
+        "Current settings and file paths as JSON",
+    ),
+    ex(
+        "probe",
+        "probe --dump-schema > schema.json",
+        "Save the firmware schema",
+    ),
+    ex(
+        "probe",
+        "probe --bench-schema --runs 5",
+        "Time schema extraction",
+    ),
+    ex(
+        "predict",
+        "predict schema.json",
+        "Predict the LED layout offline",
+    ),
+    ex(
+        "map",
+        "map --output layout.json",
+        "Identify LEDs and save a shared layout",
+    ),
+    ex(
+        "layout",
+        "layout import layout.json",
+        "Install a shared LED layout",
+    ),
+    ex(
+        "descriptor",
+        "descriptor --offset 331 --size 1",
+        "Read the selected input",
+    ),
+    ex(
+        "history",
+        "history export --since 7d",
+        "Last week's mute history as CSV",
+    ),
+    ex(
+        "history",
+        "history export --format json",
+        "All history as JSON",
+    ),
+    ex("phantom", "phantom", "Show 48V state for every switch"),
+    ex(
+        "phantom",
+        "phantom 1 on --accept",
+        "Switch 48V on without the prompt",
+    ),
+    ex("air", "air 1 presence", "Set Air mode on input 1"),
+    ex("inst", "inst 2 inst", "Switch input 2 to instrument level"),
+    ex("gain", "gain --input 1 --set 40", "Set input 1 gain"),
+    ex("autogain", "autogain 1", "Run Auto Gain on input 1"),
+    ex(
+        "alt",
+        "alt toggle",
+        "Switch between the main and alt speakers",
+    ),
+    ex(
+        "clip-safe",
+        "clip-safe 1 on",
+        "Turn Clip Safe on for input 1",
+    ),
+    ex("gradient", "gradient get", "Show the halo meter colors"),
+    ex(
+        "gradient",
+        "gradient set green yellow red",
+        "Blend the halo meter from green to red",
+    ),
+    ex(
+        "volume",
+        "volume --output 1 --set -20",
+        "Set output 1 to -20 dB",
+    ),
+    ex("qa", "qa", "Walk through the hardware release checklist"),
+    ex(
+        "reset",
+        "reset",
+        "Reinitialize the device instead of replugging it",
+    ),
+    ex(
+        "stress",
+        "stress --hours 8 --reconnect-every 30",
+        "Overnight soak test",
+    ),
+    ex(
+        "report",
+        "report --output focusmute-report.tar",
+        "Bundle device and config info for a new-device request",
+    ),
+    ex(
+        "man",
+        "man --output man/",
+        "Write man pages for every command",
+    ),
+    ex(
+        "event-schema",
+        "event-schema",
+        "Print the JSON Schema of the event stream",
+    ),
+    ex("examples", "examples gain", "Show only the gain examples"),
+];
+
+/// Examples for `command`, formatted for a `--help` footer. `None` if there
+/// are none.
+pub(crate) fn help_section(command: &str) -> Option<String> {
+    let lines: Vec<String> = EXAMPLES
+        .iter()
+        .filter(|e| e.command == command)
+        .map(|e| format!("  focusmute-cli {:<36}  {}", e.args, e.about))
+        .collect();
+    (!lines.is_empty()).then(|| format!("Examples:\n{}", lines.join("\n")))
+}
+
+/// Attach each subcommand's examples to its `--help` output.
+pub(crate) fn with_examples(mut command: clap::Command) -> clap::Command {
+    let names: Vec<String> = command
+        .get_subcommands()
+        .map(|c| c.get_name().to_string())
+        .collect();
+    for name in names {
+        if let Some(section) = help_section(&name) {
+            command = command.mut_subcommand(name, |c| c.after_help(section));
+        }
+    }
+    command.after_help("Run `focusmute-cli examples` for usage examples.")
+}
+
+pub(super) fn cmd_examples(command: Option<&str>) -> Result<()> {
+    match command {
+        Some(name) => match help_section(name) {
+            Some(section) => println!("{section}"),
+            None => {
+                return Err(FocusmuteError::Config(format!(
+                    "no examples for '{name}' — run `focusmute-cli examples` to list all"
+                )));
+            }
+        },
+        None => {
+            for e in EXAMPLES {
+                println!("  focusmute-cli {:<36}  {}", e.args, e.about);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[test]
+    fn every_example_parses() {
+        for e in EXAMPLES {
+            // Drop shell redirections like `> schema.json`
+            let args = e.args.split('>').next().unwrap().split_whitespace();
+            let parsed = crate::Args::try_parse_from(std::iter::once("focusmute-cli").chain(args));
+            assert!(parsed.is_ok(), "example does not parse: {}", e.args);
+            assert!(
+                e.args.starts_with(e.command),
+                "example filed under the wrong command: {}",
+                e.args
+            );
+        }
+    }
+
+    #[test]
+    fn help_section_lists_only_that_command() {
+        let section = help_section("gain").unwrap();
+        assert!(section.starts_with("Examples:"));
+        assert!(section.contains("gain --input 1 --set 40"));
+        assert!(!section.contains("autogain"));
+        assert!(help_section("nope").is_none());
+    }
+}
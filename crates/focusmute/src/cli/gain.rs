@@ -0,0 +1,44 @@
+//! `gain` subcommand — show or set input gain.
+
+use focusmute_lib::FocusmuteError;
+use focusmute_lib::device_controls::{self, MAX_GAIN};
+
+use super::{Result, ScarlettDevice, kv, kv_width, open_routed, schema};
+
+pub(super) fn cmd_gain(input: Option<usize>, set: Option<u8>, force_direct: bool) -> Result<()> {
+    let device = open_routed(force_direct)?;
+    let model = device.info().model().to_string();
+    let sc = schema::extract_or_cached(&device)
+        .map_err(|e| log::warn!("schema unavailable: {e}"))
+        .ok();
+    let control = device_controls::gain_control(sc.as_ref()).ok_or_else(|| {
+        FocusmuteError::Schema(format!(
+            "{model} has no preampInputGain member — gain is not software-controlled"
+        ))
+    })?;
+
+    let inputs: Vec<usize> = match input {
+        Some(0) => {
+            return Err(FocusmuteError::Config("inputs are numbered from 1".into()));
+        }
+        Some(n) => vec![n - 1],
+        None => (0..control.count).collect(),
+    };
+
+    if let (Some(value), Some(&channel)) = (set, inputs.first()) {
+        let applied = device_controls::set_gain(&device, &control, channel, value)?;
+        if applied != value {
+            println!("Gain {value} is above the maximum; set to {MAX_GAIN}.");
+        }
+    }
+
+    let labels: Vec<String> = inputs.iter().map(|c| format!("Input {}:", c + 1)).collect();
+    let keys: Vec<&str> = labels.iter().map(String::as_str).collect();
+    let w = kv_width(&keys, &[]);
+    println!("Input gain (0-{MAX_GAIN}):");
+    for (label, &channel) in labels.iter().zip(&inputs) {
+        let gain = device_controls::get_gain(&device, &control, channel)?;
+        kv(label, gain, w);
+    }
+    Ok(())
+}
@@ -0,0 +1,55 @@
+//! `gradient` subcommand — show or set the halo metering gradient.
+
+use focusmute_lib::FocusmuteError;
+
+use super::{Result, ScarlettDevice, led, open_routed, schema};
+
+/// The schema's gradient table, or an error if this model has none.
+fn gradient_schema(device: &impl ScarlettDevice) -> Result<schema::SchemaConstants> {
+    let sc = schema::extract_or_cached(device)?;
+    if sc.gradient_count == 0 {
+        return Err(FocusmuteError::Schema(format!(
+            "{} has no LEDcolors gradient",
+            device.info().model()
+        )));
+    }
+    Ok(sc)
+}
+
+pub(super) fn cmd_gradient_get(json: bool, force_direct: bool) -> Result<()> {
+    let device = open_routed(force_direct)?;
+    let sc = gradient_schema(&device)?;
+    let colors: Vec<String> = led::get_gradient(&device, &sc)?
+        .into_iter()
+        .map(led::format_color)
+        .collect();
+    if json {
+        let output = serde_json::json!({ "gradient": colors });
+        println!("{}", serde_json::to_string_pretty(&output).unwrap());
+        return Ok(());
+    }
+    println!(
+        "Metering gradient ({} segments, lowest first):",
+        colors.len()
+    );
+    for (i, color) in colors.iter().enumerate() {
+        println!("  {:>2}  {color}", i + 1);
+    }
+    Ok(())
+}
+
+pub(super) fn cmd_gradient_set(colors: &[String], force_direct: bool) -> Result<()> {
+    let stops = colors
+        .iter()
+        .map(|c| led::parse_color(c))
+        .collect::<Result<Vec<u32>>>()?;
+    let device = open_routed(force_direct)?;
+    let sc = gradient_schema(&device)?;
+    led::set_gradient(&device, &sc, &stops)?;
+    println!(
+        "Metering gradient set ({} stops over {} segments).",
+        stops.len(),
+        sc.gradient_count
+    );
+    Ok(())
+}
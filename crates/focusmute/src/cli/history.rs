@@ -0,0 +1,38 @@
+//! `history` subcommand — export recorded mute state transitions.
+
+use focusmute_lib::history::{self, ExportFormat};
+
+use super::Result;
+
+pub(super) fn cmd_history_export(since: Option<&str>, format: &str, json: bool) -> Result<()> {
+    let format: ExportFormat = if json {
+        ExportFormat::Json
+    } else {
+        format
+            .parse()
+            .map_err(focusmute_lib::FocusmuteError::Config)?
+    };
+    let cutoff = match since {
+        Some(s) => {
+            let secs = history::parse_since(s).map_err(focusmute_lib::FocusmuteError::Config)?;
+            history::now_unix().saturating_sub(secs)
+        }
+        None => 0,
+    };
+
+    let Some(path) = history::history_path() else {
+        return Err(focusmute_lib::FocusmuteError::Config(
+            "could not determine config directory".into(),
+        ));
+    };
+    let events: Vec<_> = history::load_from(&path)?
+        .into_iter()
+        .filter(|e| e.timestamp >= cutoff)
+        .collect();
+
+    print!("{}", history::export(&events, format));
+    if format == ExportFormat::Json {
+        println!();
+    }
+    Ok(())
+}
@@ -0,0 +1,49 @@
+//! `inst` subcommand — show or switch instrument/line level per input.
+
+use focusmute_lib::FocusmuteError;
+use focusmute_lib::device_controls;
+
+use super::{Result, ScarlettDevice, kv, kv_width, open_routed, schema};
+
+fn level(instrument: bool) -> &'static str {
+    if instrument { "inst" } else { "line" }
+}
+
+pub(super) fn cmd_inst(
+    input: Option<usize>,
+    instrument: Option<bool>,
+    force_direct: bool,
+) -> Result<()> {
+    let device = open_routed(force_direct)?;
+    let model = device.info().model().to_string();
+    let sc = schema::extract_or_cached(&device)
+        .map_err(|e| log::warn!("schema unavailable: {e}"))
+        .ok();
+    let control = device_controls::inst_input_control(sc.as_ref()).ok_or_else(|| {
+        FocusmuteError::Schema(format!(
+            "{model} has no instInput member — input level is not switchable"
+        ))
+    })?;
+
+    let inputs: Vec<usize> = match input {
+        Some(0) => {
+            return Err(FocusmuteError::Config("inputs are numbered from 1".into()));
+        }
+        Some(n) => vec![n - 1],
+        None => (0..control.count).collect(),
+    };
+
+    if let (Some(instrument), Some(&channel)) = (instrument, inputs.first()) {
+        device_controls::set_instrument_mode(&device, &control, channel, instrument)?;
+    }
+
+    let labels: Vec<String> = inputs.iter().map(|c| format!("Input {}:", c + 1)).collect();
+    let keys: Vec<&str> = labels.iter().map(String::as_str).collect();
+    let w = kv_width(&keys, &[]);
+    println!("Input level:");
+    for (label, &channel) in labels.iter().zip(&inputs) {
+        let instrument = device_controls::get_instrument_mode(&device, &control, channel)?;
+        kv(label, level(instrument), w);
+    }
+    Ok(())
+}
@@ -0,0 +1,33 @@
+//! `layout` subcommand — import LED layouts shared by other users.
+
+use std::path::Path;
+
+use focusmute_lib::shared_layout;
+
+use super::{Result, kv, kv_width};
+
+pub(super) fn cmd_layout_import(file: &Path) -> Result<()> {
+    let Some(dir) = shared_layout::layouts_dir() else {
+        return Err(focusmute_lib::FocusmuteError::Config(
+            "could not determine config directory".into(),
+        ));
+    };
+    let (layout, dest) = shared_layout::import_to(file, &dir)?;
+    let predicted = layout.to_predicted();
+
+    let w = kv_width(
+        &["Model:", "Firmware:", "LEDs:", "Inputs:", "Saved to:"],
+        &[],
+    );
+    println!("Imported LED layout:");
+    kv("Model:", &layout.model, w);
+    if !layout.firmware.is_empty() {
+        kv("Firmware:", &layout.firmware, w);
+    }
+    kv("LEDs:", layout.total_leds, w);
+    kv("Inputs:", predicted.input_count, w);
+    kv("Saved to:", dest.display(), w);
+    println!();
+    println!("It is used the next time a {} is connected.", layout.model);
+    Ok(())
+}
@@ -0,0 +1,21 @@
+//! `man` subcommand — generate roff man pages from the CLI definition.
+
+use std::path::Path;
+
+use clap::CommandFactory;
+
+use super::{Result, examples};
+
+pub(super) fn cmd_man(output: Option<&Path>) -> Result<()> {
+    let command = examples::with_examples(crate::Args::command());
+    match output {
+        // One page per command: focusmute-cli.1, focusmute-cli-status.1, ...
+        Some(dir) => {
+            std::fs::create_dir_all(dir)?;
+            clap_mangen::generate_to(command, dir)?;
+            println!("Man pages written to {}", dir.display());
+        }
+        None => clap_mangen::Man::new(command).render(&mut std::io::stdout())?,
+    }
+    Ok(())
+}
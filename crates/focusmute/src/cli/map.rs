@@ -1,6 +1,8 @@
 //! `map` subcommand — map directLEDValues by flashing one index at a time.
 
-use super::{DeviceContext, Result, ScarlettDevice, layout, led, models, open_device};
+use focusmute_lib::shared_layout::SharedLayout;
+
+use super::{DeviceContext, Result, ScarlettDevice, layout, led, models, open_routed};
 
 /// Get hardcoded LED labels for a model, generated from profile + button names.
 fn hardcoded_labels(model_name: &str) -> Option<Vec<String>> {
@@ -11,6 +13,26 @@ fn hardcoded_labels(model_name: &str) -> Option<Vec<String>> {
     Some(models::model_labels(profile, profile.button_labels))
 }
 
+/// Warn that LED state will be disrupted and ask to continue (skipped by `--accept`).
+pub(super) fn confirm_direct_led_mode() -> bool {
+    use std::io::Write;
+    println!("WARNING: This command puts the interface into direct LED mode.");
+    println!("After it finishes, LEDs will be in a broken state (off or wrong colors).");
+    println!("You will need to UNPLUG and REPLUG the device to restore normal LEDs.");
+    println!();
+    print!("Continue? [y/N] ");
+    std::io::stdout().flush().ok();
+
+    let mut answer = String::new();
+    if std::io::stdin().read_line(&mut answer).is_err() || !answer.trim().eq_ignore_ascii_case("y")
+    {
+        println!("Aborted.");
+        return false;
+    }
+    println!();
+    true
+}
+
 pub(super) fn cmd_map(
     value: u8,
     delay: u64,
@@ -18,29 +40,9 @@ pub(super) fn cmd_map(
     count: Option<usize>,
     output: Option<String>,
     output_code: bool,
-    accept: bool,
+    force_direct: bool,
 ) -> Result<()> {
-    // Warn that LED state will be disrupted
-    if !accept {
-        use std::io::Write;
-        println!("WARNING: This command puts the interface into direct LED mode.");
-        println!("After it finishes, LEDs will be in a broken state (off or wrong colors).");
-        println!("You will need to UNPLUG and REPLUG the device to restore normal LEDs.");
-        println!();
-        print!("Continue? [y/N] ");
-        std::io::stdout().flush().ok();
-
-        let mut answer = String::new();
-        if std::io::stdin().read_line(&mut answer).is_err()
-            || !answer.trim().eq_ignore_ascii_case("y")
-        {
-            println!("Aborted.");
-            return Ok(());
-        }
-        println!();
-    }
-
-    let device = open_device()?;
+    let device = open_routed(force_direct)?;
     let model = device.info().model().to_string();
     let ctx = DeviceContext::resolve(&device, true)?;
 
@@ -174,7 +176,9 @@ pub(super) fn cmd_map(
                     led.confidence = layout::Confidence::High;
                 }
             }
-            match serde_json::to_string_pretty(&final_layout) {
+            let firmware = device.info().firmware.to_string();
+            let shared = SharedLayout::from_layout(&final_layout, &model, &firmware);
+            match serde_json::to_string_pretty(&shared) {
                 Ok(json) => match std::fs::write(path, &json) {
                     Ok(()) => println!("Layout saved to {path}"),
                     Err(e) => log::error!("writing {path}: {e}"),
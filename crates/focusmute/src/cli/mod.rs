@@ -1,16 +1,39 @@
 //! CLI subcommands — device info, LED control, mute monitoring.
 
+mod air;
+mod alt;
+mod autogain;
+mod clip_safe;
 mod config_cmd;
 mod descriptor;
 mod devices;
+mod examples;
+mod gain;
+mod gradient;
+mod history;
+mod inst;
+mod layout_cmd;
+mod man;
 mod map;
 mod monitor;
 mod mute;
+mod phantom;
 mod predict;
 mod probe;
+mod qa;
+mod report;
+mod reset;
+mod resolve;
+mod simulate;
+mod snapshot;
 mod status;
+mod stress;
+mod tray;
+mod volume;
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+pub(crate) use examples::with_examples;
 
 use clap::Subcommand;
 use serde::Serialize;
@@ -20,7 +43,9 @@ pub(super) use focusmute_lib::audio::{self, MuteMonitor};
 pub(super) use focusmute_lib::config::Config;
 pub(super) use focusmute_lib::context::DeviceContext;
 pub(super) use focusmute_lib::device::{self, DiscoveredDevice, ScarlettDevice, open_device};
+pub(super) use focusmute_lib::device_controls::AirMode;
 pub(super) use focusmute_lib::error::Result;
+use focusmute_lib::ipc::{self, TrayDevice};
 pub(super) use focusmute_lib::layout;
 pub(super) use focusmute_lib::led;
 pub(super) use focusmute_lib::models;
@@ -63,6 +88,7 @@ pub(super) fn kv_indent(key: &str, value: impl std::fmt::Display, w: usize) {
 #[derive(Serialize)]
 pub(super) struct StatusOutput {
     pub version: String,
+    pub library: focusmute_lib::VersionInfo,
     pub device: Option<DeviceStatusJson>,
     pub microphone: Option<MicrophoneStatusJson>,
     pub config: ConfigSummaryJson,
@@ -75,12 +101,21 @@ pub(super) struct DeviceStatusJson {
     pub serial: Option<String>,
     pub path: String,
     pub led_support: Option<String>,
+    pub product_id: Option<u16>,
+    pub usb_speed: Option<device::UsbSpeed>,
+    pub driver_version: Option<String>,
+    pub clock: Option<focusmute_lib::clock::ClockStatus>,
 }
 
 #[derive(Serialize)]
 pub(super) struct MicrophoneStatusJson {
     pub muted: bool,
     pub name: Option<String>,
+    /// Who caused the current state (only known while the tray app is running).
+    pub origin: Option<focusmute_lib::monitor::MuteOrigin>,
+    /// OS privacy settings deny microphone access, so capture is silent
+    /// even when `muted` is false (Windows only).
+    pub blocked_by_privacy: bool,
 }
 
 #[derive(Serialize)]
@@ -124,7 +159,8 @@ pub enum Command {
         size: u32,
     },
 
-    /// Run mute indicator (monitors mic mute, changes LED color)
+    /// Run mute indicator (monitors mic mute, changes LED color). With
+    /// --json, prints one event per line (see `event-schema`)
     Monitor,
 
     /// Map directLEDValues — lights one index at a time to identify LEDs
@@ -141,7 +177,7 @@ pub enum Command {
         /// Number of LED indices to scan (default: auto-detected from schema, or 40)
         #[arg(long)]
         count: Option<usize>,
-        /// Save mapping results as JSON
+        /// Save mapping results as a shared layout file (see `layout import`)
         #[arg(long)]
         output: Option<String>,
         /// Print a Rust ModelProfile code snippet after mapping
@@ -157,6 +193,22 @@ pub enum Command {
         /// Dump full schema JSON to stdout
         #[arg(long)]
         dump_schema: bool,
+
+        /// Time schema extraction instead of probing
+        #[arg(long, conflicts_with = "dump_schema")]
+        bench_schema: bool,
+
+        /// Payload bytes requested per devmap page (with --bench-schema)
+        #[arg(long, default_value_t = focusmute_lib::protocol::DEVMAP_PAGE_SIZE, requires = "bench_schema")]
+        page_size: usize,
+
+        /// Device handles reading pages concurrently (with --bench-schema)
+        #[arg(long, default_value_t = 1, requires = "bench_schema")]
+        parallel: usize,
+
+        /// Number of timed extraction runs (with --bench-schema)
+        #[arg(long, default_value_t = 3, requires = "bench_schema")]
+        runs: usize,
     },
 
     /// Predict LED layout from a schema JSON file (no hardware required)
@@ -171,14 +223,218 @@ pub enum Command {
     /// Show device and microphone status
     Status,
 
+    /// Dump the decoded device state (LEDs, inputs, outputs, clock)
+    Snapshot,
+
+    /// Show how the config resolves against the device (inputs, LEDs,
+    /// colors, warnings) without changing anything
+    Resolve,
+
     /// Mute the default capture device
     Mute,
 
     /// Unmute the default capture device
     Unmute,
 
+    /// Flip the mute state of the default capture device
+    Toggle,
+
+    /// Control the running tray app
+    Tray {
+        #[command(subcommand)]
+        action: TrayCommand,
+    },
+
     /// List connected Focusrite devices
-    Devices,
+    Devices {
+        /// Show product ID, USB speed, and driver version
+        #[arg(long)]
+        detail: bool,
+    },
+
+    /// Mute history (requires `history_enabled` in config)
+    History {
+        #[command(subcommand)]
+        action: HistoryCommand,
+    },
+
+    /// Shared LED layouts
+    Layout {
+        #[command(subcommand)]
+        action: LayoutCommand,
+    },
+
+    /// Show or switch 48V phantom power
+    Phantom {
+        /// Phantom power switch, from 1 (default: show all switches)
+        switch: Option<usize>,
+        /// Switch 48V on or off
+        #[arg(value_parser = ["on", "off"], requires = "switch")]
+        state: Option<String>,
+        /// Skip the confirmation prompt
+        #[arg(long)]
+        accept: bool,
+    },
+
+    /// Show or set Air mode (off, presence, drive)
+    Air {
+        /// Input number, from 1 (default: show all inputs)
+        input: Option<usize>,
+        /// Air mode: off, presence, or drive (presence + drive)
+        #[arg(value_parser = clap::value_parser!(AirMode), requires = "input")]
+        mode: Option<AirMode>,
+    },
+
+    /// Show or set input gain
+    Gain {
+        /// Input number, from 1 (default: show all inputs)
+        #[arg(long)]
+        input: Option<usize>,
+        /// Gain to set (0-70, roughly dB; higher values are clamped)
+        #[arg(long, requires = "input")]
+        set: Option<u8>,
+    },
+
+    /// Run Auto Gain on an input and wait for the device to finish
+    Autogain {
+        /// Input number, from 1
+        input: usize,
+    },
+
+    /// Show or switch between the main and alt speakers
+    Alt {
+        /// on = alt speakers, off = main speakers, toggle = the other pair
+        #[arg(value_parser = ["on", "off", "toggle"])]
+        state: Option<String>,
+    },
+
+    /// Show or switch Clip Safe
+    ClipSafe {
+        /// Input number, from 1 (default: show all inputs)
+        input: Option<usize>,
+        /// Switch Clip Safe on or off
+        #[arg(value_parser = ["on", "off"], requires = "input")]
+        state: Option<String>,
+    },
+
+    /// Show or switch instrument/line level
+    Inst {
+        /// Input number, from 1 (default: show all inputs)
+        input: Option<usize>,
+        /// Input level: inst or line
+        #[arg(value_parser = ["inst", "line"], requires = "input")]
+        level: Option<String>,
+    },
+
+    /// Show or set the halo metering gradient
+    Gradient {
+        #[command(subcommand)]
+        action: GradientCommand,
+    },
+
+    /// Show or set output volume
+    Volume {
+        /// Output number, from 1 (default: show all outputs)
+        #[arg(long)]
+        output: Option<usize>,
+        /// Volume to set in dB (-127 to 0; out-of-range values are clamped)
+        #[arg(long, requires = "output", allow_negative_numbers = true)]
+        set: Option<i16>,
+    },
+
+    /// Guided hardware QA checklist (LEDs, mute, restore, reconnect)
+    Qa,
+
+    /// Reset the device (USB port reset on Linux) and reopen it
+    Reset,
+
+    /// Inject a synthetic state into the running tray (demos, UI testing)
+    #[command(hide = true)]
+    Simulate {
+        /// muted, live, disconnect, or off (back to the real microphone)
+        #[arg(value_parser = ["muted", "live", "disconnect", "off"])]
+        state: String,
+    },
+
+    /// Soak test: toggle the mute LEDs and reconnect for hours, then report
+    Stress {
+        /// How long to run, in hours
+        #[arg(long, default_value_t = 1.0)]
+        hours: f64,
+        /// Reopen the device every N minutes
+        #[arg(long, default_value_t = 10)]
+        reconnect_every: u64,
+        /// Ask for a physical unplug instead of reopening the handle
+        #[arg(long)]
+        unplug: bool,
+    },
+
+    /// Bundle version info, devices, probe, predicted layout, and config
+    /// into one archive for a new-device support request (serials hashed)
+    Report {
+        /// Archive to write
+        #[arg(long, short, default_value = "focusmute-report.tar")]
+        output: PathBuf,
+    },
+
+    /// Print the JSON Schema of the events `monitor --json` emits
+    EventSchema,
+
+    /// Show usage examples
+    Examples {
+        /// Only show examples for this command
+        command: Option<String>,
+    },
+
+    /// Print the man page, or write one per command with --output
+    Man {
+        /// Directory to write focusmute-cli.1 and focusmute-cli-<command>.1 into
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum LayoutCommand {
+    /// Import an LED layout file written by `map --output`
+    Import {
+        /// Path to the layout JSON file
+        file: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TrayCommand {
+    /// Quit the tray app
+    Quit,
+    /// Re-read the config file and apply it
+    ReloadConfig,
+}
+
+#[derive(Subcommand)]
+pub enum GradientCommand {
+    /// Show the gradient colors, lowest meter segment first
+    Get,
+    /// Set the gradient from color stops (low to high), blended over all
+    /// segments
+    Set {
+        /// Colors (#RRGGBB or a name), e.g. green yellow red
+        #[arg(required = true)]
+        colors: Vec<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum HistoryCommand {
+    /// Export recorded mute/unmute transitions
+    Export {
+        /// Only include events newer than this (e.g. 30m, 12h, 7d, 2w)
+        #[arg(long)]
+        since: Option<String>,
+        /// Output format: csv or json (--json implies json)
+        #[arg(long, default_value = "csv")]
+        format: String,
+    },
 }
 
 /// Load config from a custom path or the default location.
@@ -189,24 +445,196 @@ pub(super) fn load_config(path: Option<&Path>) -> Config {
     }
 }
 
+/// The device a command works on: the running tray app's, forwarded over
+/// IPC so the two never race on separate handles, or a handle of our own.
+pub(super) enum CliDevice {
+    Direct(device::PlatformDevice),
+    Tray(TrayDevice),
+}
+
+impl CliDevice {
+    /// Whether calls go through the running tray app.
+    pub(super) fn is_tray(&self) -> bool {
+        matches!(self, CliDevice::Tray(_))
+    }
+}
+
+impl ScarlettDevice for CliDevice {
+    fn open() -> device::Result<Self> {
+        open_device().map(CliDevice::Direct)
+    }
+
+    fn info(&self) -> &device::DeviceInfo {
+        match self {
+            CliDevice::Direct(dev) => dev.info(),
+            CliDevice::Tray(dev) => dev.info(),
+        }
+    }
+
+    fn get_descriptor(&self, offset: u32, size: u32) -> device::Result<Vec<u8>> {
+        match self {
+            CliDevice::Direct(dev) => dev.get_descriptor(offset, size),
+            CliDevice::Tray(dev) => dev.get_descriptor(offset, size),
+        }
+    }
+
+    fn set_descriptor(&self, offset: u32, data: &[u8]) -> device::Result<()> {
+        match self {
+            CliDevice::Direct(dev) => dev.set_descriptor(offset, data),
+            CliDevice::Tray(dev) => dev.set_descriptor(offset, data),
+        }
+    }
+
+    fn data_notify(&self, event_id: u32) -> device::Result<()> {
+        match self {
+            CliDevice::Direct(dev) => dev.data_notify(event_id),
+            CliDevice::Tray(dev) => dev.data_notify(event_id),
+        }
+    }
+
+    fn transact(&self, cmd: u32, payload: &[u8], out_size: usize) -> device::Result<Vec<u8>> {
+        match self {
+            CliDevice::Direct(dev) => dev.transact(cmd, payload, out_size),
+            CliDevice::Tray(dev) => dev.transact(cmd, payload, out_size),
+        }
+    }
+
+    fn wait_notify(&self, timeout_ms: u64) -> device::Result<Vec<u8>> {
+        match self {
+            CliDevice::Direct(dev) => dev.wait_notify(timeout_ms),
+            CliDevice::Tray(dev) => dev.wait_notify(timeout_ms),
+        }
+    }
+
+    fn supports_notify(&self) -> bool {
+        match self {
+            CliDevice::Direct(dev) => dev.supports_notify(),
+            CliDevice::Tray(dev) => dev.supports_notify(),
+        }
+    }
+
+    fn raw_ioctl(&self, code: u32, input: &[u8], out_size: usize) -> device::Result<Vec<u8>> {
+        match self {
+            CliDevice::Direct(dev) => dev.raw_ioctl(code, input, out_size),
+            CliDevice::Tray(dev) => dev.raw_ioctl(code, input, out_size),
+        }
+    }
+
+    fn reset(&self) -> device::Result<()> {
+        match self {
+            CliDevice::Direct(dev) => dev.reset(),
+            CliDevice::Tray(dev) => dev.reset(),
+        }
+    }
+}
+
+/// The running tray app's device, unless `--force-direct` was passed or
+/// the tray has none connected.
+pub(super) fn tray_device(force_direct: bool) -> Option<TrayDevice> {
+    if force_direct {
+        return None;
+    }
+    match TrayDevice::connect(ipc::connect_tray()?) {
+        Ok(dev) => {
+            log::debug!("forwarding device access through the running tray app");
+            Some(dev)
+        }
+        Err(e) => {
+            log::debug!("not using the tray app's device ({e}) — opening it directly");
+            None
+        }
+    }
+}
+
+/// Open the device for a command: through the running tray app when there
+/// is one (see [`tray_device`]), directly otherwise.
+pub(super) fn open_routed(force_direct: bool) -> device::Result<CliDevice> {
+    match tray_device(force_direct) {
+        Some(dev) => Ok(CliDevice::Tray(dev)),
+        None => open_device().map(CliDevice::Direct),
+    }
+}
+
+/// [`open_routed`] for the device picked by the config's `device_serial`.
+pub(super) fn open_routed_configured(
+    config: &Config,
+    force_direct: bool,
+) -> device::Result<CliDevice> {
+    match tray_device(force_direct) {
+        Some(dev) => Ok(CliDevice::Tray(dev)),
+        None => device::open_device_with_options(&config.device_serial, config.device_options())
+            .map(CliDevice::Direct),
+    }
+}
+
 /// Warn if `--json` was passed to a command that doesn't support it.
 fn warn_json_unsupported(cmd_name: &str) {
     log::warn!("--json is not supported for `{cmd_name}` (ignored)");
 }
 
-pub fn run(cmd: Command, json: bool, config_path: Option<&Path>) -> Result<()> {
+/// A "next step" for the user after `err`, keyed on the structured error kind.
+///
+/// `None` when the error message already says what to do (e.g. `Busy`) or
+/// there is nothing more specific to suggest.
+pub fn error_hint(err: &focusmute_lib::FocusmuteError) -> Option<&'static str> {
+    use device::DeviceError;
+    use focusmute_lib::FocusmuteError;
+
+    match err {
+        FocusmuteError::Device(DeviceError::NotFound) => Some(
+            "check the interface is connected and powered on, then run \
+             `focusmute-cli devices` to see what is detected",
+        ),
+        FocusmuteError::Device(DeviceError::OpenFailed(msg)) => {
+            let msg = msg.to_ascii_lowercase();
+            if msg.contains("permission denied") || msg.contains("access denied") {
+                Some(if cfg!(target_os = "linux") {
+                    "install the udev rule so your user can open the device: \
+                     `sudo install -m 644 99-focusrite.rules /etc/udev/rules.d/`, \
+                     then `sudo udevadm control --reload-rules && sudo udevadm trigger \
+                     --subsystem-match=usb` and replug the interface"
+                } else {
+                    "close Focusrite Control and any other app using the interface, then retry"
+                })
+            } else if msg.contains("busy") || msg.contains("claim interface") {
+                Some(
+                    "another program is using the interface — close Focusrite Control, \
+                     alsa-scarlett-gui, or the FocusMute tray app, then retry",
+                )
+            } else {
+                Some("run `focusmute-cli devices --detail` to check the driver and USB connection")
+            }
+        }
+        FocusmuteError::Device(DeviceError::InUse(_)) => Some(
+            "close that application (or whatever else uses the interface, e.g. \
+             Focusrite Control), then retry — the tray keeps retrying on its own",
+        ),
+        FocusmuteError::Device(DeviceError::InitFailed(_)) => {
+            Some("unplug and replug the interface, then retry")
+        }
+        FocusmuteError::Device(DeviceError::UnsupportedDevice(_)) | FocusmuteError::Schema(_) => {
+            Some("run `focusmute-cli probe` to check whether the firmware schema can be read")
+        }
+        FocusmuteError::Audio(_) => {
+            Some("check that a default recording device is set in your system sound settings")
+        }
+        _ => None,
+    }
+}
+
+pub fn run(cmd: Command, json: bool, config_path: Option<&Path>, force_direct: bool) -> Result<()> {
     match cmd {
         Command::Descriptor { offset, size } => {
             if json {
                 warn_json_unsupported("descriptor");
             }
-            descriptor::cmd_descriptor(offset, size)
+            descriptor::cmd_descriptor(offset, size, force_direct)
         }
-        Command::Monitor => {
-            if json {
-                warn_json_unsupported("monitor");
-            }
-            monitor::cmd_monitor(config_path)
+        Command::Monitor => monitor::cmd_monitor(config_path, json, force_direct),
+        Command::EventSchema => {
+            let schema = focusmute_lib::events::json_schema();
+            println!("{}", serde_json::to_string_pretty(&schema).unwrap());
+            Ok(())
         }
         Command::Map {
             value,
@@ -220,30 +648,183 @@ pub fn run(cmd: Command, json: bool, config_path: Option<&Path>) -> Result<()> {
             if json {
                 warn_json_unsupported("map");
             }
-            map::cmd_map(value, delay, index, count, output, output_code, accept)
+            if !accept && !map::confirm_direct_led_mode() {
+                return Ok(());
+            }
+            map::cmd_map(
+                value,
+                delay,
+                index,
+                count,
+                output,
+                output_code,
+                force_direct,
+            )
         }
-        Command::Probe { dump_schema } => {
+        Command::Probe {
+            dump_schema,
+            bench_schema,
+            page_size,
+            parallel,
+            runs,
+        } => {
             if json {
                 warn_json_unsupported("probe");
             }
-            probe::cmd_probe(dump_schema)
+            if bench_schema {
+                probe::cmd_bench_schema(page_size, parallel, runs, force_direct)
+            } else {
+                probe::cmd_probe(dump_schema, force_direct)
+            }
         }
         Command::Predict { schema_file } => predict::cmd_predict(schema_file, json),
         Command::Config => config_cmd::cmd_config(json, config_path),
-        Command::Status => status::cmd_status(json, config_path),
+        Command::Status => status::cmd_status(json, config_path, force_direct),
+        Command::Snapshot => snapshot::cmd_snapshot(json, force_direct),
+        Command::Resolve => resolve::cmd_resolve(json, config_path, force_direct),
         Command::Mute => {
             if json {
                 warn_json_unsupported("mute");
             }
-            mute::cmd_set_mute(mute::MuteAction::Mute)
+            mute::cmd_set_mute(mute::MuteAction::Mute, config_path)
         }
         Command::Unmute => {
             if json {
                 warn_json_unsupported("unmute");
             }
-            mute::cmd_set_mute(mute::MuteAction::Unmute)
+            mute::cmd_set_mute(mute::MuteAction::Unmute, config_path)
+        }
+        Command::Toggle => {
+            if json {
+                warn_json_unsupported("toggle");
+            }
+            mute::cmd_set_mute(mute::MuteAction::Toggle, config_path)
+        }
+        Command::Tray { action } => {
+            if json {
+                warn_json_unsupported("tray");
+            }
+            tray::cmd_tray(&action)
+        }
+        Command::Devices { detail } => devices::cmd_devices(json, detail),
+        Command::History {
+            action: HistoryCommand::Export { since, format },
+        } => history::cmd_history_export(since.as_deref(), &format, json),
+        Command::Layout {
+            action: LayoutCommand::Import { file },
+        } => {
+            if json {
+                warn_json_unsupported("layout import");
+            }
+            layout_cmd::cmd_layout_import(&file)
+        }
+        Command::Phantom {
+            switch,
+            state,
+            accept,
+        } => {
+            if json {
+                warn_json_unsupported("phantom");
+            }
+            phantom::cmd_phantom(switch, state.map(|s| s == "on"), accept, force_direct)
+        }
+        Command::Air { input, mode } => {
+            if json {
+                warn_json_unsupported("air");
+            }
+            air::cmd_air(input, mode, force_direct)
+        }
+        Command::Gain { input, set } => {
+            if json {
+                warn_json_unsupported("gain");
+            }
+            gain::cmd_gain(input, set, force_direct)
+        }
+        Command::Autogain { input } => {
+            if json {
+                warn_json_unsupported("autogain");
+            }
+            autogain::cmd_autogain(input, force_direct)
+        }
+        Command::Alt { state } => {
+            if json {
+                warn_json_unsupported("alt");
+            }
+            alt::cmd_alt(state.as_deref(), force_direct)
+        }
+        Command::ClipSafe { input, state } => {
+            if json {
+                warn_json_unsupported("clip-safe");
+            }
+            clip_safe::cmd_clip_safe(input, state.map(|s| s == "on"), force_direct)
+        }
+        Command::Inst { input, level } => {
+            if json {
+                warn_json_unsupported("inst");
+            }
+            inst::cmd_inst(input, level.map(|l| l == "inst"), force_direct)
+        }
+        Command::Gradient { action } => match action {
+            GradientCommand::Get => gradient::cmd_gradient_get(json, force_direct),
+            GradientCommand::Set { colors } => {
+                if json {
+                    warn_json_unsupported("gradient set");
+                }
+                gradient::cmd_gradient_set(&colors, force_direct)
+            }
+        },
+        Command::Volume { output, set } => {
+            if json {
+                warn_json_unsupported("volume");
+            }
+            volume::cmd_volume(output, set, force_direct)
+        }
+        Command::Qa => {
+            if json {
+                warn_json_unsupported("qa");
+            }
+            qa::cmd_qa(config_path, force_direct)
+        }
+        Command::Reset => {
+            if json {
+                warn_json_unsupported("reset");
+            }
+            reset::cmd_reset(config_path, force_direct)
+        }
+        Command::Simulate { state } => {
+            if json {
+                warn_json_unsupported("simulate");
+            }
+            simulate::cmd_simulate(&state)
+        }
+        Command::Stress {
+            hours,
+            reconnect_every,
+            unplug,
+        } => {
+            if json {
+                warn_json_unsupported("stress");
+            }
+            stress::cmd_stress(config_path, hours, reconnect_every, unplug, force_direct)
+        }
+        Command::Report { output } => {
+            if json {
+                warn_json_unsupported("report");
+            }
+            report::cmd_report(&output, config_path, force_direct)
+        }
+        Command::Examples { command } => {
+            if json {
+                warn_json_unsupported("examples");
+            }
+            examples::cmd_examples(command.as_deref())
+        }
+        Command::Man { output } => {
+            if json {
+                warn_json_unsupported("man");
+            }
+            man::cmd_man(output.as_deref())
         }
-        Command::Devices => devices::cmd_devices(json),
     }
 }
 
@@ -292,6 +873,9 @@ mod format_tests {
                 "Firmware:",
                 "Serial:",
                 "Path:",
+                "Product ID:",
+                "USB speed:",
+                "Driver:",
                 "LED support:",
                 "Name:",
                 "Mute color:",
@@ -358,6 +942,7 @@ mod json_struct_tests {
     fn status_output_has_expected_fields() {
         let output = StatusOutput {
             version: "0.1.0".into(),
+            library: focusmute_lib::version(),
             device: None,
             microphone: None,
             config: ConfigSummaryJson {
@@ -370,7 +955,7 @@ mod json_struct_tests {
         };
         let json = serde_json::to_value(&output).unwrap();
         let obj = json.as_object().unwrap();
-        assert_eq!(obj.len(), 4, "StatusOutput should have 4 fields");
+        assert_eq!(obj.len(), 5, "StatusOutput should have 5 fields");
     }
 
     #[test]
@@ -381,10 +966,14 @@ mod json_struct_tests {
             serial: None,
             path: "test://".into(),
             led_support: None,
+            product_id: None,
+            usb_speed: None,
+            driver_version: None,
+            clock: None,
         };
         let json = serde_json::to_value(&dev).unwrap();
         let obj = json.as_object().unwrap();
-        assert_eq!(obj.len(), 5, "DeviceStatusJson should have 5 fields");
+        assert_eq!(obj.len(), 9, "DeviceStatusJson should have 9 fields");
     }
 }
 
@@ -396,6 +985,7 @@ mod json_output_tests {
     fn status_output_with_null_device() {
         let output = StatusOutput {
             version: "0.1.0".into(),
+            library: focusmute_lib::version(),
             device: None,
             microphone: None,
             config: ConfigSummaryJson {
@@ -409,6 +999,8 @@ mod json_output_tests {
         let json = serde_json::to_string_pretty(&output).unwrap();
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed["version"], "0.1.0");
+        assert_eq!(parsed["library"]["protocol_revision"], 1);
+        assert!(parsed["library"]["profiles"].is_array());
         assert!(parsed["device"].is_null());
         assert!(parsed["microphone"].is_null());
         assert_eq!(parsed["config"]["sound_enabled"], true);
@@ -418,16 +1010,27 @@ mod json_output_tests {
     fn status_output_with_device_and_mic() {
         let output = StatusOutput {
             version: "0.1.0".into(),
+            library: focusmute_lib::version(),
             device: Some(DeviceStatusJson {
                 model: "Scarlett 2i2 4th Gen".into(),
                 firmware: "2.0.2417.0".into(),
                 serial: Some("ABC123".into()),
                 path: "test://path".into(),
                 led_support: Some("hardcoded (2 inputs, 40 LEDs)".into()),
+                product_id: Some(0x8219),
+                usb_speed: Some(device::UsbSpeed::High),
+                driver_version: None,
+                clock: Some(focusmute_lib::clock::ClockStatus {
+                    sample_rate: Some(48_000),
+                    source: Some(focusmute_lib::clock::ClockSource::Internal),
+                    locked: Some(true),
+                }),
             }),
             microphone: Some(MicrophoneStatusJson {
                 muted: true,
                 name: Some("Test Mic".into()),
+                origin: Some(focusmute_lib::monitor::MuteOrigin::External),
+                blocked_by_privacy: false,
             }),
             config: ConfigSummaryJson {
                 mute_color: "#FF0000 (red)".into(),
@@ -441,8 +1044,14 @@ mod json_output_tests {
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed["device"]["model"], "Scarlett 2i2 4th Gen");
         assert_eq!(parsed["device"]["serial"], "ABC123");
+        assert_eq!(parsed["device"]["product_id"], 0x8219);
+        assert_eq!(parsed["device"]["usb_speed"], "high");
+        assert_eq!(parsed["device"]["clock"]["sample_rate"], 48_000);
+        assert_eq!(parsed["device"]["clock"]["source"], "internal");
         assert_eq!(parsed["microphone"]["muted"], true);
+        assert_eq!(parsed["microphone"]["origin"], "external");
         assert_eq!(parsed["microphone"]["name"], "Test Mic");
+        assert_eq!(parsed["microphone"]["blocked_by_privacy"], false);
     }
 
     #[test]
@@ -514,10 +1123,18 @@ mod json_output_tests {
                 DiscoveredDevice {
                     path: "usb:001/002".into(),
                     serial: Some("SERIAL1".into()),
+                    product_id: Some(0x821A),
+                    model: Some("Scarlett 4i4 4th Gen"),
+                    usb_speed: Some(device::UsbSpeed::High),
+                    driver_version: None,
                 },
                 DiscoveredDevice {
                     path: "usb:001/003".into(),
                     serial: None,
+                    product_id: None,
+                    model: None,
+                    usb_speed: None,
+                    driver_version: None,
                 },
             ],
         };
@@ -528,6 +1145,86 @@ mod json_output_tests {
         assert_eq!(devices.len(), 2);
         assert_eq!(devices[0]["serial"], "SERIAL1");
         assert!(devices[1]["serial"].is_null());
+        assert_eq!(devices[0]["product_id"], 0x821A);
+        assert_eq!(devices[0]["model"], "Scarlett 4i4 4th Gen");
+        assert!(devices[1]["model"].is_null());
+        assert!(devices[1]["usb_speed"].is_null());
+    }
+}
+
+#[cfg(test)]
+mod hint_tests {
+    use super::*;
+    use focusmute_lib::FocusmuteError;
+    use focusmute_lib::audio::AudioError;
+    use focusmute_lib::device::DeviceError;
+
+    fn hint(e: DeviceError) -> Option<&'static str> {
+        error_hint(&FocusmuteError::Device(e))
+    }
+
+    #[test]
+    fn not_found_points_at_devices() {
+        assert!(
+            hint(DeviceError::NotFound)
+                .unwrap()
+                .contains("focusmute-cli devices")
+        );
+    }
+
+    #[test]
+    fn permission_denied_gets_access_hint() {
+        let h = hint(DeviceError::OpenFailed(
+            "USB open: Permission denied (os error 13)".into(),
+        ))
+        .unwrap();
+        if cfg!(target_os = "linux") {
+            assert!(h.contains("udev"));
+        } else {
+            assert!(h.contains("Focusrite Control"));
+        }
+    }
+
+    #[test]
+    fn claim_failure_names_competing_apps() {
+        let h = hint(DeviceError::OpenFailed(
+            "claim interface 5: Device or resource busy".into(),
+        ))
+        .unwrap();
+        assert!(h.contains("another program"));
+    }
+
+    #[test]
+    fn in_use_suggests_closing_the_holder() {
+        let h = hint(DeviceError::InUse(Some("ableton (PID 42)".into()))).unwrap();
+        assert!(h.contains("close that application"));
+    }
+
+    #[test]
+    fn schema_and_unsupported_suggest_probe() {
+        assert!(
+            error_hint(&FocusmuteError::Schema("bad".into()))
+                .unwrap()
+                .contains("probe")
+        );
+        assert!(
+            hint(DeviceError::UnsupportedDevice("X".into()))
+                .unwrap()
+                .contains("probe")
+        );
+    }
+
+    #[test]
+    fn audio_error_gets_hint() {
+        let e = FocusmuteError::Audio(AudioError::InitFailed("no device".into()));
+        assert!(error_hint(&e).is_some());
+    }
+
+    #[test]
+    fn self_explanatory_errors_have_no_hint() {
+        assert!(hint(DeviceError::Busy("tray".into())).is_none());
+        assert!(hint(DeviceError::TransactFailed("x".into())).is_none());
+        assert!(error_hint(&FocusmuteError::Config("bad".into())).is_none());
     }
 }
 
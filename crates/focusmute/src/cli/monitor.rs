@@ -1,14 +1,45 @@
 //! `monitor` subcommand — run mute indicator (monitors mic mute, changes LED color).
 
-use std::path::Path;
-use std::sync::atomic::Ordering;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 use super::{
     Config, DeviceContext, MonitorAction, MuteIndicator, MuteMonitor, RUNNING, ReconnectState,
     Result, ScarlettDevice, audio, led,
 };
-use focusmute_lib::device::{self, open_device_by_serial};
-use focusmute_lib::hooks;
+use focusmute_lib::device;
+use focusmute_lib::device_controls;
+use focusmute_lib::events::{DeviceState, Event, EventRecord};
+use focusmute_lib::indicators::Indicators;
+use focusmute_lib::ipc::{self, IpcRequest, IpcResponse};
+use focusmute_lib::meter;
+use focusmute_lib::monitor::{IdleScreensaver, IndicatorEvent, SCREENSAVER_WAKE_DB};
+use focusmute_lib::reconnect::{HotplugMonitor, ReconnectResync};
+use focusmute_lib::schema::{ControlMember, SchemaConstants};
+use focusmute_lib::{history, hooks};
+
+/// `--json`: stdout carries one event per line and the progress text goes
+/// to stderr.
+static JSON_EVENTS: AtomicBool = AtomicBool::new(false);
+
+/// Print progress text — to stderr while stdout carries JSON events.
+macro_rules! say {
+    ($($arg:tt)*) => {
+        if JSON_EVENTS.load(Ordering::Relaxed) {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
+
+/// Print `event` as a JSON line, with `--json`.
+fn emit(event: Event) {
+    if JSON_EVENTS.load(Ordering::Relaxed) {
+        println!("{}", EventRecord::now(event).to_json());
+    }
+}
 
 /// State for the `monitor` command, created during setup.
 struct MonitorCtx {
@@ -16,31 +47,66 @@ struct MonitorCtx {
     indicator: MuteIndicator,
     mute_color: u32,
     reconnect: ReconnectState,
+    /// Plug-in events that skip the reconnect backoff (Linux).
+    hotplug: Option<HotplugMonitor>,
     device_serial: String,
     config: Config,
+    /// `autogainInProgress` member — indicator writes are held while it's set.
+    autogain: Option<ControlMember>,
+    /// Extra mute indicators (busylight).
+    indicators: Indicators,
+    /// Firmware schema, for restoring the saved metering gradient.
+    schema: Option<SchemaConstants>,
+    /// Where the LED baseline is persisted.
+    baseline_path: Option<PathBuf>,
+    /// Halo meters drawn from live levels (`vu_mode`).
+    vu: Option<led::VuMeter>,
+    /// Whether the connected device's halos are handed to `vu`.
+    vu_active: bool,
+    /// Color cycle shown after a quiet spell (`idle_screensaver_mins`).
+    screensaver: Option<IdleScreensaver>,
+    /// Inputs whose levels wake the screensaver.
+    input_count: usize,
+    /// Metering points the device reports, once read.
+    meters: Option<usize>,
+    /// When the monitor was last asked whether the mic is in use.
+    in_use_checked: Option<Instant>,
 }
 
 /// Open device, detect model, resolve strategy.
 fn monitor_setup(config: &mut Config) -> Result<MonitorCtx> {
     let mute_color = led::mute_color_or_default(config);
 
-    let device = open_device_by_serial(&config.device_serial)?;
-    println!("[device] {}", device.info().path);
+    // Quietly wait for the device in case the driver is still loading (autostart).
+    let device = focusmute_lib::reconnect::open_with_startup_wait(
+        &config.device_serial,
+        config.device_options(),
+        Duration::from_secs(config.startup_wait_for_device_secs),
+    )?;
+    say!("[device] {}", device.info().path);
+    emit(Event::device(
+        DeviceState::Connected,
+        Some(device.info().model().to_string()),
+    ));
 
     let ctx = DeviceContext::resolve(&device, false)?;
 
     if let Some(p) = ctx.profile {
-        println!(
+        say!(
             "[model]  {} ({} inputs, {} LEDs)",
-            p.name, p.input_count, p.led_count
+            p.name,
+            p.input_count,
+            p.led_count
         );
     } else if let Some(ref pl) = ctx.predicted {
-        println!(
+        say!(
             "[model]  {} (predicted: {} inputs, {} LEDs)",
-            pl.product_name, pl.input_count, pl.total_leds
+            pl.product_name,
+            pl.input_count,
+            pl.total_leds
         );
     } else {
-        println!("[model]  Unknown device");
+        say!("[model]  Unknown device");
     }
     let (mute_mode, strategy, warnings) = led::resolve_strategy_from_config(
         config,
@@ -52,112 +118,437 @@ fn monitor_setup(config: &mut Config) -> Result<MonitorCtx> {
     for w in &warnings {
         log::warn!("[config] {w}");
     }
-    println!("[config] Mute inputs: {mute_mode}");
+    say!("[config] Mute inputs: {mute_mode}");
 
-    let indicator = MuteIndicator::new(2, false, mute_color, strategy);
+    let mut indicator = MuteIndicator::new(2, false, mute_color, strategy);
+    indicator.set_external_mute_color(led::external_mute_color(config));
+    indicator.set_in_use_color(led::in_use_color(config));
+    // The CLI doesn't query the OS, so only reduce_motion = "on" stops it
+    indicator.set_animation(config.mute_animation(|| None), config.animation_speed);
+    // Colors to restore, read before the startup check can show the mute
+    let baseline_path = led::LedBaseline::path();
+    match led::LedBaseline::load_or_capture(&device, &ctx, baseline_path.as_deref()) {
+        Ok(baseline) => indicator.set_baseline(Some(baseline)),
+        Err(e) => log::warn!("[led] could not read the LED baseline: {e}"),
+    }
+    let input_count = ctx.input_count().unwrap_or(0);
+    let mut reconnect = ReconnectState::with_defaults();
+    reconnect.set_flash_color(led::reconnect_flash_color(config));
+    let vu = if config.vu_mode {
+        vu_setup(&device, &ctx)
+    } else {
+        None
+    };
 
     Ok(MonitorCtx {
         device: Some(device),
         indicator,
         mute_color,
-        reconnect: ReconnectState::with_defaults(),
+        reconnect,
+        hotplug: HotplugMonitor::start(),
         device_serial: config.device_serial.clone(),
         config: config.clone(),
+        autogain: device_controls::autogain_control(ctx.schema.as_ref()),
+        indicators: Indicators::from_config(config),
+        schema: ctx.schema,
+        baseline_path,
+        vu,
+        vu_active: false,
+        screensaver: config.idle_screensaver(Instant::now()),
+        input_count,
+        meters: None,
+        in_use_checked: None,
     })
 }
 
+/// Halo meters for VU mode, colored with the device's metering gradient.
+fn vu_setup(device: &device::PlatformDevice, ctx: &DeviceContext) -> Option<led::VuMeter> {
+    // Known models skip the schema at startup; the gradient needs it
+    let schema = ctx
+        .schema
+        .clone()
+        .or_else(|| focusmute_lib::schema::extract_or_cached(device).ok());
+    let gradient = schema
+        .as_ref()
+        .filter(|sc| sc.gradient_count > 0)
+        .and_then(|sc| led::get_gradient(device, sc).ok())
+        .unwrap_or_default();
+    let vu = led::VuMeter::for_device(
+        ctx.input_count().unwrap_or(0),
+        ctx.profile,
+        ctx.predicted.as_ref(),
+        &gradient,
+    );
+    match &vu {
+        Some(vu) => say!("[vu]     Drawing {} input meter(s)", vu.input_count()),
+        None => log::warn!("[vu] this model has no input halos — VU mode is off"),
+    }
+    vu
+}
+
+/// Input levels for the screensaver's audio check — from VU mode's last
+/// poll when it's drawing, otherwise read here. Empty if they can't be read.
+fn input_levels(mctx: &mut MonitorCtx, dev: &device::PlatformDevice) -> Vec<u32> {
+    if let Some(vu) = mctx.vu.as_ref().filter(|_| mctx.vu_active) {
+        return vu.levels().to_vec();
+    }
+    let meters = match mctx.meters {
+        Some(n) => n,
+        None => match meter::meter_count(dev) {
+            Ok(n) => *mctx.meters.insert(n.max(mctx.input_count)),
+            Err(e) => {
+                log::debug!("[idle] could not read the meter count: {e}");
+                return Vec::new();
+            }
+        },
+    };
+    match meter::read_levels(dev, meters) {
+        Ok(mut levels) => {
+            levels.truncate(mctx.input_count);
+            levels
+        }
+        Err(e) => {
+            log::debug!("[idle] could not read the input levels: {e}");
+            Vec::new()
+        }
+    }
+}
+
+/// Run the idle screensaver after a poll. A mute change, input audio, or
+/// being muted wakes it and puts the normal LEDs back; otherwise it draws
+/// its next color once the quiet spell is long enough.
+fn update_screensaver(mctx: &mut MonitorCtx, action: MonitorAction) {
+    if mctx.screensaver.is_none() || mctx.indicator.is_busy() {
+        return;
+    }
+    let Some(dev) = mctx.device.take() else {
+        return;
+    };
+    let loud = input_levels(mctx, &dev)
+        .into_iter()
+        .any(|level| meter::level_db(level) > SCREENSAVER_WAKE_DB);
+    let now = Instant::now();
+    if let Some(saver) = mctx.screensaver.as_mut() {
+        let muted = mctx.indicator.is_muted();
+        if action != MonitorAction::NoChange || loud || muted {
+            if saver.wake(now) && !muted {
+                say!("[idle]   Activity — screensaver off");
+                if let Err(e) = led::execute(&dev, &mctx.indicator.clear_commands()) {
+                    log::warn!("[idle] could not restore the LEDs: {e}");
+                }
+            }
+        } else {
+            let was_running = saver.is_running();
+            let frame = saver.frame(&mctx.indicator.strategy().number_leds, now);
+            if !was_running && saver.is_running() {
+                say!("[idle]   Nothing happening — screensaver on");
+            }
+            if let Err(e) = led::execute(&dev, &frame) {
+                log::warn!("[idle] could not draw the screensaver: {e}");
+            }
+        }
+    }
+    mctx.device = Some(dev);
+}
+
+/// Redraw the VU halos from the live input levels, taking the halos over
+/// first if needed. LEDs the mute indicator paints are left alone.
+fn draw_vu(mctx: &mut MonitorCtx) {
+    let (Some(vu), Some(dev)) = (mctx.vu.as_mut(), mctx.device.as_ref()) else {
+        return;
+    };
+    if mctx.indicator.is_busy() {
+        return;
+    }
+    if !mctx.vu_active {
+        if let Err(e) = vu.start(dev) {
+            log::warn!("[vu] could not take over the halos: {e}");
+            return;
+        }
+        mctx.vu_active = true;
+    }
+    if let Err(e) = vu.poll(dev, &mctx.indicator.owned_leds()) {
+        log::debug!("[vu] could not draw the meters: {e}");
+    }
+}
+
+/// Every [`audio::IN_USE_POLL`], ask the monitor whether an application is
+/// recording and show the change.
+fn check_in_use(mctx: &mut MonitorCtx, monitor: &impl MuteMonitor) {
+    let now = Instant::now();
+    if mctx
+        .in_use_checked
+        .is_some_and(|t| now.duration_since(t) < audio::IN_USE_POLL)
+    {
+        return;
+    }
+    mctx.in_use_checked = Some(now);
+    let Some(in_use) = monitor.in_use() else {
+        return;
+    };
+    if in_use == mctx.indicator.is_in_use() {
+        return;
+    }
+    if in_use {
+        say!("  IN USE (an application is recording)");
+    } else {
+        say!("  IDLE   (no application is recording)");
+    }
+    let commands = mctx.indicator.next(IndicatorEvent::InUse(in_use));
+    if let Some(ref dev) = mctx.device
+        && let Err(e) = led::execute(dev, &commands)
+    {
+        log::warn!("[device] could not show the in-use state: {e}");
+    }
+}
+
 /// Monitor main loop: poll mute state, apply LEDs, handle reconnection.
 fn monitor_loop(mctx: &mut MonitorCtx, monitor: &impl MuteMonitor) {
     let initial = monitor.is_muted();
-    if initial {
-        // Sync the debouncer so polls don't trigger a spurious ApplyMute
-        mctx.indicator.force_state(true);
-        if let Some(ref dev) = mctx.device {
-            let _ = mctx.indicator.apply_mute(dev);
+    // Sync the debouncer so polls don't trigger a spurious ApplyMute, and
+    // reconcile LEDs left over from an unclean exit.
+    mctx.indicator.force_state(initial);
+    if let Some(ref dev) = mctx.device {
+        match mctx.indicator.reconcile_startup(initial, dev) {
+            Ok(led::StartupReconcile::Consistent) => {}
+            Ok(led::StartupReconcile::RestoredLeftover) => {
+                say!("[startup] Restored leftover mute LEDs (mic is live)")
+            }
+            Ok(led::StartupReconcile::ReappliedMute) => {
+                say!("[startup] Re-applied mute indicator (mic is muted)")
+            }
+            Err(e) => log::warn!("[startup] LED safety check failed: {e}"),
         }
-        println!(
+    }
+    if initial {
+        say!(
             "  MUTED (initial) -> {}",
             led::format_color(mctx.mute_color)
         );
     } else {
-        println!("  LIVE  (initial) -> normal");
+        say!("  LIVE  (initial) -> normal");
     }
+    mctx.indicators.show(initial, mctx.mute_color);
 
     while RUNNING.load(Ordering::SeqCst) {
         // Attempt reconnection if device is disconnected
+        if let Some(ref hotplug) = mctx.hotplug {
+            hotplug.apply(&mut mctx.reconnect);
+        }
         if mctx.device.is_none()
-            && let Some(new_dev) = focusmute_lib::reconnect::try_reconnect_and_refresh(
+            && let Some((new_dev, missed)) = focusmute_lib::reconnect::try_reconnect_and_refresh(
                 &mut mctx.reconnect,
                 mctx.indicator.strategy(),
                 mctx.indicator.mute_color(),
                 mctx.indicator.is_muted(),
                 &mctx.device_serial,
+                mctx.config.device_options(),
+                mctx.config.reconnect_resync(),
             )
         {
-            println!("[device] Reconnected to {}", new_dev.info().path);
+            say!("[device] Reconnected to {}", new_dev.info().path);
+            emit(Event::device(
+                DeviceState::Connected,
+                Some(new_dev.info().model().to_string()),
+            ));
+            if let Some(missed) = missed
+                && mctx.config.reconnect_resync() == ReconnectResync::Notify
+            {
+                say!(
+                    "[device] {} mute change(s) while disconnected, now {}",
+                    missed.count,
+                    if mctx.indicator.is_muted() {
+                        "MUTED"
+                    } else {
+                        "LIVE"
+                    }
+                );
+            }
+            if !mctx.indicator.is_muted()
+                && mctx.indicator.is_in_use()
+                && let Err(e) = led::execute(&new_dev, &mctx.indicator.live_commands())
+            {
+                log::warn!("[device] could not show the in-use state: {e}");
+            }
             mctx.device = Some(new_dev);
+            if let Some(saver) = mctx.screensaver.as_mut() {
+                saver.wake(Instant::now());
+            }
         }
 
-        // Wait for mute change event or 250ms fallback timeout
-        monitor.wait_for_change(std::time::Duration::from_millis(250));
+        // Wait for mute change event or 250ms fallback timeout (one frame
+        // while the mute indicator is animating)
+        let timeout = if mctx.vu.is_some() && mctx.device.is_some() {
+            led::VU_FRAME
+        } else if mctx.indicator.is_animating() {
+            led::ANIMATION_FRAME
+        } else {
+            std::time::Duration::from_millis(250)
+        };
+        monitor.wait_for_change(timeout);
 
         // Refresh cached mute state (no-op on Windows, required for PulseAudio)
         monitor.refresh();
 
+        if let Some(ref dev) = mctx.device
+            && let Some(ref control) = mctx.autogain
+            && let Ok(busy) = device_controls::any_autogain_in_progress(dev, control)
+            && busy != mctx.indicator.is_busy()
+        {
+            if busy {
+                say!("[device] Auto Gain running — holding LED updates");
+            } else {
+                say!("[device] Auto Gain finished — LEDs updated");
+            }
+            let commands = mctx.indicator.next(IndicatorEvent::DeviceBusy(busy));
+            if let Err(e) = led::execute(dev, &commands) {
+                log::warn!("[device] could not re-apply LEDs: {e}");
+            }
+        }
+
+        check_in_use(mctx, monitor);
+
         let muted = monitor.is_muted();
         if let Some(ref dev) = mctx.device {
             let (action, err) = mctx.indicator.poll_and_apply(muted, dev);
             if let Some(e) = err {
                 log::warn!("[device] communication error: {e}");
                 log::warn!("[device] will attempt reconnection...");
+                emit(Event::device(
+                    DeviceState::Disconnected,
+                    Some(e.to_string()),
+                ));
+                if action != MonitorAction::NoChange {
+                    mctx.reconnect.record_missed_mute(muted);
+                }
                 mctx.device = None;
+                mctx.vu_active = false;
             } else {
+                let origin = mctx.indicator.origin();
                 match action {
-                    MonitorAction::ApplyMute => {
-                        println!("  MUTED -> {}", led::format_color(mctx.mute_color))
-                    }
-                    MonitorAction::ClearMute => println!("  LIVE  -> normal"),
+                    MonitorAction::ApplyMute => say!(
+                        "  MUTED -> {} ({})",
+                        led::format_color(mctx.mute_color),
+                        origin.as_str()
+                    ),
+                    MonitorAction::ClearMute => say!("  LIVE  -> normal ({})", origin.as_str()),
                     MonitorAction::NoChange => {}
                 }
-                hooks::run_action_hook(action, &mctx.config);
+                if let Some(event) = Event::from_action(action, origin) {
+                    emit(event);
+                }
+                hooks::run_action_hook_with(action, origin, &mctx.config, |outcome| {
+                    emit(Event::hook(&outcome));
+                });
+                history::record_action(action, &mctx.config);
+                mctx.indicators.apply(action, mctx.mute_color);
+
+                let frame = mctx.indicator.animation_frame(std::time::Instant::now());
+                if let Err(e) = led::execute(dev, &frame) {
+                    log::warn!("[device] could not draw animation frame: {e}");
+                }
+                draw_vu(mctx);
+                update_screensaver(mctx, action);
             }
         } else {
             // Still feed the debouncer even when disconnected
-            mctx.indicator.update(muted);
+            let action = mctx.indicator.update(muted);
+            if action != MonitorAction::NoChange {
+                mctx.reconnect.record_missed_mute(muted);
+            }
+            if let Some(event) = Event::from_action(action, mctx.indicator.origin()) {
+                emit(event);
+            }
+            mctx.indicators.apply(action, mctx.mute_color);
         }
     }
 }
 
 /// Restore LED state on exit.
-fn monitor_teardown(mctx: &MonitorCtx) {
-    println!();
-    println!("Restoring LED state...");
+fn monitor_teardown(mctx: &mut MonitorCtx) {
+    say!();
+    say!("Restoring LED state...");
     if let Some(ref dev) = mctx.device {
-        if let Err(e) = led::restore_on_exit(dev, mctx.indicator.strategy()) {
+        if let Some(vu) = mctx.vu.as_mut().filter(|_| mctx.vu_active)
+            && let Err(e) = vu.stop(dev, mctx.schema.as_ref())
+        {
+            log::warn!("could not hand the halos back to the firmware: {e}");
+        }
+        if let Err(e) = mctx.indicator.restore_on_exit(dev, mctx.schema.as_ref()) {
             log::warn!("could not restore LED state: {e}");
         }
     } else {
         log::warn!("device disconnected, cannot restore LED state");
     }
-    println!("Done.");
+    if let Some(ref path) = mctx.baseline_path {
+        led::LedBaseline::remove_saved(path);
+    }
+    mctx.indicators.clear();
+    say!("Done.");
+}
+
+/// How often [`follow_tray`] asks the tray for its mute state.
+const TRAY_POLL: Duration = Duration::from_millis(200);
+
+/// With the tray app running, it already drives the indicator (and runs the
+/// hooks and records history): report its mute state rather than opening a
+/// second device handle.
+fn follow_tray(mut client: ipc::IpcClient) -> Result<()> {
+    say!("FocusMute — the tray app is running and drives the indicator; following it.");
+    say!("Pass --force-direct to run a separate monitor. Press Ctrl+C to exit.");
+    say!();
+    let mut last = None;
+    while RUNNING.load(Ordering::SeqCst) {
+        match client.request(IpcRequest::GetMuteState) {
+            Ok(IpcResponse::MuteState { muted, origin }) => {
+                if last.replace(muted) != Some(muted) {
+                    let action = if muted {
+                        say!("  MUTED ({})", origin.as_str());
+                        MonitorAction::ApplyMute
+                    } else {
+                        say!("  LIVE  ({})", origin.as_str());
+                        MonitorAction::ClearMute
+                    };
+                    if let Some(event) = Event::from_action(action, origin) {
+                        emit(event);
+                    }
+                }
+            }
+            Ok(other) => log::warn!("[tray] unexpected reply {other:?}"),
+            Err(e) => {
+                say!("[tray]   the tray app went away ({e})");
+                break;
+            }
+        }
+        std::thread::sleep(TRAY_POLL);
+    }
+    Ok(())
 }
 
-pub(super) fn cmd_monitor(config_path: Option<&Path>) -> Result<()> {
+pub(super) fn cmd_monitor(
+    config_path: Option<&Path>,
+    json: bool,
+    force_direct: bool,
+) -> Result<()> {
+    JSON_EVENTS.store(json, Ordering::Relaxed);
+    if !force_direct && let Some(client) = ipc::connect_tray() {
+        return follow_tray(client);
+    }
     let mut config = super::load_config(config_path);
     let mute_color = led::mute_color_or_default(&config);
 
     // Banner
     #[cfg(windows)]
-    println!("FocusMute — Monitors mic mute state via Windows audio API.");
+    say!("FocusMute — Monitors mic mute state via Windows audio API.");
     #[cfg(target_os = "linux")]
-    println!("FocusMute — Monitors mic mute state via PulseAudio.");
-    println!(
+    say!("FocusMute — Monitors mic mute state via PulseAudio.");
+    say!(
         "  Muted:   number LEDs -> {}",
         led::format_color(mute_color)
     );
-    println!("  Unmuted: number LEDs restored");
-    println!("Press Ctrl+C to exit (restores original state).");
-    println!();
+    say!("  Unmuted: number LEDs restored");
+    say!("Press Ctrl+C to exit (restores original state).");
+    say!();
 
     // Device + LED setup
     let mut mctx = monitor_setup(&mut config)?;
@@ -167,12 +558,21 @@ pub(super) fn cmd_monitor(config_path: Option<&Path>) -> Result<()> {
     audio::com_init()?;
 
     #[cfg(windows)]
-    let monitor = audio::WasapiMonitor::new()?;
+    let monitor = audio::WasapiMonitor::open_group(
+        &mctx.config.excluded_capture_devices,
+        mctx.config.mute_all_capture_devices,
+    )?
+    .with_applications(&mctx.config.mute_applications);
 
     #[cfg(target_os = "linux")]
-    let monitor = audio::PulseAudioMonitor::new()?;
+    let monitor = audio::LinuxMonitor::open_group(
+        mctx.config.audio_backend(),
+        &mctx.config.excluded_capture_devices,
+        mctx.config.mute_all_capture_devices,
+    )?
+    .with_applications(&mctx.config.mute_applications);
 
-    println!("[audio]  Capture device mute monitor ready");
+    say!("[audio]  Capture device mute monitor ready");
 
     #[cfg(not(any(windows, target_os = "linux")))]
     return Err(focusmute_lib::FocusmuteError::Audio(
@@ -181,9 +581,9 @@ pub(super) fn cmd_monitor(config_path: Option<&Path>) -> Result<()> {
         ),
     ));
 
-    println!("[mute]   Color: {}", led::format_color(mute_color));
-    println!();
-    println!("Monitoring... (Ctrl+C to stop)");
+    say!("[mute]   Color: {}", led::format_color(mute_color));
+    say!();
+    say!("Monitoring... (Ctrl+C to stop)");
 
     // Main loop
     monitor_loop(&mut mctx, &monitor);
@@ -192,12 +592,12 @@ pub(super) fn cmd_monitor(config_path: Option<&Path>) -> Result<()> {
     // (LEDs return to normal state and can no longer indicate mute).
     if monitor.is_muted() {
         match monitor.set_muted(false) {
-            Ok(()) => println!("  Unmuted inputs on exit."),
+            Ok(()) => say!("  Unmuted inputs on exit."),
             Err(e) => log::warn!("failed to unmute on exit: {e}"),
         }
     }
 
     // Cleanup
-    monitor_teardown(&mctx);
+    monitor_teardown(&mut mctx);
     Ok(())
 }
@@ -1,39 +1,75 @@
-//! `mute` / `unmute` subcommands — set OS microphone mute state.
+//! `mute` / `unmute` / `toggle` subcommands — set OS microphone mute state.
+
+use std::path::Path;
+
+use focusmute_lib::events::RemoteCommand;
+use focusmute_lib::ipc::{self, IpcRequest, IpcResponse};
 
 use super::{Result, audio};
 
+#[derive(Clone, Copy)]
 pub(super) enum MuteAction {
     Mute,
     Unmute,
+    Toggle,
 }
 
-/// Set OS mute state. LED feedback and sound are handled by the tray's
-/// polling loop when it is running; the CLI intentionally does not duplicate
-/// that to avoid double-firing.
-pub(super) fn cmd_set_mute(action: MuteAction) -> Result<()> {
-    let target = match action {
-        MuteAction::Mute => true,
-        MuteAction::Unmute => false,
-    };
+impl MuteAction {
+    fn command(self) -> RemoteCommand {
+        match self {
+            MuteAction::Mute => RemoteCommand::Mute,
+            MuteAction::Unmute => RemoteCommand::Unmute,
+            MuteAction::Toggle => RemoteCommand::Toggle,
+        }
+    }
+}
+
+/// Set OS mute state. A running tray does it for us, so its LEDs, sound, and
+/// hooks follow as for the hotkey. Without one (or in indicator-only mode)
+/// the CLI sets the OS state itself; LED feedback then comes from the tray's
+/// polling loop if it runs, and the CLI intentionally does not duplicate it.
+pub(super) fn cmd_set_mute(action: MuteAction, config_path: Option<&Path>) -> Result<()> {
+    let mut tray = ipc::connect_tray();
+    if let Some(ref mut client) = tray {
+        match client.request(IpcRequest::SetMute {
+            command: action.command(),
+        }) {
+            Ok(IpcResponse::MuteState { muted, .. }) => {
+                print_state(muted);
+                return Ok(());
+            }
+            Ok(reply) => log::debug!("tray did not set the mute state: {reply:?}"),
+            Err(e) => log::debug!("could not forward the mute change to the tray: {e}"),
+        }
+    }
+
+    // Same capture device the tray monitors
+    let config = super::load_config(config_path);
+    let excluded = &config.excluded_capture_devices;
 
     #[cfg(windows)]
     {
-        use super::MuteMonitor;
         audio::com_init()?;
-        audio::WasapiMonitor::new()?.set_muted(target)?;
+        let monitor = audio::WasapiMonitor::open_group(excluded, config.mute_all_capture_devices)?
+            .with_applications(&config.mute_applications);
+        set_directly(&monitor, action, tray.as_mut())?;
     }
 
     #[cfg(target_os = "linux")]
     {
-        use super::MuteMonitor;
-        let monitor = audio::PulseAudioMonitor::new()?;
-        audio::stabilize_pulseaudio(&monitor);
-        monitor.set_muted(target)?;
+        let monitor = audio::LinuxMonitor::open_group(
+            config.audio_backend(),
+            excluded,
+            config.mute_all_capture_devices,
+        )?
+        .with_applications(&config.mute_applications);
+        monitor.stabilize();
+        set_directly(&monitor, action, tray.as_mut())?;
     }
 
     #[cfg(not(any(windows, target_os = "linux")))]
     {
-        let _ = target;
+        let _ = (action, excluded, tray);
         return Err(focusmute_lib::FocusmuteError::Audio(
             focusmute_lib::audio::AudioError::InitFailed(
                 "Mute control is not yet supported on this platform.".into(),
@@ -41,6 +77,29 @@ pub(super) fn cmd_set_mute(action: MuteAction) -> Result<()> {
         ));
     }
 
-    println!("Microphone: {}", if target { "MUTED" } else { "UNMUTED" });
+    #[cfg(any(windows, target_os = "linux"))]
     Ok(())
 }
+
+/// Set the OS mute state without the tray.
+#[cfg(any(windows, target_os = "linux"))]
+fn set_directly(
+    monitor: &impl super::MuteMonitor,
+    action: MuteAction,
+    tray: Option<&mut ipc::IpcClient>,
+) -> Result<()> {
+    let target = action.command().target(monitor.is_muted());
+    // Tell a running tray that this change comes from FocusMute, not another app.
+    if let Some(client) = tray
+        && let Err(e) = client.request(IpcRequest::AnnounceMute { muted: target })
+    {
+        log::debug!("could not announce mute change to tray: {e}");
+    }
+    monitor.set_muted(target)?;
+    print_state(target);
+    Ok(())
+}
+
+fn print_state(muted: bool) {
+    println!("Microphone: {}", if muted { "MUTED" } else { "UNMUTED" });
+}
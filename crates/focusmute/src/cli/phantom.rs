@@ -0,0 +1,77 @@
+//! `phantom` subcommand — show or switch 48V phantom power.
+
+use focusmute_lib::FocusmuteError;
+use focusmute_lib::device_controls;
+
+use super::{Result, ScarlettDevice, kv, kv_width, open_routed, schema};
+
+fn on_off(on: bool) -> &'static str {
+    if on { "on" } else { "off" }
+}
+
+/// Ask before switching 48V. Returns `false` if the user declined.
+fn confirm(switch: usize, on: bool) -> bool {
+    use std::io::Write;
+    if on {
+        println!("WARNING: 48V phantom power can damage ribbon microphones and some");
+        println!("unbalanced equipment. Check what is plugged in before continuing.");
+        println!();
+    }
+    print!(
+        "Turn phantom power {} for switch {switch}? [y/N] ",
+        on_off(on)
+    );
+    std::io::stdout().flush().ok();
+
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer).is_ok() && answer.trim().eq_ignore_ascii_case("y")
+}
+
+pub(super) fn cmd_phantom(
+    switch: Option<usize>,
+    state: Option<bool>,
+    accept: bool,
+    force_direct: bool,
+) -> Result<()> {
+    let device = open_routed(force_direct)?;
+    let model = device.info().model().to_string();
+    let sc = schema::extract_or_cached(&device)
+        .map_err(|e| log::warn!("schema unavailable: {e}"))
+        .ok();
+    let control = device_controls::phantom_power_control(sc.as_ref(), &model).ok_or_else(|| {
+        FocusmuteError::Schema(format!(
+            "{model} has no enablePhantomPower member — phantom power is not switchable"
+        ))
+    })?;
+
+    let switches: Vec<usize> = match switch {
+        Some(0) => {
+            return Err(FocusmuteError::Config(
+                "phantom power switches are numbered from 1".into(),
+            ));
+        }
+        Some(n) => vec![n - 1],
+        None => (0..control.count).collect(),
+    };
+
+    if let (Some(on), Some(&channel)) = (state, switches.first()) {
+        if !accept && !confirm(channel + 1, on) {
+            println!("Aborted.");
+            return Ok(());
+        }
+        device_controls::set_phantom_power(&device, &control, channel, on)?;
+    }
+
+    let labels: Vec<String> = switches
+        .iter()
+        .map(|c| format!("Switch {}:", c + 1))
+        .collect();
+    let keys: Vec<&str> = labels.iter().map(String::as_str).collect();
+    let w = kv_width(&keys, &[]);
+    println!("Phantom power (48V):");
+    for (label, &channel) in labels.iter().zip(&switches) {
+        let on = device_controls::get_phantom_power(&device, &control, channel)?;
+        kv(label, on_off(on), w);
+    }
+    Ok(())
+}
@@ -1,8 +1,10 @@
 //! `probe` subcommand — probe device capabilities and extract firmware schema.
 
 use super::{
-    Result, ScarlettDevice, format_kv, kv, kv_indent, kv_width, layout, models, open_device, schema,
+    Result, ScarlettDevice, device, format_kv, kv, kv_indent, kv_width, layout, models,
+    open_routed, schema,
 };
+use focusmute_lib::protocol;
 
 fn print_manual_template(model_name: &str, sc: Option<&schema::SchemaConstants>) {
     let led_count = sc.map_or_else(|| "???".to_string(), |s| s.direct_led_count.to_string());
@@ -21,12 +23,13 @@ fn print_manual_template(model_name: &str, sc: Option<&schema::SchemaConstants>)
     println!("// }};");
 }
 
-pub(super) fn cmd_probe(dump_schema: bool) -> Result<()> {
+pub(super) fn cmd_probe(dump_schema: bool, force_direct: bool) -> Result<()> {
     let w = kv_width(
         &[
             "Device:",
             "Firmware:",
             "Serial:",
+            "Product ID:",
             "Hardcoded profile:",
             "Schema extraction:",
         ],
@@ -50,7 +53,7 @@ pub(super) fn cmd_probe(dump_schema: bool) -> Result<()> {
         ],
     );
 
-    let device = open_device()?;
+    let device = open_routed(force_direct)?;
     let info = device.info();
 
     kv("Device:", &info.device_name, w);
@@ -58,6 +61,16 @@ pub(super) fn cmd_probe(dump_schema: bool) -> Result<()> {
     if let Some(ref serial) = info.serial {
         kv("Serial:", serial, w);
     }
+    let product = info
+        .product_id
+        .and_then(models::product_by_id)
+        .or_else(|| models::product_by_name(info.model()));
+    if let Some(pid) = info.product_id {
+        match product {
+            Some(p) => kv("Product ID:", format_args!("0x{pid:04X} ({})", p.family), w),
+            None => kv("Product ID:", format_args!("0x{pid:04X} (unrecognised)"), w),
+        }
+    }
     println!();
 
     // Check hardcoded profile
@@ -71,6 +84,15 @@ pub(super) fn cmd_probe(dump_schema: bool) -> Result<()> {
             ),
             w,
         );
+    } else if let Some(p) = product {
+        kv(
+            "Hardcoded profile:",
+            format_args!(
+                "none for {} — layout is predicted from the schema",
+                p.family
+            ),
+            w,
+        );
     } else {
         kv("Hardcoded profile:", "NOT FOUND", w);
     }
@@ -206,3 +228,140 @@ pub(super) fn cmd_probe(dump_schema: bool) -> Result<()> {
 
     Ok(())
 }
+
+/// Read `pages` devmap pages, spreading them round-robin across `handles`
+/// (one thread per handle). The handles are handed back for the next run.
+fn read_pages<D: ScarlettDevice + Send + 'static>(
+    handles: Vec<D>,
+    pages: usize,
+    page_size: usize,
+) -> (Vec<D>, device::Result<Vec<u8>>) {
+    let n = handles.len();
+    let threads: Vec<_> = handles
+        .into_iter()
+        .enumerate()
+        .map(|(k, dev)| {
+            std::thread::spawn(move || {
+                let result = (k..pages)
+                    .step_by(n)
+                    .map(|page| {
+                        schema::read_devmap_page(&dev, page, page_size).map(|data| (page, data))
+                    })
+                    .collect::<device::Result<Vec<_>>>();
+                (dev, result)
+            })
+        })
+        .collect();
+
+    let mut handles = Vec::with_capacity(n);
+    let mut chunks = Vec::with_capacity(pages);
+    let mut error = None;
+    for thread in threads {
+        let (dev, result) = thread.join().expect("devmap read thread panicked");
+        handles.push(dev);
+        match result {
+            Ok(c) => chunks.extend(c),
+            Err(e) => error = error.or(Some(e)),
+        }
+    }
+    if let Some(e) = error {
+        return (handles, Err(e));
+    }
+    chunks.sort_by_key(|&(page, _)| page);
+    let raw = chunks.into_iter().flat_map(|(_, data)| data).collect();
+    (handles, Ok(raw))
+}
+
+/// `probe --bench-schema` — time schema extraction with a given devmap page
+/// size and number of concurrent device handles.
+pub(super) fn cmd_bench_schema(
+    page_size: usize,
+    parallel: usize,
+    runs: usize,
+    force_direct: bool,
+) -> Result<()> {
+    let w = kv_width(
+        &[
+            "Device:",
+            "Schema size:",
+            "Page size:",
+            "Parallel:",
+            "Fastest:",
+            "Average:",
+        ],
+        &[],
+    );
+
+    let first = open_routed(force_direct)?;
+    kv("Device:", &first.info().device_name, w);
+
+    let mut handles = vec![first];
+    while handles.len() < parallel.max(1) {
+        match open_routed(force_direct) {
+            Ok(dev) => handles.push(dev),
+            Err(e) => {
+                println!(
+                    "Could not open handle {}: {e} — continuing with {}",
+                    handles.len() + 1,
+                    handles.len()
+                );
+                break;
+            }
+        }
+    }
+
+    let total_size = schema::read_devmap_len(&handles[0])?;
+    let pages = schema::devmap_page_count(total_size, page_size)?;
+    kv("Schema size:", format_args!("{total_size} bytes"), w);
+    kv(
+        "Page size:",
+        format_args!("{page_size} bytes ({pages} pages)"),
+        w,
+    );
+    kv("Parallel:", format_args!("{} handle(s)", handles.len()), w);
+    if page_size != protocol::DEVMAP_PAGE_SIZE {
+        println!(
+            "Note: firmware pages are {} bytes; other sizes may not decode.",
+            protocol::DEVMAP_PAGE_SIZE
+        );
+    }
+    println!();
+
+    let mut times = Vec::with_capacity(runs);
+    for run in 1..=runs.max(1) {
+        let start = std::time::Instant::now();
+        let size = schema::read_devmap_len(&handles[0])?;
+        let (returned, result) = read_pages(handles, pages, page_size);
+        handles = returned;
+        let elapsed = start.elapsed();
+
+        let mut raw = result?;
+        raw.truncate(size);
+        let verdict = match schema::decode_schema(&raw).and_then(|json| schema::parse_schema(&json))
+        {
+            Ok(_) => "schema OK".to_string(),
+            Err(e) => format!("schema INVALID ({e})"),
+        };
+        println!(
+            "Run {run}: {:.1} ms ({:.2} ms/page) — {verdict}",
+            elapsed.as_secs_f64() * 1000.0,
+            elapsed.as_secs_f64() * 1000.0 / pages as f64
+        );
+        times.push(elapsed);
+    }
+    println!();
+
+    let fastest = times.iter().min().copied().unwrap_or_default();
+    let average = times.iter().sum::<std::time::Duration>() / times.len() as u32;
+    kv(
+        "Fastest:",
+        format_args!("{:.1} ms", fastest.as_secs_f64() * 1000.0),
+        w,
+    );
+    kv(
+        "Average:",
+        format_args!("{:.1} ms", average.as_secs_f64() * 1000.0),
+        w,
+    );
+    Ok(())
+}
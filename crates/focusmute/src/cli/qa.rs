@@ -0,0 +1,323 @@
+//! `qa` subcommand — guided hardware checklist for release testing.
+//!
+//! Walks through the checks a release needs on real hardware (each input's
+//! number LED, the mute indicator, restore, OS mute, reconnect), asks the
+//! tester to confirm what the device shows, and prints a pass/fail report.
+
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use focusmute_lib::FocusmuteError;
+
+use super::{Config, DeviceContext, Result, ScarlettDevice, device, led};
+
+/// How long to wait for the device to disappear / reappear.
+pub(super) const RECONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Pass,
+    Fail,
+    Skip,
+}
+
+impl Outcome {
+    fn label(self) -> &'static str {
+        match self {
+            Outcome::Pass => "PASS",
+            Outcome::Fail => "FAIL",
+            Outcome::Skip => "SKIP",
+        }
+    }
+}
+
+/// Parse a checklist answer: `y` = pass, `n` = fail, `s` = skip.
+fn parse_answer(answer: &str) -> Option<Outcome> {
+    match answer.trim().to_ascii_lowercase().as_str() {
+        "y" | "yes" => Some(Outcome::Pass),
+        "n" | "no" => Some(Outcome::Fail),
+        "s" | "skip" => Some(Outcome::Skip),
+        _ => None,
+    }
+}
+
+/// Ask a yes/no/skip question until the answer parses. EOF counts as skip.
+fn ask(question: &str) -> Outcome {
+    use std::io::Write;
+    loop {
+        print!("  {question} [y/n/s] ");
+        std::io::stdout().flush().ok();
+        let mut answer = String::new();
+        match std::io::stdin().read_line(&mut answer) {
+            Ok(0) | Err(_) => return Outcome::Skip,
+            Ok(_) => {
+                if let Some(outcome) = parse_answer(&answer) {
+                    return outcome;
+                }
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct Report {
+    checks: Vec<(String, Outcome, String)>,
+}
+
+impl Report {
+    fn record(&mut self, name: impl Into<String>, outcome: Outcome, note: impl Into<String>) {
+        let name = name.into();
+        let note = note.into();
+        if note.is_empty() {
+            println!("  -> {}", outcome.label());
+        } else {
+            println!("  -> {} ({note})", outcome.label());
+        }
+        self.checks.push((name, outcome, note));
+    }
+
+    fn count(&self, outcome: Outcome) -> usize {
+        self.checks.iter().filter(|(_, o, _)| *o == outcome).count()
+    }
+
+    fn print(&self) {
+        let w = self
+            .checks
+            .iter()
+            .map(|(name, _, _)| name.len())
+            .max()
+            .unwrap_or(0);
+        println!();
+        println!("=== QA report ===");
+        for (name, outcome, note) in &self.checks {
+            if note.is_empty() {
+                println!("  {:<4}  {name}", outcome.label());
+            } else {
+                println!("  {:<4}  {name:<w$}  {note}", outcome.label());
+            }
+        }
+        println!();
+        println!(
+            "{} passed, {} failed, {} skipped",
+            self.count(Outcome::Pass),
+            self.count(Outcome::Fail),
+            self.count(Outcome::Skip)
+        );
+    }
+}
+
+/// Toggle the OS microphone mute and check it reads back, then restore it.
+fn check_os_mute(config: &Config) -> std::result::Result<String, String> {
+    let excluded = &config.excluded_capture_devices;
+    #[cfg(any(windows, target_os = "linux"))]
+    {
+        use super::MuteMonitor;
+
+        #[cfg(windows)]
+        let monitor = {
+            super::audio::com_init().map_err(|e| e.to_string())?;
+            super::audio::WasapiMonitor::with_exclusions(excluded).map_err(|e| e.to_string())?
+        };
+        #[cfg(target_os = "linux")]
+        let monitor = {
+            let m = super::audio::LinuxMonitor::open(config.audio_backend(), excluded)
+                .map_err(|e| e.to_string())?;
+            m.stabilize();
+            m
+        };
+
+        let initial = monitor.is_muted();
+        monitor.set_muted(!initial).map_err(|e| e.to_string())?;
+        monitor.wait_for_change(Duration::from_secs(2));
+        monitor.refresh();
+        let toggled = monitor.is_muted();
+        monitor.set_muted(initial).map_err(|e| e.to_string())?;
+
+        if toggled == initial {
+            Err(format!("mute state stayed {initial} after toggling"))
+        } else {
+            Ok("toggled and restored".into())
+        }
+    }
+    #[cfg(not(any(windows, target_os = "linux")))]
+    {
+        let _ = excluded;
+        Err("mute control is not supported on this platform".into())
+    }
+}
+
+/// Poll until `cond` holds or [`RECONNECT_TIMEOUT`] passes.
+pub(super) fn wait_until(mut cond: impl FnMut() -> bool) -> bool {
+    let start = Instant::now();
+    while start.elapsed() < RECONNECT_TIMEOUT {
+        if cond() {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(500));
+    }
+    false
+}
+
+pub(super) fn cmd_qa(config_path: Option<&Path>, force_direct: bool) -> Result<()> {
+    let mut config = super::load_config(config_path);
+    let mute_color = led::mute_color_or_default(&config);
+    let mut report = Report::default();
+
+    println!("=== FocusMute hardware QA ===");
+    println!("Answer y (pass), n (fail), or s (skip) for each check.");
+    println!();
+
+    println!("[1] Open device");
+    let dev = match super::open_routed_configured(&config, force_direct) {
+        Ok(dev) => dev,
+        Err(e) => {
+            report.record("Open device", Outcome::Fail, e.to_string());
+            report.print();
+            return Err(e.into());
+        }
+    };
+    let info = dev.info();
+    report.record(
+        "Open device",
+        Outcome::Pass,
+        format!("{} fw {}", info.model(), info.firmware),
+    );
+
+    println!("[2] Resolve LED strategy");
+    // Check every input regardless of the user's mute_inputs selection
+    config.mute_inputs = "all".into();
+    config.mute_preset.clear();
+    config.mute_pattern.clear();
+    config.live_pattern.clear();
+    let ctx = DeviceContext::resolve(&dev, false)?;
+    let strategy = match led::resolve_strategy_from_config(
+        &mut config,
+        ctx.input_count(),
+        ctx.profile,
+        ctx.predicted.as_ref(),
+    ) {
+        Ok((_, strategy, _)) => {
+            report.record(
+                "Resolve LED strategy",
+                Outcome::Pass,
+                format!("{} number LEDs", strategy.number_leds.len()),
+            );
+            strategy
+        }
+        Err(e) => {
+            report.record("Resolve LED strategy", Outcome::Fail, e);
+            report.print();
+            return Err(FocusmuteError::Config("QA aborted".into()));
+        }
+    };
+
+    println!(
+        "[3] Flash each input number LED ({})",
+        led::format_color(mute_color)
+    );
+    for (&input, &index) in strategy.input_indices.iter().zip(&strategy.number_leds) {
+        let name = format!("Input {} number LED", input + 1);
+        if let Err(e) = led::set_single_led(&dev, index, mute_color) {
+            report.record(name, Outcome::Fail, e.to_string());
+            continue;
+        }
+        let outcome = ask(&format!(
+            "Is the number LED for input {} lit in the mute color?",
+            input + 1
+        ));
+        report.record(name, outcome, "");
+        led::clear_mute_indicator(&dev, &strategy).ok();
+    }
+
+    println!("[4] Mute indicator");
+    match led::apply_mute_indicator(&dev, &strategy, mute_color) {
+        Ok(()) => {
+            let outcome = ask("Are all input number LEDs showing the mute color?");
+            report.record("Mute indicator", outcome, "");
+        }
+        Err(e) => report.record("Mute indicator", Outcome::Fail, e.to_string()),
+    }
+
+    println!("[5] Restore");
+    match led::clear_mute_indicator(&dev, &strategy) {
+        Ok(()) => {
+            let outcome = ask("Are the number LEDs back to their normal colors?");
+            report.record("Restore", outcome, "");
+        }
+        Err(e) => report.record("Restore", Outcome::Fail, e.to_string()),
+    }
+
+    println!("[6] OS microphone mute");
+    match check_os_mute(&config) {
+        Ok(note) => report.record("OS mute toggle", Outcome::Pass, note),
+        Err(note) => report.record("OS mute toggle", Outcome::Fail, note),
+    }
+
+    println!("[7] Reconnect");
+    let via_tray = dev.is_tray();
+    drop(dev);
+    if via_tray {
+        // The tray reconnects on its own and would reopen the device first
+        report.record("Reconnect", Outcome::Skip, "the tray app owns the device");
+    } else if ask("Ready to unplug the device? (s to skip)") == Outcome::Pass {
+        println!(
+            "  Unplug the device now (waiting up to {}s)...",
+            RECONNECT_TIMEOUT.as_secs()
+        );
+        if !wait_until(|| device::enumerate_devices().is_empty()) {
+            report.record("Reconnect", Outcome::Fail, "device never disappeared");
+        } else {
+            println!("  Plug it back in...");
+            let mut reopened = None;
+            let back = wait_until(|| {
+                reopened = device::open_device_with_options(
+                    &config.device_serial,
+                    config.device_options(),
+                )
+                .ok();
+                reopened.is_some()
+            });
+            match reopened {
+                Some(dev) if back => {
+                    led::restore_on_exit(&dev, &strategy).ok();
+                    report.record("Reconnect", Outcome::Pass, "device reopened");
+                }
+                _ => report.record("Reconnect", Outcome::Fail, "device did not come back"),
+            }
+        }
+    } else {
+        report.record("Reconnect", Outcome::Skip, "");
+    }
+
+    report.print();
+    match report.count(Outcome::Fail) {
+        0 => Ok(()),
+        n => Err(FocusmuteError::Config(format!("{n} QA check(s) failed"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_parse() {
+        assert_eq!(parse_answer("Y\n"), Some(Outcome::Pass));
+        assert_eq!(parse_answer(" no "), Some(Outcome::Fail));
+        assert_eq!(parse_answer("s"), Some(Outcome::Skip));
+        assert_eq!(parse_answer(""), None);
+        assert_eq!(parse_answer("maybe"), None);
+    }
+
+    #[test]
+    fn report_counts_outcomes() {
+        let mut report = Report::default();
+        report.record("a", Outcome::Pass, "");
+        report.record("b", Outcome::Fail, "broken");
+        report.record("c", Outcome::Skip, "");
+        report.record("d", Outcome::Pass, "");
+        assert_eq!(report.count(Outcome::Pass), 2);
+        assert_eq!(report.count(Outcome::Fail), 1);
+        assert_eq!(report.count(Outcome::Skip), 1);
+    }
+}
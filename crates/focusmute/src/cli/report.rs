@@ -0,0 +1,301 @@
+//! `report` subcommand — bundle what a new-device support request needs
+//! into one archive.
+//!
+//! Collects version info, the device list, a probe of the open device, its
+//! firmware schema, the layout predicted from it, and the config with its
+//! validation result, each as a JSON file in an uncompressed tar archive. A
+//! step that fails records its error rather than aborting the report, so it
+//! is still useful when the device can't be opened.
+//!
+//! Serial numbers are replaced by a short hash everywhere they appear (the
+//! same unit always gets the same hash), and config values that may hold
+//! personal paths or commands are redacted.
+
+use std::path::Path;
+
+use serde_json::{Value, json};
+
+use super::{Config, Result, ScarlettDevice, device, layout, models, schema};
+
+/// Directory the files are stored under inside the archive.
+const ROOT: &str = "focusmute-report";
+
+/// Config keys whose values are replaced by [`REDACTED`] when set.
+const REDACTED_KEYS: &[&str] = &[
+    "mute_sound_path",
+    "unmute_sound_path",
+    "on_mute_command",
+    "on_unmute_command",
+    "mqtt_broker",
+    "mqtt_username",
+    "mqtt_password",
+    "websocket_token",
+];
+
+const REDACTED: &str = "<redacted>";
+
+/// Stable short tag for a serial: FNV-1a (64-bit), like the schema cache
+/// checksum.
+fn hash_serial(serial: &str) -> String {
+    let hash = serial.bytes().fold(0xcbf2_9ce4_8422_2325u64, |h, b| {
+        (h ^ u64::from(b.to_ascii_lowercase())).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    format!("serial-{hash:016x}")
+}
+
+/// `text` with every (case-insensitive) occurrence of each serial replaced
+/// by its hash.
+fn scrub(text: &str, serials: &[String]) -> String {
+    let mut text = text.to_string();
+    for serial in serials.iter().filter(|s| s.len() >= 4) {
+        let needle = serial.to_ascii_lowercase();
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text.as_str();
+        while let Some(pos) = rest.to_ascii_lowercase().find(&needle) {
+            out.push_str(&rest[..pos]);
+            out.push_str(&hash_serial(serial));
+            rest = &rest[pos + needle.len()..];
+        }
+        out.push_str(rest);
+        text = out;
+    }
+    text
+}
+
+/// The config as JSON, with personal paths, commands, and hook commands
+/// redacted.
+fn redacted_config(config: &Config) -> Value {
+    let mut value = serde_json::to_value(config).unwrap_or(Value::Null);
+    for key in REDACTED_KEYS {
+        if let Some(v) = value.get_mut(*key)
+            && v.as_str().is_some_and(|s| !s.is_empty())
+        {
+            *v = json!(REDACTED);
+        }
+    }
+    if let Some(Value::Object(hooks)) = value.get_mut("device_hooks") {
+        for v in hooks.values_mut() {
+            *v = json!(REDACTED);
+        }
+    }
+    value
+}
+
+fn error_json(e: impl std::fmt::Display) -> Value {
+    json!({ "error": e.to_string() })
+}
+
+/// Append one regular file to a ustar archive.
+fn tar_append(out: &mut Vec<u8>, name: &str, data: &[u8], mtime: u64) {
+    fn octal(field: &mut [u8], value: u64) {
+        let digits = format!("{value:0width$o}", width = field.len() - 1);
+        field[..digits.len()].copy_from_slice(digits.as_bytes());
+    }
+    let mut header = [0u8; 512];
+    header[..name.len()].copy_from_slice(name.as_bytes());
+    octal(&mut header[100..108], 0o644);
+    octal(&mut header[108..116], 0);
+    octal(&mut header[116..124], 0);
+    octal(&mut header[124..136], data.len() as u64);
+    octal(&mut header[136..148], mtime);
+    header[156] = b'0';
+    header[257..263].copy_from_slice(b"ustar\0");
+    header[263..265].copy_from_slice(b"00");
+    // The checksum is taken with its own field as spaces
+    header[148..156].fill(b' ');
+    let sum: u64 = header.iter().map(|&b| u64::from(b)).sum();
+    octal(&mut header[148..155], sum);
+    out.extend_from_slice(&header);
+    out.extend_from_slice(data);
+    out.resize(out.len().next_multiple_of(512), 0);
+}
+
+/// A ustar archive of `files` (name, contents) under [`ROOT`].
+fn build_archive(files: &[(&str, String)], mtime: u64) -> Vec<u8> {
+    let mut out = Vec::new();
+    for (name, contents) in files {
+        tar_append(
+            &mut out,
+            &format!("{ROOT}/{name}"),
+            contents.as_bytes(),
+            mtime,
+        );
+    }
+    // End-of-archive marker
+    out.resize(out.len() + 1024, 0);
+    out
+}
+
+/// Probe the device: identity, profile, and the firmware schema.
+fn probe(dev: &impl ScarlettDevice) -> (Value, Option<schema::SchemaConstants>) {
+    let info = dev.info();
+    let product = info
+        .product_id
+        .and_then(models::product_by_id)
+        .or_else(|| models::product_by_name(info.model()));
+    let (schema_json, sc) = match schema::extract_schema(dev) {
+        Ok(sc) => (json!("schema.json"), Some(sc)),
+        Err(e) => (error_json(e), None),
+    };
+    let probe = json!({
+        "model": info.model(),
+        "firmware": info.firmware.to_string(),
+        "serial": info.serial,
+        "product_id": info.product_id.map(|p| format!("0x{p:04X}")),
+        "product_family": product.map(|p| p.family.to_string()),
+        "usb_speed": info.usb_speed,
+        "driver_version": info.driver_version,
+        "profile": models::detect_model(info.model()).map(|p| p.name),
+        "schema": schema_json,
+    });
+    (probe, sc)
+}
+
+pub(super) fn cmd_report(
+    output: &Path,
+    config_path: Option<&Path>,
+    force_direct: bool,
+) -> Result<()> {
+    let pretty = |v: &Value| serde_json::to_string_pretty(v).unwrap_or_default();
+    let config = super::load_config(config_path);
+    let mut serials: Vec<String> = Vec::new();
+    if !config.device_serial.trim().is_empty() {
+        serials.push(config.device_serial.trim().to_string());
+    }
+
+    println!("[1/5] Version info");
+    let version = json!({
+        "focusmute": env!("CARGO_PKG_VERSION"),
+        "library": focusmute_lib::version(),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+    });
+
+    println!("[2/5] Devices");
+    let devices = device::enumerate_devices();
+    serials.extend(devices.iter().filter_map(|d| d.serial.clone()));
+    let devices = json!({ "count": devices.len(), "devices": devices });
+
+    println!("[3/5] Probe (reading the firmware schema may take a few seconds)");
+    let (probe, sc, input_count) = match super::open_routed_configured(&config, force_direct) {
+        Ok(dev) => {
+            serials.extend(dev.info().serial.clone());
+            let (probe, sc) = probe(&dev);
+            let input_count = sc
+                .as_ref()
+                .map(|s| s.max_inputs)
+                .or_else(|| models::detect_model(dev.info().model()).map(|p| p.input_count));
+            (probe, sc, input_count)
+        }
+        Err(e) => (error_json(e), None, None),
+    };
+
+    println!("[4/5] Predict");
+    let predict = match sc.as_ref().map(layout::predict_layout) {
+        Some(Ok(pl)) => serde_json::to_value(pl).unwrap_or(Value::Null),
+        Some(Err(e)) => error_json(e),
+        None => error_json("no firmware schema"),
+    };
+
+    println!("[5/5] Config validate");
+    let validation: Vec<String> = match config.validate(input_count, u64::MAX) {
+        Ok(()) => Vec::new(),
+        Err(errs) => errs.iter().map(|e| e.to_string()).collect(),
+    };
+    let config_json = json!({
+        "config_file_exists": config_path
+            .map(Path::to_path_buf)
+            .or_else(Config::path)
+            .is_some_and(|p| p.exists()),
+        "validation_errors": validation,
+        "settings": redacted_config(&config),
+    });
+
+    let mut files = vec![
+        ("version.json", pretty(&version)),
+        ("devices.json", pretty(&devices)),
+        ("probe.json", pretty(&probe)),
+        ("predict.json", pretty(&predict)),
+        ("config.json", pretty(&config_json)),
+    ];
+    if let Some(sc) = &sc {
+        files.push((
+            "schema.json",
+            serde_json::to_string_pretty(sc).unwrap_or_default(),
+        ));
+    }
+    for (_, contents) in &mut files {
+        *contents = scrub(contents, &serials);
+    }
+
+    let mtime = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    std::fs::write(output, build_archive(&files, mtime))?;
+    println!();
+    println!("Wrote {}", output.display());
+    println!("Attach it to your GitHub issue. Serial numbers are hashed and");
+    println!("sound paths and hook commands are redacted.");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serials_are_hashed_everywhere() {
+        let serials = vec!["S1A2B3C4".to_string()];
+        let text = r#"{"serial":"S1A2B3C4","path":"\\?\usb#vid_1235&s1a2b3c4#pal"}"#;
+        let scrubbed = scrub(text, &serials);
+        assert!(!scrubbed.to_ascii_lowercase().contains("s1a2b3c4"));
+        assert_eq!(scrubbed.matches(&hash_serial("S1A2B3C4")).count(), 2);
+        assert_eq!(hash_serial("S1A2B3C4"), hash_serial("s1a2b3c4"));
+    }
+
+    #[test]
+    fn config_paths_and_commands_are_redacted() {
+        let config = Config {
+            on_mute_command: "/home/alice/bin/obs-mute".into(),
+            mqtt_broker: "broker.lan:1883".into(),
+            mqtt_username: "alice".into(),
+            mqtt_password: "hunter2".into(),
+            websocket_token: "s3cret".into(),
+            device_hooks: std::collections::HashMap::from([(
+                "on_phantom_changed".into(),
+                "notify-send 48V".into(),
+            )]),
+            ..Config::default()
+        };
+        let value = redacted_config(&config);
+        assert_eq!(value["on_mute_command"], REDACTED);
+        assert_eq!(value["on_unmute_command"], "");
+        assert_eq!(value["device_hooks"]["on_phantom_changed"], REDACTED);
+        assert_eq!(value["mqtt_broker"], REDACTED);
+        assert_eq!(value["mqtt_username"], REDACTED);
+        assert_eq!(value["mqtt_password"], REDACTED);
+        assert_eq!(value["websocket_token"], REDACTED);
+        assert_eq!(value["mute_color"], config.mute_color);
+    }
+
+    #[test]
+    fn archive_is_valid_ustar() {
+        let archive = build_archive(&[("a.json", "{}".into()), ("b.json", "x".repeat(600))], 1);
+        // Two headers, 1 + 2 data blocks, two end blocks
+        assert_eq!(archive.len(), 512 * 7);
+
+        let header = &archive[..512];
+        assert!(header.starts_with(b"focusmute-report/a.json\0"));
+        assert_eq!(&header[124..136], b"00000000002\0");
+        assert_eq!(&header[257..262], b"ustar");
+        let stored = std::str::from_utf8(&header[148..154]).unwrap();
+        let mut blank = header.to_vec();
+        blank[148..156].fill(b' ');
+        let sum: u64 = blank.iter().map(|&b| u64::from(b)).sum();
+        assert_eq!(u64::from_str_radix(stored, 8).unwrap(), sum);
+
+        assert_eq!(&archive[512..514], b"{}");
+        assert!(archive[1024..].starts_with(b"focusmute-report/b.json"));
+        assert!(archive[512 * 5..].iter().all(|&b| b == 0));
+    }
+}
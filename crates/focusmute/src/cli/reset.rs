@@ -0,0 +1,24 @@
+//! `reset` subcommand — reinitialize a wedged device without replugging.
+
+use std::path::Path;
+
+use super::{Result, ScarlettDevice, device, load_config, tray_device};
+
+pub(super) fn cmd_reset(config_path: Option<&Path>, force_direct: bool) -> Result<()> {
+    if let Some(device) = tray_device(force_direct) {
+        println!(
+            "Resetting {} via the tray app...",
+            device.info().device_name
+        );
+        device.reset()?;
+        println!("Device reset — the tray app reopens it");
+        return Ok(());
+    }
+    let config = load_config(config_path);
+    let device = device::open_device_with_options(&config.device_serial, config.device_options())?;
+    println!("Resetting {}...", device.info().device_name);
+
+    let device = focusmute_lib::reconnect::reset_and_reopen(device, config.device_options())?;
+    println!("Device reopened: {}", device.info().path);
+    Ok(())
+}
@@ -0,0 +1,239 @@
+//! `resolve` subcommand — show how the config resolves against the device.
+//!
+//! Read-only: runs the same strategy resolution as `monitor` and the tray,
+//! then prints the result instead of touching the LEDs.
+
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::{Config, DeviceContext, Result, ScarlettDevice, kv, kv_indent, kv_width, led};
+use focusmute_lib::config::ValidationError;
+
+/// One indicated input.
+#[derive(Debug, Serialize)]
+struct Target {
+    /// Input number, from 1.
+    input: usize,
+    /// Number LED index.
+    led: u8,
+    color: String,
+}
+
+/// How the config resolved.
+#[derive(Debug, Serialize)]
+struct Resolution {
+    model: String,
+    /// Where the LED layout comes from: `profile` or `predicted`.
+    layout: &'static str,
+    input_count: Option<usize>,
+    /// Effective `mute_inputs`, after any fallback.
+    mute_inputs: String,
+    /// The `mute_preset` in effect, if any.
+    preset: Option<String>,
+    mute_color: String,
+    external_mute_color: Option<String>,
+    targets: Vec<Target>,
+    group_leds: Vec<u8>,
+    selected_color: String,
+    unselected_color: String,
+    warnings: Vec<String>,
+}
+
+fn resolve(mut config: Config, model: &str, ctx: &DeviceContext) -> Result<Resolution> {
+    let input_count = ctx.input_count();
+    // Config problems that change the result; a bad mute_inputs comes back
+    // from the strategy as a fallback warning instead
+    let mut warnings: Vec<String> = config
+        .validate(input_count, u64::MAX)
+        .err()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|e| {
+            matches!(
+                e,
+                ValidationError::InvalidColor(_)
+                    | ValidationError::InvalidExternalMuteColor(_)
+                    | ValidationError::InvalidInputColor { .. }
+            )
+        })
+        .map(|e| e.to_string())
+        .collect();
+
+    let (mute_mode, strategy, strategy_warnings) = led::resolve_strategy_from_config(
+        &mut config,
+        input_count,
+        ctx.profile,
+        ctx.predicted.as_ref(),
+    )
+    .map_err(focusmute_lib::FocusmuteError::Config)?;
+    let preset = config.mute_preset.trim();
+    let preset = (!preset.is_empty()
+        && !strategy_warnings
+            .iter()
+            .any(|w| w.starts_with("mute_preset")))
+    .then(|| preset.to_string());
+    warnings.extend(strategy_warnings);
+
+    let mute_color = led::mute_color_or_default(&config);
+    let targets = strategy
+        .input_indices
+        .iter()
+        .zip(&strategy.number_leds)
+        .enumerate()
+        .map(|(i, (&input, &led))| Target {
+            input: input + 1,
+            led,
+            color: led::format_color(strategy.mute_colors.get(i).copied().unwrap_or(mute_color)),
+        })
+        .collect();
+
+    Ok(Resolution {
+        model: model.to_string(),
+        layout: if ctx.profile.is_some() {
+            "profile"
+        } else {
+            "predicted"
+        },
+        input_count,
+        mute_inputs: mute_mode.to_string(),
+        preset,
+        mute_color: led::format_color(mute_color),
+        external_mute_color: led::external_mute_color(&config).map(led::format_color),
+        targets,
+        group_leds: strategy.group_leds,
+        selected_color: led::format_color(strategy.selected_color),
+        unselected_color: led::format_color(strategy.unselected_color),
+        warnings,
+    })
+}
+
+fn join<T: ToString>(items: &[T]) -> String {
+    if items.is_empty() {
+        "none".into()
+    } else {
+        items
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+pub(super) fn cmd_resolve(
+    json: bool,
+    config_path: Option<&Path>,
+    force_direct: bool,
+) -> Result<()> {
+    let config = super::load_config(config_path);
+    let device = super::open_routed_configured(&config, force_direct)?;
+    let ctx = DeviceContext::resolve(&device, false)?;
+    let r = resolve(config, device.info().model(), &ctx)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&r).unwrap());
+        return Ok(());
+    }
+
+    let labels: Vec<String> = r
+        .targets
+        .iter()
+        .map(|t| format!("Input {}:", t.input))
+        .collect();
+    let indent: Vec<&str> = labels.iter().map(String::as_str).collect();
+    let w = kv_width(
+        &[
+            "Model:",
+            "Layout:",
+            "Inputs:",
+            "Mute inputs:",
+            "Preset:",
+            "Mute color:",
+            "External color:",
+            "Group LEDs:",
+            "Restore colors:",
+        ],
+        &indent,
+    );
+    kv("Model:", &r.model, w);
+    kv("Layout:", r.layout, w);
+    kv(
+        "Inputs:",
+        r.input_count.map_or("unknown".into(), |n| n.to_string()),
+        w,
+    );
+    kv("Mute inputs:", &r.mute_inputs, w);
+    kv("Preset:", r.preset.as_deref().unwrap_or("none"), w);
+    kv("Mute color:", &r.mute_color, w);
+    if let Some(ref c) = r.external_mute_color {
+        kv("External color:", c, w);
+    }
+    println!("Number LEDs:");
+    if r.targets.is_empty() {
+        kv_indent("", "none", w);
+    }
+    for (label, t) in labels.iter().zip(&r.targets) {
+        kv_indent(label, format!("LED {} -> {}", t.led, t.color), w);
+    }
+    kv("Group LEDs:", join(&r.group_leds), w);
+    kv(
+        "Restore colors:",
+        format!(
+            "{} selected, {} unselected",
+            r.selected_color, r.unselected_color
+        ),
+        w,
+    );
+    for warning in &r.warnings {
+        println!("warning: {warning}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use focusmute_lib::models;
+    use focusmute_lib::offsets::DeviceOffsets;
+    use std::collections::HashMap;
+
+    fn ctx_2i2() -> DeviceContext {
+        let profile = models::detect_model("Scarlett 2i2 4th Gen").unwrap();
+        DeviceContext {
+            profile: Some(profile),
+            schema: None,
+            offsets: DeviceOffsets::for_profile(profile),
+            predicted: None,
+        }
+    }
+
+    #[test]
+    fn resolves_per_input_colors() {
+        let config = Config {
+            mute_inputs: "2".into(),
+            input_colors: HashMap::from([("2".into(), "blue".into())]),
+            ..Config::default()
+        };
+        let r = resolve(config, "Scarlett 2i2 4th Gen", &ctx_2i2()).unwrap();
+        assert_eq!(r.layout, "profile");
+        assert_eq!(r.targets.len(), 1);
+        assert_eq!(r.targets[0].input, 2);
+        assert_eq!(r.targets[0].color, "#0000FF");
+        assert!(r.warnings.is_empty());
+    }
+
+    #[test]
+    fn reports_fallbacks_as_warnings() {
+        let config = Config {
+            mute_inputs: "5".into(),
+            mute_preset: "bank".into(),
+            input_colors: HashMap::from([("1".into(), "nope".into())]),
+            ..Config::default()
+        };
+        let r = resolve(config, "Scarlett 2i2 4th Gen", &ctx_2i2()).unwrap();
+        assert_eq!(r.mute_inputs, "all");
+        assert_eq!(r.preset, None);
+        assert_eq!(r.targets.len(), 2);
+        assert_eq!(r.warnings.len(), 3, "{:?}", r.warnings);
+    }
+}
@@ -0,0 +1,49 @@
+//! `simulate` subcommand (hidden) — drive the running tray with synthetic
+//! mute / disconnect states for screenshots, plugin development, and UI
+//! testing, without touching the hardware or the OS mixer.
+
+use focusmute_lib::FocusmuteError;
+use focusmute_lib::ipc::{self, IpcRequest, IpcResponse, SimulatedState};
+
+use super::Result;
+
+fn parse_state(name: &str) -> Option<SimulatedState> {
+    match name {
+        "muted" => Some(SimulatedState::Muted),
+        "live" => Some(SimulatedState::Live),
+        "disconnect" => Some(SimulatedState::Disconnect),
+        "off" => Some(SimulatedState::Off),
+        _ => None,
+    }
+}
+
+pub(super) fn cmd_simulate(state: &str) -> Result<()> {
+    let state = parse_state(state)
+        .ok_or_else(|| FocusmuteError::Config(format!("unknown simulated state '{state}'")))?;
+    let mut client = ipc::connect_tray().ok_or_else(|| {
+        FocusmuteError::Config("the FocusMute tray app is not running — start it first".into())
+    })?;
+    match client.request(IpcRequest::Simulate { state })? {
+        IpcResponse::Ok => Ok(()),
+        IpcResponse::Error { message } => {
+            Err(FocusmuteError::Config(format!("via tray: {message}")))
+        }
+        other => Err(FocusmuteError::Config(format!(
+            "via tray: unexpected reply {other:?}"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_every_listed_state() {
+        assert_eq!(parse_state("muted"), Some(SimulatedState::Muted));
+        assert_eq!(parse_state("live"), Some(SimulatedState::Live));
+        assert_eq!(parse_state("disconnect"), Some(SimulatedState::Disconnect));
+        assert_eq!(parse_state("off"), Some(SimulatedState::Off));
+        assert_eq!(parse_state("unplugged"), None);
+    }
+}
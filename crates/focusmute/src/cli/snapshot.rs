@@ -0,0 +1,135 @@
+//! `snapshot` subcommand — dump the decoded device state.
+
+use focusmute_lib::snapshot::{DeviceSnapshot, InputSnapshot};
+
+use super::{Result, kv, kv_indent, kv_width, open_routed};
+
+pub(super) fn cmd_snapshot(json: bool, force_direct: bool) -> Result<()> {
+    let device = open_routed(force_direct)?;
+    let snap = DeviceSnapshot::capture(&device)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&snap).unwrap());
+        return Ok(());
+    }
+
+    let input_labels: Vec<String> = snap
+        .inputs
+        .iter()
+        .map(|i| format!("Input {}:", i.input))
+        .collect();
+    let output_labels: Vec<String> = snap
+        .outputs
+        .iter()
+        .map(|o| format!("Output {}:", o.output))
+        .collect();
+    let mut indent: Vec<&str> = vec!["Direct mode:", "Direct values:", "Gradient:"];
+    indent.extend(input_labels.iter().map(String::as_str));
+    indent.extend(output_labels.iter().map(String::as_str));
+    let w = kv_width(
+        &[
+            "Model:",
+            "Serial:",
+            "Firmware:",
+            "Clock:",
+            "Selected input:",
+            "Direct monitor:",
+            "Speakers:",
+            "48V:",
+        ],
+        &indent,
+    );
+
+    kv("Model:", &snap.model, w);
+    kv("Serial:", snap.serial.as_deref().unwrap_or("unknown"), w);
+    kv("Firmware:", &snap.firmware, w);
+    kv("Clock:", snap.clock, w);
+    kv("Selected input:", snap.selected_input, w);
+    if let Some(dm) = snap.direct_monitoring {
+        kv(
+            "Direct monitor:",
+            if dm == 0 {
+                "off".into()
+            } else {
+                dm.to_string()
+            },
+            w,
+        );
+    }
+    if let Some(alt) = snap.alt_speakers {
+        kv("Speakers:", if alt { "alt" } else { "main" }, w);
+    }
+    if !snap.phantom_power.is_empty() {
+        let states: Vec<&str> = snap
+            .phantom_power
+            .iter()
+            .map(|&on| if on { "on" } else { "off" })
+            .collect();
+        kv("48V:", states.join(", "), w);
+    }
+    if !snap.inputs.is_empty() {
+        println!("Inputs:");
+        for (label, input) in input_labels.iter().zip(&snap.inputs) {
+            kv_indent(label, describe_input(input), w);
+        }
+    }
+    if !snap.outputs.is_empty() {
+        println!("Outputs:");
+        for (label, output) in output_labels.iter().zip(&snap.outputs) {
+            kv_indent(label, format!("{} dB", output.volume_db), w);
+        }
+    }
+    println!("LEDs:");
+    kv_indent("Direct mode:", snap.leds.direct_mode, w);
+    kv_indent("Direct values:", snap.leds.direct_values.join(" "), w);
+    if !snap.leds.gradient.is_empty() {
+        kv_indent("Gradient:", snap.leds.gradient.join(" "), w);
+    }
+    Ok(())
+}
+
+/// One line per input, e.g. `gain 40, air presence, line, clip safe off`.
+fn describe_input(input: &InputSnapshot) -> String {
+    let mut parts = Vec::new();
+    if let Some(gain) = input.gain {
+        parts.push(format!("gain {gain}"));
+    }
+    if let Some(ref air) = input.air {
+        parts.push(format!("air {air}"));
+    }
+    if let Some(inst) = input.instrument {
+        parts.push(if inst { "inst" } else { "line" }.to_string());
+    }
+    if let Some(on) = input.clip_safe {
+        parts.push(format!("clip safe {}", if on { "on" } else { "off" }));
+    }
+    if parts.is_empty() {
+        "no software controls".into()
+    } else {
+        parts.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describes_available_controls_only() {
+        let input = InputSnapshot {
+            input: 1,
+            gain: Some(40),
+            air: Some("presence".into()),
+            instrument: Some(false),
+            clip_safe: None,
+        };
+        assert_eq!(describe_input(&input), "gain 40, air presence, line");
+        let bare = InputSnapshot {
+            gain: None,
+            air: None,
+            instrument: None,
+            ..input
+        };
+        assert_eq!(describe_input(&bare), "no software controls");
+    }
+}
@@ -2,35 +2,66 @@
 
 use std::path::Path;
 
+use focusmute_lib::clock;
+use focusmute_lib::ipc::{IpcRequest, IpcResponse};
+use focusmute_lib::monitor::MuteOrigin;
+
 use super::{
     Config, ConfigSummaryJson, DeviceContext, DeviceStatusJson, MicrophoneStatusJson, MuteMonitor,
-    Result, ScarlettDevice, StatusOutput, audio, kv, kv_indent, kv_width, led, open_device, schema,
+    Result, ScarlettDevice, StatusOutput, audio, kv, kv_indent, kv_width, led, open_routed, schema,
 };
 
 /// Query current microphone status. Returns None on unsupported platforms or errors.
-fn get_mic_status() -> Option<MicrophoneStatusJson> {
+fn get_mic_status(config: &Config) -> Option<MicrophoneStatusJson> {
+    let excluded = &config.excluded_capture_devices;
     #[cfg(windows)]
     {
         audio::com_init().ok()?;
-        let monitor = audio::WasapiMonitor::new().ok()?;
+        let monitor =
+            audio::WasapiMonitor::open_group(excluded, config.mute_all_capture_devices).ok()?;
         let muted = monitor.is_muted();
-        let name = monitor.device_name().map(|s| s.to_string());
-        Some(MicrophoneStatusJson { muted, name })
+        let name = monitor.primary().and_then(|m| m.device_name());
+        Some(MicrophoneStatusJson {
+            muted,
+            name,
+            origin: None,
+            blocked_by_privacy: audio::microphone_access_blocked().unwrap_or(false),
+        })
     }
     #[cfg(target_os = "linux")]
     {
-        let monitor = audio::PulseAudioMonitor::new().ok()?;
-        audio::stabilize_pulseaudio(&monitor);
+        let monitor = audio::LinuxMonitor::open_group(
+            config.audio_backend(),
+            excluded,
+            config.mute_all_capture_devices,
+        )
+        .ok()?;
+        monitor.stabilize();
         let muted = monitor.is_muted();
-        let name = monitor.device_name();
-        Some(MicrophoneStatusJson { muted, name })
+        let name = monitor.primary().and_then(|m| m.device_name());
+        Some(MicrophoneStatusJson {
+            muted,
+            name,
+            origin: None,
+            blocked_by_privacy: false,
+        })
     }
     #[cfg(not(any(windows, target_os = "linux")))]
     {
+        let _ = excluded;
         None
     }
 }
 
+/// Ask a running tray who caused the current mute state.
+fn get_tray_mute_origin() -> Option<MuteOrigin> {
+    let mut client = focusmute_lib::ipc::connect_tray()?;
+    match client.request(IpcRequest::GetMuteState).ok()? {
+        IpcResponse::MuteState { origin, .. } => Some(origin),
+        _ => None,
+    }
+}
+
 /// Collect device status from an open device.
 fn collect_device_status(dev: &impl ScarlettDevice) -> DeviceStatusJson {
     let info = dev.info();
@@ -54,12 +85,19 @@ fn collect_device_status(dev: &impl ScarlettDevice) -> DeviceStatusJson {
     } else {
         None
     };
+    let clock = clock::read_clock_status(dev, ctx.as_ref().and_then(|c| c.schema.as_ref()))
+        .map_err(|e| log::warn!("could not read clock status: {e}"))
+        .ok();
     DeviceStatusJson {
         model: info.model().to_string(),
         firmware: info.firmware.to_string(),
         serial: info.serial.clone(),
         path: info.path.clone(),
         led_support,
+        product_id: info.product_id,
+        usb_speed: info.usb_speed,
+        driver_version: info.driver_version.clone(),
+        clock,
     }
 }
 
@@ -71,6 +109,10 @@ fn print_status(
     json: bool,
 ) -> Result<()> {
     let color_display = match led::parse_color(&config.mute_color) {
+        // A color preset overrides mute_color
+        _ if !config.color_preset.is_empty() => {
+            led::format_color(led::mute_color_or_default(config))
+        }
         Ok(val) => led::format_color(val),
         Err(_) => format!("{} (invalid)", config.mute_color),
     };
@@ -86,6 +128,7 @@ fn print_status(
     if json {
         let output = StatusOutput {
             version: env!("CARGO_PKG_VERSION").to_string(),
+            library: focusmute_lib::version(),
             device: device_status,
             microphone: mic_status,
             config: config_summary,
@@ -99,12 +142,16 @@ fn print_status(
 
     // Human-readable output
     let w = kv_width(
-        &["Version:", "Device:", "Microphone:"],
+        &["Version:", "Protocol:", "Device:", "Microphone:"],
         &[
             "Model:",
             "Firmware:",
             "Serial:",
             "Path:",
+            "Product ID:",
+            "USB speed:",
+            "Driver:",
+            "Clock:",
             "LED support:",
             "Name:",
             "Mute color:",
@@ -116,6 +163,17 @@ fn print_status(
     );
 
     kv("Version:", env!("CARGO_PKG_VERSION"), w);
+    let library = focusmute_lib::version();
+    let profiles: Vec<&str> = library.profiles.iter().map(|p| p.name).collect();
+    kv(
+        "Protocol:",
+        format!(
+            "rev {} (built-in profiles: {})",
+            library.protocol_revision,
+            profiles.join(", ")
+        ),
+        w,
+    );
     println!();
 
     match &device_status {
@@ -127,6 +185,21 @@ fn print_status(
                 kv_indent("Serial:", serial, w);
             }
             kv_indent("Path:", &dev.path, w);
+            if let Some(pid) = dev.product_id {
+                kv_indent("Product ID:", format_args!("0x{pid:04X}"), w);
+            }
+            if let Some(speed) = dev.usb_speed {
+                kv_indent("USB speed:", speed, w);
+            }
+            if let Some(ref driver) = dev.driver_version {
+                kv_indent("Driver:", driver, w);
+            }
+            if let Some(clock) = dev.clock {
+                kv_indent("Clock:", clock, w);
+                if let Some(warning) = clock.unlocked_warning() {
+                    kv_indent("", format!("Warning: {warning}"), w);
+                }
+            }
             match &dev.led_support {
                 Some(support) => kv_indent("LED support:", support, w),
                 None => kv_indent("LED support:", "not available", w),
@@ -140,7 +213,20 @@ fn print_status(
 
     match &mic_status {
         Some(mic) => {
-            kv("Microphone:", if mic.muted { "MUTED" } else { "LIVE" }, w);
+            let state = if mic.muted { "MUTED" } else { "LIVE" };
+            if mic.blocked_by_privacy && !mic.muted {
+                kv("Microphone:", "BLOCKED (by privacy settings)", w);
+            } else {
+                match mic.origin {
+                    Some(MuteOrigin::FocusMute) => {
+                        kv("Microphone:", format!("{state} (FocusMute)"), w)
+                    }
+                    Some(MuteOrigin::External) => {
+                        kv("Microphone:", format!("{state} (external)"), w)
+                    }
+                    None => kv("Microphone:", state, w),
+                }
+            }
             if let Some(ref name) = mic.name {
                 kv_indent("Name:", name, w);
             }
@@ -161,10 +247,15 @@ fn print_status(
     Ok(())
 }
 
-pub(super) fn cmd_status(json: bool, config_path: Option<&Path>) -> Result<()> {
-    let device_status = open_device().ok().map(|dev| collect_device_status(&dev));
-    let mic_status = get_mic_status();
+pub(super) fn cmd_status(json: bool, config_path: Option<&Path>, force_direct: bool) -> Result<()> {
+    let device_status = open_routed(force_direct)
+        .ok()
+        .map(|dev| collect_device_status(&dev));
     let config = super::load_config(config_path);
+    let mut mic_status = get_mic_status(&config);
+    if let Some(ref mut mic) = mic_status {
+        mic.origin = get_tray_mute_origin();
+    }
     print_status(device_status, mic_status, &config, json)
 }
 
@@ -180,6 +271,8 @@ mod tests {
         assert!(!status.model.is_empty());
         assert!(!status.firmware.is_empty());
         assert!(!status.path.is_empty());
+        assert_eq!(status.product_id, dev.info().product_id);
+        assert_eq!(status.usb_speed, dev.info().usb_speed);
     }
 
     #[test]
@@ -205,6 +298,18 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn print_status_blocked_by_privacy_succeeds() {
+        let mic = MicrophoneStatusJson {
+            muted: false,
+            name: Some("Scarlett 2i2".into()),
+            origin: None,
+            blocked_by_privacy: true,
+        };
+        let result = print_status(None, Some(mic), &Config::default(), false);
+        assert!(result.is_ok());
+    }
+
     #[test]
     fn print_status_json_with_mock_device_succeeds() {
         let dev = MockDevice::new();
@@ -0,0 +1,281 @@
+//! `stress` subcommand — long-running soak test of LED writes and reconnects.
+//!
+//! Toggles the mute indicator continuously, periodically reopens the device
+//! (or asks the tester to unplug it), and reports error counts, write latency,
+//! and the process's handle/thread counts at start and end — slow leaks only
+//! show up after hours of uptime.
+
+use std::path::Path;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+
+use focusmute_lib::FocusmuteError;
+
+use super::{CliDevice, DeviceContext, RUNNING, Result, ScarlettDevice, device, led, qa};
+
+/// Pause between LED toggles.
+const TOGGLE_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How often a progress line is printed.
+const REPORT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Writes slower than this are counted as slow.
+const SLOW_WRITE: Duration = Duration::from_millis(100);
+
+/// Min / mean / max of recorded durations, without keeping every sample.
+#[derive(Debug, Default)]
+struct LatencyStats {
+    count: u64,
+    total: Duration,
+    min: Option<Duration>,
+    max: Duration,
+    slow: u64,
+}
+
+impl LatencyStats {
+    fn record(&mut self, d: Duration) {
+        self.count += 1;
+        self.total += d;
+        self.min = Some(self.min.map_or(d, |m| m.min(d)));
+        self.max = self.max.max(d);
+        if d >= SLOW_WRITE {
+            self.slow += 1;
+        }
+    }
+
+    fn mean(&self) -> Duration {
+        match self.count {
+            0 => Duration::ZERO,
+            n => self.total / n as u32,
+        }
+    }
+}
+
+impl std::fmt::Display for LatencyStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {:.1?}, mean {:.1?}, max {:.1?}, {} slow (>= {SLOW_WRITE:?})",
+            self.min.unwrap_or_default(),
+            self.mean(),
+            self.max,
+            self.slow
+        )
+    }
+}
+
+/// Open file descriptors and threads of this process, where the OS exposes them.
+fn resource_usage() -> Option<(usize, usize)> {
+    #[cfg(target_os = "linux")]
+    {
+        let fds = std::fs::read_dir("/proc/self/fd").ok()?.count();
+        let status = std::fs::read_to_string("/proc/self/status").ok()?;
+        let threads = status
+            .lines()
+            .find_map(|l| l.strip_prefix("Threads:"))?
+            .trim()
+            .parse()
+            .ok()?;
+        Some((fds, threads))
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+fn format_usage(usage: Option<(usize, usize)>) -> String {
+    match usage {
+        Some((fds, threads)) => format!("{fds} handles, {threads} threads"),
+        None => "not available on this platform".into(),
+    }
+}
+
+/// Parse `--hours` into a duration; rejects zero, negative, and absurd values.
+fn parse_hours(hours: f64) -> std::result::Result<Duration, String> {
+    if !(hours > 0.0 && hours <= 24.0 * 30.0) {
+        return Err(format!("--hours must be between 0 and 720 (got {hours})"));
+    }
+    Ok(Duration::from_secs_f64(hours * 3600.0))
+}
+
+pub(super) fn cmd_stress(
+    config_path: Option<&Path>,
+    hours: f64,
+    reconnect_every_mins: u64,
+    unplug: bool,
+    force_direct: bool,
+) -> Result<()> {
+    let duration = parse_hours(hours).map_err(FocusmuteError::Config)?;
+    let reopen_every = Duration::from_secs(reconnect_every_mins.max(1) * 60);
+    let mut config = super::load_config(config_path);
+    let mute_color = led::mute_color_or_default(&config);
+
+    let mut dev = super::open_routed_configured(&config, force_direct)?;
+    // Through the tray, a reopen reconnects to the tray, which keeps the device
+    let via_tray = dev.is_tray();
+    let ctx = DeviceContext::resolve(&dev, false)?;
+    let (_, strategy, _) = led::resolve_strategy_from_config(
+        &mut config,
+        ctx.input_count(),
+        ctx.profile,
+        ctx.predicted.as_ref(),
+    )
+    .map_err(FocusmuteError::Config)?;
+
+    let start_usage = resource_usage();
+    println!(
+        "Stress test on {} for {:.1}h — {} every {}m. Ctrl+C to stop early.",
+        dev.info().model(),
+        hours,
+        if unplug {
+            "unplug prompt"
+        } else {
+            "device reopen"
+        },
+        reopen_every.as_secs() / 60
+    );
+    println!("Start: {}", format_usage(start_usage));
+
+    let started = Instant::now();
+    let mut last_report = started;
+    let mut last_reopen = started;
+    let mut stats = LatencyStats::default();
+    let mut errors = 0u64;
+    let mut reconnects = 0u64;
+    let mut failed_reconnects = 0u64;
+    let mut muted = false;
+    let mut force_reopen = false;
+
+    loop {
+        if !RUNNING.load(Ordering::SeqCst) || started.elapsed() >= duration {
+            if muted {
+                led::restore_on_exit(&dev, &strategy).ok();
+            }
+            break;
+        }
+
+        let t = Instant::now();
+        let result = if muted {
+            led::clear_mute_indicator(&dev, &strategy)
+        } else {
+            led::apply_mute_indicator(&dev, &strategy, mute_color)
+        };
+        match result {
+            Ok(()) => {
+                stats.record(t.elapsed());
+                muted = !muted;
+            }
+            Err(e) => {
+                errors += 1;
+                log::warn!("LED write failed: {e} — reopening");
+                force_reopen = true;
+            }
+        }
+
+        if force_reopen || last_reopen.elapsed() >= reopen_every {
+            if muted {
+                led::clear_mute_indicator(&dev, &strategy).ok();
+                muted = false;
+            }
+            drop(dev);
+            if unplug {
+                println!("Unplug the device now, then plug it back in...");
+                if !qa::wait_until(|| device::enumerate_devices().is_empty()) {
+                    println!("Device was not unplugged — reopening anyway.");
+                }
+            }
+            let mut reopened = None;
+            qa::wait_until(|| {
+                reopened = if via_tray {
+                    super::tray_device(false).map(CliDevice::Tray)
+                } else {
+                    device::open_device_with_options(&config.device_serial, config.device_options())
+                        .ok()
+                        .map(CliDevice::Direct)
+                };
+                reopened.is_some()
+            });
+            dev = match reopened {
+                Some(d) => {
+                    reconnects += 1;
+                    d
+                }
+                None => {
+                    failed_reconnects += 1;
+                    println!("Device did not come back — stopping.");
+                    break;
+                }
+            };
+            last_reopen = Instant::now();
+            force_reopen = false;
+        }
+
+        if last_report.elapsed() >= REPORT_INTERVAL {
+            last_report = Instant::now();
+            println!(
+                "[{:>5}m] {} writes, {errors} errors, {reconnects} reconnects, {}; {}",
+                started.elapsed().as_secs() / 60,
+                stats.count,
+                stats,
+                format_usage(resource_usage())
+            );
+        }
+        std::thread::sleep(TOGGLE_INTERVAL);
+    }
+
+    let end_usage = resource_usage();
+
+    println!();
+    println!("=== Stress report ===");
+    println!("Ran for:     {:.1?}", started.elapsed());
+    println!("Writes:      {} ({errors} errors)", stats.count);
+    println!("Latency:     {stats}");
+    println!("Reconnects:  {reconnects} ({failed_reconnects} failed)");
+    println!("Start:       {}", format_usage(start_usage));
+    println!("End:         {}", format_usage(end_usage));
+    if let (Some((fds0, threads0)), Some((fds1, threads1))) = (start_usage, end_usage)
+        && (fds1 > fds0 || threads1 > threads0)
+    {
+        println!(
+            "Warning: handle/thread count grew by {}/{} — possible leak",
+            fds1.saturating_sub(fds0),
+            threads1.saturating_sub(threads0)
+        );
+    }
+
+    if errors > 0 || failed_reconnects > 0 {
+        return Err(FocusmuteError::Config(format!(
+            "stress test saw {errors} error(s) and {failed_reconnects} failed reconnect(s)"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latency_stats_track_extremes_and_slow_writes() {
+        let mut stats = LatencyStats::default();
+        assert_eq!(stats.mean(), Duration::ZERO);
+        stats.record(Duration::from_millis(2));
+        stats.record(Duration::from_millis(4));
+        stats.record(Duration::from_millis(150));
+        assert_eq!(stats.count, 3);
+        assert_eq!(stats.min, Some(Duration::from_millis(2)));
+        assert_eq!(stats.max, Duration::from_millis(150));
+        assert_eq!(stats.mean(), Duration::from_millis(52));
+        assert_eq!(stats.slow, 1);
+    }
+
+    #[test]
+    fn hours_must_be_positive_and_bounded() {
+        assert_eq!(parse_hours(0.5).unwrap(), Duration::from_secs(1800));
+        assert!(parse_hours(0.0).is_err());
+        assert!(parse_hours(-1.0).is_err());
+        assert!(parse_hours(f64::NAN).is_err());
+        assert!(parse_hours(10_000.0).is_err());
+    }
+}
@@ -0,0 +1,27 @@
+//! `tray` subcommand — control the running tray app over IPC.
+
+use focusmute_lib::FocusmuteError;
+use focusmute_lib::ipc::{self, IpcRequest, IpcResponse};
+
+use super::{Result, TrayCommand};
+
+pub(super) fn cmd_tray(action: &TrayCommand) -> Result<()> {
+    let (request, done) = match action {
+        TrayCommand::Quit => (IpcRequest::Quit, "Tray: quitting"),
+        TrayCommand::ReloadConfig => (IpcRequest::ReloadConfig, "Tray: config reloaded"),
+    };
+    let mut client = ipc::connect_tray()
+        .ok_or_else(|| FocusmuteError::Config("the FocusMute tray app is not running".into()))?;
+    match client.request(request)? {
+        IpcResponse::Ok => {
+            println!("{done}");
+            Ok(())
+        }
+        IpcResponse::Error { message } => {
+            Err(FocusmuteError::Config(format!("via tray: {message}")))
+        }
+        other => Err(FocusmuteError::Config(format!(
+            "via tray: unexpected reply {other:?}"
+        ))),
+    }
+}
@@ -0,0 +1,51 @@
+//! `volume` subcommand — show or set output volume.
+
+use focusmute_lib::FocusmuteError;
+use focusmute_lib::device_controls::{self, MIN_OUTPUT_VOLUME_DB};
+
+use super::{Result, ScarlettDevice, kv, kv_width, open_routed, schema};
+
+pub(super) fn cmd_volume(
+    output: Option<usize>,
+    set: Option<i16>,
+    force_direct: bool,
+) -> Result<()> {
+    let device = open_routed(force_direct)?;
+    let model = device.info().model().to_string();
+    let sc = schema::extract_or_cached(&device)
+        .map_err(|e| log::warn!("schema unavailable: {e}"))
+        .ok();
+    let control = device_controls::output_volume_control(sc.as_ref())
+        .ok_or_else(|| FocusmuteError::Schema(format!("{model} has no outputVol member")))?;
+
+    let outputs: Vec<usize> = match output {
+        Some(0) => {
+            return Err(FocusmuteError::Config("outputs are numbered from 1".into()));
+        }
+        Some(n) => vec![n - 1],
+        None => (0..control.count).collect(),
+    };
+
+    if let (Some(db), Some(&channel)) = (set, outputs.first()) {
+        let applied = device_controls::set_output_volume(&device, &control, channel, db)?;
+        if applied != db {
+            println!("Volume {db} dB is out of range; set to {applied} dB.");
+        }
+    }
+
+    let labels: Vec<String> = outputs
+        .iter()
+        .map(|c| format!("Output {}:", c + 1))
+        .collect();
+    let keys: Vec<&str> = labels.iter().map(String::as_str).collect();
+    let w = kv_width(&keys, &[]);
+    println!("Output volume ({MIN_OUTPUT_VOLUME_DB} to 0 dB):");
+    for (label, &channel) in labels.iter().zip(&outputs) {
+        let db = device_controls::get_output_volume(&device, &control, channel)?;
+        kv(label, format!("{db} dB"), w);
+    }
+    if control.notify.is_none() {
+        println!("Volume follows the hardware knob on this model (read-only).");
+    }
+    Ok(())
+}
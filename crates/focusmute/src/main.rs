@@ -8,12 +8,15 @@
 #[cfg(any(windows, target_os = "linux"))]
 mod icon;
 #[cfg(any(windows, target_os = "linux"))]
+mod os_prefs;
+#[cfg(any(windows, target_os = "linux"))]
 mod settings_dialog;
 #[cfg(any(windows, target_os = "linux"))]
 mod sound;
 #[cfg(any(windows, target_os = "linux"))]
 mod tray;
 
+use std::path::PathBuf;
 #[cfg(any(windows, target_os = "linux"))]
 use std::sync::atomic::AtomicBool;
 
@@ -38,17 +41,89 @@ fn has_parent_console() -> bool {
     }
 }
 
-/// Initialize the tray app logger, directing output to a log file.
+/// Tray command-line options. There is no clap parser here — anything else
+/// on the command line is meant for focusmute-cli.
+#[derive(Debug, Default, PartialEq)]
+struct TrayArgs {
+    /// `--safe-mode`: ignore the user config (hooks, custom sounds, etc.) and
+    /// run with defaults, to tell whether a problem comes from the configuration.
+    safe_mode: bool,
+    /// `--verbose` / `-v`: debug-level logging.
+    verbose: bool,
+    /// `--log-file <path>`: log here instead of the config directory.
+    log_file: Option<PathBuf>,
+    /// `--instance-name <tag>`: run as a separate named instance with its
+    /// own lock, IPC endpoint, and config file (see `focusmute_lib::instance`).
+    instance_name: Option<String>,
+    /// Arguments that aren't tray options.
+    other: Vec<String>,
+}
+
+fn parse_tray_args(args: impl IntoIterator<Item = String>) -> TrayArgs {
+    let mut parsed = TrayArgs::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--safe-mode" => parsed.safe_mode = true,
+            "--verbose" | "-v" => parsed.verbose = true,
+            "--log-file" => match args.next() {
+                Some(path) => parsed.log_file = Some(path.into()),
+                None => parsed.other.push(arg),
+            },
+            "--instance-name" => match args.next() {
+                Some(name) => parsed.instance_name = Some(name),
+                None => parsed.other.push(arg),
+            },
+            _ => {
+                if let Some(path) = arg.strip_prefix("--log-file=") {
+                    parsed.log_file = Some(path.into());
+                } else if let Some(name) = arg.strip_prefix("--instance-name=") {
+                    parsed.instance_name = Some(name.to_string());
+                } else {
+                    parsed.other.push(arg);
+                }
+            }
+        }
+    }
+    parsed
+}
+
+/// Pick the instance before anything opens a per-instance file (the log
+/// first): `--instance-name`, else the config's `instance_name` key.
+///
+/// Only an invalid `--instance-name` is an error; an invalid config key is
+/// reported by config validation and the default instance is used.
+fn select_instance(args: &TrayArgs) -> Result<(), String> {
+    use focusmute_lib::config::Config;
+    use focusmute_lib::instance;
+
+    if let Some(ref name) = args.instance_name {
+        return instance::set_from_flag(name);
+    }
+    if !args.safe_mode
+        && let Some(path) = Config::path()
+    {
+        let _ = instance::set_from_config(&Config::load_from(&path).0.instance_name);
+    }
+    Ok(())
+}
+
+/// Initialize the tray app logger, directing output to a log file
+/// (`log_file`, or `focusmute.log` in the config directory).
 ///
 /// Falls back to stderr if the log file can't be opened.
-fn init_tray_logger() {
+fn init_tray_logger(args: &TrayArgs) {
     use focusmute_lib::config::Config;
 
+    let default_level = if args.verbose { "debug" } else { "info" };
     let mut builder =
-        env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"));
+        env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(default_level));
     builder.format_target(false);
+    if focusmute_lib::trace::enabled() {
+        builder.filter_module("focusmute_lib::trace", log::LevelFilter::Trace);
+    }
 
-    if let Some(log_path) = Config::log_path() {
+    if let Some(log_path) = args.log_file.clone().or_else(Config::log_path) {
         if let Some(dir) = log_path.parent() {
             let _ = std::fs::create_dir_all(dir);
         }
@@ -61,7 +136,14 @@ fn init_tray_logger() {
 }
 
 fn main() {
-    init_tray_logger();
+    let args = parse_tray_args(std::env::args().skip(1));
+    if let Err(e) = select_instance(&args) {
+        let msg = format!("Error: {e}");
+        eprintln!("{msg}");
+        show_fatal_error(&msg);
+        std::process::exit(1);
+    }
+    init_tray_logger(&args);
 
     #[cfg(not(any(windows, target_os = "linux")))]
     {
@@ -72,19 +154,17 @@ fn main() {
 
     #[cfg(windows)]
     {
-        let args: Vec<String> = std::env::args().collect();
-
         // If launched with CLI arguments from a terminal, redirect to focusmute-cli
-        if args.len() > 1 && has_parent_console() {
+        if !args.other.is_empty() && has_parent_console() {
             eprintln!("Hint: Use focusmute-cli.exe for command-line usage.");
-            eprintln!("  Example: focusmute-cli.exe {}", args[1..].join(" "));
+            eprintln!("  Example: focusmute-cli.exe {}", args.other.join(" "));
             return;
         }
     }
 
     #[cfg(any(windows, target_os = "linux"))]
     {
-        if let Err(e) = tray::run() {
+        if let Err(e) = tray::run(args.safe_mode) {
             let msg = format!("Error: {e}");
             eprintln!("{msg}");
             show_fatal_error(&msg);
@@ -119,3 +199,43 @@ fn show_fatal_error(msg: &str) {
 fn show_fatal_error(_msg: &str) {
     // On non-Windows platforms, eprintln above is visible from the terminal.
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> TrayArgs {
+        parse_tray_args(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn parses_tray_flags() {
+        assert_eq!(parse(&[]), TrayArgs::default());
+        let args = parse(&["--safe-mode", "-v", "--log-file", "/tmp/fm.log"]);
+        assert!(args.safe_mode && args.verbose);
+        assert_eq!(args.log_file, Some(PathBuf::from("/tmp/fm.log")));
+        assert!(args.other.is_empty());
+        assert_eq!(
+            parse(&["--log-file=run.log"]).log_file,
+            Some(PathBuf::from("run.log"))
+        );
+        assert_eq!(
+            parse(&["--instance-name", "seat2"])
+                .instance_name
+                .as_deref(),
+            Some("seat2")
+        );
+        assert_eq!(
+            parse(&["--instance-name=seat3"]).instance_name.as_deref(),
+            Some("seat3")
+        );
+    }
+
+    #[test]
+    fn keeps_cli_arguments_for_redirect() {
+        let args = parse(&["--verbose", "status", "--json"]);
+        assert!(args.verbose);
+        assert_eq!(args.other, vec!["status", "--json"]);
+        assert_eq!(parse(&["--log-file"]).other, vec!["--log-file"]);
+    }
+}
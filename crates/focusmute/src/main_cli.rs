@@ -5,7 +5,7 @@
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches, Parser};
 
 mod cli;
 
@@ -19,7 +19,7 @@ pub static RUNNING: AtomicBool = AtomicBool::new(true);
     about = "Hotkey mute control for Focusrite Scarlett 4th Gen interfaces"
 )]
 struct Args {
-    /// Output as JSON (for status, config, devices, predict)
+    /// Output as JSON (for status, config, devices, predict; monitor streams events)
     #[arg(long, global = true)]
     json: bool,
 
@@ -27,10 +27,24 @@ struct Args {
     #[arg(long, short = 'v', global = true)]
     verbose: bool,
 
+    /// Write log output to this file instead of stderr
+    #[arg(long, global = true, value_name = "PATH")]
+    log_file: Option<PathBuf>,
+
     /// Path to a custom config file (default: platform config directory)
     #[arg(long, global = true)]
     config: Option<PathBuf>,
 
+    /// Talk to the tray instance started with this `--instance-name` (and
+    /// use its config file)
+    #[arg(long, global = true, value_name = "TAG")]
+    instance_name: Option<String>,
+
+    /// Open the device directly even if the tray app is running (device
+    /// commands otherwise go through the tray)
+    #[arg(long, visible_alias = "direct", global = true)]
+    force_direct: bool,
+
     #[command(subcommand)]
     command: cli::Command,
 }
@@ -44,13 +58,46 @@ unsafe extern "system" fn ctrl_handler(_ctrl_type: u32) -> windows::core::BOOL {
 }
 
 fn main() {
-    let args = Args::parse();
+    let matches = cli::with_examples(Args::command()).get_matches();
+    let args = Args::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
 
     let default_level = if args.verbose { "debug" } else { "warn" };
-    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(default_level))
-        .format_timestamp(None)
-        .format_target(false)
-        .init();
+    let mut logger =
+        env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(default_level));
+    logger.format_timestamp(None).format_target(false);
+    if focusmute_lib::trace::enabled() {
+        logger.filter_module("focusmute_lib::trace", log::LevelFilter::Trace);
+    }
+    if let Some(ref path) = args.log_file {
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        match std::fs::File::create(path) {
+            Ok(file) => {
+                logger.target(env_logger::Target::Pipe(Box::new(file)));
+            }
+            Err(e) => eprintln!(
+                "Warning: cannot open log file {}: {e} — logging to stderr",
+                path.display()
+            ),
+        }
+    }
+    logger.init();
+
+    // Pick the instance before any per-instance path (config, IPC) is used
+    let instance = match args.instance_name {
+        Some(ref name) => focusmute_lib::instance::set_from_flag(name),
+        None => focusmute_lib::instance::set_from_config(
+            &cli::load_config(args.config.as_deref()).instance_name,
+        ),
+    };
+    if let Err(e) = instance {
+        if args.instance_name.is_some() {
+            eprintln!("Error: {e}");
+            std::process::exit(1);
+        }
+        log::warn!("[config] {e}");
+    }
 
     // Install Ctrl+C handler
     #[cfg(windows)]
@@ -66,8 +113,16 @@ fn main() {
         .ok();
     }
 
-    if let Err(e) = cli::run(args.command, args.json, args.config.as_deref()) {
+    if let Err(e) = cli::run(
+        args.command,
+        args.json,
+        args.config.as_deref(),
+        args.force_direct,
+    ) {
         eprintln!("Error: {e}");
+        if let Some(hint) = cli::error_hint(&e) {
+            eprintln!("Hint: {hint}");
+        }
         std::process::exit(1);
     }
 }
@@ -0,0 +1,91 @@
+//! OS accessibility / attention preferences — do-not-disturb and reduce motion.
+//!
+//! Each query returns `None` when the platform setting can't be read; pair
+//! with [`focusmute_lib::config::resolve_os_preference`] so the config's
+//! `"on"` / `"off"` overrides win over detection.
+
+/// Whether the OS is suppressing notifications (Windows Focus Assist / quiet
+/// hours / presentation mode; GNOME "Do Not Disturb").
+pub fn do_not_disturb() -> Option<bool> {
+    #[cfg(windows)]
+    {
+        use windows::Win32::UI::Shell::{QUNS_ACCEPTS_NOTIFICATIONS, SHQueryUserNotificationState};
+        let state = unsafe { SHQueryUserNotificationState() }.ok()?;
+        Some(state != QUNS_ACCEPTS_NOTIFICATIONS)
+    }
+    #[cfg(target_os = "linux")]
+    {
+        // show-banners = false is GNOME's Do Not Disturb switch
+        gsettings_bool("org.gnome.desktop.notifications", "show-banners").map(|shown| !shown)
+    }
+    #[cfg(not(any(windows, target_os = "linux")))]
+    {
+        None
+    }
+}
+
+/// Whether the user asked the OS to reduce motion (Windows "Show animations
+/// in Windows" off; GNOME animations disabled).
+pub fn reduce_motion() -> Option<bool> {
+    #[cfg(windows)]
+    {
+        use windows::Win32::UI::WindowsAndMessaging::{
+            SPI_GETCLIENTAREAANIMATION, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS, SystemParametersInfoW,
+        };
+        let mut enabled: i32 = 1;
+        unsafe {
+            SystemParametersInfoW(
+                SPI_GETCLIENTAREAANIMATION,
+                0,
+                Some(&mut enabled as *mut i32 as *mut _),
+                SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+            )
+        }
+        .ok()?;
+        Some(enabled == 0)
+    }
+    #[cfg(target_os = "linux")]
+    {
+        gsettings_bool("org.gnome.desktop.interface", "enable-animations").map(|on| !on)
+    }
+    #[cfg(not(any(windows, target_os = "linux")))]
+    {
+        None
+    }
+}
+
+/// Read a boolean GSettings key. `None` without `gsettings` or the schema.
+#[cfg(target_os = "linux")]
+fn gsettings_bool(schema: &str, key: &str) -> Option<bool> {
+    let output = std::process::Command::new("gsettings")
+        .args(["get", schema, key])
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_gsettings_bool(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn parse_gsettings_bool(output: &str) -> Option<bool> {
+    match output.trim() {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_gsettings_output() {
+        assert_eq!(parse_gsettings_bool("true\n"), Some(true));
+        assert_eq!(parse_gsettings_bool("false"), Some(false));
+        assert_eq!(parse_gsettings_bool("'default'"), None);
+        assert_eq!(parse_gsettings_bool(""), None);
+    }
+}
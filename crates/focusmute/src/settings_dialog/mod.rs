@@ -7,9 +7,11 @@
 //! - Sound feedback (checkbox)
 //! - Custom mute/unmute sounds
 //! - Autostart (checkbox)
+//! - Schema cache (status, refresh, clear)
 //!
 //! Returns `Some(new_config)` on Save, `None` on Cancel/close.
 
+use focusmute_lib::clock::ClockStatus;
 use focusmute_lib::config::Config;
 use focusmute_lib::device::DeviceInfo;
 use focusmute_lib::models::ModelProfile;
@@ -17,6 +19,9 @@ use focusmute_lib::models::ModelProfile;
 #[cfg(any(windows, target_os = "linux"))]
 mod ui;
 
+/// Re-reads the firmware schema from the connected device into the cache.
+pub type RefreshSchema<'a> = &'a dyn Fn() -> focusmute_lib::error::Result<()>;
+
 /// Maximum custom sound file size (10 MB).
 const MAX_SOUND_FILE_BYTES: u64 = 10 * 1024 * 1024;
 
@@ -40,12 +45,24 @@ impl SoundPreviewPlayer {
     pub fn play(&self, path: &str, fallback: &'static [u8]) {
         // Stop any currently playing preview
         self.sink.stop();
-        let sound = crate::sound::load_sound_data(path, fallback);
+        let (sound, warning) = crate::sound::load_sound_data(path, fallback);
+        if let Some(w) = warning {
+            log::warn!("[sound] {w}");
+        }
         crate::sound::play_sound(&sound, &self.sink);
     }
 }
 
+/// Number of built-in dropdown items for `input_count` inputs
+/// ("All", one per input, and "Input 1+2+…" when there are several).
+fn builtin_combo_len(input_count: usize) -> usize {
+    1 + input_count + usize::from(input_count >= 2)
+}
+
 /// Build the mute_inputs dropdown items and find the selected index.
+///
+/// A selection the built-in items can't express (e.g. `"1-4,7"` from the
+/// config file) is kept as a trailing "Custom" item so saving doesn't lose it.
 pub(crate) fn inputs_combo_items(config: &Config, input_count: usize) -> (Vec<String>, usize) {
     let mut items = vec!["All".to_string()];
     for i in 1..=input_count {
@@ -62,22 +79,35 @@ pub(crate) fn inputs_combo_items(config: &Config, input_count: usize) -> (Vec<St
         focusmute_lib::config::MuteInputs::Specific(ref inputs) => {
             if input_count >= 2 && inputs.len() == input_count {
                 items.len() - 1
-            } else if inputs.len() == 1 {
+            } else if inputs.len() == 1 && inputs[0] < input_count {
                 inputs[0] + 1
+            } else if input_count > 0 {
+                items.push(format!("Custom ({})", config.mute_inputs.trim()));
+                items.len() - 1
             } else {
                 0
             }
         }
+        focusmute_lib::config::MuteInputs::AllExcept(_) if input_count > 0 => {
+            items.push(format!("Custom ({})", config.mute_inputs.trim()));
+            items.len() - 1
+        }
+        focusmute_lib::config::MuteInputs::AllExcept(_) => 0,
     };
     let selected = selected.min(items.len() - 1);
     (items, selected)
 }
 
 /// Convert combo selection index back to mute_inputs string.
-pub(crate) fn combo_to_mute_inputs(index: usize, input_count: usize) -> String {
+///
+/// `custom` is returned for the trailing "Custom" item (the original value).
+pub(crate) fn combo_to_mute_inputs(index: usize, input_count: usize, custom: &str) -> String {
     if index == 0 {
         return "all".to_string();
     }
+    if index >= builtin_combo_len(input_count) {
+        return custom.trim().to_string();
+    }
     if input_count >= 2 && index == input_count + 1 {
         let nums: Vec<String> = (1..=input_count).map(|i| i.to_string()).collect();
         return nums.join(",");
@@ -90,10 +120,16 @@ pub(crate) fn combo_to_mute_inputs(index: usize, input_count: usize) -> String {
 /// This is modal — blocks the calling thread until the dialog is closed.
 ///
 /// Must be called from the main thread (eframe/winit requirement).
+///
+/// `refresh_schema` backs the "Refresh" button of the schema cache; pass
+/// `None` without a device.
 pub fn show_settings(
     config: &Config,
     model: Option<&ModelProfile>,
     device_info: Option<&DeviceInfo>,
+    clock: Option<&ClockStatus>,
+    safe_mode: bool,
+    refresh_schema: Option<RefreshSchema<'_>>,
 ) -> Option<Config> {
     #[cfg(any(windows, target_os = "linux"))]
     {
@@ -109,9 +145,18 @@ pub fn show_settings(
             if let Some(ref serial) = info.serial {
                 device_lines.push(("Serial".into(), serial.clone()));
             }
+            if let Some(clock) = clock {
+                device_lines.push(("Clock".into(), clock.to_string()));
+            }
         } else {
             device_lines.push(("Device".into(), "not connected".into()));
         }
+        if safe_mode {
+            device_lines.push((
+                "Mode".into(),
+                "Safe mode (defaults, changes not saved)".into(),
+            ));
+        }
 
         let result: Arc<Mutex<Option<Config>>> = Arc::new(Mutex::new(None));
         let result_for_app = result.clone();
@@ -132,6 +177,7 @@ pub fn show_settings(
                     config_clone,
                     input_count,
                     device_lines,
+                    refresh_schema,
                     result_for_app,
                     cc,
                 )))
@@ -146,7 +192,7 @@ pub fn show_settings(
 
     #[cfg(not(any(windows, target_os = "linux")))]
     {
-        let _ = (config, model, device_info);
+        let _ = (config, model, device_info, clock, safe_mode, refresh_schema);
         log::warn!("Settings dialog is not available on this platform.");
         None
     }
@@ -228,37 +274,70 @@ mod tests {
 
     #[test]
     fn combo_to_mute_all() {
-        assert_eq!(combo_to_mute_inputs(0, 2), "all");
+        assert_eq!(combo_to_mute_inputs(0, 2, ""), "all");
     }
 
     #[test]
     fn combo_to_mute_input_1() {
-        assert_eq!(combo_to_mute_inputs(1, 2), "1");
+        assert_eq!(combo_to_mute_inputs(1, 2, ""), "1");
     }
 
     #[test]
     fn combo_to_mute_input_2() {
-        assert_eq!(combo_to_mute_inputs(2, 2), "2");
+        assert_eq!(combo_to_mute_inputs(2, 2, ""), "2");
     }
 
     #[test]
     fn combo_to_mute_all_combined() {
         // For 2 inputs, index 3 (= input_count + 1) is "Input 1+2"
-        assert_eq!(combo_to_mute_inputs(3, 2), "1,2");
+        assert_eq!(combo_to_mute_inputs(3, 2, ""), "1,2");
     }
 
     #[test]
     fn combo_to_mute_single_input_device() {
         // For 1 input, index 0 = "all", index 1 = "1"
-        assert_eq!(combo_to_mute_inputs(0, 1), "all");
-        assert_eq!(combo_to_mute_inputs(1, 1), "1");
+        assert_eq!(combo_to_mute_inputs(0, 1, ""), "all");
+        assert_eq!(combo_to_mute_inputs(1, 1, ""), "1");
+    }
+
+    #[test]
+    fn combo_items_range_selection_kept_as_custom() {
+        let c = Config {
+            mute_inputs: "1-4,7".into(),
+            ..Config::default()
+        };
+        let (items, sel) = inputs_combo_items(&c, 8);
+        assert_eq!(items[sel], "Custom (1-4,7)");
+        assert_eq!(combo_to_mute_inputs(sel, 8, &c.mute_inputs), "1-4,7");
+    }
+
+    #[test]
+    fn combo_items_out_of_range_single_is_custom() {
+        let c = Config {
+            mute_inputs: "5".into(),
+            ..Config::default()
+        };
+        let (items, sel) = inputs_combo_items(&c, 2);
+        assert_eq!(items.len(), 5);
+        assert_eq!(items[sel], "Custom (5)");
+    }
+
+    #[test]
+    fn combo_items_all_except_is_custom() {
+        let c = Config {
+            mute_inputs: "all except 2".into(),
+            ..Config::default()
+        };
+        let (items, sel) = inputs_combo_items(&c, 4);
+        assert_eq!(items[sel], "Custom (all except 2)");
+        assert_eq!(combo_to_mute_inputs(sel, 4, &c.mute_inputs), "all except 2");
     }
 
     // ── round-trip: combo index ↔ mute_inputs string ──
 
     #[test]
     fn roundtrip_all() {
-        let mute_str = combo_to_mute_inputs(0, 2);
+        let mute_str = combo_to_mute_inputs(0, 2, "");
         let c = Config {
             mute_inputs: mute_str,
             ..Config::default()
@@ -269,7 +348,7 @@ mod tests {
 
     #[test]
     fn roundtrip_specific_input() {
-        let mute_str = combo_to_mute_inputs(1, 2);
+        let mute_str = combo_to_mute_inputs(1, 2, "");
         let c = Config {
             mute_inputs: mute_str.clone(),
             ..Config::default()
@@ -282,7 +361,7 @@ mod tests {
 
     #[test]
     fn roundtrip_all_combined() {
-        let mute_str = combo_to_mute_inputs(3, 2);
+        let mute_str = combo_to_mute_inputs(3, 2, "");
         let c = Config {
             mute_inputs: mute_str,
             ..Config::default()
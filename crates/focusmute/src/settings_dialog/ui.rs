@@ -3,10 +3,14 @@
 use std::sync::{Arc, Mutex};
 
 use eframe::egui;
-use focusmute_lib::config::Config;
+use focusmute_lib::config::{Config, resolve_os_preference};
 use focusmute_lib::led;
+use focusmute_lib::schema::{self, CacheStatus};
 
-use super::{MAX_SOUND_FILE_BYTES, SoundPreviewPlayer, combo_to_mute_inputs, inputs_combo_items};
+use super::{
+    MAX_SOUND_FILE_BYTES, RefreshSchema, SoundPreviewPlayer, combo_to_mute_inputs,
+    inputs_combo_items,
+};
 
 /// Tracks which side of the color sync last changed.
 #[derive(PartialEq)]
@@ -16,11 +20,13 @@ pub(crate) enum ColorDirty {
     Picker,
 }
 
-pub struct SettingsApp {
+pub struct SettingsApp<'a> {
     // ── Form state ──
     color_text: String,
     color_rgb: [f32; 3],
     color_dirty: ColorDirty,
+    /// 0 for a custom color, else 1 + index into [`led::COLOR_PRESETS`].
+    color_preset_index: usize,
 
     hotkey: String,
 
@@ -36,6 +42,8 @@ pub struct SettingsApp {
 
     on_mute_command: String,
     on_unmute_command: String,
+    /// `mute_applications`, comma-separated.
+    mute_applications: String,
 
     // ── Sound preview ──
     preview_player: Option<SoundPreviewPlayer>,
@@ -43,6 +51,12 @@ pub struct SettingsApp {
     // ── Non-editable fields carried through ──
     original: Config,
 
+    // ── Device Data section ──
+    schema_status: Option<CacheStatus>,
+    schema_message: String,
+    /// Re-reads the schema from the device; `None` without a device.
+    refresh_schema: Option<RefreshSchema<'a>>,
+
     // ── About section (read-only) ──
     device_lines: Vec<(String, String)>,
 
@@ -56,14 +70,16 @@ pub struct SettingsApp {
     needs_resize: bool,
     /// Previous collapsible section openness — resize while animating.
     prev_advanced_openness: f32,
+    prev_data_openness: f32,
     prev_about_openness: f32,
 }
 
-impl SettingsApp {
+impl<'a> SettingsApp<'a> {
     pub fn new(
         config: Config,
         input_count: usize,
         device_lines: Vec<(String, String)>,
+        refresh_schema: Option<RefreshSchema<'a>>,
         result: Arc<Mutex<Option<Config>>>,
         cc: &eframe::CreationContext<'_>,
     ) -> Self {
@@ -74,15 +90,22 @@ impl SettingsApp {
         style.visuals.widgets.inactive.corner_radius = corner_radius;
         style.visuals.widgets.active.corner_radius = corner_radius;
         style.visuals.widgets.hovered.corner_radius = corner_radius;
+        if resolve_os_preference(&config.reduce_motion, crate::os_prefs::reduce_motion) {
+            style.animation_time = 0.0;
+        }
         cc.egui_ctx.set_style(style);
 
         let color_rgb = hex_to_rgb(&config.mute_color).unwrap_or([1.0, 0.0, 0.0]);
         let (mute_inputs_items, mute_inputs_index) = inputs_combo_items(&config, input_count);
+        let color_preset_index = led::find_color_preset(&config.color_preset)
+            .and_then(|found| led::COLOR_PRESETS.iter().position(|p| p.name == found.name))
+            .map_or(0, |i| i + 1);
 
         Self {
             color_text: config.mute_color.clone(),
             color_rgb,
             color_dirty: ColorDirty::Neither,
+            color_preset_index,
 
             hotkey: config.hotkey.clone(),
 
@@ -98,11 +121,16 @@ impl SettingsApp {
 
             on_mute_command: config.on_mute_command.clone(),
             on_unmute_command: config.on_unmute_command.clone(),
+            mute_applications: config.mute_applications.join(", "),
 
             preview_player: None,
 
             original: config,
 
+            schema_status: schema::cache_status(),
+            schema_message: String::new(),
+            refresh_schema,
+
             device_lines,
 
             errors: Vec::new(),
@@ -111,16 +139,41 @@ impl SettingsApp {
 
             needs_resize: true,
             prev_advanced_openness: -1.0,
+            prev_data_openness: -1.0,
             prev_about_openness: -1.0,
         }
     }
 
+    fn refresh_schema_cache(&mut self) {
+        let Some(refresh) = self.refresh_schema else {
+            return;
+        };
+        self.schema_message = match refresh() {
+            Ok(()) => "Schema re-read from the device".into(),
+            Err(e) => format!("Refresh failed: {e}"),
+        };
+        self.schema_status = schema::cache_status();
+    }
+
+    fn clear_schema_cache(&mut self) {
+        self.schema_message = match schema::clear_cache() {
+            Ok(_) => "Cache cleared — rebuilt on the next connect".into(),
+            Err(e) => format!("Could not clear the cache: {e}"),
+        };
+        self.schema_status = schema::cache_status();
+    }
+
     /// Try to save: validate, send result, and close on success.
     fn try_save(&mut self, ctx: &egui::Context) {
         match build_and_validate_config(&ValidateParams {
             color_dirty: &self.color_dirty,
             color_text: &self.color_text,
             color_rgb: self.color_rgb,
+            color_preset: self
+                .color_preset_index
+                .checked_sub(1)
+                .and_then(|i| led::COLOR_PRESETS.get(i))
+                .map_or("", |p| p.name),
             hotkey: &self.hotkey,
             sound_enabled: self.sound_enabled,
             autostart: self.autostart,
@@ -130,6 +183,7 @@ impl SettingsApp {
             unmute_sound_path: &self.unmute_sound_path,
             on_mute_command: &self.on_mute_command,
             on_unmute_command: &self.on_unmute_command,
+            mute_applications: &self.mute_applications,
             original: &self.original,
             max_sound_bytes: MAX_SOUND_FILE_BYTES,
         }) {
@@ -148,13 +202,14 @@ impl SettingsApp {
     }
 }
 
-impl eframe::App for SettingsApp {
+impl eframe::App for SettingsApp<'_> {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         // Height of the button area below content (separator + padding + buttons).
         const BUTTON_AREA_HEIGHT: f32 = 54.0;
 
         let mut content_bottom = 0.0_f32;
         let mut advanced_openness = 0.0_f32;
+        let mut data_openness = 0.0_f32;
         let mut about_openness = 0.0_f32;
         egui::CentralPanel::default().show(ctx, |ui| {
             // ── Mute Indicator section ──
@@ -180,26 +235,63 @@ impl eframe::App for SettingsApp {
                             });
                         ui.end_row();
 
-                        // Color row
-                        ui.label("Mute Color");
-                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            let before = self.color_rgb;
-                            ui.color_edit_button_rgb(&mut self.color_rgb);
-                            if self.color_rgb != before {
-                                self.color_dirty = ColorDirty::Picker;
-                                self.color_text = rgb_to_hex(self.color_rgb);
-                            }
+                        // Color preset row
+                        ui.label("Color Preset");
+                        let preset_label = |i: usize| match i.checked_sub(1) {
+                            Some(i) => led::COLOR_PRESETS[i].label,
+                            None => "Custom",
+                        };
+                        let before = self.color_preset_index;
+                        egui::ComboBox::from_id_salt("color_preset_combo")
+                            .selected_text(preset_label(self.color_preset_index))
+                            .show_ui(ui, |ui| {
+                                for i in 0..=led::COLOR_PRESETS.len() {
+                                    ui.selectable_value(
+                                        &mut self.color_preset_index,
+                                        i,
+                                        preset_label(i),
+                                    );
+                                }
+                            });
+                        if self.color_preset_index != before
+                            && let Some(preset) = self
+                                .color_preset_index
+                                .checked_sub(1)
+                                .and_then(|i| led::COLOR_PRESETS.get(i))
+                        {
+                            // Show the preset's mute color below
+                            self.color_text = led::format_color(preset.mute);
+                            self.color_rgb = hex_to_rgb(&self.color_text).unwrap_or(self.color_rgb);
+                            self.color_dirty = ColorDirty::Text;
+                        }
+                        ui.end_row();
 
-                            let text_response = ui.add(
-                                egui::TextEdit::singleline(&mut self.color_text)
-                                    .desired_width(ui.available_width()),
+                        // Color row — fixed by a preset
+                        ui.label("Mute Color");
+                        let custom = self.color_preset_index == 0;
+                        ui.add_enabled_ui(custom, |ui| {
+                            ui.with_layout(
+                                egui::Layout::right_to_left(egui::Align::Center),
+                                |ui| {
+                                    let before = self.color_rgb;
+                                    ui.color_edit_button_rgb(&mut self.color_rgb);
+                                    if self.color_rgb != before {
+                                        self.color_dirty = ColorDirty::Picker;
+                                        self.color_text = rgb_to_hex(self.color_rgb);
+                                    }
+
+                                    let text_response = ui.add(
+                                        egui::TextEdit::singleline(&mut self.color_text)
+                                            .desired_width(ui.available_width()),
+                                    );
+                                    if text_response.changed() {
+                                        self.color_dirty = ColorDirty::Text;
+                                        if let Some(rgb) = hex_to_rgb(&self.color_text) {
+                                            self.color_rgb = rgb;
+                                        }
+                                    }
+                                },
                             );
-                            if text_response.changed() {
-                                self.color_dirty = ColorDirty::Text;
-                                if let Some(rgb) = hex_to_rgb(&self.color_text) {
-                                    self.color_rgb = rgb;
-                                }
-                            }
                         });
                         ui.end_row();
                     });
@@ -318,10 +410,61 @@ impl eframe::App for SettingsApp {
                                         .desired_width(text_width)
                                         .hint_text("(none)"),
                                 );
+                                ui.add_space(4.0);
+                                ui.label("Mute Only These Applications");
+                                ui.add(
+                                    egui::TextEdit::singleline(&mut self.mute_applications)
+                                        .desired_width(text_width)
+                                        .hint_text("(whole microphone) e.g. Discord, obs"),
+                                );
                             });
                     });
             advanced_openness = advanced_header.openness;
 
+            // ── Device Data section (collapsible, collapsed by default) ──
+            ui.add_space(6.0);
+            let data_header =
+                egui::CollapsingHeader::new(egui::RichText::new("Device Data").strong().size(14.0))
+                    .default_open(false)
+                    .show_unindented(ui, |ui| {
+                        egui::Frame::group(ui.style())
+                            .inner_margin(egui::Margin::same(10))
+                            .show(ui, |ui| {
+                                ui.set_width(ui.available_width());
+                                ui.label("Schema Cache");
+                                let status = self
+                                    .schema_status
+                                    .as_ref()
+                                    .map_or_else(|| "(none)".to_string(), |s| s.to_string());
+                                ui.label(egui::RichText::new(status).weak());
+                                ui.add_space(4.0);
+                                ui.horizontal(|ui| {
+                                    let refresh = ui
+                                        .add_enabled(
+                                            self.refresh_schema.is_some(),
+                                            egui::Button::new("Refresh"),
+                                        )
+                                        .on_disabled_hover_text("Connect the device to refresh");
+                                    if refresh.clicked() {
+                                        self.refresh_schema_cache();
+                                        self.needs_resize = true;
+                                    }
+                                    let clear = ui.add_enabled(
+                                        self.schema_status.is_some(),
+                                        egui::Button::new("Clear"),
+                                    );
+                                    if clear.clicked() {
+                                        self.clear_schema_cache();
+                                        self.needs_resize = true;
+                                    }
+                                });
+                                if !self.schema_message.is_empty() {
+                                    ui.label(&self.schema_message);
+                                }
+                            });
+                    });
+            data_openness = data_header.openness;
+
             // ── About section (collapsible, collapsed by default) ──
             ui.add_space(6.0);
             let about_header =
@@ -411,11 +554,13 @@ impl eframe::App for SettingsApp {
         // so it reflects actual content height and doesn't depend on window
         // size — no feedback loop.
         let advanced_animating = (advanced_openness - self.prev_advanced_openness).abs() > 0.001;
+        let data_animating = (data_openness - self.prev_data_openness).abs() > 0.001;
         let about_animating = (about_openness - self.prev_about_openness).abs() > 0.001;
         self.prev_advanced_openness = advanced_openness;
+        self.prev_data_openness = data_openness;
         self.prev_about_openness = about_openness;
 
-        if self.needs_resize || advanced_animating || about_animating {
+        if self.needs_resize || advanced_animating || data_animating || about_animating {
             self.needs_resize = false;
             ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(egui::vec2(
                 440.0,
@@ -430,6 +575,8 @@ pub(crate) struct ValidateParams<'a> {
     pub color_dirty: &'a ColorDirty,
     pub color_text: &'a str,
     pub color_rgb: [f32; 3],
+    /// Selected color preset name; empty for a custom color.
+    pub color_preset: &'a str,
     pub hotkey: &'a str,
     pub sound_enabled: bool,
     pub autostart: bool,
@@ -439,6 +586,8 @@ pub(crate) struct ValidateParams<'a> {
     pub unmute_sound_path: &'a str,
     pub on_mute_command: &'a str,
     pub on_unmute_command: &'a str,
+    /// Comma-separated process names.
+    pub mute_applications: &'a str,
     pub original: &'a Config,
     pub max_sound_bytes: u64,
 }
@@ -447,7 +596,8 @@ pub(crate) struct ValidateParams<'a> {
 ///
 /// This is a pure function (no UI side effects) to enable unit testing.
 pub(crate) fn build_and_validate_config(p: &ValidateParams<'_>) -> Result<Config, Vec<String>> {
-    let mute_inputs = combo_to_mute_inputs(p.mute_inputs_index, p.input_count);
+    let mute_inputs =
+        combo_to_mute_inputs(p.mute_inputs_index, p.input_count, &p.original.mute_inputs);
 
     // Sync color from picker if that was the last change
     let color_str = if *p.color_dirty == ColorDirty::Picker {
@@ -468,7 +618,69 @@ pub(crate) fn build_and_validate_config(p: &ValidateParams<'_>) -> Result<Config
         on_mute_command: p.on_mute_command.to_string(),
         on_unmute_command: p.on_unmute_command.to_string(),
         input_colors: p.original.input_colors.clone(),
+        input_live_colors: p.original.input_live_colors.clone(),
         notifications_enabled: p.original.notifications_enabled,
+        history_enabled: p.original.history_enabled,
+        daily_summary_enabled: p.original.daily_summary_enabled,
+        startup_wait_for_device_secs: p.original.startup_wait_for_device_secs,
+        external_mute_color: p.original.external_mute_color.clone(),
+        mute_preset: p.original.mute_preset.clone(),
+        do_not_disturb: p.original.do_not_disturb.clone(),
+        reduce_motion: p.original.reduce_motion.clone(),
+        io_timeout_ms: p.original.io_timeout_ms,
+        io_retries: p.original.io_retries,
+        notify_timeout_ms: p.original.notify_timeout_ms,
+        indicator_only: p.original.indicator_only,
+        control_only: p.original.control_only,
+        excluded_capture_devices: p.original.excluded_capture_devices.clone(),
+        audio_backend: p.original.audio_backend.clone(),
+        mute_all_capture_devices: p.original.mute_all_capture_devices,
+        mute_applications: p
+            .mute_applications
+            .split(',')
+            .map(str::trim)
+            .filter(|app| !app.is_empty())
+            .map(str::to_string)
+            .collect(),
+        device_hooks: p.original.device_hooks.clone(),
+        mute_button: p.original.mute_button.clone(),
+        mute_button_sync: p.original.mute_button_sync,
+        mute_animation: p.original.mute_animation.clone(),
+        animation_speed: p.original.animation_speed,
+        busylight_enabled: p.original.busylight_enabled,
+        busylight_live_color: p.original.busylight_live_color.clone(),
+        instance_name: p.original.instance_name.clone(),
+        led_patterns: p.original.led_patterns.clone(),
+        mute_pattern: p.original.mute_pattern.clone(),
+        live_pattern: p.original.live_pattern.clone(),
+        disconnected_pattern: p.original.disconnected_pattern.clone(),
+        color_preset: p.color_preset.to_string(),
+        talk_warning_enabled: p.original.talk_warning_enabled,
+        talk_warning_threshold_db: p.original.talk_warning_threshold_db,
+        talk_warning_secs: p.original.talk_warning_secs,
+        reconnect_resync: p.original.reconnect_resync.clone(),
+        reconnect_flash_color: p.original.reconnect_flash_color.clone(),
+        in_use_color: p.original.in_use_color.clone(),
+        call_profile_enabled: p.original.call_profile_enabled,
+        call_applications: p.original.call_applications.clone(),
+        call_unmute: p.original.call_unmute,
+        call_mute_color: p.original.call_mute_color.clone(),
+        call_talk_warning: p.original.call_talk_warning,
+        output_duck_db: p.original.output_duck_db,
+        gain_up_hotkey: p.original.gain_up_hotkey.clone(),
+        gain_down_hotkey: p.original.gain_down_hotkey.clone(),
+        gain_hotkey_target: p.original.gain_hotkey_target.clone(),
+        gain_hotkey_step_db: p.original.gain_hotkey_step_db,
+        mqtt_broker: p.original.mqtt_broker.clone(),
+        mqtt_topic_prefix: p.original.mqtt_topic_prefix.clone(),
+        mqtt_username: p.original.mqtt_username.clone(),
+        mqtt_password: p.original.mqtt_password.clone(),
+        osc_target: p.original.osc_target.clone(),
+        osc_listen: p.original.osc_listen.clone(),
+        websocket_port: p.original.websocket_port,
+        websocket_token: p.original.websocket_token.clone(),
+        vu_mode: p.original.vu_mode,
+        idle_screensaver_mins: p.original.idle_screensaver_mins,
     };
 
     let input_count_opt = if p.input_count > 0 {
@@ -551,6 +763,7 @@ mod tests {
             color_dirty: &ColorDirty::Neither,
             color_text: "#FF0000",
             color_rgb: [1.0, 0.0, 0.0],
+            color_preset: "",
             hotkey: "Ctrl+Shift+M",
             sound_enabled: true,
             autostart: false,
@@ -560,6 +773,7 @@ mod tests {
             unmute_sound_path: "",
             on_mute_command: "",
             on_unmute_command: "",
+            mute_applications: "",
             original: &Config::default(),
             max_sound_bytes: 10_000_000,
         })
@@ -581,6 +795,7 @@ mod tests {
             color_dirty: &ColorDirty::Text,
             color_text: "not-a-color",
             color_rgb: [0.0, 0.0, 0.0],
+            color_preset: "",
             hotkey: "Ctrl+Shift+M",
             sound_enabled: true,
             autostart: false,
@@ -590,6 +805,7 @@ mod tests {
             unmute_sound_path: "",
             on_mute_command: "",
             on_unmute_command: "",
+            mute_applications: "",
             original: &Config::default(),
             max_sound_bytes: 10_000_000,
         });
@@ -607,6 +823,7 @@ mod tests {
             color_dirty: &ColorDirty::Neither,
             color_text: "#FF0000",
             color_rgb: [1.0, 0.0, 0.0],
+            color_preset: "",
             hotkey: "",
             sound_enabled: true,
             autostart: false,
@@ -616,6 +833,7 @@ mod tests {
             unmute_sound_path: "",
             on_mute_command: "",
             on_unmute_command: "",
+            mute_applications: "",
             original: &Config::default(),
             max_sound_bytes: 10_000_000,
         });
@@ -634,6 +852,7 @@ mod tests {
             color_dirty: &ColorDirty::Neither,
             color_text: "#FF0000",
             color_rgb: [1.0, 0.0, 0.0],
+            color_preset: "",
             hotkey: "Ctrl+Blah",
             sound_enabled: true,
             autostart: false,
@@ -643,6 +862,7 @@ mod tests {
             unmute_sound_path: "",
             on_mute_command: "",
             on_unmute_command: "",
+            mute_applications: "",
             original: &Config::default(),
             max_sound_bytes: 10_000_000,
         });
@@ -660,6 +880,7 @@ mod tests {
             color_dirty: &ColorDirty::Picker,
             color_text: "garbage-text",
             color_rgb: [0.0, 1.0, 0.0],
+            color_preset: "",
             hotkey: "Ctrl+Shift+M",
             sound_enabled: true,
             autostart: false,
@@ -669,6 +890,7 @@ mod tests {
             unmute_sound_path: "",
             on_mute_command: "",
             on_unmute_command: "",
+            mute_applications: "",
             original: &Config::default(),
             max_sound_bytes: 10_000_000,
         })
@@ -689,6 +911,7 @@ mod tests {
             color_dirty: &ColorDirty::Neither,
             color_text: "#FF0000",
             color_rgb: [1.0, 0.0, 0.0],
+            color_preset: "",
             hotkey: "Ctrl+Shift+M",
             sound_enabled: true,
             autostart: false,
@@ -698,6 +921,7 @@ mod tests {
             unmute_sound_path: "",
             on_mute_command: "",
             on_unmute_command: "",
+            mute_applications: "",
             original: &original,
             max_sound_bytes: 10_000_000,
         })
@@ -714,6 +938,7 @@ mod tests {
             color_dirty: &ColorDirty::Neither,
             color_text: "#FF0000",
             color_rgb: [1.0, 0.0, 0.0],
+            color_preset: "",
             hotkey: "Ctrl+Shift+M",
             sound_enabled: true,
             autostart: false,
@@ -723,6 +948,7 @@ mod tests {
             unmute_sound_path: "",
             on_mute_command: "echo muted",
             on_unmute_command: "echo unmuted",
+            mute_applications: "",
             original: &Config::default(),
             max_sound_bytes: 10_000_000,
         })
@@ -732,6 +958,56 @@ mod tests {
         assert_eq!(config.on_unmute_command, "echo unmuted");
     }
 
+    #[test]
+    fn build_splits_mute_applications() {
+        let config = build_and_validate_config(&ValidateParams {
+            color_dirty: &ColorDirty::Neither,
+            color_text: "#FF0000",
+            color_rgb: [1.0, 0.0, 0.0],
+            color_preset: "",
+            hotkey: "Ctrl+Shift+M",
+            sound_enabled: true,
+            autostart: false,
+            mute_inputs_index: 0,
+            input_count: 2,
+            mute_sound_path: "",
+            unmute_sound_path: "",
+            on_mute_command: "",
+            on_unmute_command: "",
+            mute_applications: " Discord, ,obs.exe ",
+            original: &Config::default(),
+            max_sound_bytes: 10_000_000,
+        })
+        .expect("should be Ok");
+
+        assert_eq!(config.mute_applications, vec!["Discord", "obs.exe"]);
+    }
+
+    #[test]
+    fn build_keeps_color_preset() {
+        let config = build_and_validate_config(&ValidateParams {
+            color_dirty: &ColorDirty::Neither,
+            color_text: "#D55E00",
+            color_rgb: [0.835, 0.369, 0.0],
+            color_preset: "deuteranopia",
+            hotkey: "Ctrl+Shift+M",
+            sound_enabled: true,
+            autostart: false,
+            mute_inputs_index: 0,
+            input_count: 2,
+            mute_sound_path: "",
+            unmute_sound_path: "",
+            on_mute_command: "",
+            on_unmute_command: "",
+            mute_applications: "",
+            original: &Config::default(),
+            max_sound_bytes: 10_000_000,
+        })
+        .expect("should be Ok");
+        assert_eq!(config.color_preset, "deuteranopia");
+        assert_eq!(led::mute_color_or_default(&config), 0xD55E_0000);
+    }
+
     #[test]
     fn hex_to_rgb_valid_hex() {
         let rgb = hex_to_rgb("#FF0000").unwrap();
@@ -32,25 +32,50 @@ fn decode_wav(wav_bytes: &[u8]) -> Option<DecodedSound> {
     })
 }
 
+/// Decode a built-in sound. A corrupt embedded asset plays silence rather
+/// than taking the tray down ([`verify_embedded`] reports it at startup).
+fn decode_builtin(wav_bytes: &[u8]) -> DecodedSound {
+    decode_wav(wav_bytes).unwrap_or_else(|| {
+        log::error!("[sound] built-in sound is corrupt, playing silence");
+        DecodedSound {
+            channels: 1,
+            sample_rate: 44_100,
+            samples: Vec::new(),
+        }
+    })
+}
+
 /// Load and decode sound from a custom path, falling back to built-in on any error.
-pub(crate) fn load_sound_data(path: &str, fallback: &'static [u8]) -> DecodedSound {
+///
+/// Returns the reason when a custom sound was replaced by the built-in, so
+/// the caller can tell the user instead of silently playing the wrong sound.
+pub(crate) fn load_sound_data(
+    path: &str,
+    fallback: &'static [u8],
+) -> (DecodedSound, Option<String>) {
     let path = path.trim();
     if path.is_empty() {
-        return decode_wav(fallback).expect("embedded WAV must be valid");
+        return (decode_builtin(fallback), None);
     }
-    match std::fs::read(path) {
+    let problem = match std::fs::read(path) {
         Ok(data) => match decode_wav(&data) {
-            Some(decoded) => decoded,
-            None => {
-                log::warn!("[sound] {path} is not a valid WAV file, using built-in");
-                decode_wav(fallback).expect("embedded WAV must be valid")
-            }
+            Some(decoded) => return (decoded, None),
+            None => format!("{path} is not a valid WAV file, using the built-in sound"),
         },
-        Err(e) => {
-            log::warn!("[sound] could not read {path}: {e}, using built-in");
-            decode_wav(fallback).expect("embedded WAV must be valid")
-        }
-    }
+        Err(e) => format!("could not read {path}: {e}, using the built-in sound"),
+    };
+    log::warn!("[sound] {problem}");
+    (decode_builtin(fallback), Some(problem))
+}
+
+/// Check that the embedded mute/unmute sounds decode. Returns one message
+/// per broken asset.
+pub(crate) fn verify_embedded() -> Vec<String> {
+    [("muted.wav", SOUND_MUTED), ("unmuted.wav", SOUND_UNMUTED)]
+        .into_iter()
+        .filter(|(_, data)| decode_wav(data).is_none())
+        .map(|(name, _)| format!("built-in sound {name} is corrupt — sounds will be silent"))
+        .collect()
 }
 
 /// Append a pre-decoded sound to an existing sink (non-blocking).
@@ -107,9 +132,15 @@ mod tests {
         assert!(decode_wav(b"this is not wav data").is_none());
     }
 
+    #[test]
+    fn embedded_assets_verify_clean() {
+        assert!(verify_embedded().is_empty());
+    }
+
     #[test]
     fn load_sound_data_empty_path_returns_decoded_builtin() {
-        let result = load_sound_data("", SOUND_MUTED);
+        let (result, warning) = load_sound_data("", SOUND_MUTED);
+        assert!(warning.is_none());
         let reference = decode_wav(SOUND_MUTED).unwrap();
         assert_eq!(result.channels, reference.channels);
         assert_eq!(result.sample_rate, reference.sample_rate);
@@ -118,13 +149,14 @@ mod tests {
 
     #[test]
     fn load_sound_data_whitespace_path_returns_builtin() {
-        let result = load_sound_data("   ", SOUND_MUTED);
+        let (result, _) = load_sound_data("   ", SOUND_MUTED);
         assert!(result.channels > 0);
     }
 
     #[test]
     fn load_sound_data_missing_file_returns_builtin() {
-        let result = load_sound_data("/nonexistent/path/sound.wav", SOUND_MUTED);
+        let (result, warning) = load_sound_data("/nonexistent/path/sound.wav", SOUND_MUTED);
+        assert!(warning.unwrap().contains("could not read"));
         let reference = decode_wav(SOUND_MUTED).unwrap();
         assert_eq!(result.samples.len(), reference.samples.len());
     }
@@ -136,7 +168,8 @@ mod tests {
         let path = dir.join("not_a_wav.wav");
         std::fs::write(&path, b"this is not a wav file").unwrap();
 
-        let result = load_sound_data(path.to_str().unwrap(), SOUND_MUTED);
+        let (result, warning) = load_sound_data(path.to_str().unwrap(), SOUND_MUTED);
+        assert!(warning.unwrap().contains("not a valid WAV file"));
         let reference = decode_wav(SOUND_MUTED).unwrap();
         assert_eq!(result.samples.len(), reference.samples.len());
 
@@ -150,7 +183,8 @@ mod tests {
         let path = dir.join("test.wav");
         std::fs::write(&path, SOUND_MUTED).unwrap();
 
-        let result = load_sound_data(path.to_str().unwrap(), SOUND_UNMUTED);
+        let (result, warning) = load_sound_data(path.to_str().unwrap(), SOUND_UNMUTED);
+        assert!(warning.is_none());
         // Should decode to the muted sound data, not the unmuted fallback
         let muted_ref = decode_wav(SOUND_MUTED).unwrap();
         assert_eq!(result.samples.len(), muted_ref.samples.len());
@@ -0,0 +1,94 @@
+//! Pluggable tray backends — the platform pieces the shared event loop drives.
+//!
+//! [`shared::run_core`](super::shared::run_core) holds the event loop once. A
+//! [`TrayBackend`] supplies what differs per platform: init, the audio
+//! monitor and its poll thread, the visible tray surface, the menu event
+//! source, and the pump/wait halves of the run loop. A new backend (macOS, a
+//! headless or OSD-only mode) implements this trait instead of copying the
+//! loop.
+
+use std::sync::Arc;
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+
+use focusmute_lib::audio::MuteMonitor;
+use focusmute_lib::config::Config;
+
+use muda::{Menu, MenuEvent, MenuEventReceiver};
+
+use super::state::{Msg, icon_live, icon_muted};
+use super::watchdog::PollLease;
+
+/// The visible tray surface that mute and status updates draw on.
+pub trait TrayIndicator {
+    /// Show the muted (`true`) or live icon.
+    fn show_muted(&self, muted: bool);
+
+    /// Replace the hover text.
+    fn show_tooltip(&self, text: &str);
+}
+
+impl TrayIndicator for tray_icon::TrayIcon {
+    fn show_muted(&self, muted: bool) {
+        let icon = if muted { icon_muted() } else { icon_live() };
+        self.set_icon(Some(icon)).ok();
+    }
+
+    fn show_tooltip(&self, text: &str) {
+        self.set_tooltip(Some(text)).ok();
+    }
+}
+
+/// Platform-specific hooks that differ between backends.
+///
+/// Each backend implements this trait once; `run_core` provides the shared
+/// event loop, config load, device open, menu build, etc.
+pub trait TrayBackend {
+    type Monitor: MuteMonitor + Send + Sync + 'static;
+    type Indicator: TrayIndicator;
+
+    /// One-time platform init (GTK, COM, etc.). Called before anything else.
+    fn platform_init() -> focusmute_lib::error::Result<()>;
+
+    /// Create the audio mute monitor on the **main thread**, skipping the
+    /// config's `excluded_capture_devices`.
+    /// Returns `None` if the audio subsystem is unavailable.
+    fn create_monitor(config: &Config) -> Option<Self::Monitor>;
+
+    /// Spawn the background polling thread.
+    /// The thread should call `monitor.wait_for_change()` / `refresh()` / `is_muted()`
+    /// and send `Msg::MutePoll` over `tx` (`Msg::Error` if it cannot start)
+    /// while `lease` is active. Each `MutePoll` is a watchdog heartbeat, so
+    /// send one at least every few seconds even when nothing changed.
+    fn spawn_poll_thread(
+        monitor: Arc<Self::Monitor>,
+        tx: mpsc::Sender<Msg>,
+        lease: PollLease,
+    ) -> JoinHandle<()>;
+
+    /// Create the tray surface showing the initial mute state, with `menu`
+    /// attached.
+    fn build_indicator(
+        initial_muted: bool,
+        menu: Menu,
+    ) -> focusmute_lib::error::Result<Self::Indicator>;
+
+    /// Where clicks on the tray menu arrive. Defaults to muda's global channel.
+    fn menu_events() -> &'static MenuEventReceiver {
+        MenuEvent::receiver()
+    }
+
+    /// Pump platform-specific events (Win32 messages, GTK iterations).
+    fn pump_events();
+
+    /// Block until the next platform event or a reasonable timeout.
+    fn wait_for_events();
+
+    /// Start watching OS notifications: send `Msg::SessionActive` when this
+    /// login session loses or regains the console (fast user switching) and
+    /// `Msg::Hotplug` when a device interface comes or goes. Returns `true`
+    /// if hotplug notifications are delivered this way. No-op by default.
+    fn spawn_os_watcher(_tx: mpsc::Sender<Msg>) -> bool {
+        false
+    }
+}
@@ -1,16 +1,19 @@
-//! Linux system tray — GTK event loop, PulseAudio monitoring.
+//! Linux system tray — GTK event loop, PulseAudio / PipeWire monitoring.
 
 use std::sync::Arc;
-use std::sync::atomic::Ordering;
 use std::sync::mpsc;
 use std::thread::JoinHandle;
 use std::time::Duration;
 
-use focusmute_lib::audio::{MuteMonitor, PulseAudioMonitor};
+use focusmute_lib::audio::{LinuxMonitor, MonitorGroup, MuteMonitor};
+use focusmute_lib::config::Config;
 
-use super::shared::{self, PlatformAdapter};
-use super::state::Msg;
-use crate::RUNNING;
+use muda::Menu;
+
+use super::backend::TrayBackend;
+use super::shared;
+use super::state::{self, Msg};
+use super::watchdog::PollLease;
 
 /// Detect if running under Wayland (global hotkeys may not work).
 fn is_wayland() -> bool {
@@ -19,10 +22,11 @@ fn is_wayland() -> bool {
         .unwrap_or(false)
 }
 
-pub struct LinuxAdapter;
+pub struct LinuxBackend;
 
-impl PlatformAdapter for LinuxAdapter {
-    type Monitor = PulseAudioMonitor;
+impl TrayBackend for LinuxBackend {
+    type Monitor = MonitorGroup<LinuxMonitor>;
+    type Indicator = tray_icon::TrayIcon;
 
     fn platform_init() -> focusmute_lib::error::Result<()> {
         gtk::init().expect("Failed to initialize GTK");
@@ -41,27 +45,52 @@ impl PlatformAdapter for LinuxAdapter {
         Ok(())
     }
 
-    fn create_monitor() -> Option<PulseAudioMonitor> {
-        PulseAudioMonitor::new().ok()
+    fn create_monitor(config: &Config) -> Option<Self::Monitor> {
+        LinuxMonitor::open_group(
+            config.audio_backend(),
+            &config.excluded_capture_devices,
+            config.mute_all_capture_devices,
+        )
+        .ok()
+        .map(|group| group.with_applications(&config.mute_applications))
     }
 
-    fn spawn_poll_thread(monitor: Arc<PulseAudioMonitor>, tx: mpsc::Sender<Msg>) -> JoinHandle<()> {
+    fn spawn_poll_thread(
+        monitor: Arc<Self::Monitor>,
+        tx: mpsc::Sender<Msg>,
+        lease: PollLease,
+    ) -> JoinHandle<()> {
         std::thread::spawn(move || {
             // Allow PulseAudio to settle before starting the poll loop.
             // Without this, the first few readings may be stale.
-            focusmute_lib::audio::stabilize_pulseaudio(&monitor);
+            monitor.stabilize();
 
-            while RUNNING.load(Ordering::SeqCst) {
+            let mut in_use = shared::InUsePoll::default();
+            while lease.is_active() {
                 monitor.wait_for_change(Duration::from_millis(250));
                 monitor.refresh();
                 let muted = monitor.is_muted();
                 if tx.send(Msg::MutePoll(muted)).is_err() {
                     break;
                 }
+                if in_use
+                    .poll(&*monitor)
+                    .into_iter()
+                    .any(|msg| tx.send(msg).is_err())
+                {
+                    break;
+                }
             }
         })
     }
 
+    fn build_indicator(
+        initial_muted: bool,
+        menu: Menu,
+    ) -> focusmute_lib::error::Result<tray_icon::TrayIcon> {
+        state::build_tray_icon(initial_muted, menu)
+    }
+
     fn pump_events() {
         while gtk::events_pending() {
             gtk::main_iteration_do(false);
@@ -76,6 +105,6 @@ impl PlatformAdapter for LinuxAdapter {
     }
 }
 
-pub fn run() -> focusmute_lib::error::Result<()> {
-    shared::run_core::<LinuxAdapter>()
+pub fn run(safe_mode: bool) -> focusmute_lib::error::Result<()> {
+    shared::run_core::<LinuxBackend>(safe_mode)
 }
@@ -1,7 +1,11 @@
-//! System tray — platform-specific event loops and shared state.
+//! System tray — pluggable platform backends, the shared event loop, and
+//! shared state.
 
+mod backend;
+mod remote;
 mod shared;
 pub(crate) mod state;
+mod watchdog;
 
 #[cfg(target_os = "linux")]
 mod linux;
@@ -49,22 +53,28 @@ fn register_aumid() {
     let _ = key.set_value("IconUri", &icon_path.to_string_lossy().to_string());
 }
 
-pub fn run() -> focusmute_lib::error::Result<()> {
+pub fn run(safe_mode: bool) -> focusmute_lib::error::Result<()> {
     #[cfg(windows)]
     register_aumid();
 
-    let instance = single_instance::SingleInstance::new("focusmute").map_err(|e| {
+    let instance_name = focusmute_lib::instance::name();
+    let lock_id = focusmute_lib::instance::lock_id();
+    let instance = single_instance::SingleInstance::new(&lock_id).map_err(|e| {
         focusmute_lib::FocusmuteError::Config(format!("Failed to create instance lock: {e}"))
     })?;
 
     if !instance.is_single() {
-        log::warn!("Another instance of FocusMute is already running.");
+        let body = match instance_name {
+            Some(name) => format!("Instance \"{name}\" is already running."),
+            None => "Another instance is already running.".to_string(),
+        };
+        log::warn!("{body}");
         let mut n = notify_rust::Notification::new();
         #[cfg(windows)]
         n.app_id(AUMID);
         #[cfg(target_os = "linux")]
         n.summary("FocusMute");
-        n.body("Another instance is already running.");
+        n.body(&body);
         let _ = n.show();
         return Ok(());
     }
@@ -72,11 +82,11 @@ pub fn run() -> focusmute_lib::error::Result<()> {
     // `instance` stays alive for the duration of run(), holding the lock.
     #[cfg(windows)]
     {
-        windows::run()
+        windows::run(safe_mode)
     }
 
     #[cfg(target_os = "linux")]
     {
-        linux::run()
+        linux::run(safe_mode)
     }
 }
@@ -0,0 +1,313 @@
+//! Remote surfaces — integrations outside the tray that follow its state and
+//! send it commands (OSC, the WebSocket control server, and MQTT with the
+//! `mqtt` build feature).
+//!
+//! [`Remote::sync`] runs once per loop iteration and publishes an
+//! [`Event`] for each change in the mute or device state; commands come back
+//! to the event loop as [`Msg::Remote`]. [`Remote::restart_if_changed`]
+//! rebuilds the surfaces when a config reload or the settings dialog changes
+//! their fields.
+
+use std::sync::mpsc::Sender;
+
+use focusmute_lib::config::Config;
+use focusmute_lib::control_server::ControlServer;
+use focusmute_lib::events::{DeviceState, Event, RemoteCommand};
+use focusmute_lib::osc::{OscListener, OscSender};
+
+use super::state::Msg;
+
+/// The device state as remote surfaces see it.
+pub(super) type DeviceStatus = (DeviceState, Option<String>);
+
+/// Remote surfaces and what they were last told.
+pub(super) struct Remote {
+    #[cfg(feature = "mqtt")]
+    mqtt: Option<focusmute_lib::mqtt::MqttClient>,
+    osc: Option<OscSender>,
+    /// Kept for its thread, which stops when this is dropped.
+    _osc_listener: Option<OscListener>,
+    websocket: Option<ControlServer>,
+    muted: Option<bool>,
+    device: Option<DeviceStatus>,
+    /// The config fields the surfaces were started from.
+    settings: RemoteSettings,
+}
+
+/// The config fields [`Remote::start`] reads.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct RemoteSettings {
+    osc_target: String,
+    osc_listen: String,
+    websocket_port: u16,
+    websocket_token: String,
+    mqtt_broker: String,
+    mqtt_topic_prefix: String,
+    mqtt_username: String,
+    mqtt_password: String,
+}
+
+impl RemoteSettings {
+    fn of(config: &Config) -> Self {
+        RemoteSettings {
+            osc_target: config.osc_target.clone(),
+            osc_listen: config.osc_listen.clone(),
+            websocket_port: config.websocket_port,
+            websocket_token: config.websocket_token.clone(),
+            mqtt_broker: config.mqtt_broker.clone(),
+            mqtt_topic_prefix: config.mqtt_topic_prefix.clone(),
+            mqtt_username: config.mqtt_username.clone(),
+            mqtt_password: config.mqtt_password.clone(),
+        }
+    }
+
+    /// Whether `config` still has these values (checked every loop
+    /// iteration, so without allocating).
+    fn matches(&self, config: &Config) -> bool {
+        self.osc_target == config.osc_target
+            && self.osc_listen == config.osc_listen
+            && self.websocket_port == config.websocket_port
+            && self.websocket_token == config.websocket_token
+            && self.mqtt_broker == config.mqtt_broker
+            && self.mqtt_topic_prefix == config.mqtt_topic_prefix
+            && self.mqtt_username == config.mqtt_username
+            && self.mqtt_password == config.mqtt_password
+    }
+}
+
+impl Remote {
+    /// Start the surfaces the config enables. Commands are sent on `tx`.
+    pub(super) fn start(config: &Config, tx: &Sender<Msg>) -> Self {
+        let command_sender = || {
+            let tx = tx.clone();
+            move |command: RemoteCommand| {
+                let _ = tx.send(Msg::Remote(command));
+            }
+        };
+        let osc = config
+            .osc_target()
+            .and_then(|(host, port)| match OscSender::new(&host, port) {
+                Ok(sender) => Some(sender),
+                Err(e) => {
+                    log::warn!("[osc] could not send to {host}:{port}: {e}");
+                    None
+                }
+            });
+        let osc_listener = config.osc_listen().and_then(|(host, port)| {
+            match OscListener::start(&host, port, command_sender()) {
+                Ok(listener) => Some(listener),
+                Err(e) => {
+                    log::warn!("[osc] could not listen on {host}:{port}: {e}");
+                    None
+                }
+            }
+        });
+        let websocket = (config.websocket_port != 0)
+            .then(|| {
+                let port = config.websocket_port;
+                let token = config.websocket_token.trim();
+                if token.is_empty() {
+                    log::warn!("[control] websocket_port is set without websocket_token — not starting the control server");
+                    return None;
+                }
+                match ControlServer::start(port, token, command_sender()) {
+                    Ok(server) => Some(server),
+                    Err(e) => {
+                        log::warn!("[control] could not listen on 127.0.0.1:{port}: {e}");
+                        None
+                    }
+                }
+            })
+            .flatten();
+        #[cfg(feature = "mqtt")]
+        let mqtt = focusmute_lib::mqtt::MqttSettings::from_config(config).and_then(|settings| {
+            match focusmute_lib::mqtt::MqttClient::start(settings, command_sender()) {
+                Ok(client) => Some(client),
+                Err(e) => {
+                    log::warn!("[mqtt] could not start the MQTT client: {e}");
+                    None
+                }
+            }
+        });
+        #[cfg(not(feature = "mqtt"))]
+        if !config.mqtt_broker.trim().is_empty() {
+            log::warn!("[mqtt] mqtt_broker is set, but this build has no MQTT support");
+        }
+        Remote {
+            #[cfg(feature = "mqtt")]
+            mqtt,
+            osc,
+            _osc_listener: osc_listener,
+            websocket,
+            muted: None,
+            device: None,
+            settings: RemoteSettings::of(config),
+        }
+    }
+
+    /// Stop and restart the surfaces if the broker, OSC addresses,
+    /// WebSocket port or token, or other fields they use changed in
+    /// `config`. The new surfaces are sent the current state on the next
+    /// [`sync`](Self::sync).
+    pub(super) fn restart_if_changed(&mut self, config: &Config, tx: &Sender<Msg>) {
+        if self.settings.matches(config) {
+            return;
+        }
+        log::info!("[remote] settings changed, restarting remote surfaces");
+        // Release the old ports before binding again
+        *self = Remote::stopped();
+        *self = Remote::start(config, tx);
+    }
+
+    /// No surfaces running.
+    fn stopped() -> Self {
+        Remote {
+            #[cfg(feature = "mqtt")]
+            mqtt: None,
+            osc: None,
+            _osc_listener: None,
+            websocket: None,
+            muted: None,
+            device: None,
+            settings: RemoteSettings::default(),
+        }
+    }
+
+    /// Whether any surface is running.
+    fn is_active(&self) -> bool {
+        #[cfg(feature = "mqtt")]
+        if self.mqtt.is_some() {
+            return true;
+        }
+        self.osc.is_some() || self.websocket.is_some()
+    }
+
+    /// Publish the mute state (`None` until it's known) and device state
+    /// (`None` when the tray doesn't use the device), if they changed.
+    pub(super) fn sync(&mut self, muted: Option<(bool, &str)>, device: Option<DeviceStatus>) {
+        if !self.is_active() {
+            return;
+        }
+        for event in self.changes(muted, device) {
+            if let Some(ref osc) = self.osc {
+                osc.send(&event);
+            }
+            if let Some(ref websocket) = self.websocket {
+                websocket.publish(event.clone());
+            }
+            #[cfg(feature = "mqtt")]
+            if let Some(ref mqtt) = self.mqtt {
+                mqtt.publish(event);
+            }
+        }
+    }
+
+    /// Events for what changed since the last call.
+    fn changes(&mut self, muted: Option<(bool, &str)>, device: Option<DeviceStatus>) -> Vec<Event> {
+        let mut events = Vec::new();
+        if let Some((muted, origin)) = muted
+            && self.muted.replace(muted) != Some(muted)
+        {
+            events.push(Event::Mute {
+                muted,
+                origin: origin.to_string(),
+            });
+        }
+        if let Some(device) = device
+            && self.device.as_ref() != Some(&device)
+        {
+            events.push(Event::device(device.0, device.1.clone()));
+            self.device = Some(device);
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remote() -> Remote {
+        Remote::stopped()
+    }
+
+    fn free_port() -> u16 {
+        std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port()
+    }
+
+    #[test]
+    fn changed_settings_restart_the_surfaces() {
+        let (tx, _rx) = std::sync::mpsc::channel();
+        let mut config = Config::default();
+        let mut remote = Remote::start(&config, &tx);
+        assert!(remote.websocket.is_none());
+
+        // Unrelated changes leave the surfaces alone
+        config.mute_color = "#00ff00".into();
+        remote.restart_if_changed(&config, &tx);
+        assert!(remote.websocket.is_none());
+
+        // No server without a token
+        config.websocket_port = free_port();
+        remote.restart_if_changed(&config, &tx);
+        assert!(remote.websocket.is_none());
+
+        config.websocket_token = "secret".into();
+        remote.restart_if_changed(&config, &tx);
+        let port = remote.websocket.as_ref().unwrap().local_addr().port();
+        assert_eq!(port, config.websocket_port);
+        remote.muted = Some(true);
+
+        // A new token rebinds the same port, and the state is sent again
+        config.websocket_token = "another-secret".into();
+        remote.restart_if_changed(&config, &tx);
+        assert_eq!(remote.websocket.as_ref().unwrap().local_addr().port(), port);
+        assert_eq!(remote.muted, None);
+
+        config.websocket_port = 0;
+        remote.restart_if_changed(&config, &tx);
+        assert!(remote.websocket.is_none());
+    }
+
+    #[test]
+    fn changes_are_published_once() {
+        let mut remote = remote();
+        let connected = || Some((DeviceState::Connected, Some("Scarlett 2i2".into())));
+        assert_eq!(
+            remote.changes(None, connected()),
+            [Event::device(
+                DeviceState::Connected,
+                Some("Scarlett 2i2".into())
+            )]
+        );
+        assert_eq!(
+            remote.changes(Some((true, "external")), connected()),
+            [Event::Mute {
+                muted: true,
+                origin: "external".into()
+            }]
+        );
+        assert!(
+            remote
+                .changes(Some((true, "focusmute")), connected())
+                .is_empty()
+        );
+        assert_eq!(
+            remote.changes(
+                Some((false, "focusmute")),
+                Some((DeviceState::Disconnected, None))
+            ),
+            [
+                Event::Mute {
+                    muted: false,
+                    origin: "focusmute".into()
+                },
+                Event::device(DeviceState::Disconnected, None)
+            ]
+        );
+    }
+}
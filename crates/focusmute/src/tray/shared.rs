@@ -1,94 +1,381 @@
 //! Shared tray event loop — extracted from the ~80% identical code in
 //! `windows.rs` and `linux.rs`. Platform-specific behavior is injected
-//! via the [`PlatformAdapter`] trait.
+//! via the [`TrayBackend`] trait.
 
 use std::sync::Arc;
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{self, Receiver};
 use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 
 use focusmute_lib::audio::MuteMonitor;
-use focusmute_lib::config::Config;
-use focusmute_lib::device::open_device_by_serial;
+use focusmute_lib::config::{Config, ValidationError};
+use focusmute_lib::device::{DeviceInfo, ScarlettDevice, open_device_with_options};
+use focusmute_lib::device_worker::DeviceWorker;
+use focusmute_lib::events::DeviceState;
+use focusmute_lib::ipc::{self, IpcRequest, IpcResponse, IpcServerHandle};
+use focusmute_lib::led;
+use focusmute_lib::monitor::MuteOrigin;
+use focusmute_lib::reconnect::{HotplugMonitor, StartupWait};
 
 use global_hotkey::GlobalHotKeyEvent;
-use muda::MenuEvent;
 
-use super::state::{self, Msg, TrayResources, TrayState};
+use super::backend::{TrayBackend, TrayIndicator};
+use super::remote::Remote;
+use super::state::{self, IpcCall, Msg, TrayResources, TrayState};
+use super::watchdog::{MAX_RESTARTS, PollFault, PollLease, PollWatchdog};
 use crate::RUNNING;
 
-/// Platform-specific hooks that differ between Windows and Linux.
-///
-/// Each platform implements this trait once; `run_core` provides the
-/// shared event loop, config load, device open, menu build, etc.
-pub trait PlatformAdapter {
-    type Monitor: MuteMonitor + Send + Sync + 'static;
+/// How long each loop iteration waits for a hardware notification. Kept short
+/// so the main loop stays responsive; backends without notification support
+/// return immediately.
+const DEVICE_EVENT_POLL_MS: u64 = 1;
+
+/// How often the OS microphone privacy switch is re-checked.
+const PRIVACY_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long the IPC thread waits for the main loop to answer a CLI request.
+const IPC_REPLY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Start the IPC server so CLI commands route device access through the tray
+/// instead of opening a second handle. Requests are forwarded to the main loop.
+fn start_ipc_server(tx: mpsc::Sender<IpcCall>) -> Option<IpcServerHandle> {
+    let path = ipc::endpoint_path()?;
+    let result = ipc::IpcServer::bind().and_then(|mut server| {
+        server.publish(&path)?;
+        server.spawn(move |request| {
+            let (reply, reply_rx) = mpsc::channel();
+            if tx.send(IpcCall { request, reply }).is_err() {
+                return IpcResponse::Error {
+                    message: "tray is shutting down".into(),
+                };
+            }
+            reply_rx
+                .recv_timeout(IPC_REPLY_TIMEOUT)
+                .unwrap_or_else(|_| IpcResponse::Error {
+                    message: "tray did not respond".into(),
+                })
+        })
+    });
+    match result {
+        Ok(handle) => Some(handle),
+        Err(e) => {
+            log::warn!("[ipc] could not start IPC server: {e}");
+            None
+        }
+    }
+}
+
+/// The running mute poll thread and the lease that can retire it.
+struct PollThread {
+    handle: JoinHandle<()>,
+    lease: PollLease,
+}
 
-    /// One-time platform init (GTK, COM, etc.). Called before anything else.
-    fn platform_init() -> focusmute_lib::error::Result<()>;
+fn spawn_poll<B: TrayBackend>(monitor: &Arc<B::Monitor>, tx: &mpsc::Sender<Msg>) -> PollThread {
+    let lease = PollLease::default();
+    PollThread {
+        handle: B::spawn_poll_thread(Arc::clone(monitor), tx.clone(), lease.clone()),
+        lease,
+    }
+}
+
+/// Restart the poll thread if the watchdog reports it dead or stalled, and
+/// tell the user. Gives up (leaving `poll` empty) after [`MAX_RESTARTS`];
+/// returns `true` when it just did.
+fn supervise_poll<B: TrayBackend>(
+    poll: &mut Option<PollThread>,
+    watchdog: &mut PollWatchdog,
+    monitor: &Arc<B::Monitor>,
+    tx: &mpsc::Sender<Msg>,
+) -> bool {
+    let now = Instant::now();
+    let Some(thread) = poll.as_ref() else {
+        return false;
+    };
+    let Some(fault) = watchdog.check(thread.handle.is_finished(), now) else {
+        return false;
+    };
+    if !watchdog.restart_due(now) {
+        return false;
+    }
+    let Some(thread) = poll.take() else {
+        return false;
+    };
+    thread.lease.revoke();
+    let fault = match fault {
+        // Join to recover the panic message; a stalled thread is left behind
+        // and exits on its own once it notices the revoked lease
+        PollFault::Exited(_) => PollFault::exited(thread.handle.join()),
+        stalled => stalled,
+    };
+    log::error!(
+        "[watchdog] audio monitor thread {fault} (restarts so far: {})",
+        watchdog.restarts()
+    );
+    if watchdog.record_restart(now) {
+        *poll = Some(spawn_poll::<B>(monitor, tx));
+        state::show_notification(&format!(
+            "Mute monitoring {fault} — restarted ({} of {MAX_RESTARTS})",
+            watchdog.restarts()
+        ));
+        false
+    } else {
+        log::error!("[watchdog] giving up after {MAX_RESTARTS} restarts");
+        state::show_notification(&format!(
+            "Mute monitoring {fault} and could not be restarted — \
+             the tray no longer follows the microphone. Restart FocusMute."
+        ));
+        true
+    }
+}
+
+/// Show the active device in the tray menu and refresh the device switcher.
+fn refresh_device_menu(tray_menu: &state::TrayMenu, active: Option<&DeviceInfo>) {
+    let devices = focusmute_lib::device::enumerate_devices();
+    tray_menu.set_devices(active, &devices);
+}
+
+/// Handle one [`Msg`] from a background thread (or the device event poll).
+fn handle_msg(
+    msg: Msg,
+    state: &mut TrayState,
+    device: &mut Option<DeviceWorker>,
+    tray: &impl TrayIndicator,
+    tray_menu: &state::TrayMenu,
+    resources: &TrayResources,
+) {
+    match msg {
+        Msg::MutePoll(muted) => {
+            let (action, device_lost) = state.process_mute_poll(muted, device.as_ref());
+            if device_lost {
+                *device = None;
+                tray_menu.set_device_connected(false);
+                refresh_device_menu(tray_menu, None);
+            }
+            state::apply_mute_ui(action, tray, tray_menu, state, resources);
+            state.first_poll_done = true;
+        }
+        Msg::DeviceEvent(events) => {
+            if let Some(dev) = device.as_ref() {
+                state.handle_device_events(&events, dev);
+            }
+            let tx = resources.msg_tx.clone();
+            focusmute_lib::hooks::run_device_hooks_with(&events, &state.config, move |outcome| {
+                let _ = tx.send(Msg::HookResult(outcome));
+            });
+        }
+        Msg::HookResult(outcome) => {
+            // The hook thread already logged the failure; surface it too
+            if let Err(e) = outcome.result
+                && state.notifications_allowed()
+            {
+                state::show_notification(&format!("Hook failed ({e}): {}", outcome.command));
+            }
+        }
+        Msg::MeterUpdate(levels) => {
+            if state.check_talk_while_muted(&levels, Instant::now())
+                && state.notifications_allowed()
+            {
+                state::show_notification("You're talking while muted");
+            }
+        }
+        Msg::Error(message) => {
+            log::error!("{message}");
+            state::show_notification(&message);
+        }
+        Msg::Hotplug(event) => state.on_hotplug(event),
+        Msg::AnimationFrame => {
+            if let Some(dev) = device.as_ref() {
+                state.draw_animation_frame(dev);
+            }
+        }
+        Msg::InUse(in_use) => {
+            if state.set_in_use(in_use, device.as_ref()) {
+                state::apply_in_use_ui(tray, tray_menu, state);
+            }
+        }
+        // Need the mute monitor — handled by the event loop
+        Msg::Capturing(_) | Msg::Remote(_) => {}
+        Msg::SessionActive(active) => {
+            if !state.set_session_active(active, device) || !state.leds_enabled() {
+                return;
+            }
+            if active {
+                log::info!("[session] console regained — reconnecting to the device");
+                tray_menu.set_device_connected(false);
+            } else {
+                log::info!("[session] console lost — releasing the device");
+                tray_menu.set_session_inactive();
+                refresh_device_menu(tray_menu, None);
+            }
+        }
+    }
+}
 
-    /// Create the audio mute monitor on the **main thread**.
-    /// Returns `None` if the audio subsystem is unavailable.
-    fn create_monitor() -> Option<Self::Monitor>;
+/// Poll-thread side of [`Msg::InUse`] and [`Msg::Capturing`]: asks the
+/// monitor whether the mic is in use, and by whom, every
+/// [`IN_USE_POLL`](focusmute_lib::audio::IN_USE_POLL) and reports changes.
+#[derive(Default)]
+pub(super) struct InUsePoll {
+    last: Option<bool>,
+    capturing: Option<Vec<String>>,
+    checked: Option<Instant>,
+}
 
-    /// Spawn the background polling thread.
-    /// The thread should call `monitor.wait_for_change()` / `refresh()` / `is_muted()`
-    /// and send `Msg::MutePoll` over `tx`.
-    fn spawn_poll_thread(monitor: Arc<Self::Monitor>, tx: mpsc::Sender<Msg>) -> JoinHandle<()>;
+impl InUsePoll {
+    /// The messages to send, for whatever changed since the last ones.
+    pub(super) fn poll(&mut self, monitor: &impl MuteMonitor) -> Vec<Msg> {
+        let now = Instant::now();
+        if self
+            .checked
+            .is_some_and(|t| now.duration_since(t) < focusmute_lib::audio::IN_USE_POLL)
+        {
+            return Vec::new();
+        }
+        self.checked = Some(now);
+        let mut msgs = Vec::new();
+        if let Some(in_use) = monitor.in_use()
+            && self.last.replace(in_use) != Some(in_use)
+        {
+            msgs.push(Msg::InUse(in_use));
+        }
+        if let Some(mut apps) = monitor.capture_applications() {
+            apps.sort();
+            apps.dedup();
+            if self.capturing.as_ref() != Some(&apps) {
+                self.capturing = Some(apps.clone());
+                msgs.push(Msg::Capturing(apps));
+            }
+        }
+        msgs
+    }
+}
 
-    /// Pump platform-specific events (Win32 messages, GTK iterations).
-    fn pump_events();
+/// Flip the OS mute state for the hotkey or the front-panel mute button.
+/// Ignored until the first poll and in indicator-only mode.
+fn toggle_mute_shortcut(state: &mut TrayState, monitor: Option<&impl MuteMonitor>) {
+    let target = !state.indicator.is_muted();
+    set_mute_shortcut(state, monitor, target);
+}
 
-    /// Block until the next platform event or a reasonable timeout.
-    fn wait_for_events();
+/// Set the OS mute state from the device (the synced mute switch). Same
+/// conditions as [`toggle_mute_shortcut`].
+fn set_mute_shortcut(state: &mut TrayState, monitor: Option<&impl MuteMonitor>, target: bool) {
+    if !state.first_poll_done || !state.mute_control_enabled() {
+        return;
+    }
+    let Some(m) = monitor else {
+        return;
+    };
+    state.indicator.expect_change(target);
+    if let Err(e) = m.set_muted(target) {
+        log::warn!("failed to set mute: {e}");
+    }
 }
 
 /// Shared tray event loop.
 ///
 /// Handles config load, device open, monitor creation, menu/icon build,
 /// background thread spawn, and the main event loop.  Platform-specific
-/// bits are injected via `P: PlatformAdapter`.
-pub fn run_core<P: PlatformAdapter>() -> focusmute_lib::error::Result<()> {
-    P::platform_init()?;
+/// bits are injected via `B: TrayBackend`.
+///
+/// With `safe_mode`, the config file is not read at all — the tray runs on
+/// `Config::default()` (no hooks, built-in sounds) and never saves settings.
+pub fn run_core<B: TrayBackend>(safe_mode: bool) -> focusmute_lib::error::Result<()> {
+    B::platform_init()?;
 
     // Open device and initialise shared state.
     // If the device isn't connected yet, start with a no-op strategy and
     // let the reconnect loop pick it up later.
-    let (config, parse_warnings) = Config::load_with_warnings();
+    let (config, parse_warnings) = if safe_mode {
+        log::warn!("Safe mode — ignoring the config file and running with defaults");
+        (Config::default(), Vec::new())
+    } else {
+        Config::load_with_warnings()
+    };
     for w in &parse_warnings {
         log::warn!("{w}");
     }
-    let (mut state, mut device) = match open_device_by_serial(&config.device_serial) {
-        Ok(dev) => {
-            let st = TrayState::init_with_config(config, &dev)?;
-            (st, Some(dev))
-        }
-        Err(e) => {
-            log::warn!("No device at startup ({e}) — starting without device");
-            (TrayState::init_without_device(config), None)
+    // On autostart the driver may not be ready yet — quietly keep retrying for
+    // `startup_wait_for_device_secs` before showing the device as disconnected.
+    let wait_secs = config.startup_wait_for_device_secs;
+    let mut startup_wait = StartupWait::new(Duration::from_secs(wait_secs));
+    // All device I/O runs on a dedicated worker thread that owns the device
+    let (mut state, mut device) = if config.control_only {
+        log::info!("Control-only mode — LEDs disabled, not opening the device");
+        startup_wait.finish();
+        (TrayState::init_without_device(config), None)
+    } else {
+        match open_device_with_options(&config.device_serial, config.device_options())
+            .map(DeviceWorker::spawn)
+        {
+            Ok(dev) => {
+                startup_wait.finish();
+                let st = TrayState::init_with_config(config, &dev)?;
+                (st, Some(dev))
+            }
+            Err(e) => {
+                if startup_wait.is_waiting() {
+                    log::info!("No device yet ({e}) — waiting up to {wait_secs}s for it");
+                } else {
+                    log::warn!("No device at startup ({e}) — starting without device");
+                }
+                (TrayState::init_without_device(config), None)
+            }
         }
     };
+    state.safe_mode = safe_mode;
+    // Before the startup check below can show the mute indicator
+    let baseline_path = (!safe_mode).then(led::LedBaseline::path).flatten();
+    state.persist_led_baseline(baseline_path, device.as_ref());
 
     // Create audio monitor on the main thread
-    let main_monitor: Option<Arc<P::Monitor>> = P::create_monitor().map(Arc::new);
+    let main_monitor: Option<Arc<B::Monitor>> = B::create_monitor(&state.config).map(Arc::new);
 
     // Check initial mute state
     let initial_muted = main_monitor.as_ref().is_some_and(|m| m.is_muted());
 
-    if initial_muted && let Some(ref dev) = device {
-        state.set_initial_muted(true, dev);
+    // Startup safety check: reconcile LEDs with the real mute state (a previous
+    // unclean exit may have left number LEDs showing the mute color).
+    if main_monitor.is_some()
+        && let Some(ref dev) = device
+    {
+        state.set_initial_muted(initial_muted, dev);
     }
+    if main_monitor.is_none()
+        && let Some(ref dev) = device
+    {
+        state.show_disconnected(dev);
+    }
+    if main_monitor.is_some() {
+        state
+            .indicators
+            .show(initial_muted, state.indicator.mute_color());
+    }
+
+    // Channel for background → main thread communication
+    let (tx, rx): (mpsc::Sender<Msg>, Receiver<Msg>) = mpsc::channel();
 
     // Init audio/hotkey resources
-    let mut resources = TrayResources::init(&state.config)?;
+    let mut resources = TrayResources::init(&state.config, tx.clone())?;
 
     // Build tray menu and icon
     let (menu, tray_menu) = state::build_tray_menu(&state.config, initial_muted);
-    let tray = state::build_tray_icon(initial_muted, menu)?;
+    let tray = B::build_indicator(initial_muted, menu)?;
+    if state.leds_enabled() {
+        refresh_device_menu(&tray_menu, device.as_ref().map(|d| d.info()));
+    } else {
+        tray_menu.set_control_only();
+    }
 
-    // If no device at startup, show disconnected status immediately
-    if device.is_none() {
-        tray_menu.set_device_connected(false);
+    // If no device at startup, show waiting (grace period) or disconnected status
+    if device.is_none() && state.leds_enabled() {
+        if startup_wait.is_waiting() {
+            tray_menu.set_device_waiting();
+        } else {
+            tray_menu.set_device_connected(false);
+        }
     }
 
     // Show startup warnings (parse errors + validation errors)
@@ -96,33 +383,88 @@ pub fn run_core<P: PlatformAdapter>() -> focusmute_lib::error::Result<()> {
         const MAX_SOUND_BYTES: u64 = 10_000_000;
         let mut all_warnings = parse_warnings;
         let input_count = state.ctx.as_ref().and_then(|c| c.input_count());
+        let mut invalid_sounds = Vec::new();
         if let Err(errs) = state.config.validate(input_count, MAX_SOUND_BYTES) {
             for e in &errs {
+                if let ValidationError::InvalidSoundPath { field, .. } = e {
+                    invalid_sounds.push(*field);
+                }
                 let msg = e.to_string();
                 log::warn!("[config] {msg}");
                 all_warnings.push(msg);
             }
         }
+        // A sound file can pass validation and still fail to decode
+        for (field, w) in std::mem::take(&mut resources.load_warnings) {
+            if !invalid_sounds.contains(&field) {
+                all_warnings.push(format!("{field}: {w}"));
+            }
+        }
+        for problem in state::verify_embedded_assets() {
+            log::error!("[assets] {problem}");
+            all_warnings.push(problem);
+        }
         if !all_warnings.is_empty() {
             state::show_startup_warnings(&all_warnings);
         }
+        if safe_mode {
+            state::show_notification(
+                "Safe mode — hooks, custom sounds, and your settings are disabled",
+            );
+        }
     }
 
-    // Channel for background → main thread communication
-    let (tx, rx): (mpsc::Sender<Msg>, Receiver<Msg>) = mpsc::channel();
+    // Session switches (release the device to another user) and, on
+    // Windows, device arrival notifications
+    let os_hotplug = B::spawn_os_watcher(tx.clone());
 
-    // Spawn background poll thread
-    let bg_handle = if let Some(ref monitor) = main_monitor {
-        Some(P::spawn_poll_thread(Arc::clone(monitor), tx))
+    // Mute animation frames — the ticker only posts them while the
+    // indicator is animating
+    let animating = Arc::new(AtomicBool::new(false));
+    {
+        let animating = Arc::clone(&animating);
+        let tx = tx.clone();
+        let ticker = led::spawn_frame_ticker(move || {
+            if animating.load(Ordering::Relaxed) && tx.send(Msg::AnimationFrame).is_err() {
+                return false;
+            }
+            RUNNING.load(Ordering::SeqCst)
+        });
+        if let Err(e) = ticker {
+            log::warn!("[led] could not start the animation thread: {e}");
+        }
+    }
+
+    // Spawn background poll thread, supervised by the watchdog
+    let mut poll = if let Some(ref monitor) = main_monitor {
+        Some(spawn_poll::<B>(monitor, &tx))
     } else {
         log::warn!("No audio monitor available — mute polling disabled");
         None
     };
+    let mut watchdog = PollWatchdog::new(Instant::now());
+
+    // IPC server for CLI commands (kept alive until run_core returns)
+    let (ipc_tx, ipc_rx) = mpsc::channel::<IpcCall>();
+    let _ipc_server = start_ipc_server(ipc_tx);
+
+    // OSC, MQTT, and other remote surfaces
+    let mut remote = Remote::start(&state.config, &tx);
 
     // Main event loop
-    let menu_rx = MenuEvent::receiver();
+    let menu_rx = B::menu_events();
     let hotkey_rx = GlobalHotKeyEvent::receiver();
-    let mut poll_thread_dead = false;
+    let mut last_privacy_check: Option<Instant> = None;
+    // "Device in use by ..." status currently shown, if any
+    let mut in_use_shown: Option<String> = None;
+    // Plug-in events skip the reconnect backoff, and with them there's no
+    // need to keep probing on a timer while the device is away
+    let hotplug = (!os_hotplug && state.leds_enabled())
+        .then(HotplugMonitor::start)
+        .flatten();
+    if os_hotplug || hotplug.is_some() {
+        state.reconnect.set_hotplug_driven(true);
+    }
 
     loop {
         if !RUNNING.load(Ordering::SeqCst) {
@@ -130,39 +472,155 @@ pub fn run_core<P: PlatformAdapter>() -> focusmute_lib::error::Result<()> {
         }
 
         // 1. Platform event pump
-        P::pump_events();
+        B::pump_events();
 
         // 2. Reconnect
+        for event in hotplug.iter().flat_map(HotplugMonitor::poll) {
+            state.on_hotplug(event);
+        }
         if device.is_none()
             && let Some(new_dev) = state.try_reconnect()
         {
+            if poll.is_none() {
+                state.show_disconnected(&new_dev);
+            }
             device = Some(new_dev);
+            startup_wait.finish();
+            in_use_shown = None;
             tray_menu.set_device_connected(true);
+            refresh_device_menu(&tray_menu, device.as_ref().map(|d| d.info()));
+        }
+        // Another application holding the device gets its own status
+        if device.is_none() {
+            let in_use = state.device_in_use();
+            if in_use != in_use_shown {
+                tray_menu.set_device_in_use(in_use.as_deref());
+                in_use_shown = in_use;
+            }
+        }
+        if device.is_none() && startup_wait.poll_expired() {
+            log::warn!("Device did not appear within {wait_secs}s — continuing without device");
+            tray_menu.set_device_connected(false);
         }
 
-        // 3. Drain mute polls (non-blocking)
-        loop {
-            match rx.try_recv() {
-                Ok(Msg::MutePoll(muted)) => {
-                    let (action, device_lost) = state.process_mute_poll(muted, device.as_ref());
-                    if device_lost {
-                        device = None;
+        // 3. Drain background messages (non-blocking)
+        while let Ok(msg) = rx.try_recv() {
+            if matches!(msg, Msg::MutePoll(_)) {
+                watchdog.beat(Instant::now());
+            }
+            // A call starting or ending may switch the mute state
+            if let Msg::Capturing(apps) = msg {
+                if let Some(muted) = state.follow_call(&apps, device.as_ref()) {
+                    set_mute_shortcut(&mut state, main_monitor.as_deref(), muted);
+                }
+                continue;
+            }
+            if let Msg::Remote(command) = msg {
+                let target = command.target(state.indicator.is_muted());
+                set_mute_shortcut(&mut state, main_monitor.as_deref(), target);
+                continue;
+            }
+            handle_msg(msg, &mut state, &mut device, &tray, &tray_menu, &resources);
+        }
+        // The channel stays open (resources hold a sender), so watch the thread
+        if let Some(ref monitor) = main_monitor
+            && supervise_poll::<B>(&mut poll, &mut watchdog, monitor, &tx)
+            && let Some(ref dev) = device
+        {
+            state.show_disconnected(dev);
+        }
+
+        // 4. CLI requests over IPC
+        let mut quit_requested = false;
+        while let Ok(call) = ipc_rx.try_recv() {
+            let response = match call.request {
+                IpcRequest::SetMute { command } => {
+                    if state.first_poll_done
+                        && state.mute_control_enabled()
+                        && main_monitor.is_some()
+                    {
+                        let target = command.target(state.indicator.is_muted());
+                        set_mute_shortcut(&mut state, main_monitor.as_deref(), target);
+                        IpcResponse::MuteState {
+                            muted: target,
+                            origin: MuteOrigin::FocusMute,
+                        }
+                    } else {
+                        IpcResponse::Error {
+                            message: "the tray isn't controlling the mute state".into(),
+                        }
+                    }
+                }
+                IpcRequest::Quit => {
+                    log::info!("quit requested over IPC");
+                    quit_requested = true;
+                    IpcResponse::Ok
+                }
+                IpcRequest::ReloadConfig => {
+                    state::reload_config(&tray_menu, &mut state, device.as_ref(), &mut resources)
+                }
+                IpcRequest::Simulate { state: sim } => {
+                    let action = state.simulate(sim, &mut device);
+                    if device.is_none() && state.leds_enabled() {
                         tray_menu.set_device_connected(false);
+                        refresh_device_menu(&tray_menu, None);
                     }
                     state::apply_mute_ui(action, &tray, &tray_menu, &state, &resources);
+                    IpcResponse::Ok
                 }
-                Err(std::sync::mpsc::TryRecvError::Empty) => break,
-                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
-                    if !poll_thread_dead {
-                        log::error!("audio monitor thread stopped unexpectedly");
-                        poll_thread_dead = true;
+                // The handle is unusable after a reset: drop it and let the
+                // reconnect below open the device again
+                IpcRequest::ResetDevice => match device.take() {
+                    Some(dev) => {
+                        let result = dev.reset();
+                        tray_menu.set_device_connected(false);
+                        refresh_device_menu(&tray_menu, None);
+                        match result {
+                            Ok(()) => {
+                                log::info!("device reset requested over IPC");
+                                IpcResponse::Ok
+                            }
+                            Err(e) => IpcResponse::Error {
+                                message: e.to_string(),
+                            },
+                        }
                     }
-                    break;
+                    None => IpcResponse::Error {
+                        message: "tray has no device connected".into(),
+                    },
+                },
+                request => state.handle_ipc(request, device.as_ref()),
+            };
+            let _ = call.reply.send(response);
+        }
+        if quit_requested {
+            RUNNING.store(false, Ordering::SeqCst);
+            break;
+        }
+
+        // 4b. Hardware events (front panel, Focusrite Control 2)
+        if let Some(ref dev) = device {
+            let events = state.watcher.poll(dev, DEVICE_EVENT_POLL_MS);
+            if !events.is_empty() {
+                // The front-panel mute button also works on the lock screen,
+                // where the hotkey never reaches us
+                if let Some(muted) = state.mute_switch_flipped(&events, dev) {
+                    set_mute_shortcut(&mut state, main_monitor.as_deref(), muted);
+                } else if state.mute_button_pressed(&events) {
+                    toggle_mute_shortcut(&mut state, main_monitor.as_deref());
                 }
+                handle_msg(
+                    Msg::DeviceEvent(events),
+                    &mut state,
+                    &mut device,
+                    &tray,
+                    &tray_menu,
+                    &resources,
+                );
             }
         }
 
-        // 4. Menu events
+        // 5. Menu events
         while let Ok(event) = menu_rx.try_recv() {
             let toggle_mute = |is_muted: bool| {
                 if let Some(ref m) = main_monitor
@@ -185,31 +643,83 @@ pub fn run_core<P: PlatformAdapter>() -> focusmute_lib::error::Result<()> {
             }
         }
 
-        // 5. Hotkey events
+        // 6. Hotkey events
         while let Ok(event) = hotkey_rx.try_recv() {
-            if event.id == resources.hotkey.id
-                && let Some(ref m) = main_monitor
-                && let Err(e) = m.set_muted(!state.indicator.is_muted())
+            if event.id == resources.hotkey.id {
+                toggle_mute_shortcut(&mut state, main_monitor.as_deref());
+            } else if event.state == global_hotkey::HotKeyState::Pressed
+                && let Some(up) = resources.hotkey.gain_direction(event.id)
+                && state.step_capture_level(up, main_monitor.as_deref(), device.as_ref())
             {
-                log::warn!("failed to toggle mute: {e}");
+                state::apply_level_ui(&tray, &tray_menu, &state);
             }
         }
 
-        // 6. Wait for events (platform-specific sleep/block)
-        P::wait_for_events();
+        // 7. Daily summary (once per local day rollover)
+        if let Some(path) = focusmute_lib::history::history_path()
+            && let Some(body) = state.take_daily_summary(focusmute_lib::history::now_unix(), &path)
+        {
+            if state.do_not_disturb() {
+                log::info!("[history] daily summary skipped (do not disturb): {body}");
+            } else {
+                state::show_notification(&body);
+            }
+        }
+
+        // 7b. OS microphone privacy switch (silently blocks capture)
+        if last_privacy_check.is_none_or(|t| t.elapsed() >= PRIVACY_CHECK_INTERVAL) {
+            last_privacy_check = Some(Instant::now());
+            if let Some(blocked) =
+                state.update_privacy_blocked(focusmute_lib::audio::microphone_access_blocked())
+            {
+                state::apply_privacy_ui(blocked, &tray, &tray_menu, &state);
+            }
+        }
+
+        // 7c. Tell remote surfaces what changed, restarting them first if a
+        // reload or the settings dialog changed their config
+        remote.restart_if_changed(&state.config, &tx);
+        let muted = (main_monitor.is_some() && state.first_poll_done).then(|| {
+            (
+                state.indicator.is_muted(),
+                state.indicator.origin().as_str(),
+            )
+        });
+        let device_status = state
+            .leds_enabled()
+            .then(|| match (&device, &in_use_shown) {
+                (Some(dev), _) => (DeviceState::Connected, Some(dev.info().model().to_string())),
+                (None, Some(holder)) => (DeviceState::InUse, Some(holder.clone())),
+                (None, None) => (DeviceState::Disconnected, None),
+            });
+        remote.sync(muted, device_status);
+
+        // 8. Batched config saves
+        state.save_config_if_due();
+
+        // 8b. Start or idle the animation ticker
+        animating.store(device.is_some() && state.is_animating(), Ordering::Relaxed);
+
+        // 9. Wait for events (platform-specific sleep/block)
+        B::wait_for_events();
     }
 
     // Cleanup — join background thread, unmute, restore LEDs, then drop monitor.
     // Joining before drop ensures the monitor is dropped on the main thread
     // (important for COM cleanup on Windows).
     RUNNING.store(false, Ordering::SeqCst);
-    if let Some(handle) = bg_handle {
-        let _ = handle.join();
+    if let Some(thread) = poll {
+        if let Some(ref monitor) = main_monitor {
+            monitor.wake();
+        }
+        let _ = thread.handle.join();
     }
 
     // Unmute all inputs so the user isn't left silently muted after exit
     // (LEDs return to normal state and can no longer indicate mute).
-    if let Some(ref monitor) = main_monitor
+    // Indicator-only mode leaves mute to whatever app owns it.
+    if state.mute_control_enabled()
+        && let Some(ref monitor) = main_monitor
         && monitor.is_muted()
         && let Err(e) = monitor.set_muted(false)
     {
@@ -220,5 +730,8 @@ pub fn run_core<P: PlatformAdapter>() -> focusmute_lib::error::Result<()> {
     if let Some(ref dev) = device {
         state.restore_on_exit(dev);
     }
+    state.discard_led_baseline();
+    state.indicators.clear();
+    state.flush_config();
     Ok(())
 }
@@ -1,47 +1,127 @@
-//! Global hotkey registration and management.
-
-use global_hotkey::{GlobalHotKeyManager, hotkey::HotKey};
-
-/// Tracks the currently registered global hotkey.
-pub struct HotkeyState {
-    pub manager: GlobalHotKeyManager,
-    pub current: HotKey,
-    pub id: u32,
-}
-
-/// Parse and register the initial global hotkey.
-pub fn register_hotkey(hotkey_str: &str) -> focusmute_lib::error::Result<HotkeyState> {
-    let manager = GlobalHotKeyManager::new().map_err(|e| {
-        focusmute_lib::FocusmuteError::Config(format!("Failed to init hotkey manager: {e}"))
-    })?;
-    let hotkey: HotKey = hotkey_str
-        .parse()
-        .unwrap_or_else(|_| "Ctrl+Shift+M".parse().unwrap());
-    let id = hotkey.id();
-    if let Err(e) = manager.register(hotkey) {
-        log::warn!("could not register hotkey '{hotkey_str}': {e}");
-    }
-    Ok(HotkeyState {
-        manager,
-        current: hotkey,
-        id,
-    })
-}
-
-/// Unregister the old hotkey and register a new one. Updates state in place.
-pub fn reregister_hotkey(hk: &mut HotkeyState, new_hotkey_str: &str) {
-    let _ = hk.manager.unregister(hk.current);
-    match new_hotkey_str.parse::<HotKey>() {
-        Ok(new_hk) => {
-            if let Err(e) = hk.manager.register(new_hk) {
-                log::warn!("[config] could not register hotkey '{new_hotkey_str}': {e}");
-            } else {
-                hk.current = new_hk;
-                hk.id = new_hk.id();
-            }
-        }
-        Err(e) => {
-            log::warn!("[config] invalid hotkey '{new_hotkey_str}': {e}");
-        }
-    }
-}
+//! Global hotkey registration and management.
+
+use global_hotkey::{GlobalHotKeyManager, hotkey::HotKey};
+
+/// Tracks the currently registered global hotkeys.
+pub struct HotkeyState {
+    pub manager: GlobalHotKeyManager,
+    pub current: HotKey,
+    pub id: u32,
+    /// `false` while the hotkey is deliberately unregistered (`indicator_only`).
+    pub active: bool,
+    /// Mic level hotkeys (`gain_up_hotkey`, `gain_down_hotkey`), if set.
+    /// They stay registered in indicator-only mode.
+    pub gain_up: Option<HotKey>,
+    pub gain_down: Option<HotKey>,
+}
+
+impl HotkeyState {
+    /// Which way a mic level hotkey steps: `Some(true)` for up,
+    /// `Some(false)` for down, `None` if `id` isn't one of them.
+    pub fn gain_direction(&self, id: u32) -> Option<bool> {
+        if self.gain_up.is_some_and(|h| h.id() == id) {
+            Some(true)
+        } else if self.gain_down.is_some_and(|h| h.id() == id) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+/// Parse and register the initial global hotkey.
+pub fn register_hotkey(hotkey_str: &str) -> focusmute_lib::error::Result<HotkeyState> {
+    let manager = GlobalHotKeyManager::new().map_err(|e| {
+        focusmute_lib::FocusmuteError::Config(format!("Failed to init hotkey manager: {e}"))
+    })?;
+    let hotkey: HotKey = hotkey_str
+        .parse()
+        .unwrap_or_else(|_| "Ctrl+Shift+M".parse().unwrap());
+    let id = hotkey.id();
+    if let Err(e) = manager.register(hotkey) {
+        log::warn!("could not register hotkey '{hotkey_str}': {e}");
+    }
+    Ok(HotkeyState {
+        manager,
+        current: hotkey,
+        id,
+        active: true,
+        gain_up: None,
+        gain_down: None,
+    })
+}
+
+/// Register the mic level hotkeys, replacing any registered before. Empty
+/// ones are left unset; invalid ones are logged and left unset.
+pub fn register_gain_hotkeys(hk: &mut HotkeyState, up: &str, down: &str) {
+    for old in [hk.gain_up.take(), hk.gain_down.take()]
+        .into_iter()
+        .flatten()
+    {
+        let _ = hk.manager.unregister(old);
+    }
+    hk.gain_up = register_optional(&hk.manager, up);
+    hk.gain_down = register_optional(&hk.manager, down);
+}
+
+fn register_optional(manager: &GlobalHotKeyManager, hotkey_str: &str) -> Option<HotKey> {
+    if hotkey_str.trim().is_empty() {
+        return None;
+    }
+    let hotkey: HotKey = match hotkey_str.parse() {
+        Ok(hotkey) => hotkey,
+        Err(e) => {
+            log::warn!("[config] invalid hotkey '{hotkey_str}': {e}");
+            return None;
+        }
+    };
+    match manager.register(hotkey) {
+        Ok(()) => Some(hotkey),
+        Err(e) => {
+            log::warn!("could not register hotkey '{hotkey_str}': {e}");
+            None
+        }
+    }
+}
+
+/// Register or unregister the current hotkey, e.g. when `indicator_only`
+/// changes. An inactive hotkey is free for other apps to use.
+pub fn set_hotkey_active(hk: &mut HotkeyState, active: bool) {
+    if hk.active == active {
+        return;
+    }
+    let result = if active {
+        hk.manager.register(hk.current)
+    } else {
+        hk.manager.unregister(hk.current)
+    };
+    if let Err(e) = result {
+        log::warn!("could not update hotkey registration: {e}");
+    }
+    hk.active = active;
+}
+
+/// Unregister the old hotkey and register a new one. Updates state in place.
+/// While inactive, the new hotkey is only remembered.
+pub fn reregister_hotkey(hk: &mut HotkeyState, new_hotkey_str: &str) {
+    if hk.active {
+        let _ = hk.manager.unregister(hk.current);
+    }
+    match new_hotkey_str.parse::<HotKey>() {
+        Ok(new_hk) if !hk.active => {
+            hk.current = new_hk;
+            hk.id = new_hk.id();
+        }
+        Ok(new_hk) => {
+            if let Err(e) = hk.manager.register(new_hk) {
+                log::warn!("[config] could not register hotkey '{new_hotkey_str}': {e}");
+            } else {
+                hk.current = new_hk;
+                hk.id = new_hk.id();
+            }
+        }
+        Err(e) => {
+            log::warn!("[config] invalid hotkey '{new_hotkey_str}': {e}");
+        }
+    }
+}
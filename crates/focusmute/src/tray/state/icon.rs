@@ -21,15 +21,28 @@ struct CachedIcon {
 }
 
 impl CachedIcon {
-    fn decode(ico_data: &[u8]) -> Self {
-        let img = decode_ico_entry(ico_data, TRAY_ICON_SIZE)
-            .expect("Failed to decode embedded icon")
-            .into_rgba8();
-        let (w, h) = img.dimensions();
-        Self {
-            rgba: img.into_raw(),
-            width: w,
-            height: h,
+    /// Decode an embedded ICO. A corrupt asset falls back to a plain square
+    /// of `fallback` (RGBA) so the tray still shows the mute state.
+    fn decode(ico_data: &[u8], fallback: [u8; 4]) -> Self {
+        match decode_ico_entry(ico_data, TRAY_ICON_SIZE) {
+            Ok(img) => {
+                let img = img.into_rgba8();
+                let (w, h) = img.dimensions();
+                Self {
+                    rgba: img.into_raw(),
+                    width: w,
+                    height: h,
+                }
+            }
+            Err(e) => {
+                log::error!("[icon] embedded tray icon is corrupt ({e}), using a plain square");
+                let size = u32::from(TRAY_ICON_SIZE);
+                Self {
+                    rgba: fallback.repeat((size * size) as usize),
+                    width: size,
+                    height: size,
+                }
+            }
         }
     }
 
@@ -77,6 +90,10 @@ fn decode_ico_entry(
 
     // Read offset and size of the chosen entry's image data
     let entry = 6 + best_idx * 16;
+    if entry + 16 > ico_data.len() {
+        // Truncated directory — not a usable ICO
+        return image::load_from_memory(ico_data);
+    }
     let data_size = u32::from_le_bytes([
         ico_data[entry + 8],
         ico_data[entry + 9],
@@ -90,7 +107,7 @@ fn decode_ico_entry(
         ico_data[entry + 15],
     ]) as usize;
 
-    if data_offset + data_size <= ico_data.len() {
+    if data_offset.saturating_add(data_size) <= ico_data.len() {
         let entry_data = &ico_data[data_offset..data_offset + data_size];
         // Individual entries are typically PNG or BMP; image crate handles both.
         image::load_from_memory(entry_data)
@@ -103,7 +120,7 @@ pub fn icon_live() -> Icon {
     use std::sync::OnceLock;
     static CACHE: OnceLock<CachedIcon> = OnceLock::new();
     CACHE
-        .get_or_init(|| CachedIcon::decode(ICON_LIVE_ICO))
+        .get_or_init(|| CachedIcon::decode(ICON_LIVE_ICO, [0x2E, 0xA0, 0x43, 0xFF]))
         .to_icon()
 }
 
@@ -111,14 +128,44 @@ pub fn icon_muted() -> Icon {
     use std::sync::OnceLock;
     static CACHE: OnceLock<CachedIcon> = OnceLock::new();
     CACHE
-        .get_or_init(|| CachedIcon::decode(ICON_MUTED_ICO))
+        .get_or_init(|| CachedIcon::decode(ICON_MUTED_ICO, [0xD0, 0x2C, 0x2C, 0xFF]))
         .to_icon()
 }
 
+/// Check that the embedded tray and window icons decode. Returns one
+/// message per broken asset.
+pub(crate) fn verify_embedded() -> Vec<String> {
+    let mut problems = Vec::new();
+    for (name, data) in [
+        ("icon-live.ico", ICON_LIVE_ICO),
+        ("icon-muted.ico", ICON_MUTED_ICO),
+    ] {
+        if let Err(e) = decode_ico_entry(data, TRAY_ICON_SIZE) {
+            problems.push(format!("built-in icon {name} is corrupt: {e}"));
+        }
+    }
+    if let Err(e) = image::load_from_memory(crate::icon::ICON_PNG) {
+        problems.push(format!("built-in icon icon-live.png is corrupt: {e}"));
+    }
+    problems
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn embedded_icons_verify_clean() {
+        assert!(verify_embedded().is_empty());
+    }
+
+    #[test]
+    fn corrupt_icon_falls_back_to_square() {
+        let icon = CachedIcon::decode(b"not an icon", [1, 2, 3, 4]);
+        assert_eq!(icon.width, u32::from(TRAY_ICON_SIZE));
+        assert_eq!(&icon.rgba[..4], &[1, 2, 3, 4]);
+    }
+
     /// Build a minimal synthetic ICO file with given entries.
     /// Each entry is `(width_byte, png_data)`. Width byte 0 means 256px.
     fn build_synthetic_ico(entries: &[(u8, &[u8])]) -> Vec<u8> {
@@ -1,150 +1,380 @@
-//! Tray menu construction, notifications, and mute-state UI updates.
-
-use focusmute_lib::config::Config;
-use focusmute_lib::monitor::MonitorAction;
-
-use muda::{Menu, MenuItem, PredefinedMenuItem};
-
-use super::icon::{icon_live, icon_muted};
-use super::{TrayResources, TrayState};
-use crate::sound;
-
-// ── Shared menu construction ──
-
-/// All menu items the tray uses, returned from `build_tray_menu`.
-pub struct TrayMenu {
-    pub status_item: MenuItem,
-    pub toggle_item: MenuItem,
-    pub settings_item: MenuItem,
-    pub reconnect_item: MenuItem,
-    pub quit_item: MenuItem,
-}
-
-impl TrayMenu {
-    /// Update menu state based on device connection status.
-    pub fn set_device_connected(&self, connected: bool) {
-        self.reconnect_item.set_enabled(!connected);
-        self.status_item
-            .set_text(if connected { "Live" } else { "Disconnected" });
-    }
-}
-
-/// Build the tray context menu with all standard items.
-pub fn build_tray_menu(config: &Config, initial_muted: bool) -> (Menu, TrayMenu) {
-    let menu = Menu::new();
-    let initial_status = if initial_muted { "Muted" } else { "Live" };
-    let status_item = MenuItem::new(initial_status, false, None);
-    let toggle_label = format!("Toggle Mute\t{}", config.hotkey);
-    let toggle_item = MenuItem::new(&toggle_label, true, None);
-    let settings_item = MenuItem::new("Settings...", true, None);
-    let reconnect_item = MenuItem::new("Reconnect Device", false, None);
-    let quit_item = MenuItem::new("Quit", true, None);
-
-    let _ = menu.append(&status_item);
-    let _ = menu.append(&PredefinedMenuItem::separator());
-    let _ = menu.append(&toggle_item);
-    let _ = menu.append(&PredefinedMenuItem::separator());
-    let _ = menu.append(&settings_item);
-    let _ = menu.append(&reconnect_item);
-    let _ = menu.append(&PredefinedMenuItem::separator());
-    let _ = menu.append(&quit_item);
-
-    (
-        menu,
-        TrayMenu {
-            status_item,
-            toggle_item,
-            settings_item,
-            reconnect_item,
-            quit_item,
-        },
-    )
-}
-
-/// Build the tray icon with the correct initial state.
-pub fn build_tray_icon(
-    initial_muted: bool,
-    menu: Menu,
-) -> focusmute_lib::error::Result<tray_icon::TrayIcon> {
-    let initial_tooltip = if initial_muted {
-        "FocusMute — Muted"
-    } else {
-        "FocusMute — Live"
-    };
-    let initial_icon = if initial_muted {
-        icon_muted()
-    } else {
-        icon_live()
-    };
-    tray_icon::TrayIconBuilder::new()
-        .with_tooltip(initial_tooltip)
-        .with_icon(initial_icon)
-        .with_menu(Box::new(menu))
-        .build()
-        .map_err(|e| {
-            focusmute_lib::FocusmuteError::Config(format!("Failed to create tray icon: {e}"))
-        })
-}
-
-/// Show startup warnings as a desktop notification.
-///
-/// Always shown regardless of `notifications_enabled` — if the config is broken,
-/// that flag itself may be wrong.
-pub(crate) fn show_startup_warnings(warnings: &[String]) {
-    if warnings.is_empty() {
-        return;
-    }
-    let body = warnings.join("\n");
-    show_notification(&format!("Config warnings:\n{body}"));
-}
-
-/// Show a desktop notification with the given body text.
-fn show_notification(body: &str) {
-    let mut n = notify_rust::Notification::new();
-    #[cfg(windows)]
-    n.app_id(crate::tray::AUMID);
-    #[cfg(target_os = "linux")]
-    n.summary("FocusMute");
-    n.body(body);
-    let _ = n.show();
-}
-
-/// Apply mute-state UI updates to the tray icon and status item.
-pub fn apply_mute_ui(
-    action: MonitorAction,
-    tray: &tray_icon::TrayIcon,
-    menu: &TrayMenu,
-    state: &TrayState,
-    resources: &TrayResources,
-) {
-    match action {
-        MonitorAction::ApplyMute => {
-            tray.set_icon(Some(icon_muted())).ok();
-            tray.set_tooltip(Some("FocusMute — Muted")).ok();
-            menu.status_item.set_text("Muted");
-            if state.config.sound_enabled
-                && let Some(ref s) = resources.sink
-            {
-                sound::play_sound(&resources.mute_sound, s);
-            }
-            if state.config.notifications_enabled {
-                show_notification("Microphone Muted");
-            }
-        }
-        MonitorAction::ClearMute => {
-            tray.set_icon(Some(icon_live())).ok();
-            tray.set_tooltip(Some("FocusMute — Live")).ok();
-            menu.status_item.set_text("Live");
-            if state.config.sound_enabled
-                && let Some(ref s) = resources.sink
-            {
-                sound::play_sound(&resources.unmute_sound, s);
-            }
-            if state.config.notifications_enabled {
-                show_notification("Microphone Live");
-            }
-        }
-        MonitorAction::NoChange => {}
-    }
-    focusmute_lib::hooks::run_action_hook(action, &state.config);
-}
+//! Tray menu construction, notifications, and mute-state UI updates.
+
+use std::cell::RefCell;
+
+use focusmute_lib::config::Config;
+use focusmute_lib::device::{DeviceInfo, DiscoveredDevice};
+use focusmute_lib::monitor::{MonitorAction, MuteOrigin};
+
+use muda::{CheckMenuItem, Menu, MenuId, MenuItem, PredefinedMenuItem, Submenu};
+
+use super::icon::{icon_live, icon_muted};
+use super::{TrayResources, TrayState};
+use crate::sound;
+use crate::tray::backend::TrayIndicator;
+
+// ── Shared menu construction ──
+
+/// Menu label for the active device: model plus serial, when known.
+pub fn device_label(info: Option<&DeviceInfo>) -> String {
+    match info {
+        Some(info) => match info.serial.as_deref() {
+            Some(serial) => format!("{} ({serial})", info.model()),
+            None => info.model().to_string(),
+        },
+        None => "No device".to_string(),
+    }
+}
+
+/// One entry of the "Switch Device" submenu.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwitchEntry {
+    pub label: String,
+    /// Serial to persist as `device_serial`; `None` if the device has no
+    /// serial and can't be selected.
+    pub serial: Option<String>,
+    pub active: bool,
+}
+
+/// Build the "Switch Device" entries. Empty unless more than one device is
+/// enumerated.
+pub fn switch_entries(
+    devices: &[DiscoveredDevice],
+    active_serial: Option<&str>,
+) -> Vec<SwitchEntry> {
+    if devices.len() < 2 {
+        return Vec::new();
+    }
+    devices
+        .iter()
+        .map(|d| {
+            let model = d.model.unwrap_or("Focusrite device");
+            let active = matches!(
+                (d.serial.as_deref(), active_serial),
+                (Some(s), Some(a)) if s.eq_ignore_ascii_case(a)
+            );
+            SwitchEntry {
+                label: match d.serial.as_deref() {
+                    Some(serial) => format!("{model} ({serial})"),
+                    None => format!("{model} (no serial)"),
+                },
+                serial: d.serial.clone(),
+                active,
+            }
+        })
+        .collect()
+}
+
+/// All menu items the tray uses, returned from `build_tray_menu`.
+pub struct TrayMenu {
+    pub device_item: MenuItem,
+    pub status_item: MenuItem,
+    pub toggle_item: MenuItem,
+    pub settings_item: MenuItem,
+    pub reconnect_item: MenuItem,
+    pub reset_item: MenuItem,
+    pub quit_item: MenuItem,
+    menu: Menu,
+    switch_menu: Submenu,
+    /// Items in `switch_menu` with the serial each one selects.
+    switch_items: RefCell<Vec<(CheckMenuItem, String)>>,
+}
+
+impl TrayMenu {
+    /// Show the active device at the top of the menu and rebuild the
+    /// "Switch Device" submenu (shown only when several devices are present).
+    pub fn set_devices(&self, active: Option<&DeviceInfo>, devices: &[DiscoveredDevice]) {
+        self.device_item.set_text(device_label(active));
+
+        let active_serial = active.and_then(|i| i.serial.as_deref());
+        let entries = switch_entries(devices, active_serial);
+        let mut items = self.switch_items.borrow_mut();
+        for (item, _) in items.drain(..) {
+            let _ = self.switch_menu.remove(&item);
+        }
+        let _ = self.menu.remove(&self.switch_menu);
+        if entries.is_empty() {
+            return;
+        }
+        for entry in entries {
+            let enabled = entry.serial.is_some() && !entry.active;
+            let item = CheckMenuItem::new(&entry.label, enabled, entry.active, None);
+            let _ = self.switch_menu.append(&item);
+            if let Some(serial) = entry.serial {
+                items.push((item, serial));
+            }
+        }
+        // Below the device header and status line
+        let _ = self.menu.insert(&self.switch_menu, 2);
+    }
+
+    /// The serial selected by a "Switch Device" menu event, if any.
+    pub fn switch_target(&self, id: &MenuId) -> Option<String> {
+        self.switch_items
+            .borrow()
+            .iter()
+            .find(|(item, _)| item.id() == id)
+            .map(|(_, serial)| serial.clone())
+    }
+
+    /// Show a neutral status while quietly waiting for the device at startup.
+    pub fn set_device_waiting(&self) {
+        self.reconnect_item.set_enabled(false);
+        self.reset_item.set_enabled(false);
+        self.status_item.set_text("Waiting for device...");
+    }
+
+    /// Control-only mode: no device is used, so there is nothing to show,
+    /// switch, or reconnect.
+    pub fn set_control_only(&self) {
+        self.device_item.set_text("LEDs off (control-only mode)");
+        self.reconnect_item.set_enabled(false);
+        self.reset_item.set_enabled(false);
+    }
+
+    /// Another user's session owns the console — the device is released to
+    /// them until this session is back.
+    pub fn set_session_inactive(&self) {
+        self.reconnect_item.set_enabled(false);
+        self.reset_item.set_enabled(false);
+        self.status_item
+            .set_text("Paused (another session is active)");
+    }
+
+    /// Update menu state based on device connection status.
+    pub fn set_device_connected(&self, connected: bool) {
+        self.reconnect_item.set_enabled(!connected);
+        self.reset_item.set_enabled(connected);
+        self.status_item
+            .set_text(if connected { "Live" } else { "Disconnected" });
+    }
+
+    /// Show that another application holds the device (`status` from
+    /// [`TrayState::device_in_use`]), or back to "Disconnected" for `None`.
+    pub fn set_device_in_use(&self, status: Option<&str>) {
+        self.status_item.set_text(status.unwrap_or("Disconnected"));
+    }
+}
+
+/// Build the tray context menu with all standard items.
+pub fn build_tray_menu(config: &Config, initial_muted: bool) -> (Menu, TrayMenu) {
+    let menu = Menu::new();
+    let device_item = MenuItem::new(device_label(None), false, None);
+    let switch_menu = Submenu::new("Switch Device", true);
+    let initial_status = if initial_muted { "Muted" } else { "Live" };
+    let status_item = MenuItem::new(initial_status, false, None);
+    let toggle_label = format!("Toggle Mute\t{}", config.hotkey);
+    // Indicator-only mode never changes the OS mute state
+    let toggle_item = MenuItem::new(&toggle_label, !config.indicator_only, None);
+    let settings_item = MenuItem::new("Settings...", true, None);
+    let reconnect_item = MenuItem::new("Reconnect Device", false, None);
+    let reset_item = MenuItem::new("Reset Device", false, None);
+    let quit_item = MenuItem::new("Quit", true, None);
+
+    let _ = menu.append(&device_item);
+    let _ = menu.append(&status_item);
+    let _ = menu.append(&PredefinedMenuItem::separator());
+    let _ = menu.append(&toggle_item);
+    let _ = menu.append(&PredefinedMenuItem::separator());
+    let _ = menu.append(&settings_item);
+    let _ = menu.append(&reconnect_item);
+    let _ = menu.append(&reset_item);
+    let _ = menu.append(&PredefinedMenuItem::separator());
+    let _ = menu.append(&quit_item);
+
+    (
+        menu.clone(),
+        TrayMenu {
+            device_item,
+            status_item,
+            toggle_item,
+            settings_item,
+            reconnect_item,
+            reset_item,
+            quit_item,
+            menu,
+            switch_menu,
+            switch_items: RefCell::new(Vec::new()),
+        },
+    )
+}
+
+/// Build the tray icon with the correct initial state.
+pub fn build_tray_icon(
+    initial_muted: bool,
+    menu: Menu,
+) -> focusmute_lib::error::Result<tray_icon::TrayIcon> {
+    let initial_tooltip = if initial_muted {
+        "FocusMute — Muted"
+    } else {
+        "FocusMute — Live"
+    };
+    let initial_icon = if initial_muted {
+        icon_muted()
+    } else {
+        icon_live()
+    };
+    tray_icon::TrayIconBuilder::new()
+        .with_tooltip(initial_tooltip)
+        .with_icon(initial_icon)
+        .with_menu(Box::new(menu))
+        .build()
+        .map_err(|e| {
+            focusmute_lib::FocusmuteError::Config(format!("Failed to create tray icon: {e}"))
+        })
+}
+
+/// Show startup warnings as a desktop notification.
+///
+/// Always shown regardless of `notifications_enabled` — if the config is broken,
+/// that flag itself may be wrong.
+pub(crate) fn show_startup_warnings(warnings: &[String]) {
+    if warnings.is_empty() {
+        return;
+    }
+    let body = warnings.join("\n");
+    show_notification(&format!("Config warnings:\n{body}"));
+}
+
+/// Show a desktop notification with the given body text.
+pub(crate) fn show_notification(body: &str) {
+    let mut n = notify_rust::Notification::new();
+    #[cfg(windows)]
+    n.app_id(crate::tray::AUMID);
+    #[cfg(target_os = "linux")]
+    n.summary("FocusMute");
+    n.body(body);
+    let _ = n.show();
+}
+
+/// Show [`TrayState::status_text`] in the tooltip and status item.
+fn set_status(tray: &impl TrayIndicator, menu: &TrayMenu, state: &TrayState) {
+    tray.show_tooltip(&state.tooltip_text());
+    menu.status_item.set_text(state.status_text());
+}
+
+/// Reflect a change in the OS microphone privacy switch. Announced like mute
+/// changes, since a blocked mic otherwise looks live.
+pub fn apply_privacy_ui(
+    blocked: bool,
+    tray: &impl TrayIndicator,
+    menu: &TrayMenu,
+    state: &TrayState,
+) {
+    set_status(tray, menu, state);
+    if state.notifications_allowed() {
+        show_notification(if blocked {
+            "Microphone blocked by privacy settings — apps can't hear you"
+        } else {
+            "Microphone access allowed again"
+        });
+    }
+}
+
+/// Reflect an application starting or stopping recording from the mic.
+pub fn apply_in_use_ui(tray: &impl TrayIndicator, menu: &TrayMenu, state: &TrayState) {
+    set_status(tray, menu, state);
+}
+
+/// Reflect a mic level hotkey changing the capture volume or gain.
+pub fn apply_level_ui(tray: &impl TrayIndicator, menu: &TrayMenu, state: &TrayState) {
+    set_status(tray, menu, state);
+}
+
+/// Apply mute-state UI updates to the tray icon and status item.
+pub fn apply_mute_ui(
+    action: MonitorAction,
+    tray: &impl TrayIndicator,
+    menu: &TrayMenu,
+    state: &TrayState,
+    resources: &TrayResources,
+) {
+    match action {
+        MonitorAction::ApplyMute => {
+            tray.show_muted(true);
+            set_status(tray, menu, state);
+            if state.config.sound_enabled
+                && let Some(ref s) = resources.sink
+            {
+                sound::play_sound(&resources.mute_sound, s);
+            }
+            if state.notifications_allowed() {
+                show_notification(match state.indicator.origin() {
+                    MuteOrigin::FocusMute => "Microphone Muted",
+                    MuteOrigin::External => "Microphone Muted (by another app)",
+                });
+            }
+        }
+        MonitorAction::ClearMute => {
+            tray.show_muted(false);
+            set_status(tray, menu, state);
+            if state.config.sound_enabled
+                && let Some(ref s) = resources.sink
+            {
+                sound::play_sound(&resources.unmute_sound, s);
+            }
+            if state.notifications_allowed() {
+                show_notification(match state.indicator.origin() {
+                    MuteOrigin::FocusMute => "Microphone Live",
+                    MuteOrigin::External => "Microphone Live (unmuted by another app)",
+                });
+            }
+        }
+        MonitorAction::NoChange => {}
+    }
+    // Hooks stay inactive until the first poll has confirmed the real mute state.
+    if state.first_poll_done {
+        let tx = resources.msg_tx.clone();
+        focusmute_lib::hooks::run_action_hook_with(
+            action,
+            state.indicator.origin(),
+            &state.config,
+            move |outcome| {
+                let _ = tx.send(super::Msg::HookResult(outcome));
+            },
+        );
+    }
+    focusmute_lib::history::record_action(action, &state.config);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn discovered(serial: Option<&str>) -> DiscoveredDevice {
+        DiscoveredDevice {
+            path: String::new(),
+            serial: serial.map(str::to_string),
+            product_id: Some(0x8219),
+            model: Some("Scarlett 2i2 4th Gen"),
+            usb_speed: None,
+            driver_version: None,
+        }
+    }
+
+    #[test]
+    fn switch_entries_hidden_for_single_device() {
+        assert!(switch_entries(&[discovered(Some("A1"))], Some("A1")).is_empty());
+    }
+
+    #[test]
+    fn switch_entries_mark_active_by_serial() {
+        let devices = [
+            discovered(Some("A1")),
+            discovered(Some("b2")),
+            discovered(None),
+        ];
+        let entries = switch_entries(&devices, Some("B2"));
+        assert_eq!(entries.len(), 3);
+        assert!(!entries[0].active);
+        assert!(entries[1].active);
+        assert_eq!(entries[1].label, "Scarlett 2i2 4th Gen (b2)");
+        assert_eq!(entries[2].serial, None);
+        assert_eq!(entries[2].label, "Scarlett 2i2 4th Gen (no serial)");
+    }
+
+    #[test]
+    fn device_label_without_device() {
+        assert_eq!(device_label(None), "No device");
+    }
+}
@@ -1,6 +1,6 @@
 //! Shared tray state and business logic — used by both Windows and Linux tray apps.
 //!
-//! Platform-specific event loops and UI code live in `windows.rs` / `linux.rs`.
+//! Platform backends live in `windows.rs` / `linux.rs` (see `backend.rs`).
 //! This module provides:
 //! - Core `TrayState` (config, indicator, reconnection)
 //! - Menu + tray icon construction (`build_tray_menu`, `build_tray_icon`)
@@ -12,16 +12,37 @@ mod hotkey;
 mod icon;
 mod menu;
 
-pub use hotkey::{HotkeyState, register_hotkey, reregister_hotkey};
-pub(crate) use menu::show_startup_warnings;
-pub use menu::{TrayMenu, apply_mute_ui, build_tray_icon, build_tray_menu};
-
-use focusmute_lib::config::Config;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+pub use hotkey::{
+    HotkeyState, register_gain_hotkeys, register_hotkey, reregister_hotkey, set_hotkey_active,
+};
+pub use icon::{icon_live, icon_muted};
+pub use menu::{
+    TrayMenu, apply_in_use_ui, apply_level_ui, apply_mute_ui, apply_privacy_ui, build_tray_icon,
+    build_tray_menu,
+};
+pub(crate) use menu::{show_notification, show_startup_warnings};
+
+use focusmute_lib::audio::MuteMonitor;
+use focusmute_lib::clock::{self, ClockStatus};
+use focusmute_lib::config::{Config, GainHotkeyTarget, SaveThrottle, resolve_os_preference};
 use focusmute_lib::context::DeviceContext;
-use focusmute_lib::device::ScarlettDevice;
+use focusmute_lib::device::{DeviceError, ScarlettDevice};
+use focusmute_lib::device_controls::{self, OutputDuck};
+use focusmute_lib::device_events::DeviceEvent;
+use focusmute_lib::device_worker::DeviceWorker;
+use focusmute_lib::events::RemoteCommand;
+use focusmute_lib::history;
+use focusmute_lib::hooks::HookOutcome;
+use focusmute_lib::indicators::Indicators;
+use focusmute_lib::ipc::{IpcRequest, IpcResponse, SimulatedState};
 use focusmute_lib::led;
-use focusmute_lib::monitor::{MonitorAction, MuteIndicator};
-use focusmute_lib::reconnect::ReconnectState;
+use focusmute_lib::monitor::{IndicatorEvent, MonitorAction, MuteIndicator, TalkDetector};
+use focusmute_lib::reconnect::{HotplugEvent, MissedMuteChanges, ReconnectResync, ReconnectState};
+use focusmute_lib::schema::VolumeMember;
+use focusmute_lib::watcher::DescriptorWatcher;
 
 use auto_launch::AutoLaunchBuilder;
 use muda::MenuEvent;
@@ -35,45 +56,124 @@ use crate::sound;
 /// The toggle-mute closure stays as a parameter since it captures
 /// platform-specific state (`main_monitor`) and can't be bundled.
 pub struct TrayResources {
+    /// Sender for background work (hooks) that reports back to the main loop.
+    pub msg_tx: std::sync::mpsc::Sender<Msg>,
     pub mute_sound: sound::DecodedSound,
     pub unmute_sound: sound::DecodedSound,
     pub hotkey: HotkeyState,
     pub sink: Option<rodio::Sink>,
     pub _audio_stream: Option<rodio::OutputStream>,
+    /// Custom sounds that fell back to the built-in, keyed by config field
+    /// (`mute_sound_path` / `unmute_sound_path`), for the startup notification.
+    pub load_warnings: Vec<(&'static str, String)>,
 }
 
 impl TrayResources {
-    pub fn init(config: &Config) -> focusmute_lib::error::Result<Self> {
+    pub fn init(
+        config: &Config,
+        msg_tx: std::sync::mpsc::Sender<Msg>,
+    ) -> focusmute_lib::error::Result<Self> {
         let (_audio_stream, sink) = sound::init_audio_output();
-        let mute_sound = sound::load_sound_data(&config.mute_sound_path, sound::SOUND_MUTED);
-        let unmute_sound = sound::load_sound_data(&config.unmute_sound_path, sound::SOUND_UNMUTED);
-        let hotkey = register_hotkey(&config.hotkey)?;
+        let (mute_sound, mute_warning) =
+            sound::load_sound_data(&config.mute_sound_path, sound::SOUND_MUTED);
+        let (unmute_sound, unmute_warning) =
+            sound::load_sound_data(&config.unmute_sound_path, sound::SOUND_UNMUTED);
+        let mut hotkey = register_hotkey(&config.hotkey)?;
+        set_hotkey_active(&mut hotkey, !config.indicator_only);
+        register_gain_hotkeys(
+            &mut hotkey,
+            &config.gain_up_hotkey,
+            &config.gain_down_hotkey,
+        );
         Ok(Self {
+            msg_tx,
             mute_sound,
             unmute_sound,
             hotkey,
             sink,
             _audio_stream,
+            load_warnings: [
+                mute_warning.map(|w| ("mute_sound_path", w)),
+                unmute_warning.map(|w| ("unmute_sound_path", w)),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
         })
     }
 }
 
+/// Startup integrity check of the embedded icons and sounds. Returns one
+/// message per broken asset (each falls back rather than failing).
+pub(crate) fn verify_embedded_assets() -> Vec<String> {
+    let mut problems = icon::verify_embedded();
+    problems.extend(sound::verify_embedded());
+    problems
+}
+
 // ── Messages from background thread ──
 
+/// Everything that reaches the main loop from another thread goes through one
+/// channel as a `Msg`, handled in `shared::handle_msg`.
 pub enum Msg {
+    /// OS microphone mute state from the audio poll thread.
     MutePoll(bool),
+    /// Decoded hardware notifications.
+    DeviceEvent(Vec<DeviceEvent>),
+    /// A mute/unmute hook command finished.
+    HookResult(HookOutcome),
+    /// Input peak levels per channel from the audio poll thread, sent while
+    /// muted (see [`MuteMonitor::peak_levels`](focusmute_lib::audio::MuteMonitor::peak_levels)).
+    #[cfg_attr(not(windows), allow(dead_code))] // only WASAPI has a meter yet
+    MeterUpdate(Vec<f32>),
+    /// A background thread failed and stopped doing its job.
+    #[cfg_attr(not(windows), allow(dead_code))] // only the WASAPI poll thread reports yet
+    Error(String),
+    /// A Focusrite device interface arrived or was removed (Windows).
+    #[cfg_attr(not(windows), allow(dead_code))] // Linux hotplug is polled
+    Hotplug(HotplugEvent),
+    /// This login session gained (`true`) or lost the console.
+    #[cfg_attr(not(windows), allow(dead_code))] // only Windows watches sessions yet
+    SessionActive(bool),
+    /// Time for the next mute animation frame.
+    AnimationFrame,
+    /// An application started (`true`) or stopped recording from the
+    /// microphone, from the audio poll thread.
+    InUse(bool),
+    /// The applications recording from the microphone changed (sorted),
+    /// from the audio poll thread.
+    Capturing(Vec<String>),
+    /// A remote surface (OSC, MQTT) asked to change the mute state.
+    Remote(RemoteCommand),
+}
+
+/// A CLI request forwarded from the IPC server thread, with its reply channel.
+pub struct IpcCall {
+    pub request: IpcRequest,
+    pub reply: std::sync::mpsc::Sender<IpcResponse>,
 }
 
 // ── Autostart ──
 
+/// Autostart entry for this instance. A named instance registers its own
+/// entry, passing `--instance-name` back if that is where the name came from.
 pub fn get_auto_launch() -> Option<auto_launch::AutoLaunch> {
     let exe = std::env::current_exe().ok()?;
     let path = exe.to_str()?;
-    AutoLaunchBuilder::new()
-        .set_app_name("FocusMute")
-        .set_app_path(path)
-        .build()
-        .ok()
+    let mut builder = AutoLaunchBuilder::new();
+    builder.set_app_path(path);
+    match focusmute_lib::instance::name() {
+        Some(name) => {
+            builder.set_app_name(&format!("FocusMute-{name}"));
+            if focusmute_lib::instance::is_from_flag() {
+                builder.set_args(&["--instance-name", name]);
+            }
+        }
+        None => {
+            builder.set_app_name("FocusMute");
+        }
+    }
+    builder.build().ok()
 }
 
 pub fn set_autostart(enabled: bool) {
@@ -87,15 +187,96 @@ pub fn set_autostart(enabled: bool) {
 
 // ── Shared tray state ──
 
+/// Write the config once settings have been still for this long...
+const CONFIG_SAVE_QUIET: Duration = Duration::from_secs(1);
+/// ...or at the latest this long after the first unsaved change.
+const CONFIG_SAVE_MAX_DELAY: Duration = Duration::from_secs(5);
+
+fn new_save_throttle() -> SaveThrottle {
+    SaveThrottle::new(CONFIG_SAVE_QUIET, CONFIG_SAVE_MAX_DELAY)
+}
+
+/// Notification text for mute changes the device missed while disconnected.
+fn missed_mutes_message(missed: MissedMuteChanges, muted: bool) -> String {
+    let now = if muted { "muted" } else { "live" };
+    let changes = if missed.count == 1 {
+        "1 mute change".to_string()
+    } else {
+        format!("{} mute changes", missed.count)
+    };
+    format!("Device reconnected — {changes} while disconnected, now {now}")
+}
+
+/// Answer a CLI device request with `f`, or an error without a device.
+fn with_device<D: ScarlettDevice>(
+    device: Option<&D>,
+    f: impl FnOnce(&D) -> focusmute_lib::device::Result<IpcResponse>,
+) -> IpcResponse {
+    let Some(dev) = device else {
+        return IpcResponse::Error {
+            message: "tray has no device connected".into(),
+        };
+    };
+    f(dev).unwrap_or_else(|e| IpcResponse::Error {
+        message: e.to_string(),
+    })
+}
+
+/// A conferencing application recording from the microphone, which put
+/// the tray into its call profile (see [`Config::call_application`]).
+pub struct ActiveCall {
+    pub application: String,
+    /// The call profile unmuted the microphone, so it's muted again when
+    /// the call ends.
+    pub unmuted: bool,
+}
+
 /// Platform-independent tray application state.
 ///
-/// Holds everything except the device (which is managed by the platform-specific
-/// `run()` function since `open_device()` returns `impl ScarlettDevice`).
+/// Holds everything except the device, which the shared event loop keeps as a
+/// [`DeviceWorker`] handle (all device I/O runs on the worker thread).
 pub struct TrayState {
     pub config: Config,
     pub indicator: MuteIndicator,
     pub reconnect: ReconnectState,
     pub ctx: Option<DeviceContext>,
+    /// Local day (days since epoch) of the last daily-summary check.
+    pub summary_day: u64,
+    /// Set once the first mute poll has been processed. Hotkey and hooks
+    /// stay inactive until then so they never act on a guessed state.
+    pub first_poll_done: bool,
+    /// Launched with `--safe-mode`: running on default config, settings are
+    /// applied for this session only and never written to disk.
+    pub safe_mode: bool,
+    /// Last clock status read from the device (sample rate, source, lock).
+    pub clock: Option<ClockStatus>,
+    /// Pending config writes — settings changes are batched so rapid edits
+    /// don't rewrite the file each time.
+    pub save_throttle: SaveThrottle,
+    /// Turns notifications (or, without them, descriptor diffs) into
+    /// [`DeviceEvent`]s, so changes from Focusrite Control 2 are noticed.
+    pub watcher: DescriptorWatcher,
+    /// OS privacy settings deny microphone access (Windows "Microphone
+    /// access" off) — capture is silent while the mic reads "live".
+    pub privacy_blocked: bool,
+    /// This login session owns the console. Cleared while another user is
+    /// switched in, so only their tray talks to the device.
+    pub session_active: bool,
+    /// Synthetic state injected by `focusmute-cli simulate`. Real mute polls
+    /// are ignored while set, and reconnects while it is `Disconnect`.
+    pub simulation: Option<SimulatedState>,
+    /// Extra mute indicators (busylight) that mirror every mute change.
+    pub indicators: Indicators,
+    /// Where the LED baseline is persisted (`None` = memory only).
+    pub led_baseline_path: Option<PathBuf>,
+    /// Notices talking while muted from the input meter.
+    pub talk_detector: TalkDetector,
+    /// The call in progress, while the call profile applies.
+    pub call: Option<ActiveCall>,
+    /// Output volumes lowered while muted (`output_duck_db`), to put back.
+    pub output_duck: Option<OutputDuck>,
+    /// The mic level a volume hotkey last set, for the tooltip.
+    pub capture_level: Option<String>,
 }
 
 impl TrayState {
@@ -120,14 +301,39 @@ impl TrayState {
             log::warn!("[config] {w}");
         }
 
-        let indicator = MuteIndicator::new(2, false, init_mute_color, strategy);
+        let mut indicator = MuteIndicator::new(2, false, init_mute_color, strategy);
+        indicator.set_external_mute_color(led::external_mute_color(&config));
+        indicator.set_in_use_color(led::in_use_color(&config));
+        indicator.set_animation(
+            config.mute_animation(crate::os_prefs::reduce_motion),
+            config.animation_speed,
+        );
+        let clock = clock::read_clock_status(device, ctx.schema.as_ref()).ok();
+        let watcher = DescriptorWatcher::new(ctx.schema.as_ref());
 
-        Ok(TrayState {
+        let mut state = TrayState {
+            indicators: Indicators::from_config(&config),
+            talk_detector: config.talk_detector(),
             config,
             indicator,
             reconnect: ReconnectState::with_defaults(),
             ctx: Some(ctx),
-        })
+            summary_day: history::local_day(history::now_unix()),
+            first_poll_done: false,
+            safe_mode: false,
+            clock,
+            save_throttle: new_save_throttle(),
+            watcher,
+            privacy_blocked: false,
+            session_active: true,
+            simulation: None,
+            led_baseline_path: None,
+            call: None,
+            output_duck: None,
+            capture_level: None,
+        };
+        state.take_led_baseline(device);
+        Ok(state)
     }
 
     /// Initialize without a device — uses a no-op strategy (empty LED vectors).
@@ -143,14 +349,37 @@ impl TrayState {
             mute_colors: vec![],
             selected_color: 0,
             unselected_color: 0,
+            group_leds: Vec::new(),
+            patterns: led::LedPatterns::default(),
         };
-        let indicator = MuteIndicator::new(2, false, init_mute_color, noop_strategy);
+        let mut indicator = MuteIndicator::new(2, false, init_mute_color, noop_strategy);
+        indicator.set_external_mute_color(led::external_mute_color(&config));
+        indicator.set_in_use_color(led::in_use_color(&config));
+        indicator.set_animation(
+            config.mute_animation(crate::os_prefs::reduce_motion),
+            config.animation_speed,
+        );
 
         TrayState {
+            indicators: Indicators::from_config(&config),
+            talk_detector: config.talk_detector(),
             config,
             indicator,
             reconnect: ReconnectState::with_defaults(),
             ctx: None,
+            summary_day: history::local_day(history::now_unix()),
+            first_poll_done: false,
+            safe_mode: false,
+            clock: None,
+            save_throttle: new_save_throttle(),
+            watcher: DescriptorWatcher::new(None),
+            privacy_blocked: false,
+            session_active: true,
+            simulation: None,
+            led_baseline_path: None,
+            call: None,
+            output_duck: None,
+            capture_level: None,
         }
     }
 
@@ -174,24 +403,348 @@ impl TrayState {
         }
 
         self.indicator.set_strategy(strategy);
+        if self.call.is_some() {
+            self.resolve_call_colors();
+        }
+        self.clock = clock::read_clock_status(device, ctx.schema.as_ref()).ok();
+        self.watcher = DescriptorWatcher::new(ctx.schema.as_ref());
         self.ctx = Some(ctx);
+        self.take_led_baseline(device);
         Ok(warnings)
     }
 
+    /// Read the LED baseline restores return to, before anything is muted
+    /// (see [`led::LedBaseline`]).
+    fn take_led_baseline(&mut self, device: &impl ScarlettDevice) {
+        let Some(ctx) = self.ctx.as_ref() else {
+            return;
+        };
+        let baseline =
+            led::LedBaseline::load_or_capture(device, ctx, self.led_baseline_path.as_deref());
+        if let Err(e) = &baseline {
+            log::warn!("[led] could not read the LED baseline: {e}");
+        }
+        self.indicator.set_baseline(baseline.ok());
+    }
+
+    /// Persist the LED baseline at `path` from here on. A baseline an unclean
+    /// exit left there is reused, so its colors win over the leftover LEDs.
+    pub fn persist_led_baseline(
+        &mut self,
+        path: Option<PathBuf>,
+        device: Option<&impl ScarlettDevice>,
+    ) {
+        self.led_baseline_path = path;
+        if let Some(dev) = device {
+            self.take_led_baseline(dev);
+        }
+    }
+
+    /// Delete the persisted baseline after the final restore.
+    pub fn discard_led_baseline(&self) {
+        if let Some(path) = &self.led_baseline_path {
+            led::LedBaseline::remove_saved(path);
+        }
+    }
+
     /// Apply initial mute state (call after audio monitor is ready).
     ///
     /// Syncs the debouncer to the known state so subsequent polls won't
-    /// trigger a spurious ApplyMute/ClearMute event.
+    /// trigger a spurious ApplyMute/ClearMute event, and runs the startup
+    /// safety check so LEDs left red by an unclean exit are restored.
     pub fn set_initial_muted(&mut self, muted: bool, device: &impl ScarlettDevice) {
-        self.indicator.force_state(muted);
-        if muted {
-            let _ = self.indicator.apply_mute(device);
+        match self.indicator.reconcile_startup(muted, device) {
+            Ok(led::StartupReconcile::Consistent) => {}
+            Ok(led::StartupReconcile::RestoredLeftover) => {
+                log::info!("[startup] mic is live — restored leftover mute LEDs");
+            }
+            Ok(led::StartupReconcile::ReappliedMute) => {
+                log::info!("[startup] mic is muted — re-applied mute indicator");
+            }
+            Err(e) => log::warn!("[startup] LED safety check failed: {e}"),
+        }
+    }
+
+    /// Paint the `disconnected_pattern`, if any — the device is there but
+    /// the mute state can't be read.
+    pub fn show_disconnected(&self, device: &impl ScarlettDevice) {
+        let commands = self.indicator.disconnected_commands();
+        if !commands.is_empty()
+            && let Err(e) = led::execute(device, &commands)
+        {
+            log::warn!("[led] could not show the disconnected pattern: {e}");
+        }
+    }
+
+    /// The OS is in do-not-disturb / Focus Assist, or `do_not_disturb` forces it.
+    pub fn do_not_disturb(&self) -> bool {
+        resolve_os_preference(&self.config.do_not_disturb, crate::os_prefs::do_not_disturb)
+    }
+
+    /// Whether mute-change and hook-failure notifications should be shown.
+    pub fn notifications_allowed(&self) -> bool {
+        self.config.notifications_enabled && !self.do_not_disturb()
+    }
+
+    /// Record the OS microphone privacy state (`None` = unknown, treated as
+    /// not blocked). Returns the new state if it changed.
+    pub fn update_privacy_blocked(&mut self, blocked: Option<bool>) -> Option<bool> {
+        let blocked = blocked.unwrap_or(false);
+        if blocked == self.privacy_blocked {
+            return None;
+        }
+        if blocked {
+            log::warn!("[audio] microphone access is blocked by OS privacy settings");
+        } else {
+            log::info!("[audio] microphone access allowed again by OS privacy settings");
+        }
+        self.privacy_blocked = blocked;
+        Some(blocked)
+    }
+
+    /// Whether FocusMute may change the OS mute state (hotkey, Toggle Mute,
+    /// unmute on exit). `false` in indicator-only mode.
+    pub fn mute_control_enabled(&self) -> bool {
+        !self.config.indicator_only
+    }
+
+    /// Whether `events` include a press of the configured `mute_button`.
+    /// Never with a synced [`mute_switch`](Self::mute_switch), whose state
+    /// is the mute state rather than a toggle.
+    pub fn mute_button_pressed(&self, events: &[DeviceEvent]) -> bool {
+        self.mute_switch().is_none()
+            && DeviceEvent::from_button_name(&self.config.mute_button)
+                .is_some_and(|button| events.contains(&button))
+    }
+
+    /// The front-panel switch kept in step with mute, when
+    /// `mute_button_sync` is on and the device's schema has it.
+    pub fn mute_switch(&self) -> Option<device_controls::MuteSwitch> {
+        if !self.config.mute_button_sync {
+            return None;
+        }
+        let schema = self.ctx.as_ref().and_then(|c| c.schema.as_ref());
+        device_controls::MuteSwitch::for_button(&self.config.mute_button, schema)
+    }
+
+    /// The mute state the user picked by flipping the synced switch on the
+    /// device — `None` unless `events` include it and it now disagrees with
+    /// the indicator.
+    pub fn mute_switch_flipped(
+        &self,
+        events: &[DeviceEvent],
+        device: &impl ScarlettDevice,
+    ) -> Option<bool> {
+        let switch = self.mute_switch()?;
+        if !events.contains(&switch.event()) {
+            return None;
+        }
+        match switch.get(device) {
+            Ok(on) => (on != self.indicator.is_muted()).then_some(on),
+            Err(e) => {
+                log::warn!("could not read the mute switch: {e}");
+                None
+            }
+        }
+    }
+
+    /// Flip the synced switch to match the mute state.
+    pub fn sync_mute_switch(&self, device: &impl ScarlettDevice) {
+        let Some(switch) = self.mute_switch() else {
+            return;
+        };
+        match switch.set(device, self.indicator.is_muted()) {
+            Ok(true) => log::debug!("[device] mute switch set to {}", self.indicator.is_muted()),
+            Ok(false) => {}
+            Err(e) => log::warn!("could not set the mute switch: {e}"),
+        }
+    }
+
+    /// The device's output volume control, if it has one.
+    fn output_volume_control(&self) -> Option<VolumeMember> {
+        device_controls::output_volume_control(self.ctx.as_ref()?.schema.as_ref())
+    }
+
+    /// Lower the outputs while muted, or put them back, per
+    /// `output_duck_db`. A failed restore is retried on the next call.
+    pub fn sync_output_duck(&mut self, device: &impl ScarlettDevice) {
+        let duck = self.indicator.is_muted()
+            && self.config.output_duck_db > 0
+            && self.simulation.is_none();
+        let Some(control) = self.output_volume_control() else {
+            return;
+        };
+        match &self.output_duck {
+            None if duck => {
+                match device_controls::duck_outputs(device, &control, self.config.output_duck_db) {
+                    Ok(saved) => {
+                        log::info!(
+                            "[duck] outputs lowered by {} dB",
+                            self.config.output_duck_db
+                        );
+                        self.output_duck = Some(saved);
+                    }
+                    Err(e) => log::warn!("could not lower the outputs: {e}"),
+                }
+            }
+            Some(saved) if !duck => match saved.restore(device, &control) {
+                Ok(restored) => {
+                    log::info!("[duck] {restored} output(s) restored");
+                    self.output_duck = None;
+                }
+                Err(e) => log::warn!("could not restore the output volume: {e}"),
+            },
+            _ => {}
+        }
+    }
+
+    /// Whether the device is used at all. `false` in control-only mode, where
+    /// the tray never opens it and leaves the LEDs alone.
+    pub fn leds_enabled(&self) -> bool {
+        !self.config.control_only
+    }
+
+    /// Status line for the tray menu and tooltip.
+    pub fn status_text(&self) -> &'static str {
+        if self.indicator.is_muted() {
+            "Muted"
+        } else if self.privacy_blocked {
+            "Blocked by privacy settings"
+        } else if self.indicator.is_in_use() {
+            "Live — in use"
+        } else {
+            "Live"
+        }
+    }
+
+    /// Tooltip of the tray icon: the status, plus the mic level once a
+    /// volume hotkey has changed it.
+    pub fn tooltip_text(&self) -> String {
+        match &self.capture_level {
+            Some(level) => format!("FocusMute — {} ({level})", self.status_text()),
+            None => format!("FocusMute — {}", self.status_text()),
+        }
+    }
+
+    /// Step the mic level for a volume hotkey: the OS capture volume
+    /// through `monitor`, or the preamp gain of the muted inputs on
+    /// `device` (`gain_hotkey_target`). Returns `true` if it changed.
+    pub fn step_capture_level(
+        &mut self,
+        up: bool,
+        monitor: Option<&impl MuteMonitor>,
+        device: Option<&impl ScarlettDevice>,
+    ) -> bool {
+        let step = self.config.gain_hotkey_step_db;
+        let level = match self.config.gain_hotkey_target() {
+            GainHotkeyTarget::Os => match monitor {
+                Some(m) => {
+                    let step_db = if up {
+                        f64::from(step)
+                    } else {
+                        -f64::from(step)
+                    };
+                    m.adjust_capture_volume(step_db)
+                        .map(|db| format!("mic volume {db:.0} dB"))
+                        .map_err(|e| e.to_string())
+                }
+                None => Err("no capture device".into()),
+            },
+            GainHotkeyTarget::Hardware => self.step_gain(up, step, device),
+        };
+        match level {
+            Ok(level) => {
+                log::info!("[gain] {level}");
+                self.capture_level = Some(level);
+                true
+            }
+            Err(e) => {
+                log::warn!("could not change the mic level: {e}");
+                false
+            }
+        }
+    }
+
+    /// Step the preamp gain of the inputs the mute indicator covers.
+    fn step_gain(
+        &self,
+        up: bool,
+        step: u32,
+        device: Option<&impl ScarlettDevice>,
+    ) -> Result<String, String> {
+        let dev = device.ok_or("no device connected")?;
+        let schema = self.ctx.as_ref().and_then(|c| c.schema.as_ref());
+        let control = device_controls::gain_control(schema)
+            .ok_or("this model has no software gain control")?;
+        let step = u8::try_from(step).unwrap_or(u8::MAX);
+        let mut gains = Vec::new();
+        for &input in &self.indicator.strategy().input_indices {
+            let gain =
+                device_controls::get_gain(dev, &control, input).map_err(|e| e.to_string())?;
+            let target = if up {
+                gain.saturating_add(step)
+            } else {
+                gain.saturating_sub(step)
+            };
+            let set = device_controls::set_gain(dev, &control, input, target)
+                .map_err(|e| e.to_string())?;
+            gains.push(set.to_string());
+        }
+        if gains.is_empty() {
+            return Err("no inputs to adjust".into());
+        }
+        Ok(format!("gain {}", gains.join("/")))
+    }
+
+    /// Build the daily summary notification text once the local day rolls
+    /// over (local midnight).
+    ///
+    /// Returns `None` if the day hasn't changed, the summary is disabled, or
+    /// nothing was recorded for the previous day.
+    pub fn take_daily_summary(&mut self, now: u64, history_path: &Path) -> Option<String> {
+        let today = history::local_day(now);
+        if today <= self.summary_day {
+            return None;
+        }
+        self.summary_day = today;
+        if !self.config.daily_summary_enabled || !self.config.history_enabled {
+            return None;
+        }
+        let events = match history::load_from(history_path) {
+            Ok(ev) => ev,
+            Err(e) => {
+                log::warn!("[history] could not read {}: {e}", history_path.display());
+                return None;
+            }
+        };
+        let summary = history::summarize_local_day(&events, today - 1);
+        if summary == history::Summary::default() {
+            return None;
+        }
+        Some(format!("Yesterday: {summary}"))
+    }
+
+    /// Status text while another application holds the device exclusively,
+    /// e.g. "Device in use by ableton (PID 42)".
+    pub fn device_in_use(&self) -> Option<String> {
+        match self.reconnect.last_error() {
+            Some(e @ DeviceError::InUse(_)) => Some(e.to_string()),
+            _ => None,
         }
     }
 
     /// Reset the reconnection backoff so the next attempt happens immediately.
     pub fn reset_backoff(&mut self) {
-        self.reconnect = ReconnectState::with_defaults();
+        self.reconnect.reset();
+    }
+
+    /// Handle a hotplug event — a Focusrite device arriving retries the
+    /// connection right away.
+    pub fn on_hotplug(&mut self, event: HotplugEvent) {
+        if event == HotplugEvent::Arrived {
+            log::info!("[hotplug] Focusrite device connected — retrying");
+            self.reconnect.retry_now();
+        }
     }
 
     /// Attempt device reconnection with backoff + LED state refresh.
@@ -202,22 +755,51 @@ impl TrayState {
     /// real strategy.
     ///
     /// Returns the new device on success, `None` if not ready or failed.
-    pub fn try_reconnect(&mut self) -> Option<focusmute_lib::device::PlatformDevice> {
+    pub fn try_reconnect(&mut self) -> Option<DeviceWorker> {
+        if !self.leds_enabled()
+            || !self.session_active
+            || self.simulation == Some(SimulatedState::Disconnect)
+        {
+            return None;
+        }
         if self.ctx.is_some() {
             // Normal reconnect: device was previously connected, strategy is valid.
+            self.reconnect
+                .set_flash_color(led::reconnect_flash_color(&self.config));
             focusmute_lib::reconnect::try_reconnect_and_refresh(
                 &mut self.reconnect,
                 self.indicator.strategy(),
                 self.indicator.mute_color(),
                 self.indicator.is_muted(),
                 &self.config.device_serial,
+                self.config.device_options(),
+                self.config.reconnect_resync(),
             )
+            .map(|(dev, missed)| {
+                // Values may have changed while unplugged — take a new baseline
+                self.watcher.reset();
+                if let Some(missed) = missed
+                    && self.config.reconnect_resync() == ReconnectResync::Notify
+                    && self.notifications_allowed()
+                {
+                    show_notification(&missed_mutes_message(missed, self.indicator.is_muted()));
+                }
+                let dev = DeviceWorker::spawn(dev);
+                self.sync_mute_switch(&dev);
+                self.sync_output_duck(&dev);
+                self.show_in_use(&dev);
+                dev
+            })
         } else {
             // First connect: no DeviceContext yet — open device and resolve context.
             let dev = focusmute_lib::reconnect::try_reopen(
                 &mut self.reconnect,
                 &self.config.device_serial,
-            )?;
+                self.config.device_options(),
+            )
+            .map(DeviceWorker::spawn)?;
+            // Nothing was shown before, so there's nothing to resync
+            self.reconnect.take_missed_mutes();
             match self.reinit_device_context(&dev) {
                 Ok(warnings) => {
                     for w in &warnings {
@@ -229,6 +811,9 @@ impl TrayState {
                     {
                         log::warn!("could not apply mute after first connect: {e}");
                     }
+                    self.sync_mute_switch(&dev);
+                    self.sync_output_duck(&dev);
+                    self.show_in_use(&dev);
                     Some(dev)
                 }
                 Err(e) => {
@@ -239,6 +824,95 @@ impl TrayState {
         }
     }
 
+    /// Follow an application starting or stopping recording from the
+    /// microphone — shown in the in-use color while live, if one is set.
+    /// Returns `true` if the state changed.
+    pub fn set_in_use(&mut self, in_use: bool, device: Option<&impl ScarlettDevice>) -> bool {
+        if in_use == self.indicator.is_in_use() {
+            return false;
+        }
+        log::info!(
+            "[audio] microphone {}",
+            if in_use { "in use" } else { "idle" }
+        );
+        let commands = self.indicator.next(IndicatorEvent::InUse(in_use));
+        // A simulation owns the LEDs; the state still changes underneath
+        if self.simulation.is_none()
+            && let Some(dev) = device
+            && let Err(e) = led::execute(dev, &commands)
+        {
+            log::warn!("could not show the in-use state: {e}");
+        }
+        true
+    }
+
+    /// Re-show the in-use color on a freshly opened device.
+    fn show_in_use(&self, device: &impl ScarlettDevice) {
+        if self.indicator.is_muted() || !self.indicator.is_in_use() {
+            return;
+        }
+        if let Err(e) = led::execute(device, &self.indicator.live_commands()) {
+            log::warn!("could not show the in-use state: {e}");
+        }
+    }
+
+    /// Serve a CLI request forwarded over IPC using the tray's device handle.
+    pub fn handle_ipc(
+        &mut self,
+        request: IpcRequest,
+        device: Option<&impl ScarlettDevice>,
+    ) -> IpcResponse {
+        match request {
+            IpcRequest::Ping => IpcResponse::Pong,
+            IpcRequest::AnnounceMute { muted } => {
+                self.indicator.expect_change(muted);
+                IpcResponse::Ok
+            }
+            IpcRequest::GetMuteState => IpcResponse::MuteState {
+                muted: self.indicator.is_muted(),
+                origin: self.indicator.origin(),
+            },
+            // CLI commands run on the tray's device (`ipc::TrayDevice`)
+            IpcRequest::GetDeviceInfo => with_device(device, |dev| {
+                let info = dev.info().clone();
+                Ok(IpcResponse::DeviceInfo {
+                    config_raw: info.config_raw.clone(),
+                    init_raw: info.init_raw.clone(),
+                    info,
+                })
+            }),
+            IpcRequest::GetDescriptor { offset, size } => with_device(device, |dev| {
+                let data = dev.get_descriptor(offset, size)?;
+                Ok(IpcResponse::Descriptor { data })
+            }),
+            IpcRequest::SetDescriptor { offset, data } => with_device(device, |dev| {
+                dev.set_descriptor(offset, &data)?;
+                Ok(IpcResponse::Ok)
+            }),
+            IpcRequest::DataNotify { event_id } => with_device(device, |dev| {
+                dev.data_notify(event_id)?;
+                Ok(IpcResponse::Ok)
+            }),
+            IpcRequest::Transact {
+                command,
+                payload,
+                out_size,
+            } => with_device(device, |dev| {
+                let data = dev.transact(command, &payload, out_size)?;
+                Ok(IpcResponse::Transact { data })
+            }),
+            // Need the tray UI, the monitor, or to replace the device —
+            // handled by the event loop
+            IpcRequest::Simulate { .. }
+            | IpcRequest::SetMute { .. }
+            | IpcRequest::Quit
+            | IpcRequest::ReloadConfig
+            | IpcRequest::ResetDevice => IpcResponse::Error {
+                message: "not supported here".into(),
+            },
+        }
+    }
+
     /// Process a mute poll from the background thread. Returns the resulting action.
     /// If a device error occurs, returns `(action, true)` to signal device loss.
     pub fn process_mute_poll(
@@ -246,14 +920,136 @@ impl TrayState {
         muted: bool,
         device: Option<&impl ScarlettDevice>,
     ) -> (MonitorAction, bool) {
-        if let Some(dev) = device {
+        if self.simulation.is_some() {
+            return (MonitorAction::NoChange, false);
+        }
+        let (action, device_lost) = if let Some(dev) = device {
             let (action, err) = self.indicator.poll_and_apply(muted, dev);
             (action, err.is_some())
         } else {
             (self.indicator.update(muted), false)
+        };
+        if action != MonitorAction::NoChange && (device.is_none() || device_lost) {
+            self.reconnect.record_missed_mute(muted);
+        }
+        if action != MonitorAction::NoChange
+            && !device_lost
+            && let Some(dev) = device
+        {
+            self.sync_mute_switch(dev);
+            self.sync_output_duck(dev);
+        }
+        self.indicators.apply(action, self.indicator.mute_color());
+        (action, device_lost)
+    }
+
+    /// Whether mute animation frames are due (never while simulating).
+    pub fn is_animating(&self) -> bool {
+        self.simulation.is_none() && self.indicator.is_animating()
+    }
+
+    /// Feed input peak levels (sampled while muted). On talking while muted,
+    /// flash the indicator and return `true` so the caller can notify.
+    pub fn check_talk_while_muted(&mut self, levels: &[f32], now: Instant) -> bool {
+        let enabled = self.config.talk_warning_enabled
+            || (self.call.is_some() && self.config.call_talk_warning);
+        if !enabled || !self.indicator.is_muted() {
+            self.talk_detector.reset();
+            return false;
+        }
+        let peak = levels.iter().copied().fold(0.0, f32::max);
+        if !self.talk_detector.update(peak, now) {
+            return false;
+        }
+        log::info!("[talk] talking while muted (peak {peak:.2})");
+        self.indicator.start_flash(now);
+        true
+    }
+
+    /// Draw the current mute animation frame, if it changed.
+    pub fn draw_animation_frame(&self, device: &impl ScarlettDevice) {
+        if !self.is_animating() {
+            return;
+        }
+        let commands = self.indicator.animation_frame(Instant::now());
+        if let Err(e) = led::execute(device, &commands) {
+            log::warn!("could not draw mute animation frame: {e}");
         }
     }
 
+    /// React to hardware events from the device's front panel.
+    ///
+    /// A Select press makes the firmware repaint the number LEDs, wiping the
+    /// mute color — re-apply the indicator while muted. A clock change
+    /// re-reads the sample rate and clock source. Auto Gain drives the LEDs
+    /// itself, so indicator writes are held until it finishes.
+    pub fn handle_device_events(&mut self, events: &[DeviceEvent], device: &impl ScarlettDevice) {
+        for event in events {
+            log::debug!("[device] {event:?}");
+        }
+        if events.contains(&DeviceEvent::ClockChanged) {
+            self.refresh_clock(device);
+        }
+        if events.contains(&DeviceEvent::AutogainChanged) {
+            self.refresh_device_busy(device);
+        }
+        if self.indicator.is_muted()
+            && !self.indicator.is_busy()
+            && events.contains(&DeviceEvent::SelectedInputChanged)
+            && let Err(e) = self.indicator.apply_mute(device)
+        {
+            log::warn!("could not re-apply mute indicator after Select: {e}");
+        }
+    }
+
+    /// Re-read `autogainInProgress` and hold or release indicator writes.
+    pub fn refresh_device_busy(&mut self, device: &impl ScarlettDevice) {
+        let schema = self.ctx.as_ref().and_then(|c| c.schema.as_ref());
+        let Some(control) = device_controls::autogain_control(schema) else {
+            return;
+        };
+        let busy = match device_controls::any_autogain_in_progress(device, &control) {
+            Ok(busy) => busy,
+            Err(e) => {
+                log::warn!("could not read Auto Gain state: {e}");
+                return;
+            }
+        };
+        if busy == self.indicator.is_busy() {
+            return;
+        }
+        if busy {
+            log::info!("[device] Auto Gain running — holding mute indicator updates");
+        } else {
+            log::info!("[device] Auto Gain finished — re-applying mute indicator");
+        }
+        let commands = self.indicator.next(IndicatorEvent::DeviceBusy(busy));
+        if let Err(e) = led::execute(device, &commands) {
+            log::warn!("could not re-apply mute indicator after Auto Gain: {e}");
+        }
+    }
+
+    /// Re-read the clock status. Returns `true` if it changed.
+    pub fn refresh_clock(&mut self, device: &impl ScarlettDevice) -> bool {
+        let schema = self.ctx.as_ref().and_then(|c| c.schema.as_ref());
+        let clock = match clock::read_clock_status(device, schema) {
+            Ok(clock) => clock,
+            Err(e) => {
+                log::warn!("could not read clock status: {e}");
+                return false;
+            }
+        };
+        if self.clock == Some(clock) {
+            return false;
+        }
+        match self.clock {
+            Some(old) => log::info!("[device] clock changed: {old} -> {clock}"),
+            None => log::info!("[device] clock: {clock}"),
+        }
+        self.clock = Some(clock);
+        true
+    }
+
     /// Apply new configuration from settings dialog. Returns list of warnings.
     pub fn apply_config(
         &mut self,
@@ -262,22 +1058,61 @@ impl TrayState {
     ) -> Vec<String> {
         let mut warnings = Vec::new();
 
+        let shown = self.shown_commands();
+
         // Update mute color
-        if let Ok(color) = led::parse_color(&new_config.mute_color) {
+        if let Some(preset) = led::find_color_preset(&new_config.color_preset) {
+            self.indicator.set_mute_color(preset.mute);
+        } else if let Ok(color) = led::parse_color(&new_config.mute_color) {
             self.indicator.set_mute_color(color);
         }
+        self.indicator
+            .set_external_mute_color(led::external_mute_color(&new_config));
+        self.indicator
+            .set_in_use_color(led::in_use_color(&new_config));
+        self.indicator.set_animation(
+            new_config.mute_animation(crate::os_prefs::reduce_motion),
+            new_config.animation_speed,
+        );
+
+        if new_config.talk_warning_threshold_db != self.config.talk_warning_threshold_db
+            || new_config.talk_warning_secs != self.config.talk_warning_secs
+        {
+            self.talk_detector = new_config.talk_detector();
+        }
 
         // Update autostart
         if new_config.autostart != self.config.autostart {
             set_autostart(new_config.autostart);
         }
 
-        // Re-resolve strategy if mute_inputs, input_colors, or mute_color changed.
+        // Reopen the busylight with the new settings and show the current state
+        if new_config.busylight_enabled != self.config.busylight_enabled
+            || new_config.busylight_live_color != self.config.busylight_live_color
+            || new_config.mute_color != self.config.mute_color
+            || new_config.color_preset != self.config.color_preset
+        {
+            self.indicators.clear();
+            self.indicators = Indicators::from_config(&new_config);
+            if self.first_poll_done {
+                self.indicators
+                    .show(self.indicator.is_muted(), self.indicator.mute_color());
+            }
+        }
+
+        // Re-resolve strategy if mute_inputs, mute_preset, input_colors, mute_color,
+        // color_preset, or the LED patterns changed.
         // mute_color affects strategy.mute_colors — without this, changing the
         // global color leaves the per-input strategy colors stale.
         if new_config.mute_inputs != self.config.mute_inputs
+            || new_config.mute_preset != self.config.mute_preset
             || new_config.input_colors != self.config.input_colors
             || new_config.mute_color != self.config.mute_color
+            || new_config.color_preset != self.config.color_preset
+            || new_config.led_patterns != self.config.led_patterns
+            || new_config.mute_pattern != self.config.mute_pattern
+            || new_config.live_pattern != self.config.live_pattern
+            || new_config.disconnected_pattern != self.config.disconnected_pattern
         {
             let (input_count, profile, predicted) = match self.ctx.as_ref() {
                 Some(ctx) => (ctx.input_count(), ctx.profile, ctx.predicted.as_ref()),
@@ -291,12 +1126,6 @@ impl TrayState {
             ) {
                 Ok((_mode, new_strategy, sw)) => {
                     warnings.extend(sw);
-                    // Clear old indicator before switching strategy
-                    if self.indicator.is_muted()
-                        && let Some(dev) = device
-                    {
-                        let _ = self.indicator.clear_mute(dev);
-                    }
                     self.indicator.set_strategy(new_strategy);
                 }
                 Err(e) => {
@@ -305,22 +1134,131 @@ impl TrayState {
             }
         }
 
-        // Re-apply current mute LED state with new settings
-        if self.indicator.is_muted()
+        // Move the mute indicator to the new settings in one pass
+        if let Some((mute, reset)) = shown
             && let Some(dev) = device
         {
-            let _ = self.indicator.apply_mute(dev);
+            let target = self.indicator.mute_commands();
+            let _ = led::execute(dev, &led::transition_commands(&mute, &reset, &target));
         }
 
-        // Save to disk and update config
+        // Update config and queue a save (safe mode must not overwrite the
+        // user's real config with defaults)
+        let duck_changed = new_config.output_duck_db != self.config.output_duck_db;
         self.config = new_config;
-        if let Err(e) = self.config.save() {
-            log::warn!("could not save config: {e}");
+
+        // Put ducked outputs back before lowering them by the new amount
+        if duck_changed && let Some(dev) = device {
+            let db = std::mem::take(&mut self.config.output_duck_db);
+            self.sync_output_duck(dev);
+            self.config.output_duck_db = db;
+            self.sync_output_duck(dev);
+        }
+
+        // Keep the call profile's colors over the new settings, or drop
+        // them if the call profile was turned off
+        if self.call.is_some() {
+            if !self.config.call_profile_enabled {
+                self.call = None;
+            }
+            self.show_call_colors(device);
+        }
+        if self.safe_mode {
+            log::info!("safe mode — settings applied for this session only");
+        } else {
+            self.save_throttle.mark(Instant::now());
         }
 
         warnings
     }
 
+    /// The mute commands and the restore commands for what the LEDs show
+    /// now (`None` while live), so a color change rewrites only the LEDs it
+    /// affects instead of clearing and repainting (a visible blink).
+    /// Animated LEDs may be dimmed, so nothing counts as already shown.
+    fn shown_commands(&self) -> Option<(Vec<led::LedCommand>, Vec<led::LedCommand>)> {
+        self.indicator.is_muted().then(|| {
+            let mute = if self.indicator.is_animating() {
+                Vec::new()
+            } else {
+                self.indicator.mute_commands()
+            };
+            (mute, self.indicator.clear_commands())
+        })
+    }
+
+    /// Follow the applications recording from the microphone into and out
+    /// of the call profile. Returns the mute state to switch to, if the
+    /// profile changes it (`call_unmute`).
+    pub fn follow_call(
+        &mut self,
+        capturing: &[String],
+        device: Option<&impl ScarlettDevice>,
+    ) -> Option<bool> {
+        let application = self.config.call_application(capturing);
+        match (application, self.call.take()) {
+            (Some(application), None) => {
+                log::info!("[call] {application} started a call");
+                let unmute = self.config.call_unmute && self.indicator.is_muted();
+                self.call = Some(ActiveCall {
+                    application: application.to_string(),
+                    unmuted: unmute,
+                });
+                self.show_call_colors(device);
+                unmute.then_some(false)
+            }
+            (None, Some(call)) => {
+                log::info!("[call] {} call ended", call.application);
+                self.show_call_colors(device);
+                call.unmuted.then_some(true)
+            }
+            (_, call) => {
+                self.call = call;
+                None
+            }
+        }
+    }
+
+    /// Switch the mute indicator to the call profile's colors while in a
+    /// call, or back to the configured ones.
+    fn resolve_call_colors(&mut self) {
+        let mut config = if self.call.is_some() {
+            self.config.during_call()
+        } else {
+            self.config.clone()
+        };
+        self.indicator
+            .set_mute_color(led::mute_color_or_default(&config));
+        let (input_count, profile, predicted) = match self.ctx.as_ref() {
+            Some(ctx) => (ctx.input_count(), ctx.profile, ctx.predicted.as_ref()),
+            None => (None, None, None),
+        };
+        match led::resolve_strategy_from_config(&mut config, input_count, profile, predicted) {
+            Ok((_mode, strategy, _warnings)) => self.indicator.set_strategy(strategy),
+            Err(e) => log::warn!("[call] could not switch the mute colors: {e}"),
+        }
+    }
+
+    /// [`resolve_call_colors`](Self::resolve_call_colors) and repaint what
+    /// shows the mute state.
+    fn show_call_colors(&mut self, device: Option<&impl ScarlettDevice>) {
+        let shown = self.shown_commands();
+        self.resolve_call_colors();
+        if self.simulation.is_none()
+            && let Some((mute, reset)) = shown
+            && let Some(dev) = device
+        {
+            let target = self.indicator.mute_commands();
+            if let Err(e) = led::execute(dev, &led::transition_commands(&mute, &reset, &target)) {
+                log::warn!("could not show the call colors: {e}");
+            }
+        }
+        if self.first_poll_done {
+            self.indicators
+                .show(self.indicator.is_muted(), self.indicator.mute_color());
+        }
+    }
+
     /// Handle settings dialog result: apply config, return what changed.
     ///
     /// Returns `(warnings, mute_sound_changed, unmute_sound_changed, hotkey_changed, new_hotkey_str)`.
@@ -345,11 +1283,139 @@ impl TrayState {
         )
     }
 
+    /// Write queued config changes if they have settled (see [`SaveThrottle`]).
+    pub fn save_config_if_due(&mut self) {
+        if self.save_throttle.due(Instant::now()) {
+            self.flush_config();
+        }
+    }
+
+    /// Write queued config changes now. Called on exit.
+    pub fn flush_config(&mut self) {
+        if !self.save_throttle.is_pending() {
+            return;
+        }
+        self.save_throttle.clear();
+        if let Err(e) = self.config.save() {
+            log::warn!("could not save config: {e}");
+        }
+    }
+
     /// Restore LED state on exit.
     pub fn restore_on_exit(&self, device: &impl ScarlettDevice) {
-        if let Err(e) = led::restore_on_exit(device, self.indicator.strategy()) {
+        let schema = self.ctx.as_ref().and_then(|c| c.schema.as_ref());
+        if let Err(e) = self.indicator.restore_on_exit(device, schema) {
             log::warn!("could not restore LED state: {e}");
         }
+        if let Some(saved) = &self.output_duck
+            && let Some(control) = self.output_volume_control()
+            && let Err(e) = saved.restore(device, &control)
+        {
+            log::warn!("could not restore the output volume: {e}");
+        }
+    }
+
+    /// Make the device with `serial` the active one.
+    ///
+    /// Restores the current device's LEDs and drops it, persists `serial` as
+    /// `device_serial` (unless in safe mode), and clears the device context so
+    /// the next [`try_reconnect`](Self::try_reconnect) opens the new device and
+    /// resolves a fresh strategy.
+    pub fn switch_device(&mut self, serial: &str, device: &mut Option<impl ScarlettDevice>) {
+        if let Some(dev) = device.take() {
+            self.restore_on_exit(&dev);
+        }
+        self.output_duck = None;
+        self.config.device_serial = serial.to_string();
+        if !self.safe_mode {
+            self.save_throttle.mark(Instant::now());
+        }
+        self.ctx = None;
+        self.clock = None;
+        self.reset_backoff();
+    }
+
+    /// Reset a device whose transactions keep failing.
+    ///
+    /// Restores the LEDs, resets and drops the device, and clears the backoff
+    /// so the next [`try_reconnect`](Self::try_reconnect) reopens it. The
+    /// device is dropped even if the reset fails.
+    pub fn reset_device(
+        &mut self,
+        device: &mut Option<impl ScarlettDevice>,
+    ) -> focusmute_lib::error::Result<()> {
+        let Some(dev) = device.take() else {
+            return Ok(());
+        };
+        self.restore_on_exit(&dev);
+        let result = dev.reset();
+        drop(dev);
+        self.reset_backoff();
+        Ok(result?)
+    }
+
+    /// Inject a synthetic state from `focusmute-cli simulate`.
+    ///
+    /// `Muted` / `Live` drive the indicator (LEDs included) as if the
+    /// microphone had changed, then ignore real polls until `Off`.
+    /// `Disconnect` restores the LEDs, drops the device, and holds reconnects.
+    /// Returns the mute action for the tray UI.
+    pub fn simulate(
+        &mut self,
+        sim: SimulatedState,
+        device: &mut Option<impl ScarlettDevice>,
+    ) -> MonitorAction {
+        log::info!("[simulate] {sim:?}");
+        let muted = match sim {
+            SimulatedState::Muted => true,
+            SimulatedState::Live => false,
+            SimulatedState::Disconnect => {
+                self.simulation = Some(sim);
+                if let Some(dev) = device.take() {
+                    self.restore_on_exit(&dev);
+                }
+                return MonitorAction::NoChange;
+            }
+            SimulatedState::Off => {
+                self.simulation = None;
+                self.reset_backoff();
+                return MonitorAction::NoChange;
+            }
+        };
+        self.simulation = None;
+        let mut action = MonitorAction::NoChange;
+        // Feed the debouncer until it confirms the new state
+        while self.indicator.is_muted() != muted {
+            let (next, device_lost) = self.process_mute_poll(muted, device.as_ref());
+            if device_lost {
+                *device = None;
+            }
+            action = next;
+        }
+        self.simulation = Some(sim);
+        action
+    }
+
+    /// Follow a session switch (fast user switching, remote desktop).
+    ///
+    /// Losing the console restores the LEDs and releases the device so the
+    /// other session's tray can open it; reconnects stay paused until the
+    /// session is back. Returns `true` if the state changed.
+    pub fn set_session_active(
+        &mut self,
+        active: bool,
+        device: &mut Option<impl ScarlettDevice>,
+    ) -> bool {
+        if active == self.session_active {
+            return false;
+        }
+        self.session_active = active;
+        if active {
+            self.reset_backoff();
+        } else if let Some(dev) = device.take() {
+            self.restore_on_exit(&dev);
+        }
+        true
     }
 }
 
@@ -366,47 +1432,135 @@ pub fn handle_menu_event(
 ) -> bool {
     if event.id() == menu.quit_item.id() {
         return true;
-    } else if event.id() == menu.toggle_item.id() {
-        toggle_mute_fn(state.indicator.is_muted());
+    } else if event.id() == menu.toggle_item.id() && state.mute_control_enabled() {
+        let is_muted = state.indicator.is_muted();
+        state.indicator.expect_change(!is_muted);
+        toggle_mute_fn(is_muted);
     } else if event.id() == menu.settings_item.id() {
         let info = device.as_ref().map(|d| d.info());
         let profile = state.ctx.as_ref().and_then(|c| c.profile);
-        if let Some(new_config) =
-            crate::settings_dialog::show_settings(&state.config, profile, info)
+        let schema_refreshed = std::cell::Cell::new(false);
+        let refresh_fn;
+        let refresh_schema: Option<crate::settings_dialog::RefreshSchema<'_>> =
+            match device.as_ref() {
+                Some(dev) => {
+                    let refreshed = &schema_refreshed;
+                    refresh_fn = move || {
+                        focusmute_lib::schema::refresh_cache(dev)?;
+                        refreshed.set(true);
+                        Ok(())
+                    };
+                    Some(&refresh_fn)
+                }
+                None => None,
+            };
+        let new_config = crate::settings_dialog::show_settings(
+            &state.config,
+            profile,
+            info,
+            state.clock.as_ref(),
+            state.safe_mode,
+            refresh_schema,
+        );
+        // Pick up a re-read schema before applying any new settings
+        if schema_refreshed.get()
+            && let Some(dev) = device.as_ref()
+            && let Err(e) = state.reinit_device_context(dev)
         {
-            let (warnings, mute_changed, unmute_changed, hotkey_changed, new_hotkey_str) =
-                state.handle_settings_result(new_config, device.as_ref());
-            for w in &warnings {
-                log::warn!("[config] {w}");
-            }
-
-            if mute_changed {
-                resources.mute_sound =
-                    sound::load_sound_data(&state.config.mute_sound_path, sound::SOUND_MUTED);
-            }
-            if unmute_changed {
-                resources.unmute_sound =
-                    sound::load_sound_data(&state.config.unmute_sound_path, sound::SOUND_UNMUTED);
-            }
-
-            if hotkey_changed {
-                reregister_hotkey(&mut resources.hotkey, &new_hotkey_str);
-                menu.toggle_item
-                    .set_text(format!("Toggle Mute\t{}", new_hotkey_str));
-            }
+            log::warn!("[schema] could not reload device context: {e}");
+        }
+        if let Some(new_config) = new_config {
+            apply_new_config(new_config, menu, state, device.as_ref(), resources);
         }
     } else if event.id() == menu.reconnect_item.id() {
         state.reset_backoff();
         // Next loop iteration will attempt reconnect immediately
+    } else if event.id() == menu.reset_item.id() {
+        log::info!("resetting device");
+        if let Err(e) = state.reset_device(device) {
+            log::warn!("device reset failed: {e}");
+            show_notification(&format!("Device reset failed: {e}"));
+        }
+        menu.set_device_waiting();
+    } else if let Some(serial) = menu.switch_target(event.id()) {
+        log::info!("switching to device {serial}");
+        state.switch_device(&serial, device);
+        menu.set_device_waiting();
     }
     false
 }
 
+/// Apply a new config — saved from the settings dialog or reloaded from
+/// disk — and reload the sounds and hotkey it changes.
+fn apply_new_config(
+    new_config: Config,
+    menu: &TrayMenu,
+    state: &mut TrayState,
+    device: Option<&impl ScarlettDevice>,
+    resources: &mut TrayResources,
+) {
+    let (warnings, mute_changed, unmute_changed, hotkey_changed, new_hotkey_str) =
+        state.handle_settings_result(new_config, device);
+    for w in &warnings {
+        log::warn!("[config] {w}");
+    }
+
+    if mute_changed {
+        let (sound, warning) =
+            sound::load_sound_data(&state.config.mute_sound_path, sound::SOUND_MUTED);
+        resources.mute_sound = sound;
+        if let Some(w) = warning {
+            show_notification(&format!("Mute sound: {w}"));
+        }
+    }
+    if unmute_changed {
+        let (sound, warning) =
+            sound::load_sound_data(&state.config.unmute_sound_path, sound::SOUND_UNMUTED);
+        resources.unmute_sound = sound;
+        if let Some(w) = warning {
+            show_notification(&format!("Unmute sound: {w}"));
+        }
+    }
+
+    if hotkey_changed {
+        reregister_hotkey(&mut resources.hotkey, &new_hotkey_str);
+        menu.toggle_item
+            .set_text(format!("Toggle Mute\t{}", new_hotkey_str));
+    }
+}
+
+/// Re-read the config file and apply it (`focusmute-cli tray reload-config`).
+/// A file that doesn't parse is refused rather than reset to defaults.
+pub fn reload_config(
+    menu: &TrayMenu,
+    state: &mut TrayState,
+    device: Option<&impl ScarlettDevice>,
+    resources: &mut TrayResources,
+) -> IpcResponse {
+    if state.safe_mode {
+        return IpcResponse::Error {
+            message: "safe mode — the config file is not read".into(),
+        };
+    }
+    let (config, warnings) = Config::load_with_warnings();
+    if let Some(warning) = warnings.into_iter().next() {
+        return IpcResponse::Error { message: warning };
+    }
+    log::info!("[config] reloading the config file");
+    apply_new_config(config, menu, state, device, resources);
+    // Already what the file says
+    state.save_throttle.clear();
+    IpcResponse::Ok
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use focusmute_lib::device::mock::MockDevice;
+    use focusmute_lib::device_events::DeviceButton;
+    use focusmute_lib::monitor::MuteOrigin;
     use focusmute_lib::protocol::*;
+    use focusmute_lib::schema::{ControlMember, SchemaConstants};
 
     /// Create a MockDevice with the "Scarlett 2i2 4th Gen" name so that
     /// TrayState::init_with_config succeeds (known profile, no schema extraction needed).
@@ -418,6 +1572,37 @@ mod tests {
         dev
     }
 
+    /// A 2i2 schema with no optional controls; tests add the ones they need.
+    fn schema_2i2() -> SchemaConstants {
+        SchemaConstants {
+            product_name: "Scarlett 2i2 4th Gen".into(),
+            max_leds: 40,
+            max_inputs: 2,
+            max_outputs: 2,
+            gradient_count: 0,
+            gradient_offset: 0,
+            gradient_notify: 9,
+            direct_led_count: 40,
+            direct_led_offset: 92,
+            metering_segments: 25,
+            input_controls: vec![],
+            app_space_features: vec![],
+            firmware_version: String::new(),
+            phantom_power: None,
+            air: None,
+            inst_input: None,
+            preamp_gain: None,
+            clip_safe: None,
+            autogain: None,
+            autogain_status_offset: None,
+            sample_rate_offset: None,
+            sync_source_offset: None,
+            direct_monitoring_offset: None,
+            alt_speakers: None,
+            output_volume: None,
+        }
+    }
+
     #[test]
     fn init_creates_valid_state() {
         let dev = make_mock_device();
@@ -441,6 +1626,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn set_initial_live_restores_leftover_mute_led() {
+        let dev = make_mock_device();
+        let mut state = TrayState::init_with_config(Config::default(), &dev).unwrap();
+        // Previous run left input 2's number LED red
+        led::set_single_led(&dev, 8, 0xFF00_0000).unwrap();
+        dev.notifies.borrow_mut().clear();
+
+        state.set_initial_muted(false, &dev);
+        assert!(!state.indicator.is_muted());
+        assert!(
+            !dev.notifies.borrow().is_empty(),
+            "leftover mute LED should be restored"
+        );
+    }
+
     #[test]
     fn handle_mute_poll_returns_updates() {
         let dev = make_mock_device();
@@ -472,6 +1673,22 @@ mod tests {
         new_config.sound_enabled = false;
         state.apply_config(new_config, Some(&dev));
         assert!(!state.config.sound_enabled);
+        // Saved later, once the settings stop changing
+        assert!(state.save_throttle.is_pending());
+    }
+
+    #[test]
+    fn apply_config_in_safe_mode_updates_session_config() {
+        let dev = make_mock_device();
+        let mut state = TrayState::init_with_config(Config::default(), &dev).unwrap();
+        assert!(!state.safe_mode);
+        state.safe_mode = true;
+
+        let mut new_config = state.config.clone();
+        new_config.sound_enabled = false;
+        state.apply_config(new_config, Some(&dev));
+        assert!(!state.config.sound_enabled);
+        assert!(!state.save_throttle.is_pending());
     }
 
     #[test]
@@ -498,6 +1715,24 @@ mod tests {
         assert_eq!(state.indicator.strategy().input_indices, &[0]);
     }
 
+    #[test]
+    fn apply_config_compiles_led_patterns() {
+        let dev = make_mock_device();
+        let mut state = TrayState::init_with_config(Config::default(), &dev).unwrap();
+
+        let mut new_config = state.config.clone();
+        new_config.led_patterns = std::collections::HashMap::from([(
+            "onair".into(),
+            std::collections::HashMap::from([("16".into(), "red".into())]),
+        )]);
+        new_config.mute_pattern = "onair".into();
+        state.apply_config(new_config, Some(&dev));
+        assert_eq!(
+            state.indicator.strategy().patterns.muted,
+            vec![(16, 0xFF00_0000)]
+        );
+    }
+
     #[test]
     fn restore_on_exit_restores_leds() {
         let dev = make_mock_device();
@@ -515,6 +1750,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn led_baseline_persists_until_clean_exit() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("led_baseline.json");
+        let dev = make_mock_device();
+        let mut state = TrayState::init_with_config(Config::default(), &dev).unwrap();
+        assert!(state.indicator.baseline().is_some());
+
+        state.persist_led_baseline(Some(path.clone()), Some(&dev));
+        assert!(path.exists());
+        state.restore_on_exit(&dev);
+        state.discard_led_baseline();
+        assert!(!path.exists());
+    }
+
     #[test]
     fn try_reconnect_respects_backoff() {
         let dev = make_mock_device();
@@ -575,6 +1825,22 @@ mod tests {
         assert!(state.indicator.is_muted());
     }
 
+    #[test]
+    fn switch_device_restores_and_clears_context() {
+        let dev = make_mock_device();
+        let mut state = TrayState::init_with_config(Config::default(), &dev).unwrap();
+        state.safe_mode = true;
+        state.set_initial_muted(true, &dev);
+        dev.notifies.borrow_mut().clear();
+
+        let mut device = Some(dev);
+        state.switch_device("S2X1234", &mut device);
+
+        assert!(device.is_none());
+        assert!(state.ctx.is_none());
+        assert_eq!(state.config.device_serial, "S2X1234");
+    }
+
     #[test]
     fn reconnect_backoff_progression() {
         let dev = make_mock_device();
@@ -704,8 +1970,15 @@ mod tests {
         // Switch strategy to target only input 1
         let mut new_config = state.config.clone();
         new_config.mute_inputs = "1".into();
+        dev.notifies.borrow_mut().clear();
         state.apply_config(new_config, Some(&dev));
 
+        // Only input 2's LED changes — input 1 stays lit, with no clear/repaint blink
+        assert_eq!(dev.notifies.borrow().len(), 1);
+        dev.notifies.borrow_mut().clear();
+        state.apply_config(state.config.clone(), Some(&dev));
+        assert!(dev.notifies.borrow().is_empty());
+
         // Strategy should target only input 1
         assert_eq!(
             state.indicator.strategy().input_indices,
@@ -870,4 +2143,611 @@ mod tests {
             "should write LED color after reinit"
         );
     }
+
+    #[test]
+    fn daily_summary_only_after_day_rollover() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.csv");
+        // Local midnights, so the test holds in any time zone
+        let day10 = history::local_day_start(10);
+        let day11 = history::local_day_start(11);
+        history::append_event_to(
+            &path,
+            &history::HistoryEvent {
+                timestamp: day10,
+                muted: true,
+            },
+        )
+        .unwrap();
+        history::append_event_to(
+            &path,
+            &history::HistoryEvent {
+                timestamp: day10 + 3600,
+                muted: false,
+            },
+        )
+        .unwrap();
+
+        let config = Config {
+            history_enabled: true,
+            daily_summary_enabled: true,
+            ..Config::default()
+        };
+        let mut state = TrayState::init_without_device(config);
+        state.summary_day = 10;
+
+        assert!(state.take_daily_summary(day10 + 7200, &path).is_none());
+        let body = state.take_daily_summary(day11 + 5, &path).unwrap();
+        assert!(body.contains("Muted 1h 00m"), "got: {body}");
+        assert!(body.contains("1 toggle"), "got: {body}");
+        // Only fires once per day
+        assert!(state.take_daily_summary(day11 + 60, &path).is_none());
+    }
+
+    #[test]
+    fn do_not_disturb_override_suppresses_notifications() {
+        let mut state = TrayState::init_without_device(Config {
+            notifications_enabled: true,
+            do_not_disturb: "on".into(),
+            ..Config::default()
+        });
+        assert!(state.do_not_disturb());
+        assert!(!state.notifications_allowed());
+
+        state.config.do_not_disturb = "off".into();
+        assert!(state.notifications_allowed());
+        state.config.notifications_enabled = false;
+        assert!(!state.notifications_allowed());
+    }
+
+    #[test]
+    fn talking_while_muted_flashes_once() {
+        let dev = make_mock_device();
+        let config = Config {
+            talk_warning_enabled: true,
+            talk_warning_secs: 1.0,
+            ..Config::default()
+        };
+        let mut state = TrayState::init_with_config(config, &dev).unwrap();
+        let t0 = Instant::now();
+        let at = |ms: u64| t0 + Duration::from_millis(ms);
+
+        // Live — talking is fine
+        assert!(!state.check_talk_while_muted(&[0.5, 0.0], at(0)));
+        assert!(!state.check_talk_while_muted(&[0.5, 0.0], at(1500)));
+
+        state.process_mute_poll(true, Some(&dev));
+        state.process_mute_poll(true, Some(&dev));
+        assert!(!state.is_animating());
+        let warned: Vec<u64> = (2000..=4000)
+            .step_by(250)
+            .filter(|&ms| state.check_talk_while_muted(&[0.0, 0.5], at(ms)))
+            .collect();
+        assert_eq!(warned, vec![3000]);
+        assert!(state.is_animating(), "the indicator flashes");
+    }
+
+    #[test]
+    fn call_profile_unmutes_recolors_and_reverts() {
+        let dev = make_mock_device();
+        let config = Config {
+            call_profile_enabled: true,
+            call_unmute: true,
+            call_mute_color: "#0000FF".into(),
+            ..Config::default()
+        };
+        let mut state = TrayState::init_with_config(config, &dev).unwrap();
+        state.process_mute_poll(true, Some(&dev));
+        state.process_mute_poll(true, Some(&dev));
+
+        assert_eq!(
+            state.follow_call(&["obs".into()], Option::<&MockDevice>::None),
+            None
+        );
+        let capturing = vec!["Zoom.exe".to_string()];
+        assert_eq!(state.follow_call(&capturing, Some(&dev)), Some(false));
+        assert_eq!(state.call.as_ref().unwrap().application, "Zoom.exe");
+        assert_eq!(state.indicator.mute_color(), 0x0000_FF00);
+        assert!(
+            state
+                .indicator
+                .strategy()
+                .mute_colors
+                .iter()
+                .all(|&c| c == 0x0000_FF00)
+        );
+        // The talk warning is on for the call
+        state.talk_detector = TalkDetector::new(-30.0, Duration::ZERO);
+        assert!(state.check_talk_while_muted(&[0.5], Instant::now()));
+        assert_eq!(state.follow_call(&capturing, Some(&dev)), None);
+
+        assert_eq!(state.follow_call(&[], Some(&dev)), Some(true));
+        assert!(state.call.is_none());
+        assert_eq!(state.indicator.mute_color(), 0xFF00_0000);
+        assert!(!state.check_talk_while_muted(&[0.5], Instant::now()));
+    }
+
+    #[test]
+    fn muting_ducks_the_outputs_until_unmute() {
+        let dev = make_mock_device();
+        let config = Config {
+            output_duck_db: 20,
+            ..Config::default()
+        };
+        let mut state = TrayState::init_with_config(config, &dev).unwrap();
+        let control = VolumeMember {
+            offset: 1000,
+            count: 2,
+            notify: Some(28),
+        };
+        state.ctx.as_mut().unwrap().schema = Some(SchemaConstants {
+            output_volume: Some(control),
+            ..schema_2i2()
+        });
+        dev.set_descriptor(1000, &(-6i16).to_le_bytes()).unwrap();
+        let volume = |output| device_controls::get_output_volume(&dev, &control, output).unwrap();
+
+        state.process_mute_poll(true, Some(&dev));
+        state.process_mute_poll(true, Some(&dev));
+        assert_eq!((volume(0), volume(1)), (-26, -20));
+        // Already ducked: no further drop
+        state.sync_output_duck(&dev);
+        assert_eq!(volume(0), -26);
+
+        state.process_mute_poll(false, Some(&dev));
+        state.process_mute_poll(false, Some(&dev));
+        assert_eq!((volume(0), volume(1)), (-6, 0));
+        assert!(state.output_duck.is_none());
+
+        // Exiting while muted puts them back too
+        state.process_mute_poll(true, Some(&dev));
+        state.process_mute_poll(true, Some(&dev));
+        assert_eq!(volume(1), -20);
+        state.restore_on_exit(&dev);
+        assert_eq!((volume(0), volume(1)), (-6, 0));
+    }
+
+    /// Capture volume only — enough for the mic level hotkeys.
+    struct VolumeMonitor(std::cell::Cell<f64>);
+
+    impl MuteMonitor for VolumeMonitor {
+        fn is_muted(&self) -> bool {
+            false
+        }
+        fn set_muted(&self, _muted: bool) -> focusmute_lib::audio::Result<()> {
+            Ok(())
+        }
+        fn wait_for_change(&self, _timeout: Duration) -> bool {
+            false
+        }
+        fn adjust_capture_volume(&self, step_db: f64) -> focusmute_lib::audio::Result<f64> {
+            self.0.set((self.0.get() + step_db).min(0.0));
+            Ok(self.0.get())
+        }
+    }
+
+    #[test]
+    fn level_hotkeys_step_volume_or_gain_and_show_it() {
+        let dev = make_mock_device();
+        let mut state = TrayState::init_with_config(Config::default(), &dev).unwrap();
+        let monitor = VolumeMonitor(std::cell::Cell::new(-1.0));
+        assert_eq!(state.tooltip_text(), "FocusMute — Live");
+
+        assert!(state.step_capture_level(true, Some(&monitor), Some(&dev)));
+        assert_eq!(state.tooltip_text(), "FocusMute — Live (mic volume 0 dB)");
+        assert!(state.step_capture_level(false, Some(&monitor), Some(&dev)));
+        assert_eq!(monitor.0.get(), -2.0);
+
+        // Hardware gain of the muted inputs
+        state.config.gain_hotkey_target = "hardware".into();
+        assert!(!state.step_capture_level(true, Some(&monitor), Some(&dev)));
+        let control = ControlMember {
+            offset: 2000,
+            count: 2,
+            notify: 12,
+            via_parameter_buffer: false,
+        };
+        state.ctx.as_mut().unwrap().schema = Some(SchemaConstants {
+            preamp_gain: Some(control),
+            ..schema_2i2()
+        });
+        dev.set_descriptor(2000, &[10, 1]).unwrap();
+        assert!(state.step_capture_level(false, Some(&monitor), Some(&dev)));
+        assert_eq!(dev.get_descriptor(2000, 2).unwrap(), vec![8, 0]);
+        assert_eq!(state.capture_level.as_deref(), Some("gain 8/0"));
+    }
+
+    #[test]
+    fn device_in_use_has_its_own_status() {
+        let mut state = TrayState::init_without_device(Config::default());
+        assert_eq!(state.device_in_use(), None);
+        state
+            .reconnect
+            .record_error(DeviceError::InUse(Some("ableton (PID 42)".into())));
+        assert_eq!(
+            state.device_in_use().as_deref(),
+            Some("Device in use by ableton (PID 42)")
+        );
+        state.reconnect.record_error(DeviceError::NotFound);
+        assert_eq!(state.device_in_use(), None);
+    }
+
+    #[test]
+    fn privacy_block_changes_status_text() {
+        let mut state = TrayState::init_without_device(Config::default());
+        assert_eq!(state.status_text(), "Live");
+        assert_eq!(state.update_privacy_blocked(Some(true)), Some(true));
+        assert_eq!(state.update_privacy_blocked(Some(true)), None);
+        assert_eq!(state.status_text(), "Blocked by privacy settings");
+        // Unknown counts as not blocked
+        assert_eq!(state.update_privacy_blocked(None), Some(false));
+        assert_eq!(state.status_text(), "Live");
+    }
+
+    #[test]
+    fn in_use_lights_leds_and_changes_status_text() {
+        let dev = make_mock_device();
+        let config = Config {
+            in_use_color: "#00FF00".into(),
+            ..Config::default()
+        };
+        let mut state = TrayState::init_with_config(config, &dev).unwrap();
+        assert!(state.set_in_use(true, Some(&dev)));
+        assert!(!state.set_in_use(true, Some(&dev)));
+        assert_eq!(state.status_text(), "Live — in use");
+        assert_eq!(
+            dev.get_descriptor(OFF_DIRECT_LED_COLOUR, 4).unwrap(),
+            0x00FF_0000u32.to_le_bytes().to_vec()
+        );
+        assert!(state.set_in_use(false, Some(&dev)));
+        assert_eq!(state.status_text(), "Live");
+    }
+
+    #[test]
+    fn indicator_only_disables_mute_control() {
+        let state = TrayState::init_without_device(Config::default());
+        assert!(state.mute_control_enabled());
+        let state = TrayState::init_without_device(Config {
+            indicator_only: true,
+            ..Config::default()
+        });
+        assert!(!state.mute_control_enabled());
+    }
+
+    #[test]
+    fn control_only_never_reconnects() {
+        let mut state = TrayState::init_without_device(Config {
+            control_only: true,
+            ..Config::default()
+        });
+        assert!(!state.leds_enabled());
+        assert!(state.try_reconnect().is_none());
+        assert!(state.mute_control_enabled());
+    }
+
+    #[test]
+    fn hotplug_arrival_resumes_reconnect() {
+        let mut state = TrayState::init_without_device(Config::default());
+        state.reconnect.set_hotplug_driven(true);
+        for _ in 0..5 {
+            state.reconnect.record_failure();
+        }
+        assert!(!state.reconnect.should_attempt());
+
+        state.on_hotplug(HotplugEvent::Removed);
+        assert!(!state.reconnect.should_attempt());
+        state.on_hotplug(HotplugEvent::Arrived);
+        assert!(state.reconnect.should_attempt());
+
+        // A manual reconnect keeps hotplug-driven mode
+        state.reset_backoff();
+        for _ in 0..5 {
+            state.reconnect.record_failure();
+        }
+        assert!(!state.reconnect.should_attempt());
+    }
+
+    #[test]
+    fn inactive_session_releases_device() {
+        let dev = make_mock_device();
+        let mut state = TrayState::init_with_config(Config::default(), &dev).unwrap();
+        let mut device = Some(dev);
+
+        assert!(state.set_session_active(false, &mut device));
+        assert!(device.is_none());
+        assert!(state.try_reconnect().is_none());
+        assert!(!state.set_session_active(false, &mut device));
+
+        assert!(state.set_session_active(true, &mut device));
+        assert!(state.session_active);
+    }
+
+    #[test]
+    fn mute_changes_while_disconnected_are_recorded() {
+        let dev = make_mock_device();
+        let mut state = TrayState::init_with_config(Config::default(), &dev).unwrap();
+        let none = Option::<&MockDevice>::None;
+        for muted in [true, true, false, false, true, true] {
+            state.process_mute_poll(muted, none);
+        }
+        let missed = state.reconnect.take_missed_mutes().unwrap();
+        assert_eq!(
+            missed,
+            MissedMuteChanges {
+                shown_muted: false,
+                count: 3
+            }
+        );
+        assert_eq!(
+            missed_mutes_message(missed, true),
+            "Device reconnected — 3 mute changes while disconnected, now muted"
+        );
+
+        // Changes the device shows aren't missed
+        state.process_mute_poll(false, Some(&dev));
+        state.process_mute_poll(false, Some(&dev));
+        assert_eq!(state.reconnect.take_missed_mutes(), None);
+    }
+
+    #[test]
+    fn reset_device_drops_handle_for_reconnect() {
+        let dev = make_mock_device();
+        let mut state = TrayState::init_with_config(Config::default(), &dev).unwrap();
+        state.reconnect.record_failure();
+        let mut device = Some(dev);
+
+        state.reset_device(&mut device).unwrap();
+        assert!(device.is_none());
+        assert!(state.reconnect.should_attempt());
+        // Nothing to reset without a device
+        state.reset_device(&mut device).unwrap();
+    }
+
+    #[test]
+    fn simulate_overrides_real_mute_state() {
+        let dev = make_mock_device();
+        let mut state = TrayState::init_with_config(Config::default(), &dev).unwrap();
+        let mut device = Some(dev);
+
+        let action = state.simulate(SimulatedState::Muted, &mut device);
+        assert_eq!(action, MonitorAction::ApplyMute);
+        assert!(state.indicator.is_muted());
+        // Real polls don't undo the simulation
+        state.process_mute_poll(false, device.as_ref());
+        state.process_mute_poll(false, device.as_ref());
+        assert!(state.indicator.is_muted());
+
+        state.simulate(SimulatedState::Disconnect, &mut device);
+        assert!(device.is_none());
+        assert!(state.try_reconnect().is_none());
+
+        state.simulate(SimulatedState::Off, &mut device);
+        assert!(state.simulation.is_none());
+        assert!(state.reconnect.should_attempt());
+    }
+
+    #[test]
+    fn daily_summary_disabled_by_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.csv");
+        history::append_event_to(
+            &path,
+            &history::HistoryEvent {
+                timestamp: 5,
+                muted: true,
+            },
+        )
+        .unwrap();
+
+        let mut state = TrayState::init_without_device(Config {
+            history_enabled: true,
+            ..Config::default()
+        });
+        state.summary_day = 0;
+        assert!(
+            state
+                .take_daily_summary(history::local_day_start(1), &path)
+                .is_none()
+        );
+        assert_eq!(state.summary_day, 1);
+    }
+
+    #[test]
+    fn handle_ipc_reads_descriptor_through_tray_device() {
+        let dev = make_mock_device();
+        dev.set_descriptor(100, &[0xAB; 4]).unwrap();
+        let mut state = TrayState::init_with_config(Config::default(), &dev).unwrap();
+
+        let resp = state.handle_ipc(
+            IpcRequest::GetDescriptor {
+                offset: 100,
+                size: 4,
+            },
+            Some(&dev),
+        );
+        assert_eq!(
+            resp,
+            IpcResponse::Descriptor {
+                data: vec![0xAB; 4]
+            }
+        );
+    }
+
+    #[test]
+    fn handle_ipc_writes_descriptor_through_tray_device() {
+        let dev = make_mock_device();
+        let mut state = TrayState::init_with_config(Config::default(), &dev).unwrap();
+
+        let resp = state.handle_ipc(
+            IpcRequest::SetDescriptor {
+                offset: 200,
+                data: vec![1, 2, 3],
+            },
+            Some(&dev),
+        );
+        assert_eq!(resp, IpcResponse::Ok);
+        assert_eq!(dev.get_descriptor(200, 3).unwrap(), vec![1, 2, 3]);
+
+        let resp = state.handle_ipc(IpcRequest::GetDeviceInfo, Some(&dev));
+        assert!(
+            matches!(resp, IpcResponse::DeviceInfo { ref info, .. } if info == dev.info()),
+            "{resp:?}"
+        );
+    }
+
+    #[test]
+    fn select_press_reapplies_mute_indicator() {
+        let dev = make_mock_device();
+        let mut state = TrayState::init_with_config(Config::default(), &dev).unwrap();
+        state.set_initial_muted(true, &dev);
+        dev.notifies.borrow_mut().clear();
+
+        state.handle_device_events(&[DeviceEvent::SelectedInputChanged], &dev);
+        assert_eq!(
+            dev.notifies.borrow().as_slice(),
+            &[NOTIFY_DIRECT_LED_COLOUR; 2],
+            "both number LEDs re-painted"
+        );
+    }
+
+    #[test]
+    fn device_events_while_live_write_nothing() {
+        let dev = make_mock_device();
+        let mut state = TrayState::init_with_config(Config::default(), &dev).unwrap();
+        dev.notifies.borrow_mut().clear();
+
+        state.handle_device_events(
+            &[
+                DeviceEvent::SelectedInputChanged,
+                DeviceEvent::PhantomPowerChanged,
+            ],
+            &dev,
+        );
+        assert!(dev.notifies.borrow().is_empty());
+    }
+
+    #[test]
+    fn mute_button_matches_configured_press() {
+        let dev = make_mock_device();
+        let config = Config {
+            mute_button: "direct_monitoring".into(),
+            ..Config::default()
+        };
+        let state = TrayState::init_with_config(config, &dev).unwrap();
+        assert!(state.mute_button_pressed(&[
+            DeviceEvent::InputGainChanged,
+            DeviceEvent::ButtonPressed(DeviceButton::DirectMonitoring),
+        ]));
+        assert!(!state.mute_button_pressed(&[DeviceEvent::ButtonPressed(DeviceButton::Air)]));
+
+        let state = TrayState::init_with_config(Config::default(), &dev).unwrap();
+        assert!(!state.mute_button_pressed(&[DeviceEvent::SelectedInputChanged]));
+
+        // Without the schema member to sync, the button toggles as before
+        let config = Config {
+            mute_button: "air".into(),
+            mute_button_sync: true,
+            ..Config::default()
+        };
+        let state = TrayState::init_with_config(config, &dev).unwrap();
+        assert!(state.mute_switch().is_none());
+        assert!(state.mute_button_pressed(&[DeviceEvent::ButtonPressed(DeviceButton::Air)]));
+    }
+
+    #[test]
+    fn mute_animation_follows_config_and_reduce_motion() {
+        let dev = make_mock_device();
+        let config = Config {
+            mute_animation: "blink".into(),
+            reduce_motion: "off".into(),
+            ..Config::default()
+        };
+        let mut state = TrayState::init_with_config(config, &dev).unwrap();
+        assert!(!state.is_animating());
+        state.indicator.force_state(true);
+        assert!(state.is_animating());
+        dev.notifies.borrow_mut().clear();
+        // Still in the "on" half of the first blink — nothing to redraw
+        state.draw_animation_frame(&dev);
+        assert!(dev.notifies.borrow().is_empty());
+
+        let mut reduced = state.config.clone();
+        reduced.reduce_motion = "on".into();
+        state.apply_config(reduced, Some(&dev));
+        assert!(!state.is_animating());
+    }
+
+    #[test]
+    fn clock_change_event_rereads_sample_rate() {
+        let dev = make_mock_device();
+        let mut state = TrayState::init_with_config(Config::default(), &dev).unwrap();
+        let mut resp = vec![0u8; 8];
+        resp.extend_from_slice(&0u32.to_le_bytes());
+        dev.add_transact_response(CMD_GET_SYNC, resp);
+
+        state.handle_device_events(&[DeviceEvent::ClockChanged], &dev);
+        assert_eq!(state.clock.and_then(|c| c.locked), Some(false));
+        // No more GET_SYNC responses: lock becomes unknown once, then stays put
+        assert!(state.refresh_clock(&dev));
+        assert!(!state.refresh_clock(&dev));
+    }
+
+    #[test]
+    fn handle_ipc_without_device_returns_error() {
+        let mut state = TrayState::init_without_device(Config::default());
+        let resp = state.handle_ipc(
+            IpcRequest::GetDescriptor { offset: 0, size: 4 },
+            Option::<&MockDevice>::None,
+        );
+        assert!(matches!(resp, IpcResponse::Error { .. }));
+    }
+
+    #[test]
+    fn hotkey_and_hooks_inactive_until_first_poll() {
+        let dev = make_mock_device();
+        let state = TrayState::init_with_config(Config::default(), &dev).unwrap();
+        assert!(!state.first_poll_done);
+        let state = TrayState::init_without_device(Config::default());
+        assert!(!state.first_poll_done);
+    }
+
+    #[test]
+    fn announced_mute_is_attributed_to_focusmute() {
+        let mut state = TrayState::init_without_device(Config::default());
+        let resp = state.handle_ipc(
+            IpcRequest::AnnounceMute { muted: true },
+            Option::<&MockDevice>::None,
+        );
+        assert_eq!(resp, IpcResponse::Ok);
+        state.process_mute_poll(true, Option::<&MockDevice>::None);
+        state.process_mute_poll(true, Option::<&MockDevice>::None);
+
+        let resp = state.handle_ipc(IpcRequest::GetMuteState, Option::<&MockDevice>::None);
+        assert_eq!(
+            resp,
+            IpcResponse::MuteState {
+                muted: true,
+                origin: MuteOrigin::FocusMute
+            }
+        );
+    }
+
+    #[test]
+    fn apply_config_updates_external_mute_color() {
+        let dev = make_mock_device();
+        let mut state = TrayState::init_with_config(Config::default(), &dev).unwrap();
+        let new_config = Config {
+            external_mute_color: "#0000FF".into(),
+            ..state.config.clone()
+        };
+        state.apply_config(new_config, Some(&dev));
+
+        // External mute now uses the external color
+        state.process_mute_poll(true, Some(&dev));
+        state.process_mute_poll(true, Some(&dev));
+        assert_eq!(
+            dev.get_descriptor(OFF_DIRECT_LED_COLOUR, 4).unwrap(),
+            0x0000_FF00u32.to_le_bytes().to_vec()
+        );
+    }
 }
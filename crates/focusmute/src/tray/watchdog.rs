@@ -0,0 +1,226 @@
+//! Poll thread watchdog — notices when the background mute poll thread
+//! panics or stops sending heartbeats, so the event loop can restart it.
+//!
+//! Every iteration of a poll thread sends a `Msg::MutePoll`, at least every
+//! couple of seconds, and each one counts as a heartbeat. [`PollWatchdog`]
+//! reports a [`PollFault`] when the thread has exited or no heartbeat
+//! arrived for [`STALL_TIMEOUT`]. A stalled thread can't be killed, so its [`PollLease`]
+//! is revoked instead: it stops as soon as it wakes up, and its replacement
+//! takes over.
+
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::RUNNING;
+
+/// Longest a poll thread may go without a heartbeat before it counts as stalled.
+pub const STALL_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Consecutive restarts (with no heartbeat in between) before giving up.
+pub const MAX_RESTARTS: u32 = 3;
+
+/// Minimum time between two restarts, so a thread that dies on start doesn't
+/// burn through every restart at once.
+const RESTART_DELAY: Duration = Duration::from_secs(2);
+
+/// A gap this long between two checks means the whole process was suspended
+/// (system sleep), not that the poll thread stalled.
+const SUSPEND_GAP: Duration = Duration::from_secs(5);
+
+/// Tells one generation of the poll thread whether to keep going.
+#[derive(Clone, Default)]
+pub struct PollLease(Arc<AtomicBool>);
+
+impl PollLease {
+    /// `false` once the tray is shutting down or the thread was replaced.
+    pub fn is_active(&self) -> bool {
+        RUNNING.load(Ordering::SeqCst) && !self.0.load(Ordering::SeqCst)
+    }
+
+    /// Ask the thread holding this lease to stop.
+    pub fn revoke(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Why the poll thread needs a restart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PollFault {
+    /// The thread returned or panicked (with the panic message, if any).
+    Exited(Option<String>),
+    /// No heartbeat for this long.
+    Stalled(Duration),
+}
+
+impl PollFault {
+    /// The fault for a joined thread's result.
+    pub fn exited(result: std::thread::Result<()>) -> Self {
+        PollFault::Exited(result.err().map(|payload| panic_message(&*payload)))
+    }
+}
+
+impl fmt::Display for PollFault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PollFault::Exited(Some(message)) => write!(f, "panicked: {message}"),
+            PollFault::Exited(None) => f.write_str("stopped unexpectedly"),
+            PollFault::Stalled(since) => {
+                write!(f, "stalled (no update for {}s)", since.as_secs())
+            }
+        }
+    }
+}
+
+/// Best-effort text of a panic payload.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        (*s).to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Heartbeat and restart bookkeeping for the poll thread.
+pub struct PollWatchdog {
+    last_beat: Instant,
+    last_check: Instant,
+    last_restart: Option<Instant>,
+    /// Restarts since the last heartbeat.
+    restarts: u32,
+}
+
+impl PollWatchdog {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            last_beat: now,
+            last_check: now,
+            last_restart: None,
+            restarts: 0,
+        }
+    }
+
+    /// The poll thread reported in.
+    pub fn beat(&mut self, now: Instant) {
+        self.last_beat = now;
+        self.restarts = 0;
+    }
+
+    /// Check on the thread; `finished` is whether it has returned.
+    pub fn check(&mut self, finished: bool, now: Instant) -> Option<PollFault> {
+        if now.duration_since(self.last_check) >= SUSPEND_GAP {
+            // Woken from sleep — give the thread a fresh window to report
+            self.last_beat = now;
+        }
+        self.last_check = now;
+        if finished {
+            return Some(PollFault::Exited(None));
+        }
+        let since = now.duration_since(self.last_beat);
+        (since >= STALL_TIMEOUT).then_some(PollFault::Stalled(since))
+    }
+
+    /// Whether a restart may happen now (see [`RESTART_DELAY`]).
+    pub fn restart_due(&self, now: Instant) -> bool {
+        self.last_restart
+            .is_none_or(|t| now.duration_since(t) >= RESTART_DELAY)
+    }
+
+    /// Record a restart. Returns `false` once [`MAX_RESTARTS`] is used up.
+    pub fn record_restart(&mut self, now: Instant) -> bool {
+        if self.restarts >= MAX_RESTARTS {
+            return false;
+        }
+        self.restarts += 1;
+        self.last_restart = Some(now);
+        self.last_beat = now;
+        true
+    }
+
+    /// Restarts since the last heartbeat.
+    pub fn restarts(&self) -> u32 {
+        self.restarts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_stall_after_timeout() {
+        let t0 = Instant::now();
+        let mut dog = PollWatchdog::new(t0);
+        let mut now = t0;
+        // Checked every second, as the event loop would
+        while now < t0 + STALL_TIMEOUT - Duration::from_secs(1) {
+            now += Duration::from_secs(1);
+            assert_eq!(dog.check(false, now), None);
+        }
+        now += Duration::from_secs(1);
+        assert_eq!(
+            dog.check(false, now),
+            Some(PollFault::Stalled(STALL_TIMEOUT))
+        );
+
+        dog.beat(now);
+        assert_eq!(dog.check(false, now + Duration::from_secs(1)), None);
+    }
+
+    #[test]
+    fn long_gap_between_checks_is_a_suspend() {
+        let t0 = Instant::now();
+        let mut dog = PollWatchdog::new(t0);
+        assert_eq!(dog.check(false, t0 + Duration::from_secs(60)), None);
+    }
+
+    #[test]
+    fn exited_thread_is_a_fault() {
+        let mut dog = PollWatchdog::new(Instant::now());
+        assert_eq!(
+            dog.check(true, Instant::now()),
+            Some(PollFault::Exited(None))
+        );
+
+        let panicked = std::thread::spawn(|| panic!("pulse went away")).join();
+        let fault = PollFault::exited(panicked);
+        assert_eq!(fault.to_string(), "panicked: pulse went away");
+        assert_eq!(
+            PollFault::exited(Ok(())).to_string(),
+            "stopped unexpectedly"
+        );
+    }
+
+    #[test]
+    fn gives_up_after_max_restarts() {
+        let t0 = Instant::now();
+        let mut dog = PollWatchdog::new(t0);
+        let mut now = t0;
+        for _ in 0..MAX_RESTARTS {
+            assert!(dog.restart_due(now));
+            assert!(dog.record_restart(now));
+            assert!(!dog.restart_due(now + Duration::from_millis(100)));
+            now += RESTART_DELAY;
+        }
+        assert!(!dog.record_restart(now));
+
+        // A heartbeat from the new thread resets the budget
+        dog.beat(now);
+        assert_eq!(dog.restarts(), 0);
+        assert!(dog.record_restart(now));
+    }
+
+    #[test]
+    fn revoked_lease_stops_only_its_thread() {
+        let old = PollLease::default();
+        let new = PollLease::default();
+        let held = old.clone();
+        old.revoke();
+        assert!(!held.is_active());
+        assert!(new.is_active());
+    }
+}
@@ -1,20 +1,39 @@
-//! Windows system tray — Win32 message loop, WASAPI monitoring.
+//! Windows system tray — Win32 message loop, WASAPI monitoring, session
+//! switch and device arrival notifications.
 
+use std::cell::RefCell;
 use std::sync::Arc;
 use std::sync::atomic::Ordering;
 use std::sync::mpsc;
 use std::thread::JoinHandle;
 use std::time::Duration;
 
-use focusmute_lib::audio::{self, MuteMonitor, WasapiMonitor};
+use focusmute_lib::audio::{self, MonitorGroup, MuteMonitor, WasapiMonitor};
+use focusmute_lib::config::Config;
+use focusmute_lib::protocol::FOCUSRITE_GUID;
+use focusmute_lib::reconnect::HotplugEvent;
 
+use muda::Menu;
+use windows::Win32::Foundation::{HANDLE, HWND, LPARAM, LRESULT, WPARAM};
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::System::RemoteDesktop::{
+    NOTIFY_FOR_THIS_SESSION, WTSRegisterSessionNotification, WTSUnRegisterSessionNotification,
+};
 use windows::Win32::UI::WindowsAndMessaging::{
-    DispatchMessageW, MSG, MsgWaitForMultipleObjects, PM_REMOVE, PeekMessageW, QS_ALLINPUT,
-    TranslateMessage, WM_QUIT,
+    CreateWindowExW, DBT_DEVICEARRIVAL, DBT_DEVICEREMOVECOMPLETE, DBT_DEVTYP_DEVICEINTERFACE,
+    DEV_BROADCAST_DEVICEINTERFACE_W, DEVICE_NOTIFY_WINDOW_HANDLE, DefWindowProcW, DestroyWindow,
+    DispatchMessageW, HDEVNOTIFY, HWND_MESSAGE, MSG, MsgWaitForMultipleObjects, PM_REMOVE,
+    PeekMessageW, QS_ALLINPUT, RegisterClassW, RegisterDeviceNotificationW, TranslateMessage,
+    UnregisterDeviceNotification, WINDOW_EX_STYLE, WINDOW_STYLE, WM_DEVICECHANGE, WM_QUIT,
+    WM_WTSSESSION_CHANGE, WNDCLASSW, WTS_CONSOLE_CONNECT, WTS_CONSOLE_DISCONNECT,
+    WTS_REMOTE_CONNECT, WTS_REMOTE_DISCONNECT,
 };
+use windows::core::w;
 
-use super::shared::{self, PlatformAdapter};
-use super::state::Msg;
+use super::backend::TrayBackend;
+use super::shared;
+use super::state::{self, Msg};
+use super::watchdog::PollLease;
 use crate::RUNNING;
 
 /// Pump all pending Win32 messages. Required for tray-icon and global-hotkey
@@ -33,38 +52,192 @@ fn pump_messages() {
     }
 }
 
-pub struct WindowsAdapter;
+thread_local! {
+    /// Where the notification window procedure reports to (its thread only).
+    static NOTIFY_TX: RefCell<Option<mpsc::Sender<Msg>>> = const { RefCell::new(None) };
+}
+
+/// Map a `WM_WTSSESSION_CHANGE` event to "this session owns the console".
+fn session_change(event: u32) -> Option<bool> {
+    match event {
+        WTS_CONSOLE_CONNECT | WTS_REMOTE_CONNECT => Some(true),
+        WTS_CONSOLE_DISCONNECT | WTS_REMOTE_DISCONNECT => Some(false),
+        _ => None,
+    }
+}
+
+/// Map a `WM_DEVICECHANGE` event for the Focusrite interface.
+fn device_change(event: u32) -> Option<HotplugEvent> {
+    match event {
+        DBT_DEVICEARRIVAL => Some(HotplugEvent::Arrived),
+        DBT_DEVICEREMOVECOMPLETE => Some(HotplugEvent::Removed),
+        _ => None,
+    }
+}
+
+unsafe extern "system" fn notify_wndproc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    let event = match msg {
+        WM_WTSSESSION_CHANGE => session_change(wparam.0 as u32).map(Msg::SessionActive),
+        WM_DEVICECHANGE => device_change(wparam.0 as u32).map(Msg::Hotplug),
+        _ => None,
+    };
+    if let Some(event) = event {
+        NOTIFY_TX.with(|tx| {
+            if let Some(tx) = tx.borrow().as_ref() {
+                let _ = tx.send(event);
+            }
+        });
+    }
+    unsafe { DefWindowProcW(hwnd, msg, wparam, lparam) }
+}
+
+/// Message-only window that receives session and device notifications.
+struct NotifyWindow {
+    hwnd: HWND,
+    /// `None` if device notifications couldn't be registered.
+    devices: Option<HDEVNOTIFY>,
+}
+
+impl NotifyWindow {
+    fn create() -> windows::core::Result<Self> {
+        unsafe {
+            let instance = GetModuleHandleW(None)?;
+            let class = WNDCLASSW {
+                lpfnWndProc: Some(notify_wndproc),
+                hInstance: instance.into(),
+                lpszClassName: w!("FocusMuteNotifyWindow"),
+                ..Default::default()
+            };
+            RegisterClassW(&class);
+            let hwnd = CreateWindowExW(
+                WINDOW_EX_STYLE::default(),
+                w!("FocusMuteNotifyWindow"),
+                w!("FocusMute notifications"),
+                WINDOW_STYLE::default(),
+                0,
+                0,
+                0,
+                0,
+                Some(HWND_MESSAGE),
+                None,
+                Some(instance.into()),
+                None,
+            )?;
+            if let Err(e) = WTSRegisterSessionNotification(hwnd, NOTIFY_FOR_THIS_SESSION) {
+                log::warn!("[session] session notifications unavailable: {e}");
+            }
+            // Only the SwRoot driver's interface — arrival means it is ready
+            let filter = DEV_BROADCAST_DEVICEINTERFACE_W {
+                dbcc_size: std::mem::size_of::<DEV_BROADCAST_DEVICEINTERFACE_W>() as u32,
+                dbcc_devicetype: DBT_DEVTYP_DEVICEINTERFACE.0,
+                dbcc_classguid: FOCUSRITE_GUID,
+                ..Default::default()
+            };
+            let devices = RegisterDeviceNotificationW(
+                HANDLE(hwnd.0),
+                std::ptr::from_ref(&filter).cast(),
+                DEVICE_NOTIFY_WINDOW_HANDLE,
+            )
+            .inspect_err(|e| log::warn!("[hotplug] device notifications unavailable: {e}"))
+            .ok();
+            Ok(Self { hwnd, devices })
+        }
+    }
+}
+
+impl Drop for NotifyWindow {
+    fn drop(&mut self) {
+        unsafe {
+            if let Some(devices) = self.devices {
+                let _ = UnregisterDeviceNotification(devices);
+            }
+            let _ = WTSUnRegisterSessionNotification(self.hwnd);
+            let _ = DestroyWindow(self.hwnd);
+        }
+    }
+}
+
+/// How long the poll thread sleeps while live. The WASAPI callback wakes it
+/// on every mute change, so this only paces the watchdog heartbeat.
+const IDLE_WAIT: Duration = Duration::from_secs(2);
+
+/// How long it sleeps while muted, when it also samples the input meter for
+/// the talk-while-muted warning.
+const METER_WAIT: Duration = Duration::from_millis(250);
 
-impl PlatformAdapter for WindowsAdapter {
-    type Monitor = WasapiMonitor;
+pub struct WindowsBackend;
+
+impl TrayBackend for WindowsBackend {
+    type Monitor = MonitorGroup<WasapiMonitor>;
+    type Indicator = tray_icon::TrayIcon;
 
     fn platform_init() -> focusmute_lib::error::Result<()> {
         audio::com_init()?;
         Ok(())
     }
 
-    fn create_monitor() -> Option<WasapiMonitor> {
-        WasapiMonitor::new().ok()
+    fn create_monitor(config: &Config) -> Option<Self::Monitor> {
+        WasapiMonitor::open_group(
+            &config.excluded_capture_devices,
+            config.mute_all_capture_devices,
+        )
+        .ok()
+        .map(|group| group.with_applications(&config.mute_applications))
     }
 
-    fn spawn_poll_thread(monitor: Arc<WasapiMonitor>, tx: mpsc::Sender<Msg>) -> JoinHandle<()> {
+    fn spawn_poll_thread(
+        monitor: Arc<Self::Monitor>,
+        tx: mpsc::Sender<Msg>,
+        lease: PollLease,
+    ) -> JoinHandle<()> {
         std::thread::spawn(move || {
             if let Err(e) = audio::com_init() {
-                log::error!("Audio init error: {e}");
+                let _ = tx.send(Msg::Error(format!("Audio init error: {e}")));
                 return;
             }
 
-            while RUNNING.load(Ordering::SeqCst) {
-                monitor.wait_for_change(Duration::from_millis(250));
+            let mut in_use = shared::InUsePoll::default();
+            while lease.is_active() {
+                let wait = if monitor.is_muted() {
+                    METER_WAIT
+                } else {
+                    IDLE_WAIT
+                };
+                monitor.wait_for_change(wait);
                 monitor.refresh();
                 let muted = monitor.is_muted();
                 if tx.send(Msg::MutePoll(muted)).is_err() {
                     break;
                 }
+                if muted
+                    && let Some(levels) = monitor.peak_levels()
+                    && tx.send(Msg::MeterUpdate(levels)).is_err()
+                {
+                    break;
+                }
+                if in_use
+                    .poll(&*monitor)
+                    .into_iter()
+                    .any(|msg| tx.send(msg).is_err())
+                {
+                    break;
+                }
             }
         })
     }
 
+    fn build_indicator(
+        initial_muted: bool,
+        menu: Menu,
+    ) -> focusmute_lib::error::Result<tray_icon::TrayIcon> {
+        state::build_tray_icon(initial_muted, menu)
+    }
+
     fn pump_events() {
         pump_messages();
     }
@@ -74,8 +247,44 @@ impl PlatformAdapter for WindowsAdapter {
             MsgWaitForMultipleObjects(None, false, 50, QS_ALLINPUT);
         }
     }
+
+    fn spawn_os_watcher(tx: mpsc::Sender<Msg>) -> bool {
+        // The window lives on its own thread; report back whether device
+        // notifications were registered before the main loop relies on them
+        let (ready, ready_rx) = mpsc::channel();
+        let spawned = std::thread::Builder::new()
+            .name("os-notify".into())
+            .spawn(move || {
+                NOTIFY_TX.with(|s| *s.borrow_mut() = Some(tx));
+                let window = match NotifyWindow::create() {
+                    Ok(window) => window,
+                    Err(e) => {
+                        log::warn!("[notify] could not create notification window: {e}");
+                        let _ = ready.send(false);
+                        return;
+                    }
+                };
+                let _ = ready.send(window.devices.is_some());
+                while RUNNING.load(Ordering::SeqCst) {
+                    unsafe {
+                        MsgWaitForMultipleObjects(None, false, 250, QS_ALLINPUT);
+                        let mut msg: MSG = std::mem::zeroed();
+                        while PeekMessageW(&mut msg, None, 0, 0, PM_REMOVE).as_bool() {
+                            DispatchMessageW(&msg);
+                        }
+                    }
+                }
+            });
+        match spawned {
+            Ok(_) => ready_rx.recv().unwrap_or(false),
+            Err(e) => {
+                log::warn!("[notify] could not start notification thread: {e}");
+                false
+            }
+        }
+    }
 }
 
-pub fn run() -> focusmute_lib::error::Result<()> {
-    shared::run_core::<WindowsAdapter>()
+pub fn run(safe_mode: bool) -> focusmute_lib::error::Result<()> {
+    shared::run_core::<WindowsBackend>(safe_mode)
 }
@@ -62,6 +62,19 @@ fn cli_verbose_long_flag_accepted() {
     cli().args(["--verbose", "config"]).assert().success();
 }
 
+#[test]
+fn cli_log_file_is_created() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = dir.path().join("logs").join("cli.log");
+    cli()
+        .args(["-v", "--log-file"])
+        .arg(&log)
+        .arg("config")
+        .assert()
+        .success();
+    assert!(log.is_file(), "log file should be created");
+}
+
 // ── Subcommand integration tests ──
 // Device-requiring commands tested via --help to avoid platform-dependent errors.
 
@@ -93,6 +106,15 @@ fn cli_unmute_help_succeeds() {
         .stdout(predicate::str::contains("Unmute"));
 }
 
+#[test]
+fn cli_tray_needs_a_running_tray() {
+    cli()
+        .args(["--instance-name", "no-tray-here", "tray", "reload-config"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("tray app is not running"));
+}
+
 #[test]
 fn cli_descriptor_help_succeeds() {
     cli()
@@ -111,6 +133,51 @@ fn cli_probe_help_succeeds() {
         .stdout(predicate::str::contains("Probe"));
 }
 
+#[test]
+fn cli_probe_page_size_requires_bench_schema() {
+    cli()
+        .args(["probe", "--page-size", "512"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--bench-schema"));
+}
+
+#[test]
+fn cli_phantom_help_succeeds() {
+    cli()
+        .args(["phantom", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("phantom power"));
+}
+
+#[test]
+fn cli_phantom_rejects_unknown_state() {
+    cli()
+        .args(["phantom", "1", "maybe"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value"));
+}
+
+#[test]
+fn cli_air_rejects_unknown_mode() {
+    cli()
+        .args(["air", "1", "loud"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown Air mode"));
+}
+
+#[test]
+fn cli_rejects_invalid_instance_name() {
+    cli()
+        .args(["--instance-name", "two words", "status"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid instance name"));
+}
+
 #[test]
 fn cli_monitor_help_succeeds() {
     cli()
@@ -129,6 +196,33 @@ fn cli_map_help_succeeds() {
         .stdout(predicate::str::contains("map"));
 }
 
+#[test]
+fn cli_history_export_help_succeeds() {
+    cli()
+        .args(["history", "export", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--since"));
+}
+
+#[test]
+fn cli_history_export_rejects_bad_since() {
+    cli()
+        .args(["history", "export", "--since", "7y"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown unit"));
+}
+
+#[test]
+fn cli_history_export_rejects_bad_format() {
+    cli()
+        .args(["history", "export", "--format", "xml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown format"));
+}
+
 // ── --config flag ──
 
 #[test]
@@ -149,3 +243,130 @@ fn cli_monitor_help_with_config() {
         .success()
         .stdout(predicate::str::contains("monitor"));
 }
+
+#[test]
+fn cli_inst_rejects_unknown_level() {
+    cli()
+        .args(["inst", "1", "mic"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value"));
+}
+
+#[test]
+fn cli_layout_import_rejects_non_layout_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("not-a-layout.json");
+    std::fs::write(&path, r#"{"format":"other","version":1}"#).unwrap();
+    cli()
+        .args(["layout", "import"])
+        .arg(&path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid layout file"));
+}
+
+#[test]
+fn cli_gain_set_requires_input() {
+    cli()
+        .args(["gain", "--set", "40"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--input"));
+}
+
+#[test]
+fn cli_autogain_requires_input() {
+    cli()
+        .args(["autogain"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("<INPUT>"));
+}
+
+#[test]
+fn cli_stress_rejects_non_numeric_hours() {
+    cli()
+        .args(["stress", "--hours", "long"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--hours"));
+}
+
+#[test]
+fn cli_reset_help() {
+    cli()
+        .args(["reset", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("USB port reset"));
+}
+
+#[test]
+fn cli_resolve_help() {
+    cli()
+        .args(["resolve", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("without changing anything"));
+}
+
+#[test]
+fn cli_simulate_is_hidden_from_help() {
+    cli()
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("simulate").not());
+    cli()
+        .args(["simulate", "unplugged"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("possible values"));
+}
+
+#[test]
+fn cli_subcommand_help_shows_examples() {
+    cli()
+        .args(["gain", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Examples:"))
+        .stdout(predicate::str::contains("gain --input 1 --set 40"));
+}
+
+#[test]
+fn cli_man_prints_roff_page() {
+    cli()
+        .arg("man")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(".TH focusmute-cli"));
+}
+
+#[test]
+fn cli_clip_safe_rejects_unknown_state() {
+    cli()
+        .args(["clip-safe", "1", "maybe"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value"));
+}
+
+#[test]
+fn cli_alt_rejects_unknown_state() {
+    cli()
+        .args(["alt", "b"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value"));
+}
+
+#[test]
+fn cli_volume_set_requires_output() {
+    cli()
+        .args(["volume", "--set", "-20"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--output"));
+}